@@ -19,11 +19,18 @@
 use anyhow::Result;
 use constellation_core::*;
 use std::collections::HashMap;
+use std::sync::mpsc::{self, Receiver, Sender};
 use uuid::Uuid;
 
 pub struct AudioProcessor {
     sample_rate: u32,
     channels: u16,
+    /// Frames (samples per channel) per processing block, 0 = no blocking
+    block_size: usize,
+    /// Samples waiting for a full block, in the layout of `pending_format`
+    pending: Vec<f32>,
+    /// Sample rate and channel count of the pending samples
+    pending_format: Option<(u32, u16)>,
 }
 
 impl AudioProcessor {
@@ -31,19 +38,111 @@ impl AudioProcessor {
         Self {
             sample_rate,
             channels,
+            block_size: 0,
+            pending: Vec::new(),
+            pending_format: None,
         }
     }
 
+    /// Process audio in fixed blocks of `frames` samples per channel so latency
+    /// doesn't depend on how much audio each call delivers. 0 disables blocking
+    pub fn set_block_size(&mut self, frames: usize) {
+        self.block_size = frames;
+    }
+
+    pub fn block_size(&self) -> usize {
+        self.block_size
+    }
+
+    /// Samples buffered until the next block is complete
+    pub fn pending_samples(&self) -> usize {
+        self.pending.len()
+    }
+
+    /// Process the input and return every completed block joined into one frame.
+    /// Incomplete blocks stay buffered for the next call (see `flush`).
+    /// Fails without consuming anything if the input format differs from samples
+    /// still pending, since one frame can't carry two layouts; `flush` first or
+    /// use `process_blocks` when the input format can change mid-stream
     pub fn process_audio(&mut self, input: &AudioFrame) -> Result<AudioFrame> {
+        if let Some((sample_rate, channels)) = self.pending_format {
+            if !self.pending.is_empty()
+                && (sample_rate, channels) != (input.sample_rate, input.channels)
+            {
+                anyhow::bail!(
+                    "Audio format changed from {} Hz/{} ch to {} Hz/{} ch with {} samples pending",
+                    sample_rate,
+                    channels,
+                    input.sample_rate,
+                    input.channels,
+                    self.pending.len()
+                );
+            }
+        }
+
+        let blocks = self.process_blocks(input)?;
         Ok(AudioFrame {
             sample_rate: input.sample_rate,
             channels: input.channels,
-            samples: input.samples.clone(),
+            samples: blocks.into_iter().flat_map(|block| block.samples).collect(),
+        })
+    }
+
+    /// Accumulate the input and return each completed block separately.
+    /// Without a block size the input is returned as a single block
+    pub fn process_blocks(&mut self, input: &AudioFrame) -> Result<Vec<AudioFrame>> {
+        if self.block_size == 0 {
+            return Ok(vec![input.clone()]);
+        }
+        if input.channels == 0 {
+            anyhow::bail!("Invalid audio frame: 0 channels");
+        }
+
+        let mut blocks = Vec::new();
+
+        // A format change ends the current stream; don't splice different layouts
+        let format = (input.sample_rate, input.channels);
+        if self.pending_format.is_some_and(|pending| pending != format) {
+            blocks.extend(self.flush());
+        }
+        self.pending_format = Some(format);
+        self.pending.extend_from_slice(&input.samples);
+
+        let block_len = self.block_size * input.channels as usize;
+        let complete = self.pending.len() / block_len * block_len;
+        let ready: Vec<f32> = self.pending.drain(..complete).collect();
+        blocks.extend(ready.chunks_exact(block_len).map(|block| AudioFrame {
+            sample_rate: input.sample_rate,
+            channels: input.channels,
+            samples: block.to_vec(),
+        }));
+
+        Ok(blocks)
+    }
+
+    /// Emit the buffered partial block, e.g. when the stream stops
+    pub fn flush(&mut self) -> Option<AudioFrame> {
+        let (sample_rate, channels) = self.pending_format.take()?;
+        if self.pending.is_empty() {
+            return None;
+        }
+
+        Some(AudioFrame {
+            sample_rate,
+            channels,
+            samples: std::mem::take(&mut self.pending),
         })
     }
 
     pub fn mix_audio(&self, inputs: &[AudioFrame]) -> Result<AudioFrame> {
-        if inputs.is_empty() {
+        // Bring every input to the processor's sample rate / channel layout first
+        let converted: Vec<AudioFrame> = inputs
+            .iter()
+            .filter(|input| !input.samples.is_empty())
+            .map(|input| self.resample(input))
+            .collect::<Result<_>>()?;
+
+        if converted.is_empty() {
             return Ok(AudioFrame {
                 sample_rate: self.sample_rate,
                 channels: self.channels,
@@ -51,28 +150,219 @@ impl AudioProcessor {
             });
         }
 
-        let first_frame = &inputs[0];
-        let mut mixed_samples = first_frame.samples.clone();
-
-        for input in inputs.iter().skip(1) {
-            for (i, &sample) in input.samples.iter().enumerate() {
-                if i < mixed_samples.len() {
-                    mixed_samples[i] += sample;
-                }
+        // Shorter inputs are zero-padded to the longest one
+        let output_len = converted
+            .iter()
+            .map(|frame| frame.samples.len())
+            .max()
+            .unwrap_or(0);
+        let mut mixed_samples = vec![0.0f32; output_len];
+
+        for frame in &converted {
+            for (mixed, &sample) in mixed_samples.iter_mut().zip(frame.samples.iter()) {
+                *mixed += sample;
             }
         }
 
-        let num_inputs = inputs.len() as f32;
+        let num_inputs = converted.len() as f32;
         for sample in &mut mixed_samples {
             *sample /= num_inputs;
         }
 
         Ok(AudioFrame {
-            sample_rate: first_frame.sample_rate,
-            channels: first_frame.channels,
+            sample_rate: self.sample_rate,
+            channels: self.channels,
             samples: mixed_samples,
         })
     }
+
+    /// Convert a frame to the processor's sample rate and channel count
+    /// using linear interpolation. Mono is duplicated to every output channel
+    /// and multi-channel input is averaged when downmixing to mono.
+    pub fn resample(&self, input: &AudioFrame) -> Result<AudioFrame> {
+        if input.channels == 0 || input.sample_rate == 0 {
+            anyhow::bail!(
+                "Invalid audio frame: {} Hz, {} channels",
+                input.sample_rate,
+                input.channels
+            );
+        }
+
+        let remixed = Self::remix_channels(&input.samples, input.channels, self.channels);
+        let samples = if input.sample_rate == self.sample_rate {
+            remixed
+        } else {
+            Self::resample_linear(&remixed, self.channels, input.sample_rate, self.sample_rate)
+        };
+
+        Ok(AudioFrame {
+            sample_rate: self.sample_rate,
+            channels: self.channels,
+            samples,
+        })
+    }
+
+    fn remix_channels(samples: &[f32], from: u16, to: u16) -> Vec<f32> {
+        if from == to {
+            return samples.to_vec();
+        }
+
+        let (from, to) = (from as usize, to as usize);
+        let frame_count = samples.len() / from;
+        let mut output = Vec::with_capacity(frame_count * to);
+
+        for frame in samples.chunks_exact(from) {
+            if from == 1 {
+                // Mono upmix
+                output.extend(std::iter::repeat_n(frame[0], to));
+            } else if to == 1 {
+                // Downmix to mono
+                output.push(frame.iter().sum::<f32>() / from as f32);
+            } else {
+                // Keep matching channels, silence any extra output channels
+                output.extend((0..to).map(|channel| frame.get(channel).copied().unwrap_or(0.0)));
+            }
+        }
+
+        output
+    }
+
+    fn resample_linear(samples: &[f32], channels: u16, from_rate: u32, to_rate: u32) -> Vec<f32> {
+        let channels = channels as usize;
+        let input_frames = samples.len() / channels;
+        if input_frames == 0 {
+            return Vec::new();
+        }
+
+        let output_frames =
+            ((input_frames as u64 * to_rate as u64).div_ceil(from_rate as u64)) as usize;
+        let step = from_rate as f64 / to_rate as f64;
+        let mut output = Vec::with_capacity(output_frames * channels);
+
+        for frame in 0..output_frames {
+            let position = frame as f64 * step;
+            let index = (position as usize).min(input_frames - 1);
+            let next = (index + 1).min(input_frames - 1);
+            let fraction = (position - index as f64) as f32;
+
+            for channel in 0..channels {
+                let a = samples[index * channels + channel];
+                let b = samples[next * channels + channel];
+                output.push(a + (b - a) * fraction);
+            }
+        }
+
+        output
+    }
+}
+
+/// Linear resampler for a continuous stream delivered in blocks.
+/// Unlike `AudioProcessor::resample`, the interpolation phase and the last
+/// input frame carry over between calls, so block boundaries don't click and
+/// the output length doesn't drift from the input duration. The state starts
+/// over whenever the input or output format changes
+#[derive(Debug, Default)]
+pub struct StreamResampler {
+    /// (input rate, input channels, output rate, output channels) of the stream
+    format: Option<(u32, u16, u32, u16)>,
+    /// Position of the next output frame in input frames, relative to the
+    /// first frame of the next block; -1..0 interpolates from `last_frame`
+    position: f64,
+    /// Last input frame of the previous block, already remixed to the output channels
+    last_frame: Vec<f32>,
+}
+
+impl StreamResampler {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Forget the carried-over phase, e.g. when the stream restarts
+    pub fn reset(&mut self) {
+        self.format = None;
+        self.position = 0.0;
+        self.last_frame.clear();
+    }
+
+    /// Convert the next block of the stream to `sample_rate` / `channels`
+    pub fn resample(
+        &mut self,
+        input: &AudioFrame,
+        sample_rate: u32,
+        channels: u16,
+    ) -> Result<AudioFrame> {
+        if input.channels == 0 || input.sample_rate == 0 || channels == 0 || sample_rate == 0 {
+            anyhow::bail!(
+                "Invalid resampling: {} Hz/{} ch to {} Hz/{} ch",
+                input.sample_rate,
+                input.channels,
+                sample_rate,
+                channels
+            );
+        }
+
+        let format = (input.sample_rate, input.channels, sample_rate, channels);
+        if self.format != Some(format) {
+            self.reset();
+            self.format = Some(format);
+        }
+
+        let remixed = AudioProcessor::remix_channels(&input.samples, input.channels, channels);
+        if input.sample_rate == sample_rate {
+            return Ok(AudioFrame {
+                sample_rate,
+                channels,
+                samples: remixed,
+            });
+        }
+
+        let width = channels as usize;
+        let input_frames = remixed.len() / width;
+        if input_frames == 0 {
+            return Ok(AudioFrame {
+                sample_rate,
+                channels,
+                samples: Vec::new(),
+            });
+        }
+
+        let step = input.sample_rate as f64 / sample_rate as f64;
+        let frame_at = |index: isize| -> &[f32] {
+            if index < 0 {
+                &self.last_frame
+            } else {
+                let start = index as usize * width;
+                &remixed[start..start + width]
+            }
+        };
+
+        // Stop before the last frame: interpolating past it needs the next block
+        let mut samples = Vec::new();
+        let mut position = self.position;
+        while position < (input_frames - 1) as f64 {
+            let index = position.floor() as isize;
+            let fraction = (position - index as f64) as f32;
+            let (a, b) = (frame_at(index), frame_at(index + 1));
+            samples.extend(a.iter().zip(b).map(|(&a, &b)| a + (b - a) * fraction));
+            position += step;
+        }
+
+        self.position = position - input_frames as f64;
+        self.last_frame = remixed[(input_frames - 1) * width..].to_vec();
+
+        Ok(AudioFrame {
+            sample_rate,
+            channels,
+            samples,
+        })
+    }
+}
+
+/// A freshly computed level for a node, delivered to analyzer subscribers
+#[derive(Debug, Clone, PartialEq)]
+pub struct AudioLevelUpdate {
+    pub node_id: Uuid,
+    pub level: AudioLevel,
 }
 
 /// Real-time audio level analyzer for live monitoring
@@ -83,6 +373,8 @@ pub struct AudioLevelAnalyzer {
     update_interval_ms: u64,
     /// Last update instant per node
     last_update: HashMap<Uuid, std::time::Instant>,
+    /// Listeners notified whenever a new level is computed
+    subscribers: Vec<Sender<AudioLevelUpdate>>,
 }
 
 impl Default for AudioLevelAnalyzer {
@@ -97,9 +389,22 @@ impl AudioLevelAnalyzer {
             node_levels: HashMap::new(),
             update_interval_ms: 16, // ~60fps update rate
             last_update: HashMap::new(),
+            subscribers: Vec::new(),
         }
     }
 
+    /// Subscribe to level updates. Cached (throttled) results are not re-sent
+    pub fn subscribe(&mut self) -> Receiver<AudioLevelUpdate> {
+        let (sender, receiver) = mpsc::channel();
+        self.subscribers.push(sender);
+        receiver
+    }
+
+    /// Number of live subscribers
+    pub fn subscriber_count(&self) -> usize {
+        self.subscribers.len()
+    }
+
     /// Set update interval for level analysis
     pub fn set_update_interval(&mut self, interval_ms: u64) {
         self.update_interval_ms = interval_ms;
@@ -128,6 +433,14 @@ impl AudioLevelAnalyzer {
         self.node_levels.insert(node_id, level.clone());
         self.last_update.insert(node_id, now);
 
+        // Notify subscribers, dropping any whose receiver has gone away
+        let update = AudioLevelUpdate {
+            node_id,
+            level: level.clone(),
+        };
+        self.subscribers
+            .retain(|subscriber| subscriber.send(update.clone()).is_ok());
+
         Some(level)
     }
 
@@ -217,6 +530,122 @@ mod tests {
         assert_eq!(output.samples.len(), 4);
     }
 
+    #[test]
+    fn test_block_processing_emits_fixed_blocks_without_losing_samples() {
+        let mut processor = AudioProcessor::new(48000, 2);
+        processor.set_block_size(4);
+
+        // Irregular call sizes, including an odd sample count that splits a frame
+        let mut next = 0.0f32;
+        let mut fed = Vec::new();
+        let mut emitted = Vec::new();
+        for len in [3, 10, 1, 0, 17, 6] {
+            let samples: Vec<f32> = (0..len)
+                .map(|_| {
+                    next += 1.0;
+                    next
+                })
+                .collect();
+            fed.extend_from_slice(&samples);
+
+            let blocks = processor
+                .process_blocks(&AudioFrame {
+                    sample_rate: 48000,
+                    channels: 2,
+                    samples,
+                })
+                .unwrap();
+            for block in blocks {
+                assert_eq!(block.samples.len(), 8);
+                assert_eq!((block.sample_rate, block.channels), (48000, 2));
+                emitted.extend(block.samples);
+            }
+        }
+
+        // 37 samples = 4 full blocks + 5 pending
+        assert_eq!(emitted.len(), 32);
+        assert_eq!(processor.pending_samples(), 5);
+
+        let tail = processor.flush().unwrap();
+        assert_eq!(tail.samples.len(), 5);
+        emitted.extend(tail.samples);
+        assert_eq!(emitted, fed);
+
+        assert!(processor.flush().is_none());
+        assert_eq!(processor.pending_samples(), 0);
+    }
+
+    #[test]
+    fn test_block_processing_flushes_on_format_change() {
+        let mut processor = AudioProcessor::new(48000, 2);
+        processor.set_block_size(256);
+
+        let stereo = AudioFrame {
+            sample_rate: 48000,
+            channels: 2,
+            samples: vec![0.25; 100],
+        };
+        assert!(processor.process_blocks(&stereo).unwrap().is_empty());
+
+        let mono = AudioFrame {
+            sample_rate: 48000,
+            channels: 1,
+            samples: vec![0.5; 300],
+        };
+        let blocks = processor.process_blocks(&mono).unwrap();
+
+        // The stereo remainder comes out first, then one full mono block
+        assert_eq!(blocks.len(), 2);
+        assert_eq!((blocks[0].channels, blocks[0].samples.len()), (2, 100));
+        assert_eq!((blocks[1].channels, blocks[1].samples.len()), (1, 256));
+        assert_eq!(processor.pending_samples(), 44);
+    }
+
+    #[test]
+    fn test_process_audio_rejects_format_change_with_pending_samples() {
+        let mut processor = AudioProcessor::new(48000, 2);
+        processor.set_block_size(256);
+
+        let stereo = AudioFrame {
+            sample_rate: 48000,
+            channels: 2,
+            samples: vec![0.25; 100],
+        };
+        assert!(processor.process_audio(&stereo).unwrap().samples.is_empty());
+
+        let mono = AudioFrame {
+            sample_rate: 48000,
+            channels: 1,
+            samples: vec![0.5; 300],
+        };
+        assert!(processor.process_audio(&mono).is_err());
+        // Nothing was consumed; flushing the old stream lets the new one through
+        assert_eq!(processor.pending_samples(), 100);
+        assert_eq!(processor.flush().unwrap().channels, 2);
+        let output = processor.process_audio(&mono).unwrap();
+        assert_eq!((output.channels, output.samples.len()), (1, 256));
+    }
+
+    #[test]
+    fn test_process_audio_without_block_size_passes_through() {
+        let mut processor = AudioProcessor::new(48000, 2);
+        assert_eq!(processor.block_size(), 0);
+
+        let input = AudioFrame {
+            sample_rate: 44100,
+            channels: 1,
+            samples: vec![0.1, 0.2, 0.3],
+        };
+        let output = processor.process_audio(&input).unwrap();
+        assert_eq!(output.samples, input.samples);
+        assert!(processor.flush().is_none());
+
+        processor.set_block_size(2);
+        let output = processor.process_audio(&input).unwrap();
+        assert_eq!(output.samples, vec![0.1, 0.2]);
+        assert_eq!(processor.flush().unwrap().samples, vec![0.3]);
+    }
+
     #[test]
     fn test_audio_mixing() {
         let processor = AudioProcessor::new(48000, 2);
@@ -242,6 +671,126 @@ mod tests {
         assert_eq!(mixed.samples[1], 0.4); // (0.5 + 0.3) / 2
     }
 
+    #[test]
+    fn test_mix_audio_mismatched_sample_rates() {
+        let processor = AudioProcessor::new(48000, 2);
+
+        // 10ms of 44.1kHz mono and 10ms of 48kHz stereo
+        let mono_44k = AudioFrame {
+            sample_rate: 44100,
+            channels: 1,
+            samples: vec![0.5; 441],
+        };
+        let stereo_48k = AudioFrame {
+            sample_rate: 48000,
+            channels: 2,
+            samples: [0.1, -0.1].repeat(480),
+        };
+
+        let mixed = processor.mix_audio(&[mono_44k, stereo_48k]).unwrap();
+        assert_eq!(mixed.sample_rate, 48000);
+        assert_eq!(mixed.channels, 2);
+        assert_eq!(mixed.samples.len(), 480 * 2);
+
+        // Mono input is upmixed to both channels before averaging
+        assert!((mixed.samples[0] - 0.3).abs() < 1e-6);
+        assert!((mixed.samples[1] - 0.2).abs() < 1e-6);
+    }
+
+    #[test]
+    fn test_mix_audio_zero_pads_shorter_inputs() {
+        let processor = AudioProcessor::new(48000, 1);
+
+        let long = AudioFrame {
+            sample_rate: 48000,
+            channels: 1,
+            samples: vec![0.4; 4],
+        };
+        let short = AudioFrame {
+            sample_rate: 48000,
+            channels: 1,
+            samples: vec![0.2; 2],
+        };
+
+        let mixed = processor.mix_audio(&[long, short]).unwrap();
+        assert_eq!(mixed.samples.len(), 4);
+        assert!((mixed.samples[0] - 0.3).abs() < 1e-6);
+        assert!((mixed.samples[3] - 0.2).abs() < 1e-6);
+    }
+
+    #[test]
+    fn test_resample_linear_interpolation() {
+        let processor = AudioProcessor::new(48000, 1);
+        let input = AudioFrame {
+            sample_rate: 24000,
+            channels: 1,
+            samples: vec![0.0, 1.0, 0.0],
+        };
+
+        let output = processor.resample(&input).unwrap();
+        assert_eq!(output.samples.len(), 6);
+        assert_eq!(&output.samples[..4], &[0.0, 0.5, 1.0, 0.5]);
+    }
+
+    #[test]
+    fn test_stream_resampler_matches_one_shot_across_blocks() {
+        let ramp: Vec<f32> = (0..441).map(|i| i as f32 / 441.0).collect();
+        let one_shot = AudioProcessor::new(48000, 1)
+            .resample(&AudioFrame {
+                sample_rate: 44100,
+                channels: 1,
+                samples: ramp.clone(),
+            })
+            .unwrap();
+
+        // Uneven blocks must join into the same continuous ramp
+        let mut resampler = StreamResampler::new();
+        let mut streamed = Vec::new();
+        for block in ramp.chunks(37) {
+            let frame = AudioFrame {
+                sample_rate: 44100,
+                channels: 1,
+                samples: block.to_vec(),
+            };
+            streamed.extend(resampler.resample(&frame, 48000, 1).unwrap().samples);
+        }
+
+        // Only the final frame, which needs the next block, is held back
+        assert_eq!(streamed.len(), one_shot.samples.len() - 1);
+        for (streamed, expected) in streamed.iter().zip(&one_shot.samples) {
+            assert!((streamed - expected).abs() < 1e-5);
+        }
+    }
+
+    #[test]
+    fn test_stream_resampler_does_not_drift() {
+        let mut resampler = StreamResampler::new();
+        let block = AudioFrame {
+            sample_rate: 44100,
+            channels: 2,
+            samples: vec![0.25; 441 * 2],
+        };
+
+        // 100 blocks of 10ms make exactly one second at 48kHz, give or take a frame
+        let frames: usize = (0..100)
+            .map(|_| resampler.resample(&block, 48000, 2).unwrap().samples.len() / 2)
+            .sum();
+        assert!((47999..=48000).contains(&frames), "got {} frames", frames);
+    }
+
+    #[test]
+    fn test_stream_resampler_passes_through_and_remixes() {
+        let mut resampler = StreamResampler::new();
+        let mono = AudioFrame {
+            sample_rate: 48000,
+            channels: 1,
+            samples: vec![0.5, -0.5],
+        };
+
+        let output = resampler.resample(&mono, 48000, 2).unwrap();
+        assert_eq!(output.samples, vec![0.5, 0.5, -0.5, -0.5]);
+    }
+
     #[test]
     fn test_audio_level_analyzer() {
         let mut analyzer = AudioLevelAnalyzer::new();
@@ -343,4 +892,30 @@ mod tests {
         let overall_rms = analyzer.get_overall_rms();
         assert!(overall_rms > 0.0);
     }
+
+    #[test]
+    fn test_audio_level_subscribers_receive_fresh_levels() {
+        let mut analyzer = AudioLevelAnalyzer::new();
+        analyzer.set_update_interval(60_000);
+        let receiver = analyzer.subscribe();
+        let dropped = analyzer.subscribe();
+        drop(dropped);
+
+        let node_id = Uuid::new_v4();
+        let audio = UnifiedAudioData::Stereo {
+            sample_rate: 48000,
+            channels: 2,
+            samples: vec![0.5, -0.5],
+        };
+
+        analyzer.analyze_frame(node_id, &audio);
+        // Throttled call returns the cached level without notifying again
+        analyzer.analyze_frame(node_id, &audio);
+
+        let update = receiver.try_recv().unwrap();
+        assert_eq!(update.node_id, node_id);
+        assert!((update.level.peak_left - 0.5).abs() < 1e-5);
+        assert!(receiver.try_recv().is_err());
+        assert_eq!(analyzer.subscriber_count(), 1);
+    }
 }
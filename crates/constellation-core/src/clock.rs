@@ -0,0 +1,223 @@
+/*
+ * Constellation Studio - Professional Real-time Video Processing
+ * Copyright (c) 2025 MACHIKO LAB
+ *
+ * This program is free software: you can redistribute it and/or modify
+ * it under the terms of the GNU Affero General Public License as published by
+ * the Free Software Foundation, either version 3 of the License, or
+ * (at your option) any later version.
+ *
+ * This program is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+ * GNU Affero General Public License for more details.
+ *
+ * You should have received a copy of the GNU Affero General Public License
+ * along with this program. If not, see <https://www.gnu.org/licenses/>.
+ */
+
+use crate::error::{ConstellationError, ConstellationResult};
+use serde::{Deserialize, Serialize};
+use std::time::{Duration, Instant};
+
+/// フレーム予算を超過したときの扱い
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum FrameOverrunPolicy {
+    /// 完全に過ぎたフレーム枠を捨て、現在の枠から再開する
+    Drop,
+    /// 枠を捨てずに待ち時間なしで連続処理し、遅れを取り戻す
+    CatchUp,
+}
+
+/// 1回のtickの結果
+#[derive(Debug, Clone, PartialEq)]
+pub struct FrameTick {
+    /// フレーム枠の番号（開始時が0）
+    pub slot: u64,
+    /// 開始時刻からの予定時刻
+    pub scheduled: Duration,
+    /// 開始時刻からの実際の時刻
+    pub actual: Duration,
+    /// 前回のtickからの経過時間
+    pub interval: Duration,
+    /// 今回のtickで捨てたフレーム枠の数
+    pub skipped: u64,
+}
+
+impl FrameTick {
+    /// 予定時刻からの遅れ
+    pub fn lateness(&self) -> Duration {
+        self.actual.saturating_sub(self.scheduled)
+    }
+}
+
+/// 実行中のタイミング統計
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct FrameClockStats {
+    pub frames: u64,
+    pub dropped_frames: u64,
+    pub late_frames: u64,
+    pub max_lateness: Duration,
+}
+
+/// 固定フレームレートでループを刻むクロック
+#[derive(Debug)]
+pub struct FrameClock {
+    frame_duration: Duration,
+    overrun_policy: FrameOverrunPolicy,
+    start: Option<Instant>,
+    last_tick: Option<Instant>,
+    next_slot: u64,
+    stats: FrameClockStats,
+}
+
+impl FrameClock {
+    pub fn new(target_fps: f64) -> ConstellationResult<Self> {
+        if !target_fps.is_finite() || target_fps <= 0.0 {
+            return Err(ConstellationError::InvalidParameter {
+                parameter: "target_fps".to_string(),
+                value: target_fps.to_string(),
+            });
+        }
+
+        Ok(Self {
+            frame_duration: Duration::from_secs_f64(1.0 / target_fps),
+            overrun_policy: FrameOverrunPolicy::Drop,
+            start: None,
+            last_tick: None,
+            next_slot: 0,
+            stats: FrameClockStats::default(),
+        })
+    }
+
+    pub fn with_overrun_policy(mut self, policy: FrameOverrunPolicy) -> Self {
+        self.overrun_policy = policy;
+        self
+    }
+
+    pub fn frame_duration(&self) -> Duration {
+        self.frame_duration
+    }
+
+    pub fn stats(&self) -> &FrameClockStats {
+        &self.stats
+    }
+
+    fn slot_time(&self, slot: u64) -> Duration {
+        Duration::from_nanos((self.frame_duration.as_nanos() as u64).saturating_mul(slot))
+    }
+
+    /// 次のフレーム枠まで待機する（最初の呼び出しは即座に戻る）
+    pub fn tick(&mut self) -> FrameTick {
+        let start = *self.start.get_or_insert_with(Instant::now);
+        let mut slot = self.next_slot;
+        let mut skipped = 0;
+
+        let deadline = start + self.slot_time(slot);
+        let now = Instant::now();
+        if now < deadline {
+            std::thread::sleep(deadline - now);
+        } else if self.overrun_policy == FrameOverrunPolicy::Drop {
+            // 1枠未満の遅れはそのまま処理し、完全に過ぎた枠だけを捨てる
+            let current_slot =
+                (now.duration_since(start).as_nanos() / self.frame_duration.as_nanos()) as u64;
+            if current_slot > slot {
+                skipped = current_slot - slot;
+                slot = current_slot;
+            }
+        }
+
+        let now = Instant::now();
+        let tick = FrameTick {
+            slot,
+            scheduled: self.slot_time(slot),
+            actual: now.duration_since(start),
+            interval: self
+                .last_tick
+                .map(|last| now.duration_since(last))
+                .unwrap_or_default(),
+            skipped,
+        };
+
+        self.last_tick = Some(now);
+        self.next_slot = slot + 1;
+
+        let lateness = tick.lateness();
+        self.stats.frames += 1;
+        self.stats.dropped_frames += skipped;
+        if lateness >= self.frame_duration {
+            self.stats.late_frames += 1;
+        }
+        self.stats.max_lateness = self.stats.max_lateness.max(lateness);
+
+        tick
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_invalid_fps_rejected() {
+        assert!(FrameClock::new(0.0).is_err());
+        assert!(FrameClock::new(-30.0).is_err());
+        assert!(FrameClock::new(f64::NAN).is_err());
+    }
+
+    #[test]
+    fn test_ten_frames_at_30fps() {
+        let mut clock = FrameClock::new(30.0).unwrap();
+        let start = Instant::now();
+        let ticks: Vec<FrameTick> = (0..10).map(|_| clock.tick()).collect();
+        let elapsed = start.elapsed();
+
+        // 最初のフレームは即座に、以降は33.3ms間隔で9回待つ
+        assert!(
+            elapsed >= Duration::from_millis(295) && elapsed < Duration::from_millis(400),
+            "elapsed {:?}",
+            elapsed
+        );
+        assert_eq!(
+            ticks.iter().map(|t| t.slot).collect::<Vec<_>>(),
+            (0..10).collect::<Vec<_>>()
+        );
+        assert_eq!(clock.stats().frames, 10);
+        assert_eq!(clock.stats().dropped_frames, 0);
+    }
+
+    #[test]
+    fn test_overrun_drops_missed_slots() {
+        let mut clock = FrameClock::new(100.0).unwrap();
+        clock.tick();
+
+        // 予算(10ms)を大きく超える処理
+        std::thread::sleep(Duration::from_millis(35));
+        let tick = clock.tick();
+        assert!(tick.skipped >= 2, "skipped {}", tick.skipped);
+        assert_eq!(tick.slot, 1 + tick.skipped);
+        assert!(tick.lateness() < clock.frame_duration());
+        assert_eq!(clock.stats().dropped_frames, tick.skipped);
+    }
+
+    #[test]
+    fn test_overrun_catches_up_without_dropping() {
+        let mut clock = FrameClock::new(100.0)
+            .unwrap()
+            .with_overrun_policy(FrameOverrunPolicy::CatchUp);
+        clock.tick();
+        std::thread::sleep(Duration::from_millis(35));
+
+        // 遅れている間は待たずに次の枠を処理する
+        let before = Instant::now();
+        let ticks: Vec<FrameTick> = (0..3).map(|_| clock.tick()).collect();
+        assert!(before.elapsed() < Duration::from_millis(10));
+        assert_eq!(
+            ticks.iter().map(|t| t.slot).collect::<Vec<_>>(),
+            vec![1, 2, 3]
+        );
+        assert!(ticks.iter().all(|t| t.skipped == 0));
+        assert_eq!(clock.stats().dropped_frames, 0);
+        assert!(clock.stats().late_frames >= 1);
+    }
+}
@@ -0,0 +1,222 @@
+/*
+ * Constellation Studio - Professional Real-time Video Processing
+ * Copyright (c) 2025 MACHIKO LAB
+ *
+ * This program is free software: you can redistribute it and/or modify
+ * it under the terms of the GNU Affero General Public License as published by
+ * the Free Software Foundation, either version 3 of the License, or
+ * (at your option) any later version.
+ *
+ * This program is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+ * GNU Affero General Public License for more details.
+ *
+ * You should have received a copy of the GNU Affero General Public License
+ * along with this program. If not, see <https://www.gnu.org/licenses/>.
+ */
+
+//! エンジンの起動設定
+
+use crate::OutOfRangePolicy;
+use constellation_vulkan::FrameSize;
+use serde::{Deserialize, Serialize};
+
+/// 映像処理のバックエンド
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum BackendKind {
+    /// GPU（Vulkan）でフレームプールを使う
+    Vulkan,
+    /// Vulkanを使わずCPUのエフェクト処理だけで動かす（CI・ヘッドレス環境向け）
+    Cpu,
+}
+
+/// 起動時に確保するフレームプール
+#[derive(Debug, Clone, PartialEq)]
+pub struct FramePoolConfig {
+    pub frame_size: FrameSize,
+    pub buffer_count: u32,
+    // trueならデバイスローカル、falseならCPUから書き込めるホスト可視メモリ
+    pub device_local: bool,
+}
+
+/// `ConstellationEngine::with_config` に渡す設定
+#[derive(Debug, Clone, PartialEq)]
+pub struct EngineConfig {
+    /// 使うバックエンド。Noneなら Vulkan を試し、初期化に失敗したらCPUにフォールバックする
+    pub backend: Option<BackendKind>,
+    /// レジリエンス機能（リトライ・フォールバック）を有効にする
+    pub enable_resilience: bool,
+    /// パフォーマンストレースを記録するフレームの割合（0.0〜1.0）
+    pub telemetry_sample_rate: f32,
+    /// `VulkanContext::list_physical_devices` の順序で使うGPU。Noneなら自動選択
+    pub device_index: Option<usize>,
+    /// 起動時に事前確保するフレームプール
+    pub frame_pools: Vec<FramePoolConfig>,
+    /// `prepare_pools` がグラフのフレームサイズごとに確保するバッファ数
+    pub frame_pool_buffer_count: u32,
+    /// パラメータ定義の範囲外の値を拒否するか丸めるか
+    pub parameter_policy: OutOfRangePolicy,
+}
+
+impl Default for EngineConfig {
+    fn default() -> Self {
+        Self {
+            backend: None,
+            enable_resilience: false,
+            telemetry_sample_rate: 1.0,
+            device_index: None,
+            frame_pools: Vec::new(),
+            frame_pool_buffer_count: 4,
+            parameter_policy: OutOfRangePolicy::Reject,
+        }
+    }
+}
+
+impl EngineConfig {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn with_backend(mut self, backend: BackendKind) -> Self {
+        self.backend = Some(backend);
+        self
+    }
+
+    pub fn with_resilience(mut self, enabled: bool) -> Self {
+        self.enable_resilience = enabled;
+        self
+    }
+
+    /// 範囲外の値は0.0〜1.0に丸める
+    pub fn with_telemetry_sample_rate(mut self, rate: f32) -> Self {
+        self.telemetry_sample_rate = if rate.is_nan() {
+            1.0
+        } else {
+            rate.clamp(0.0, 1.0)
+        };
+        self
+    }
+
+    pub fn with_device_index(mut self, device_index: usize) -> Self {
+        self.device_index = Some(device_index);
+        self
+    }
+
+    pub fn with_parameter_policy(mut self, policy: OutOfRangePolicy) -> Self {
+        self.parameter_policy = policy;
+        self
+    }
+
+    /// ホスト可視メモリのフレームプールを追加する
+    pub fn with_frame_pool(mut self, frame_size: FrameSize, buffer_count: u32) -> Self {
+        self.frame_pools.push(FramePoolConfig {
+            frame_size,
+            buffer_count,
+            device_local: false,
+        });
+        self
+    }
+
+    pub fn with_frame_pool_buffer_count(mut self, buffer_count: u32) -> Self {
+        self.frame_pool_buffer_count = buffer_count;
+        self
+    }
+
+    /// 設定値の検証
+    pub fn validate(&self) -> Result<(), String> {
+        if self.frame_pool_buffer_count == 0 {
+            return Err("Frame pool buffer count must be at least 1".to_string());
+        }
+        if let Some(pool) = self.frame_pools.iter().find(|pool| pool.buffer_count == 0) {
+            return Err(format!(
+                "Frame pool {}x{} {:?} needs at least one buffer",
+                pool.frame_size.width, pool.frame_size.height, pool.frame_size.format
+            ));
+        }
+        if let Some(pool) = self
+            .frame_pools
+            .iter()
+            .find(|pool| pool.frame_size.width == 0 || pool.frame_size.height == 0)
+        {
+            return Err(format!(
+                "Frame pool {}x{} has an empty resolution",
+                pool.frame_size.width, pool.frame_size.height
+            ));
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use constellation_vulkan::FrameFormat;
+
+    fn hd_frame() -> FrameSize {
+        FrameSize {
+            width: 1920,
+            height: 1080,
+            format: FrameFormat::Rgba8,
+        }
+    }
+
+    #[test]
+    fn test_default_matches_previous_engine_behavior() {
+        let config = EngineConfig::default();
+        assert_eq!(config.backend, None);
+        assert!(!config.enable_resilience);
+        assert_eq!(config.telemetry_sample_rate, 1.0);
+        assert_eq!(config.device_index, None);
+        assert!(config.frame_pools.is_empty());
+        assert_eq!(config.frame_pool_buffer_count, 4);
+        assert!(config.validate().is_ok());
+    }
+
+    #[test]
+    fn test_builder_sets_every_option() {
+        let config = EngineConfig::new()
+            .with_backend(BackendKind::Cpu)
+            .with_resilience(true)
+            .with_telemetry_sample_rate(0.25)
+            .with_device_index(1)
+            .with_frame_pool(hd_frame(), 6);
+
+        assert_eq!(config.backend, Some(BackendKind::Cpu));
+        assert!(config.enable_resilience);
+        assert_eq!(config.telemetry_sample_rate, 0.25);
+        assert_eq!(config.device_index, Some(1));
+        assert_eq!(
+            config.frame_pools,
+            vec![FramePoolConfig {
+                frame_size: hd_frame(),
+                buffer_count: 6,
+                device_local: false,
+            }]
+        );
+    }
+
+    #[test]
+    fn test_invalid_values_are_clamped_or_rejected() {
+        assert_eq!(
+            EngineConfig::new()
+                .with_telemetry_sample_rate(4.0)
+                .telemetry_sample_rate,
+            1.0
+        );
+        assert_eq!(
+            EngineConfig::new()
+                .with_telemetry_sample_rate(-1.0)
+                .telemetry_sample_rate,
+            0.0
+        );
+        assert!(EngineConfig::new()
+            .with_frame_pool(hd_frame(), 0)
+            .validate()
+            .is_err());
+        assert!(EngineConfig::new()
+            .with_frame_pool_buffer_count(0)
+            .validate()
+            .is_err());
+    }
+}
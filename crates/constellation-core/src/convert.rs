@@ -0,0 +1,398 @@
+/*
+ * Constellation Studio - Professional Real-time Video Processing
+ * Copyright (c) 2025 MACHIKO LAB
+ *
+ * This program is free software: you can redistribute it and/or modify
+ * it under the terms of the GNU Affero General Public License as published by
+ * the Free Software Foundation, either version 3 of the License, or
+ * (at your option) any later version.
+ *
+ * This program is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+ * GNU Affero General Public License for more details.
+ *
+ * You should have received a copy of the GNU Affero General Public License
+ * along with this program. If not, see <https://www.gnu.org/licenses/>.
+ */
+
+//! 非圧縮フレームのフォーマット変換（RGBA8を中間表現とし、YUVはBT.709リミテッドレンジ）
+//!
+//! パイプラインのフォーマット交渉と出力ノードの両方がこのモジュールを使う
+
+use crate::{VideoFormat, VideoFrame};
+use anyhow::{bail, Result};
+
+// BT.709 輝度係数
+const KR: f32 = 0.2126;
+const KB: f32 = 0.0722;
+const KG: f32 = 1.0 - KR - KB;
+
+/// VideoFrameを指定フォーマットに変換する
+/// 非圧縮フォーマット間のみ対応し、RGBA8を中間表現として使用する
+pub fn convert_video_frame(frame: VideoFrame, target: &VideoFormat) -> Result<VideoFrame> {
+    if frame.format == *target {
+        return Ok(frame);
+    }
+
+    let rgba = frame_to_rgba(&frame)?;
+    let data = rgba_to_format(&rgba, frame.width, frame.height, target)?;
+
+    Ok(VideoFrame {
+        width: frame.width,
+        height: frame.height,
+        format: target.clone(),
+        data,
+    })
+}
+
+/// フォーマットごとの期待データサイズ（Yuv420pの色差面は切り上げ）
+pub fn expected_data_len(format: &VideoFormat, width: u32, height: u32) -> Option<usize> {
+    let (w, h) = (width as usize, height as usize);
+    match format {
+        VideoFormat::Rgba8 | VideoFormat::Bgra8 => Some(w * h * 4),
+        VideoFormat::Rgb8 | VideoFormat::Bgr8 => Some(w * h * 3),
+        VideoFormat::Rgba16 => Some(w * h * 8),
+        VideoFormat::Yuv420p => Some(w * h + 2 * w.div_ceil(2) * h.div_ceil(2)),
+        VideoFormat::Jpeg | VideoFormat::Png => None,
+    }
+}
+
+/// 非圧縮フレームを詰めたRGBA8にデコードする
+pub fn frame_to_rgba(frame: &VideoFrame) -> Result<Vec<u8>> {
+    let Some(expected) = expected_data_len(&frame.format, frame.width, frame.height) else {
+        bail!("Cannot convert compressed format {:?}", frame.format);
+    };
+    if frame.data.len() < expected {
+        bail!(
+            "Frame data too short for {:?} {}x{}: {} < {}",
+            frame.format,
+            frame.width,
+            frame.height,
+            frame.data.len(),
+            expected
+        );
+    }
+
+    let data = &frame.data[..expected];
+    let rgba = match frame.format {
+        VideoFormat::Rgba8 => data.to_vec(),
+        VideoFormat::Bgra8 => data
+            .chunks_exact(4)
+            .flat_map(|p| [p[2], p[1], p[0], p[3]])
+            .collect(),
+        VideoFormat::Rgb8 => data
+            .chunks_exact(3)
+            .flat_map(|p| [p[0], p[1], p[2], 255])
+            .collect(),
+        VideoFormat::Bgr8 => data
+            .chunks_exact(3)
+            .flat_map(|p| [p[2], p[1], p[0], 255])
+            .collect(),
+        // 各チャンネル（リトルエンディアン）の上位バイトを取る
+        VideoFormat::Rgba16 => data
+            .chunks_exact(8)
+            .flat_map(|p| [p[1], p[3], p[5], p[7]])
+            .collect(),
+        VideoFormat::Yuv420p => yuv420_to_rgba(data, frame.width, frame.height)?,
+        VideoFormat::Jpeg | VideoFormat::Png => unreachable!(),
+    };
+
+    Ok(rgba)
+}
+
+/// 詰めたRGBA8を指定フォーマットにエンコードする
+pub fn rgba_to_format(
+    rgba: &[u8],
+    width: u32,
+    height: u32,
+    target: &VideoFormat,
+) -> Result<Vec<u8>> {
+    let data = match target {
+        VideoFormat::Rgba8 => rgba.to_vec(),
+        VideoFormat::Bgra8 => rgba
+            .chunks_exact(4)
+            .flat_map(|p| [p[2], p[1], p[0], p[3]])
+            .collect(),
+        VideoFormat::Rgb8 => rgba
+            .chunks_exact(4)
+            .flat_map(|p| [p[0], p[1], p[2]])
+            .collect(),
+        VideoFormat::Bgr8 => rgba
+            .chunks_exact(4)
+            .flat_map(|p| [p[2], p[1], p[0]])
+            .collect(),
+        // v * 257 で 0..=255 を 0..=65535 に広げる
+        VideoFormat::Rgba16 => rgba
+            .iter()
+            .flat_map(|&v| (v as u16 * 257).to_le_bytes())
+            .collect(),
+        VideoFormat::Yuv420p => rgba_to_yuv420(rgba, width, height),
+        VideoFormat::Jpeg | VideoFormat::Png => {
+            bail!("Cannot convert to compressed format {:?}", target)
+        }
+    };
+    Ok(data)
+}
+
+/// RGBA8をプレーナーI420（Y, U, Vの順）に変換する
+/// 色差面は ceil(width/2) x ceil(height/2)
+pub fn rgba_to_yuv420(rgba: &[u8], width: u32, height: u32) -> Vec<u8> {
+    let (y_plane, chroma) = rgba_to_planes(rgba, width, height);
+    let mut out = y_plane;
+    out.extend(chroma.iter().map(|&(u, _)| u));
+    out.extend(chroma.iter().map(|&(_, v)| v));
+    out
+}
+
+/// RGBA8をNV12（Y面＋U/Vインターリーブ面）に変換する
+pub fn rgba_to_nv12(rgba: &[u8], width: u32, height: u32) -> Vec<u8> {
+    let (y_plane, chroma) = rgba_to_planes(rgba, width, height);
+    let mut out = y_plane;
+    out.extend(chroma.iter().flat_map(|&(u, v)| [u, v]));
+    out
+}
+
+/// RGBA8をパック形式4:2:2のUYVYに変換する
+/// 横2画素で平均した(U, V)を共有し、奇数幅の最終列は自身と組にする
+pub fn rgba_to_uyvy(rgba: &[u8], width: u32, height: u32) -> Vec<u8> {
+    let (w, h) = (width as usize, height as usize);
+    let mut out = Vec::with_capacity(w.div_ceil(2) * 4 * h);
+
+    for row in rgba.chunks_exact(w * 4).take(h) {
+        for pair in 0..w.div_ceil(2) {
+            let left = &row[pair * 8..pair * 8 + 4];
+            let right = row.get(pair * 8 + 4..pair * 8 + 8).unwrap_or(left);
+
+            let [r, g, b] = [0, 1, 2].map(|c| (left[c] as f32 + right[c] as f32) / 2.0);
+            let (u, v) = chroma_sample(r, g, b);
+            out.extend_from_slice(&[u, luma_sample(left), v, luma_sample(right)]);
+        }
+    }
+
+    out
+}
+
+/// プレーナーI420をRGBA8に変換する
+pub fn yuv420_to_rgba(yuv: &[u8], width: u32, height: u32) -> Result<Vec<u8>> {
+    let (w, h) = (width as usize, height as usize);
+    let (cw, ch) = (w.div_ceil(2), h.div_ceil(2));
+    let expected = w * h + 2 * cw * ch;
+    if yuv.len() < expected {
+        bail!("YUV420 buffer too short: {} < {}", yuv.len(), expected);
+    }
+
+    let (y_plane, chroma) = yuv.split_at(w * h);
+    let (u_plane, v_plane) = chroma.split_at(cw * ch);
+    let mut rgba = Vec::with_capacity(w * h * 4);
+
+    for y in 0..h {
+        for x in 0..w {
+            let chroma_index = (y / 2) * cw + x / 2;
+            let [r, g, b] = yuv_to_rgb(
+                y_plane[y * w + x],
+                u_plane[chroma_index],
+                v_plane[chroma_index],
+            );
+            rgba.extend_from_slice(&[r, g, b, 255]);
+        }
+    }
+
+    Ok(rgba)
+}
+
+/// Y面と2x2平均の(U, V)サンプルを求める
+fn rgba_to_planes(rgba: &[u8], width: u32, height: u32) -> (Vec<u8>, Vec<(u8, u8)>) {
+    let (w, h) = (width as usize, height as usize);
+    let (cw, ch) = (w.div_ceil(2), h.div_ceil(2));
+
+    let y_plane = rgba.chunks_exact(4).take(w * h).map(luma_sample).collect();
+
+    let mut chroma = Vec::with_capacity(cw * ch);
+    for cy in 0..ch {
+        for cx in 0..cw {
+            let mut sum = [0.0f32; 3];
+            let mut count = 0.0f32;
+            // 奇数サイズの端は存在する画素だけで平均する
+            for y in (cy * 2)..(cy * 2 + 2).min(h) {
+                for x in (cx * 2)..(cx * 2 + 2).min(w) {
+                    let p = &rgba[(y * w + x) * 4..];
+                    sum[0] += p[0] as f32;
+                    sum[1] += p[1] as f32;
+                    sum[2] += p[2] as f32;
+                    count += 1.0;
+                }
+            }
+
+            let [r, g, b] = sum.map(|c| c / count);
+            chroma.push(chroma_sample(r, g, b));
+        }
+    }
+
+    (y_plane, chroma)
+}
+
+fn luma(r: f32, g: f32, b: f32) -> f32 {
+    KR * r + KG * g + KB * b
+}
+
+/// リミテッドレンジのY
+fn luma_sample(p: &[u8]) -> u8 {
+    to_u8(16.0 + luma(p[0] as f32, p[1] as f32, p[2] as f32) * 219.0 / 255.0)
+}
+
+/// リミテッドレンジの(U, V)
+fn chroma_sample(r: f32, g: f32, b: f32) -> (u8, u8) {
+    let y = luma(r, g, b);
+    let u = (b - y) / (2.0 * (1.0 - KB));
+    let v = (r - y) / (2.0 * (1.0 - KR));
+    (
+        to_u8(128.0 + u * 224.0 / 255.0),
+        to_u8(128.0 + v * 224.0 / 255.0),
+    )
+}
+
+fn yuv_to_rgb(y: u8, u: u8, v: u8) -> [u8; 3] {
+    let luma = (y as f32 - 16.0) * 255.0 / 219.0;
+    let u = (u as f32 - 128.0) * 255.0 / 224.0;
+    let v = (v as f32 - 128.0) * 255.0 / 224.0;
+
+    let r = luma + 2.0 * (1.0 - KR) * v;
+    let b = luma + 2.0 * (1.0 - KB) * u;
+    let g = (luma - KR * r - KB * b) / KG;
+
+    [to_u8(r), to_u8(g), to_u8(b)]
+}
+
+fn to_u8(value: f32) -> u8 {
+    value.round().clamp(0.0, 255.0) as u8
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn frame(format: VideoFormat, data: Vec<u8>) -> VideoFrame {
+        VideoFrame {
+            width: 2,
+            height: 1,
+            format,
+            data,
+        }
+    }
+
+    fn gradient(width: u32, height: u32) -> VideoFrame {
+        let data = (0..height)
+            .flat_map(|y| {
+                (0..width).flat_map(move |x| {
+                    [
+                        (x * 8) as u8,
+                        (y * 16) as u8,
+                        (255 - x * 4 - y * 4) as u8,
+                        255,
+                    ]
+                })
+            })
+            .collect();
+        VideoFrame {
+            width,
+            height,
+            format: VideoFormat::Rgba8,
+            data,
+        }
+    }
+
+    fn assert_close(actual: &[u8], expected: &[u8], tolerance: i32) {
+        assert_eq!(actual.len(), expected.len());
+        for (i, (a, b)) in actual.iter().zip(expected).enumerate() {
+            assert!(
+                (*a as i32 - *b as i32).abs() <= tolerance,
+                "byte {}: {} vs {}",
+                i,
+                a,
+                b
+            );
+        }
+    }
+
+    #[test]
+    fn test_bgra_to_rgba_swaps_channels() {
+        let input = frame(VideoFormat::Bgra8, vec![10, 20, 30, 40, 50, 60, 70, 80]);
+        let output = convert_video_frame(input, &VideoFormat::Rgba8).unwrap();
+        assert_eq!(output.format, VideoFormat::Rgba8);
+        assert_eq!(output.data, vec![30, 20, 10, 40, 70, 60, 50, 80]);
+    }
+
+    #[test]
+    fn test_packed_format_round_trip() {
+        let input = frame(VideoFormat::Rgba8, vec![1, 2, 3, 255, 4, 5, 6, 255]);
+        for target in [
+            VideoFormat::Rgb8,
+            VideoFormat::Bgr8,
+            VideoFormat::Bgra8,
+            VideoFormat::Rgba16,
+        ] {
+            let converted = convert_video_frame(input.clone(), &target).unwrap();
+            assert_eq!(
+                converted.data.len(),
+                expected_data_len(&target, 2, 1).unwrap()
+            );
+            let back = convert_video_frame(converted, &VideoFormat::Rgba8).unwrap();
+            assert_eq!(back.data, input.data);
+        }
+    }
+
+    #[test]
+    fn test_yuv420p_round_trip_odd_dimensions() {
+        let (width, height) = (3, 3);
+        let data = [200u8, 100, 50, 255].repeat((width * height) as usize);
+        let input = VideoFrame {
+            width,
+            height,
+            format: VideoFormat::Rgba8,
+            data,
+        };
+
+        let yuv = convert_video_frame(input.clone(), &VideoFormat::Yuv420p).unwrap();
+        assert_eq!(yuv.data.len(), 9 + 2 * 4);
+
+        let back = convert_video_frame(yuv, &VideoFormat::Rgba8).unwrap();
+        assert_close(&back.data, &input.data, 2);
+    }
+
+    #[test]
+    fn test_yuv420p_round_trip_gradient() {
+        // 2x2で色差を共有するため、隣接画素の差が小さいグラデーションで許容誤差を見る
+        let input = gradient(16, 8);
+        let yuv = rgba_to_yuv420(&input.data, 16, 8);
+        assert_eq!(
+            yuv.len(),
+            expected_data_len(&VideoFormat::Yuv420p, 16, 8).unwrap()
+        );
+
+        let back = yuv420_to_rgba(&yuv, 16, 8).unwrap();
+        assert_close(&back, &input.data, 12);
+    }
+
+    #[test]
+    fn test_yuv420_known_values() {
+        // 白・黒はリミテッドレンジの端、色差は中央
+        let white = rgba_to_yuv420(&[255, 255, 255, 255], 1, 1);
+        assert_eq!(white, vec![235, 128, 128]);
+        let black = rgba_to_yuv420(&[0, 0, 0, 255], 1, 1);
+        assert_eq!(black, vec![16, 128, 128]);
+    }
+
+    #[test]
+    fn test_short_yuv420_rejected() {
+        assert!(yuv420_to_rgba(&[16; 5], 2, 2).is_err());
+    }
+
+    #[test]
+    fn test_compressed_format_rejected() {
+        let input = frame(VideoFormat::Jpeg, vec![0xFF, 0xD8]);
+        assert!(convert_video_frame(input, &VideoFormat::Rgba8).is_err());
+        let input = frame(VideoFormat::Rgba8, vec![0; 8]);
+        assert!(convert_video_frame(input, &VideoFormat::Png).is_err());
+    }
+}
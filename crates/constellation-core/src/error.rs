@@ -52,6 +52,14 @@ pub enum ConstellationError {
         connection_type: String,
     },
 
+    #[error("Incompatible connection: {source_id} -> {target_id} ({connection_type}): {reason}")]
+    IncompatibleConnection {
+        source_id: Uuid,
+        target_id: Uuid,
+        connection_type: String,
+        reason: String,
+    },
+
     #[error("Connection cycle detected: {path:?}")]
     ConnectionCycleDetected { path: Vec<Uuid> },
 
@@ -68,6 +76,13 @@ pub enum ConstellationError {
     #[error("Frame processing timeout: {timeout_ms}ms")]
     FrameProcessingTimeout { timeout_ms: u64 },
 
+    #[error("Batch processing aborted after {completed}/{total} frames: {source}")]
+    BatchProcessingFailed {
+        completed: usize,
+        total: usize,
+        source: Box<ConstellationError>,
+    },
+
     // === リソースエラー ===
     #[error("Insufficient memory: required {required_bytes} bytes")]
     InsufficientMemory { required_bytes: u64 },
@@ -130,6 +145,10 @@ pub enum ConstellationError {
         max: String,
     },
 
+    /// ParameterDefinitionの型・範囲を満たさない値
+    #[error("Parameter validation failed: {parameter} - {reason}")]
+    ParameterValidationFailed { parameter: String, reason: String },
+
     #[error("Configuration error: {reason}")]
     ConfigurationError { reason: String },
 
@@ -159,7 +178,7 @@ pub enum ConstellationError {
 }
 
 /// エラーの重要度レベル
-#[derive(Debug, Clone, Copy, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, serde::Serialize, serde::Deserialize)]
 pub enum ErrorSeverity {
     /// 情報レベル（動作に影響なし）
     Info,
@@ -172,7 +191,7 @@ pub enum ErrorSeverity {
 }
 
 /// エラーカテゴリ
-#[derive(Debug, Clone, Copy, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, serde::Serialize, serde::Deserialize)]
 pub enum ErrorCategory {
     System,
     Node,
@@ -191,6 +210,9 @@ impl ConstellationError {
     /// エラーの重要度を取得
     pub fn severity(&self) -> ErrorSeverity {
         match self {
+            // バッチの中断は原因となったエラーで判定する
+            ConstellationError::BatchProcessingFailed { source, .. } => source.severity(),
+
             // 致命的エラー
             ConstellationError::EngineInitializationFailed { .. }
             | ConstellationError::InsufficientMemory { .. }
@@ -200,6 +222,7 @@ impl ConstellationError {
             // 通常のエラー
             ConstellationError::NodeNotFound { .. }
             | ConstellationError::InvalidConnection { .. }
+            | ConstellationError::IncompatibleConnection { .. }
             | ConstellationError::FrameProcessingFailed { .. }
             | ConstellationError::DeviceAccessFailed { .. }
             | ConstellationError::FileNotFound { .. } => ErrorSeverity::Error,
@@ -217,6 +240,8 @@ impl ConstellationError {
     /// エラーのカテゴリを取得
     pub fn category(&self) -> ErrorCategory {
         match self {
+            ConstellationError::BatchProcessingFailed { source, .. } => source.category(),
+
             ConstellationError::EngineInitializationFailed { .. }
             | ConstellationError::EngineNotRunning
             | ConstellationError::EngineAlreadyRunning => ErrorCategory::System,
@@ -226,6 +251,7 @@ impl ConstellationError {
             | ConstellationError::NodeCreationFailed { .. }
             | ConstellationError::NodeProcessingFailed { .. }
             | ConstellationError::InvalidConnection { .. }
+            | ConstellationError::IncompatibleConnection { .. }
             | ConstellationError::ConnectionCycleDetected { .. } => ErrorCategory::Node,
 
             ConstellationError::FrameProcessingFailed { .. }
@@ -253,6 +279,7 @@ impl ConstellationError {
 
             ConstellationError::InvalidParameter { .. }
             | ConstellationError::ParameterOutOfRange { .. }
+            | ConstellationError::ParameterValidationFailed { .. }
             | ConstellationError::ConfigurationError { .. } => ErrorCategory::Configuration,
 
             ConstellationError::PlatformNotSupported { .. }
@@ -279,6 +306,9 @@ impl ConstellationError {
             ConstellationError::InvalidConnection { .. } => {
                 "ノードの接続が無効です。接続タイプを確認してください。".to_string()
             }
+            ConstellationError::IncompatibleConnection { reason, .. } => {
+                format!("この接続タイプは使用できません: {}", reason)
+            }
             ConstellationError::FrameProcessingFailed { .. } => {
                 "映像処理中にエラーが発生しました。".to_string()
             }
@@ -305,6 +335,8 @@ impl ConstellationError {
     /// 復旧可能かどうかを判定
     pub fn is_recoverable(&self) -> bool {
         match self {
+            ConstellationError::BatchProcessingFailed { source, .. } => source.is_recoverable(),
+
             // 復旧不可能
             ConstellationError::EngineInitializationFailed { .. }
             | ConstellationError::HardwareNotSupported { .. }
@@ -410,4 +442,19 @@ mod tests {
         };
         assert!(recoverable_error.is_recoverable());
     }
+
+    #[test]
+    fn test_batch_error_delegates_to_source() {
+        let error = ConstellationError::BatchProcessingFailed {
+            completed: 3,
+            total: 8,
+            source: Box::new(ConstellationError::HardwareNotSupported {
+                hardware: "test".to_string(),
+            }),
+        };
+        assert_eq!(error.category(), ErrorCategory::Hardware);
+        assert_eq!(error.severity(), ErrorSeverity::Critical);
+        assert!(!error.is_recoverable());
+        assert!(error.to_string().contains("3/8"));
+    }
 }
@@ -0,0 +1,285 @@
+/*
+ * Constellation Studio - Professional Real-time Video Processing
+ * Copyright (c) 2025 MACHIKO LAB
+ *
+ * This program is free software: you can redistribute it and/or modify
+ * it under the terms of the GNU Affero General Public License as published by
+ * the Free Software Foundation, either version 3 of the License, or
+ * (at your option) any later version.
+ *
+ * This program is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+ * GNU Affero General Public License for more details.
+ *
+ * You should have received a copy of the GNU Affero General Public License
+ * along with this program. If not, see <https://www.gnu.org/licenses/>.
+ */
+
+//! ノードグラフの構造検証
+
+use crate::{ConnectionType, NodeGraph, NodeType};
+use serde::{Deserialize, Serialize};
+use std::collections::{HashMap, HashSet};
+use uuid::Uuid;
+
+/// グラフ上で検出された問題
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(tag = "kind")]
+pub enum GraphIssue {
+    /// どのノードとも接続されていない
+    OrphanNode { node_id: Uuid },
+    /// 出力・エフェクトノードの上流に映像ソース（入力ノード）がない
+    MissingRenderSource { node_id: Uuid },
+    /// 接続種別が送信元の出力または送信先の入力に含まれない
+    TypeMismatch {
+        source_id: Uuid,
+        target_id: Uuid,
+        connection_type: ConnectionType,
+    },
+    /// 循環している接続（強連結成分のノードIDをソート済みで保持）
+    Cycle { node_ids: Vec<Uuid> },
+}
+
+impl NodeGraph {
+    /// グラフ構造の問題点を列挙する（問題がなければ空）
+    ///
+    /// 型検査は `Node::ports` が設定されているノードのみを対象とする。
+    pub fn validate(&self) -> Vec<GraphIssue> {
+        let mut node_ids: Vec<Uuid> = self.nodes.keys().copied().collect();
+        node_ids.sort();
+
+        let mut issues = Vec::new();
+
+        for &node_id in &node_ids {
+            let connected = self
+                .connections
+                .iter()
+                .any(|(source, target, _)| *source == node_id || *target == node_id);
+            if !connected {
+                issues.push(GraphIssue::OrphanNode { node_id });
+            }
+        }
+
+        for &node_id in &node_ids {
+            let needs_source = matches!(
+                self.nodes[&node_id].node_type,
+                NodeType::Output(_) | NodeType::Effect(_)
+            );
+            if needs_source && !self.has_render_source(node_id) {
+                issues.push(GraphIssue::MissingRenderSource { node_id });
+            }
+        }
+
+        for (source_id, target_id, connection_type) in &self.connections {
+            let source_ok = self
+                .nodes
+                .get(source_id)
+                .and_then(|node| node.ports.as_ref())
+                .is_none_or(|ports| ports.outputs.contains(connection_type));
+            let target_ok = self
+                .nodes
+                .get(target_id)
+                .and_then(|node| node.ports.as_ref())
+                .is_none_or(|ports| ports.inputs.contains(connection_type));
+            if !source_ok || !target_ok {
+                issues.push(GraphIssue::TypeMismatch {
+                    source_id: *source_id,
+                    target_id: *target_id,
+                    connection_type: connection_type.clone(),
+                });
+            }
+        }
+
+        issues.extend(
+            self.cycles()
+                .into_iter()
+                .map(|node_ids| GraphIssue::Cycle { node_ids }),
+        );
+
+        issues
+    }
+
+    /// RenderData接続を上流に辿って入力ノードに到達できるか
+    fn has_render_source(&self, node_id: Uuid) -> bool {
+        let mut visited = HashSet::from([node_id]);
+        let mut stack = vec![node_id];
+
+        while let Some(current) = stack.pop() {
+            for (source, target, connection_type) in &self.connections {
+                if *target != current
+                    || *connection_type != ConnectionType::RenderData
+                    || !visited.insert(*source)
+                {
+                    continue;
+                }
+                if matches!(
+                    self.nodes.get(source).map(|node| &node.node_type),
+                    Some(NodeType::Input(_))
+                ) {
+                    return true;
+                }
+                stack.push(*source);
+            }
+        }
+
+        false
+    }
+
+    /// 接続種別を問わず、循環を構成するノード集合（強連結成分）を列挙する
+    fn cycles(&self) -> Vec<Vec<Uuid>> {
+        let mut reachable: HashMap<Uuid, HashSet<Uuid>> = HashMap::new();
+        for &node_id in self.nodes.keys() {
+            reachable.insert(node_id, self.reachable_from(node_id));
+        }
+
+        let mut assigned = HashSet::new();
+        let mut cycles = Vec::new();
+        let mut node_ids: Vec<Uuid> = self.nodes.keys().copied().collect();
+        node_ids.sort();
+
+        for node_id in node_ids {
+            // 自分自身に戻れるノードだけが循環に含まれる
+            if assigned.contains(&node_id) || !reachable[&node_id].contains(&node_id) {
+                continue;
+            }
+
+            let mut component: Vec<Uuid> = reachable[&node_id]
+                .iter()
+                .copied()
+                .filter(|other| reachable.get(other).is_some_and(|r| r.contains(&node_id)))
+                .collect();
+            component.sort();
+            assigned.extend(component.iter().copied());
+            cycles.push(component);
+        }
+
+        cycles
+    }
+
+    /// 1本以上の接続を辿って到達できるノード
+    fn reachable_from(&self, node_id: Uuid) -> HashSet<Uuid> {
+        let mut visited = HashSet::new();
+        let mut stack = vec![node_id];
+
+        while let Some(current) = stack.pop() {
+            for (source, target, _) in &self.connections {
+                if *source == current && visited.insert(*target) {
+                    stack.push(*target);
+                }
+            }
+        }
+
+        visited
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{EffectType, InputType, Node, NodeConfig, NodePorts, OutputType};
+
+    fn add(graph: &mut NodeGraph, node_type: NodeType) -> Uuid {
+        let id = Uuid::new_v4();
+        graph.add_node(Node::new(
+            id,
+            node_type,
+            NodeConfig {
+                parameters: HashMap::new(),
+            },
+        ));
+        id
+    }
+
+    fn render_ports() -> NodePorts {
+        NodePorts {
+            inputs: vec![ConnectionType::RenderData],
+            outputs: vec![ConnectionType::RenderData],
+        }
+    }
+
+    #[test]
+    fn test_valid_chain_has_no_issues() {
+        let mut graph = NodeGraph::new();
+        let input = add(&mut graph, NodeType::Input(InputType::TestPattern));
+        let effect = add(&mut graph, NodeType::Effect(EffectType::Blur));
+        let output = add(&mut graph, NodeType::Output(OutputType::Preview));
+        graph
+            .connect_nodes(input, effect, ConnectionType::RenderData)
+            .unwrap();
+        graph
+            .connect_nodes(effect, output, ConnectionType::RenderData)
+            .unwrap();
+
+        assert_eq!(graph.validate(), Vec::new());
+    }
+
+    #[test]
+    fn test_reports_orphan_and_missing_source() {
+        let mut graph = NodeGraph::new();
+        let orphan = add(&mut graph, NodeType::Input(InputType::TestPattern));
+        let effect = add(&mut graph, NodeType::Effect(EffectType::Blur));
+        let output = add(&mut graph, NodeType::Output(OutputType::Preview));
+        // 入力のないエフェクトから出力へ繋いでいる
+        graph
+            .connect_nodes(effect, output, ConnectionType::RenderData)
+            .unwrap();
+
+        let issues = graph.validate();
+        assert!(issues.contains(&GraphIssue::OrphanNode { node_id: orphan }));
+        assert!(issues.contains(&GraphIssue::MissingRenderSource { node_id: effect }));
+        assert!(issues.contains(&GraphIssue::MissingRenderSource { node_id: output }));
+        assert_eq!(issues.len(), 3);
+    }
+
+    #[test]
+    fn test_reports_type_mismatch_against_declared_ports() {
+        let mut graph = NodeGraph::new();
+        let input = add(&mut graph, NodeType::Input(InputType::TestPattern));
+        let output = add(&mut graph, NodeType::Output(OutputType::Preview));
+        graph.get_node_mut(&input).unwrap().ports = Some(NodePorts {
+            inputs: vec![],
+            outputs: vec![ConnectionType::RenderData],
+        });
+        graph.get_node_mut(&output).unwrap().ports = Some(render_ports());
+        graph
+            .connections
+            .push((input, output, ConnectionType::RenderData));
+        graph
+            .connections
+            .push((input, output, ConnectionType::Audio));
+
+        let issues = graph.validate();
+        assert_eq!(
+            issues,
+            vec![GraphIssue::TypeMismatch {
+                source_id: input,
+                target_id: output,
+                connection_type: ConnectionType::Audio,
+            }]
+        );
+    }
+
+    #[test]
+    fn test_reports_cycle_members() {
+        let mut graph = NodeGraph::new();
+        let input = add(&mut graph, NodeType::Input(InputType::TestPattern));
+        let a = add(&mut graph, NodeType::Effect(EffectType::Blur));
+        let b = add(&mut graph, NodeType::Effect(EffectType::Sharpen));
+        graph
+            .connect_nodes(input, a, ConnectionType::RenderData)
+            .unwrap();
+        graph
+            .connect_nodes(a, b, ConnectionType::RenderData)
+            .unwrap();
+        // connect_nodesは循環を拒否するため直接追加する
+        graph.connections.push((b, a, ConnectionType::RenderData));
+
+        let mut members = vec![a, b];
+        members.sort();
+        assert_eq!(
+            graph.validate(),
+            vec![GraphIssue::Cycle { node_ids: members }]
+        );
+    }
+}
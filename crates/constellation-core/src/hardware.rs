@@ -17,8 +17,10 @@
  */
 
 use crate::error::{ConstellationError, ConstellationResult};
+use constellation_vulkan::VulkanContext;
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
+use sysinfo::System;
 
 /// ハードウェア互換性チェックおよび要件管理システム
 pub struct HardwareCompatibilityChecker {
@@ -221,9 +223,13 @@ pub struct CompatibilityReport {
     pub critical_issues: Vec<String>,
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
 pub enum CompatibilityLevel {
+    /// プロフェッショナル要件まで満たす
+    ProfessionalSupported,
+    /// 推奨要件まで満たす
     FullySupported,
+    /// 最小要件のみ満たす
     Supported,
     PartiallySupported,
     NotSupported,
@@ -236,7 +242,10 @@ pub struct PhaseCompatibilityReport {
     pub minimum_met: bool,
     pub recommended_met: bool,
     pub professional_met: Option<bool>,
+    /// 最小要件に対する評価
     pub component_reports: HashMap<String, ComponentCompatibilityReport>,
+    /// 推奨要件に届かなかったコンポーネントの詳細
+    pub recommended_shortfalls: Vec<String>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -256,6 +265,28 @@ pub enum ComponentStatus {
     Missing,
 }
 
+impl CompatibilityLevel {
+    /// 大きいほど上位のティア
+    fn rank(&self) -> u8 {
+        match self {
+            CompatibilityLevel::ProfessionalSupported => 4,
+            CompatibilityLevel::FullySupported => 3,
+            CompatibilityLevel::Supported => 2,
+            CompatibilityLevel::PartiallySupported => 1,
+            CompatibilityLevel::NotSupported => 0,
+        }
+    }
+}
+
+impl ComponentStatus {
+    fn meets_requirement(&self) -> bool {
+        matches!(
+            self,
+            ComponentStatus::Adequate | ComponentStatus::Good | ComponentStatus::Excellent
+        )
+    }
+}
+
 impl HardwareCompatibilityChecker {
     pub fn new() -> ConstellationResult<Self> {
         let system_info = Self::detect_system_info()?;
@@ -292,25 +323,11 @@ impl HardwareCompatibilityChecker {
     fn detect_windows_system_info() -> ConstellationResult<SystemInfo> {
         // Windows固有のシステム情報取得
         // WMI、レジストリ、Win32 API等を使用
+        let system = Self::probe_system();
         Ok(SystemInfo {
-            cpu: CpuInfo {
-                model: "Unknown".to_string(),
-                vendor: "Unknown".to_string(),
-                cores: num_cpus::get() as u32,
-                threads: num_cpus::get() as u32,
-                base_frequency_mhz: 2400.0, // デフォルト値
-                boost_frequency_mhz: None,
-                architecture: std::env::consts::ARCH.to_string(),
-                features: vec![],
-            },
-            memory: MemoryInfo {
-                total_bytes: 8 * 1024 * 1024 * 1024,     // 8GB デフォルト
-                available_bytes: 4 * 1024 * 1024 * 1024, // 4GB デフォルト
-                memory_type: "DDR4".to_string(),
-                channels: 2,
-                speed_mhz: Some(3200.0),
-            },
-            gpu: vec![],
+            cpu: Self::detect_cpu_info(&system, 2400.0),
+            memory: Self::detect_memory_info(&system, "DDR4", 2, Some(3200.0)),
+            gpu: Self::detect_gpus(),
             storage: StorageInfo {
                 drives: vec![],
                 total_space: 1024 * 1024 * 1024 * 1024, // 1TB
@@ -338,25 +355,11 @@ impl HardwareCompatibilityChecker {
     fn detect_macos_system_info() -> ConstellationResult<SystemInfo> {
         // macOS固有のシステム情報取得
         // system_profiler、sysctl等を使用
+        let system = Self::probe_system();
         Ok(SystemInfo {
-            cpu: CpuInfo {
-                model: "Unknown".to_string(),
-                vendor: "Apple".to_string(),
-                cores: num_cpus::get() as u32,
-                threads: num_cpus::get() as u32,
-                base_frequency_mhz: 3000.0,
-                boost_frequency_mhz: Some(3500.0),
-                architecture: std::env::consts::ARCH.to_string(),
-                features: vec!["NEON".to_string()],
-            },
-            memory: MemoryInfo {
-                total_bytes: 16 * 1024 * 1024 * 1024,    // 16GB デフォルト
-                available_bytes: 8 * 1024 * 1024 * 1024, // 8GB デフォルト
-                memory_type: "LPDDR5".to_string(),
-                channels: 2,
-                speed_mhz: Some(6400.0),
-            },
-            gpu: vec![],
+            cpu: Self::detect_cpu_info(&system, 3000.0),
+            memory: Self::detect_memory_info(&system, "LPDDR5", 2, Some(6400.0)),
+            gpu: Self::detect_gpus(),
             storage: StorageInfo {
                 drives: vec![],
                 total_space: 512 * 1024 * 1024 * 1024, // 512GB
@@ -384,25 +387,11 @@ impl HardwareCompatibilityChecker {
     fn detect_linux_system_info() -> ConstellationResult<SystemInfo> {
         // Linux固有のシステム情報取得
         // /proc/cpuinfo, /proc/meminfo, lspci等を使用
+        let system = Self::probe_system();
         Ok(SystemInfo {
-            cpu: CpuInfo {
-                model: "Unknown".to_string(),
-                vendor: "Unknown".to_string(),
-                cores: num_cpus::get() as u32,
-                threads: num_cpus::get() as u32,
-                base_frequency_mhz: 2800.0,
-                boost_frequency_mhz: Some(4200.0),
-                architecture: std::env::consts::ARCH.to_string(),
-                features: vec!["AVX2".to_string(), "SSE4.2".to_string()],
-            },
-            memory: MemoryInfo {
-                total_bytes: 32 * 1024 * 1024 * 1024,     // 32GB デフォルト
-                available_bytes: 16 * 1024 * 1024 * 1024, // 16GB デフォルト
-                memory_type: "DDR4".to_string(),
-                channels: 4,
-                speed_mhz: Some(3200.0),
-            },
-            gpu: vec![],
+            cpu: Self::detect_cpu_info(&system, 2800.0),
+            memory: Self::detect_memory_info(&system, "DDR4", 4, Some(3200.0)),
+            gpu: Self::detect_gpus(),
             storage: StorageInfo {
                 drives: vec![],
                 total_space: 2 * 1024 * 1024 * 1024 * 1024, // 2TB
@@ -426,6 +415,121 @@ impl HardwareCompatibilityChecker {
         })
     }
 
+    /// CPU・メモリ検出用のスナップショット
+    fn probe_system() -> System {
+        let mut system = System::new();
+        system.refresh_cpu_all();
+        system.refresh_memory();
+        system
+    }
+
+    /// CPU情報の検出（プラットフォーム共通）
+    fn detect_cpu_info(system: &System, fallback_frequency_mhz: f32) -> CpuInfo {
+        let cpu = system.cpus().first();
+        let model = cpu
+            .map(|cpu| cpu.brand().trim())
+            .filter(|brand| !brand.is_empty())
+            .unwrap_or("Unknown")
+            .to_string();
+        let vendor = match cpu.map(|cpu| cpu.vendor_id().trim()).unwrap_or("") {
+            "GenuineIntel" => "Intel".to_string(),
+            "AuthenticAMD" => "AMD".to_string(),
+            "" => "Unknown".to_string(),
+            other => other.to_string(),
+        };
+        // コンテナ等では周波数が取れず0になることがある
+        let base_frequency_mhz = system
+            .cpus()
+            .iter()
+            .map(|cpu| cpu.frequency())
+            .max()
+            .filter(|&mhz| mhz > 0)
+            .map(|mhz| mhz as f32)
+            .unwrap_or(fallback_frequency_mhz);
+
+        CpuInfo {
+            model,
+            vendor,
+            cores: System::physical_core_count().unwrap_or_else(num_cpus::get_physical) as u32,
+            threads: num_cpus::get() as u32,
+            base_frequency_mhz,
+            boost_frequency_mhz: None,
+            architecture: std::env::consts::ARCH.to_string(),
+            features: Self::detect_cpu_features(),
+        }
+    }
+
+    /// メモリ情報の検出（種類・チャネル数はOSから取得できないため呼び出し側が指定）
+    fn detect_memory_info(
+        system: &System,
+        memory_type: &str,
+        channels: u32,
+        speed_mhz: Option<f32>,
+    ) -> MemoryInfo {
+        MemoryInfo {
+            total_bytes: system.total_memory(),
+            available_bytes: system.available_memory(),
+            memory_type: memory_type.to_string(),
+            channels,
+            speed_mhz,
+        }
+    }
+
+    /// 映像処理に関係する命令セット拡張の検出
+    fn detect_cpu_features() -> Vec<String> {
+        #[allow(unused_mut)]
+        let mut features: Vec<String> = Vec::new();
+
+        #[cfg(target_arch = "x86_64")]
+        {
+            if std::arch::is_x86_feature_detected!("sse4.2") {
+                features.push("SSE4.2".to_string());
+            }
+            if std::arch::is_x86_feature_detected!("avx") {
+                features.push("AVX".to_string());
+            }
+            if std::arch::is_x86_feature_detected!("avx2") {
+                features.push("AVX2".to_string());
+            }
+            if std::arch::is_x86_feature_detected!("fma") {
+                features.push("FMA".to_string());
+            }
+            if std::arch::is_x86_feature_detected!("avx512f") {
+                features.push("AVX-512".to_string());
+            }
+        }
+
+        #[cfg(target_arch = "aarch64")]
+        {
+            if std::arch::is_aarch64_feature_detected!("neon") {
+                features.push("NEON".to_string());
+            }
+        }
+
+        features
+    }
+
+    /// Vulkanから利用可能なGPUを検出（Vulkanが使えない環境では空）
+    fn detect_gpus() -> Vec<GpuInfo> {
+        VulkanContext::describe_physical_devices()
+            .into_iter()
+            .map(|device| GpuInfo {
+                vendor: vendor_name(device.vendor_id),
+                device_id: format!("{:04x}:{:04x}", device.vendor_id, device.device_id),
+                memory_bytes: device.device_local_memory_bytes,
+                driver_version: driver_version_string(device.vendor_id, device.driver_version),
+                vulkan_version: Some(format!(
+                    "{}.{}.{}",
+                    device.api_version.0, device.api_version.1, device.api_version.2
+                )),
+                opencl_version: None,
+                compute_capability: None,
+                features: device.features,
+                name: device.name,
+            })
+            .collect()
+    }
+
     /// ハードウェア要件定義をロード
     fn load_hardware_requirements() -> HardwareRequirements {
         let mut phases = HashMap::new();
@@ -669,18 +773,24 @@ impl HardwareCompatibilityChecker {
 
             if phase_report.minimum_met && !phase_report.recommended_met {
                 warnings.push(format!(
-                    "{}の推奨要件を満たしていません",
-                    phase_req.phase_name
+                    "{}の推奨要件を満たしていません: {}",
+                    phase_req.phase_name,
+                    phase_report.recommended_shortfalls.join(", ")
                 ));
             }
 
             phase_reports.insert(phase_id.clone(), phase_report);
         }
 
+        // 全フェーズが最小要件を満たす場合は、最も低いフェーズのティアを総合評価とする
         let overall_compatibility = if supported_phases.is_empty() {
             CompatibilityLevel::NotSupported
         } else if supported_phases.len() == self.requirements.phases.len() {
-            CompatibilityLevel::FullySupported
+            phase_reports
+                .values()
+                .map(|report| report.compatibility.clone())
+                .min_by_key(CompatibilityLevel::rank)
+                .unwrap_or(CompatibilityLevel::Supported)
         } else {
             CompatibilityLevel::PartiallySupported
         };
@@ -702,53 +812,31 @@ impl HardwareCompatibilityChecker {
         &self,
         phase_req: &PhaseRequirements,
     ) -> ConstellationResult<PhaseCompatibilityReport> {
-        let mut component_reports = HashMap::new();
-
-        // CPU チェック
-        let cpu_report = self.check_cpu_compatibility(&phase_req.minimum.cpu);
-        let cpu_meets_min = matches!(
-            cpu_report.status,
-            ComponentStatus::Adequate | ComponentStatus::Good | ComponentStatus::Excellent
-        );
-        component_reports.insert("cpu".to_string(), cpu_report);
-
-        // Memory チェック
-        let memory_report = self.check_memory_compatibility(&phase_req.minimum.memory);
-        let memory_meets_min = matches!(
-            memory_report.status,
-            ComponentStatus::Adequate | ComponentStatus::Good | ComponentStatus::Excellent
-        );
-        component_reports.insert("memory".to_string(), memory_report);
-
-        // GPU チェック
-        let gpu_report = self.check_gpu_compatibility(&phase_req.minimum.gpu);
-        let gpu_meets_min = matches!(
-            gpu_report.status,
-            ComponentStatus::Adequate | ComponentStatus::Good | ComponentStatus::Excellent
-        );
-        component_reports.insert("gpu".to_string(), gpu_report);
-
-        // Storage チェック
-        let storage_report = self.check_storage_compatibility(&phase_req.minimum.storage);
-        let storage_meets_min = matches!(
-            storage_report.status,
-            ComponentStatus::Adequate | ComponentStatus::Good | ComponentStatus::Excellent
-        );
-        component_reports.insert("storage".to_string(), storage_report);
-
-        let minimum_met = cpu_meets_min && memory_meets_min && gpu_meets_min && storage_meets_min;
-
-        // 推奨要件チェック（簡素化）
-        let recommended_met = minimum_met; // 実際には詳細チェックが必要
-
-        let compatibility = if minimum_met {
-            if recommended_met {
-                CompatibilityLevel::FullySupported
-            } else {
-                CompatibilityLevel::Supported
-            }
-        } else {
+        let component_reports = self.check_requirement_spec(&phase_req.minimum);
+        let minimum_met = Self::all_components_met(&component_reports);
+
+        // 上位ティアは下位ティアを満たしている場合のみ評価する
+        let recommended_reports = self.check_requirement_spec(&phase_req.recommended);
+        let recommended_met = minimum_met && Self::all_components_met(&recommended_reports);
+        let professional_met = phase_req.professional.as_ref().map(|spec| {
+            recommended_met && Self::all_components_met(&self.check_requirement_spec(spec))
+        });
+
+        let mut recommended_shortfalls: Vec<String> = recommended_reports
+            .values()
+            .filter(|report| !report.status.meets_requirement())
+            .map(|report| format!("{}: {}", report.component_name, report.details))
+            .collect();
+        recommended_shortfalls.sort();
+
+        let compatibility = if !minimum_met {
             CompatibilityLevel::NotSupported
+        } else if professional_met == Some(true) {
+            CompatibilityLevel::ProfessionalSupported
+        } else if recommended_met {
+            CompatibilityLevel::FullySupported
+        } else {
+            CompatibilityLevel::Supported
         };
 
         Ok(PhaseCompatibilityReport {
@@ -756,11 +844,37 @@ impl HardwareCompatibilityChecker {
             compatibility,
             minimum_met,
             recommended_met,
-            professional_met: None, // 簡素化のため省略
+            professional_met,
             component_reports,
+            recommended_shortfalls,
         })
     }
 
+    /// 1つの要件ティアに対してコンポーネントごとに評価
+    fn check_requirement_spec(
+        &self,
+        spec: &RequirementSpec,
+    ) -> HashMap<String, ComponentCompatibilityReport> {
+        HashMap::from([
+            ("cpu".to_string(), self.check_cpu_compatibility(&spec.cpu)),
+            (
+                "memory".to_string(),
+                self.check_memory_compatibility(&spec.memory),
+            ),
+            ("gpu".to_string(), self.check_gpu_compatibility(&spec.gpu)),
+            (
+                "storage".to_string(),
+                self.check_storage_compatibility(&spec.storage),
+            ),
+        ])
+    }
+
+    fn all_components_met(reports: &HashMap<String, ComponentCompatibilityReport>) -> bool {
+        reports
+            .values()
+            .all(|report| report.status.meets_requirement())
+    }
+
     fn check_cpu_compatibility(&self, req: &CpuRequirement) -> ComponentCompatibilityReport {
         let cpu = &self.system_info.cpu;
 
@@ -792,6 +906,24 @@ impl HardwareCompatibilityChecker {
             };
         }
 
+        let missing_features: Vec<&str> = req
+            .required_features
+            .iter()
+            .filter(|feature| !cpu.features.contains(feature))
+            .map(String::as_str)
+            .collect();
+        if !missing_features.is_empty() {
+            return ComponentCompatibilityReport {
+                component_name: "CPU".to_string(),
+                status: ComponentStatus::Insufficient,
+                details: format!("命令セット不足: {}", missing_features.join(", ")),
+                recommendations: vec![format!(
+                    "{}に対応したCPUにアップグレードしてください",
+                    missing_features.join("/")
+                )],
+            };
+        }
+
         let status = if cpu.cores >= req.min_cores * 2
             && cpu.base_frequency_mhz >= req.min_frequency_mhz * 1.5
         {
@@ -898,6 +1030,25 @@ impl HardwareCompatibilityChecker {
             }
         }
 
+        if let Some(min_version) = &req.min_vulkan_version {
+            let supported = gpu
+                .vulkan_version
+                .as_deref()
+                .is_some_and(|version| parse_version(version) >= parse_version(min_version));
+            if !supported {
+                return ComponentCompatibilityReport {
+                    component_name: "GPU".to_string(),
+                    status: ComponentStatus::Insufficient,
+                    details: format!(
+                        "Vulkanバージョン不足: {} (要求: {})",
+                        gpu.vulkan_version.as_deref().unwrap_or("未対応"),
+                        min_version
+                    ),
+                    recommendations: vec!["GPUドライバーを更新してください".to_string()],
+                };
+            }
+        }
+
         let status = if memory_gb >= req.min_memory_gb.unwrap_or(0.0) * 2.0 {
             ComponentStatus::Excellent
         } else if memory_gb >= req.min_memory_gb.unwrap_or(0.0) * 1.5 {
@@ -985,6 +1136,52 @@ impl HardwareCompatibilityChecker {
     }
 }
 
+/// "1.3.250" のようなバージョン文字列を比較可能な形に変換
+fn parse_version(version: &str) -> Vec<u32> {
+    version
+        .split('.')
+        .map(|part| part.trim().parse().unwrap_or(0))
+        .collect()
+}
+
+/// PCIベンダーIDからベンダー名を得る
+fn vendor_name(vendor_id: u32) -> String {
+    match vendor_id {
+        0x10DE => "NVIDIA".to_string(),
+        0x1002 => "AMD".to_string(),
+        0x8086 => "Intel".to_string(),
+        0x106B => "Apple".to_string(),
+        0x13B5 => "ARM".to_string(),
+        0x5143 => "Qualcomm".to_string(),
+        0x10005 => "Mesa".to_string(),
+        _ => format!("Unknown (0x{:04x})", vendor_id),
+    }
+}
+
+/// ドライバーバージョンはベンダーごとにエンコードが異なる
+fn driver_version_string(vendor_id: u32, version: u32) -> String {
+    match vendor_id {
+        // NVIDIA: 10.8.8.6 bit
+        0x10DE => format!(
+            "{}.{}.{}.{}",
+            version >> 22,
+            (version >> 14) & 0xff,
+            (version >> 6) & 0xff,
+            version & 0x3f
+        ),
+        // Windows版Intel: 18.14 bit
+        0x8086 if cfg!(target_os = "windows") => {
+            format!("{}.{}", version >> 14, version & 0x3fff)
+        }
+        _ => format!(
+            "{}.{}.{}",
+            version >> 22,
+            (version >> 12) & 0x3ff,
+            version & 0xfff
+        ),
+    }
+}
+
 impl Default for HardwareCompatibilityChecker {
     fn default() -> Self {
         Self::new().unwrap_or_else(|_| Self {
@@ -1056,4 +1253,157 @@ mod tests {
         let level = CompatibilityLevel::FullySupported;
         assert!(matches!(level, CompatibilityLevel::FullySupported));
     }
+
+    #[test]
+    fn test_driver_version_decoding() {
+        // NVIDIA 535.104.5.0
+        let nvidia = (535 << 22) | (104 << 14) | (5 << 6);
+        assert_eq!(driver_version_string(0x10DE, nvidia), "535.104.5.0");
+        assert_eq!(
+            driver_version_string(0x1002, (2 << 22) | (1 << 12) | 279),
+            "2.1.279"
+        );
+        assert_eq!(vendor_name(0x1234), "Unknown (0x1234)");
+    }
+
+    #[test]
+    fn test_gpu_detected_from_vulkan() {
+        if VulkanContext::describe_physical_devices().is_empty() {
+            return;
+        }
+
+        let mut checker = HardwareCompatibilityChecker::new().unwrap();
+        let gpus = &checker.get_system_info().gpu;
+        assert!(!gpus.is_empty());
+        assert!(gpus.iter().all(|gpu| gpu.memory_bytes > 0));
+        assert!(gpus[0].vulkan_version.is_some());
+
+        let report = checker.check_compatibility().unwrap();
+        let gpu_report = &report.phase_reports["phase1"].component_reports["gpu"];
+        assert!(!matches!(gpu_report.status, ComponentStatus::Missing));
+    }
+
+    #[test]
+    fn test_cpu_and_memory_detected() {
+        let checker = HardwareCompatibilityChecker::new().unwrap();
+        let info = checker.get_system_info();
+
+        assert!(info.memory.total_bytes > 0);
+        assert!(info.memory.available_bytes <= info.memory.total_bytes);
+        assert_eq!(info.cpu.threads, num_cpus::get() as u32);
+        assert!(info.cpu.cores >= 1);
+        assert!(info.cpu.base_frequency_mhz > 0.0);
+    }
+
+    const GB: u64 = 1024 * 1024 * 1024;
+
+    fn checker_with_system(
+        cores: u32,
+        features: &[&str],
+        memory_gb: u64,
+        vram_gb: u64,
+        vulkan_version: &str,
+    ) -> HardwareCompatibilityChecker {
+        let mut system_info = HardwareCompatibilityChecker::default().system_info;
+        system_info.cpu.cores = cores;
+        system_info.cpu.base_frequency_mhz = 3500.0;
+        system_info.cpu.features = features.iter().map(|f| f.to_string()).collect();
+        system_info.memory.total_bytes = memory_gb * GB;
+        system_info.memory.available_bytes = memory_gb * GB / 2;
+        system_info.gpu = vec![GpuInfo {
+            name: "Test GPU".to_string(),
+            vendor: "NVIDIA".to_string(),
+            device_id: "10de:0000".to_string(),
+            memory_bytes: vram_gb * GB,
+            driver_version: "1.0.0".to_string(),
+            vulkan_version: Some(vulkan_version.to_string()),
+            opencl_version: None,
+            compute_capability: None,
+            features: vec![],
+        }];
+        system_info.storage.available_space = 1024 * GB;
+
+        HardwareCompatibilityChecker {
+            system_info,
+            requirements: HardwareCompatibilityChecker::load_hardware_requirements(),
+            compatibility_report: None,
+        }
+    }
+
+    #[test]
+    fn test_minimum_but_not_recommended() {
+        let mut checker = checker_with_system(4, &[], 8, 4, "1.2.0");
+        let report = checker.check_compatibility().unwrap();
+        let phase1 = &report.phase_reports["phase1"];
+
+        assert!(phase1.minimum_met);
+        assert!(!phase1.recommended_met);
+        assert_eq!(phase1.professional_met, None);
+        assert_eq!(phase1.compatibility, CompatibilityLevel::Supported);
+        // コア数、AVX2、VRAM、Vulkan 1.3、メモリが推奨に届かない
+        assert!(phase1
+            .recommended_shortfalls
+            .iter()
+            .any(|s| s.starts_with("CPU")));
+        assert!(phase1
+            .recommended_shortfalls
+            .iter()
+            .any(|s| s.starts_with("GPU")));
+        assert!(report
+            .warnings
+            .iter()
+            .any(|w| w.contains(&phase1.phase_name)));
+    }
+
+    #[test]
+    fn test_recommended_tier_met() {
+        let mut checker = checker_with_system(16, &["AVX2"], 32, 12, "1.3.250");
+        let report = checker.check_compatibility().unwrap();
+        let phase1 = &report.phase_reports["phase1"];
+
+        assert!(phase1.minimum_met);
+        assert!(phase1.recommended_met);
+        assert!(phase1.recommended_shortfalls.is_empty());
+        assert_eq!(phase1.compatibility, CompatibilityLevel::FullySupported);
+    }
+
+    #[test]
+    fn test_professional_tier_evaluated() {
+        let mut checker = checker_with_system(16, &["AVX2"], 32, 12, "1.3.250");
+        let phase1 = checker.requirements.phases.get_mut("phase1").unwrap();
+        let mut professional = phase1.recommended.clone();
+        professional.cpu.min_cores = 12;
+        phase1.professional = Some(professional.clone());
+
+        let report = checker
+            .check_phase_compatibility(&checker.requirements.phases["phase1"])
+            .unwrap();
+        assert_eq!(report.professional_met, Some(true));
+        assert_eq!(
+            report.compatibility,
+            CompatibilityLevel::ProfessionalSupported
+        );
+
+        // プロフェッショナル要件だけ満たさない
+        professional.cpu.min_cores = 32;
+        checker
+            .requirements
+            .phases
+            .get_mut("phase1")
+            .unwrap()
+            .professional = Some(professional);
+        let report = checker
+            .check_phase_compatibility(&checker.requirements.phases["phase1"])
+            .unwrap();
+        assert!(report.recommended_met);
+        assert_eq!(report.professional_met, Some(false));
+        assert_eq!(report.compatibility, CompatibilityLevel::FullySupported);
+    }
+
+    #[test]
+    fn test_vulkan_version_comparison() {
+        assert!(parse_version("1.3.250") >= parse_version("1.3"));
+        assert!(parse_version("1.2.198") < parse_version("1.3"));
+        assert!(parse_version("1.10") > parse_version("1.9"));
+    }
 }
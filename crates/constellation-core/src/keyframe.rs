@@ -0,0 +1,271 @@
+/*
+ * Constellation Studio - Professional Real-time Video Processing
+ * Copyright (c) 2025 MACHIKO LAB
+ *
+ * This program is free software: you can redistribute it and/or modify
+ * it under the terms of the GNU Affero General Public License as published by
+ * the Free Software Foundation, either version 3 of the License, or
+ * (at your option) any later version.
+ *
+ * This program is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+ * GNU Affero General Public License for more details.
+ *
+ * You should have received a copy of the GNU Affero General Public License
+ * along with this program. If not, see <https://www.gnu.org/licenses/>.
+ */
+
+//! キーフレーム補間の評価
+
+use crate::{InterpolationType, Keyframe, ParameterValue, Vector3};
+
+impl InterpolationType {
+    /// 0-1の進行度にイージングを適用する
+    ///
+    /// `Bezier(x1, y1, x2, y2)`はCSSの`cubic-bezier()`と同じく、
+    /// (0,0)と(1,1)を端点とする2つの制御点として扱う。
+    pub fn ease(&self, t: f32) -> f32 {
+        let t = t.clamp(0.0, 1.0);
+        match *self {
+            InterpolationType::Linear => t,
+            InterpolationType::EaseIn => t * t,
+            InterpolationType::EaseOut => 1.0 - (1.0 - t).powi(2),
+            InterpolationType::EaseInOut => {
+                if t < 0.5 {
+                    2.0 * t * t
+                } else {
+                    1.0 - 2.0 * (1.0 - t).powi(2)
+                }
+            }
+            InterpolationType::Bezier(x1, y1, x2, y2) => {
+                cubic_bezier(x1.clamp(0.0, 1.0), y1, x2.clamp(0.0, 1.0), y2, t)
+            }
+        }
+    }
+}
+
+/// 指定時刻のキーフレーム値を補間して返す
+///
+/// キーフレームは時間の昇順で並んでいる前提。区間の補間には開始側キーフレームの
+/// `interpolation`を使い、最初のキーフレームより前・最後より後の時刻は端の値にクランプする。
+/// キーフレームがなければ`Float(0.0)`を返す。
+pub fn evaluate_keyframes(keyframes: &[Keyframe], time: f32) -> ParameterValue {
+    let (Some(first), Some(last)) = (keyframes.first(), keyframes.last()) else {
+        return ParameterValue::Float(0.0);
+    };
+
+    if time <= first.time {
+        return first.value.clone();
+    }
+    if time >= last.time {
+        return last.value.clone();
+    }
+
+    // timeを挟む区間を探す（両端のクランプ済みなので必ず見つかる）
+    let index = keyframes
+        .windows(2)
+        .position(|pair| time < pair[1].time)
+        .unwrap_or(keyframes.len() - 2);
+    let (before, after) = (&keyframes[index], &keyframes[index + 1]);
+
+    let span = after.time - before.time;
+    if span <= f32::EPSILON {
+        return after.value.clone();
+    }
+
+    let progress = before.interpolation.ease((time - before.time) / span);
+    interpolate_value(&before.value, &after.value, progress)
+}
+
+/// 2つの値を補間する。数値系は線形補間、補間できない型はt=1で切り替える
+fn interpolate_value(from: &ParameterValue, to: &ParameterValue, t: f32) -> ParameterValue {
+    let lerp = |a: f32, b: f32| a + (b - a) * t;
+
+    match (from, to) {
+        (ParameterValue::Float(a), ParameterValue::Float(b)) => ParameterValue::Float(lerp(*a, *b)),
+        (ParameterValue::Integer(a), ParameterValue::Integer(b)) => {
+            ParameterValue::Integer(lerp(*a as f32, *b as f32).round() as i32)
+        }
+        (ParameterValue::Vector3(a), ParameterValue::Vector3(b)) => {
+            ParameterValue::Vector3(Vector3 {
+                x: lerp(a.x, b.x),
+                y: lerp(a.y, b.y),
+                z: lerp(a.z, b.z),
+            })
+        }
+        (ParameterValue::Color(a), ParameterValue::Color(b)) => {
+            ParameterValue::Color(std::array::from_fn(|i| lerp(a[i], b[i])))
+        }
+        (ParameterValue::Array(a), ParameterValue::Array(b)) if a.len() == b.len() => {
+            ParameterValue::Array(
+                a.iter()
+                    .zip(b)
+                    .map(|(a, b)| interpolate_value(a, b, t))
+                    .collect(),
+            )
+        }
+        _ if t >= 1.0 => to.clone(),
+        _ => from.clone(),
+    }
+}
+
+/// 制御点(x1,y1),(x2,y2)の3次ベジェ曲線で、x=tとなる点のyを求める
+fn cubic_bezier(x1: f32, y1: f32, x2: f32, y2: f32, t: f32) -> f32 {
+    // B(s) = 3(1-s)^2 s p1 + 3(1-s) s^2 p2 + s^3
+    let bezier = |s: f32, p1: f32, p2: f32| {
+        let ms = 1.0 - s;
+        3.0 * ms * ms * s * p1 + 3.0 * ms * s * s * p2 + s * s * s
+    };
+    let bezier_slope = |s: f32, p1: f32, p2: f32| {
+        let ms = 1.0 - s;
+        3.0 * ms * ms * p1 + 6.0 * ms * s * (p2 - p1) + 3.0 * s * s * (1.0 - p2)
+    };
+
+    // ニュートン法で x(s) = t を解き、収束しなければ二分法にフォールバック
+    let mut s = t;
+    for _ in 0..8 {
+        let error = bezier(s, x1, x2) - t;
+        if error.abs() < 1e-6 {
+            return bezier(s, y1, y2);
+        }
+        let slope = bezier_slope(s, x1, x2);
+        if slope.abs() < 1e-6 {
+            break;
+        }
+        s -= error / slope;
+    }
+
+    let (mut low, mut high) = (0.0f32, 1.0f32);
+    s = t;
+    for _ in 0..32 {
+        let x = bezier(s, x1, x2);
+        if (x - t).abs() < 1e-6 {
+            break;
+        }
+        if x < t {
+            low = s;
+        } else {
+            high = s;
+        }
+        s = (low + high) * 0.5;
+    }
+
+    bezier(s, y1, y2)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn two_keyframes(interpolation: InterpolationType) -> Vec<Keyframe> {
+        vec![
+            Keyframe {
+                time: 1.0,
+                value: ParameterValue::Float(0.0),
+                interpolation,
+            },
+            Keyframe {
+                time: 3.0,
+                value: ParameterValue::Float(10.0),
+                interpolation: InterpolationType::Linear,
+            },
+        ]
+    }
+
+    fn sample(keyframes: &[Keyframe], time: f32) -> f32 {
+        match evaluate_keyframes(keyframes, time) {
+            ParameterValue::Float(value) => value,
+            other => panic!("Floatを期待したが {:?}", other),
+        }
+    }
+
+    fn assert_close(actual: f32, expected: f32) {
+        assert!(
+            (actual - expected).abs() < 1e-3,
+            "expected {expected}, got {actual}"
+        );
+    }
+
+    #[test]
+    fn test_clamps_outside_keyframe_range() {
+        let keyframes = two_keyframes(InterpolationType::Linear);
+        assert_close(sample(&keyframes, -5.0), 0.0);
+        assert_close(sample(&keyframes, 1.0), 0.0);
+        assert_close(sample(&keyframes, 3.0), 10.0);
+        assert_close(sample(&keyframes, 100.0), 10.0);
+    }
+
+    #[test]
+    fn test_linear() {
+        let keyframes = two_keyframes(InterpolationType::Linear);
+        assert_close(sample(&keyframes, 1.5), 2.5);
+        assert_close(sample(&keyframes, 2.0), 5.0);
+        assert_close(sample(&keyframes, 2.5), 7.5);
+    }
+
+    #[test]
+    fn test_ease_in() {
+        let keyframes = two_keyframes(InterpolationType::EaseIn);
+        assert_close(sample(&keyframes, 1.5), 0.625);
+        assert_close(sample(&keyframes, 2.0), 2.5);
+        assert_close(sample(&keyframes, 2.5), 5.625);
+    }
+
+    #[test]
+    fn test_ease_out() {
+        let keyframes = two_keyframes(InterpolationType::EaseOut);
+        assert_close(sample(&keyframes, 1.5), 4.375);
+        assert_close(sample(&keyframes, 2.0), 7.5);
+        assert_close(sample(&keyframes, 2.5), 9.375);
+    }
+
+    #[test]
+    fn test_ease_in_out() {
+        let keyframes = two_keyframes(InterpolationType::EaseInOut);
+        assert_close(sample(&keyframes, 1.5), 1.25);
+        assert_close(sample(&keyframes, 2.0), 5.0);
+        assert_close(sample(&keyframes, 2.5), 8.75);
+    }
+
+    #[test]
+    fn test_bezier() {
+        // 制御点が対角線上なら線形と一致する
+        let linear = two_keyframes(InterpolationType::Bezier(0.25, 0.25, 0.75, 0.75));
+        assert_close(sample(&linear, 1.5), 2.5);
+        assert_close(sample(&linear, 2.5), 7.5);
+
+        // CSSのease-in-out (0.42, 0, 0.58, 1) は中点対称
+        let ease = two_keyframes(InterpolationType::Bezier(0.42, 0.0, 0.58, 1.0));
+        assert_close(sample(&ease, 2.0), 5.0);
+        let early = sample(&ease, 1.5);
+        let late = sample(&ease, 2.5);
+        assert!(early < 2.5);
+        assert_close(early + late, 10.0);
+    }
+
+    #[test]
+    fn test_interpolates_vector_and_color() {
+        let keyframes = vec![
+            Keyframe {
+                time: 0.0,
+                value: ParameterValue::Color([0.0, 0.0, 0.0, 1.0]),
+                interpolation: InterpolationType::Linear,
+            },
+            Keyframe {
+                time: 2.0,
+                value: ParameterValue::Color([1.0, 0.5, 0.0, 1.0]),
+                interpolation: InterpolationType::Linear,
+            },
+        ];
+
+        match evaluate_keyframes(&keyframes, 1.0) {
+            ParameterValue::Color(color) => {
+                assert_close(color[0], 0.5);
+                assert_close(color[1], 0.25);
+                assert_close(color[3], 1.0);
+            }
+            other => panic!("Colorを期待したが {:?}", other),
+        }
+    }
+}
@@ -16,71 +16,144 @@
  * along with this program. If not, see <https://www.gnu.org/licenses/>.
  */
 
+pub mod clock;
+pub mod config;
+pub mod convert;
 pub mod error;
+pub mod graph_validation;
 pub mod hardware;
+pub mod keyframe;
+pub mod pause;
+pub mod pixel;
 pub mod resilience;
 pub mod telemetry;
-use constellation_vulkan::{MemoryManager, VulkanContext};
+pub mod validation;
+pub mod video_engine;
+pub use clock::{FrameClock, FrameClockStats, FrameOverrunPolicy, FrameTick};
+pub use config::{BackendKind, EngineConfig, FramePoolConfig};
+pub use constellation_vulkan::{FrameFormat, FrameSize, MemoryUsage, PoolStats, PooledFrameBuffer};
+use constellation_vulkan::{MemoryManager, VulkanContext, VulkanError};
 pub use error::{ConstellationError, ConstellationResult, ErrorCategory, ErrorSeverity};
+pub use graph_validation::GraphIssue;
 pub use hardware::{
     CompatibilityLevel, CompatibilityReport, HardwareCompatibilityChecker, SystemInfo,
 };
-pub use resilience::{HealthMonitor, RecoveryAction, ResilienceManager, SystemStatus};
+pub use keyframe::evaluate_keyframes;
+pub use pause::{PauseController, PauseMode};
+pub use pixel::PixelLayout;
+pub use resilience::{
+    HealthMonitor, RecoveryAction, RecoveryPolicy, ResilienceManager, RetryPolicy, SystemStatus,
+};
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
 use std::time::Duration;
-pub use telemetry::{MetricValue, SessionStats, TelemetryManager};
+pub use telemetry::{ErrorListener, MetricValue, SessionStats, TelemetryManager, TrackedError};
 use uuid::Uuid;
+pub use validation::{validate_parameter, OutOfRangePolicy};
+pub use video_engine::{EngineStatus, VideoEngine};
 
 pub struct ConstellationEngine {
     #[allow(dead_code)]
-    vulkan_context: VulkanContext,
-    #[allow(dead_code)]
-    memory_manager: MemoryManager,
+    // Vulkanバックエンドのみ。CPUバックエンドではNone
+    gpu: Option<GpuResources>,
     node_graph: NodeGraph,
     frame_processors: Vec<FrameProcessor>,
     resilience_manager: Option<ResilienceManager>,
     telemetry_manager: TelemetryManager,
     hardware_checker: HardwareCompatibilityChecker,
+    // 次に処理するフレームの連番
+    next_frame_sequence: u64,
+    // 最初のフレームを処理した時刻（表示時刻の基準）
+    stream_start: Option<std::time::Instant>,
+    pause_controller: PauseController,
+    // start/stopで切り替える稼働フラグ
+    running: bool,
+    config: EngineConfig,
+}
+
+/// Vulkanバックエンドで使うGPUリソース
+/// フィールドは宣言順に破棄されるため、メモリはデバイスより先に解放される
+struct GpuResources {
+    memory_manager: MemoryManager,
+    #[allow(dead_code)]
+    vulkan_context: VulkanContext,
+}
+
+impl GpuResources {
+    fn new(config: &EngineConfig) -> ConstellationResult<Self> {
+        let vulkan_context = match config.device_index {
+            Some(index) => VulkanContext::new_with_device_index(index),
+            None => VulkanContext::new(),
+        }
+        .map_err(map_vulkan_error)?;
+        let mut memory_manager = MemoryManager::new(&vulkan_context).map_err(map_vulkan_error)?;
+        for pool in &config.frame_pools {
+            memory_manager
+                .create_frame_pool(
+                    pool.frame_size.clone(),
+                    pool.buffer_count,
+                    pool.device_local,
+                )
+                .map_err(map_vulkan_error)?;
+        }
+
+        Ok(Self {
+            memory_manager,
+            vulkan_context,
+        })
+    }
+}
+
+fn map_vulkan_error(error: VulkanError) -> ConstellationError {
+    match error {
+        VulkanError::InitializationFailed { reason } => {
+            ConstellationError::EngineInitializationFailed { reason }
+        }
+        VulkanError::DeviceCreationFailed { reason } => {
+            ConstellationError::EngineInitializationFailed { reason }
+        }
+        VulkanError::HardwareNotSupported { hardware } => {
+            ConstellationError::HardwareNotSupported { hardware }
+        }
+        VulkanError::InsufficientMemory { required_bytes } => {
+            ConstellationError::InsufficientMemory { required_bytes }
+        }
+        VulkanError::GpuProcessingFailed { reason } => {
+            ConstellationError::GpuProcessingFailed { reason }
+        }
+    }
 }
 
 impl ConstellationEngine {
-    pub fn new() -> ConstellationResult<Self> {
-        let vulkan_context = VulkanContext::new().map_err(|e| match e {
-            constellation_vulkan::VulkanError::InitializationFailed { reason } => {
-                ConstellationError::EngineInitializationFailed { reason }
-            }
-            constellation_vulkan::VulkanError::DeviceCreationFailed { reason } => {
-                ConstellationError::EngineInitializationFailed { reason }
-            }
-            constellation_vulkan::VulkanError::HardwareNotSupported { hardware } => {
-                ConstellationError::HardwareNotSupported { hardware }
-            }
-            constellation_vulkan::VulkanError::InsufficientMemory { required_bytes } => {
-                ConstellationError::InsufficientMemory { required_bytes }
-            }
-            constellation_vulkan::VulkanError::GpuProcessingFailed { reason } => {
-                ConstellationError::GpuProcessingFailed { reason }
-            }
-        })?;
-        let memory_manager = MemoryManager::new(&vulkan_context).map_err(|e| match e {
-            constellation_vulkan::VulkanError::InitializationFailed { reason } => {
-                ConstellationError::EngineInitializationFailed { reason }
-            }
-            constellation_vulkan::VulkanError::DeviceCreationFailed { reason } => {
-                ConstellationError::EngineInitializationFailed { reason }
-            }
-            constellation_vulkan::VulkanError::HardwareNotSupported { hardware } => {
-                ConstellationError::HardwareNotSupported { hardware }
-            }
-            constellation_vulkan::VulkanError::InsufficientMemory { required_bytes } => {
-                ConstellationError::InsufficientMemory { required_bytes }
-            }
-            constellation_vulkan::VulkanError::GpuProcessingFailed { reason } => {
-                ConstellationError::GpuProcessingFailed { reason }
-            }
-        })?;
-        let node_graph = NodeGraph::new();
+    /// エンジンを初期化する
+    /// `device_index` を指定すると `VulkanContext::list_physical_devices` の順序でGPUを固定する
+    pub fn new(device_index: Option<usize>) -> ConstellationResult<Self> {
+        Self::with_config(EngineConfig {
+            device_index,
+            ..EngineConfig::default()
+        })
+    }
+
+    /// 設定を指定してエンジンを初期化する
+    pub fn with_config(config: EngineConfig) -> ConstellationResult<Self> {
+        config
+            .validate()
+            .map_err(|reason| ConstellationError::ConfigurationError { reason })?;
+
+        // バックエンド未指定ならVulkanを試し、使えなければCPUで動かす
+        let gpu = match config.backend {
+            Some(BackendKind::Cpu) => None,
+            Some(BackendKind::Vulkan) => Some(GpuResources::new(&config)?),
+            None => match GpuResources::new(&config) {
+                Ok(gpu) => Some(gpu),
+                Err(e) => {
+                    tracing::warn!("Vulkan unavailable, falling back to CPU backend: {}", e);
+                    None
+                }
+            },
+        };
+        let mut node_graph = NodeGraph::new();
+        node_graph.set_parameter_policy(config.parameter_policy);
         let frame_processors = Vec::new();
 
         // ハードウェア互換性チェック
@@ -94,54 +167,293 @@ impl ConstellationEngine {
             "Hardware compatibility checked"
         );
 
+        // GPU要件はVulkanバックエンドにのみ適用する
         if matches!(
             compatibility_report.overall_compatibility,
             CompatibilityLevel::NotSupported
         ) {
-            return Err(ConstellationError::HardwareNotSupported {
-                hardware: "System does not meet minimum requirements for any phase".to_string(),
-            });
+            if gpu.is_some() {
+                return Err(ConstellationError::HardwareNotSupported {
+                    hardware: "System does not meet minimum requirements for any phase".to_string(),
+                });
+            }
+            tracing::warn!("Hardware does not meet GPU requirements, running on CPU backend");
         }
 
         Ok(Self {
-            vulkan_context,
-            memory_manager,
+            gpu,
             node_graph,
             frame_processors,
-            resilience_manager: None, // 後で初期化
-            telemetry_manager: TelemetryManager::new(),
+            resilience_manager: config.enable_resilience.then(ResilienceManager::new),
+            telemetry_manager: TelemetryManager::new()
+                .with_trace_sample_rate(config.telemetry_sample_rate),
             hardware_checker,
+            next_frame_sequence: 1,
+            stream_start: None,
+            pause_controller: PauseController::default(),
+            running: false,
+            config,
         })
     }
 
+    /// 起動時の設定
+    pub fn config(&self) -> &EngineConfig {
+        &self.config
+    }
+
+    /// 実際に使われているバックエンド
+    pub fn backend(&self) -> BackendKind {
+        if self.gpu.is_some() {
+            BackendKind::Vulkan
+        } else {
+            BackendKind::Cpu
+        }
+    }
+
+    fn gpu_mut(&mut self) -> ConstellationResult<&mut GpuResources> {
+        self.gpu
+            .as_mut()
+            .ok_or_else(|| ConstellationError::HardwareNotSupported {
+                hardware: "Frame pools require the Vulkan backend".to_string(),
+            })
+    }
+
+    /// グラフの各ノードが出力するフレームサイズごとにフレームプールを事前確保する
+    /// バッファ数は `EngineConfig::frame_pool_buffer_count`。確保済みのサイズはそのまま使う
+    /// CPUバックエンドではフレームを通常のメモリで扱うため何も確保しない
+    pub fn prepare_pools(&mut self, graph: &NodeGraph) -> ConstellationResult<Vec<FrameSize>> {
+        let buffer_count = self.config.frame_pool_buffer_count;
+        let Some(gpu) = self.gpu.as_mut() else {
+            tracing::debug!("CPU backend has no frame pools to prepare");
+            return Ok(Vec::new());
+        };
+
+        let sizes = graph.frame_sizes();
+        for frame_size in &sizes {
+            gpu.memory_manager
+                .create_frame_pool(frame_size.clone(), buffer_count, false)
+                .map_err(map_vulkan_error)?;
+        }
+
+        tracing::info!(
+            pools = sizes.len(),
+            buffer_count = buffer_count,
+            "Prepared frame pools for graph"
+        );
+        Ok(sizes)
+    }
+
+    /// 事前確保したプールからフレームバッファを取得する
+    pub fn acquire_frame_buffer(
+        &mut self,
+        frame_size: &FrameSize,
+    ) -> ConstellationResult<PooledFrameBuffer> {
+        self.gpu_mut()?
+            .memory_manager
+            .acquire_frame_buffer(frame_size)
+            .map_err(map_vulkan_error)
+    }
+
+    /// フレームバッファをプールに返す
+    pub fn release_frame_buffer(&mut self, frame_buffer: PooledFrameBuffer) {
+        if let Some(gpu) = self.gpu.as_mut() {
+            gpu.memory_manager.release_frame_buffer(frame_buffer);
+        }
+    }
+
+    /// フレームプールを含むGPUメモリの使用状況（CPUバックエンドではNone）
+    pub fn memory_usage(&self) -> Option<MemoryUsage> {
+        self.gpu
+            .as_ref()
+            .map(|gpu| gpu.memory_manager.get_memory_usage())
+    }
+
+    /// フレームプールの使用状況（未確保のサイズやCPUバックエンドならNone）
+    pub fn frame_pool_stats(&self, frame_size: &FrameSize) -> Option<PoolStats> {
+        self.gpu
+            .as_ref()
+            .and_then(|gpu| gpu.memory_manager.pool_stats(frame_size))
+    }
+
     /// レジリエンス機能を有効化
     pub fn enable_resilience(&mut self) -> ConstellationResult<()> {
-        let engine_ref = std::sync::Arc::new(unsafe {
-            // 注意: これは安全でない操作です。本来は適切な設計でArcを共有する必要があります
-            std::ptr::read(self as *const Self)
-        });
-        self.resilience_manager = Some(ResilienceManager::new(engine_ref));
+        self.resilience_manager = Some(ResilienceManager::new());
         Ok(())
     }
 
+    pub fn is_resilience_enabled(&self) -> bool {
+        self.resilience_manager.is_some()
+    }
+
+    /// 処理を一時停止する
+    /// 停止中の`process_frame`は最後の出力を返し、`PauseMode::Buffer`なら入力を溜める
+    pub fn pause(&mut self) {
+        self.pause_controller.pause();
+    }
+
+    /// 処理を再開し、停止中に溜めたフレームを到着順に処理した結果を返す
+    pub fn resume(&mut self) -> ConstellationResult<Vec<FrameData>> {
+        let buffered = self.pause_controller.resume();
+        if buffered.is_empty() {
+            return Ok(Vec::new());
+        }
+        self.process_frames(&buffered)
+    }
+
+    pub fn is_paused(&self) -> bool {
+        self.pause_controller.is_paused()
+    }
+
+    pub fn pause_mode(&self) -> PauseMode {
+        self.pause_controller.mode()
+    }
+
+    pub fn set_pause_mode(&mut self, mode: PauseMode) {
+        self.pause_controller.set_mode(mode);
+    }
+
+    /// 停止中に溜めるフレーム数の上限。超えた分は古いものから捨てる
+    pub fn set_pause_buffer_capacity(&mut self, capacity: usize) {
+        self.pause_controller.set_capacity(capacity);
+    }
+
+    /// 停止中に溜めているフレーム数
+    pub fn paused_frame_count(&self) -> usize {
+        self.pause_controller.buffered_frames()
+    }
+
     pub fn process_frame(&mut self, input: &FrameData) -> ConstellationResult<FrameData> {
+        if self.pause_controller.is_paused() {
+            return Ok(self.pause_controller.hold(input));
+        }
+
         let frame_id = Uuid::new_v4();
         let _frame_span = self.telemetry_manager.start_frame_processing(frame_id);
 
+        let start_time = std::time::Instant::now();
+        let frame = Self::process_frame_untraced(
+            &mut self.frame_processors,
+            &mut self.resilience_manager,
+            &self.telemetry_manager,
+            &mut self.next_frame_sequence,
+            &mut self.stream_start,
+            input,
+        )?;
+
+        // パフォーマンス監視とメトリクス記録
+        self.record_processed_frames(1, start_time.elapsed());
+        self.pause_controller.record_output(&frame);
+        Ok(frame)
+    }
+
+    /// 複数フレームをまとめて処理する
+    /// スパンとフレーム統計の記録をバッチ全体で1回にまとめ、エラー処理と復旧はフレームごとに行う。
+    /// 復旧できないエラーが発生した時点で中断し、それまでに処理できた枚数をエラーに含める
+    pub fn process_frames(&mut self, inputs: &[FrameData]) -> ConstellationResult<Vec<FrameData>> {
+        if self.pause_controller.is_paused() {
+            return Ok(inputs
+                .iter()
+                .map(|input| self.pause_controller.hold(input))
+                .collect());
+        }
+
+        let batch_id = Uuid::new_v4();
+        let batch_span = self.telemetry_manager.start_frame_processing(batch_id);
+        batch_span.add_event(
+            "frame_batch".to_string(),
+            [("frame_count".to_string(), serde_json::json!(inputs.len()))]
+                .into_iter()
+                .collect(),
+        );
+
+        let start_time = std::time::Instant::now();
+        let mut outputs = Vec::with_capacity(inputs.len());
+        for input in inputs {
+            let result = Self::process_frame_untraced(
+                &mut self.frame_processors,
+                &mut self.resilience_manager,
+                &self.telemetry_manager,
+                &mut self.next_frame_sequence,
+                &mut self.stream_start,
+                input,
+            );
+
+            match result {
+                Ok(frame) => outputs.push(frame),
+                Err(error) => {
+                    let completed = outputs.len();
+                    self.record_processed_frames(completed as u64, start_time.elapsed());
+                    return Err(ConstellationError::BatchProcessingFailed {
+                        completed,
+                        total: inputs.len(),
+                        source: Box::new(error),
+                    });
+                }
+            }
+        }
+
+        self.record_processed_frames(outputs.len() as u64, start_time.elapsed());
+        if let Some(last) = outputs.last() {
+            self.pause_controller.record_output(last);
+        }
+        Ok(outputs)
+    }
+
+    fn record_processed_frames(&self, count: u64, processing_time: Duration) {
+        // テレメトリにフレーム統計を記録
+        self.telemetry_manager
+            .metrics_collector
+            .frame_count
+            .fetch_add(count, std::sync::atomic::Ordering::Relaxed);
+        self.telemetry_manager
+            .metrics_collector
+            .total_processing_time
+            .fetch_add(
+                processing_time.as_micros() as u64,
+                std::sync::atomic::Ordering::Relaxed,
+            );
+    }
+
+    /// スパンとフレーム統計を除いた1フレーム分の処理
+    /// スパンのガードが`telemetry_manager`を借用したまま呼べるよう、フィールドを個別に受け取る
+    fn process_frame_untraced(
+        frame_processors: &mut [FrameProcessor],
+        resilience_manager: &mut Option<ResilienceManager>,
+        telemetry_manager: &TelemetryManager,
+        next_frame_sequence: &mut u64,
+        stream_start: &mut Option<std::time::Instant>,
+        input: &FrameData,
+    ) -> ConstellationResult<FrameData> {
         let start_time = std::time::Instant::now();
         let mut current_frame = input.clone();
 
-        for processor in &mut self.frame_processors {
+        // 連番は処理開始時に割り当てる。処理に失敗したフレームは欠番となりドロップとして検出される
+        // 入力側で連番が設定されていればそれを引き継ぎ、ソースでの欠番もドロップとして数える
+        let sequence = if input.sequence == 0 {
+            *next_frame_sequence
+        } else {
+            input.sequence
+        };
+        *next_frame_sequence = sequence + 1;
+        let stream_start = *stream_start.get_or_insert(start_time);
+        // 入力側で表示時刻が設定されていればそれを優先する
+        let timestamp = if input.timestamp.is_zero() {
+            start_time.duration_since(stream_start)
+        } else {
+            input.timestamp
+        };
+
+        for processor in frame_processors.iter_mut() {
             match processor.process(&current_frame) {
                 Ok(frame) => {
                     current_frame = frame;
                 }
                 Err(error) => {
                     // エラーをテレメトリに記録
-                    self.telemetry_manager.record_error(&error, None);
+                    telemetry_manager.record_error(&error, Some(processor.node_id));
 
                     // エラーハンドリングと復旧処理
-                    if let Some(ref mut resilience_manager) = self.resilience_manager {
+                    if let Some(resilience_manager) = resilience_manager.as_mut() {
                         match resilience_manager.handle_error(&error) {
                             Ok(RecoveryAction::Retry {
                                 max_attempts,
@@ -191,6 +503,10 @@ impl ConstellationEngine {
                                     FrameProcessor::new(Uuid::new_v4(), fallback_processor);
                                 current_frame = fallback.process(&current_frame)?;
                             }
+                            Ok(RecoveryAction::FailFast) => {
+                                // 再試行しても解決しないエラーはそのまま返す
+                                return Err(error);
+                            }
                             Ok(RecoveryAction::GracefulShutdown { .. }) => {
                                 // システム停止
                                 return Err(ConstellationError::EngineNotRunning);
@@ -212,30 +528,83 @@ impl ConstellationEngine {
             }
         }
 
-        // パフォーマンス監視とメトリクス記録
-        let processing_time = start_time.elapsed();
-
-        // テレメトリにフレーム統計を記録
-        self.telemetry_manager
-            .metrics_collector
-            .frame_count
-            .fetch_add(1, std::sync::atomic::Ordering::Relaxed);
-        self.telemetry_manager
-            .metrics_collector
-            .total_processing_time
-            .fetch_add(
-                processing_time.as_micros() as u64,
-                std::sync::atomic::Ordering::Relaxed,
-            );
+        current_frame.sequence = sequence;
+        current_frame.timestamp = timestamp;
+        telemetry_manager.record_frame_sequence(sequence);
 
         // レジリエンス監視
-        if let Some(ref mut resilience_manager) = self.resilience_manager {
-            resilience_manager.monitor_performance(&current_frame, processing_time);
+        if let Some(resilience_manager) = resilience_manager.as_mut() {
+            resilience_manager.monitor_performance(&current_frame, start_time.elapsed());
         }
 
         Ok(current_frame)
     }
 
+    /// 目標フレームレートで`source_fn`から取得したフレームを処理し、`sink_fn`へ渡す
+    /// `source_fn`がNoneを返すと終了する。予算超過時は過ぎた枠を捨てる
+    pub fn run_at_fps<S, K>(
+        &mut self,
+        target_fps: f64,
+        source_fn: S,
+        sink_fn: K,
+    ) -> ConstellationResult<FrameClockStats>
+    where
+        S: FnMut() -> Option<FrameData>,
+        K: FnMut(FrameData),
+    {
+        let mut clock = FrameClock::new(target_fps)?;
+        self.run_with_clock(&mut clock, source_fn, sink_fn)
+    }
+
+    /// 任意のクロック（超過時の扱いを変えたい場合など）でループを実行する
+    pub fn run_with_clock<S, K>(
+        &mut self,
+        clock: &mut FrameClock,
+        mut source_fn: S,
+        mut sink_fn: K,
+    ) -> ConstellationResult<FrameClockStats>
+    where
+        S: FnMut() -> Option<FrameData>,
+        K: FnMut(FrameData),
+    {
+        let target_ms = clock.frame_duration().as_secs_f64() * 1000.0;
+        self.telemetry_manager.record_metric(
+            "frame_clock.target_interval_ms".to_string(),
+            MetricValue::Gauge(target_ms),
+        );
+
+        // ソースを先に確認し、終了時に余分な枠を待たない・数えない
+        while let Some(input) = source_fn() {
+            let tick = clock.tick();
+            self.telemetry_manager.record_dropped_frames(tick.skipped);
+
+            let processing_start = std::time::Instant::now();
+            let output = self.process_frame(&input)?;
+            let processing_time = processing_start.elapsed();
+            sink_fn(output);
+
+            if tick.slot > 0 {
+                self.telemetry_manager.record_metric(
+                    "frame_clock.actual_interval_ms".to_string(),
+                    MetricValue::Gauge(tick.interval.as_secs_f64() * 1000.0),
+                );
+            }
+            self.telemetry_manager.record_metric(
+                "frame_clock.lateness_ms".to_string(),
+                MetricValue::Gauge(tick.lateness().as_secs_f64() * 1000.0),
+            );
+            if processing_time > clock.frame_duration() {
+                tracing::warn!(
+                    processing_ms = processing_time.as_secs_f64() * 1000.0,
+                    target_ms = target_ms,
+                    "Frame processing exceeded frame budget"
+                );
+            }
+        }
+
+        Ok(clock.stats().clone())
+    }
+
     pub fn add_node(
         &mut self,
         node_type: NodeType,
@@ -247,6 +616,10 @@ impl ConstellationEngine {
         Ok(node_id)
     }
 
+    pub fn remove_node(&mut self, node_id: Uuid) -> ConstellationResult<()> {
+        self.node_graph.remove_node(&node_id)
+    }
+
     pub fn connect_nodes(
         &mut self,
         source_id: Uuid,
@@ -257,6 +630,81 @@ impl ConstellationEngine {
             .connect_nodes(source_id, target_id, connection_type)
     }
 
+    pub fn disconnect_nodes(
+        &mut self,
+        source_id: Uuid,
+        target_id: Uuid,
+        connection_type: ConnectionType,
+    ) -> ConstellationResult<()> {
+        self.node_graph
+            .disconnect_nodes(source_id, target_id, connection_type)
+    }
+
+    /// ノードを複製して新しいIDを返す（接続は複製しない）
+    pub fn duplicate_node(&mut self, node_id: Uuid) -> ConstellationResult<Uuid> {
+        self.node_graph.duplicate_node(&node_id)
+    }
+
+    /// グラフ上のノードを取得する
+    pub fn get_node(&self, node_id: Uuid) -> Option<&Node> {
+        self.node_graph.get_node(&node_id)
+    }
+
+    /// ノード実装が宣言する入出力の接続種別を登録する
+    pub fn set_node_ports(&mut self, node_id: Uuid, ports: NodePorts) -> ConstellationResult<()> {
+        let node = self
+            .node_graph
+            .get_node_mut(&node_id)
+            .ok_or(ConstellationError::NodeNotFound { node_id })?;
+        node.ports = Some(ports);
+        Ok(())
+    }
+
+    /// グラフ構造の問題点を列挙する
+    pub fn validate_graph(&self) -> Vec<GraphIssue> {
+        self.node_graph.validate()
+    }
+
+    /// ノード処理の失敗を発生元ノード付きで記録する（エラーリスナーにも通知される）
+    pub fn report_node_error(&self, node_id: Uuid, error: &ConstellationError) {
+        self.telemetry_manager.record_error(error, Some(node_id));
+    }
+
+    /// 処理中のエラー（発生元ノード付き）を受け取るリスナーを登録する
+    pub fn add_error_listener(&self, listener: ErrorListener) {
+        self.telemetry_manager.add_error_listener(listener);
+    }
+
+    /// エンジンを停止して最終的なセッション統計を返す
+    ///
+    /// 停止中に溜めたフレームを破棄し、フレームプロセッサを止め、
+    /// テレメトリのファイル出力を書き出して終了し、フレームプールを解放する。
+    /// ノード側の後始末は `PipelineProcessor::stop` が `NodeProcessor::on_stop` で行う。
+    pub fn shutdown(mut self) -> ConstellationResult<SessionStats> {
+        let discarded = self.pause_controller.resume().len();
+        if discarded > 0 {
+            tracing::info!("Discarding {} paused frames on shutdown", discarded);
+        }
+
+        self.frame_processors.clear();
+
+        // 統計はフラッシュ前に確定させる（以降のフレームは存在しない）
+        let stats = self.telemetry_manager.get_session_stats();
+        self.telemetry_manager.stop_file_export()?;
+
+        if let Some(gpu) = self.gpu.as_mut() {
+            gpu.memory_manager.release_frame_pools();
+        }
+
+        tracing::info!(
+            frame_count = stats.frame_count,
+            error_count = stats.error_count,
+            dropped_frames = stats.dropped_frames,
+            "Engine shut down"
+        );
+        Ok(stats)
+    }
+
     /// セッション統計の取得
     pub fn get_session_stats(&self) -> SessionStats {
         self.telemetry_manager.get_session_stats()
@@ -273,6 +721,11 @@ impl ConstellationEngine {
             .record_system_state(cpu_usage, memory_usage, gpu_usage);
     }
 
+    /// 直近に記録されたGPU使用率
+    pub fn gpu_utilization(&self) -> Option<f32> {
+        self.telemetry_manager.gpu_utilization()
+    }
+
     /// ログの書き出し（JSON形式）
     pub fn export_logs_json(&self) -> serde_json::Result<String> {
         self.telemetry_manager.export_logs_json()
@@ -320,6 +773,62 @@ pub struct FrameData {
     pub control_data: Option<ControlData>,
     // Tally自動伝播用メタデータ
     pub tally_metadata: TallyMetadata,
+    // 表示時刻（プレゼンテーションタイム）
+    pub timestamp: Duration,
+    // フレーム連番（ドロップ検出用）。ソースは1から数え、0は未設定としてエンジンが割り当てる
+    pub sequence: u64,
+    // 映像バッファのCRC32（デバッグ用の破損検出。無効時はNone）
+    pub checksum: Option<u32>,
+}
+
+impl FrameData {
+    /// 何も運ばない空のフレーム（時刻0・連番未設定・Tallyなし）
+    pub fn empty() -> Self {
+        Self {
+            render_data: None,
+            audio_data: None,
+            control_data: None,
+            tally_metadata: TallyMetadata::new(),
+            timestamp: Duration::ZERO,
+            sequence: 0,
+            checksum: None,
+        }
+    }
+
+    /// 映像（Raster2D）だけを運ぶフレーム
+    pub fn from_raster(frame: VideoFrame) -> Self {
+        Self {
+            render_data: Some(RenderData::Raster2D(frame)),
+            ..Self::empty()
+        }
+    }
+
+    /// 映像バッファ（Raster2D）のCRC32。映像を持たないフレームはNone
+    pub fn compute_checksum(&self) -> Option<u32> {
+        let Some(RenderData::Raster2D(frame)) = &self.render_data else {
+            return None;
+        };
+        let mut hasher = crc32fast::Hasher::new();
+        hasher.update(&frame.width.to_le_bytes());
+        hasher.update(&frame.height.to_le_bytes());
+        hasher.update(&frame.data);
+        Some(hasher.finalize())
+    }
+
+    /// 現在の映像バッファのCRC32を `checksum` に記録する
+    pub fn stamp_checksum(&mut self) {
+        self.checksum = self.compute_checksum();
+    }
+
+    /// 記録済みのCRC32と現在の映像バッファを照合する
+    ///
+    /// 一致しない場合は (記録値, 実際の値) を返す。未記録のフレームは常にNone。
+    /// 映像が失われた場合の実際の値は0とする。
+    pub fn checksum_mismatch(&self) -> Option<(u32, u32)> {
+        let expected = self.checksum?;
+        let actual = self.compute_checksum().unwrap_or(0);
+        (expected != actual).then_some((expected, actual))
+    }
 }
 
 #[derive(Debug, Clone)]
@@ -569,12 +1078,89 @@ impl AudioLevel {
                     }
                 }
             }
-            UnifiedAudioData::Spatial { .. } => {
-                // For spatial audio, return silence levels for now
-                // TODO: Implement spatial audio level calculation
-                Self::new()
+            UnifiedAudioData::Spatial {
+                sources, listener, ..
+            } => {
+                let timestamp = std::time::SystemTime::now()
+                    .duration_since(std::time::UNIX_EPOCH)
+                    .unwrap_or_default()
+                    .as_millis() as u64;
+
+                // Mix all sources down to stereo relative to the listener
+                let (left_mix, right_mix) = Self::mixdown_spatial(sources, listener);
+                let (peak_left, rms_left) = Self::calculate_peak_rms(&left_mix);
+                let (peak_right, rms_right) = Self::calculate_peak_rms(&right_mix);
+
+                Self {
+                    peak_left,
+                    peak_right,
+                    rms_left,
+                    rms_right,
+                    db_peak_left: Self::linear_to_db(peak_left),
+                    db_peak_right: Self::linear_to_db(peak_right),
+                    db_rms_left: Self::linear_to_db(rms_left),
+                    db_rms_right: Self::linear_to_db(rms_right),
+                    is_clipping: peak_left >= 1.0 || peak_right >= 1.0,
+                    timestamp,
+                }
+            }
+        }
+    }
+
+    /// Simple distance-based stereo mixdown of spatial sources
+    /// Gain: attenuation / distance (clamped to 1 unit), equal-power panning
+    fn mixdown_spatial(
+        sources: &[SpatialAudioSource],
+        listener: &AudioListener,
+    ) -> (Vec<f32>, Vec<f32>) {
+        let frames = sources
+            .iter()
+            .map(|source| source.audio_data.len())
+            .max()
+            .unwrap_or(0);
+        let mut left = vec![0.0f32; frames];
+        let mut right = vec![0.0f32; frames];
+
+        // Listener right vector = forward x up
+        let forward = &listener.orientation;
+        let up = &listener.up;
+        let right_axis = (
+            forward.y * up.z - forward.z * up.y,
+            forward.z * up.x - forward.x * up.z,
+            forward.x * up.y - forward.y * up.x,
+        );
+        let right_len = (right_axis.0 * right_axis.0
+            + right_axis.1 * right_axis.1
+            + right_axis.2 * right_axis.2)
+            .sqrt();
+
+        for source in sources {
+            let offset = (
+                source.position.x - listener.position.x,
+                source.position.y - listener.position.y,
+                source.position.z - listener.position.z,
+            );
+            let distance = (offset.0 * offset.0 + offset.1 * offset.1 + offset.2 * offset.2).sqrt();
+            let gain = source.attenuation / distance.max(1.0);
+
+            // -1.0 (left) .. 1.0 (right)
+            let pan = if distance > f32::EPSILON && right_len > f32::EPSILON {
+                ((offset.0 * right_axis.0 + offset.1 * right_axis.1 + offset.2 * right_axis.2)
+                    / (distance * right_len))
+                    .clamp(-1.0, 1.0)
+            } else {
+                0.0
+            };
+            let angle = (pan + 1.0) * std::f32::consts::FRAC_PI_4;
+            let (left_gain, right_gain) = (gain * angle.cos(), gain * angle.sin());
+
+            for (i, &sample) in source.audio_data.iter().enumerate() {
+                left[i] += sample * left_gain;
+                right[i] += sample * right_gain;
             }
         }
+
+        (left, right)
     }
 
     /// Calculate peak and RMS values for a slice of samples
@@ -733,14 +1319,18 @@ pub enum ControlData {
     },
 
     // 3D変換制御
+    // target_node_id がNoneならControl線で受け取ったノード自身が適用する。
+    // 指定した場合はパイプラインが対象ノードの position / rotation / scale パラメータに書き込む
     Transform {
+        target_node_id: Option<Uuid>,
         position: Option<Vector3>,
         rotation: Option<Quaternion>,
         scale: Option<Vector3>,
     },
 
-    // カメラ制御
+    // カメラ制御（target_node_id の扱いは Transform と同じ）
     Camera {
+        target_node_id: Option<Uuid>,
         position: Option<Vector3>,
         target: Option<Vector3>,
         fov: Option<f32>,
@@ -748,8 +1338,10 @@ pub enum ControlData {
         far: Option<f32>,
     },
 
-    // アニメーション制御
+    // アニメーション制御（time 時点のキーフレーム値を対象ノードの parameter_name に書き込む）
     Animation {
+        target_node_id: Option<Uuid>,
+        parameter_name: String,
         keyframes: Vec<Keyframe>,
         time: f32,
         interpolation: InterpolationType,
@@ -999,6 +1591,8 @@ pub enum VideoFormat {
     Rgb8,
     Bgra8,
     Bgr8,
+    /// チャンネルごと16bit（リトルエンディアン）のRGBA。HDR/10bit素材を劣化なしで通す
+    Rgba16,
     Yuv420p,
     Jpeg,
     Png,
@@ -1027,6 +1621,10 @@ pub enum InputType {
 pub enum OutputType {
     VirtualWebcam,
     Preview,
+    Recorder,
+    Scope, // ヒストグラム・波形の解析出力
+    Ndi,   // NDIネットワーク出力（`ndi` フィーチャー）
+    Srt,   // SRTストリーミング出力（`srt` フィーチャー）
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
@@ -1034,8 +1632,13 @@ pub enum EffectType {
     ColorCorrection,
     Blur,
     Sharpen,
+    Grayscale,
     Transform,
     Composite,
+    Deinterlace,
+    ChromaKey,
+    Transition,
+    Lut,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
@@ -1044,6 +1647,8 @@ pub enum AudioType {
     Mixer,
     Effect,
     Output,
+    FileInput,
+    FileOutput,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
@@ -1092,6 +1697,10 @@ pub struct Node {
     pub config: NodeConfig,
     pub inputs: Vec<Connection>,
     pub outputs: Vec<Connection>,
+    /// ノード実装が宣言する入出力の接続種別（未設定なら型検査しない）
+    pub ports: Option<NodePorts>,
+    /// ノード実装が宣言するパラメータ定義（未設定なら値を検証しない）
+    pub parameter_definitions: Option<HashMap<String, ParameterDefinition>>,
 }
 
 impl Node {
@@ -1102,32 +1711,146 @@ impl Node {
             config,
             inputs: Vec::new(),
             outputs: Vec::new(),
+            ports: None,
+            parameter_definitions: None,
         }
     }
-}
-
-#[derive(Debug, Clone)]
-pub struct Connection {
-    pub connection_type: ConnectionType,
-    pub connected_node: Option<Uuid>,
-}
 
-pub struct NodeGraph {
-    nodes: HashMap<Uuid, Node>,
-    connections: Vec<(Uuid, Uuid, ConnectionType)>,
-}
+    pub fn with_ports(mut self, ports: NodePorts) -> Self {
+        self.ports = Some(ports);
+        self
+    }
 
-impl Default for NodeGraph {
-    fn default() -> Self {
-        Self::new()
+    pub fn with_parameter_definitions(
+        mut self,
+        definitions: HashMap<String, ParameterDefinition>,
+    ) -> Self {
+        self.parameter_definitions = Some(definitions);
+        self
     }
-}
 
-impl NodeGraph {
-    pub fn new() -> Self {
-        Self {
+    /// 設定パラメータから映像出力のフレームサイズを読み取る
+    /// `width`/`height` または `resolution`（"1920x1080"形式）を持つ映像系ノードのみ対象で、
+    /// 設定に値がなければパラメータ定義の既定値を使う。出力フォーマットはRGBA8とみなす
+    pub fn output_frame_size(&self) -> Option<FrameSize> {
+        if matches!(
+            self.node_type,
+            NodeType::Audio(_) | NodeType::Tally(_) | NodeType::Control(_)
+        ) {
+            return None;
+        }
+
+        let parameter = |key: &str| {
+            self.config.parameters.get(key).or_else(|| {
+                self.parameter_definitions
+                    .as_ref()?
+                    .get(key)
+                    .map(|definition| &definition.default_value)
+            })
+        };
+        let dimension = |key: &str| {
+            parameter(key)
+                .and_then(|v| v.as_u64())
+                .and_then(|v| u32::try_from(v).ok())
+        };
+        let (width, height) = match (dimension("width"), dimension("height")) {
+            (Some(width), Some(height)) => (width, height),
+            _ => {
+                let resolution = parameter("resolution")?.as_str()?;
+                let (width, height) = resolution.split_once('x')?;
+                (width.trim().parse().ok()?, height.trim().parse().ok()?)
+            }
+        };
+        if width == 0 || height == 0 {
+            return None;
+        }
+
+        Some(FrameSize {
+            width,
+            height,
+            format: FrameFormat::Rgba8,
+        })
+    }
+}
+
+/// ノードが受け付ける入力・提供する出力の接続種別
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct NodePorts {
+    pub inputs: Vec<ConnectionType>,
+    pub outputs: Vec<ConnectionType>,
+}
+
+/// ノード実装が宣言するパラメータの型・既定値・範囲
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ParameterDefinition {
+    pub name: String,
+    pub parameter_type: ParameterType,
+    pub default_value: serde_json::Value,
+    pub min_value: Option<serde_json::Value>,
+    pub max_value: Option<serde_json::Value>,
+    pub description: String,
+}
+
+/// パラメータの値の型
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub enum ParameterType {
+    Float,
+    Integer,
+    Boolean,
+    String,
+    Color,
+    Vector2,
+    Vector3,
+    Vector4,
+    Enum(Vec<String>),
+}
+
+#[derive(Debug, Clone)]
+pub struct Connection {
+    pub connection_type: ConnectionType,
+    pub connected_node: Option<Uuid>,
+}
+
+pub struct NodeGraph {
+    nodes: HashMap<Uuid, Node>,
+    connections: Vec<(Uuid, Uuid, ConnectionType)>,
+    // set_node_parameterで範囲外の値を拒否するか丸めるか
+    parameter_policy: OutOfRangePolicy,
+}
+
+/// NodeGraphの永続化用表現
+#[derive(Debug, Serialize, Deserialize)]
+struct SerializedGraph {
+    nodes: Vec<SerializedNode>,
+    connections: Vec<SerializedConnection>,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+struct SerializedNode {
+    id: Uuid,
+    node_type: NodeType,
+    config: NodeConfig,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+struct SerializedConnection {
+    source: Uuid,
+    target: Uuid,
+    connection_type: ConnectionType,
+}
+
+impl Default for NodeGraph {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl NodeGraph {
+    pub fn new() -> Self {
+        Self {
             nodes: HashMap::new(),
             connections: Vec::new(),
+            parameter_policy: OutOfRangePolicy::default(),
         }
     }
 
@@ -1135,6 +1858,17 @@ impl NodeGraph {
         self.nodes.insert(node.id, node);
     }
 
+    /// ノードを削除し、関連する接続もすべて取り除く
+    pub fn remove_node(&mut self, id: &Uuid) -> ConstellationResult<()> {
+        if self.nodes.remove(id).is_none() {
+            return Err(ConstellationError::NodeNotFound { node_id: *id });
+        }
+
+        self.connections
+            .retain(|(source, target, _)| source != id && target != id);
+        Ok(())
+    }
+
     pub fn connect_nodes(
         &mut self,
         source_id: Uuid,
@@ -1148,6 +1882,9 @@ impl NodeGraph {
             return Err(ConstellationError::NodeNotFound { node_id: target_id });
         }
 
+        // 宣言済みポートとの型チェック
+        self.check_connection_types(source_id, target_id, &connection_type)?;
+
         // 循環参照チェック
         if self.would_create_cycle(source_id, target_id) {
             return Err(ConstellationError::ConnectionCycleDetected {
@@ -1160,14 +1897,295 @@ impl NodeGraph {
         Ok(())
     }
 
+    /// 指定した接続を削除する（該当する接続がなければエラー）
+    pub fn disconnect_nodes(
+        &mut self,
+        source_id: Uuid,
+        target_id: Uuid,
+        connection_type: ConnectionType,
+    ) -> ConstellationResult<()> {
+        let Some(index) = self.connections.iter().position(|(source, target, kind)| {
+            *source == source_id && *target == target_id && *kind == connection_type
+        }) else {
+            return Err(ConstellationError::InvalidConnection {
+                source_id,
+                target_id,
+                connection_type: format!("{:?}", connection_type),
+            });
+        };
+
+        self.connections.remove(index);
+        Ok(())
+    }
+
     pub fn get_node(&self, id: &Uuid) -> Option<&Node> {
         self.nodes.get(id)
     }
 
+    /// 同じ種別・パラメータ・ポート宣言を持つノードを新しいIDで追加する
+    /// 接続は複製しない
+    pub fn duplicate_node(&mut self, id: &Uuid) -> ConstellationResult<Uuid> {
+        let original = self
+            .nodes
+            .get(id)
+            .ok_or(ConstellationError::NodeNotFound { node_id: *id })?;
+        let duplicate_id = Uuid::new_v4();
+        let mut duplicate = Node::new(
+            duplicate_id,
+            original.node_type.clone(),
+            original.config.clone(),
+        );
+        duplicate.ports = original.ports.clone();
+        duplicate.parameter_definitions = original.parameter_definitions.clone();
+        self.add_node(duplicate);
+        Ok(duplicate_id)
+    }
+
     pub fn get_node_mut(&mut self, id: &Uuid) -> Option<&mut Node> {
         self.nodes.get_mut(id)
     }
 
+    pub fn parameter_policy(&self) -> OutOfRangePolicy {
+        self.parameter_policy
+    }
+
+    pub fn set_parameter_policy(&mut self, policy: OutOfRangePolicy) {
+        self.parameter_policy = policy;
+    }
+
+    /// ノードのパラメータを設定し、受け付けた値を返す
+    ///
+    /// パラメータ定義が登録されていればその型・範囲で検証する。
+    /// `OutOfRangePolicy::Clamp` なら範囲外の数値は丸めた値が保存される。
+    /// 定義のないキーはノード固有の設定としてそのまま保存する。
+    pub fn set_node_parameter(
+        &mut self,
+        node_id: &Uuid,
+        parameter: &str,
+        value: serde_json::Value,
+    ) -> ConstellationResult<serde_json::Value> {
+        let policy = self.parameter_policy;
+        let node = self
+            .nodes
+            .get_mut(node_id)
+            .ok_or(ConstellationError::NodeNotFound { node_id: *node_id })?;
+
+        let definition = node
+            .parameter_definitions
+            .as_ref()
+            .and_then(|definitions| definitions.get(parameter));
+        let value = match definition {
+            Some(definition) => validate_parameter(definition, value, policy).map_err(|e| {
+                ConstellationError::ParameterValidationFailed {
+                    parameter: parameter.to_string(),
+                    reason: e.to_string(),
+                }
+            })?,
+            None => value,
+        };
+
+        node.config
+            .parameters
+            .insert(parameter.to_string(), value.clone());
+        Ok(value)
+    }
+
+    pub fn get_connections(&self) -> &[(Uuid, Uuid, ConnectionType)] {
+        &self.connections
+    }
+
+    /// 指定ノードの出力先ノードと接続種別を接続順に返す
+    pub fn get_downstream(&self, node_id: &Uuid) -> Vec<(Uuid, ConnectionType)> {
+        self.connections
+            .iter()
+            .filter(|(source, _, _)| source == node_id)
+            .map(|(_, target, connection_type)| (*target, connection_type.clone()))
+            .collect()
+    }
+
+    /// 指定ノードの入力元ノードと接続種別を接続順に返す
+    pub fn get_upstream(&self, node_id: &Uuid) -> Vec<(Uuid, ConnectionType)> {
+        self.connections
+            .iter()
+            .filter(|(_, target, _)| target == node_id)
+            .map(|(source, _, connection_type)| (*source, connection_type.clone()))
+            .collect()
+    }
+
+    pub fn node_ids(&self) -> impl Iterator<Item = &Uuid> {
+        self.nodes.keys()
+    }
+
+    /// グラフ内のノードが出力するフレームサイズ（重複なし、解像度順）
+    pub fn frame_sizes(&self) -> Vec<FrameSize> {
+        let mut sizes: Vec<FrameSize> = Vec::new();
+        for size in self.nodes.values().filter_map(Node::output_frame_size) {
+            if !sizes.contains(&size) {
+                sizes.push(size);
+            }
+        }
+        sizes.sort_by_key(|size| (size.width, size.height));
+        sizes
+    }
+
+    /// ノード（ID・種別・設定）と接続一覧をJSONにシリアライズする
+    pub fn to_json(&self) -> ConstellationResult<String> {
+        // 出力を安定させるためIDでソート
+        let mut nodes: Vec<SerializedNode> = self
+            .nodes
+            .values()
+            .map(|node| SerializedNode {
+                id: node.id,
+                node_type: node.node_type.clone(),
+                config: node.config.clone(),
+            })
+            .collect();
+        nodes.sort_by_key(|node| node.id);
+
+        let connections = self
+            .connections
+            .iter()
+            .map(|(source, target, connection_type)| SerializedConnection {
+                source: *source,
+                target: *target,
+                connection_type: connection_type.clone(),
+            })
+            .collect();
+
+        serde_json::to_string_pretty(&SerializedGraph { nodes, connections }).map_err(|e| {
+            ConstellationError::ConfigurationError {
+                reason: format!("Failed to serialize node graph: {e}"),
+            }
+        })
+    }
+
+    /// JSONからNodeGraphを復元する（接続先の存在と循環を検証）
+    pub fn from_json(json: &str) -> ConstellationResult<Self> {
+        let serialized: SerializedGraph =
+            serde_json::from_str(json).map_err(|e| ConstellationError::ConfigurationError {
+                reason: format!("Failed to parse node graph: {e}"),
+            })?;
+
+        let mut graph = Self::new();
+        for node in serialized.nodes {
+            if graph.nodes.contains_key(&node.id) {
+                return Err(ConstellationError::ConfigurationError {
+                    reason: format!("Duplicate node id in node graph: {}", node.id),
+                });
+            }
+            graph.add_node(Node::new(node.id, node.node_type, node.config));
+        }
+
+        for connection in serialized.connections {
+            if !graph.nodes.contains_key(&connection.source)
+                || !graph.nodes.contains_key(&connection.target)
+            {
+                return Err(ConstellationError::InvalidConnection {
+                    source_id: connection.source,
+                    target_id: connection.target,
+                    connection_type: format!("{:?}", connection.connection_type),
+                });
+            }
+            graph.connect_nodes(
+                connection.source,
+                connection.target,
+                connection.connection_type,
+            )?;
+        }
+
+        Ok(graph)
+    }
+
+    /// RenderData・Audio接続に基づくトポロジカル順序（Kahnのアルゴリズム）
+    ///
+    /// 入力ノードが常に依存先より先に並ぶ。これらの接続を持たないノードも含まれる。
+    pub fn topological_order(&self) -> ConstellationResult<Vec<Uuid>> {
+        let mut in_degree: HashMap<Uuid, usize> = self.nodes.keys().map(|&id| (id, 0)).collect();
+        let mut adjacency: HashMap<Uuid, Vec<Uuid>> = HashMap::new();
+
+        for (source, target, connection_type) in &self.connections {
+            if *connection_type == ConnectionType::Control {
+                continue;
+            }
+            adjacency.entry(*source).or_default().push(*target);
+            *in_degree.entry(*target).or_insert(0) += 1;
+        }
+
+        // 実行順を安定させるためIDでソートしてからキューに積む
+        let mut ready: Vec<Uuid> = in_degree
+            .iter()
+            .filter(|(_, &degree)| degree == 0)
+            .map(|(&id, _)| id)
+            .collect();
+        ready.sort();
+        let mut queue: std::collections::VecDeque<Uuid> = ready.into();
+
+        let mut order = Vec::with_capacity(in_degree.len());
+        while let Some(current) = queue.pop_front() {
+            order.push(current);
+
+            if let Some(targets) = adjacency.get(&current) {
+                let mut newly_ready = Vec::new();
+                for target in targets {
+                    if let Some(degree) = in_degree.get_mut(target) {
+                        *degree -= 1;
+                        if *degree == 0 {
+                            newly_ready.push(*target);
+                        }
+                    }
+                }
+                newly_ready.sort();
+                queue.extend(newly_ready);
+            }
+        }
+
+        if order.len() != in_degree.len() {
+            let mut remaining: Vec<Uuid> = in_degree
+                .into_iter()
+                .filter(|(_, degree)| *degree > 0)
+                .map(|(id, _)| id)
+                .collect();
+            remaining.sort();
+            return Err(ConstellationError::ConnectionCycleDetected { path: remaining });
+        }
+
+        Ok(order)
+    }
+
+    /// 送信元の出力・送信先の入力に接続種別が含まれるか確認する（ポート未宣言なら許可）
+    fn check_connection_types(
+        &self,
+        source_id: Uuid,
+        target_id: Uuid,
+        connection_type: &ConnectionType,
+    ) -> ConstellationResult<()> {
+        let incompatible = |reason: String| ConstellationError::IncompatibleConnection {
+            source_id,
+            target_id,
+            connection_type: format!("{:?}", connection_type),
+            reason,
+        };
+
+        if let Some(ports) = self.nodes.get(&source_id).and_then(|n| n.ports.as_ref()) {
+            if !ports.outputs.contains(connection_type) {
+                return Err(incompatible(format!(
+                    "source node does not output {:?} (outputs: {:?})",
+                    connection_type, ports.outputs
+                )));
+            }
+        }
+        if let Some(ports) = self.nodes.get(&target_id).and_then(|n| n.ports.as_ref()) {
+            if !ports.inputs.contains(connection_type) {
+                return Err(incompatible(format!(
+                    "target node does not accept {:?} (inputs: {:?})",
+                    connection_type, ports.inputs
+                )));
+            }
+        }
+
+        Ok(())
+    }
+
     /// 循環参照をチェックする
     fn would_create_cycle(&self, source_id: Uuid, target_id: Uuid) -> bool {
         self.has_path(target_id, source_id)
@@ -1200,47 +2218,47 @@ impl NodeGraph {
     }
 
     /// 循環パスを見つける
+    /// source→targetの接続を追加した場合に生じる循環を target → ... → source → target の順で返す
     fn find_cycle_path(&self, source_id: Uuid, target_id: Uuid) -> Vec<Uuid> {
-        let mut path = Vec::new();
-        let mut visited = std::collections::HashSet::new();
-        self.find_path_recursive(target_id, source_id, &mut path, &mut visited);
-        path.push(source_id);
-        path.push(target_id);
-        path
-    }
-
-    fn find_path_recursive(
-        &self,
-        current: Uuid,
-        target: Uuid,
-        path: &mut Vec<Uuid>,
-        visited: &mut std::collections::HashSet<Uuid>,
-    ) -> bool {
-        if current == target {
-            return true;
-        }
-
-        if visited.contains(&current) {
-            return false;
+        // targetからsourceまでの最短経路を幅優先探索で求める
+        let mut parents: HashMap<Uuid, Uuid> = HashMap::new();
+        let mut visited = std::collections::HashSet::from([target_id]);
+        let mut queue = std::collections::VecDeque::from([target_id]);
+
+        while let Some(current) = queue.pop_front() {
+            if current == source_id {
+                break;
+            }
+            for (from, to, _) in &self.connections {
+                if *from == current && visited.insert(*to) {
+                    parents.insert(*to, current);
+                    queue.push_back(*to);
+                }
+            }
         }
-        visited.insert(current);
-        path.push(current);
 
-        for (source, next, _) in &self.connections {
-            if *source == current && self.find_path_recursive(*next, target, path, visited) {
-                return true;
+        // sourceから親を辿って経路を復元する
+        let mut path = vec![source_id];
+        let mut current = source_id;
+        while current != target_id {
+            match parents.get(&current) {
+                Some(&parent) => {
+                    path.push(parent);
+                    current = parent;
+                }
+                None => break,
             }
         }
-
-        path.pop();
-        false
+        path.reverse();
+        path.push(target_id);
+        path
     }
 }
 
 pub struct FrameProcessor {
-    #[allow(dead_code)]
     node_id: Uuid,
     processor_type: ProcessorType,
+    parameters: HashMap<String, serde_json::Value>,
 }
 
 impl FrameProcessor {
@@ -1248,6 +2266,33 @@ impl FrameProcessor {
         Self {
             node_id,
             processor_type,
+            parameters: HashMap::new(),
+        }
+    }
+
+    pub fn set_parameter(&mut self, key: &str, value: serde_json::Value) {
+        self.parameters.insert(key.to_string(), value);
+    }
+
+    pub fn get_parameter(&self, key: &str) -> Option<&serde_json::Value> {
+        self.parameters.get(key)
+    }
+
+    fn float_parameter(&self, key: &str, default: f32) -> f32 {
+        self.parameters
+            .get(key)
+            .and_then(|v| v.as_f64())
+            .map(|v| v as f32)
+            .unwrap_or(default)
+    }
+
+    fn vec2_parameter(&self, key: &str, default: [f32; 2]) -> [f32; 2] {
+        match self.parameters.get(key).and_then(|v| v.as_array()) {
+            Some(values) if values.len() >= 2 => [
+                values[0].as_f64().map(|v| v as f32).unwrap_or(default[0]),
+                values[1].as_f64().map(|v| v as f32).unwrap_or(default[1]),
+            ],
+            _ => default,
         }
     }
 
@@ -1261,7 +2306,16 @@ impl FrameProcessor {
     }
 
     fn process_color_correction(&mut self, input: &FrameData) -> ConstellationResult<FrameData> {
-        Ok(input.clone())
+        let mut output = input.clone();
+        if let Some(RenderData::Raster2D(ref mut frame)) = output.render_data {
+            let adjustment = ColorAdjustment {
+                brightness: self.float_parameter("brightness", 0.0),
+                contrast: self.float_parameter("contrast", 1.0),
+                saturation: self.float_parameter("saturation", 1.0),
+            };
+            adjustment.apply(frame);
+        }
+        Ok(output)
     }
 
     fn process_blur(&mut self, input: &FrameData) -> ConstellationResult<FrameData> {
@@ -1269,7 +2323,239 @@ impl FrameProcessor {
     }
 
     fn process_transform(&mut self, input: &FrameData) -> ConstellationResult<FrameData> {
-        Ok(input.clone())
+        let mut output = input.clone();
+        if let Some(RenderData::Raster2D(ref mut frame)) = output.render_data {
+            let mut transform = Transform2D {
+                translation: self.vec2_parameter("position", [0.0, 0.0]),
+                rotation_degrees: self.float_parameter("rotation", 0.0),
+                scale: self.vec2_parameter("scale", [1.0, 1.0]),
+                pivot: self.vec2_parameter("pivot", [0.5, 0.5]),
+            };
+            // Control線からの変換指定はパラメータより優先
+            if let Some(ControlData::Transform {
+                position,
+                rotation,
+                scale,
+                ..
+            }) = &input.control_data
+            {
+                transform.apply_control(position.as_ref(), rotation.as_ref(), scale.as_ref());
+            }
+            *frame = transform.apply(frame);
+        }
+        Ok(output)
+    }
+}
+
+/// 明るさ・コントラスト・彩度の色補正パラメータ（値は0.0〜1.0の正規化空間で扱う）
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct ColorAdjustment {
+    /// 加算オフセット（0.0で変化なし）
+    pub brightness: f32,
+    /// 0.5を中心としたコントラスト倍率（1.0で変化なし）
+    pub contrast: f32,
+    /// 輝度を保った彩度倍率（0.0でグレースケール、1.0で変化なし）
+    pub saturation: f32,
+}
+
+impl Default for ColorAdjustment {
+    fn default() -> Self {
+        Self {
+            brightness: 0.0,
+            contrast: 1.0,
+            saturation: 1.0,
+        }
+    }
+}
+
+impl ColorAdjustment {
+    /// BT.709の輝度係数
+    const LUMA_WEIGHTS: [f32; 3] = [0.2126, 0.7152, 0.0722];
+
+    /// 1ピクセル（正規化RGB）に補正を適用する。結果は0.0〜1.0にクランプされる
+    pub fn adjust_pixel(&self, rgb: [f32; 3]) -> [f32; 3] {
+        let adjusted = rgb.map(|c| (c - 0.5) * self.contrast + 0.5 + self.brightness);
+
+        let luma: f32 = adjusted
+            .iter()
+            .zip(Self::LUMA_WEIGHTS.iter())
+            .map(|(c, w)| c * w)
+            .sum();
+
+        adjusted.map(|c| (luma + (c - luma) * self.saturation).clamp(0.0, 1.0))
+    }
+
+    /// 非圧縮RGB系フレームに補正を適用する（アルファは変更しない）
+    pub fn apply(&self, frame: &mut VideoFrame) {
+        if frame.format == VideoFormat::Rgba16 {
+            self.apply_rgba16(frame);
+            return;
+        }
+
+        let Some(layout) = frame.format.pixel_layout() else {
+            return;
+        };
+
+        for pixel in frame.pixel_iter_mut() {
+            let rgb = layout.rgb(pixel).map(|c| c as f32 / 255.0);
+            let adjusted = self.adjust_pixel(rgb).map(|c| (c * 255.0).round() as u8);
+            layout.set_rgb(pixel, adjusted);
+        }
+    }
+
+    /// 16bitフレームは8bitに丸めずにそのままの精度で補正する
+    fn apply_rgba16(&self, frame: &mut VideoFrame) {
+        let pixel_count = frame.width as usize * frame.height as usize;
+
+        for pixel in frame.data.chunks_exact_mut(8).take(pixel_count) {
+            let rgb: [f32; 3] = std::array::from_fn(|c| {
+                u16::from_le_bytes([pixel[c * 2], pixel[c * 2 + 1]]) as f32 / 65535.0
+            });
+            for (c, value) in self.adjust_pixel(rgb).into_iter().enumerate() {
+                let value = (value * 65535.0).round() as u16;
+                pixel[c * 2..c * 2 + 2].copy_from_slice(&value.to_le_bytes());
+            }
+        }
+    }
+}
+
+/// 2Dアフィン変換（拡大縮小 → 回転 → 平行移動の順にピボット周りで適用）
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Transform2D {
+    /// ピクセル単位の平行移動
+    pub translation: [f32; 2],
+    /// 度単位の回転（画面上で時計回りが正、Y軸下向き）
+    pub rotation_degrees: f32,
+    pub scale: [f32; 2],
+    /// 画像サイズで正規化したピボット位置（[0.5, 0.5]で中心）
+    pub pivot: [f32; 2],
+}
+
+impl Default for Transform2D {
+    fn default() -> Self {
+        Self {
+            translation: [0.0, 0.0],
+            rotation_degrees: 0.0,
+            scale: [1.0, 1.0],
+            pivot: [0.5, 0.5],
+        }
+    }
+}
+
+impl Transform2D {
+    /// ControlData::Transformの値で上書きする（回転はZ軸周りの成分のみ使用）
+    pub fn apply_control(
+        &mut self,
+        position: Option<&Vector3>,
+        rotation: Option<&Quaternion>,
+        scale: Option<&Vector3>,
+    ) {
+        if let Some(position) = position {
+            self.translation = [position.x, position.y];
+        }
+        if let Some(rotation) = rotation {
+            self.rotation_degrees = (2.0 * rotation.z.atan2(rotation.w)).to_degrees();
+        }
+        if let Some(scale) = scale {
+            self.scale = [scale.x, scale.y];
+        }
+    }
+
+    /// パック形式RGB系のフレームを変換する（フォーマットは入力と同じ）
+    /// バイリニア補間で逆写像し、範囲外は透明な黒（アルファなしのフォーマットでは黒）で埋める
+    pub fn apply(&self, frame: &VideoFrame) -> VideoFrame {
+        let Some(layout) = frame.format.pixel_layout() else {
+            return frame.clone();
+        };
+        let bytes_per_pixel = layout.bytes_per_pixel;
+        let (width, height) = (frame.width as usize, frame.height as usize);
+        if frame.data.len() < width * height * bytes_per_pixel {
+            return frame.clone();
+        }
+
+        let pivot_x = self.pivot[0] * width as f32;
+        let pivot_y = self.pivot[1] * height as f32;
+        let (sin, cos) = self.rotation_degrees.to_radians().sin_cos();
+        let scale_x = if self.scale[0].abs() > f32::EPSILON {
+            self.scale[0]
+        } else {
+            f32::EPSILON
+        };
+        let scale_y = if self.scale[1].abs() > f32::EPSILON {
+            self.scale[1]
+        } else {
+            f32::EPSILON
+        };
+
+        let mut data = vec![0u8; width * height * bytes_per_pixel];
+        for y in 0..height {
+            for x in 0..width {
+                // 出力ピクセル中心 → 入力座標（逆変換）
+                let dx = x as f32 + 0.5 - pivot_x - self.translation[0];
+                let dy = y as f32 + 0.5 - pivot_y - self.translation[1];
+                let rx = cos * dx + sin * dy;
+                let ry = -sin * dx + cos * dy;
+                let src_x = rx / scale_x + pivot_x - 0.5;
+                let src_y = ry / scale_y + pivot_y - 0.5;
+
+                let mut pixel = Self::sample_bilinear(frame, &layout, src_x, src_y);
+                if layout.alpha.is_none() {
+                    // アルファを保持できないので黒の上に合成する
+                    let coverage = pixel[3] as f32 / 255.0;
+                    for c in &mut pixel[..3] {
+                        *c = (*c as f32 * coverage).round() as u8;
+                    }
+                }
+                let offset = (y * width + x) * bytes_per_pixel;
+                layout.set_rgba(&mut data[offset..offset + bytes_per_pixel], pixel);
+            }
+        }
+
+        VideoFrame {
+            width: frame.width,
+            height: frame.height,
+            format: frame.format.clone(),
+            data,
+        }
+    }
+
+    /// プリマルチプライド空間でバイリニア補間する（RGBA順で返す。範囲外は透明な黒）
+    fn sample_bilinear(frame: &VideoFrame, layout: &PixelLayout, x: f32, y: f32) -> [u8; 4] {
+        let (width, height) = (frame.width as i64, frame.height as i64);
+        let x0 = x.floor();
+        let y0 = y.floor();
+        let fx = x - x0;
+        let fy = y - y0;
+        let (x0, y0) = (x0 as i64, y0 as i64);
+
+        let mut accum = [0.0f32; 4];
+        for (sx, sy, weight) in [
+            (x0, y0, (1.0 - fx) * (1.0 - fy)),
+            (x0 + 1, y0, fx * (1.0 - fy)),
+            (x0, y0 + 1, (1.0 - fx) * fy),
+            (x0 + 1, y0 + 1, fx * fy),
+        ] {
+            if weight <= 0.0 || sx < 0 || sy < 0 || sx >= width || sy >= height {
+                continue;
+            }
+            let offset = (sy * width + sx) as usize * layout.bytes_per_pixel;
+            let [r, g, b, a] = layout.rgba(&frame.data[offset..offset + layout.bytes_per_pixel]);
+            let alpha = a as f32;
+            for (value, c) in accum.iter_mut().zip([r, g, b]) {
+                *value += c as f32 * alpha * weight;
+            }
+            accum[3] += alpha * weight;
+        }
+
+        if accum[3] <= 0.0 {
+            return [0, 0, 0, 0];
+        }
+        [
+            (accum[0] / accum[3]).round().clamp(0.0, 255.0) as u8,
+            (accum[1] / accum[3]).round().clamp(0.0, 255.0) as u8,
+            (accum[2] / accum[3]).round().clamp(0.0, 255.0) as u8,
+            accum[3].round().clamp(0.0, 255.0) as u8,
+        ]
     }
 }
 
@@ -1287,7 +2573,7 @@ mod tests {
 
     #[test]
     fn test_constellation_engine_creation() {
-        let result = ConstellationEngine::new();
+        let result = ConstellationEngine::new(None);
         // Note: This may fail in CI environments without Vulkan drivers
         if result.is_err() {
             println!(
@@ -1298,20 +2584,1088 @@ mod tests {
     }
 
     #[test]
-    fn test_node_graph_operations() {
-        let mut graph = NodeGraph::new();
-        let node_id = Uuid::new_v4();
-        let node = Node::new(
-            node_id,
-            NodeType::Input(InputType::Camera),
-            NodeConfig {
-                parameters: HashMap::new(),
-            },
-        );
-
-        graph.add_node(node);
-        assert!(graph.get_node(&node_id).is_some());
-    }
+    fn test_engine_counts_skipped_source_sequence_as_drops() {
+        let mut engine =
+            ConstellationEngine::with_config(EngineConfig::new().with_backend(BackendKind::Cpu))
+                .unwrap();
+        let frame = |sequence| FrameData {
+            render_data: None,
+            audio_data: None,
+            control_data: None,
+            tally_metadata: TallyMetadata::new(),
+            timestamp: Duration::ZERO,
+            sequence,
+            checksum: None,
+        };
+
+        for sequence in [1, 2, 3] {
+            assert_eq!(
+                engine.process_frame(&frame(sequence)).unwrap().sequence,
+                sequence
+            );
+        }
+        assert_eq!(engine.get_session_stats().dropped_frames, 0);
+
+        // 4と5を飛ばすと欠番の数だけドロップが増える
+        assert_eq!(engine.process_frame(&frame(6)).unwrap().sequence, 6);
+        assert_eq!(engine.get_session_stats().dropped_frames, 2);
+
+        // バッチ処理でも同じように検出する
+        engine.process_frames(&[frame(7), frame(10)]).unwrap();
+        assert_eq!(engine.get_session_stats().dropped_frames, 4);
+
+        // 連番のないフレームは直前の続きになる
+        assert_eq!(engine.process_frame(&frame(0)).unwrap().sequence, 11);
+        assert_eq!(engine.get_session_stats().dropped_frames, 4);
+    }
+
+    #[test]
+    fn test_engine_numbers_unset_sequences_from_one() {
+        let mut engine =
+            ConstellationEngine::with_config(EngineConfig::new().with_backend(BackendKind::Cpu))
+                .unwrap();
+        let frame = |sequence| FrameData {
+            sequence,
+            ..FrameData::empty()
+        };
+
+        // 0は未設定なので、エンジンもソースと同じく1から数える
+        assert_eq!(engine.process_frame(&frame(0)).unwrap().sequence, 1);
+        assert_eq!(engine.process_frame(&frame(0)).unwrap().sequence, 2);
+
+        // ソースの最初のフレーム（1）はそのまま引き継がれ、ドロップにならない
+        let mut engine =
+            ConstellationEngine::with_config(EngineConfig::new().with_backend(BackendKind::Cpu))
+                .unwrap();
+        assert_eq!(engine.process_frame(&frame(1)).unwrap().sequence, 1);
+        assert_eq!(engine.process_frame(&frame(2)).unwrap().sequence, 2);
+        assert_eq!(engine.get_session_stats().dropped_frames, 0);
+    }
+
+    #[test]
+    fn test_engine_assigns_monotonic_frame_sequence() {
+        // Vulkanが利用できない環境ではスキップ
+        let Ok(mut engine) = ConstellationEngine::new(None) else {
+            return;
+        };
+
+        let input = FrameData {
+            render_data: None,
+            audio_data: None,
+            control_data: None,
+            tally_metadata: TallyMetadata::new(),
+            timestamp: Duration::ZERO,
+            sequence: 0,
+            checksum: None,
+        };
+
+        let outputs: Vec<FrameData> = (0..5)
+            .map(|_| engine.process_frame(&input).unwrap())
+            .collect();
+        for pair in outputs.windows(2) {
+            assert_eq!(pair[1].sequence, pair[0].sequence + 1);
+            assert!(pair[1].timestamp >= pair[0].timestamp);
+        }
+        assert_eq!(engine.get_session_stats().dropped_frames, 0);
+    }
+
+    #[test]
+    fn test_process_frames_matches_sequential() {
+        // Vulkanが利用できない環境ではスキップ
+        let (Ok(mut sequential), Ok(mut batched)) = (
+            ConstellationEngine::new(None),
+            ConstellationEngine::new(None),
+        ) else {
+            return;
+        };
+        for engine in [&mut sequential, &mut batched] {
+            let mut processor = FrameProcessor::new(Uuid::new_v4(), ProcessorType::ColorCorrection);
+            processor.set_parameter("brightness", serde_json::json!(0.2));
+            engine.frame_processors.push(processor);
+        }
+
+        let inputs: Vec<FrameData> = (0..4u8)
+            .map(|i| FrameData {
+                render_data: Some(RenderData::Raster2D(VideoFrame {
+                    width: 2,
+                    height: 1,
+                    format: VideoFormat::Rgba8,
+                    data: vec![i * 40, 20, 200, 255, 10, i * 50, 90, 255],
+                })),
+                audio_data: None,
+                control_data: None,
+                tally_metadata: TallyMetadata::new(),
+                // 表示時刻を固定して処理時刻に依存しないようにする
+                timestamp: Duration::from_millis(33 * (i as u64 + 1)),
+                sequence: 0,
+                checksum: None,
+            })
+            .collect();
+
+        let expected: Vec<FrameData> = inputs
+            .iter()
+            .map(|input| sequential.process_frame(input).unwrap())
+            .collect();
+        let actual = batched.process_frames(&inputs).unwrap();
+
+        assert_eq!(actual.len(), expected.len());
+        for (actual, expected) in actual.iter().zip(&expected) {
+            assert_eq!(actual.sequence, expected.sequence);
+            assert_eq!(actual.timestamp, expected.timestamp);
+            match (&actual.render_data, &expected.render_data) {
+                (Some(RenderData::Raster2D(a)), Some(RenderData::Raster2D(b))) => {
+                    assert_eq!(a.data, b.data);
+                }
+                other => panic!("unexpected render data: {:?}", other),
+            }
+        }
+        assert_eq!(
+            batched.get_session_stats().frame_count,
+            sequential.get_session_stats().frame_count
+        );
+        assert!(batched.process_frames(&[]).unwrap().is_empty());
+    }
+
+    #[test]
+    fn test_run_at_fps_paces_frames() {
+        // Vulkanが利用できない環境ではスキップ
+        let Ok(mut engine) = ConstellationEngine::new(None) else {
+            return;
+        };
+
+        let mut remaining = 10;
+        let mut outputs = Vec::new();
+        let start = std::time::Instant::now();
+        let stats = engine
+            .run_at_fps(
+                30.0,
+                || {
+                    (remaining > 0).then(|| {
+                        remaining -= 1;
+                        FrameData {
+                            render_data: None,
+                            audio_data: None,
+                            control_data: None,
+                            tally_metadata: TallyMetadata::new(),
+                            timestamp: Duration::ZERO,
+                            sequence: 0,
+                            checksum: None,
+                        }
+                    })
+                },
+                |frame| outputs.push(frame.sequence),
+            )
+            .unwrap();
+        let elapsed = start.elapsed();
+
+        assert_eq!(outputs, (1..=10).collect::<Vec<_>>());
+        assert!(
+            elapsed >= Duration::from_millis(295) && elapsed < Duration::from_millis(500),
+            "elapsed {:?}",
+            elapsed
+        );
+        assert_eq!(stats.frames, 10);
+    }
+
+    #[test]
+    fn test_pause_buffers_and_resume_replays_in_order() {
+        // Vulkanが利用できない環境ではスキップ
+        let Ok(mut engine) = ConstellationEngine::new(None) else {
+            return;
+        };
+
+        let frame = |millis: u64| FrameData {
+            render_data: None,
+            audio_data: None,
+            control_data: None,
+            tally_metadata: TallyMetadata::new(),
+            timestamp: Duration::from_millis(millis),
+            sequence: 0,
+            checksum: None,
+        };
+
+        let first = engine.process_frame(&frame(10)).unwrap();
+        engine.set_pause_mode(PauseMode::Buffer);
+        engine.set_pause_buffer_capacity(3);
+        engine.pause();
+        assert!(engine.is_paused());
+
+        for millis in [20, 30, 40, 50] {
+            // 停止中は最後の出力が出続ける
+            let held = engine.process_frame(&frame(millis)).unwrap();
+            assert_eq!(held.timestamp, first.timestamp);
+        }
+        assert_eq!(engine.paused_frame_count(), 3);
+
+        // 上限を超えた最も古いフレームは捨てられている
+        let replayed = engine.resume().unwrap();
+        assert!(!engine.is_paused());
+        assert_eq!(
+            replayed.iter().map(|f| f.timestamp).collect::<Vec<_>>(),
+            [30, 40, 50].map(Duration::from_millis)
+        );
+        assert_eq!(
+            replayed.iter().map(|f| f.sequence).collect::<Vec<_>>(),
+            vec![2, 3, 4]
+        );
+    }
+
+    #[test]
+    fn test_cpu_backend_processes_frames_without_vulkan() {
+        let mut engine =
+            ConstellationEngine::with_config(EngineConfig::new().with_backend(BackendKind::Cpu))
+                .unwrap();
+        assert_eq!(engine.backend(), BackendKind::Cpu);
+
+        let mut processor = FrameProcessor::new(Uuid::new_v4(), ProcessorType::ColorCorrection);
+        processor.set_parameter("brightness", serde_json::json!(0.2));
+        engine.frame_processors.push(processor);
+
+        let input = FrameData {
+            render_data: Some(RenderData::Raster2D(VideoFrame {
+                width: 2,
+                height: 1,
+                format: VideoFormat::Rgba8,
+                data: vec![100, 100, 100, 255, 0, 0, 0, 255],
+            })),
+            audio_data: None,
+            control_data: None,
+            tally_metadata: TallyMetadata::new(),
+            timestamp: Duration::ZERO,
+            sequence: 0,
+            checksum: None,
+        };
+        let output = engine.process_frame(&input).unwrap();
+        match output.render_data {
+            Some(RenderData::Raster2D(frame)) => {
+                assert_eq!((frame.width, frame.height), (2, 1));
+                assert!(frame.data[0] > 100);
+                assert_eq!(frame.data[3], 255);
+            }
+            other => panic!("unexpected render data: {:?}", other),
+        }
+        assert_eq!(engine.get_session_stats().frame_count, 1);
+    }
+
+    #[test]
+    fn test_cpu_backend_has_no_frame_pools() {
+        let frame_size = FrameSize {
+            width: 640,
+            height: 360,
+            format: FrameFormat::Rgba8,
+        };
+        let mut engine = ConstellationEngine::with_config(
+            EngineConfig::new()
+                .with_backend(BackendKind::Cpu)
+                .with_frame_pool(frame_size.clone(), 2),
+        )
+        .unwrap();
+
+        let mut graph = NodeGraph::new();
+        let mut parameters = HashMap::new();
+        parameters.insert("resolution".to_string(), serde_json::json!("640x360"));
+        graph.add_node(Node::new(
+            Uuid::new_v4(),
+            NodeType::Input(InputType::Camera),
+            NodeConfig { parameters },
+        ));
+
+        assert!(engine.prepare_pools(&graph).unwrap().is_empty());
+        assert!(engine.frame_pool_stats(&frame_size).is_none());
+        assert!(engine.memory_usage().is_none());
+        assert!(matches!(
+            engine.acquire_frame_buffer(&frame_size),
+            Err(ConstellationError::HardwareNotSupported { .. })
+        ));
+        assert_eq!(engine.shutdown().unwrap().frame_count, 0);
+    }
+
+    #[test]
+    fn test_video_engine_status_tracks_graph_and_lifecycle() {
+        let mut engine: Box<dyn VideoEngine> = Box::new(
+            ConstellationEngine::with_config(EngineConfig::new().with_backend(BackendKind::Cpu))
+                .unwrap(),
+        );
+        assert!(!engine.status().running);
+
+        let source = engine
+            .create_node(
+                NodeType::Input(InputType::TestPattern),
+                NodeConfig {
+                    parameters: HashMap::new(),
+                },
+            )
+            .unwrap();
+        let target = engine
+            .create_node(
+                NodeType::Output(OutputType::Preview),
+                NodeConfig {
+                    parameters: HashMap::new(),
+                },
+            )
+            .unwrap();
+        engine
+            .connect(source, target, ConnectionType::RenderData)
+            .unwrap();
+        engine.start().unwrap();
+
+        let status = engine.status();
+        assert!(status.running);
+        assert_eq!(status.backend, BackendKind::Cpu);
+        assert_eq!((status.node_count, status.connection_count), (2, 1));
+
+        engine.remove_node(source).unwrap();
+        engine.stop().unwrap();
+        let status = engine.status();
+        assert!(!status.running);
+        assert_eq!((status.node_count, status.connection_count), (1, 0));
+    }
+
+    #[test]
+    fn test_with_config_applies_settings() {
+        let frame_size = FrameSize {
+            width: 640,
+            height: 360,
+            format: FrameFormat::Rgba8,
+        };
+        let config = EngineConfig::new()
+            .with_backend(BackendKind::Vulkan)
+            .with_resilience(true)
+            .with_frame_pool(frame_size.clone(), 3);
+
+        // Vulkanが利用できない環境ではスキップ
+        let Ok(engine) = ConstellationEngine::with_config(config) else {
+            return;
+        };
+
+        assert!(engine.is_resilience_enabled());
+        let stats = engine.frame_pool_stats(&frame_size).unwrap();
+        assert_eq!(stats.buffer_count, 3);
+        assert_eq!(stats.in_use, 0);
+    }
+
+    #[test]
+    fn test_with_config_rejects_invalid_config_before_gpu_init() {
+        let config = EngineConfig::new().with_frame_pool(
+            FrameSize {
+                width: 640,
+                height: 360,
+                format: FrameFormat::Rgba8,
+            },
+            0,
+        );
+        assert!(matches!(
+            ConstellationEngine::with_config(config),
+            Err(ConstellationError::ConfigurationError { .. })
+        ));
+    }
+
+    #[test]
+    fn test_frame_sizes_collects_distinct_video_resolutions() {
+        let mut graph = NodeGraph::new();
+        let node = |node_type: NodeType, parameters: &[(&str, serde_json::Value)]| {
+            Node::new(
+                Uuid::new_v4(),
+                node_type,
+                NodeConfig {
+                    parameters: parameters
+                        .iter()
+                        .map(|(key, value)| (key.to_string(), value.clone()))
+                        .collect(),
+                },
+            )
+        };
+
+        graph.add_node(node(
+            NodeType::Input(InputType::Camera),
+            &[("resolution", serde_json::json!("1280x720"))],
+        ));
+        graph.add_node(node(
+            NodeType::Input(InputType::TestPattern),
+            &[
+                ("width", serde_json::json!(1920)),
+                ("height", serde_json::json!(1080)),
+            ],
+        ));
+        graph.add_node(node(
+            NodeType::Output(OutputType::VirtualWebcam),
+            &[("resolution", serde_json::json!("1920x1080"))],
+        ));
+        // 解像度を持たない・映像を出さないノードは対象外
+        graph.add_node(node(NodeType::Effect(EffectType::Blur), &[]));
+        graph.add_node(node(
+            NodeType::Control(ControlType::Lfo),
+            &[("resolution", serde_json::json!("640x480"))],
+        ));
+
+        let sizes: Vec<(u32, u32)> = graph
+            .frame_sizes()
+            .iter()
+            .map(|size| (size.width, size.height))
+            .collect();
+        assert_eq!(sizes, vec![(1280, 720), (1920, 1080)]);
+        assert!(graph
+            .frame_sizes()
+            .iter()
+            .all(|size| size.format == FrameFormat::Rgba8));
+    }
+
+    #[test]
+    fn test_output_frame_size_falls_back_to_parameter_defaults() {
+        let definition = |name: &str, parameter_type: ParameterType, default_value| {
+            (
+                name.to_string(),
+                ParameterDefinition {
+                    name: name.to_string(),
+                    parameter_type,
+                    default_value,
+                    min_value: None,
+                    max_value: None,
+                    description: String::new(),
+                },
+            )
+        };
+        let mut node = Node::new(
+            Uuid::new_v4(),
+            NodeType::Input(InputType::TestPattern),
+            NodeConfig {
+                parameters: HashMap::from([("height".to_string(), serde_json::json!(720))]),
+            },
+        );
+        assert_eq!(node.output_frame_size(), None);
+
+        node = node.with_parameter_definitions(HashMap::from([
+            definition("width", ParameterType::Integer, serde_json::json!(1280)),
+            definition("height", ParameterType::Integer, serde_json::json!(1080)),
+        ]));
+        let size = node.output_frame_size().unwrap();
+        // 明示した値が既定値より優先される
+        assert_eq!((size.width, size.height), (1280, 720));
+
+        let camera = Node::new(
+            Uuid::new_v4(),
+            NodeType::Input(InputType::Camera),
+            NodeConfig {
+                parameters: HashMap::new(),
+            },
+        )
+        .with_parameter_definitions(HashMap::from([definition(
+            "resolution",
+            ParameterType::String,
+            serde_json::json!("640x480"),
+        )]));
+        let size = camera.output_frame_size().unwrap();
+        assert_eq!((size.width, size.height), (640, 480));
+    }
+
+    #[test]
+    fn test_prepare_pools_allows_pooled_acquisition() {
+        // Vulkanが利用できない環境ではスキップ
+        let Ok(mut engine) = ConstellationEngine::with_config(
+            EngineConfig::new()
+                .with_backend(BackendKind::Vulkan)
+                .with_frame_pool_buffer_count(2),
+        ) else {
+            return;
+        };
+
+        let mut graph = NodeGraph::new();
+        let mut parameters = HashMap::new();
+        parameters.insert("resolution".to_string(), serde_json::json!("640x360"));
+        graph.add_node(Node::new(
+            Uuid::new_v4(),
+            NodeType::Input(InputType::Camera),
+            NodeConfig { parameters },
+        ));
+
+        let sizes = engine.prepare_pools(&graph).unwrap();
+        assert_eq!(sizes.len(), 1);
+        let allocated = engine.memory_usage().unwrap().total_allocated;
+
+        // プールから取得でき、追加のメモリ確保は発生しない
+        let first = engine.acquire_frame_buffer(&sizes[0]).unwrap();
+        let second = engine.acquire_frame_buffer(&sizes[0]).unwrap();
+        assert_eq!(engine.memory_usage().unwrap().total_allocated, allocated);
+        assert_eq!(engine.frame_pool_stats(&sizes[0]).unwrap().in_use, 2);
+        assert!(engine.acquire_frame_buffer(&sizes[0]).is_err());
+
+        engine.release_frame_buffer(first);
+        engine.release_frame_buffer(second);
+        assert_eq!(engine.frame_pool_stats(&sizes[0]).unwrap().in_use, 0);
+    }
+
+    #[test]
+    fn test_shutdown_returns_session_stats() {
+        // Vulkanが利用できない環境ではスキップ
+        let Ok(mut engine) = ConstellationEngine::new(None) else {
+            return;
+        };
+
+        let export_dir =
+            std::env::temp_dir().join(format!("constellation-shutdown-{}", Uuid::new_v4()));
+        let export_path = export_dir.join("telemetry.jsonl");
+        std::fs::create_dir_all(&export_dir).unwrap();
+        engine
+            .telemetry_manager
+            .start_file_export(&export_path, 1024 * 1024)
+            .unwrap();
+
+        let input = FrameData {
+            render_data: None,
+            audio_data: None,
+            control_data: None,
+            tally_metadata: TallyMetadata::new(),
+            timestamp: Duration::ZERO,
+            sequence: 0,
+            checksum: None,
+        };
+        for _ in 0..4 {
+            engine.process_frame(&input).unwrap();
+        }
+
+        let stats = engine.shutdown().unwrap();
+        assert_eq!(stats.frame_count, 4);
+
+        // 最終統計がファイルに書き出されている
+        let exported = std::fs::read_to_string(&export_path).unwrap();
+        assert!(exported
+            .lines()
+            .last()
+            .unwrap()
+            .contains("\"frame_count\":4"));
+        let _ = std::fs::remove_dir_all(export_dir);
+    }
+
+    #[test]
+    fn test_enable_resilience_then_drop_engine() {
+        // Vulkanが利用できない環境ではスキップ
+        let Ok(mut engine) = ConstellationEngine::new(None) else {
+            return;
+        };
+
+        // 以前はエンジンを複製していたため、ここでの破棄が二重解放になっていた
+        engine.enable_resilience().unwrap();
+        engine.enable_resilience().unwrap();
+        assert!(engine.is_resilience_enabled());
+
+        let input = FrameData {
+            render_data: None,
+            audio_data: None,
+            control_data: None,
+            tally_metadata: TallyMetadata::new(),
+            timestamp: Duration::ZERO,
+            sequence: 0,
+            checksum: None,
+        };
+        engine.process_frame(&input).unwrap();
+        drop(engine);
+    }
+
+    #[test]
+    fn test_node_graph_operations() {
+        let mut graph = NodeGraph::new();
+        let node_id = Uuid::new_v4();
+        let node = Node::new(
+            node_id,
+            NodeType::Input(InputType::Camera),
+            NodeConfig {
+                parameters: HashMap::new(),
+            },
+        );
+
+        graph.add_node(node);
+        assert!(graph.get_node(&node_id).is_some());
+    }
+
+    fn create_test_node(graph: &mut NodeGraph) -> Uuid {
+        let node_id = Uuid::new_v4();
+        graph.add_node(Node::new(
+            node_id,
+            NodeType::Effect(EffectType::Blur),
+            NodeConfig {
+                parameters: HashMap::new(),
+            },
+        ));
+        node_id
+    }
+
+    #[test]
+    fn test_node_graph_remove_node_cleans_connections() {
+        let mut graph = NodeGraph::new();
+        let a = create_test_node(&mut graph);
+        let b = create_test_node(&mut graph);
+        let c = create_test_node(&mut graph);
+
+        graph
+            .connect_nodes(a, b, ConnectionType::RenderData)
+            .unwrap();
+        graph
+            .connect_nodes(b, c, ConnectionType::RenderData)
+            .unwrap();
+        graph.connect_nodes(a, c, ConnectionType::Control).unwrap();
+
+        graph.remove_node(&b).unwrap();
+
+        assert!(graph.get_node(&b).is_none());
+        assert_eq!(graph.get_connections().len(), 1);
+        assert!(graph
+            .get_connections()
+            .iter()
+            .all(|(source, target, _)| *source != b && *target != b));
+    }
+
+    #[test]
+    fn test_duplicate_node_copies_config_but_not_connections() {
+        let mut engine =
+            ConstellationEngine::with_config(EngineConfig::new().with_backend(BackendKind::Cpu))
+                .unwrap();
+        let mut parameters = HashMap::new();
+        parameters.insert("radius".to_string(), serde_json::json!(4.5));
+        parameters.insert(
+            "kernel".to_string(),
+            serde_json::json!({ "weights": [1, 2, 1] }),
+        );
+        let original = engine
+            .add_node(
+                NodeType::Effect(EffectType::Blur),
+                NodeConfig { parameters },
+            )
+            .unwrap();
+        let upstream = engine
+            .add_node(
+                NodeType::Input(InputType::TestPattern),
+                NodeConfig {
+                    parameters: HashMap::new(),
+                },
+            )
+            .unwrap();
+        engine
+            .connect_nodes(upstream, original, ConnectionType::RenderData)
+            .unwrap();
+
+        let duplicate = engine.duplicate_node(original).unwrap();
+        assert_ne!(duplicate, original);
+
+        let copy = engine.get_node(duplicate).unwrap();
+        assert_eq!(copy.id, duplicate);
+        assert_eq!(copy.node_type, NodeType::Effect(EffectType::Blur));
+        assert_eq!(
+            copy.config.parameters,
+            engine.get_node(original).unwrap().config.parameters
+        );
+        assert!(engine.node_graph.get_upstream(&duplicate).is_empty());
+        assert_eq!(engine.node_graph.get_connections().len(), 1);
+
+        // 複製後の変更は元のノードに影響しない
+        engine
+            .node_graph
+            .get_node_mut(&duplicate)
+            .unwrap()
+            .config
+            .parameters
+            .insert("radius".to_string(), serde_json::json!(1.0));
+        assert_eq!(
+            engine.get_node(original).unwrap().config.parameters["radius"],
+            serde_json::json!(4.5)
+        );
+
+        assert!(matches!(
+            engine.duplicate_node(Uuid::new_v4()),
+            Err(ConstellationError::NodeNotFound { .. })
+        ));
+    }
+
+    #[test]
+    fn test_node_graph_remove_missing_node() {
+        let mut graph = NodeGraph::new();
+        let missing = Uuid::new_v4();
+
+        match graph.remove_node(&missing) {
+            Err(ConstellationError::NodeNotFound { node_id }) => assert_eq!(node_id, missing),
+            other => panic!("Expected NodeNotFound, got {other:?}"),
+        }
+    }
+
+    fn position_of(order: &[Uuid], id: Uuid) -> usize {
+        order.iter().position(|&node| node == id).unwrap()
+    }
+
+    #[test]
+    fn test_node_graph_json_round_trip() {
+        let mut graph = NodeGraph::new();
+        let input = Uuid::new_v4();
+        let mut parameters = HashMap::new();
+        parameters.insert("pattern".to_string(), serde_json::json!("ColorBars"));
+        graph.add_node(Node::new(
+            input,
+            NodeType::Input(InputType::TestPattern),
+            NodeConfig { parameters },
+        ));
+        let effect = create_test_node(&mut graph);
+        let output = Uuid::new_v4();
+        graph.add_node(Node::new(
+            output,
+            NodeType::Output(OutputType::Preview),
+            NodeConfig {
+                parameters: HashMap::new(),
+            },
+        ));
+        graph
+            .connect_nodes(input, effect, ConnectionType::RenderData)
+            .unwrap();
+        graph
+            .connect_nodes(effect, output, ConnectionType::RenderData)
+            .unwrap();
+        graph
+            .connect_nodes(input, output, ConnectionType::Control)
+            .unwrap();
+
+        let json = graph.to_json().unwrap();
+        let restored = NodeGraph::from_json(&json).unwrap();
+
+        let mut original_ids: Vec<&Uuid> = graph.node_ids().collect();
+        let mut restored_ids: Vec<&Uuid> = restored.node_ids().collect();
+        original_ids.sort();
+        restored_ids.sort();
+        assert_eq!(original_ids, restored_ids);
+
+        for id in original_ids {
+            let (a, b) = (graph.get_node(id).unwrap(), restored.get_node(id).unwrap());
+            assert_eq!(a.node_type, b.node_type);
+            assert_eq!(a.config.parameters, b.config.parameters);
+        }
+        assert_eq!(graph.get_connections(), restored.get_connections());
+    }
+
+    #[test]
+    fn test_node_graph_from_json_rejects_dangling_connection() {
+        let mut graph = NodeGraph::new();
+        let node = create_test_node(&mut graph);
+        let json = graph.to_json().unwrap();
+
+        let mut value: serde_json::Value = serde_json::from_str(&json).unwrap();
+        value["connections"] = serde_json::json!([{
+            "source": node,
+            "target": Uuid::new_v4(),
+            "connection_type": "RenderData",
+        }]);
+
+        let result = NodeGraph::from_json(&value.to_string());
+        assert!(matches!(
+            result,
+            Err(ConstellationError::InvalidConnection { .. })
+        ));
+    }
+
+    #[test]
+    fn test_node_graph_from_json_rejects_duplicate_node_id() {
+        let mut graph = NodeGraph::new();
+        create_test_node(&mut graph);
+        let json = graph.to_json().unwrap();
+
+        let mut value: serde_json::Value = serde_json::from_str(&json).unwrap();
+        let duplicate = value["nodes"][0].clone();
+        value["nodes"].as_array_mut().unwrap().push(duplicate);
+
+        let result = NodeGraph::from_json(&value.to_string());
+        assert!(matches!(
+            result,
+            Err(ConstellationError::ConfigurationError { .. })
+        ));
+    }
+
+    #[test]
+    fn test_topological_order_chain() {
+        let mut graph = NodeGraph::new();
+        let source = create_test_node(&mut graph);
+        let effect = create_test_node(&mut graph);
+        let sink = create_test_node(&mut graph);
+
+        graph
+            .connect_nodes(effect, sink, ConnectionType::RenderData)
+            .unwrap();
+        graph
+            .connect_nodes(source, effect, ConnectionType::RenderData)
+            .unwrap();
+
+        let order = graph.topological_order().unwrap();
+        assert_eq!(order, vec![source, effect, sink]);
+    }
+
+    #[test]
+    fn test_topological_order_diamond() {
+        let mut graph = NodeGraph::new();
+        let source = create_test_node(&mut graph);
+        let left = create_test_node(&mut graph);
+        let right = create_test_node(&mut graph);
+        let sink = create_test_node(&mut graph);
+
+        graph
+            .connect_nodes(source, left, ConnectionType::RenderData)
+            .unwrap();
+        graph
+            .connect_nodes(source, right, ConnectionType::RenderData)
+            .unwrap();
+        graph
+            .connect_nodes(left, sink, ConnectionType::RenderData)
+            .unwrap();
+        graph
+            .connect_nodes(right, sink, ConnectionType::RenderData)
+            .unwrap();
+
+        let order = graph.topological_order().unwrap();
+        assert_eq!(order.len(), 4);
+        assert!(position_of(&order, source) < position_of(&order, left));
+        assert!(position_of(&order, source) < position_of(&order, right));
+        assert!(position_of(&order, left) < position_of(&order, sink));
+        assert!(position_of(&order, right) < position_of(&order, sink));
+    }
+
+    fn graph_with_nodes(count: usize) -> (NodeGraph, Vec<Uuid>) {
+        let mut graph = NodeGraph::new();
+        let ids = (0..count).map(|_| create_test_node(&mut graph)).collect();
+        (graph, ids)
+    }
+
+    fn cycle_error_path(result: ConstellationResult<()>) -> Vec<Uuid> {
+        match result {
+            Err(ConstellationError::ConnectionCycleDetected { path }) => path,
+            other => panic!("循環エラーが必要: {:?}", other),
+        }
+    }
+
+    #[test]
+    fn test_cycle_path_three_nodes() {
+        let (mut graph, ids) = graph_with_nodes(3);
+        graph
+            .connect_nodes(ids[0], ids[1], ConnectionType::RenderData)
+            .unwrap();
+        graph
+            .connect_nodes(ids[1], ids[2], ConnectionType::RenderData)
+            .unwrap();
+
+        let path =
+            cycle_error_path(graph.connect_nodes(ids[2], ids[0], ConnectionType::RenderData));
+        assert_eq!(path, vec![ids[0], ids[1], ids[2], ids[0]]);
+    }
+
+    #[test]
+    fn test_cycle_path_four_nodes_with_branch() {
+        let (mut graph, ids) = graph_with_nodes(5);
+        // 0 → 1 → 2 → 3 の鎖と、循環に関係しない 1 → 4 の分岐
+        for (source, target) in [(0, 1), (1, 4), (1, 2), (2, 3)] {
+            graph
+                .connect_nodes(ids[source], ids[target], ConnectionType::RenderData)
+                .unwrap();
+        }
+
+        let path =
+            cycle_error_path(graph.connect_nodes(ids[3], ids[0], ConnectionType::RenderData));
+        assert_eq!(path, vec![ids[0], ids[1], ids[2], ids[3], ids[0]]);
+
+        // 閉じる要素以外に重複がない
+        let unique: std::collections::HashSet<_> = path[..path.len() - 1].iter().collect();
+        assert_eq!(unique.len(), path.len() - 1);
+    }
+
+    #[test]
+    fn test_neighbor_queries_by_connection_type() {
+        let (mut graph, ids) = graph_with_nodes(4);
+        // 0 → 1 (RenderData), 0 → 2 (Audio), 3 → 1 (Control), 1 → 2 (RenderData)
+        graph
+            .connect_nodes(ids[0], ids[1], ConnectionType::RenderData)
+            .unwrap();
+        graph
+            .connect_nodes(ids[0], ids[2], ConnectionType::Audio)
+            .unwrap();
+        graph
+            .connect_nodes(ids[3], ids[1], ConnectionType::Control)
+            .unwrap();
+        graph
+            .connect_nodes(ids[1], ids[2], ConnectionType::RenderData)
+            .unwrap();
+
+        assert_eq!(
+            graph.get_downstream(&ids[0]),
+            vec![
+                (ids[1], ConnectionType::RenderData),
+                (ids[2], ConnectionType::Audio)
+            ]
+        );
+        assert_eq!(
+            graph.get_upstream(&ids[1]),
+            vec![
+                (ids[0], ConnectionType::RenderData),
+                (ids[3], ConnectionType::Control)
+            ]
+        );
+        assert_eq!(
+            graph.get_upstream(&ids[2]),
+            vec![
+                (ids[0], ConnectionType::Audio),
+                (ids[1], ConnectionType::RenderData)
+            ]
+        );
+        assert_eq!(
+            graph.get_downstream(&ids[3]),
+            vec![(ids[1], ConnectionType::Control)]
+        );
+        assert!(graph.get_upstream(&ids[0]).is_empty());
+        assert!(graph.get_downstream(&ids[2]).is_empty());
+        assert!(graph.get_downstream(&Uuid::new_v4()).is_empty());
+        assert_eq!(graph.get_connections().len(), 4);
+    }
+
+    #[test]
+    fn test_disconnect_nodes_removes_only_matching_edge() {
+        let (mut graph, ids) = graph_with_nodes(2);
+        graph
+            .connect_nodes(ids[0], ids[1], ConnectionType::RenderData)
+            .unwrap();
+        graph
+            .connect_nodes(ids[0], ids[1], ConnectionType::Audio)
+            .unwrap();
+
+        graph
+            .disconnect_nodes(ids[0], ids[1], ConnectionType::RenderData)
+            .unwrap();
+        assert_eq!(
+            graph.get_downstream(&ids[0]),
+            vec![(ids[1], ConnectionType::Audio)]
+        );
+
+        // 同じ接続を再度削除するとエラー
+        let result = graph.disconnect_nodes(ids[0], ids[1], ConnectionType::RenderData);
+        assert!(matches!(
+            result,
+            Err(ConstellationError::InvalidConnection { .. })
+        ));
+    }
+
+    #[test]
+    fn test_disconnect_nonexistent_edge_errors() {
+        let (mut graph, ids) = graph_with_nodes(2);
+        assert!(graph
+            .disconnect_nodes(ids[0], ids[1], ConnectionType::Control)
+            .is_err());
+        assert!(graph
+            .disconnect_nodes(Uuid::new_v4(), ids[1], ConnectionType::RenderData)
+            .is_err());
+    }
+
+    #[test]
+    fn test_connect_nodes_enforces_declared_ports() {
+        let (mut graph, ids) = graph_with_nodes(3);
+        // 0: 音声のみ出力、1と2: 映像を入出力
+        graph.get_node_mut(&ids[0]).unwrap().ports = Some(NodePorts {
+            inputs: vec![],
+            outputs: vec![ConnectionType::Audio],
+        });
+        for id in &ids[1..] {
+            graph.get_node_mut(id).unwrap().ports = Some(NodePorts {
+                inputs: vec![ConnectionType::RenderData],
+                outputs: vec![ConnectionType::RenderData],
+            });
+        }
+
+        // Audio → RenderData入力は拒否
+        match graph.connect_nodes(ids[0], ids[1], ConnectionType::Audio) {
+            Err(ConstellationError::IncompatibleConnection { reason, .. }) => {
+                assert!(reason.contains("does not accept Audio"), "{reason}");
+            }
+            other => panic!("Expected IncompatibleConnection, got {other:?}"),
+        }
+        // 送信元が出力しない種別も拒否
+        match graph.connect_nodes(ids[0], ids[1], ConnectionType::RenderData) {
+            Err(ConstellationError::IncompatibleConnection { reason, .. }) => {
+                assert!(reason.contains("does not output RenderData"), "{reason}");
+            }
+            other => panic!("Expected IncompatibleConnection, got {other:?}"),
+        }
+        assert!(graph.get_connections().is_empty());
+
+        // RenderData → RenderDataは接続できる
+        graph
+            .connect_nodes(ids[1], ids[2], ConnectionType::RenderData)
+            .unwrap();
+        assert_eq!(graph.get_connections().len(), 1);
+    }
+
+    #[test]
+    fn test_connect_nodes_without_ports_is_unchecked() {
+        let (mut graph, ids) = graph_with_nodes(2);
+        graph
+            .connect_nodes(ids[0], ids[1], ConnectionType::Audio)
+            .unwrap();
+    }
+
+    fn graph_with_opacity_parameter() -> (NodeGraph, Uuid) {
+        let (mut graph, ids) = graph_with_nodes(1);
+        let definitions = HashMap::from([(
+            "opacity".to_string(),
+            ParameterDefinition {
+                name: "Opacity".to_string(),
+                parameter_type: ParameterType::Float,
+                default_value: serde_json::json!(1.0),
+                min_value: Some(serde_json::json!(0.0)),
+                max_value: Some(serde_json::json!(1.0)),
+                description: "Opacity level".to_string(),
+            },
+        )]);
+        graph.get_node_mut(&ids[0]).unwrap().parameter_definitions = Some(definitions);
+        (graph, ids[0])
+    }
+
+    #[test]
+    fn test_set_node_parameter_rejects_invalid_values() {
+        let (mut graph, id) = graph_with_opacity_parameter();
+        let stored = |graph: &NodeGraph| graph.get_node(&id).unwrap().config.parameters.clone();
+
+        for value in [serde_json::json!(1.5), serde_json::json!("opaque")] {
+            match graph.set_node_parameter(&id, "opacity", value) {
+                Err(ConstellationError::ParameterValidationFailed { parameter, .. }) => {
+                    assert_eq!(parameter, "opacity");
+                }
+                other => panic!("Expected ParameterValidationFailed, got {other:?}"),
+            }
+        }
+        assert!(!stored(&graph).contains_key("opacity"));
+
+        let accepted = graph
+            .set_node_parameter(&id, "opacity", serde_json::json!(0.5))
+            .unwrap();
+        assert_eq!(accepted, serde_json::json!(0.5));
+        // 定義のないキーは検証せずに保存する
+        graph
+            .set_node_parameter(&id, "label", serde_json::json!(42))
+            .unwrap();
+        assert_eq!(stored(&graph)["opacity"], serde_json::json!(0.5));
+        assert_eq!(stored(&graph)["label"], serde_json::json!(42));
+
+        assert!(matches!(
+            graph.set_node_parameter(&Uuid::new_v4(), "opacity", serde_json::json!(0.5)),
+            Err(ConstellationError::NodeNotFound { .. })
+        ));
+    }
+
+    #[test]
+    fn test_set_node_parameter_clamps_with_clamp_policy() {
+        let (mut graph, id) = graph_with_opacity_parameter();
+        graph.set_parameter_policy(OutOfRangePolicy::Clamp);
+
+        let accepted = graph
+            .set_node_parameter(&id, "opacity", serde_json::json!(1.5))
+            .unwrap();
+        assert_eq!(accepted, serde_json::json!(1.0));
+        assert_eq!(
+            graph.get_node(&id).unwrap().config.parameters["opacity"],
+            serde_json::json!(1.0)
+        );
+        // 型の不一致は丸められない
+        assert!(graph
+            .set_node_parameter(&id, "opacity", serde_json::json!(true))
+            .is_err());
+    }
 
     #[test]
     fn test_frame_processor() {
@@ -1323,9 +3677,253 @@ mod tests {
             audio_data: None,
             control_data: None,
             tally_metadata: TallyMetadata::new(),
+            timestamp: Duration::ZERO,
+            sequence: 0,
+            checksum: None,
         };
 
         let result = processor.process(&input_frame);
         assert!(result.is_ok());
     }
+
+    #[test]
+    fn test_pass_through_keeps_rgba16_frame() {
+        let mut processor = FrameProcessor::new(Uuid::new_v4(), ProcessorType::PassThrough);
+        // 8bitでは表現できない下位バイトを含む値
+        let data: Vec<u8> = [0x1234u16, 0xABCD, 0x00FF, 0xFFFF]
+            .iter()
+            .flat_map(|v| v.to_le_bytes())
+            .collect();
+        let input = FrameData {
+            render_data: Some(RenderData::Raster2D(VideoFrame {
+                width: 1,
+                height: 1,
+                format: VideoFormat::Rgba16,
+                data: data.clone(),
+            })),
+            audio_data: None,
+            control_data: None,
+            tally_metadata: TallyMetadata::new(),
+            timestamp: Duration::ZERO,
+            sequence: 0,
+            checksum: None,
+        };
+
+        let output = processor.process(&input).unwrap();
+        match output.render_data {
+            Some(RenderData::Raster2D(frame)) => {
+                assert_eq!(frame.format, VideoFormat::Rgba16);
+                assert_eq!(frame.data, data);
+            }
+            _ => panic!("Expected Raster2D output"),
+        }
+    }
+
+    #[test]
+    fn test_color_adjustment_keeps_16bit_precision() {
+        let to_bytes =
+            |values: [u16; 4]| -> Vec<u8> { values.iter().flat_map(|v| v.to_le_bytes()).collect() };
+        let mut frame = VideoFrame {
+            width: 1,
+            height: 1,
+            format: VideoFormat::Rgba16,
+            data: to_bytes([1000, 30000, 65535, 4321]),
+        };
+
+        // 無補正なら値はそのまま
+        ColorAdjustment::default().apply(&mut frame);
+        assert_eq!(frame.data, to_bytes([1000, 30000, 65535, 4321]));
+
+        // 8bitに丸めると257刻みになるが、16bitのまま細かい値が残る
+        ColorAdjustment {
+            brightness: 0.001,
+            ..Default::default()
+        }
+        .apply(&mut frame);
+        let red = u16::from_le_bytes([frame.data[0], frame.data[1]]);
+        assert_eq!(red, 1066);
+        // アルファは変更しない
+        assert_eq!(u16::from_le_bytes([frame.data[6], frame.data[7]]), 4321);
+    }
+
+    fn color_frame_data(pixels: &[[u8; 4]]) -> FrameData {
+        FrameData {
+            render_data: Some(RenderData::Raster2D(VideoFrame {
+                width: pixels.len() as u32,
+                height: 1,
+                format: VideoFormat::Rgba8,
+                data: pixels.concat(),
+            })),
+            audio_data: None,
+            control_data: None,
+            tally_metadata: TallyMetadata::new(),
+            timestamp: Duration::ZERO,
+            sequence: 0,
+            checksum: None,
+        }
+    }
+
+    fn raster_data(frame_data: &FrameData) -> &[u8] {
+        match frame_data.render_data.as_ref() {
+            Some(RenderData::Raster2D(frame)) => &frame.data,
+            _ => panic!("Raster2Dが必要"),
+        }
+    }
+
+    #[test]
+    fn test_color_correction_zero_saturation_is_luma_grayscale() {
+        let mut processor = FrameProcessor::new(Uuid::new_v4(), ProcessorType::ColorCorrection);
+        processor.set_parameter("saturation", serde_json::json!(0.0));
+
+        let pixels = [[255, 0, 0, 255], [30, 200, 90, 128]];
+        let output = processor.process(&color_frame_data(&pixels)).unwrap();
+        let data = raster_data(&output);
+
+        for (pixel, original) in data.chunks_exact(4).zip(pixels.iter()) {
+            let luma = 0.2126 * original[0] as f32
+                + 0.7152 * original[1] as f32
+                + 0.0722 * original[2] as f32;
+            assert_eq!(pixel[0], pixel[1]);
+            assert_eq!(pixel[1], pixel[2]);
+            assert!((pixel[0] as f32 - luma).abs() <= 1.0);
+            // アルファは保持される
+            assert_eq!(pixel[3], original[3]);
+        }
+    }
+
+    #[test]
+    fn test_color_correction_brightness_shifts_channels_uniformly() {
+        let mut processor = FrameProcessor::new(Uuid::new_v4(), ProcessorType::ColorCorrection);
+        processor.set_parameter("brightness", serde_json::json!(0.2));
+
+        let pixels = [[10, 100, 150, 255]];
+        let output = processor.process(&color_frame_data(&pixels)).unwrap();
+        let data = raster_data(&output);
+
+        // 0.2 * 255 = 51
+        assert_eq!(&data[..4], &[61, 151, 201, 255]);
+
+        // 上限でクランプされる
+        processor.set_parameter("brightness", serde_json::json!(1.0));
+        let output = processor.process(&color_frame_data(&pixels)).unwrap();
+        assert_eq!(&raster_data(&output)[..4], &[255, 255, 255, 255]);
+    }
+
+    /// 4x4の非対称パターン（各ピクセルの赤チャンネルにインデックスを持たせる）
+    fn indexed_frame_data(size: u32) -> FrameData {
+        let pixels: Vec<[u8; 4]> = (0..size * size)
+            .map(|i| [(i * 10) as u8, 0, 0, 255])
+            .collect();
+        let mut frame_data = color_frame_data(&pixels);
+        if let Some(RenderData::Raster2D(ref mut frame)) = frame_data.render_data {
+            frame.width = size;
+            frame.height = size;
+        }
+        frame_data
+    }
+
+    #[test]
+    fn test_transform_identity_keeps_pixels() {
+        let mut processor = FrameProcessor::new(Uuid::new_v4(), ProcessorType::Transform);
+        processor.set_parameter("scale", serde_json::json!([1.0, 1.0]));
+        processor.set_parameter("rotation", serde_json::json!(0.0));
+
+        let input = indexed_frame_data(4);
+        let output = processor.process(&input).unwrap();
+        assert_eq!(raster_data(&output), raster_data(&input));
+    }
+
+    #[test]
+    fn test_transform_rotate_90_maps_corners() {
+        let mut processor = FrameProcessor::new(Uuid::new_v4(), ProcessorType::Transform);
+        processor.set_parameter("rotation", serde_json::json!(90.0));
+
+        let output = processor.process(&indexed_frame_data(4)).unwrap();
+        let data = raster_data(&output);
+        let red_at = |x: usize, y: usize| data[(y * 4 + x) * 4];
+
+        // 時計回り: 左上→右上→右下→左下→左上
+        assert_eq!(red_at(3, 0), 0); // 元の左上 (0,0)
+        assert_eq!(red_at(3, 3), 30); // 元の右上 (3,0)
+        assert_eq!(red_at(0, 3), 150); // 元の右下 (3,3)
+        assert_eq!(red_at(0, 0), 120); // 元の左下 (0,3)
+        assert!(data.chunks_exact(4).all(|p| p[3] == 255));
+    }
+
+    #[test]
+    fn test_transform_out_of_bounds_is_transparent() {
+        let mut processor = FrameProcessor::new(Uuid::new_v4(), ProcessorType::Transform);
+        processor.set_parameter("position", serde_json::json!([2.0, 0.0]));
+
+        let output = processor.process(&indexed_frame_data(4)).unwrap();
+        let data = raster_data(&output);
+
+        // 左2列は範囲外になる
+        assert_eq!(&data[..4], &[0, 0, 0, 0]);
+        assert_eq!(&data[4..8], &[0, 0, 0, 0]);
+        assert_eq!(&data[8..12], &[0, 0, 0, 255]);
+    }
+
+    fn spatial_source(x: f32, z: f32, attenuation: f32) -> SpatialAudioSource {
+        SpatialAudioSource {
+            position: Vector3 { x, y: 0.0, z },
+            velocity: Vector3 {
+                x: 0.0,
+                y: 0.0,
+                z: 0.0,
+            },
+            audio_data: vec![0.5, -0.5, 0.5, -0.5],
+            sample_rate: 48000,
+            attenuation,
+            doppler_factor: 0.0,
+        }
+    }
+
+    #[test]
+    fn test_spatial_audio_level_nearer_source_dominates() {
+        let listener = AudioListener {
+            position: Vector3 {
+                x: 0.0,
+                y: 0.0,
+                z: 0.0,
+            },
+            orientation: Vector3 {
+                x: 0.0,
+                y: 0.0,
+                z: -1.0,
+            },
+            up: Vector3 {
+                x: 0.0,
+                y: 1.0,
+                z: 0.0,
+            },
+        };
+
+        // 近い音源は右、遠い音源は左
+        let audio_data = UnifiedAudioData::Spatial {
+            sources: vec![
+                spatial_source(1.0, 0.0, 1.0),
+                spatial_source(-8.0, 0.0, 1.0),
+            ],
+            listener: listener.clone(),
+            room_response: None,
+        };
+
+        let level = AudioLevel::from_audio_data(&audio_data);
+        assert!(level.peak_right > level.peak_left);
+        assert!(level.rms_right > level.rms_left);
+        assert!((level.peak_right - 0.5).abs() < 1e-4);
+        assert!(!level.is_clipping);
+
+        // 合算した寄与でクリッピング判定
+        let loud = UnifiedAudioData::Spatial {
+            sources: vec![
+                spatial_source(0.0, -1.0, 2.0),
+                spatial_source(0.0, -1.0, 2.0),
+            ],
+            listener,
+            room_response: None,
+        };
+        assert!(AudioLevel::from_audio_data(&loud).is_clipping);
+    }
 }
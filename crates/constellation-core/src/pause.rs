@@ -0,0 +1,204 @@
+/*
+ * Constellation Studio - Professional Real-time Video Processing
+ * Copyright (c) 2025 MACHIKO LAB
+ *
+ * This program is free software: you can redistribute it and/or modify
+ * it under the terms of the GNU Affero General Public License as published by
+ * the Free Software Foundation, either version 3 of the License, or
+ * (at your option) any later version.
+ *
+ * This program is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+ * GNU Affero General Public License for more details.
+ *
+ * You should have received a copy of the GNU Affero General Public License
+ * along with this program. If not, see <https://www.gnu.org/licenses/>.
+ */
+
+//! エンジンの一時停止と再開
+
+use crate::FrameData;
+use serde::{Deserialize, Serialize};
+use std::collections::VecDeque;
+
+/// 一時停止中の入力フレームの扱い
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum PauseMode {
+    /// 入力を捨て、最後に出力したフレームを出し続ける
+    Freeze,
+    /// 最後の出力を出し続けながら入力を上限まで溜め、再開時に順番に処理する
+    Buffer,
+}
+
+/// 一時停止状態と停止中に溜めたフレームを管理する
+#[derive(Debug)]
+pub struct PauseController {
+    mode: PauseMode,
+    paused: bool,
+    capacity: usize,
+    buffered: VecDeque<FrameData>,
+    // 一時停止中に出し続けるフレーム
+    last_output: Option<FrameData>,
+    dropped_frames: u64,
+}
+
+impl PauseController {
+    /// 既定のバッファ上限（30fpsで10秒分）
+    pub const DEFAULT_CAPACITY: usize = 300;
+
+    pub fn new(mode: PauseMode, capacity: usize) -> Self {
+        Self {
+            mode,
+            paused: false,
+            capacity: capacity.max(1),
+            buffered: VecDeque::new(),
+            last_output: None,
+            dropped_frames: 0,
+        }
+    }
+
+    pub fn mode(&self) -> PauseMode {
+        self.mode
+    }
+
+    /// 一時停止中に変更した場合は次の入力から新しいモードで扱う
+    pub fn set_mode(&mut self, mode: PauseMode) {
+        self.mode = mode;
+    }
+
+    pub fn capacity(&self) -> usize {
+        self.capacity
+    }
+
+    /// 上限を下げた場合は古いフレームから捨てる
+    pub fn set_capacity(&mut self, capacity: usize) {
+        self.capacity = capacity.max(1);
+        self.trim();
+    }
+
+    pub fn is_paused(&self) -> bool {
+        self.paused
+    }
+
+    pub fn buffered_frames(&self) -> usize {
+        self.buffered.len()
+    }
+
+    /// 上限を超えて捨てたフレーム数（一時停止ごとにリセット）
+    pub fn dropped_frames(&self) -> u64 {
+        self.dropped_frames
+    }
+
+    pub fn pause(&mut self) {
+        if !self.paused {
+            self.paused = true;
+            self.dropped_frames = 0;
+        }
+    }
+
+    /// 再開し、停止中に溜めたフレームを到着順に返す
+    pub fn resume(&mut self) -> Vec<FrameData> {
+        self.paused = false;
+        self.buffered.drain(..).collect()
+    }
+
+    /// 処理済みの出力を記録する（一時停止中に出し続けるフレーム）
+    pub fn record_output(&mut self, output: &FrameData) {
+        self.last_output = Some(output.clone());
+    }
+
+    /// 一時停止中の入力を受け取り、代わりに出力するフレームを返す
+    /// まだ出力がない場合は入力をそのまま通す
+    pub fn hold(&mut self, input: &FrameData) -> FrameData {
+        if self.mode == PauseMode::Buffer {
+            self.buffered.push_back(input.clone());
+            self.trim();
+        }
+
+        self.last_output.clone().unwrap_or_else(|| input.clone())
+    }
+
+    fn trim(&mut self) {
+        while self.buffered.len() > self.capacity {
+            self.buffered.pop_front();
+            self.dropped_frames += 1;
+        }
+    }
+}
+
+impl Default for PauseController {
+    fn default() -> Self {
+        Self::new(PauseMode::Freeze, Self::DEFAULT_CAPACITY)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::TallyMetadata;
+    use std::time::Duration;
+
+    fn frame(sequence: u64) -> FrameData {
+        FrameData {
+            render_data: None,
+            audio_data: None,
+            control_data: None,
+            tally_metadata: TallyMetadata::new(),
+            timestamp: Duration::from_millis(sequence * 33),
+            sequence,
+            checksum: None,
+        }
+    }
+
+    #[test]
+    fn test_buffered_frames_replay_in_order() {
+        let mut controller = PauseController::new(PauseMode::Buffer, 10);
+        controller.record_output(&frame(0));
+        controller.pause();
+
+        for sequence in 1..=4 {
+            // 停止中は最後の出力を出し続ける
+            assert_eq!(controller.hold(&frame(sequence)).sequence, 0);
+        }
+        assert_eq!(controller.buffered_frames(), 4);
+
+        let replayed = controller.resume();
+        assert!(!controller.is_paused());
+        assert_eq!(
+            replayed.iter().map(|f| f.sequence).collect::<Vec<_>>(),
+            vec![1, 2, 3, 4]
+        );
+        assert_eq!(controller.buffered_frames(), 0);
+    }
+
+    #[test]
+    fn test_buffer_drops_oldest_beyond_capacity() {
+        let mut controller = PauseController::new(PauseMode::Buffer, 3);
+        controller.pause();
+        for sequence in 0..5 {
+            controller.hold(&frame(sequence));
+        }
+
+        assert_eq!(controller.dropped_frames(), 2);
+        let replayed = controller.resume();
+        assert_eq!(
+            replayed.iter().map(|f| f.sequence).collect::<Vec<_>>(),
+            vec![2, 3, 4]
+        );
+    }
+
+    #[test]
+    fn test_freeze_discards_input() {
+        let mut controller = PauseController::new(PauseMode::Freeze, 10);
+        controller.pause();
+
+        // 出力がまだなければ入力を通す
+        assert_eq!(controller.hold(&frame(1)).sequence, 1);
+        controller.record_output(&frame(7));
+        assert_eq!(controller.hold(&frame(2)).sequence, 7);
+
+        assert_eq!(controller.buffered_frames(), 0);
+        assert!(controller.resume().is_empty());
+    }
+}
@@ -0,0 +1,213 @@
+/*
+ * Constellation Studio - Professional Real-time Video Processing
+ * Copyright (c) 2025 MACHIKO LAB
+ *
+ * This program is free software: you can redistribute it and/or modify
+ * it under the terms of the GNU Affero General Public License as published by
+ * the Free Software Foundation, either version 3 of the License, or
+ * (at your option) any later version.
+ *
+ * This program is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+ * GNU Affero General Public License for more details.
+ *
+ * You should have received a copy of the GNU Affero General Public License
+ * along with this program. If not, see <https://www.gnu.org/licenses/>.
+ */
+
+//! パック形式8bit RGB系フレームの画素アクセスと、フレームのバッファサイズ計算
+
+use crate::{FrameFormat, FrameSize, VideoFormat, VideoFrame};
+
+/// 1画素内のチャンネル配置
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct PixelLayout {
+    pub bytes_per_pixel: usize,
+    pub red: usize,
+    pub green: usize,
+    pub blue: usize,
+    /// アルファを持たないフォーマットではNone（不透明として扱う）
+    pub alpha: Option<usize>,
+}
+
+impl PixelLayout {
+    /// RGB各チャンネルのバイト位置（R, G, Bの順）
+    pub fn color_offsets(&self) -> [usize; 3] {
+        [self.red, self.green, self.blue]
+    }
+
+    pub fn rgb(&self, pixel: &[u8]) -> [u8; 3] {
+        [pixel[self.red], pixel[self.green], pixel[self.blue]]
+    }
+
+    pub fn set_rgb(&self, pixel: &mut [u8], [r, g, b]: [u8; 3]) {
+        pixel[self.red] = r;
+        pixel[self.green] = g;
+        pixel[self.blue] = b;
+    }
+
+    pub fn alpha(&self, pixel: &[u8]) -> u8 {
+        self.alpha.map_or(255, |offset| pixel[offset])
+    }
+
+    /// アルファを持たないフォーマットでは何もしない
+    pub fn set_alpha(&self, pixel: &mut [u8], alpha: u8) {
+        if let Some(offset) = self.alpha {
+            pixel[offset] = alpha;
+        }
+    }
+
+    /// RGBA順の値を書き込む
+    pub fn set_rgba(&self, pixel: &mut [u8], [r, g, b, a]: [u8; 4]) {
+        self.set_rgb(pixel, [r, g, b]);
+        self.set_alpha(pixel, a);
+    }
+
+    /// RGBA順で読み出す
+    pub fn rgba(&self, pixel: &[u8]) -> [u8; 4] {
+        let [r, g, b] = self.rgb(pixel);
+        [r, g, b, self.alpha(pixel)]
+    }
+}
+
+impl VideoFormat {
+    /// パック形式のRGB系フォーマットの画素配置（圧縮・プレーナー形式はNone）
+    pub fn pixel_layout(&self) -> Option<PixelLayout> {
+        let (bytes_per_pixel, red, blue, alpha) = match self {
+            VideoFormat::Rgba8 => (4, 0, 2, Some(3)),
+            VideoFormat::Bgra8 => (4, 2, 0, Some(3)),
+            VideoFormat::Rgb8 => (3, 0, 2, None),
+            VideoFormat::Bgr8 => (3, 2, 0, None),
+            // 16bitは8bit前提の処理に渡さない
+            VideoFormat::Rgba16 | VideoFormat::Yuv420p | VideoFormat::Jpeg | VideoFormat::Png => {
+                return None
+            }
+        };
+
+        Some(PixelLayout {
+            bytes_per_pixel,
+            red,
+            green: 1,
+            blue,
+            alpha,
+        })
+    }
+}
+
+impl VideoFormat {
+    /// メモリプールで扱うフォーマット（圧縮・プレーナー形式はNone）
+    /// プールはバイト数しか見ないので、BGR系は同じサイズのRGB系に対応させる
+    pub fn frame_format(&self) -> Option<FrameFormat> {
+        match self {
+            VideoFormat::Rgba8 => Some(FrameFormat::Rgba8),
+            VideoFormat::Bgra8 => Some(FrameFormat::Bgra8),
+            VideoFormat::Rgb8 | VideoFormat::Bgr8 => Some(FrameFormat::Rgb8),
+            VideoFormat::Rgba16 => Some(FrameFormat::Rgba16),
+            VideoFormat::Yuv420p | VideoFormat::Jpeg | VideoFormat::Png => None,
+        }
+    }
+}
+
+impl VideoFrame {
+    /// プールからバッファを確保するときのサイズ
+    pub fn frame_size(&self) -> Option<FrameSize> {
+        Some(FrameSize {
+            width: self.width,
+            height: self.height,
+            format: self.format.frame_format()?,
+        })
+    }
+
+    /// 画素ごとのバイト列を走査する
+    /// パック形式以外、またはデータが足りない分の画素は返さない
+    pub fn pixel_iter(&self) -> impl Iterator<Item = &[u8]> {
+        let (bytes_per_pixel, pixel_count) = self.packed_extent();
+        self.data.chunks_exact(bytes_per_pixel).take(pixel_count)
+    }
+
+    /// `pixel_iter` の可変版
+    pub fn pixel_iter_mut(&mut self) -> impl Iterator<Item = &mut [u8]> {
+        let (bytes_per_pixel, pixel_count) = self.packed_extent();
+        self.data
+            .chunks_exact_mut(bytes_per_pixel)
+            .take(pixel_count)
+    }
+
+    fn packed_extent(&self) -> (usize, usize) {
+        match self.format.pixel_layout() {
+            Some(layout) => (
+                layout.bytes_per_pixel,
+                self.width as usize * self.height as usize,
+            ),
+            // 非対応フォーマットでは何も走査しない
+            None => (1, 0),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn frame(format: VideoFormat, data: Vec<u8>) -> VideoFrame {
+        VideoFrame {
+            width: 2,
+            height: 1,
+            format,
+            data,
+        }
+    }
+
+    #[test]
+    fn test_layout_reads_rgb_in_channel_order() {
+        let rgb = frame(VideoFormat::Rgb8, vec![10, 20, 30, 40, 50, 60]);
+        let bgr = frame(VideoFormat::Bgr8, vec![30, 20, 10, 60, 50, 40]);
+        for frame in [rgb, bgr] {
+            let layout = frame.format.pixel_layout().unwrap();
+            assert_eq!(layout.bytes_per_pixel, 3);
+            let pixels: Vec<[u8; 4]> = frame.pixel_iter().map(|p| layout.rgba(p)).collect();
+            assert_eq!(pixels, vec![[10, 20, 30, 255], [40, 50, 60, 255]]);
+        }
+    }
+
+    #[test]
+    fn test_pixel_iter_mut_writes_through_layout() {
+        let mut frame = frame(VideoFormat::Bgra8, vec![0; 8]);
+        let layout = frame.format.pixel_layout().unwrap();
+        for pixel in frame.pixel_iter_mut() {
+            layout.set_rgba(pixel, [1, 2, 3, 4]);
+        }
+        assert_eq!(frame.data, vec![3, 2, 1, 4, 3, 2, 1, 4]);
+    }
+
+    #[test]
+    fn test_unsupported_or_short_frames_are_not_iterated() {
+        assert_eq!(
+            frame(VideoFormat::Yuv420p, vec![0; 6]).pixel_iter().count(),
+            0
+        );
+        // 2画素分に満たないデータは1画素だけ走査する
+        assert_eq!(frame(VideoFormat::Rgb8, vec![0; 5]).pixel_iter().count(), 1);
+        // 16bitは8bit前提の走査に乗せない
+        assert_eq!(
+            frame(VideoFormat::Rgba16, vec![0; 16]).pixel_iter().count(),
+            0
+        );
+    }
+
+    #[test]
+    fn test_rgba16_frame_size_is_twice_rgba8() {
+        let rgba8 = frame(VideoFormat::Rgba8, vec![0; 8]).frame_size().unwrap();
+        let rgba16 = frame(VideoFormat::Rgba16, vec![0; 16])
+            .frame_size()
+            .unwrap();
+
+        assert_eq!(rgba16.format, FrameFormat::Rgba16);
+        assert_eq!(rgba16.buffer_size(), 16);
+        assert_eq!(rgba16.buffer_size(), 2 * rgba8.buffer_size());
+        assert!(frame(VideoFormat::Yuv420p, vec![0; 6])
+            .frame_size()
+            .is_none());
+    }
+}
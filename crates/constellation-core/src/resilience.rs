@@ -16,23 +16,29 @@
  * along with this program. If not, see <https://www.gnu.org/licenses/>.
  */
 
-use crate::error::{ConstellationError, ConstellationResult};
-use crate::{ConstellationEngine, FrameData, NodeType, ProcessorType};
+use crate::error::{ConstellationError, ConstellationResult, ErrorSeverity};
+use crate::{FrameData, NodeType, ProcessorType};
 use std::collections::HashMap;
 use std::sync::atomic::{AtomicU64, Ordering};
 use std::sync::Arc;
 use std::time::{Duration, Instant};
 
 /// システム健全性監視および自動復旧システム
+/// エンジン本体は保持せず、エラーとフレーム統計のみから判断する
 pub struct ResilienceManager {
-    #[allow(dead_code)]
-    engine: Arc<ConstellationEngine>,
     health_monitor: HealthMonitor,
     recovery_strategies: HashMap<ErrorCategory, RecoveryStrategy>,
+    recovery_policy: RecoveryPolicy,
     fallback_modes: FallbackModeManager,
     performance_monitor: PerformanceMonitor,
 }
 
+impl Default for ResilienceManager {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
 /// システム健全性監視
 #[derive(Debug)]
 pub struct HealthMonitor {
@@ -92,6 +98,119 @@ pub enum RecoveryStrategy {
     },
 }
 
+/// 再試行パラメータ
+#[derive(Debug, Clone, PartialEq)]
+pub struct RetryPolicy {
+    pub max_attempts: u32,
+    pub delay: Duration,
+    pub backoff_multiplier: f32,
+}
+
+impl RetryPolicy {
+    pub fn new(max_attempts: u32, delay: Duration, backoff_multiplier: f32) -> Self {
+        Self {
+            max_attempts,
+            delay,
+            backoff_multiplier,
+        }
+    }
+
+    /// n回目（0始まり）の再試行前に待つ時間
+    pub fn delay_for_attempt(&self, attempt: u32) -> Duration {
+        let factor = (self.backoff_multiplier.max(0.0) as f64).powi(attempt as i32);
+        Duration::from_nanos((self.delay.as_nanos() as f64 * factor).round() as u64)
+    }
+}
+
+/// エラーカテゴリ・重要度ごとの再試行方針
+///
+/// 判定順: 復旧不可能なエラー → カテゴリ別設定 → 重要度別設定。
+/// どれにも該当しない場合は従来の復旧戦略に委ねる。
+/// 設定値が`None`のものは再試行せずに即座に失敗させる
+#[derive(Debug, Clone)]
+pub struct RecoveryPolicy {
+    /// `is_recoverable()`がfalseのエラーは再試行しない
+    pub fail_fast_unrecoverable: bool,
+    pub categories: HashMap<crate::error::ErrorCategory, Option<RetryPolicy>>,
+    pub severities: HashMap<ErrorSeverity, Option<RetryPolicy>>,
+}
+
+impl RecoveryPolicy {
+    /// 方針を何も設定しない（すべて従来の復旧戦略に委ねる）
+    pub fn empty() -> Self {
+        Self {
+            fail_fast_unrecoverable: false,
+            categories: HashMap::new(),
+            severities: HashMap::new(),
+        }
+    }
+
+    pub fn with_category(
+        mut self,
+        category: crate::error::ErrorCategory,
+        retry: Option<RetryPolicy>,
+    ) -> Self {
+        self.categories.insert(category, retry);
+        self
+    }
+
+    pub fn with_severity(mut self, severity: ErrorSeverity, retry: Option<RetryPolicy>) -> Self {
+        self.severities.insert(severity, retry);
+        self
+    }
+
+    pub fn with_fail_fast_unrecoverable(mut self, fail_fast: bool) -> Self {
+        self.fail_fast_unrecoverable = fail_fast;
+        self
+    }
+
+    /// エラーに対する復旧アクションを決定（未設定ならNone）
+    pub fn recovery_action(&self, error: &ConstellationError) -> Option<RecoveryAction> {
+        if self.fail_fast_unrecoverable && !error.is_recoverable() {
+            return Some(RecoveryAction::FailFast);
+        }
+
+        let retry = self
+            .categories
+            .get(&error.category())
+            .or_else(|| self.severities.get(&error.severity()))?;
+
+        Some(match retry {
+            Some(retry) if retry.max_attempts > 0 => RecoveryAction::Retry {
+                max_attempts: retry.max_attempts,
+                delay: retry.delay,
+                backoff_multiplier: retry.backoff_multiplier,
+            },
+            _ => RecoveryAction::FailFast,
+        })
+    }
+}
+
+impl Default for RecoveryPolicy {
+    fn default() -> Self {
+        use crate::error::ErrorCategory as Category;
+
+        Self::empty()
+            .with_fail_fast_unrecoverable(true)
+            // GPU・デバイスの一時的なエラーはバックオフ付きで再試行
+            .with_category(
+                Category::Hardware,
+                Some(RetryPolicy::new(3, Duration::from_millis(50), 2.0)),
+            )
+            .with_category(
+                Category::Frame,
+                Some(RetryPolicy::new(3, Duration::from_millis(100), 2.0)),
+            )
+            .with_category(
+                Category::Network,
+                Some(RetryPolicy::new(5, Duration::from_millis(250), 2.0)),
+            )
+            // 設定・権限の問題は再試行しても解決しない
+            .with_category(Category::Configuration, None)
+            .with_category(Category::Security, None)
+    }
+}
+
 /// フォールバックモード管理
 #[derive(Debug)]
 pub struct FallbackModeManager {
@@ -127,7 +246,7 @@ pub struct PerformanceMonitor {
 }
 
 impl ResilienceManager {
-    pub fn new(engine: Arc<ConstellationEngine>) -> Self {
+    pub fn new() -> Self {
         let mut recovery_strategies = HashMap::new();
 
         // デフォルト復旧戦略を設定
@@ -167,14 +286,28 @@ impl ResilienceManager {
         );
 
         Self {
-            engine,
             health_monitor: HealthMonitor::new(),
             recovery_strategies,
+            recovery_policy: RecoveryPolicy::default(),
             fallback_modes: FallbackModeManager::new(),
             performance_monitor: PerformanceMonitor::new(),
         }
     }
 
+    /// 再試行方針を差し替える
+    pub fn with_recovery_policy(mut self, policy: RecoveryPolicy) -> Self {
+        self.recovery_policy = policy;
+        self
+    }
+
+    pub fn set_recovery_policy(&mut self, policy: RecoveryPolicy) {
+        self.recovery_policy = policy;
+    }
+
+    pub fn recovery_policy(&self) -> &RecoveryPolicy {
+        &self.recovery_policy
+    }
+
     /// エラー処理とリカバリー実行
     pub fn handle_error(
         &mut self,
@@ -190,6 +323,14 @@ impl ResilienceManager {
         let system_status = self.evaluate_system_health();
         self.health_monitor.update_status(system_status.clone());
 
+        // 再試行方針が設定されていればそれを優先
+        if let Some(action) = self.recovery_policy.recovery_action(error) {
+            if matches!(action, RecoveryAction::FailFast) {
+                tracing::warn!("Failing fast on error: {}", error);
+            }
+            return Ok(action);
+        }
+
         // 復旧戦略を実行
         if let Some(strategy) = self.recovery_strategies.get(&error_category) {
             let strategy = strategy.clone(); // Clone to avoid borrowing issues
@@ -375,6 +516,8 @@ pub enum RecoveryAction {
         preserve_data: bool,
         cleanup_timeout: Duration,
     },
+    /// 再試行せずにエラーを返す
+    FailFast,
     LogAndContinue,
 }
 
@@ -485,8 +628,144 @@ mod tests {
 
     #[test]
     fn test_error_classification() {
-        // テスト用にConstellationEngineのモックを作成する必要があります
-        // 実際の実装では、mock frameworkまたはtest doubleを使用します
+        let manager = ResilienceManager::new();
+        assert_eq!(
+            manager.classify_error(&ConstellationError::GpuProcessingFailed {
+                reason: "timeout".to_string()
+            }),
+            ErrorCategory::GpuProcessing
+        );
+        assert_eq!(
+            manager.classify_error(&ConstellationError::InsufficientMemory {
+                required_bytes: 1024
+            }),
+            ErrorCategory::MemoryAllocation
+        );
+        assert_eq!(
+            manager.classify_error(&ConstellationError::DeviceAccessFailed {
+                device: "camera".to_string(),
+                reason: "busy".to_string()
+            }),
+            ErrorCategory::HardwareFailure
+        );
+    }
+
+    #[test]
+    fn test_handle_error_without_engine() {
+        let mut manager = ResilienceManager::new().with_recovery_policy(RecoveryPolicy::empty());
+
+        // 方針が空なら従来の復旧戦略が使われる
+        let action = manager
+            .handle_error(&ConstellationError::InsufficientMemory {
+                required_bytes: 1024,
+            })
+            .unwrap();
+        assert!(matches!(action, RecoveryAction::QualityReduced));
+        assert_eq!(
+            manager
+                .health_monitor
+                .memory_allocation_failures
+                .load(Ordering::Relaxed),
+            1
+        );
+    }
+
+    #[test]
+    fn test_default_recovery_policy_by_category() {
+        let policy = RecoveryPolicy::default();
+
+        // 一時的なGPUエラーはバックオフ付きで3回再試行
+        let gpu_error = ConstellationError::GpuProcessingFailed {
+            reason: "device lost".to_string(),
+        };
+        match policy.recovery_action(&gpu_error) {
+            Some(RecoveryAction::Retry {
+                max_attempts,
+                delay,
+                backoff_multiplier,
+            }) => {
+                assert_eq!(max_attempts, 3);
+                assert_eq!(delay, Duration::from_millis(50));
+                assert_eq!(backoff_multiplier, 2.0);
+            }
+            other => panic!("expected retry, got {:?}", other),
+        }
+
+        // 非対応ハードウェアは同じカテゴリでも即座に失敗
+        let unsupported = ConstellationError::HardwareNotSupported {
+            hardware: "GPU".to_string(),
+        };
+        assert!(matches!(
+            policy.recovery_action(&unsupported),
+            Some(RecoveryAction::FailFast)
+        ));
+
+        let config_error = ConstellationError::ConfigurationError {
+            reason: "bad".to_string(),
+        };
+        assert!(matches!(
+            policy.recovery_action(&config_error),
+            Some(RecoveryAction::FailFast)
+        ));
+
+        // 未設定のカテゴリは従来の復旧戦略に委ねる
+        let node_error = ConstellationError::NodeNotFound {
+            node_id: uuid::Uuid::new_v4(),
+        };
+        assert!(policy.recovery_action(&node_error).is_none());
+    }
+
+    #[test]
+    fn test_custom_recovery_policy_prefers_category_over_severity() {
+        use crate::error::ErrorCategory as Category;
+
+        let policy = RecoveryPolicy::empty()
+            .with_category(
+                Category::Network,
+                Some(RetryPolicy::new(7, Duration::from_millis(10), 1.5)),
+            )
+            .with_severity(
+                ErrorSeverity::Warning,
+                Some(RetryPolicy::new(1, Duration::from_millis(5), 1.0)),
+            )
+            .with_severity(ErrorSeverity::Critical, None);
+
+        let network = ConstellationError::NetworkConnectionFailed {
+            endpoint: "rtmp://example".to_string(),
+        };
+        assert!(matches!(
+            policy.recovery_action(&network),
+            Some(RecoveryAction::Retry {
+                max_attempts: 7,
+                ..
+            })
+        ));
+
+        let timeout = ConstellationError::FrameProcessingTimeout { timeout_ms: 33 };
+        assert!(matches!(
+            policy.recovery_action(&timeout),
+            Some(RecoveryAction::Retry {
+                max_attempts: 1,
+                ..
+            })
+        ));
+
+        // fail_fast_unrecoverableが無効でも、Noneを設定した重要度は再試行しない
+        let out_of_memory = ConstellationError::InsufficientMemory {
+            required_bytes: 1024,
+        };
+        assert!(matches!(
+            policy.recovery_action(&out_of_memory),
+            Some(RecoveryAction::FailFast)
+        ));
+    }
+
+    #[test]
+    fn test_retry_delay_backoff() {
+        let retry = RetryPolicy::new(3, Duration::from_millis(100), 2.0);
+        assert_eq!(retry.delay_for_attempt(0), Duration::from_millis(100));
+        assert_eq!(retry.delay_for_attempt(1), Duration::from_millis(200));
+        assert_eq!(retry.delay_for_attempt(2), Duration::from_millis(400));
     }
 
     #[test]
@@ -16,22 +16,61 @@
  * along with this program. If not, see <https://www.gnu.org/licenses/>.
  */
 
-use crate::error::{ConstellationError, ErrorCategory, ErrorSeverity};
+use crate::error::{ConstellationError, ConstellationResult, ErrorCategory, ErrorSeverity};
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
+use std::fs::{File, OpenOptions};
+use std::io::{BufWriter, Write};
+use std::path::{Path, PathBuf};
 use std::sync::atomic::{AtomicU64, Ordering};
 use std::time::{Duration, Instant, SystemTime, UNIX_EPOCH};
 use tracing::{debug, error, info, warn};
 use uuid::Uuid;
 
+/// 記録されたエラーの通知先
+pub type ErrorListener = Box<dyn Fn(&TrackedError) + Send + Sync>;
+
 /// 構造化ログとテレメトリシステム
 pub struct TelemetryManager {
     pub metrics_collector: MetricsCollector,
     event_logger: EventLogger,
     performance_tracer: PerformanceTracer,
     error_tracker: ErrorTracker,
+    file_exporter: std::sync::Mutex<Option<TelemetryFileExporter>>,
+    error_listeners: std::sync::Mutex<Vec<ErrorListener>>,
     session_id: Uuid,
     start_time: Instant,
+    // フレームトレースを記録する割合（0.0〜1.0）
+    trace_sample_rate: f32,
+    // トレース対象の判定に使うフレーム数
+    traced_frame_candidates: AtomicU64,
+}
+
+/// テレメトリのファイル出力（改行区切りJSON、サイズでローテーション）
+#[derive(Debug)]
+struct TelemetryFileExporter {
+    base_path: PathBuf,
+    rotate_bytes: u64,
+    file_index: u32,
+    current_path: PathBuf,
+    current_size: u64,
+    writer: BufWriter<File>,
+}
+
+/// ファイルに書き出す1行分のレコード
+#[derive(Debug, Serialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+enum TelemetryRecord<'a> {
+    Metric {
+        timestamp_ms: u64,
+        session_id: Uuid,
+        name: &'a str,
+        value: &'a MetricValue,
+    },
+    SessionStats {
+        timestamp_ms: u64,
+        stats: &'a SessionStats,
+    },
 }
 
 /// メトリクス収集
@@ -41,6 +80,9 @@ pub struct MetricsCollector {
     pub error_count: AtomicU64,
     pub total_processing_time: AtomicU64, // microseconds
     pub memory_usage_peak: AtomicU64,     // bytes
+    pub dropped_frames: AtomicU64,
+    pub checksum_mismatches: AtomicU64,
+    pub last_frame_sequence: std::sync::Mutex<Option<u64>>,
     pub gpu_utilization_samples: std::sync::Mutex<Vec<f32>>,
     pub custom_metrics: std::sync::Mutex<HashMap<String, MetricValue>>,
 }
@@ -175,13 +217,47 @@ impl TelemetryManager {
             event_logger: EventLogger::new(1000), // 1000 events buffer
             performance_tracer: PerformanceTracer::new(),
             error_tracker: ErrorTracker::new(),
+            file_exporter: std::sync::Mutex::new(None),
+            error_listeners: std::sync::Mutex::new(Vec::new()),
             session_id,
             start_time: Instant::now(),
+            trace_sample_rate: 1.0,
+            traced_frame_candidates: AtomicU64::new(0),
         }
     }
 
+    /// フレームトレースを記録する割合を設定する（0.0で無効、1.0で全フレーム）
+    /// メトリクスとセッション統計はサンプリングに関係なく全フレーム分集計される
+    pub fn with_trace_sample_rate(mut self, rate: f32) -> Self {
+        self.trace_sample_rate = if rate.is_nan() {
+            1.0
+        } else {
+            rate.clamp(0.0, 1.0)
+        };
+        self
+    }
+
+    pub fn trace_sample_rate(&self) -> f32 {
+        self.trace_sample_rate
+    }
+
+    /// このフレームをトレースするか（割合に応じて等間隔に選ぶ）
+    fn should_trace_frame(&self) -> bool {
+        let n = self.traced_frame_candidates.fetch_add(1, Ordering::Relaxed) + 1;
+        let rate = self.trace_sample_rate as f64;
+        (n as f64 * rate).floor() > ((n - 1) as f64 * rate).floor()
+    }
+
     /// フレーム処理開始のトレース
-    pub fn start_frame_processing(&self, frame_id: Uuid) -> PerformanceSpanGuard {
+    pub fn start_frame_processing(&self, frame_id: Uuid) -> PerformanceSpanGuard<'_> {
+        if !self.should_trace_frame() {
+            // 記録されないスパン（終了時も何もしない）
+            return PerformanceSpanGuard {
+                span_id: Uuid::nil(),
+                tracer: &self.performance_tracer,
+            };
+        }
+
         let span_id = self.performance_tracer.start_span(
             "frame_processing".to_string(),
             None,
@@ -209,7 +285,7 @@ impl TelemetryManager {
         node_id: Uuid,
         node_type: &str,
         parent_span: Option<Uuid>,
-    ) -> PerformanceSpanGuard {
+    ) -> PerformanceSpanGuard<'_> {
         let span_id = self.performance_tracer.start_span(
             format!("node_processing:{}", node_type),
             parent_span,
@@ -235,6 +311,13 @@ impl TelemetryManager {
         }
     }
 
+    /// エラーが記録されるたびに呼ばれるリスナーを登録する
+    pub fn add_error_listener(&self, listener: ErrorListener) {
+        if let Ok(mut listeners) = self.error_listeners.lock() {
+            listeners.push(listener);
+        }
+    }
+
     /// エラーの記録
     pub fn record_error(&self, error: &ConstellationError, node_id: Option<Uuid>) {
         let tracked_error = TrackedError {
@@ -252,6 +335,11 @@ impl TelemetryManager {
         };
 
         self.error_tracker.record_error(tracked_error.clone());
+        if let Ok(listeners) = self.error_listeners.lock() {
+            for listener in listeners.iter() {
+                listener(&tracked_error);
+            }
+        }
 
         // 構造化ログに記録
         let level = match error.severity() {
@@ -342,6 +430,13 @@ impl TelemetryManager {
             metrics.insert(name.clone(), value.clone());
         }
 
+        self.write_export_record(&TelemetryRecord::Metric {
+            timestamp_ms: unix_timestamp_ms(),
+            session_id: self.session_id,
+            name: &name,
+            value: &value,
+        });
+
         debug!(
             metric_name = name,
             metric_value = ?value,
@@ -349,6 +444,55 @@ impl TelemetryManager {
         );
     }
 
+    /// フレーム連番を記録し、前回からの欠番をドロップとして数える
+    /// 連番が戻った場合（ストリームの再開など）はドロップとみなさない
+    pub fn record_frame_sequence(&self, sequence: u64) {
+        let Ok(mut last) = self.metrics_collector.last_frame_sequence.lock() else {
+            return;
+        };
+
+        if let Some(previous) = *last {
+            if sequence > previous + 1 {
+                let dropped = sequence - previous - 1;
+                self.metrics_collector
+                    .dropped_frames
+                    .fetch_add(dropped, Ordering::Relaxed);
+                warn!(
+                    previous_sequence = previous,
+                    sequence = sequence,
+                    dropped = dropped,
+                    "Frame drop detected"
+                );
+            }
+        }
+        *last = Some(sequence);
+    }
+
+    /// 連番以外で検出したドロップ（フレームクロックの枠落ちなど）を記録
+    pub fn record_dropped_frames(&self, count: u64) {
+        if count == 0 {
+            return;
+        }
+        self.metrics_collector
+            .dropped_frames
+            .fetch_add(count, Ordering::Relaxed);
+        warn!(dropped = count, "Frame slots dropped");
+    }
+
+    /// ノード間で受け渡したフレームのCRC32不一致を記録
+    /// 累計は `frame_checksum_mismatches` メトリクスとしても出力する
+    pub fn record_checksum_mismatch(&self) {
+        let total = self
+            .metrics_collector
+            .checksum_mismatches
+            .fetch_add(1, Ordering::Relaxed)
+            + 1;
+        self.record_metric(
+            "frame_checksum_mismatches".to_string(),
+            MetricValue::Counter(total),
+        );
+    }
+
     /// システム状態の記録
     pub fn record_system_state(&self, cpu_usage: f32, memory_usage: u64, gpu_usage: f32) {
         if let Ok(mut samples) = self.metrics_collector.gpu_utilization_samples.lock() {
@@ -378,6 +522,15 @@ impl TelemetryManager {
         );
     }
 
+    /// 直近に記録されたGPU使用率（未記録ならNone）
+    pub fn gpu_utilization(&self) -> Option<f32> {
+        self.metrics_collector
+            .gpu_utilization_samples
+            .lock()
+            .ok()
+            .and_then(|samples| samples.last().copied())
+    }
+
     /// セッション統計の取得
     pub fn get_session_stats(&self) -> SessionStats {
         let uptime = self.start_time.elapsed();
@@ -404,6 +557,14 @@ impl TelemetryManager {
                 .metrics_collector
                 .memory_usage_peak
                 .load(Ordering::Relaxed),
+            dropped_frames: self
+                .metrics_collector
+                .dropped_frames
+                .load(Ordering::Relaxed),
+            checksum_mismatches: self
+                .metrics_collector
+                .checksum_mismatches
+                .load(Ordering::Relaxed),
         }
     }
 
@@ -418,6 +579,191 @@ impl TelemetryManager {
         let traces = self.performance_tracer.get_completed_spans();
         serde_json::to_string_pretty(&traces)
     }
+
+    /// ファイル出力を開始する
+    /// 以降のメトリクスとセッション統計を1行1レコードのJSONとして追記し、
+    /// ファイルがrotate_bytesを超えたら連番付きの新しいファイルに切り替える
+    pub fn start_file_export(
+        &self,
+        path: impl AsRef<Path>,
+        rotate_bytes: u64,
+    ) -> ConstellationResult<()> {
+        let exporter = TelemetryFileExporter::open(path.as_ref(), rotate_bytes)?;
+        info!(
+            path = %exporter.current_path.display(),
+            rotate_bytes = rotate_bytes,
+            "Telemetry file export started"
+        );
+
+        let mut guard = self.lock_file_exporter()?;
+        if let Some(mut previous) = guard.replace(exporter) {
+            previous.flush()?;
+        }
+        drop(guard);
+
+        self.write_session_stats();
+        Ok(())
+    }
+
+    /// 現在のセッション統計を書き出してバッファをフラッシュする
+    pub fn flush_file_export(&self) -> ConstellationResult<()> {
+        self.write_session_stats();
+        match self.lock_file_exporter()?.as_mut() {
+            Some(exporter) => exporter.flush(),
+            None => Ok(()),
+        }
+    }
+
+    /// 最終的なセッション統計を書き出してファイル出力を終了する
+    pub fn stop_file_export(&self) -> ConstellationResult<()> {
+        self.write_session_stats();
+        let exporter = self.lock_file_exporter()?.take();
+        match exporter {
+            Some(mut exporter) => {
+                exporter.flush()?;
+                info!(
+                    path = %exporter.current_path.display(),
+                    "Telemetry file export stopped"
+                );
+                Ok(())
+            }
+            None => Ok(()),
+        }
+    }
+
+    /// ファイル出力中の書き込み先パス
+    pub fn file_export_path(&self) -> Option<PathBuf> {
+        self.file_exporter
+            .lock()
+            .ok()?
+            .as_ref()
+            .map(|exporter| exporter.current_path.clone())
+    }
+
+    fn lock_file_exporter(
+        &self,
+    ) -> ConstellationResult<std::sync::MutexGuard<'_, Option<TelemetryFileExporter>>> {
+        self.file_exporter
+            .lock()
+            .map_err(|_| ConstellationError::InternalError {
+                reason: "Telemetry file exporter lock poisoned".to_string(),
+            })
+    }
+
+    fn write_session_stats(&self) {
+        let stats = self.get_session_stats();
+        self.write_export_record(&TelemetryRecord::SessionStats {
+            timestamp_ms: unix_timestamp_ms(),
+            stats: &stats,
+        });
+    }
+
+    fn write_export_record(&self, record: &TelemetryRecord<'_>) {
+        let Ok(mut guard) = self.file_exporter.lock() else {
+            return;
+        };
+        if let Some(exporter) = guard.as_mut() {
+            // テレメトリの書き込み失敗で処理を止めない
+            if let Err(e) = exporter.write_record(record) {
+                warn!(error = %e, "Failed to write telemetry record");
+            }
+        }
+    }
+}
+
+impl TelemetryFileExporter {
+    fn open(base_path: &Path, rotate_bytes: u64) -> ConstellationResult<Self> {
+        let (writer, current_size) = Self::open_file(base_path)?;
+        Ok(Self {
+            base_path: base_path.to_path_buf(),
+            rotate_bytes,
+            file_index: 0,
+            current_path: base_path.to_path_buf(),
+            current_size,
+            writer,
+        })
+    }
+
+    fn open_file(path: &Path) -> ConstellationResult<(BufWriter<File>, u64)> {
+        let file = OpenOptions::new()
+            .create(true)
+            .append(true)
+            .open(path)
+            .map_err(|e| file_io_error(path, e))?;
+        let size = file.metadata().map_err(|e| file_io_error(path, e))?.len();
+        Ok((BufWriter::new(file), size))
+    }
+
+    /// n番目のローテーション先（telemetry.jsonl -> telemetry.1.jsonl）
+    fn rotated_path(&self, index: u32) -> PathBuf {
+        let stem = self
+            .base_path
+            .file_stem()
+            .map(|stem| stem.to_string_lossy().into_owned())
+            .unwrap_or_default();
+        let file_name = match self.base_path.extension() {
+            Some(extension) => format!("{stem}.{index}.{}", extension.to_string_lossy()),
+            None => format!("{stem}.{index}"),
+        };
+        self.base_path.with_file_name(file_name)
+    }
+
+    fn write_record(&mut self, record: &TelemetryRecord<'_>) -> ConstellationResult<()> {
+        let mut line =
+            serde_json::to_vec(record).map_err(|e| ConstellationError::InternalError {
+                reason: format!("Failed to serialize telemetry record: {e}"),
+            })?;
+        line.push(b'\n');
+
+        // 上限を超える場合は書き込む前に新しいファイルへ切り替える
+        if self.current_size > 0 && self.current_size + line.len() as u64 > self.rotate_bytes {
+            self.rotate()?;
+        }
+
+        self.writer
+            .write_all(&line)
+            .map_err(|e| file_io_error(&self.current_path, e))?;
+        self.current_size += line.len() as u64;
+        Ok(())
+    }
+
+    fn rotate(&mut self) -> ConstellationResult<()> {
+        self.flush()?;
+        self.file_index += 1;
+        let path = self.rotated_path(self.file_index);
+        let (writer, size) = Self::open_file(&path)?;
+        debug!(path = %path.display(), "Rotated telemetry export file");
+        self.writer = writer;
+        self.current_path = path;
+        self.current_size = size;
+        Ok(())
+    }
+
+    fn flush(&mut self) -> ConstellationResult<()> {
+        self.writer
+            .flush()
+            .map_err(|e| file_io_error(&self.current_path, e))
+    }
+}
+
+impl Drop for TelemetryFileExporter {
+    fn drop(&mut self) {
+        let _ = self.writer.flush();
+    }
+}
+
+fn file_io_error(path: &Path, error: std::io::Error) -> ConstellationError {
+    ConstellationError::FileIoFailed {
+        path: path.display().to_string(),
+        reason: error.to_string(),
+    }
+}
+
+fn unix_timestamp_ms() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .unwrap_or_default()
+        .as_millis() as u64
 }
 
 impl Default for TelemetryManager {
@@ -435,6 +781,8 @@ pub struct SessionStats {
     pub total_processing_time: Duration,
     pub average_frame_time: Option<Duration>,
     pub memory_peak: u64,
+    pub dropped_frames: u64,
+    pub checksum_mismatches: u64,
 }
 
 /// RAII パフォーマンススパンガード
@@ -466,6 +814,9 @@ impl MetricsCollector {
             error_count: AtomicU64::new(0),
             total_processing_time: AtomicU64::new(0),
             memory_usage_peak: AtomicU64::new(0),
+            dropped_frames: AtomicU64::new(0),
+            checksum_mismatches: AtomicU64::new(0),
+            last_frame_sequence: std::sync::Mutex::new(None),
             gpu_utilization_samples: std::sync::Mutex::new(Vec::new()),
             custom_metrics: std::sync::Mutex::new(HashMap::new()),
         }
@@ -610,6 +961,7 @@ impl ErrorTracker {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use std::sync::{Arc, Mutex};
 
     #[test]
     fn test_telemetry_manager_creation() {
@@ -633,6 +985,50 @@ mod tests {
         assert!(completed[0].duration_us > 0);
     }
 
+    #[test]
+    fn test_error_listener_receives_node_attribution() {
+        let manager = TelemetryManager::new();
+        let received = Arc::new(Mutex::new(Vec::new()));
+        let sink = Arc::clone(&received);
+        manager.add_error_listener(Box::new(move |error| {
+            sink.lock().unwrap().push(error.clone());
+        }));
+
+        let node_id = Uuid::new_v4();
+        let error = ConstellationError::FrameProcessingFailed {
+            reason: "テスト".to_string(),
+        };
+        manager.record_error(&error, Some(node_id));
+        manager.record_error(&error, None);
+
+        let received = received.lock().unwrap();
+        assert_eq!(received.len(), 2);
+        assert_eq!(received[0].node_id, Some(node_id));
+        assert_eq!(received[0].category, ErrorCategory::Frame);
+        assert_eq!(received[1].node_id, None);
+    }
+
+    #[test]
+    fn test_trace_sample_rate_limits_recorded_frames() {
+        let telemetry = TelemetryManager::new().with_trace_sample_rate(0.25);
+        for _ in 0..8 {
+            let _span = telemetry.start_frame_processing(Uuid::new_v4());
+        }
+        assert_eq!(telemetry.performance_tracer.get_completed_spans().len(), 2);
+
+        let disabled = TelemetryManager::new().with_trace_sample_rate(0.0);
+        for _ in 0..8 {
+            let _span = disabled.start_frame_processing(Uuid::new_v4());
+        }
+        assert!(disabled.performance_tracer.get_completed_spans().is_empty());
+
+        let full = TelemetryManager::new();
+        for _ in 0..3 {
+            let _span = full.start_frame_processing(Uuid::new_v4());
+        }
+        assert_eq!(full.performance_tracer.get_completed_spans().len(), 3);
+    }
+
     #[test]
     fn test_metrics_collection() {
         let collector = MetricsCollector::new();
@@ -641,4 +1037,117 @@ mod tests {
         collector.frame_count.fetch_add(1, Ordering::Relaxed);
         assert_eq!(collector.frame_count.load(Ordering::Relaxed), 1);
     }
+
+    #[test]
+    fn test_frame_sequence_gap_counts_drops() {
+        let manager = TelemetryManager::new();
+        for sequence in [0, 1, 2, 5, 6, 9] {
+            manager.record_frame_sequence(sequence);
+        }
+        assert_eq!(manager.get_session_stats().dropped_frames, 4);
+
+        // 連番のリセットはドロップに含めない
+        manager.record_frame_sequence(0);
+        manager.record_frame_sequence(1);
+        assert_eq!(manager.get_session_stats().dropped_frames, 4);
+    }
+
+    #[test]
+    fn test_checksum_mismatches_appear_in_session_stats() {
+        let manager = TelemetryManager::new();
+        assert_eq!(manager.get_session_stats().checksum_mismatches, 0);
+
+        manager.record_checksum_mismatch();
+        manager.record_checksum_mismatch();
+        assert_eq!(manager.get_session_stats().checksum_mismatches, 2);
+
+        let metrics = manager.metrics_collector.custom_metrics.lock().unwrap();
+        assert!(matches!(
+            metrics.get("frame_checksum_mismatches"),
+            Some(MetricValue::Counter(2))
+        ));
+    }
+
+    #[test]
+    fn test_gpu_utilization_reports_latest_sample() {
+        let manager = TelemetryManager::new();
+        assert_eq!(manager.gpu_utilization(), None);
+
+        manager.record_system_state(10.0, 1024, 40.0);
+        manager.record_system_state(12.0, 1024, 55.0);
+        assert_eq!(manager.gpu_utilization(), Some(55.0));
+    }
+
+    fn export_dir(name: &str) -> PathBuf {
+        let dir = std::env::temp_dir().join(format!("{name}-{}", Uuid::new_v4()));
+        std::fs::create_dir_all(&dir).unwrap();
+        dir
+    }
+
+    fn read_json_lines(path: &Path) -> Vec<serde_json::Value> {
+        std::fs::read_to_string(path)
+            .unwrap()
+            .lines()
+            .map(|line| serde_json::from_str(line).expect("each line should be valid JSON"))
+            .collect()
+    }
+
+    #[test]
+    fn test_file_export_writes_json_lines() {
+        let dir = export_dir("telemetry-export");
+        let path = dir.join("telemetry.jsonl");
+        let manager = TelemetryManager::new();
+
+        manager.start_file_export(&path, 1024 * 1024).unwrap();
+        for i in 0..5 {
+            manager.record_metric(format!("metric_{i}"), MetricValue::Gauge(i as f64));
+        }
+        manager.flush_file_export().unwrap();
+
+        let records = read_json_lines(&path);
+        // 開始時とフラッシュ時のセッション統計 + メトリクス5件
+        assert_eq!(records.len(), 7);
+        assert_eq!(records[0]["type"], "session_stats");
+        assert_eq!(records[1]["type"], "metric");
+        assert_eq!(records[1]["name"], "metric_0");
+        assert_eq!(records[5]["value"]["Gauge"], 4.0);
+        assert_eq!(
+            records[6]["stats"]["session_id"],
+            manager.get_session_stats().session_id.to_string()
+        );
+
+        manager.stop_file_export().unwrap();
+        assert!(manager.file_export_path().is_none());
+        // 停止後のメトリクスは書き出されない
+        manager.record_metric("after_stop".to_string(), MetricValue::Counter(1));
+        let records = read_json_lines(&path);
+        assert_eq!(records.len(), 8);
+        assert_eq!(records[7]["type"], "session_stats");
+
+        std::fs::remove_dir_all(dir).unwrap();
+    }
+
+    #[test]
+    fn test_file_export_rotates_past_threshold() {
+        let dir = export_dir("telemetry-rotation");
+        let path = dir.join("telemetry.jsonl");
+        let manager = TelemetryManager::new();
+
+        manager.start_file_export(&path, 512).unwrap();
+        for i in 0..20 {
+            manager.record_metric(format!("metric_{i}"), MetricValue::Counter(i));
+        }
+        manager.stop_file_export().unwrap();
+
+        let rotated = dir.join("telemetry.1.jsonl");
+        assert!(path.exists());
+        assert!(rotated.exists());
+        assert!(std::fs::metadata(&path).unwrap().len() <= 512);
+
+        let first = read_json_lines(&path);
+        let second = read_json_lines(&rotated);
+        assert!(!first.is_empty() && !second.is_empty());
+
+        std::fs::remove_dir_all(dir).unwrap();
+    }
 }
@@ -0,0 +1,256 @@
+/*
+ * Constellation Studio - Professional Real-time Video Processing
+ * Copyright (c) 2025 MACHIKO LAB
+ *
+ * This program is free software: you can redistribute it and/or modify
+ * it under the terms of the GNU Affero General Public License as published by
+ * the Free Software Foundation, either version 3 of the License, or
+ * (at your option) any later version.
+ *
+ * This program is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+ * GNU Affero General Public License for more details.
+ *
+ * You should have received a copy of the GNU Affero General Public License
+ * along with this program. If not, see <https://www.gnu.org/licenses/>.
+ */
+
+//! ParameterDefinitionに基づくパラメータ値の検証
+
+use crate::{ParameterDefinition, ParameterType};
+use anyhow::{anyhow, bail, Result};
+use serde_json::Value;
+
+/// 数値パラメータがmin/maxを外れた場合の扱い
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum OutOfRangePolicy {
+    /// エラーとして拒否する
+    #[default]
+    Reject,
+    /// min/maxに丸めて受け付ける
+    Clamp,
+}
+
+/// 値がParameterDefinitionの型と数値範囲を満たすか検証する
+///
+/// 受け付けた値を返す。`OutOfRangePolicy::Clamp`の場合、範囲外の数値は丸めた値になる。
+pub fn validate_parameter(
+    def: &ParameterDefinition,
+    value: Value,
+    policy: OutOfRangePolicy,
+) -> Result<Value> {
+    match &def.parameter_type {
+        ParameterType::Float => {
+            let number = value
+                .as_f64()
+                .ok_or_else(|| type_mismatch(def, "a number", &value))?;
+            let checked = check_range(def, None, number, policy)?;
+            Ok(if checked == number {
+                value
+            } else {
+                Value::from(checked)
+            })
+        }
+        ParameterType::Integer => {
+            let number = value
+                .as_i64()
+                .ok_or_else(|| type_mismatch(def, "an integer", &value))?;
+            let checked = check_range(def, None, number as f64, policy)?;
+            Ok(if checked == number as f64 {
+                value
+            } else {
+                Value::from(checked.round() as i64)
+            })
+        }
+        ParameterType::Boolean if value.is_boolean() => Ok(value),
+        ParameterType::Boolean => Err(type_mismatch(def, "a boolean", &value)),
+        ParameterType::String if value.is_string() => Ok(value),
+        ParameterType::String => Err(type_mismatch(def, "a string", &value)),
+        ParameterType::Enum(options) => {
+            let selected = value
+                .as_str()
+                .ok_or_else(|| type_mismatch(def, "a string", &value))?;
+            if options.iter().any(|option| option == selected) {
+                Ok(value)
+            } else {
+                bail!(
+                    "Parameter '{}' must be one of [{}], got '{}'",
+                    def.name,
+                    options.join(", "),
+                    selected
+                )
+            }
+        }
+        // UIのカラーピッカーは"#rrggbb"形式の文字列を送ってくる
+        ParameterType::Color if value.as_str().is_some_and(is_hex_color) => Ok(value),
+        ParameterType::Color => validate_components(def, value, 3..=4, policy),
+        ParameterType::Vector2 => validate_components(def, value, 2..=2, policy),
+        ParameterType::Vector3 => validate_components(def, value, 3..=3, policy),
+        ParameterType::Vector4 => validate_components(def, value, 4..=4, policy),
+    }
+}
+
+fn validate_components(
+    def: &ParameterDefinition,
+    value: Value,
+    len: std::ops::RangeInclusive<usize>,
+    policy: OutOfRangePolicy,
+) -> Result<Value> {
+    let expected = if len.start() == len.end() {
+        format!("an array of {} numbers", len.start())
+    } else {
+        format!("an array of {} to {} numbers", len.start(), len.end())
+    };
+    let components = value
+        .as_array()
+        .filter(|components| len.contains(&components.len()))
+        .ok_or_else(|| type_mismatch(def, &expected, &value))?;
+
+    let checked = components
+        .iter()
+        .enumerate()
+        .map(|(index, component)| {
+            let number = component
+                .as_f64()
+                .ok_or_else(|| type_mismatch(def, &expected, &value))?;
+            check_range(def, Some(index), number, policy).map(Value::from)
+        })
+        .collect::<Result<Vec<_>>>()?;
+
+    Ok(Value::Array(checked))
+}
+
+/// min/maxはスカラー（全成分に適用）か成分ごとの配列で指定できる
+fn bound_for(bound: Option<&Value>, index: Option<usize>) -> Option<f64> {
+    match (bound?, index) {
+        (Value::Array(components), Some(index)) => components.get(index)?.as_f64(),
+        (bound, _) => bound.as_f64(),
+    }
+}
+
+fn check_range(
+    def: &ParameterDefinition,
+    index: Option<usize>,
+    number: f64,
+    policy: OutOfRangePolicy,
+) -> Result<f64> {
+    if !number.is_finite() {
+        bail!("Parameter '{}' must be finite, got {}", def.name, number);
+    }
+
+    let min = bound_for(def.min_value.as_ref(), index);
+    let max = bound_for(def.max_value.as_ref(), index);
+    let component = index
+        .map(|index| format!(" component {}", index))
+        .unwrap_or_default();
+
+    match (min, max) {
+        (Some(min), _) if number < min => match policy {
+            OutOfRangePolicy::Clamp => Ok(min),
+            OutOfRangePolicy::Reject => bail!(
+                "Parameter '{}'{} is below the minimum of {}, got {}",
+                def.name,
+                component,
+                min,
+                number
+            ),
+        },
+        (_, Some(max)) if number > max => match policy {
+            OutOfRangePolicy::Clamp => Ok(max),
+            OutOfRangePolicy::Reject => bail!(
+                "Parameter '{}'{} is above the maximum of {}, got {}",
+                def.name,
+                component,
+                max,
+                number
+            ),
+        },
+        _ => Ok(number),
+    }
+}
+
+fn is_hex_color(value: &str) -> bool {
+    value
+        .strip_prefix('#')
+        .is_some_and(|hex| matches!(hex.len(), 6 | 8) && hex.chars().all(|c| c.is_ascii_hexdigit()))
+}
+
+fn type_mismatch(def: &ParameterDefinition, expected: &str, value: &Value) -> anyhow::Error {
+    anyhow!(
+        "Parameter '{}' expects {}, got {}",
+        def.name,
+        expected,
+        value
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn brightness_definition() -> ParameterDefinition {
+        ParameterDefinition {
+            name: "brightness".to_string(),
+            parameter_type: ParameterType::Float,
+            default_value: serde_json::Value::from(1.0),
+            min_value: Some(serde_json::Value::from(0.0)),
+            max_value: Some(serde_json::Value::from(2.0)),
+            description: "Brightness adjustment".to_string(),
+        }
+    }
+
+    #[test]
+    fn test_validate_parameter_rejects_out_of_range_float() {
+        let def = brightness_definition();
+
+        let above = validate_parameter(&def, serde_json::json!(2.5), OutOfRangePolicy::Reject);
+        assert!(above.unwrap_err().to_string().contains("maximum"));
+
+        let below = validate_parameter(&def, serde_json::json!(-0.5), OutOfRangePolicy::Reject);
+        assert!(below.unwrap_err().to_string().contains("minimum"));
+
+        let within = validate_parameter(&def, serde_json::json!(1.5), OutOfRangePolicy::Reject);
+        assert_eq!(within.unwrap(), serde_json::json!(1.5));
+    }
+
+    #[test]
+    fn test_validate_parameter_clamps_out_of_range_float() {
+        let def = brightness_definition();
+
+        let above = validate_parameter(&def, serde_json::json!(2.5), OutOfRangePolicy::Clamp);
+        assert_eq!(above.unwrap(), serde_json::json!(2.0));
+
+        let below = validate_parameter(&def, serde_json::json!(-0.5), OutOfRangePolicy::Clamp);
+        assert_eq!(below.unwrap(), serde_json::json!(0.0));
+    }
+
+    #[test]
+    fn test_validate_parameter_checks_type() {
+        let def = brightness_definition();
+        assert!(
+            validate_parameter(&def, serde_json::json!("bright"), OutOfRangePolicy::Clamp).is_err()
+        );
+
+        let scale = ParameterDefinition {
+            name: "scale".to_string(),
+            parameter_type: ParameterType::Vector2,
+            default_value: serde_json::json!([1.0, 1.0]),
+            min_value: Some(serde_json::json!([0.1, 0.1])),
+            max_value: Some(serde_json::json!([10.0, 10.0])),
+            description: "Scale".to_string(),
+        };
+        assert!(
+            validate_parameter(&scale, serde_json::json!([1.0]), OutOfRangePolicy::Clamp).is_err()
+        );
+        assert_eq!(
+            validate_parameter(
+                &scale,
+                serde_json::json!([0.0, 20.0]),
+                OutOfRangePolicy::Clamp
+            )
+            .unwrap(),
+            serde_json::json!([0.1, 10.0])
+        );
+    }
+}
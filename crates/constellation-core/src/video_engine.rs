@@ -0,0 +1,205 @@
+use crate::{
+    BackendKind, ConnectionType, ConstellationError, ConstellationResult, ErrorListener, FrameData,
+    GraphIssue, Node, NodeConfig, NodeGraph, NodePorts, NodeType, OutOfRangePolicy,
+    ParameterDefinition, PauseMode, SessionStats, TelemetryManager,
+};
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use uuid::Uuid;
+
+/// エンジンの稼働状態のスナップショット
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct EngineStatus {
+    pub running: bool,
+    pub backend: BackendKind,
+    pub paused: bool,
+    pub pause_mode: PauseMode,
+    /// `PauseMode::Buffer` で停止中に溜めているフレーム数
+    pub buffered_frames: usize,
+    pub frame_count: u64,
+    pub node_count: usize,
+    pub connection_count: usize,
+}
+
+/// Webサーバーから操作するエンジンの共通インターフェース
+///
+/// 実機の `ConstellationEngine` と、GPUなしで動く開発用モックの両方が実装する。
+/// グラフ操作とテレメトリは `graph` / `telemetry` を使った既定実装を持つ。
+pub trait VideoEngine: Send {
+    fn start(&mut self) -> ConstellationResult<()>;
+
+    fn stop(&mut self) -> ConstellationResult<()>;
+
+    fn status(&self) -> EngineStatus;
+
+    fn create_node(&mut self, node_type: NodeType, config: NodeConfig)
+        -> ConstellationResult<Uuid>;
+
+    fn connect(
+        &mut self,
+        source_id: Uuid,
+        target_id: Uuid,
+        connection_type: ConnectionType,
+    ) -> ConstellationResult<()>;
+
+    fn pause(&mut self);
+
+    /// 再開し、停止中に溜めていたフレームを返す
+    fn resume(&mut self) -> ConstellationResult<Vec<FrameData>>;
+
+    fn graph(&self) -> &NodeGraph;
+
+    fn graph_mut(&mut self) -> &mut NodeGraph;
+
+    fn telemetry(&self) -> &TelemetryManager;
+
+    fn remove_node(&mut self, node_id: Uuid) -> ConstellationResult<()> {
+        self.graph_mut().remove_node(&node_id)
+    }
+
+    fn disconnect(
+        &mut self,
+        source_id: Uuid,
+        target_id: Uuid,
+        connection_type: ConnectionType,
+    ) -> ConstellationResult<()> {
+        self.graph_mut()
+            .disconnect_nodes(source_id, target_id, connection_type)
+    }
+
+    /// ノードを複製して新しいIDを返す（接続は複製しない）
+    fn duplicate_node(&mut self, node_id: Uuid) -> ConstellationResult<Uuid> {
+        self.graph_mut().duplicate_node(&node_id)
+    }
+
+    fn node(&self, node_id: Uuid) -> Option<&Node> {
+        self.graph().get_node(&node_id)
+    }
+
+    /// ノードのパラメータを検証して更新し、受け付けた値を返す
+    ///
+    /// `set_node_parameter_definitions` で登録した定義とグラフの `OutOfRangePolicy` で検証する
+    fn set_node_parameter(
+        &mut self,
+        node_id: Uuid,
+        parameter: String,
+        value: serde_json::Value,
+    ) -> ConstellationResult<serde_json::Value> {
+        self.graph_mut()
+            .set_node_parameter(&node_id, &parameter, value)
+    }
+
+    /// ノード実装が宣言するパラメータ定義を登録する
+    fn set_node_parameter_definitions(
+        &mut self,
+        node_id: Uuid,
+        definitions: HashMap<String, ParameterDefinition>,
+    ) -> ConstellationResult<()> {
+        let node = self
+            .graph_mut()
+            .get_node_mut(&node_id)
+            .ok_or(ConstellationError::NodeNotFound { node_id })?;
+        node.parameter_definitions = Some(definitions);
+        Ok(())
+    }
+
+    /// 範囲外のパラメータ値を拒否するか丸めるかを切り替える
+    fn set_parameter_policy(&mut self, policy: OutOfRangePolicy) {
+        self.graph_mut().set_parameter_policy(policy);
+    }
+
+    /// ノード実装が宣言する入出力の接続種別を登録する
+    fn set_node_ports(&mut self, node_id: Uuid, ports: NodePorts) -> ConstellationResult<()> {
+        let node = self
+            .graph_mut()
+            .get_node_mut(&node_id)
+            .ok_or(ConstellationError::NodeNotFound { node_id })?;
+        node.ports = Some(ports);
+        Ok(())
+    }
+
+    fn validate_graph(&self) -> Vec<GraphIssue> {
+        self.graph().validate()
+    }
+
+    /// ノード処理の失敗を発生元ノード付きで記録する（エラーリスナーにも通知される）
+    fn report_node_error(&self, node_id: Uuid, error: &ConstellationError) {
+        self.telemetry().record_error(error, Some(node_id));
+    }
+
+    fn add_error_listener(&self, listener: ErrorListener) {
+        self.telemetry().add_error_listener(listener);
+    }
+
+    fn session_stats(&self) -> SessionStats {
+        self.telemetry().get_session_stats()
+    }
+
+    fn gpu_utilization(&self) -> Option<f32> {
+        self.telemetry().gpu_utilization()
+    }
+}
+
+impl VideoEngine for crate::ConstellationEngine {
+    fn start(&mut self) -> ConstellationResult<()> {
+        self.running = true;
+        tracing::info!(backend = ?self.backend(), "Engine started");
+        Ok(())
+    }
+
+    fn stop(&mut self) -> ConstellationResult<()> {
+        self.running = false;
+        tracing::info!("Engine stopped");
+        Ok(())
+    }
+
+    fn status(&self) -> EngineStatus {
+        EngineStatus {
+            running: self.running,
+            backend: self.backend(),
+            paused: self.is_paused(),
+            pause_mode: self.pause_mode(),
+            buffered_frames: self.paused_frame_count(),
+            frame_count: self.telemetry_manager.get_session_stats().frame_count,
+            node_count: self.node_graph.node_ids().count(),
+            connection_count: self.node_graph.get_connections().len(),
+        }
+    }
+
+    fn create_node(
+        &mut self,
+        node_type: NodeType,
+        config: NodeConfig,
+    ) -> ConstellationResult<Uuid> {
+        self.add_node(node_type, config)
+    }
+
+    fn connect(
+        &mut self,
+        source_id: Uuid,
+        target_id: Uuid,
+        connection_type: ConnectionType,
+    ) -> ConstellationResult<()> {
+        self.connect_nodes(source_id, target_id, connection_type)
+    }
+
+    fn pause(&mut self) {
+        crate::ConstellationEngine::pause(self);
+    }
+
+    fn resume(&mut self) -> ConstellationResult<Vec<FrameData>> {
+        crate::ConstellationEngine::resume(self)
+    }
+
+    fn graph(&self) -> &NodeGraph {
+        &self.node_graph
+    }
+
+    fn graph_mut(&mut self) -> &mut NodeGraph {
+        &mut self.node_graph
+    }
+
+    fn telemetry(&self) -> &TelemetryManager {
+        &self.telemetry_manager
+    }
+}
@@ -0,0 +1,459 @@
+/*
+ * Constellation Studio - Professional Real-time Video Processing
+ * Copyright (c) 2025 MACHIKO LAB
+ *
+ * This program is free software: you can redistribute it and/or modify
+ * it under the terms of the GNU Affero General Public License as published by
+ * the Free Software Foundation, either version 3 of the License, or
+ * (at your option) any later version.
+ *
+ * This program is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+ * GNU Affero General Public License for more details.
+ *
+ * You should have received a copy of the GNU Affero General Public License
+ * along with this program. If not, see <https://www.gnu.org/licenses/>.
+ */
+
+//! System audio device playback and capture
+//!
+//! `cpal` streams are not `Send`, so each stream lives on its own thread and
+//! exchanges interleaved `f32` samples with the node through a [`SampleRing`].
+
+use anyhow::Result;
+use std::collections::VecDeque;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{mpsc, Arc, Mutex};
+use std::thread::JoinHandle;
+
+/// Device name that selects the host's default device
+pub const DEFAULT_DEVICE: &str = "default";
+
+/// Jitter buffer between the pipeline and an audio device callback
+///
+/// Playback waits until `prefill` samples are queued before it starts draining,
+/// and goes back to waiting after an underrun so one late block costs a single
+/// gap instead of a crackle on every callback.
+#[derive(Debug)]
+pub struct SampleRing {
+    state: Mutex<RingState>,
+    capacity: usize,
+    prefill: usize,
+}
+
+#[derive(Debug, Default)]
+struct RingState {
+    samples: VecDeque<f32>,
+    primed: bool,
+    underruns: u64,
+    overruns: u64,
+}
+
+impl SampleRing {
+    pub fn new(capacity: usize, prefill: usize) -> Self {
+        let capacity = capacity.max(1);
+        Self {
+            state: Mutex::new(RingState {
+                samples: VecDeque::with_capacity(capacity),
+                ..RingState::default()
+            }),
+            capacity,
+            prefill: prefill.min(capacity),
+        }
+    }
+
+    /// Queue samples, dropping the oldest ones when full. Returns how many were dropped.
+    pub fn push(&self, samples: &[f32]) -> usize {
+        let mut state = self.state.lock().unwrap();
+        state.samples.extend(samples);
+        let dropped = state.samples.len().saturating_sub(self.capacity);
+        if dropped > 0 {
+            state.samples.drain(..dropped);
+            state.overruns += 1;
+        }
+        dropped
+    }
+
+    /// Fill `out` from the queue, padding with silence.
+    /// Returns how many real samples were written.
+    pub fn pop_into(&self, out: &mut [f32]) -> usize {
+        let mut state = self.state.lock().unwrap();
+        if !state.primed {
+            if state.samples.len() < self.prefill.max(1) {
+                out.fill(0.0);
+                return 0;
+            }
+            state.primed = true;
+        }
+
+        let available = state.samples.len().min(out.len());
+        for (slot, sample) in out.iter_mut().zip(state.samples.drain(..available)) {
+            *slot = sample;
+        }
+        out[available..].fill(0.0);
+        if available < out.len() {
+            state.underruns += 1;
+            state.primed = false;
+        }
+        available
+    }
+
+    /// Take up to `max` queued samples without waiting for the prefill
+    pub fn drain(&self, max: usize) -> Vec<f32> {
+        let mut state = self.state.lock().unwrap();
+        let count = state.samples.len().min(max);
+        state.samples.drain(..count).collect()
+    }
+
+    pub fn len(&self) -> usize {
+        self.state.lock().unwrap().samples.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    pub fn capacity(&self) -> usize {
+        self.capacity
+    }
+
+    /// Times playback ran dry after it had started
+    pub fn underruns(&self) -> u64 {
+        self.state.lock().unwrap().underruns
+    }
+
+    /// Times queued samples were discarded because the buffer was full
+    pub fn overruns(&self) -> u64 {
+        self.state.lock().unwrap().overruns
+    }
+
+    pub fn clear(&self) {
+        let mut state = self.state.lock().unwrap();
+        state.samples.clear();
+        state.primed = false;
+    }
+}
+
+/// Format the device stream runs at
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DeviceConfig {
+    pub device_name: String,
+    pub sample_rate: u32,
+    pub channels: u16,
+}
+
+/// An open playback or capture stream, owned by a dedicated thread
+pub struct DeviceStream {
+    config: DeviceConfig,
+    ring: Arc<SampleRing>,
+    disconnected: Arc<AtomicBool>,
+    // Dropping the sender tells the stream thread to close the stream
+    stop: Option<mpsc::Sender<()>>,
+    thread: Option<JoinHandle<()>>,
+}
+
+impl DeviceStream {
+    /// Open `device` (or the default output) for playback.
+    ///
+    /// The ring holds `buffer` worth of audio at the device format and playback
+    /// starts once half of it is queued.
+    pub fn open_output(device: &str, buffer: std::time::Duration) -> Result<Self> {
+        Self::spawn(device, Direction::Output, buffer)
+    }
+
+    /// Open `device` (or the default input) for capture, keeping up to `buffer`
+    /// of unread audio
+    pub fn open_input(device: &str, buffer: std::time::Duration) -> Result<Self> {
+        Self::spawn(device, Direction::Input, buffer)
+    }
+
+    pub fn config(&self) -> &DeviceConfig {
+        &self.config
+    }
+
+    pub fn ring(&self) -> &Arc<SampleRing> {
+        &self.ring
+    }
+
+    /// True once the device reported that it went away
+    pub fn is_disconnected(&self) -> bool {
+        self.disconnected.load(Ordering::Relaxed)
+    }
+
+    fn spawn(device: &str, direction: Direction, buffer: std::time::Duration) -> Result<Self> {
+        let device = device.to_string();
+        let disconnected = Arc::new(AtomicBool::new(false));
+        let (ready_sender, ready_receiver) = mpsc::sync_channel(1);
+        let (stop, stop_receiver) = mpsc::channel::<()>();
+
+        let thread_disconnected = disconnected.clone();
+        let thread = std::thread::Builder::new()
+            .name(format!("audio-{}", direction.name()))
+            .spawn(move || {
+                match backend::open(&device, direction, buffer, thread_disconnected) {
+                    // The stream stays open until this arm returns
+                    Ok((_stream, config, ring)) => {
+                        let _ = ready_sender.send(Ok((config, ring)));
+                        // Blocks until the node drops its sender
+                        let _ = stop_receiver.recv();
+                    }
+                    Err(e) => {
+                        let _ = ready_sender.send(Err(e));
+                    }
+                }
+            })?;
+
+        let (config, ring) = ready_receiver
+            .recv()
+            .map_err(|_| anyhow::anyhow!("Audio {} thread exited", direction.name()))??;
+
+        Ok(Self {
+            config,
+            ring,
+            disconnected,
+            stop: Some(stop),
+            thread: Some(thread),
+        })
+    }
+}
+
+impl Drop for DeviceStream {
+    fn drop(&mut self) {
+        self.stop.take();
+        if let Some(thread) = self.thread.take() {
+            let _ = thread.join();
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Direction {
+    Input,
+    Output,
+}
+
+impl Direction {
+    fn name(self) -> &'static str {
+        match self {
+            Direction::Input => "input",
+            Direction::Output => "output",
+        }
+    }
+}
+
+/// Names of the playback devices the host offers
+pub fn output_devices() -> Vec<String> {
+    backend::device_names(Direction::Output)
+}
+
+/// Names of the capture devices the host offers
+pub fn input_devices() -> Vec<String> {
+    backend::device_names(Direction::Input)
+}
+
+#[cfg(feature = "audio-device")]
+mod backend {
+    use super::{DeviceConfig, Direction, SampleRing, DEFAULT_DEVICE};
+    use anyhow::{anyhow, bail, Result};
+    use cpal::traits::{DeviceTrait, HostTrait, StreamTrait};
+    use cpal::{FromSample, SampleFormat, SizedSample};
+    use std::sync::atomic::{AtomicBool, Ordering};
+    use std::sync::Arc;
+    use std::time::Duration;
+    use tracing::{info, warn};
+
+    pub(super) fn device_names(direction: Direction) -> Vec<String> {
+        let host = cpal::default_host();
+        let devices = match direction {
+            Direction::Input => host.input_devices().map(|d| d.collect::<Vec<_>>()),
+            Direction::Output => host.output_devices().map(|d| d.collect::<Vec<_>>()),
+        };
+        devices
+            .map(|devices| devices.iter().filter_map(|d| d.name().ok()).collect())
+            .unwrap_or_default()
+    }
+
+    fn find_device(host: &cpal::Host, name: &str, direction: Direction) -> Result<cpal::Device> {
+        if name.is_empty() || name == DEFAULT_DEVICE {
+            let device = match direction {
+                Direction::Input => host.default_input_device(),
+                Direction::Output => host.default_output_device(),
+            };
+            return device.ok_or_else(|| anyhow!("No default audio {} device", direction.name()));
+        }
+
+        let devices: Vec<cpal::Device> = match direction {
+            Direction::Input => host.input_devices()?.collect(),
+            Direction::Output => host.output_devices()?.collect(),
+        };
+        devices
+            .into_iter()
+            .find(|device| device.name().map(|n| n.contains(name)).unwrap_or(false))
+            .ok_or_else(|| anyhow!("Audio {} device not found: {}", direction.name(), name))
+    }
+
+    pub(super) fn open(
+        name: &str,
+        direction: Direction,
+        buffer: Duration,
+        disconnected: Arc<AtomicBool>,
+    ) -> Result<(cpal::Stream, DeviceConfig, Arc<SampleRing>)> {
+        let host = cpal::default_host();
+        let device = find_device(&host, name, direction)?;
+        let device_name = device.name().unwrap_or_else(|_| name.to_string());
+        let supported = match direction {
+            Direction::Input => device.default_input_config()?,
+            Direction::Output => device.default_output_config()?,
+        };
+        let sample_format = supported.sample_format();
+        let stream_config: cpal::StreamConfig = supported.into();
+        let config = DeviceConfig {
+            device_name,
+            sample_rate: stream_config.sample_rate.0,
+            channels: stream_config.channels,
+        };
+
+        // Whole frames, so dropping the oldest samples never splits one
+        let frames = (buffer.as_secs_f64() * config.sample_rate as f64).ceil() as usize;
+        let capacity = frames.max(1) * config.channels as usize;
+        let ring = Arc::new(match direction {
+            Direction::Input => SampleRing::new(capacity, 0),
+            Direction::Output => SampleRing::new(capacity, capacity / 2),
+        });
+
+        let error_name = config.device_name.clone();
+        let on_error = move |error: cpal::StreamError| {
+            warn!("Audio device {} error: {}", error_name, error);
+            if matches!(error, cpal::StreamError::DeviceNotAvailable) {
+                disconnected.store(true, Ordering::Relaxed);
+            }
+        };
+
+        let stream = match (direction, sample_format) {
+            (Direction::Output, SampleFormat::F32) => {
+                output_stream::<f32>(&device, &stream_config, ring.clone(), on_error)
+            }
+            (Direction::Output, SampleFormat::F64) => {
+                output_stream::<f64>(&device, &stream_config, ring.clone(), on_error)
+            }
+            (Direction::Output, SampleFormat::I16) => {
+                output_stream::<i16>(&device, &stream_config, ring.clone(), on_error)
+            }
+            (Direction::Output, SampleFormat::I32) => {
+                output_stream::<i32>(&device, &stream_config, ring.clone(), on_error)
+            }
+            (Direction::Output, SampleFormat::U16) => {
+                output_stream::<u16>(&device, &stream_config, ring.clone(), on_error)
+            }
+            (Direction::Output, SampleFormat::U8) => {
+                output_stream::<u8>(&device, &stream_config, ring.clone(), on_error)
+            }
+            (Direction::Input, SampleFormat::F32) => {
+                input_stream::<f32>(&device, &stream_config, ring.clone(), on_error)
+            }
+            (Direction::Input, SampleFormat::F64) => {
+                input_stream::<f64>(&device, &stream_config, ring.clone(), on_error)
+            }
+            (Direction::Input, SampleFormat::I16) => {
+                input_stream::<i16>(&device, &stream_config, ring.clone(), on_error)
+            }
+            (Direction::Input, SampleFormat::I32) => {
+                input_stream::<i32>(&device, &stream_config, ring.clone(), on_error)
+            }
+            (Direction::Input, SampleFormat::U16) => {
+                input_stream::<u16>(&device, &stream_config, ring.clone(), on_error)
+            }
+            (Direction::Input, SampleFormat::U8) => {
+                input_stream::<u8>(&device, &stream_config, ring.clone(), on_error)
+            }
+            (_, format) => bail!("Unsupported audio sample format {:?}", format),
+        }?;
+        stream.play()?;
+
+        info!(
+            "Opened audio {} {} ({} Hz, {} channels, {:?})",
+            direction.name(),
+            config.device_name,
+            config.sample_rate,
+            config.channels,
+            sample_format
+        );
+        Ok((stream, config, ring))
+    }
+
+    fn output_stream<T>(
+        device: &cpal::Device,
+        config: &cpal::StreamConfig,
+        ring: Arc<SampleRing>,
+        on_error: impl FnMut(cpal::StreamError) + Send + 'static,
+    ) -> Result<cpal::Stream>
+    where
+        T: SizedSample + FromSample<f32>,
+    {
+        // Grows to the callback size once, then is reused
+        let mut scratch = Vec::new();
+        let stream = device.build_output_stream(
+            config,
+            move |data: &mut [T], _: &cpal::OutputCallbackInfo| {
+                scratch.resize(data.len(), 0.0f32);
+                ring.pop_into(&mut scratch);
+                for (out, &sample) in data.iter_mut().zip(&scratch) {
+                    *out = T::from_sample(sample);
+                }
+            },
+            on_error,
+            None,
+        )?;
+        Ok(stream)
+    }
+
+    fn input_stream<T>(
+        device: &cpal::Device,
+        config: &cpal::StreamConfig,
+        ring: Arc<SampleRing>,
+        on_error: impl FnMut(cpal::StreamError) + Send + 'static,
+    ) -> Result<cpal::Stream>
+    where
+        T: SizedSample,
+        f32: FromSample<T>,
+    {
+        let mut scratch = Vec::new();
+        let stream = device.build_input_stream(
+            config,
+            move |data: &[T], _: &cpal::InputCallbackInfo| {
+                scratch.clear();
+                scratch.extend(data.iter().map(|sample| sample.to_sample::<f32>()));
+                ring.push(&scratch);
+            },
+            on_error,
+            None,
+        )?;
+        Ok(stream)
+    }
+}
+
+#[cfg(not(feature = "audio-device"))]
+mod backend {
+    use super::{DeviceConfig, Direction, SampleRing};
+    use anyhow::Result;
+    use std::sync::atomic::AtomicBool;
+    use std::sync::Arc;
+    use std::time::Duration;
+
+    pub(super) fn device_names(_direction: Direction) -> Vec<String> {
+        Vec::new()
+    }
+
+    pub(super) fn open(
+        _name: &str,
+        direction: Direction,
+        _buffer: Duration,
+        _disconnected: Arc<AtomicBool>,
+    ) -> Result<((), DeviceConfig, Arc<SampleRing>)> {
+        anyhow::bail!(
+            "Audio {} requires building with the `audio-device` feature",
+            direction.name()
+        )
+    }
+}
@@ -0,0 +1,484 @@
+/*
+ * Constellation Studio - Professional Real-time Video Processing
+ * Copyright (c) 2025 MACHIKO LAB
+ *
+ * This program is free software: you can redistribute it and/or modify
+ * it under the terms of the GNU Affero General Public License as published by
+ * the Free Software Foundation, either version 3 of the License, or
+ * (at your option) any later version.
+ *
+ * This program is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+ * GNU Affero General Public License for more details.
+ *
+ * You should have received a copy of the GNU Affero General Public License
+ * along with this program. If not, see <https://www.gnu.org/licenses/>.
+ */
+
+//! WAV file playback and recording nodes
+
+use crate::{NodeProcessor, NodeProperties, ParameterDefinition, ParameterType};
+use anyhow::{bail, Context, Result};
+use constellation_audio::{AudioProcessor, StreamResampler};
+use constellation_core::*;
+use serde_json::Value;
+use std::collections::HashMap;
+use std::fs::File;
+use std::io::BufWriter;
+use std::path::Path;
+use std::time::Duration;
+use tracing::{info, warn};
+use uuid::Uuid;
+
+/// Channel count of the audio the file input delivers to the pipeline
+const PIPELINE_CHANNELS: u16 = 2;
+
+/// Decode a WAV file into interleaved `f32` samples in the -1.0..1.0 range
+pub fn read_wav(path: impl AsRef<Path>) -> Result<AudioFrame> {
+    let path = path.as_ref();
+    let reader = hound::WavReader::open(path)
+        .with_context(|| format!("Failed to open WAV file {}", path.display()))?;
+    let spec = reader.spec();
+
+    let samples = match spec.sample_format {
+        hound::SampleFormat::Float => reader
+            .into_samples::<f32>()
+            .collect::<std::result::Result<Vec<_>, _>>()?,
+        hound::SampleFormat::Int => {
+            let scale = 1.0 / (1u64 << (spec.bits_per_sample - 1)) as f32;
+            reader
+                .into_samples::<i32>()
+                .map(|sample| sample.map(|s| s as f32 * scale))
+                .collect::<std::result::Result<Vec<_>, _>>()?
+        }
+    };
+
+    Ok(AudioFrame {
+        sample_rate: spec.sample_rate,
+        channels: spec.channels,
+        samples,
+    })
+}
+
+/// Plays a WAV file into the pipeline as stereo audio blocks.
+///
+/// The whole file is decoded on start and converted to the pipeline sample
+/// rate, so playback never touches the disk on the processing thread.
+pub struct AudioFileInputNode {
+    id: Uuid,
+    config: NodeConfig,
+    properties: NodeProperties,
+    // Interleaved stereo at the pipeline rate; None until the file is loaded
+    samples: Option<Vec<f32>>,
+    // Next frame (sample per channel) to deliver
+    position: usize,
+    // Blocks delivered so far; the next block's frame sequence is this + 1
+    blocks: u64,
+    // Frames delivered so far, used for the presentation time
+    frames_delivered: u64,
+}
+
+impl AudioFileInputNode {
+    pub fn new(id: Uuid, config: NodeConfig) -> Result<Self> {
+        let mut parameters = HashMap::new();
+        parameters.insert(
+            "file_path".to_string(),
+            ParameterDefinition {
+                name: "File Path".to_string(),
+                parameter_type: ParameterType::String,
+                default_value: Value::String("".to_string()),
+                min_value: None,
+                max_value: None,
+                description: "Path to WAV file".to_string(),
+            },
+        );
+        parameters.insert(
+            "loop".to_string(),
+            ParameterDefinition {
+                name: "Loop".to_string(),
+                parameter_type: ParameterType::Boolean,
+                default_value: Value::Bool(false),
+                min_value: None,
+                max_value: None,
+                description: "Restart from the beginning at the end of the file".to_string(),
+            },
+        );
+        parameters.insert(
+            "sample_rate".to_string(),
+            ParameterDefinition {
+                name: "Sample Rate".to_string(),
+                parameter_type: ParameterType::Integer,
+                default_value: Value::from(48000),
+                min_value: Some(Value::from(8000)),
+                max_value: Some(Value::from(192000)),
+                description: "Pipeline sample rate the file is converted to".to_string(),
+            },
+        );
+        parameters.insert(
+            "block_size".to_string(),
+            ParameterDefinition {
+                name: "Block Size".to_string(),
+                parameter_type: ParameterType::Integer,
+                default_value: Value::from(1024),
+                min_value: Some(Value::from(16)),
+                max_value: Some(Value::from(16384)),
+                description: "Frames (samples per channel) delivered per processed frame"
+                    .to_string(),
+            },
+        );
+
+        let properties = NodeProperties {
+            id,
+            name: "Audio File Input".to_string(),
+            node_type: NodeType::Audio(AudioType::FileInput),
+            input_types: vec![],
+            output_types: vec![ConnectionType::Audio],
+            parameters,
+            enabled: true,
+        };
+
+        Ok(Self {
+            id,
+            config,
+            properties,
+            samples: None,
+            position: 0,
+            blocks: 0,
+            frames_delivered: 0,
+        })
+    }
+
+    fn integer_parameter(&self, key: &str, default: u64) -> u64 {
+        self.get_parameter(key)
+            .and_then(|v| v.as_u64())
+            .filter(|&v| v > 0)
+            .unwrap_or(default)
+    }
+
+    fn sample_rate(&self) -> u32 {
+        self.integer_parameter("sample_rate", 48000) as u32
+    }
+
+    fn load(&mut self) -> Result<()> {
+        let file_path = self
+            .get_parameter("file_path")
+            .and_then(|v| v.as_str().map(str::to_string))
+            .unwrap_or_default();
+        if file_path.is_empty() {
+            bail!("No audio file path specified");
+        }
+
+        let file = read_wav(&file_path)?;
+        let converted = AudioProcessor::new(self.sample_rate(), PIPELINE_CHANNELS)
+            .resample(&file)
+            .with_context(|| format!("Failed to convert {}", file_path))?;
+        info!(
+            "Loaded {} ({} Hz, {} channels) as {} frames at {} Hz",
+            file_path,
+            file.sample_rate,
+            file.channels,
+            converted.samples.len() / PIPELINE_CHANNELS as usize,
+            converted.sample_rate
+        );
+
+        self.samples = Some(converted.samples);
+        self.position = 0;
+        Ok(())
+    }
+
+    /// Take the next block, wrapping to the start when looping.
+    ///
+    /// Returns None once a non-looping file has been played to the end.
+    fn next_block(&mut self) -> Option<Vec<f32>> {
+        let looping = self
+            .get_parameter("loop")
+            .and_then(|v| v.as_bool())
+            .unwrap_or(false);
+        let block_frames = self.integer_parameter("block_size", 1024) as usize;
+        let channels = PIPELINE_CHANNELS as usize;
+
+        let samples = self.samples.as_ref()?;
+        let total_frames = samples.len() / channels;
+        if total_frames == 0 || (!looping && self.position >= total_frames) {
+            return None;
+        }
+
+        let mut block = Vec::with_capacity(block_frames * channels);
+        while block.len() < block_frames * channels {
+            if self.position >= total_frames {
+                if !looping {
+                    break;
+                }
+                self.position = 0;
+            }
+            let frames = (block_frames - block.len() / channels).min(total_frames - self.position);
+            block.extend_from_slice(
+                &samples[self.position * channels..(self.position + frames) * channels],
+            );
+            self.position += frames;
+        }
+
+        Some(block)
+    }
+}
+
+impl NodeProcessor for AudioFileInputNode {
+    fn process(&mut self, _input: FrameData) -> Result<FrameData> {
+        if self.samples.is_none() {
+            self.load()?;
+        }
+
+        let sample_rate = self.sample_rate();
+        let timestamp = Duration::from_secs_f64(self.frames_delivered as f64 / sample_rate as f64);
+        // Sequences count from 1; 0 would ask the engine to number the frame
+        let sequence = self.blocks + 1;
+
+        let audio_data = self.next_block().map(|samples| {
+            self.blocks += 1;
+            self.frames_delivered += (samples.len() / PIPELINE_CHANNELS as usize) as u64;
+            UnifiedAudioData::Stereo {
+                sample_rate,
+                channels: PIPELINE_CHANNELS,
+                samples,
+            }
+        });
+
+        Ok(FrameData {
+            render_data: None,
+            audio_data,
+            control_data: None,
+            tally_metadata: TallyMetadata::new(),
+            timestamp,
+            sequence,
+            checksum: None,
+        })
+    }
+
+    fn get_properties(&self) -> NodeProperties {
+        self.properties.clone()
+    }
+
+    fn on_start(&mut self) -> Result<()> {
+        if self.samples.is_none() {
+            self.load()?;
+        }
+        Ok(())
+    }
+
+    fn on_stop(&mut self) -> Result<()> {
+        self.samples = None;
+        self.blocks = 0;
+        self.frames_delivered = 0;
+        Ok(())
+    }
+
+    fn set_parameter(&mut self, key: &str, value: Value) -> Result<()> {
+        self.config.parameters.insert(key.to_string(), value);
+        // The file and rate are baked into the decoded buffer
+        if key == "file_path" || key == "sample_rate" {
+            self.samples = None;
+        }
+        Ok(())
+    }
+
+    fn get_parameter(&self, key: &str) -> Option<Value> {
+        self.config.parameters.get(key).cloned()
+    }
+}
+
+/// Records the pipeline's audio into a WAV file.
+///
+/// The file takes the sample rate and channel count of the first block it
+/// receives; later blocks in a different format are converted to match. The
+/// file is finalized on stop, when the path changes, or when the node is dropped.
+pub struct AudioFileOutputNode {
+    id: Uuid,
+    config: NodeConfig,
+    properties: NodeProperties,
+    writer: Option<hound::WavWriter<BufWriter<File>>>,
+    // Converts blocks in another format to the file's without clicks at block edges
+    resampler: StreamResampler,
+}
+
+impl AudioFileOutputNode {
+    pub fn new(id: Uuid, config: NodeConfig) -> Result<Self> {
+        let mut parameters = HashMap::new();
+        parameters.insert(
+            "file_path".to_string(),
+            ParameterDefinition {
+                name: "File Path".to_string(),
+                parameter_type: ParameterType::String,
+                default_value: Value::String(
+                    std::env::temp_dir()
+                        .join("constellation-audio.wav")
+                        .to_string_lossy()
+                        .into_owned(),
+                ),
+                min_value: None,
+                max_value: None,
+                description: "WAV file the audio is recorded to".to_string(),
+            },
+        );
+        parameters.insert(
+            "sample_format".to_string(),
+            ParameterDefinition {
+                name: "Sample Format".to_string(),
+                parameter_type: ParameterType::Enum(vec![
+                    "Float 32".to_string(),
+                    "PCM 16".to_string(),
+                ]),
+                default_value: Value::String("Float 32".to_string()),
+                min_value: None,
+                max_value: None,
+                description: "Sample encoding written to the file".to_string(),
+            },
+        );
+
+        let properties = NodeProperties {
+            id,
+            name: "Audio File Output".to_string(),
+            node_type: NodeType::Audio(AudioType::FileOutput),
+            input_types: vec![ConnectionType::Audio],
+            output_types: vec![ConnectionType::Audio],
+            parameters,
+            enabled: true,
+        };
+
+        Ok(Self {
+            id,
+            config,
+            properties,
+            writer: None,
+            resampler: StreamResampler::new(),
+        })
+    }
+
+    fn open_writer(
+        &self,
+        sample_rate: u32,
+        channels: u16,
+    ) -> Result<hound::WavWriter<BufWriter<File>>> {
+        let file_path = self
+            .get_parameter("file_path")
+            .and_then(|v| v.as_str().map(str::to_string))
+            .unwrap_or_default();
+        if file_path.is_empty() {
+            bail!("No audio file path specified");
+        }
+
+        let sample_format = self
+            .get_parameter("sample_format")
+            .and_then(|v| v.as_str().map(str::to_string))
+            .unwrap_or_else(|| "Float 32".to_string());
+        let (sample_format, bits_per_sample) = match sample_format.as_str() {
+            "Float 32" => (hound::SampleFormat::Float, 32),
+            "PCM 16" => (hound::SampleFormat::Int, 16),
+            other => bail!("Unknown sample format: {}", other),
+        };
+
+        let spec = hound::WavSpec {
+            channels,
+            sample_rate,
+            bits_per_sample,
+            sample_format,
+        };
+        info!(
+            "Recording audio to {} ({} Hz, {} channels)",
+            file_path, sample_rate, channels
+        );
+        hound::WavWriter::create(&file_path, spec)
+            .with_context(|| format!("Failed to create WAV file {}", file_path))
+    }
+
+    fn write_block(&mut self, frame: AudioFrame) -> Result<()> {
+        if self.writer.is_none() {
+            self.writer = Some(self.open_writer(frame.sample_rate, frame.channels)?);
+        }
+        let Some(writer) = self.writer.as_mut() else {
+            return Ok(());
+        };
+
+        let spec = writer.spec();
+        let frame = if frame.sample_rate == spec.sample_rate && frame.channels == spec.channels {
+            frame
+        } else {
+            self.resampler
+                .resample(&frame, spec.sample_rate, spec.channels)?
+        };
+
+        match spec.sample_format {
+            hound::SampleFormat::Float => {
+                for sample in frame.samples {
+                    writer.write_sample(sample)?;
+                }
+            }
+            hound::SampleFormat::Int => {
+                for sample in frame.samples {
+                    writer
+                        .write_sample((sample.clamp(-1.0, 1.0) * i16::MAX as f32).round() as i16)?;
+                }
+            }
+        }
+        Ok(())
+    }
+
+    /// Flush the WAV header and close the file
+    pub fn finalize(&mut self) -> Result<()> {
+        self.resampler.reset();
+        match self.writer.take() {
+            Some(writer) => writer.finalize().context("Failed to finalize WAV file"),
+            None => Ok(()),
+        }
+    }
+}
+
+impl NodeProcessor for AudioFileOutputNode {
+    fn process(&mut self, input: FrameData) -> Result<FrameData> {
+        if let Some(UnifiedAudioData::Stereo {
+            sample_rate,
+            channels,
+            ref samples,
+        }) = input.audio_data
+        {
+            if !samples.is_empty() {
+                self.write_block(AudioFrame {
+                    sample_rate,
+                    channels,
+                    samples: samples.clone(),
+                })?;
+            }
+        }
+
+        Ok(input)
+    }
+
+    fn get_properties(&self) -> NodeProperties {
+        self.properties.clone()
+    }
+
+    fn on_stop(&mut self) -> Result<()> {
+        self.finalize()
+    }
+
+    fn set_parameter(&mut self, key: &str, value: Value) -> Result<()> {
+        // A new path or format starts a new file
+        if key == "file_path" || key == "sample_format" {
+            self.finalize()?;
+        }
+        self.config.parameters.insert(key.to_string(), value);
+        Ok(())
+    }
+
+    fn get_parameter(&self, key: &str) -> Option<Value> {
+        self.config.parameters.get(key).cloned()
+    }
+}
+
+impl Drop for AudioFileOutputNode {
+    fn drop(&mut self) {
+        if let Err(e) = self.finalize() {
+            warn!("Failed to finalize audio recording: {}", e);
+        }
+    }
+}
@@ -17,18 +17,152 @@
  */
 
 use anyhow::Result;
-use constellation_core::{VideoFormat, VideoFrame};
+use constellation_core::{FrameClock, VideoFormat, VideoFrame};
 use nokhwa::pixel_format::RgbFormat;
 use nokhwa::utils::{ApiBackend, CameraIndex, RequestedFormat, RequestedFormatType, Resolution};
 use nokhwa::Camera;
 use std::collections::HashMap;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Arc;
+use std::thread::JoinHandle;
+use std::time::Duration;
 use tokio::sync::mpsc;
 use tracing::{debug, error, info, warn};
 
 pub mod platform;
 
+/// Backoff applied by the capture thread after a failed frame read
+const CAPTURE_RETRY_DELAY: Duration = Duration::from_millis(10);
+
+/// Blocking producer of frames, driven from the capture thread
+pub trait FrameSource {
+    /// Wait for and return the next frame
+    fn next_frame(&mut self) -> Result<VideoFrame>;
+
+    /// Release the underlying device
+    fn close(&mut self) -> Result<()> {
+        Ok(())
+    }
+}
+
+struct NokhwaFrameSource {
+    camera: Camera,
+    format: VideoFormat,
+}
+
+impl FrameSource for NokhwaFrameSource {
+    fn next_frame(&mut self) -> Result<VideoFrame> {
+        let frame = self.camera.frame()?;
+        let buffer = frame.buffer_bytes();
+
+        // Convert to our VideoFrame format
+        let video_frame = VideoFrame {
+            width: frame.resolution().width_x,
+            height: frame.resolution().height_y,
+            format: self.format.clone(),
+            data: buffer.to_vec(),
+        };
+
+        debug!(
+            "Captured frame: {}x{}, {} bytes",
+            video_frame.width,
+            video_frame.height,
+            video_frame.data.len()
+        );
+
+        Ok(video_frame)
+    }
+
+    fn close(&mut self) -> Result<()> {
+        self.camera.stop_stream()?;
+        Ok(())
+    }
+}
+
+struct CaptureThread {
+    stop: Arc<AtomicBool>,
+    handle: JoinHandle<()>,
+}
+
+impl CaptureThread {
+    /// Open a source on a dedicated thread and stream its frames into `sender`.
+    /// The source is constructed on the thread itself since camera handles are
+    /// not `Send`; open errors are reported back before this returns.
+    fn spawn<S, F>(open: F, fps: u32, sender: mpsc::UnboundedSender<VideoFrame>) -> Result<Self>
+    where
+        S: FrameSource,
+        F: FnOnce() -> Result<S> + Send + 'static,
+    {
+        let stop = Arc::new(AtomicBool::new(false));
+        let thread_stop = stop.clone();
+        let (ready_tx, ready_rx) = std::sync::mpsc::channel();
+
+        let handle = std::thread::Builder::new()
+            .name("camera-capture".to_string())
+            .spawn(move || {
+                let mut source = match open() {
+                    Ok(source) => {
+                        let _ = ready_tx.send(Ok(()));
+                        source
+                    }
+                    Err(e) => {
+                        let _ = ready_tx.send(Err(e));
+                        return;
+                    }
+                };
+
+                // Pace reads so sources that return immediately do not spin
+                let mut clock = FrameClock::new(fps as f64).ok();
+                while !thread_stop.load(Ordering::Relaxed) {
+                    if let Some(clock) = clock.as_mut() {
+                        clock.tick();
+                    }
+
+                    match source.next_frame() {
+                        Ok(frame) => {
+                            if sender.send(frame).is_err() {
+                                break;
+                            }
+                        }
+                        Err(e) => {
+                            warn!("Failed to read camera frame: {}", e);
+                            std::thread::sleep(CAPTURE_RETRY_DELAY);
+                        }
+                    }
+                }
+
+                if let Err(e) = source.close() {
+                    error!("Failed to close camera source: {}", e);
+                }
+            })?;
+
+        match ready_rx.recv() {
+            Ok(Ok(())) => Ok(Self { stop, handle }),
+            Ok(Err(e)) => {
+                let _ = handle.join();
+                Err(e)
+            }
+            Err(_) => {
+                let _ = handle.join();
+                Err(anyhow::anyhow!(
+                    "Camera capture thread exited during startup"
+                ))
+            }
+        }
+    }
+
+    fn stop(self) {
+        self.stop.store(true, Ordering::Relaxed);
+        if self.handle.join().is_err() {
+            error!("Camera capture thread panicked");
+        }
+    }
+}
+
 pub struct CameraCapture {
-    camera: Option<Camera>,
+    capture_thread: Option<CaptureThread>,
+    latest_frame: Option<VideoFrame>,
+    dropped_frames: u64,
     is_running: bool,
     device_index: CameraIndex,
     width: u32,
@@ -50,7 +184,9 @@ impl CameraCapture {
         let (frame_sender, frame_receiver) = mpsc::unbounded_channel();
 
         Ok(Self {
-            camera: None,
+            capture_thread: None,
+            latest_frame: None,
+            dropped_frames: 0,
             is_running: false,
             device_index,
             width,
@@ -106,26 +242,45 @@ impl CameraCapture {
             self.device_index, self.width, self.height, self.fps
         );
 
-        let requested_format =
-            RequestedFormat::new::<RgbFormat>(RequestedFormatType::AbsoluteHighestFrameRate);
+        let device_index = self.device_index.clone();
+        let (width, height, fps, format) = (self.width, self.height, self.fps, self.format.clone());
 
-        let mut camera = Camera::new(self.device_index.clone(), requested_format)?;
+        self.spawn_capture(move || {
+            let requested_format =
+                RequestedFormat::new::<RgbFormat>(RequestedFormatType::AbsoluteHighestFrameRate);
 
-        // Set camera resolution and frame rate
-        let resolution = Resolution::new(self.width, self.height);
-        camera.set_resolution(resolution)?;
-        camera.set_frame_rate(self.fps)?;
+            let mut camera = Camera::new(device_index, requested_format)?;
 
-        // Open camera stream
-        camera.open_stream()?;
+            // Set camera resolution and frame rate
+            let resolution = Resolution::new(width, height);
+            camera.set_resolution(resolution)?;
+            camera.set_frame_rate(fps)?;
 
-        self.camera = Some(camera);
-        self.is_running = true;
+            // Open camera stream
+            camera.open_stream()?;
+
+            Ok(NokhwaFrameSource { camera, format })
+        })?;
 
         info!("Camera capture started successfully");
         Ok(())
     }
 
+    fn spawn_capture<S, F>(&mut self, open: F) -> Result<()>
+    where
+        S: FrameSource,
+        F: FnOnce() -> Result<S> + Send + 'static,
+    {
+        let sender = self
+            .frame_sender
+            .clone()
+            .ok_or_else(|| anyhow::anyhow!("Camera frame channel closed"))?;
+
+        self.capture_thread = Some(CaptureThread::spawn(open, self.fps, sender)?);
+        self.is_running = true;
+        Ok(())
+    }
+
     pub fn stop_capture(&mut self) -> Result<()> {
         if !self.is_running {
             return Ok(());
@@ -133,49 +288,58 @@ impl CameraCapture {
 
         info!("Stopping camera capture");
 
-        if let Some(mut camera) = self.camera.take() {
-            camera.stop_stream()?;
+        if let Some(thread) = self.capture_thread.take() {
+            thread.stop();
         }
 
+        // Frames from this session must not leak into the next one
+        self.drain_frames();
+        self.latest_frame = None;
+
         self.is_running = false;
         info!("Camera capture stopped");
         Ok(())
     }
 
+    /// Pull everything the capture thread has queued, keeping only the newest frame
+    fn drain_frames(&mut self) {
+        let Some(receiver) = self.frame_receiver.as_mut() else {
+            return;
+        };
+
+        let mut received = 0u64;
+        while let Ok(frame) = receiver.try_recv() {
+            self.latest_frame = Some(frame);
+            received += 1;
+        }
+        self.dropped_frames += received.saturating_sub(1);
+    }
+
+    /// Return the most recent frame without blocking, dropping any older
+    /// frames queued since the last call. The same frame is returned again
+    /// if the camera has not delivered a new one yet.
     pub fn capture_frame(&mut self) -> Result<VideoFrame> {
         if !self.is_running {
             return Err(anyhow::anyhow!("Camera capture not started"));
         }
 
-        let camera = self
-            .camera
-            .as_mut()
-            .ok_or_else(|| anyhow::anyhow!("Camera not initialized"))?;
-
-        let frame = camera.frame()?;
-        let buffer = frame.buffer_bytes();
-
-        // Convert to our VideoFrame format
-        let video_frame = VideoFrame {
-            width: frame.resolution().width_x,
-            height: frame.resolution().height_y,
-            format: self.format.clone(),
-            data: buffer.to_vec(),
-        };
+        self.latest_frame()
+            .ok_or_else(|| anyhow::anyhow!("No camera frame captured yet"))
+    }
 
-        debug!(
-            "Captured frame: {}x{}, {} bytes",
-            video_frame.width,
-            video_frame.height,
-            video_frame.data.len()
-        );
+    /// Most recent frame delivered by the capture thread, if any. Never blocks.
+    pub fn latest_frame(&mut self) -> Option<VideoFrame> {
+        self.drain_frames();
+        self.latest_frame.clone()
+    }
 
-        Ok(video_frame)
+    /// Number of stale frames discarded in favour of newer ones
+    pub fn dropped_frames(&self) -> u64 {
+        self.dropped_frames
     }
 
     pub fn get_capabilities(&self) -> Result<CameraCapabilities> {
-        if let Some(camera) = &self.camera {
-            let _camera_format = camera.camera_format();
+        if self.is_running {
             Ok(CameraCapabilities {
                 resolutions: vec![(self.width, self.height)], // Simplified for now
                 frame_rates: vec![self.fps],
@@ -299,4 +463,93 @@ mod tests {
         assert_eq!(capture.height, 1080);
         assert_eq!(capture.fps, 60);
     }
+
+    /// Emits numbered 1x1 frames, then reports errors once exhausted
+    struct MockFrameSource {
+        remaining: std::vec::IntoIter<u8>,
+        delay: Duration,
+        produced: Arc<std::sync::atomic::AtomicUsize>,
+    }
+
+    impl FrameSource for MockFrameSource {
+        fn next_frame(&mut self) -> Result<VideoFrame> {
+            std::thread::sleep(self.delay);
+            let value = self
+                .remaining
+                .next()
+                .ok_or_else(|| anyhow::anyhow!("mock source exhausted"))?;
+            self.produced.fetch_add(1, Ordering::SeqCst);
+            Ok(VideoFrame {
+                width: 1,
+                height: 1,
+                format: VideoFormat::Rgba8,
+                data: vec![value, 0, 0, 255],
+            })
+        }
+    }
+
+    fn start_mock(
+        capture: &mut CameraCapture,
+        frames: Vec<u8>,
+        delay: Duration,
+    ) -> Arc<std::sync::atomic::AtomicUsize> {
+        let produced = Arc::new(std::sync::atomic::AtomicUsize::new(0));
+        let counter = produced.clone();
+        capture
+            .spawn_capture(move || {
+                Ok(MockFrameSource {
+                    remaining: frames.into_iter(),
+                    delay,
+                    produced: counter,
+                })
+            })
+            .unwrap();
+        produced
+    }
+
+    #[test]
+    fn test_capture_returns_newest_frame_and_drops_stale() {
+        let mut capture = CameraCapture::new(0, 1, 1, 1000).unwrap();
+        let produced = start_mock(&mut capture, vec![0, 1, 2, 3, 4], Duration::ZERO);
+
+        let deadline = std::time::Instant::now() + Duration::from_secs(2);
+        while produced.load(Ordering::SeqCst) < 5 && std::time::Instant::now() < deadline {
+            std::thread::sleep(Duration::from_millis(1));
+        }
+        assert_eq!(produced.load(Ordering::SeqCst), 5);
+
+        let frame = capture.capture_frame().unwrap();
+        assert_eq!(frame.data[0], 4);
+        assert_eq!(capture.dropped_frames(), 4);
+
+        // Without new frames the latest one is served again
+        assert_eq!(capture.latest_frame().unwrap().data[0], 4);
+        assert_eq!(capture.dropped_frames(), 4);
+
+        capture.stop_capture().unwrap();
+        assert!(capture.latest_frame().is_none());
+    }
+
+    #[test]
+    fn test_capture_frame_does_not_block() {
+        let mut capture = CameraCapture::new(0, 1, 1, 30).unwrap();
+        start_mock(&mut capture, vec![0], Duration::from_millis(200));
+
+        let start = std::time::Instant::now();
+        assert!(capture.capture_frame().is_err());
+        assert!(capture.latest_frame().is_none());
+        assert!(start.elapsed() < Duration::from_millis(50));
+
+        capture.stop_capture().unwrap();
+        assert!(capture.capture_frame().is_err());
+    }
+
+    #[test]
+    fn test_capture_open_failure_is_reported() {
+        let mut capture = CameraCapture::new(0, 1, 1, 30).unwrap();
+        let result = capture
+            .spawn_capture(|| -> Result<MockFrameSource> { Err(anyhow::anyhow!("device busy")) });
+        assert!(result.is_err());
+        assert!(!capture.is_running());
+    }
 }
@@ -25,153 +25,383 @@ use std::ptr;
 
 // X11 bindings
 use x11::xlib::{
-    Display, Window, XCloseDisplay, XDefaultRootWindow, XDefaultScreen, XDestroyImage,
-    XDisplayHeight, XDisplayWidth, XFetchName, XFree, XGetImage, XGetWindowAttributes, XImage,
-    XOpenDisplay, XQueryTree, XWindowAttributes, ZPixmap,
+    Display, Window, XCloseDisplay, XDefaultRootWindow, XDestroyImage, XFetchName, XFree,
+    XGetImage, XGetWindowAttributes, XImage, XOpenDisplay, XQueryTree, XWindowAttributes, ZPixmap,
 };
+use xcb::{randr, shm, x, xfixes};
 
 // X11 constants not available in the x11 crate
 const ALL_PLANES: u64 = !0;
 
-pub struct LinuxScreenCapture {
-    display_id: u32,
-    capture_cursor: bool,
-    width: u32,
-    height: u32,
-    display: *mut Display,
-    root_window: Window,
+/// Display server the current desktop session runs on
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SessionType {
+    X11,
+    Wayland,
 }
 
-// SAFETY: LinuxScreenCapture is Send + Sync because:
-// - X11 Display connections are thread-safe when used properly
-// - Each instance manages its own display connection
-// - Raw pointers are only used within X11 API calls which are internally synchronized
-unsafe impl Send for LinuxScreenCapture {}
-unsafe impl Sync for LinuxScreenCapture {}
+/// Detect the session type from the environment, `None` when headless
+pub fn detect_session() -> Option<SessionType> {
+    session_from_env(
+        std::env::var("XDG_SESSION_TYPE").ok().as_deref(),
+        std::env::var("WAYLAND_DISPLAY").ok().as_deref(),
+        std::env::var("DISPLAY").ok().as_deref(),
+    )
+}
 
-impl ScreenCaptureBackend for LinuxScreenCapture {
-    fn new(display_id: u32, capture_cursor: bool) -> Result<Self> {
-        unsafe {
-            let display = XOpenDisplay(ptr::null());
-            if display.is_null() {
-                return Err(anyhow::anyhow!("Failed to open X11 display"));
-            }
+fn session_from_env(
+    session_type: Option<&str>,
+    wayland_display: Option<&str>,
+    x_display: Option<&str>,
+) -> Option<SessionType> {
+    let set = |value: Option<&str>| value.is_some_and(|v| !v.is_empty());
+
+    match session_type {
+        Some("wayland") => Some(SessionType::Wayland),
+        Some("x11") => Some(SessionType::X11),
+        // tty or unset (e.g. started over ssh): fall back to the server sockets
+        _ if set(wayland_display) => Some(SessionType::Wayland),
+        _ if set(x_display) => Some(SessionType::X11),
+        _ => None,
+    }
+}
 
-            let screen = XDefaultScreen(display);
-            let root_window = XDefaultRootWindow(display);
-            let width = XDisplayWidth(display, screen) as u32;
-            let height = XDisplayHeight(display, screen) as u32;
+/// Open an X connection for screen capture, rejecting sessions it can't serve
+fn connect_screen_server() -> Result<(xcb::Connection, i32)> {
+    match detect_session() {
+        Some(SessionType::X11) => {}
+        Some(SessionType::Wayland) => {
+            // The XWayland root window only contains X clients, so it can't stand in
+            // for the desktop; Wayland needs the xdg-desktop-portal ScreenCast stream
+            return Err(anyhow::anyhow!(
+                "Screen capture on Wayland requires the xdg-desktop-portal ScreenCast \
+                 (PipeWire) backend, which is not available in this build"
+            ));
+        }
+        None => return Err(anyhow::anyhow!("No display server available")),
+    }
 
-            Ok(Self {
-                display_id,
-                capture_cursor,
-                width,
-                height,
-                display,
-                root_window,
-            })
+    xcb::Connection::connect_with_extensions(
+        None,
+        &[],
+        &[
+            xcb::Extension::RandR,
+            xcb::Extension::Shm,
+            xcb::Extension::XFixes,
+        ],
+    )
+    .map_err(|e| anyhow::anyhow!("Failed to connect to X server: {}", e))
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+struct MonitorBounds {
+    x: i16,
+    y: i16,
+    width: u16,
+    height: u16,
+}
+
+/// Active monitors with the primary first, so `display_id` 0 is the primary display
+fn query_monitors(conn: &xcb::Connection, screen: &x::Screen) -> Result<Vec<MonitorBounds>> {
+    let has_randr = conn
+        .active_extensions()
+        .any(|ext| ext == xcb::Extension::RandR);
+
+    let mut monitors = Vec::new();
+    if has_randr {
+        let cookie = conn.send_request(&randr::GetMonitors {
+            window: screen.root(),
+            get_active: true,
+        });
+        if let Ok(reply) = conn.wait_for_reply(cookie) {
+            let mut found: Vec<_> = reply
+                .monitors()
+                .map(|monitor| {
+                    (
+                        !monitor.primary(),
+                        MonitorBounds {
+                            x: monitor.x(),
+                            y: monitor.y(),
+                            width: monitor.width(),
+                            height: monitor.height(),
+                        },
+                    )
+                })
+                .collect();
+            found.sort_by_key(|(not_primary, _)| *not_primary);
+            monitors.extend(found.into_iter().map(|(_, bounds)| bounds));
         }
     }
 
-    fn capture_frame(&mut self) -> Result<VideoFrame> {
+    // Without RandR the whole screen is a single display
+    if monitors.is_empty() {
+        monitors.push(MonitorBounds {
+            x: 0,
+            y: 0,
+            width: screen.width_in_pixels(),
+            height: screen.height_in_pixels(),
+        });
+    }
+
+    Ok(monitors)
+}
+
+fn default_screen(conn: &xcb::Connection, screen_num: i32) -> Result<&x::Screen> {
+    conn.get_setup()
+        .roots()
+        .nth(screen_num as usize)
+        .ok_or_else(|| anyhow::anyhow!("X screen {} not found", screen_num))
+}
+
+/// System V shared memory segment attached to the X server (MIT-SHM)
+struct ShmSegment {
+    seg: shm::Seg,
+    addr: *mut u8,
+    size: usize,
+}
+
+impl ShmSegment {
+    fn attach(conn: &xcb::Connection, size: usize) -> Result<Self> {
         unsafe {
-            let image = XGetImage(
-                self.display,
-                self.root_window,
-                0,
-                0,
-                self.width,
-                self.height,
-                ALL_PLANES,
-                ZPixmap,
-            );
+            let id = libc::shmget(libc::IPC_PRIVATE, size, libc::IPC_CREAT | 0o600);
+            if id < 0 {
+                return Err(anyhow::anyhow!("shmget failed"));
+            }
 
-            if image.is_null() {
-                return Err(anyhow::anyhow!("Failed to capture X11 screen image"));
+            let addr = libc::shmat(id, ptr::null(), 0);
+            if addr as isize == -1 {
+                libc::shmctl(id, libc::IPC_RMID, ptr::null_mut());
+                return Err(anyhow::anyhow!("shmat failed"));
             }
 
-            let frame_data = self.convert_ximage_to_frame_data(image)?;
+            let seg = conn.generate_id();
+            let attached = conn.send_and_check_request(&shm::Attach {
+                shmseg: seg,
+                shmid: id as u32,
+                read_only: false,
+            });
 
-            XDestroyImage(image);
+            // The segment is freed once both sides have detached
+            libc::shmctl(id, libc::IPC_RMID, ptr::null_mut());
 
-            Ok(VideoFrame {
-                width: self.width,
-                height: self.height,
-                format: VideoFormat::Bgra8,
-                data: frame_data,
+            if let Err(e) = attached {
+                libc::shmdt(addr);
+                return Err(anyhow::anyhow!("X server refused MIT-SHM segment: {}", e));
+            }
+
+            Ok(Self {
+                seg,
+                addr: addr as *mut u8,
+                size,
             })
         }
     }
 
-    fn get_display_count() -> Result<u32> {
-        // For simplicity, return 1 for now
-        // Real implementation would check DISPLAY environment variable
-        // and enumerate available X11 displays
-        Ok(1)
+    fn release(&mut self, conn: &xcb::Connection) {
+        let _ = conn.send_and_check_request(&shm::Detach { shmseg: self.seg });
+        unsafe {
+            libc::shmdt(self.addr as *const _);
+        }
     }
+}
 
-    fn get_display_bounds(&self, _display_id: u32) -> Result<(u32, u32, u32, u32)> {
-        Ok((0, 0, self.width, self.height))
-    }
+pub struct LinuxScreenCapture {
+    display_id: u32,
+    capture_cursor: bool,
+    conn: xcb::Connection,
+    root: x::Window,
+    monitors: Vec<MonitorBounds>,
+    bounds: MonitorBounds,
+    // None when the server is remote or lacks MIT-SHM
+    shm: Option<ShmSegment>,
+    has_xfixes: bool,
 }
 
-impl LinuxScreenCapture {
-    fn convert_ximage_to_frame_data(&self, image: *mut XImage) -> Result<Vec<u8>> {
-        unsafe {
-            let image_ref = &*image;
-            let width = image_ref.width as u32;
-            let height = image_ref.height as u32;
-            let bytes_per_pixel = (image_ref.bits_per_pixel / 8) as usize;
+// SAFETY: LinuxScreenCapture is Send + Sync because:
+// - libxcb connections are thread-safe
+// - The shared memory mapping is owned by this instance and only read through &mut self
+unsafe impl Send for LinuxScreenCapture {}
+unsafe impl Sync for LinuxScreenCapture {}
 
-            if bytes_per_pixel != 4 {
+impl ScreenCaptureBackend for LinuxScreenCapture {
+    fn new(display_id: u32, capture_cursor: bool) -> Result<Self> {
+        let (conn, screen_num) = connect_screen_server()?;
+        let screen = default_screen(&conn, screen_num)?;
+        let root = screen.root();
+        let monitors = query_monitors(&conn, screen)?;
+        let bounds = *monitors
+            .get(display_id as usize)
+            .ok_or_else(|| anyhow::anyhow!("Display {} not found", display_id))?;
+
+        let shm = if conn
+            .active_extensions()
+            .any(|ext| ext == xcb::Extension::Shm)
+        {
+            let size = bounds.width as usize * bounds.height as usize * 4;
+            match ShmSegment::attach(&conn, size) {
+                Ok(segment) => Some(segment),
+                Err(e) => {
+                    tracing::warn!("MIT-SHM unavailable, using plain GetImage: {}", e);
+                    None
+                }
+            }
+        } else {
+            None
+        };
+
+        // XFixes requests fail until the client has announced its version
+        let has_xfixes = capture_cursor
+            && conn
+                .active_extensions()
+                .any(|ext| ext == xcb::Extension::XFixes)
+            && conn
+                .wait_for_reply(conn.send_request(&xfixes::QueryVersion {
+                    client_major_version: 4,
+                    client_minor_version: 0,
+                }))
+                .is_ok();
+
+        Ok(Self {
+            display_id,
+            capture_cursor,
+            conn,
+            root,
+            monitors,
+            bounds,
+            shm,
+            has_xfixes,
+        })
+    }
+
+    fn capture_frame(&mut self) -> Result<VideoFrame> {
+        let MonitorBounds {
+            x,
+            y,
+            width,
+            height,
+        } = self.bounds;
+        let size = width as usize * height as usize * 4;
+
+        let mut frame_data = if let Some(segment) = &self.shm {
+            let cookie = self.conn.send_request(&shm::GetImage {
+                drawable: x::Drawable::Window(self.root),
+                x,
+                y,
+                width,
+                height,
+                plane_mask: !0,
+                format: x::ImageFormat::ZPixmap as u8,
+                shmseg: segment.seg,
+                offset: 0,
+            });
+            let reply = self
+                .conn
+                .wait_for_reply(cookie)
+                .map_err(|e| anyhow::anyhow!("MIT-SHM GetImage failed: {}", e))?;
+            if reply.size() as usize != size || size > segment.size {
                 return Err(anyhow::anyhow!(
-                    "Unsupported pixel format: {} bits per pixel",
-                    image_ref.bits_per_pixel
+                    "Unsupported screen depth {} for capture",
+                    reply.depth()
+                ));
+            }
+            unsafe { std::slice::from_raw_parts(segment.addr, size).to_vec() }
+        } else {
+            let cookie = self.conn.send_request(&x::GetImage {
+                format: x::ImageFormat::ZPixmap,
+                drawable: x::Drawable::Window(self.root),
+                x,
+                y,
+                width,
+                height,
+                plane_mask: !0,
+            });
+            let reply = self
+                .conn
+                .wait_for_reply(cookie)
+                .map_err(|e| anyhow::anyhow!("Failed to capture X11 screen image: {}", e))?;
+            if reply.data().len() != size {
+                return Err(anyhow::anyhow!(
+                    "Unsupported screen depth {} for capture",
+                    reply.depth()
                 ));
             }
+            reply.data().to_vec()
+        };
 
-            let data_size = (width * height * 4) as usize;
-            let mut frame_data = Vec::with_capacity(data_size);
+        // 24-bit visuals leave the padding byte undefined; treat the desktop as opaque
+        for pixel in frame_data.chunks_exact_mut(4) {
+            pixel[3] = 0xFF;
+        }
 
-            let src_data = std::slice::from_raw_parts(
-                image_ref.data as *const u8,
-                (height as usize) * (image_ref.bytes_per_line as usize),
-            );
+        if self.capture_cursor && self.has_xfixes {
+            self.draw_cursor(&mut frame_data);
+        }
 
-            // Copy and convert pixel format if needed
-            for y in 0..height {
-                let src_row_offset = (y as usize) * (image_ref.bytes_per_line as usize);
-                for x in 0..width {
-                    let src_pixel_offset = src_row_offset + (x as usize) * bytes_per_pixel;
+        Ok(VideoFrame {
+            width: width as u32,
+            height: height as u32,
+            format: VideoFormat::Bgra8,
+            data: frame_data,
+        })
+    }
 
-                    if src_pixel_offset + 3 < src_data.len() {
-                        // X11 typically uses BGRA format
-                        let b = src_data[src_pixel_offset];
-                        let g = src_data[src_pixel_offset + 1];
-                        let r = src_data[src_pixel_offset + 2];
-                        let a = src_data[src_pixel_offset + 3];
+    fn get_display_count() -> Result<u32> {
+        get_display_count()
+    }
 
-                        // Convert to BGRA format expected by our system
-                        frame_data.push(b);
-                        frame_data.push(g);
-                        frame_data.push(r);
-                        frame_data.push(a);
-                    }
-                }
+    fn get_display_bounds(&self, display_id: u32) -> Result<(i32, i32, u32, u32)> {
+        let bounds = self
+            .monitors
+            .get(display_id as usize)
+            .ok_or_else(|| anyhow::anyhow!("Display {} not found", display_id))?;
+        Ok((
+            i32::from(bounds.x),
+            i32::from(bounds.y),
+            u32::from(bounds.width),
+            u32::from(bounds.height),
+        ))
+    }
+}
+
+impl LinuxScreenCapture {
+    /// Composite the XFixes cursor image (premultiplied ARGB) onto the frame
+    fn draw_cursor(&self, frame: &mut [u8]) {
+        let cookie = self.conn.send_request(&xfixes::GetCursorImage {});
+        let Ok(cursor) = self.conn.wait_for_reply(cookie) else {
+            return;
+        };
+
+        let bounds = self.bounds;
+        let left = cursor.x() as i32 - cursor.xhot() as i32 - bounds.x as i32;
+        let top = cursor.y() as i32 - cursor.yhot() as i32 - bounds.y as i32;
+        let cursor_width = cursor.width() as i32;
+
+        for (i, &argb) in cursor.cursor_image().iter().enumerate() {
+            let dx = left + i as i32 % cursor_width;
+            let dy = top + i as i32 / cursor_width;
+            if dx < 0 || dy < 0 || dx >= bounds.width as i32 || dy >= bounds.height as i32 {
+                continue;
             }
 
-            Ok(frame_data)
+            let alpha = argb >> 24;
+            if alpha == 0 {
+                continue;
+            }
+            let offset = (dy as usize * bounds.width as usize + dx as usize) * 4;
+            let [b, g, r, _] = argb.to_le_bytes();
+            for (dst, src) in frame[offset..offset + 3].iter_mut().zip([b, g, r]) {
+                *dst = (src as u32 + *dst as u32 * (255 - alpha) / 255).min(255) as u8;
+            }
         }
     }
 }
 
 impl Drop for LinuxScreenCapture {
     fn drop(&mut self) {
-        unsafe {
-            if !self.display.is_null() {
-                XCloseDisplay(self.display);
-            }
+        if let Some(mut segment) = self.shm.take() {
+            segment.release(&self.conn);
         }
+        tracing::debug!(display_id = self.display_id, "Screen capture closed");
     }
 }
 
@@ -488,11 +718,76 @@ fn enumerate_windows(
 }
 
 pub fn get_display_count() -> Result<u32> {
-    // For X11, typically there's one display per DISPLAY environment variable
-    // Real implementation would parse DISPLAY and check for multiple screens
-    Ok(1)
+    let (conn, screen_num) = connect_screen_server()?;
+    let screen = default_screen(&conn, screen_num)?;
+    Ok(query_monitors(&conn, screen)?.len() as u32)
 }
 
 pub fn get_window_list() -> Result<Vec<WindowInfo>> {
     get_window_list_impl()
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Capture tests need a live X server; headless CI has none
+    fn x11_available() -> bool {
+        std::env::var("CI").is_err() && detect_session() == Some(SessionType::X11)
+    }
+
+    #[test]
+    fn test_session_detection() {
+        assert_eq!(
+            session_from_env(Some("wayland"), None, Some(":0")),
+            Some(SessionType::Wayland)
+        );
+        assert_eq!(
+            session_from_env(Some("x11"), Some("wayland-0"), Some(":0")),
+            Some(SessionType::X11)
+        );
+        assert_eq!(
+            session_from_env(Some("tty"), Some("wayland-0"), Some(":0")),
+            Some(SessionType::Wayland)
+        );
+        assert_eq!(
+            session_from_env(None, None, Some(":1")),
+            Some(SessionType::X11)
+        );
+        assert_eq!(session_from_env(None, Some(""), Some("")), None);
+    }
+
+    #[test]
+    fn test_display_enumeration() {
+        // Skipped without a live X server (see `x11_available`)
+        if !x11_available() {
+            return;
+        }
+
+        let count = get_display_count().unwrap();
+        assert!(count >= 1);
+
+        let capture = LinuxScreenCapture::new(0, false).unwrap();
+        for display_id in 0..count {
+            let (_, _, width, height) = capture.get_display_bounds(display_id).unwrap();
+            assert!(width > 0 && height > 0);
+        }
+        assert!(capture.get_display_bounds(count).is_err());
+    }
+
+    #[test]
+    fn test_screen_capture_frame() {
+        // Skipped without a live X server (see `x11_available`)
+        if !x11_available() {
+            return;
+        }
+
+        let mut capture = LinuxScreenCapture::new(0, true).unwrap();
+        let (_, _, width, height) = capture.get_display_bounds(0).unwrap();
+        let frame = capture.capture_frame().unwrap();
+
+        assert_eq!((frame.width, frame.height), (width, height));
+        assert_eq!(frame.data.len(), (width * height * 4) as usize);
+        assert!(frame.data.chunks_exact(4).all(|pixel| pixel[3] == 0xFF));
+    }
+}
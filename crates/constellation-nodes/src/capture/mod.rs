@@ -39,7 +39,9 @@ pub trait ScreenCaptureBackend: Send + Sync {
         Self: Sized;
     fn capture_frame(&mut self) -> Result<VideoFrame>;
     fn get_display_count() -> Result<u32>;
-    fn get_display_bounds(&self, display_id: u32) -> Result<(u32, u32, u32, u32)>; // x, y, width, height
+    // x, y, width, height; the origin is signed because displays left of or
+    // above the primary sit at negative desktop coordinates
+    fn get_display_bounds(&self, display_id: u32) -> Result<(i32, i32, u32, u32)>;
 }
 
 /// Platform-agnostic window capture traits
@@ -185,6 +187,7 @@ mod tests {
     #[cfg(feature = "test-capture-backends")]
     mod backend_tests {
         use super::*;
+        use std::time::Duration;
 
         #[test]
         fn test_screen_capture_backend_display_detection() {
@@ -221,6 +224,9 @@ mod tests {
                     audio_data: None,
                     control_data: None,
                     tally_metadata: TallyMetadata::new(),
+                    timestamp: Duration::ZERO,
+                    sequence: 0,
+                    checksum: None,
                 };
 
                 if let Ok(output) = node.process(dummy_input) {
@@ -21,6 +21,7 @@ use anyhow::Result;
 use constellation_core::*;
 use serde_json::Value;
 use std::collections::HashMap;
+use std::time::Duration;
 use uuid::Uuid;
 
 #[cfg(target_os = "linux")]
@@ -127,6 +128,7 @@ impl ScreenCaptureNode {
             input_types: vec![],
             output_types: vec![ConnectionType::RenderData],
             parameters: parameters.clone(),
+            enabled: true,
         };
 
         // Initialize config with default values if not provided
@@ -180,6 +182,9 @@ impl NodeProcessor for ScreenCaptureNode {
             audio_data: None,
             control_data: None,
             tally_metadata: TallyMetadata::new(),
+            timestamp: Duration::ZERO,
+            sequence: 0,
+            checksum: None,
         })
     }
 
@@ -187,6 +192,18 @@ impl NodeProcessor for ScreenCaptureNode {
         self.properties.clone()
     }
 
+    fn on_start(&mut self) -> Result<()> {
+        if self.capture_context.is_none() {
+            self.initialize_capture()?;
+        }
+        Ok(())
+    }
+
+    fn on_stop(&mut self) -> Result<()> {
+        self.capture_context = None;
+        Ok(())
+    }
+
     fn set_parameter(&mut self, key: &str, value: Value) -> Result<()> {
         self.config.parameters.insert(key.to_string(), value);
         // Reset capture context to apply new parameters
@@ -25,22 +25,32 @@ use windows::{
     core::*,
     Win32::{
         Foundation::*,
-        Graphics::{Direct3D11::*, Dxgi::Common::*, Dxgi::*, Gdi::*},
+        Graphics::{Direct3D::*, Direct3D11::*, Dxgi::Common::*, Dxgi::*, Gdi::*},
         System::Com::*,
         UI::WindowsAndMessaging::*,
     },
 };
 
+/// How long to wait for the first desktop image after (re)creating a duplication
+const FIRST_FRAME_TIMEOUT_MS: u32 = 500;
+
 pub struct WindowsScreenCapture {
     display_id: u32,
     capture_cursor: bool,
     width: u32,
     height: u32,
-    hdc: Option<HDC>,
-    mem_dc: Option<HDC>,
-    bitmap: Option<HBITMAP>,
+    session: Option<DuplicationSession>,
+    pointer: PointerState,
+    // Desktop image without the cursor, reused while the desktop is idle
+    last_frame: Option<Vec<u8>>,
 }
 
+// SAFETY: WindowsScreenCapture is Send + Sync because:
+// - The D3D11 device is created without D3D11_CREATE_DEVICE_SINGLETHREADED
+// - The immediate context and duplication are only touched through &mut self
+unsafe impl Send for WindowsScreenCapture {}
+unsafe impl Sync for WindowsScreenCapture {}
+
 impl ScreenCaptureBackend for WindowsScreenCapture {
     fn new(display_id: u32, capture_cursor: bool) -> Result<Self> {
         let (width, height) = get_display_dimensions(display_id)?;
@@ -50,23 +60,43 @@ impl ScreenCaptureBackend for WindowsScreenCapture {
             capture_cursor,
             width,
             height,
-            hdc: None,
-            mem_dc: None,
-            bitmap: None,
+            session: None,
+            pointer: PointerState::default(),
+            last_frame: None,
         })
     }
 
     fn capture_frame(&mut self) -> Result<VideoFrame> {
-        self.initialize_capture_context()?;
+        let mut desktop = match self.acquire_desktop() {
+            Err(e) if e.code() == DXGI_ERROR_ACCESS_LOST => {
+                // Mode changes, fullscreen apps and the secure desktop invalidate the
+                // duplication; recreate it and try once more
+                tracing::warn!(
+                    display_id = self.display_id,
+                    "Desktop duplication access lost, reacquiring"
+                );
+                self.session = None;
+                self.acquire_desktop()?
+            }
+            result => result?,
+        };
 
-        // Use BitBlt for now (legacy but reliable method)
-        let frame_data = self.capture_with_bitblt()?;
+        if self.capture_cursor && self.pointer.visible {
+            draw_pointer(
+                &mut desktop,
+                self.width,
+                self.height,
+                self.pointer.position,
+                &self.pointer.shape_info,
+                &self.pointer.shape,
+            );
+        }
 
         Ok(VideoFrame {
             width: self.width,
             height: self.height,
             format: VideoFormat::Bgra8,
-            data: frame_data,
+            data: desktop,
         })
     }
 
@@ -74,138 +104,350 @@ impl ScreenCaptureBackend for WindowsScreenCapture {
         get_display_count()
     }
 
-    fn get_display_bounds(&self, display_id: u32) -> Result<(u32, u32, u32, u32)> {
-        // Get display bounds for specified display
-        let mut display_index = 0;
-        let mut bounds = (0, 0, 0, 0);
+    fn get_display_bounds(&self, display_id: u32) -> Result<(i32, i32, u32, u32)> {
+        get_display_bounds(display_id)
+    }
+}
+
+impl WindowsScreenCapture {
+    /// Grab the current desktop image (without cursor) through Desktop Duplication
+    fn acquire_desktop(&mut self) -> windows::core::Result<Vec<u8>> {
+        if self.session.is_none() {
+            let session = DuplicationSession::new(self.display_id)?;
+            self.width = session.width;
+            self.height = session.height;
+            self.session = Some(session);
+            self.last_frame = None;
+        }
+        // Cloning only adds a COM reference; it keeps `self` free for read_frame
+        let duplication = self
+            .session
+            .as_ref()
+            .expect("session initialized above")
+            .duplication
+            .clone();
+
+        let timeout = if self.last_frame.is_some() {
+            0
+        } else {
+            FIRST_FRAME_TIMEOUT_MS
+        };
+        let mut frame_info = DXGI_OUTDUPL_FRAME_INFO::default();
+        let mut resource = None;
 
         unsafe {
-            let mut enum_context = DisplayEnumContext {
-                target_index: display_id,
-                current_index: 0,
-                found_bounds: None,
-            };
+            match duplication.AcquireNextFrame(timeout, &mut frame_info, &mut resource) {
+                Ok(()) => {}
+                Err(e) if e.code() == DXGI_ERROR_WAIT_TIMEOUT => {
+                    // Nothing changed on screen since the last capture
+                    if let Some(frame) = &self.last_frame {
+                        return Ok(frame.clone());
+                    }
+                    return Err(e);
+                }
+                Err(e) => return Err(e),
+            }
 
-            EnumDisplayMonitors(
-                None,
-                None,
-                Some(enum_display_proc),
-                LPARAM(&mut enum_context as *mut _ as isize),
-            );
+            let result = self.read_frame(&frame_info, resource);
+            // The frame must be released even if reading it failed
+            let released = duplication.ReleaseFrame();
+            let frame = result?;
+            released?;
 
-            if let Some(found_bounds) = enum_context.found_bounds {
-                bounds = found_bounds;
-            }
+            self.last_frame = Some(frame.clone());
+            Ok(frame)
         }
-
-        Ok(bounds)
     }
-}
 
-impl WindowsScreenCapture {
-    fn initialize_capture_context(&mut self) -> Result<()> {
-        if self.hdc.is_some() {
-            return Ok(());
-        }
+    unsafe fn read_frame(
+        &mut self,
+        frame_info: &DXGI_OUTDUPL_FRAME_INFO,
+        resource: Option<IDXGIResource>,
+    ) -> windows::core::Result<Vec<u8>> {
+        let session = self.session.as_ref().expect("session initialized");
 
-        unsafe {
-            let hdc = GetDC(None);
-            let mem_dc = CreateCompatibleDC(hdc);
-            let bitmap = CreateCompatibleBitmap(hdc, self.width as i32, self.height as i32);
+        if frame_info.LastMouseUpdateTime != 0 {
+            self.pointer.position = frame_info.PointerPosition.Position;
+            self.pointer.visible = frame_info.PointerPosition.Visible.as_bool();
+        }
+        if self.capture_cursor && frame_info.PointerShapeBufferSize > 0 {
+            let mut shape = vec![0u8; frame_info.PointerShapeBufferSize as usize];
+            let mut required = 0;
+            session.duplication.GetFramePointerShape(
+                shape.len() as u32,
+                shape.as_mut_ptr() as *mut _,
+                &mut required,
+                &mut self.pointer.shape_info,
+            )?;
+            shape.truncate(required as usize);
+            self.pointer.shape = shape;
+        }
 
-            if hdc.is_invalid() || mem_dc.is_invalid() || bitmap.is_invalid() {
-                return Err(anyhow::anyhow!("Failed to create capture context"));
+        // Only the pointer moved; the desktop image is unchanged
+        if frame_info.LastPresentTime == 0 {
+            if let Some(frame) = &self.last_frame {
+                return Ok(frame.clone());
             }
+        }
 
-            SelectObject(mem_dc, bitmap);
-
-            self.hdc = Some(hdc);
-            self.mem_dc = Some(mem_dc);
-            self.bitmap = Some(bitmap);
+        let texture: ID3D11Texture2D = resource
+            .ok_or_else(|| windows::core::Error::from(DXGI_ERROR_ACCESS_LOST))?
+            .cast()?;
+        session.context.CopyResource(&session.staging, &texture);
+
+        let mut mapped = D3D11_MAPPED_SUBRESOURCE::default();
+        session
+            .context
+            .Map(&session.staging, 0, D3D11_MAP_READ, 0, Some(&mut mapped))?;
+
+        let row_bytes = (session.mode_width * 4) as usize;
+        let mut buffer = vec![0u8; row_bytes * session.mode_height as usize];
+        for (y, row) in buffer.chunks_exact_mut(row_bytes).enumerate() {
+            let src = (mapped.pData as *const u8).add(y * mapped.RowPitch as usize);
+            row.copy_from_slice(std::slice::from_raw_parts(src, row_bytes));
         }
 
-        Ok(())
+        session.context.Unmap(&session.staging, 0);
+        Ok(rotate_to_desktop(
+            buffer,
+            session.mode_width,
+            session.mode_height,
+            session.rotation,
+        ))
     }
+}
 
-    fn capture_with_bitblt(&mut self) -> Result<Vec<u8>> {
-        let hdc = self
-            .hdc
-            .ok_or_else(|| anyhow::anyhow!("HDC not initialized"))?;
-        let mem_dc = self
-            .mem_dc
-            .ok_or_else(|| anyhow::anyhow!("Memory DC not initialized"))?;
+/// Turn a duplicated BGRA image from the output's scan-out orientation into
+/// desktop orientation (the layout the monitor bounds and pointer use)
+fn rotate_to_desktop(
+    image: Vec<u8>,
+    mode_width: u32,
+    mode_height: u32,
+    rotation: DXGI_MODE_ROTATION,
+) -> Vec<u8> {
+    let (width, height) = (mode_width as usize, mode_height as usize);
+    let (desktop_width, desktop_height) =
+        if rotation == DXGI_MODE_ROTATION_ROTATE90 || rotation == DXGI_MODE_ROTATION_ROTATE270 {
+            (height, width)
+        } else if rotation == DXGI_MODE_ROTATION_ROTATE180 {
+            (width, height)
+        } else {
+            return image;
+        };
+
+    let mut rotated = vec![0u8; image.len()];
+    for y in 0..desktop_height {
+        for x in 0..desktop_width {
+            let (source_x, source_y) = if rotation == DXGI_MODE_ROTATION_ROTATE90 {
+                (y, desktop_width - 1 - x)
+            } else if rotation == DXGI_MODE_ROTATION_ROTATE270 {
+                (desktop_height - 1 - y, x)
+            } else {
+                (width - 1 - x, height - 1 - y)
+            };
+            let src = (source_y * width + source_x) * 4;
+            let dst = (y * desktop_width + x) * 4;
+            rotated[dst..dst + 4].copy_from_slice(&image[src..src + 4]);
+        }
+    }
+    rotated
+}
+
+/// DXGI output duplication for one monitor plus a CPU-readable staging texture
+struct DuplicationSession {
+    _device: ID3D11Device,
+    context: ID3D11DeviceContext,
+    duplication: IDXGIOutputDuplication,
+    staging: ID3D11Texture2D,
+    // Scan-out size of the duplicated surface, before undoing the output rotation
+    mode_width: u32,
+    mode_height: u32,
+    rotation: DXGI_MODE_ROTATION,
+    // Desktop-oriented size, matching the monitor bounds
+    width: u32,
+    height: u32,
+}
+
+impl DuplicationSession {
+    fn new(display_id: u32) -> windows::core::Result<Self> {
+        let monitor = *enumerate_monitors()
+            .get(display_id as usize)
+            .ok_or_else(|| windows::core::Error::from(DXGI_ERROR_NOT_FOUND))?;
 
         unsafe {
-            // Copy screen to memory DC
-            if !BitBlt(
-                mem_dc,
-                0,
-                0,
-                self.width as i32,
-                self.height as i32,
-                hdc,
-                0,
-                0,
-                SRCCOPY,
-            )
-            .as_bool()
+            let (adapter, output) = find_output(monitor)?;
+
+            let mut device = None;
+            let mut context = None;
+            D3D11CreateDevice(
+                &adapter,
+                D3D_DRIVER_TYPE_UNKNOWN,
+                None,
+                D3D11_CREATE_DEVICE_BGRA_SUPPORT,
+                None,
+                D3D11_SDK_VERSION,
+                Some(&mut device),
+                None,
+                Some(&mut context),
+            )?;
+            let device = device.ok_or_else(|| windows::core::Error::from(E_FAIL))?;
+            let context = context.ok_or_else(|| windows::core::Error::from(E_FAIL))?;
+
+            let duplication = output.cast::<IDXGIOutput1>()?.DuplicateOutput(&device)?;
+            let mut desc = DXGI_OUTDUPL_DESC::default();
+            duplication.GetDesc(&mut desc);
+            let (mode_width, mode_height) = (desc.ModeDesc.Width, desc.ModeDesc.Height);
+            let rotation = desc.Rotation;
+            // Portrait outputs duplicate in their landscape scan-out orientation
+            let (width, height) = if rotation == DXGI_MODE_ROTATION_ROTATE90
+                || rotation == DXGI_MODE_ROTATION_ROTATE270
             {
-                return Err(anyhow::anyhow!("BitBlt failed"));
-            }
+                (mode_height, mode_width)
+            } else {
+                (mode_width, mode_height)
+            };
 
-            // Get bitmap data
-            let bitmap = self
-                .bitmap
-                .ok_or_else(|| anyhow::anyhow!("Bitmap not initialized"))?;
-            let mut bitmap_info = BITMAPINFO {
-                bmiHeader: BITMAPINFOHEADER {
-                    biSize: std::mem::size_of::<BITMAPINFOHEADER>() as u32,
-                    biWidth: self.width as i32,
-                    biHeight: -(self.height as i32), // Top-down DIB
-                    biPlanes: 1,
-                    biBitCount: 32,
-                    biCompression: BI_RGB.0,
-                    biSizeImage: 0,
-                    biXPelsPerMeter: 0,
-                    biYPelsPerMeter: 0,
-                    biClrUsed: 0,
-                    biClrImportant: 0,
+            // Duplicated desktop surfaces are always B8G8R8A8_UNORM
+            let staging_desc = D3D11_TEXTURE2D_DESC {
+                Width: mode_width,
+                Height: mode_height,
+                MipLevels: 1,
+                ArraySize: 1,
+                Format: DXGI_FORMAT_B8G8R8A8_UNORM,
+                SampleDesc: DXGI_SAMPLE_DESC {
+                    Count: 1,
+                    Quality: 0,
                 },
-                bmiColors: [RGBQUAD::default(); 1],
+                Usage: D3D11_USAGE_STAGING,
+                BindFlags: D3D11_BIND_FLAG(0),
+                CPUAccessFlags: D3D11_CPU_ACCESS_READ,
+                MiscFlags: D3D11_RESOURCE_MISC_FLAG(0),
             };
-
-            let mut buffer = vec![0u8; (self.width * self.height * 4) as usize];
-
-            let lines = GetDIBits(
-                hdc,
-                bitmap,
-                0,
-                self.height,
-                Some(buffer.as_mut_ptr() as *mut _),
-                &mut bitmap_info,
-                DIB_RGB_COLORS,
+            let mut staging = None;
+            device.CreateTexture2D(&staging_desc, None, Some(&mut staging))?;
+            let staging = staging.ok_or_else(|| windows::core::Error::from(E_FAIL))?;
+
+            tracing::info!(
+                display_id,
+                width,
+                height,
+                rotation = rotation.0,
+                "Desktop duplication started"
             );
 
-            if lines == 0 {
-                return Err(anyhow::anyhow!("GetDIBits failed"));
-            }
-
-            Ok(buffer)
+            Ok(Self {
+                _device: device,
+                context,
+                duplication,
+                staging,
+                mode_width,
+                mode_height,
+                rotation,
+                width,
+                height,
+            })
         }
     }
 }
 
-impl Drop for WindowsScreenCapture {
-    fn drop(&mut self) {
-        unsafe {
-            if let Some(bitmap) = self.bitmap {
-                DeleteObject(bitmap);
+/// Find the adapter and DXGI output that drive the given monitor
+unsafe fn find_output(monitor: HMONITOR) -> windows::core::Result<(IDXGIAdapter1, IDXGIOutput)> {
+    let factory: IDXGIFactory1 = CreateDXGIFactory1()?;
+
+    let mut adapter_index = 0;
+    while let Ok(adapter) = factory.EnumAdapters1(adapter_index) {
+        let mut output_index = 0;
+        while let Ok(output) = adapter.EnumOutputs(output_index) {
+            let mut desc = DXGI_OUTPUT_DESC::default();
+            output.GetDesc(&mut desc)?;
+            if desc.Monitor == monitor {
+                return Ok((adapter, output));
             }
-            if let Some(mem_dc) = self.mem_dc {
-                DeleteDC(mem_dc);
+            output_index += 1;
+        }
+        adapter_index += 1;
+    }
+
+    Err(DXGI_ERROR_NOT_FOUND.into())
+}
+
+#[derive(Default)]
+struct PointerState {
+    position: POINT,
+    visible: bool,
+    shape_info: DXGI_OUTDUPL_POINTER_SHAPE_INFO,
+    shape: Vec<u8>,
+}
+
+/// Composite the duplicated pointer shape onto a BGRA desktop image
+///
+/// Desktop Duplication delivers the cursor separately from the desktop surface, so
+/// it has to be drawn in software when `capture_cursor` is enabled.
+fn draw_pointer(
+    frame: &mut [u8],
+    width: u32,
+    height: u32,
+    position: POINT,
+    info: &DXGI_OUTDUPL_POINTER_SHAPE_INFO,
+    shape: &[u8],
+) {
+    let monochrome = info.Type == DXGI_OUTDUPL_POINTER_SHAPE_TYPE_MONOCHROME.0 as u32;
+    let masked = info.Type == DXGI_OUTDUPL_POINTER_SHAPE_TYPE_MASKED_COLOR.0 as u32;
+    // Monochrome shapes stack the AND mask on top of the XOR mask
+    let shape_height = if monochrome {
+        info.Height / 2
+    } else {
+        info.Height
+    };
+    let pitch = info.Pitch as usize;
+
+    for sy in 0..shape_height {
+        let dy = position.y + sy as i32;
+        if dy < 0 || dy >= height as i32 {
+            continue;
+        }
+        for sx in 0..info.Width {
+            let dx = position.x + sx as i32;
+            if dx < 0 || dx >= width as i32 {
+                continue;
             }
-            if let Some(hdc) = self.hdc {
-                ReleaseDC(None, hdc);
+
+            let offset = (dy as usize * width as usize + dx as usize) * 4;
+            let dst = &mut frame[offset..offset + 4];
+
+            if monochrome {
+                let bit = 0x80u8 >> (sx % 8);
+                let byte = (sx / 8) as usize;
+                let (Some(&and_mask), Some(&xor_mask)) = (
+                    shape.get(sy as usize * pitch + byte),
+                    shape.get((sy + shape_height) as usize * pitch + byte),
+                ) else {
+                    return;
+                };
+                for channel in &mut dst[..3] {
+                    let mut value = if and_mask & bit != 0 { *channel } else { 0 };
+                    if xor_mask & bit != 0 {
+                        value ^= 0xFF;
+                    }
+                    *channel = value;
+                }
+            } else {
+                let start = sy as usize * pitch + sx as usize * 4;
+                let Some(src) = shape.get(start..start + 4) else {
+                    return;
+                };
+                if masked {
+                    // Alpha acts as a mask: 0 replaces the pixel, 0xFF XORs it
+                    let replace = src[3] == 0;
+                    for (d, &s) in dst[..3].iter_mut().zip(&src[..3]) {
+                        *d = if replace { s } else { *d ^ s };
+                    }
+                } else {
+                    let alpha = src[3] as u32;
+                    for (d, &s) in dst[..3].iter_mut().zip(&src[..3]) {
+                        *d = ((s as u32 * alpha + *d as u32 * (255 - alpha)) / 255) as u8;
+                    }
+                }
             }
         }
     }
@@ -385,28 +627,61 @@ impl Drop for WindowsWindowCapture {
 
 // Helper functions
 fn get_display_count() -> Result<u32> {
-    unsafe {
-        let count = GetSystemMetrics(SM_CMONITORS);
-        if count > 0 {
-            Ok(count as u32)
-        } else {
-            Ok(1) // Fallback to single display
-        }
+    let count = enumerate_monitors().len() as u32;
+    if count > 0 {
+        Ok(count)
+    } else {
+        Err(anyhow::anyhow!("No displays attached to the desktop"))
     }
 }
 
 fn get_display_dimensions(display_id: u32) -> Result<(u32, u32)> {
+    let (_, _, width, height) = get_display_bounds(display_id)?;
+    Ok((width, height))
+}
+
+fn get_display_bounds(display_id: u32) -> Result<(i32, i32, u32, u32)> {
+    let monitor = *enumerate_monitors()
+        .get(display_id as usize)
+        .ok_or_else(|| anyhow::anyhow!("Display {} not found", display_id))?;
+
+    let mut monitor_info = MONITORINFO {
+        cbSize: std::mem::size_of::<MONITORINFO>() as u32,
+        ..Default::default()
+    };
+
     unsafe {
-        if display_id == 0 {
-            // Primary display
-            let width = GetSystemMetrics(SM_CXSCREEN) as u32;
-            let height = GetSystemMetrics(SM_CYSCREEN) as u32;
-            Ok((width, height))
-        } else {
-            // Secondary displays - simplified for now
-            Ok((1920, 1080)) // TODO: Implement proper multi-monitor support
+        if !GetMonitorInfoW(monitor, &mut monitor_info).as_bool() {
+            return Err(anyhow::anyhow!(
+                "Failed to get bounds for display {}",
+                display_id
+            ));
         }
     }
+
+    let rect = monitor_info.rcMonitor;
+    Ok((
+        rect.left,
+        rect.top,
+        (rect.right - rect.left) as u32,
+        (rect.bottom - rect.top) as u32,
+    ))
+}
+
+/// Monitors in EnumDisplayMonitors order, which is what `display_id` indexes
+fn enumerate_monitors() -> Vec<HMONITOR> {
+    let mut monitors: Vec<HMONITOR> = Vec::new();
+
+    unsafe {
+        EnumDisplayMonitors(
+            None,
+            None,
+            Some(enum_display_proc),
+            LPARAM(&mut monitors as *mut _ as isize),
+        );
+    }
+
+    monitors
 }
 
 fn get_window_dimensions(hwnd: HWND) -> Result<(u32, u32)> {
@@ -450,40 +725,14 @@ fn find_window_by_title(title: &str) -> Result<HWND> {
     }
 }
 
-struct DisplayEnumContext {
-    target_index: u32,
-    current_index: u32,
-    found_bounds: Option<(u32, u32, u32, u32)>,
-}
-
 unsafe extern "system" fn enum_display_proc(
     hmonitor: HMONITOR,
     _hdc: HDC,
     _lprect: *mut RECT,
     lparam: LPARAM,
 ) -> BOOL {
-    let context = &mut *(lparam.0 as *mut DisplayEnumContext);
-
-    if context.current_index == context.target_index {
-        let mut monitor_info = MONITORINFO {
-            cbSize: std::mem::size_of::<MONITORINFO>() as u32,
-            ..Default::default()
-        };
-
-        if GetMonitorInfoW(hmonitor, &mut monitor_info).as_bool() {
-            let rect = monitor_info.rcMonitor;
-            context.found_bounds = Some((
-                rect.left as u32,
-                rect.top as u32,
-                (rect.right - rect.left) as u32,
-                (rect.bottom - rect.top) as u32,
-            ));
-        }
-
-        return FALSE; // Stop enumeration
-    }
-
-    context.current_index += 1;
+    let monitors = &mut *(lparam.0 as *mut Vec<HMONITOR>);
+    monitors.push(hmonitor);
     TRUE
 }
 
@@ -538,3 +787,111 @@ unsafe extern "system" fn enum_windows_proc(hwnd: HWND, lparam: LPARAM) -> BOOL
 
     TRUE
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_display_enumeration() {
+        if std::env::var("CI").is_ok() {
+            println!("Skipping display enumeration in CI environment");
+            return;
+        }
+
+        let count = get_display_count().unwrap();
+        assert!(count >= 1);
+
+        for display_id in 0..count {
+            let (_, _, width, height) = get_display_bounds(display_id).unwrap();
+            assert!((320..=16384).contains(&width), "width {width}");
+            assert!((200..=16384).contains(&height), "height {height}");
+        }
+        assert!(get_display_bounds(count).is_err());
+    }
+
+    #[test]
+    fn test_rotate_to_desktop() {
+        // 3x2 scan-out image; the first byte of each pixel is its index
+        let image: Vec<u8> = (0..6u8).flat_map(|i| [i, 0, 0, 255]).collect();
+        let first_bytes = |image: Vec<u8>| image.chunks_exact(4).map(|p| p[0]).collect::<Vec<_>>();
+
+        assert_eq!(
+            first_bytes(rotate_to_desktop(
+                image.clone(),
+                3,
+                2,
+                DXGI_MODE_ROTATION_IDENTITY
+            )),
+            [0, 1, 2, 3, 4, 5]
+        );
+        // Desktop is 2x3 for the quarter turns
+        assert_eq!(
+            first_bytes(rotate_to_desktop(
+                image.clone(),
+                3,
+                2,
+                DXGI_MODE_ROTATION_ROTATE90
+            )),
+            [3, 0, 4, 1, 5, 2]
+        );
+        assert_eq!(
+            first_bytes(rotate_to_desktop(
+                image.clone(),
+                3,
+                2,
+                DXGI_MODE_ROTATION_ROTATE180
+            )),
+            [5, 4, 3, 2, 1, 0]
+        );
+        assert_eq!(
+            first_bytes(rotate_to_desktop(image, 3, 2, DXGI_MODE_ROTATION_ROTATE270)),
+            [2, 5, 1, 4, 0, 3]
+        );
+    }
+
+    #[test]
+    fn test_desktop_duplication_captures_frame() {
+        if std::env::var("CI").is_ok() {
+            println!("Skipping desktop duplication in CI environment");
+            return;
+        }
+
+        let mut capture = WindowsScreenCapture::new(0, true).unwrap();
+        let frame = capture.capture_frame().unwrap();
+
+        let (_, _, width, height) = capture.get_display_bounds(0).unwrap();
+        assert_eq!((frame.width, frame.height), (width, height));
+        assert_eq!(frame.format, VideoFormat::Bgra8);
+        assert_eq!(frame.data.len(), (width * height * 4) as usize);
+
+        // An idle desktop must still yield a frame
+        let again = capture.capture_frame().unwrap();
+        assert_eq!(again.data.len(), frame.data.len());
+    }
+
+    #[test]
+    fn test_draw_pointer_blends_and_clips_color_shape() {
+        let mut frame = vec![0u8; 4 * 4 * 4];
+        let info = DXGI_OUTDUPL_POINTER_SHAPE_INFO {
+            Type: DXGI_OUTDUPL_POINTER_SHAPE_TYPE_COLOR.0 as u32,
+            Width: 2,
+            Height: 2,
+            Pitch: 8,
+            ..Default::default()
+        };
+        // Opaque white, half-transparent white, transparent, opaque white
+        let shape = [
+            255, 255, 255, 255, 255, 255, 255, 128, //
+            255, 255, 255, 0, 255, 255, 255, 255,
+        ];
+
+        draw_pointer(&mut frame, 4, 4, POINT { x: 3, y: 2 }, &info, &shape);
+
+        let pixel = |x: usize, y: usize| &frame[(y * 4 + x) * 4..(y * 4 + x) * 4 + 3];
+        assert_eq!(pixel(3, 2), [255, 255, 255]);
+        // Column 4 is off-screen and the transparent texel leaves the pixel alone
+        assert_eq!(pixel(3, 3), [0, 0, 0]);
+        assert_eq!(pixel(2, 2), [0, 0, 0]);
+    }
+}
@@ -0,0 +1,557 @@
+/*
+ * Constellation Studio - Professional Real-time Video Processing
+ * Copyright (c) 2025 MACHIKO LAB
+ *
+ * This program is free software: you can redistribute it and/or modify
+ * it under the terms of the GNU Affero General Public License as published by
+ * the Free Software Foundation, either version 3 of the License, or
+ * (at your option) any later version.
+ *
+ * This program is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+ * GNU Affero General Public License for more details.
+ *
+ * You should have received a copy of the GNU Affero General Public License
+ * along with this program. If not, see <https://www.gnu.org/licenses/>.
+ */
+
+use crate::controller::{apply_mappings, ControllerConfig, ControllerNode};
+use crate::{NodeProcessor, NodeProperties, ParameterDefinition, ParameterType};
+use anyhow::Result;
+use constellation_core::*;
+use http_body_util::{BodyExt, Empty};
+use hyper::body::Bytes;
+use hyper::{Request, Uri};
+use hyper_util::rt::TokioIo;
+use serde_json::Value;
+use std::collections::HashMap;
+use std::sync::mpsc::{self, Receiver, Sender};
+use std::thread::JoinHandle;
+use std::time::Duration;
+use tokio::sync::watch;
+use uuid::Uuid;
+
+const DEFAULT_URL: &str = "http://127.0.0.1:8080/";
+const DEFAULT_POLL_INTERVAL_MS: u64 = 1000;
+const DEFAULT_TIMEOUT_MS: u64 = 2000;
+
+/// 取得した数値を出力するソースパラメータ名
+pub const VALUE_PARAMETER: &str = "value";
+
+/// JSONボディからJSON Pointer（RFC 6901）で数値を取り出す
+/// 空のポインタはボディ全体を指す
+pub fn extract_value(body: &[u8], pointer: &str) -> Result<f32> {
+    let json: Value = serde_json::from_slice(body)
+        .map_err(|e| anyhow::anyhow!("Response is not valid JSON: {}", e))?;
+    let value = json
+        .pointer(pointer)
+        .ok_or_else(|| anyhow::anyhow!("JSON pointer '{}' not found in response", pointer))?;
+
+    match value.as_f64() {
+        Some(number) if number.is_finite() => Ok(number as f32),
+        _ => anyhow::bail!("Value at '{}' is not a number: {}", pointer, value),
+    }
+}
+
+/// ポーリング設定（変更されたらワーカーを作り直す）
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct PollSettings {
+    uri: Uri,
+    pointer: String,
+    interval: Duration,
+    timeout: Duration,
+}
+
+/// ポーリング用スレッド（内部でtokioランタイムを動かす）
+struct ApiPollWorker {
+    shutdown: watch::Sender<bool>,
+    thread: Option<JoinHandle<()>>,
+}
+
+impl ApiPollWorker {
+    fn spawn(settings: PollSettings, sender: Sender<f32>) -> Result<Self> {
+        let runtime = tokio::runtime::Builder::new_current_thread()
+            .enable_all()
+            .build()?;
+        let (shutdown, shutdown_receiver) = watch::channel(false);
+
+        let thread = std::thread::spawn(move || {
+            runtime.block_on(run_poller(settings, sender, shutdown_receiver));
+        });
+
+        Ok(Self {
+            shutdown,
+            thread: Some(thread),
+        })
+    }
+}
+
+impl Drop for ApiPollWorker {
+    fn drop(&mut self) {
+        let _ = self.shutdown.send(true);
+        if let Some(thread) = self.thread.take() {
+            let _ = thread.join();
+        }
+    }
+}
+
+/// 一定間隔でGETし、取り出せた値だけを送る。失敗時は送らず直前の値を保持させる
+async fn run_poller(
+    settings: PollSettings,
+    sender: Sender<f32>,
+    mut shutdown: watch::Receiver<bool>,
+) {
+    let mut interval = tokio::time::interval(settings.interval);
+    interval.set_missed_tick_behavior(tokio::time::MissedTickBehavior::Delay);
+
+    loop {
+        tokio::select! {
+            _ = interval.tick() => {}
+            _ = shutdown.changed() => return,
+        }
+
+        let result = tokio::select! {
+            result = tokio::time::timeout(settings.timeout, fetch(&settings.uri)) => result,
+            _ = shutdown.changed() => return,
+        };
+
+        let value = match result {
+            Ok(Ok(body)) => extract_value(&body, &settings.pointer),
+            Ok(Err(e)) => Err(e),
+            Err(_) => Err(anyhow::anyhow!(
+                "Request timed out after {:?}",
+                settings.timeout
+            )),
+        };
+
+        match value {
+            Ok(value) => {
+                if sender.send(value).is_err() {
+                    return;
+                }
+            }
+            Err(e) => tracing::warn!(
+                "API controller poll of {} failed, holding last value: {}",
+                settings.uri,
+                e
+            ),
+        }
+    }
+}
+
+/// HTTP/1.1でGETし、2xxならボディを返す
+async fn fetch(uri: &Uri) -> Result<Bytes> {
+    let host = uri
+        .host()
+        .ok_or_else(|| anyhow::anyhow!("URL has no host: {}", uri))?;
+    let port = uri.port_u16().unwrap_or(80);
+
+    let stream = tokio::net::TcpStream::connect((host, port)).await?;
+    let (mut sender, connection) =
+        hyper::client::conn::http1::handshake(TokioIo::new(stream)).await?;
+    tokio::spawn(async move {
+        if let Err(e) = connection.await {
+            tracing::debug!("API controller connection closed with error: {}", e);
+        }
+    });
+
+    let authority = uri.authority().map(|a| a.as_str()).unwrap_or(host);
+    let path = uri.path_and_query().map(|p| p.as_str()).unwrap_or("/");
+    let request = Request::get(path)
+        .header(hyper::header::HOST, authority)
+        .header(hyper::header::ACCEPT, "application/json")
+        .body(Empty::<Bytes>::new())?;
+
+    let response = sender.send_request(request).await?;
+    let status = response.status();
+    if !status.is_success() {
+        anyhow::bail!("Server responded with {}", status);
+    }
+
+    Ok(response.into_body().collect().await?.to_bytes())
+}
+
+/// APIコントローラ - REST APIを定期的にポーリングし、JSON内の数値を制御値に変換
+pub struct APIController {
+    id: Uuid,
+    config: NodeConfig,
+    properties: NodeProperties,
+    controller_config: ControllerConfig,
+
+    // ポーリング状態
+    current_settings: Option<PollSettings>,
+    worker: Option<ApiPollWorker>,
+
+    // 値の受信
+    value_sender: Sender<f32>,
+    value_receiver: Receiver<f32>,
+
+    // 現在の値（最初の取得に成功するまでは空）
+    control_values: HashMap<String, f32>,
+}
+
+impl APIController {
+    pub fn new(id: Uuid, config: NodeConfig) -> Result<Self> {
+        let mut parameters = HashMap::new();
+
+        parameters.insert(
+            "url".to_string(),
+            ParameterDefinition {
+                name: "URL".to_string(),
+                parameter_type: ParameterType::String,
+                default_value: Value::String(DEFAULT_URL.to_string()),
+                min_value: None,
+                max_value: None,
+                description: "HTTP endpoint returning JSON".to_string(),
+            },
+        );
+
+        parameters.insert(
+            "json_pointer".to_string(),
+            ParameterDefinition {
+                name: "JSON Pointer".to_string(),
+                parameter_type: ParameterType::String,
+                default_value: Value::String(String::new()),
+                min_value: None,
+                max_value: None,
+                description: "Path to the numeric value, e.g. /data/temperature".to_string(),
+            },
+        );
+
+        parameters.insert(
+            "poll_interval".to_string(),
+            ParameterDefinition {
+                name: "Poll Interval".to_string(),
+                parameter_type: ParameterType::Integer,
+                default_value: Value::from(DEFAULT_POLL_INTERVAL_MS),
+                min_value: Some(Value::from(10)),
+                max_value: Some(Value::from(3_600_000)),
+                description: "Time between requests in milliseconds".to_string(),
+            },
+        );
+
+        parameters.insert(
+            "timeout".to_string(),
+            ParameterDefinition {
+                name: "Timeout".to_string(),
+                parameter_type: ParameterType::Integer,
+                default_value: Value::from(DEFAULT_TIMEOUT_MS),
+                min_value: Some(Value::from(10)),
+                max_value: Some(Value::from(60_000)),
+                description: "Request timeout in milliseconds".to_string(),
+            },
+        );
+
+        parameters.insert(
+            "enabled".to_string(),
+            ParameterDefinition {
+                name: "Enabled".to_string(),
+                parameter_type: ParameterType::Boolean,
+                default_value: Value::Bool(true),
+                min_value: None,
+                max_value: None,
+                description: "Enable/disable API polling".to_string(),
+            },
+        );
+
+        let properties = NodeProperties {
+            id,
+            name: "API Controller".to_string(),
+            node_type: NodeType::Control(ControlType::APIController),
+            input_types: vec![], // 外部APIのみを入力とする
+            output_types: vec![ConnectionType::Control],
+            parameters,
+            enabled: true,
+        };
+
+        let (value_sender, value_receiver) = mpsc::channel();
+
+        Ok(Self {
+            id,
+            config,
+            properties,
+            controller_config: ControllerConfig::default(),
+            current_settings: None,
+            worker: None,
+            value_sender,
+            value_receiver,
+            control_values: HashMap::new(),
+        })
+    }
+
+    /// 最後に取得できた値
+    pub fn value(&self) -> Option<f32> {
+        self.control_values.get(VALUE_PARAMETER).copied()
+    }
+
+    /// 受信済みの値を反映（最新のものが残る）
+    fn drain_values(&mut self) {
+        if let Some(value) = self.value_receiver.try_iter().last() {
+            self.control_values
+                .insert(VALUE_PARAMETER.to_string(), value);
+        }
+    }
+
+    fn millis_parameter(&self, key: &str, default: u64) -> Duration {
+        Duration::from_millis(
+            self.get_parameter(key)
+                .and_then(|v| v.as_u64())
+                .unwrap_or(default)
+                .max(1),
+        )
+    }
+
+    /// パラメータを更新
+    fn update_parameters(&mut self) -> Result<()> {
+        self.controller_config.enabled = self
+            .get_parameter("enabled")
+            .and_then(|v| v.as_bool())
+            .unwrap_or(true);
+
+        let url = self
+            .get_parameter("url")
+            .and_then(|v| v.as_str().map(str::to_string))
+            .unwrap_or_else(|| DEFAULT_URL.to_string());
+        let uri: Uri = url
+            .parse()
+            .map_err(|e| anyhow::anyhow!("Invalid API controller URL {}: {}", url, e))?;
+        if uri.scheme_str() != Some("http") {
+            anyhow::bail!("API controller only supports http:// URLs, got {}", url);
+        }
+
+        let settings = PollSettings {
+            uri,
+            pointer: self
+                .get_parameter("json_pointer")
+                .and_then(|v| v.as_str().map(str::to_string))
+                .unwrap_or_default(),
+            interval: self.millis_parameter("poll_interval", DEFAULT_POLL_INTERVAL_MS),
+            timeout: self.millis_parameter("timeout", DEFAULT_TIMEOUT_MS),
+        };
+        let desired = self.controller_config.enabled.then_some(settings);
+
+        if desired != self.current_settings {
+            // 既存のポーリングを止めてから開始し直す
+            self.worker = None;
+            self.current_settings = desired.clone();
+            if let Some(settings) = desired {
+                self.worker = Some(ApiPollWorker::spawn(settings, self.value_sender.clone())?);
+            }
+        }
+
+        Ok(())
+    }
+}
+
+impl NodeProcessor for APIController {
+    fn process(&mut self, input: FrameData) -> Result<FrameData> {
+        // パラメータを更新
+        self.update_parameters()?;
+
+        // 無効なら入力をそのまま通す
+        if !self.controller_config.enabled {
+            return Ok(input);
+        }
+
+        // 取得済みの値を反映
+        self.drain_values();
+
+        // 制御コマンドを生成
+        let control_commands = self.generate_control_commands();
+
+        let control_data = if !control_commands.is_empty() {
+            Some(ControlData::MultiControl {
+                commands: control_commands,
+            })
+        } else {
+            input.control_data
+        };
+
+        Ok(FrameData {
+            control_data,
+            ..input
+        })
+    }
+
+    fn get_properties(&self) -> NodeProperties {
+        self.properties.clone()
+    }
+
+    fn set_parameter(&mut self, key: &str, value: Value) -> Result<()> {
+        self.config.parameters.insert(key.to_string(), value);
+        Ok(())
+    }
+
+    fn get_parameter(&self, key: &str) -> Option<Value> {
+        self.config.parameters.get(key).cloned()
+    }
+}
+
+impl ControllerNode for APIController {
+    fn add_mapping(&mut self, mapping: ControlMapping) {
+        self.controller_config.mappings.push(mapping);
+    }
+
+    fn remove_mapping(&mut self, source_parameter: &str) {
+        self.controller_config
+            .mappings
+            .retain(|m| m.source_parameter != source_parameter);
+    }
+
+    fn get_control_value(&self, parameter: &str) -> Option<f32> {
+        self.control_values.get(parameter).copied()
+    }
+
+    fn generate_control_commands(&self) -> Vec<ControlCommand> {
+        apply_mappings(&self.controller_config.mappings, &self.control_values)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::io::{Read, Write};
+    use std::sync::{Arc, Mutex};
+
+    /// 設定されたステータスとボディを返し続けるテスト用HTTPサーバー
+    fn spawn_server(response: Arc<Mutex<(u16, String)>>) -> std::net::SocketAddr {
+        let listener = std::net::TcpListener::bind("127.0.0.1:0").unwrap();
+        let addr = listener.local_addr().unwrap();
+
+        std::thread::spawn(move || {
+            for mut stream in listener.incoming().flatten() {
+                // リクエストヘッダの終わりまで読み捨てる
+                let mut request = Vec::new();
+                let mut buffer = [0u8; 1024];
+                while !request.windows(4).any(|w| w == b"\r\n\r\n") {
+                    match stream.read(&mut buffer) {
+                        Ok(0) | Err(_) => break,
+                        Ok(n) => request.extend_from_slice(&buffer[..n]),
+                    }
+                }
+
+                let (status, body) = response.lock().unwrap().clone();
+                let _ = write!(
+                    stream,
+                    "HTTP/1.1 {status} Status\r\nContent-Type: application/json\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{body}",
+                    body.len()
+                );
+            }
+        });
+
+        addr
+    }
+
+    fn create_controller(addr: std::net::SocketAddr, pointer: &str) -> APIController {
+        let mut parameters = HashMap::new();
+        parameters.insert(
+            "url".to_string(),
+            Value::String(format!("http://{addr}/metrics")),
+        );
+        parameters.insert(
+            "json_pointer".to_string(),
+            Value::String(pointer.to_string()),
+        );
+        parameters.insert("poll_interval".to_string(), Value::from(20));
+        parameters.insert("timeout".to_string(), Value::from(500));
+        APIController::new(Uuid::new_v4(), NodeConfig { parameters }).unwrap()
+    }
+
+    /// 値が条件を満たすまでフレームを処理し続ける
+    fn wait_for(controller: &mut APIController, condition: impl Fn(Option<f32>) -> bool) -> bool {
+        let deadline = std::time::Instant::now() + Duration::from_secs(5);
+        while std::time::Instant::now() < deadline {
+            controller.process(FrameData::empty()).unwrap();
+            if condition(controller.value()) {
+                return true;
+            }
+            std::thread::sleep(Duration::from_millis(10));
+        }
+        false
+    }
+
+    #[test]
+    fn test_extract_value_with_json_pointer() {
+        let body = br#"{"data": {"readings": [1.5, 2.5]}, "count": 3}"#;
+        assert_eq!(extract_value(body, "/data/readings/1").unwrap(), 2.5);
+        assert_eq!(extract_value(body, "/count").unwrap(), 3.0);
+        assert_eq!(extract_value(b"42", "").unwrap(), 42.0);
+
+        assert!(extract_value(body, "/missing").is_err());
+        assert!(extract_value(body, "/data").is_err());
+        assert!(extract_value(b"{not json", "").is_err());
+    }
+
+    #[test]
+    fn test_poll_updates_control_value() {
+        let response = Arc::new(Mutex::new((
+            200,
+            r#"{"data": {"temperature": 21.5}}"#.to_string(),
+        )));
+        let addr = spawn_server(response.clone());
+
+        let mut controller = create_controller(addr, "/data/temperature");
+        let target = Uuid::new_v4();
+        controller.add_mapping(ControlMapping::new(
+            VALUE_PARAMETER.to_string(),
+            target,
+            "opacity".to_string(),
+        ));
+
+        assert!(wait_for(&mut controller, |value| value == Some(21.5)));
+        let output = controller.process(FrameData::empty()).unwrap();
+        match output.control_data {
+            Some(ControlData::MultiControl { commands }) => {
+                assert_eq!(commands[0].target_node_id, target);
+            }
+            other => panic!("Expected control commands, got {other:?}"),
+        }
+
+        *response.lock().unwrap() = (200, r#"{"data": {"temperature": 30}}"#.to_string());
+        assert!(wait_for(&mut controller, |value| value == Some(30.0)));
+    }
+
+    #[test]
+    fn test_failed_polls_hold_last_good_value() {
+        let response = Arc::new(Mutex::new((200, r#"{"level": 0.75}"#.to_string())));
+        let addr = spawn_server(response.clone());
+
+        let mut controller = create_controller(addr, "/level");
+        assert!(wait_for(&mut controller, |value| value == Some(0.75)));
+
+        // エラー応答や壊れたJSONの間は直前の値を保持する
+        *response.lock().unwrap() = (500, r#"{"level": 0.1}"#.to_string());
+        assert!(!wait_for_changes(&mut controller));
+        *response.lock().unwrap() = (200, "{broken".to_string());
+        assert!(!wait_for_changes(&mut controller));
+        assert_eq!(controller.value(), Some(0.75));
+
+        *response.lock().unwrap() = (200, r#"{"level": 0.25}"#.to_string());
+        assert!(wait_for(&mut controller, |value| value == Some(0.25)));
+    }
+
+    /// 数回のポーリング間隔のあいだに値が変わったか
+    fn wait_for_changes(controller: &mut APIController) -> bool {
+        let before = controller.value();
+        for _ in 0..10 {
+            std::thread::sleep(Duration::from_millis(20));
+            controller.process(FrameData::empty()).unwrap();
+            if controller.value() != before {
+                return true;
+            }
+        }
+        false
+    }
+
+    #[test]
+    fn test_rejects_non_http_urls() {
+        let mut parameters = HashMap::new();
+        parameters.insert(
+            "url".to_string(),
+            Value::String("https://example.com/value".to_string()),
+        );
+        let mut controller = APIController::new(Uuid::new_v4(), NodeConfig { parameters }).unwrap();
+        assert!(controller.process(FrameData::empty()).is_err());
+    }
+}
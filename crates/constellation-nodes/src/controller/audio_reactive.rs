@@ -0,0 +1,496 @@
+/*
+ * Constellation Studio - Professional Real-time Video Processing
+ * Copyright (c) 2025 MACHIKO LAB
+ *
+ * This program is free software: you can redistribute it and/or modify
+ * it under the terms of the GNU Affero General Public License as published by
+ * the Free Software Foundation, either version 3 of the License, or
+ * (at your option) any later version.
+ *
+ * This program is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+ * GNU Affero General Public License for more details.
+ *
+ * You should have received a copy of the GNU Affero General Public License
+ * along with this program. If not, see <https://www.gnu.org/licenses/>.
+ */
+
+use crate::controller::{apply_mappings, ControllerConfig, ControllerNode};
+use crate::{NodeProcessor, NodeProperties, ParameterDefinition, ParameterType};
+use anyhow::Result;
+use constellation_audio::{AudioLevelAnalyzer, AudioLevelUpdate};
+use constellation_core::*;
+use serde_json::Value;
+use std::collections::HashMap;
+use std::sync::mpsc::Receiver;
+use uuid::Uuid;
+
+/// 制御値として出力するソースパラメータ名
+pub const LEVEL_PARAMETER: &str = "level";
+
+/// 参照するレベルの種類
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum LevelMode {
+    Peak,
+    Rms,
+}
+
+impl LevelMode {
+    pub fn from_name(name: &str) -> Option<Self> {
+        match name.to_ascii_lowercase().as_str() {
+            "peak" => Some(Self::Peak),
+            "rms" => Some(Self::Rms),
+            _ => None,
+        }
+    }
+
+    fn read(&self, level: &AudioLevel) -> f32 {
+        match self {
+            Self::Peak => level.mono_peak(),
+            Self::Rms => level.mono_rms(),
+        }
+    }
+}
+
+/// アタック/リリースの時定数で目標値へ近づける（1次のバリスティクス）
+pub fn smooth_level(
+    current: f32,
+    target: f32,
+    elapsed_ms: f32,
+    attack_ms: f32,
+    release_ms: f32,
+) -> f32 {
+    let time_constant = if target > current {
+        attack_ms
+    } else {
+        release_ms
+    };
+    if time_constant <= 0.0 {
+        return target;
+    }
+
+    let coefficient = 1.0 - (-elapsed_ms / time_constant).exp();
+    current + (target - current) * coefficient
+}
+
+/// オーディオリアクティブコントローラ - 音声レベルを制御値に変換
+pub struct AudioReactiveController {
+    id: Uuid,
+    config: NodeConfig,
+    properties: NodeProperties,
+    controller_config: ControllerConfig,
+
+    // レベル設定
+    source_node: Option<Uuid>, // 参照するオーディオノード
+    mode: LevelMode,
+    attack_ms: f32,
+    release_ms: f32,
+    sensitivity: f32,
+
+    // レベル受信
+    level_receiver: Option<Receiver<AudioLevelUpdate>>,
+    last_timestamp: Option<u64>, // 直前のレベル更新時刻（ミリ秒）
+
+    // 現在の値
+    control_values: HashMap<String, f32>,
+}
+
+impl AudioReactiveController {
+    pub fn new(id: Uuid, config: NodeConfig) -> Result<Self> {
+        let mut parameters = HashMap::new();
+
+        parameters.insert(
+            "source_node".to_string(),
+            ParameterDefinition {
+                name: "Source Node".to_string(),
+                parameter_type: ParameterType::String,
+                default_value: Value::String(String::new()),
+                min_value: None,
+                max_value: None,
+                description: "ID of the audio node whose level drives this controller".to_string(),
+            },
+        );
+
+        parameters.insert(
+            "level_mode".to_string(),
+            ParameterDefinition {
+                name: "Level Mode".to_string(),
+                parameter_type: ParameterType::Enum(vec!["peak".to_string(), "rms".to_string()]),
+                default_value: Value::String("rms".to_string()),
+                min_value: None,
+                max_value: None,
+                description: "Use the peak or RMS level of the source".to_string(),
+            },
+        );
+
+        parameters.insert(
+            "attack".to_string(),
+            ParameterDefinition {
+                name: "Attack".to_string(),
+                parameter_type: ParameterType::Float,
+                default_value: Value::from(10.0),
+                min_value: Some(Value::from(0.0)),
+                max_value: Some(Value::from(5000.0)),
+                description: "Time constant for rising levels in milliseconds".to_string(),
+            },
+        );
+
+        parameters.insert(
+            "release".to_string(),
+            ParameterDefinition {
+                name: "Release".to_string(),
+                parameter_type: ParameterType::Float,
+                default_value: Value::from(300.0),
+                min_value: Some(Value::from(0.0)),
+                max_value: Some(Value::from(10000.0)),
+                description: "Time constant for falling levels in milliseconds".to_string(),
+            },
+        );
+
+        parameters.insert(
+            "sensitivity".to_string(),
+            ParameterDefinition {
+                name: "Sensitivity".to_string(),
+                parameter_type: ParameterType::Float,
+                default_value: Value::from(1.0),
+                min_value: Some(Value::from(0.0)),
+                max_value: Some(Value::from(100.0)),
+                description: "Gain applied to the level before clamping to 0-1".to_string(),
+            },
+        );
+
+        parameters.insert(
+            "enabled".to_string(),
+            ParameterDefinition {
+                name: "Enabled".to_string(),
+                parameter_type: ParameterType::Boolean,
+                default_value: Value::Bool(true),
+                min_value: None,
+                max_value: None,
+                description: "Enable/disable audio reactive controller".to_string(),
+            },
+        );
+
+        let properties = NodeProperties {
+            id,
+            name: "Audio Reactive".to_string(),
+            node_type: NodeType::Control(ControlType::AudioReactive),
+            input_types: vec![], // レベルはアナライザから購読
+            output_types: vec![ConnectionType::Control],
+            parameters,
+            enabled: true,
+        };
+
+        let mut control_values = HashMap::new();
+        control_values.insert(LEVEL_PARAMETER.to_string(), 0.0);
+
+        Ok(Self {
+            id,
+            config,
+            properties,
+            controller_config: ControllerConfig::default(),
+            source_node: None,
+            mode: LevelMode::Rms,
+            attack_ms: 10.0,
+            release_ms: 300.0,
+            sensitivity: 1.0,
+            level_receiver: None,
+            last_timestamp: None,
+            control_values,
+        })
+    }
+
+    /// アナライザのレベル更新を購読する
+    pub fn subscribe(&mut self, analyzer: &mut AudioLevelAnalyzer) {
+        self.level_receiver = Some(analyzer.subscribe());
+        self.last_timestamp = None;
+    }
+
+    /// 平滑化後の現在値
+    pub fn level(&self) -> f32 {
+        self.control_values
+            .get(LEVEL_PARAMETER)
+            .copied()
+            .unwrap_or(0.0)
+    }
+
+    /// 受信済みのレベル更新を順に平滑化して反映
+    fn drain_levels(&mut self) {
+        let Some(receiver) = &self.level_receiver else {
+            return;
+        };
+        let updates: Vec<AudioLevelUpdate> = receiver.try_iter().collect();
+
+        let mut level = self.level();
+        for update in updates {
+            if Some(update.node_id) != self.source_node {
+                continue;
+            }
+
+            let target = (self.mode.read(&update.level) * self.sensitivity).clamp(0.0, 1.0);
+            let elapsed_ms = self
+                .last_timestamp
+                .map(|last| update.level.timestamp.saturating_sub(last) as f32)
+                .unwrap_or(0.0);
+            self.last_timestamp = Some(update.level.timestamp);
+
+            level = smooth_level(level, target, elapsed_ms, self.attack_ms, self.release_ms);
+        }
+
+        self.control_values
+            .insert(LEVEL_PARAMETER.to_string(), level);
+    }
+
+    /// パラメータを更新
+    fn update_parameters(&mut self) {
+        let source_node = self
+            .get_parameter("source_node")
+            .and_then(|v| v.as_str().and_then(|s| Uuid::parse_str(s).ok()));
+        if source_node != self.source_node {
+            self.source_node = source_node;
+            self.last_timestamp = None;
+        }
+
+        if let Some(mode) = self
+            .get_parameter("level_mode")
+            .and_then(|v| v.as_str().and_then(LevelMode::from_name))
+        {
+            self.mode = mode;
+        }
+
+        if let Some(attack) = self.get_parameter("attack").and_then(|v| v.as_f64()) {
+            self.attack_ms = attack.max(0.0) as f32;
+        }
+
+        if let Some(release) = self.get_parameter("release").and_then(|v| v.as_f64()) {
+            self.release_ms = release.max(0.0) as f32;
+        }
+
+        if let Some(sensitivity) = self.get_parameter("sensitivity").and_then(|v| v.as_f64()) {
+            self.sensitivity = sensitivity.max(0.0) as f32;
+        }
+
+        self.controller_config.enabled = self
+            .get_parameter("enabled")
+            .and_then(|v| v.as_bool())
+            .unwrap_or(true);
+    }
+}
+
+impl NodeProcessor for AudioReactiveController {
+    fn process(&mut self, input: FrameData) -> Result<FrameData> {
+        // パラメータを更新
+        self.update_parameters();
+
+        // 無効なら入力をそのまま通す
+        if !self.controller_config.enabled {
+            return Ok(input);
+        }
+
+        // レベル更新を反映
+        self.drain_levels();
+
+        // 制御コマンドを生成
+        let control_commands = self.generate_control_commands();
+
+        let control_data = if !control_commands.is_empty() {
+            Some(ControlData::MultiControl {
+                commands: control_commands,
+            })
+        } else {
+            input.control_data
+        };
+
+        Ok(FrameData {
+            control_data,
+            ..input
+        })
+    }
+
+    fn get_properties(&self) -> NodeProperties {
+        self.properties.clone()
+    }
+
+    fn set_parameter(&mut self, key: &str, value: Value) -> Result<()> {
+        self.config.parameters.insert(key.to_string(), value);
+        Ok(())
+    }
+
+    fn get_parameter(&self, key: &str) -> Option<Value> {
+        self.config.parameters.get(key).cloned()
+    }
+}
+
+impl ControllerNode for AudioReactiveController {
+    fn add_mapping(&mut self, mapping: ControlMapping) {
+        self.controller_config.mappings.push(mapping);
+    }
+
+    fn remove_mapping(&mut self, source_parameter: &str) {
+        self.controller_config
+            .mappings
+            .retain(|m| m.source_parameter != source_parameter);
+    }
+
+    fn get_control_value(&self, parameter: &str) -> Option<f32> {
+        self.control_values.get(parameter).copied()
+    }
+
+    fn generate_control_commands(&self) -> Vec<ControlCommand> {
+        apply_mappings(&self.controller_config.mappings, &self.control_values)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn create_controller(parameters: HashMap<String, Value>) -> AudioReactiveController {
+        AudioReactiveController::new(Uuid::new_v4(), NodeConfig { parameters }).unwrap()
+    }
+
+    fn level(peak: f32, rms: f32, timestamp: u64) -> AudioLevel {
+        AudioLevel {
+            peak_left: peak,
+            peak_right: peak,
+            rms_left: rms,
+            rms_right: rms,
+            timestamp,
+            ..AudioLevel::new()
+        }
+    }
+
+    fn ballistics_parameters(source: Uuid) -> HashMap<String, Value> {
+        let mut parameters = HashMap::new();
+        parameters.insert("source_node".to_string(), Value::String(source.to_string()));
+        parameters.insert("attack".to_string(), Value::from(50.0));
+        parameters.insert("release".to_string(), Value::from(200.0));
+        parameters
+    }
+
+    /// アナライザ購読とは別に、テスト用にレベル更新を直接流し込む
+    fn feed(
+        controller: &mut AudioReactiveController,
+        sender: &std::sync::mpsc::Sender<AudioLevelUpdate>,
+        node_id: Uuid,
+        level: AudioLevel,
+    ) -> f32 {
+        sender.send(AudioLevelUpdate { node_id, level }).unwrap();
+        controller.process(FrameData::empty()).unwrap();
+        controller.level()
+    }
+
+    #[test]
+    fn test_smoothing_tracks_rising_then_falling_level() {
+        let source = Uuid::new_v4();
+        let mut controller = create_controller(ballistics_parameters(source));
+        let (sender, receiver) = std::sync::mpsc::channel();
+        controller.level_receiver = Some(receiver);
+
+        // 無音から開始
+        feed(&mut controller, &sender, source, level(0.0, 0.0, 0));
+
+        // 立ち上がり: アタック時定数(50ms)経過で約63%
+        let mut previous = 0.0;
+        let mut value = 0.0;
+        for step in 1..=5 {
+            value = feed(&mut controller, &sender, source, level(0.0, 0.8, step * 10));
+            assert!(value > previous, "level should rise monotonically");
+            previous = value;
+        }
+        let expected = 0.8 * (1.0 - (-1.0f32).exp());
+        assert!(
+            (value - expected).abs() < 1e-4,
+            "got {value}, expected {expected}"
+        );
+
+        // 立ち下がり: リリース時定数(200ms)経過で約37%まで減衰
+        let peak = value;
+        for step in 1..=20 {
+            value = feed(
+                &mut controller,
+                &sender,
+                source,
+                level(0.0, 0.0, 50 + step * 10),
+            );
+            assert!(value < previous, "level should fall monotonically");
+            previous = value;
+        }
+        let expected = peak * (-1.0f32).exp();
+        assert!(
+            (value - expected).abs() < 1e-4,
+            "got {value}, expected {expected}"
+        );
+    }
+
+    #[test]
+    fn test_subscribes_to_analyzer_and_applies_sensitivity() {
+        let source = Uuid::new_v4();
+        let mut parameters = HashMap::new();
+        parameters.insert("source_node".to_string(), Value::String(source.to_string()));
+        parameters.insert("level_mode".to_string(), Value::String("peak".to_string()));
+        parameters.insert("attack".to_string(), Value::from(0.0));
+        parameters.insert("sensitivity".to_string(), Value::from(2.0));
+        let mut controller = create_controller(parameters);
+
+        let target = Uuid::new_v4();
+        controller.add_mapping(ControlMapping::new(
+            LEVEL_PARAMETER.to_string(),
+            target,
+            "opacity".to_string(),
+        ));
+
+        let mut analyzer = AudioLevelAnalyzer::new();
+        controller.subscribe(&mut analyzer);
+
+        // 参照していないノードは無視される
+        let loud = UnifiedAudioData::Stereo {
+            sample_rate: 48000,
+            channels: 2,
+            samples: vec![0.9, -0.9],
+        };
+        analyzer.analyze_frame(Uuid::new_v4(), &loud);
+        controller.process(FrameData::empty()).unwrap();
+        assert_eq!(controller.level(), 0.0);
+
+        let quiet = UnifiedAudioData::Stereo {
+            sample_rate: 48000,
+            channels: 2,
+            samples: vec![0.3, -0.3],
+        };
+        analyzer.analyze_frame(source, &quiet);
+        let output = controller.process(FrameData::empty()).unwrap();
+        assert!((controller.level() - 0.6).abs() < 1e-5);
+
+        match output.control_data {
+            Some(ControlData::MultiControl { commands }) => {
+                assert_eq!(commands.len(), 1);
+                assert_eq!(commands[0].target_node_id, target);
+                assert!(
+                    matches!(commands[0].value, ParameterValue::Float(v) if (v - 0.6).abs() < 1e-5)
+                );
+            }
+            other => panic!("expected MultiControl, got {:?}", other),
+        }
+
+        // 感度で1.0を超える場合はクランプ
+        controller
+            .set_parameter("sensitivity", Value::from(10.0))
+            .unwrap();
+        analyzer.clear_node(&source);
+        analyzer.analyze_frame(source, &quiet);
+        controller.process(FrameData::empty()).unwrap();
+        assert_eq!(controller.level(), 1.0);
+    }
+
+    #[test]
+    fn test_smooth_level_uses_attack_and_release() {
+        assert_eq!(smooth_level(0.0, 1.0, 10.0, 0.0, 100.0), 1.0);
+        let rising = smooth_level(0.0, 1.0, 10.0, 10.0, 100.0);
+        let falling = smooth_level(1.0, 0.0, 10.0, 100.0, 10.0);
+        assert!((rising - (1.0 - (-1.0f32).exp())).abs() < 1e-6);
+        assert!((falling - (-1.0f32).exp()).abs() < 1e-6);
+        assert_eq!(smooth_level(0.5, 1.0, 0.0, 10.0, 10.0), 0.5);
+    }
+}
@@ -0,0 +1,482 @@
+/*
+ * Constellation Studio - Professional Real-time Video Processing
+ * Copyright (c) 2025 MACHIKO LAB
+ *
+ * This program is free software: you can redistribute it and/or modify
+ * it under the terms of the GNU Affero General Public License as published by
+ * the Free Software Foundation, either version 3 of the License, or
+ * (at your option) any later version.
+ *
+ * This program is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+ * GNU Affero General Public License for more details.
+ *
+ * You should have received a copy of the GNU Affero General Public License
+ * along with this program. If not, see <https://www.gnu.org/licenses/>.
+ */
+
+use crate::controller::{apply_mappings, ControllerConfig, ControllerNode};
+use crate::{NodeProcessor, NodeProperties, ParameterDefinition, ParameterType};
+use anyhow::Result;
+use constellation_core::*;
+use serde_json::Value;
+use std::collections::HashMap;
+use std::time::Instant;
+use uuid::Uuid;
+
+/// エンベロープの段階
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum EnvelopeStage {
+    Idle,
+    Attack,
+    Decay,
+    Sustain,
+    Release,
+}
+
+/// ADSR エンベロープコントローラ
+pub struct EnvelopeController {
+    id: Uuid,
+    config: NodeConfig,
+    properties: NodeProperties,
+    controller_config: ControllerConfig,
+
+    // ADSR設定
+    attack: f32,  // アタック時間（秒）
+    decay: f32,   // ディケイ時間（秒）
+    sustain: f32, // サスティンレベル (0.0-1.0)
+    release: f32, // リリース時間（秒）
+
+    // 状態
+    stage: EnvelopeStage,
+    stage_time: f32,        // 現在の段階に入ってからの経過時間
+    stage_start_level: f32, // 段階開始時のレベル
+    gate: bool,             // トリガー状態
+
+    // 時間管理
+    last_update: Instant,
+
+    // 現在の値
+    current_value: f32,
+}
+
+impl EnvelopeController {
+    pub fn new(id: Uuid, config: NodeConfig) -> Result<Self> {
+        let mut parameters = HashMap::new();
+
+        parameters.insert(
+            "attack".to_string(),
+            ParameterDefinition {
+                name: "Attack".to_string(),
+                parameter_type: ParameterType::Float,
+                default_value: Value::from(0.1),
+                min_value: Some(Value::from(0.0)),
+                max_value: Some(Value::from(60.0)),
+                description: "Attack time in seconds".to_string(),
+            },
+        );
+
+        parameters.insert(
+            "decay".to_string(),
+            ParameterDefinition {
+                name: "Decay".to_string(),
+                parameter_type: ParameterType::Float,
+                default_value: Value::from(0.2),
+                min_value: Some(Value::from(0.0)),
+                max_value: Some(Value::from(60.0)),
+                description: "Decay time in seconds".to_string(),
+            },
+        );
+
+        parameters.insert(
+            "sustain".to_string(),
+            ParameterDefinition {
+                name: "Sustain".to_string(),
+                parameter_type: ParameterType::Float,
+                default_value: Value::from(0.7),
+                min_value: Some(Value::from(0.0)),
+                max_value: Some(Value::from(1.0)),
+                description: "Sustain level".to_string(),
+            },
+        );
+
+        parameters.insert(
+            "release".to_string(),
+            ParameterDefinition {
+                name: "Release".to_string(),
+                parameter_type: ParameterType::Float,
+                default_value: Value::from(0.5),
+                min_value: Some(Value::from(0.0)),
+                max_value: Some(Value::from(60.0)),
+                description: "Release time in seconds".to_string(),
+            },
+        );
+
+        parameters.insert(
+            "trigger".to_string(),
+            ParameterDefinition {
+                name: "Trigger".to_string(),
+                parameter_type: ParameterType::Boolean,
+                default_value: Value::Bool(false),
+                min_value: None,
+                max_value: None,
+                description: "Gate: true starts attack, false starts release".to_string(),
+            },
+        );
+
+        parameters.insert(
+            "enabled".to_string(),
+            ParameterDefinition {
+                name: "Enabled".to_string(),
+                parameter_type: ParameterType::Boolean,
+                default_value: Value::Bool(true),
+                min_value: None,
+                max_value: None,
+                description: "Enable/disable envelope".to_string(),
+            },
+        );
+
+        let properties = NodeProperties {
+            id,
+            name: "Envelope Controller".to_string(),
+            node_type: NodeType::Control(ControlType::Envelope),
+            input_types: vec![ConnectionType::Control],
+            output_types: vec![ConnectionType::Control],
+            parameters,
+            enabled: true,
+        };
+
+        Ok(Self {
+            id,
+            config,
+            properties,
+            controller_config: ControllerConfig::default(),
+            attack: 0.1,
+            decay: 0.2,
+            sustain: 0.7,
+            release: 0.5,
+            stage: EnvelopeStage::Idle,
+            stage_time: 0.0,
+            stage_start_level: 0.0,
+            gate: false,
+            last_update: Instant::now(),
+            current_value: 0.0,
+        })
+    }
+
+    pub fn stage(&self) -> EnvelopeStage {
+        self.stage
+    }
+
+    /// ノートオン: 現在のレベルからアタックを開始（リリース中の再トリガーも含む）
+    pub fn note_on(&mut self) {
+        self.gate = true;
+        self.enter_stage(EnvelopeStage::Attack);
+    }
+
+    /// ノートオフ: 現在のレベルからリリースを開始（アタック中でも可）
+    pub fn note_off(&mut self) {
+        self.gate = false;
+        if self.stage != EnvelopeStage::Idle {
+            self.enter_stage(EnvelopeStage::Release);
+        }
+    }
+
+    fn enter_stage(&mut self, stage: EnvelopeStage) {
+        self.stage = stage;
+        self.stage_time = 0.0;
+        self.stage_start_level = self.current_value;
+    }
+
+    /// 経過時間だけエンベロープを進める
+    pub fn advance(&mut self, delta_time: f32) {
+        let mut remaining = delta_time.max(0.0);
+
+        // 1回の更新で複数段階をまたぐ場合に備えてループ
+        loop {
+            let duration = match self.stage {
+                EnvelopeStage::Idle => {
+                    self.current_value = 0.0;
+                    return;
+                }
+                EnvelopeStage::Sustain => {
+                    self.current_value = self.sustain;
+                    return;
+                }
+                EnvelopeStage::Attack => self.attack,
+                EnvelopeStage::Decay => self.decay,
+                EnvelopeStage::Release => self.release,
+            };
+
+            let stage_left = (duration - self.stage_time).max(0.0);
+            if remaining < stage_left {
+                self.stage_time += remaining;
+                self.current_value = self.level_in_stage(self.stage_time / duration);
+                return;
+            }
+
+            remaining -= stage_left;
+            let next_stage = match self.stage {
+                EnvelopeStage::Attack => {
+                    self.current_value = 1.0;
+                    EnvelopeStage::Decay
+                }
+                EnvelopeStage::Decay => {
+                    self.current_value = self.sustain;
+                    EnvelopeStage::Sustain
+                }
+                _ => {
+                    self.current_value = 0.0;
+                    EnvelopeStage::Idle
+                }
+            };
+            self.enter_stage(next_stage);
+        }
+    }
+
+    /// 段階内の進行度 (0.0-1.0) に対応するレベル
+    fn level_in_stage(&self, progress: f32) -> f32 {
+        let progress = progress.clamp(0.0, 1.0);
+        match self.stage {
+            EnvelopeStage::Attack => {
+                self.stage_start_level + (1.0 - self.stage_start_level) * progress
+            }
+            EnvelopeStage::Decay => 1.0 - (1.0 - self.sustain) * progress,
+            EnvelopeStage::Release => self.stage_start_level * (1.0 - progress),
+            EnvelopeStage::Sustain => self.sustain,
+            EnvelopeStage::Idle => 0.0,
+        }
+    }
+
+    /// パラメータを更新
+    fn update_parameters(&mut self) {
+        self.attack = self
+            .get_parameter("attack")
+            .and_then(|v| v.as_f64())
+            .unwrap_or(0.1)
+            .max(0.0) as f32;
+
+        self.decay = self
+            .get_parameter("decay")
+            .and_then(|v| v.as_f64())
+            .unwrap_or(0.2)
+            .max(0.0) as f32;
+
+        self.sustain = self
+            .get_parameter("sustain")
+            .and_then(|v| v.as_f64())
+            .unwrap_or(0.7)
+            .clamp(0.0, 1.0) as f32;
+
+        self.release = self
+            .get_parameter("release")
+            .and_then(|v| v.as_f64())
+            .unwrap_or(0.5)
+            .max(0.0) as f32;
+
+        // ゲートのエッジでノートオン/オフ
+        let trigger = self
+            .get_parameter("trigger")
+            .and_then(|v| v.as_bool())
+            .unwrap_or(false);
+        if trigger && !self.gate {
+            self.note_on();
+        } else if !trigger && self.gate {
+            self.note_off();
+        }
+
+        // コントローラ有効状態を更新
+        self.controller_config.enabled = self
+            .get_parameter("enabled")
+            .and_then(|v| v.as_bool())
+            .unwrap_or(true);
+    }
+}
+
+impl NodeProcessor for EnvelopeController {
+    fn process(&mut self, input: FrameData) -> Result<FrameData> {
+        // パラメータを更新
+        self.update_parameters();
+
+        // 経過時間を計算
+        let now = Instant::now();
+        let delta_time = now.duration_since(self.last_update).as_secs_f32();
+        self.last_update = now;
+
+        // 無効なら入力をそのまま通す
+        if !self.controller_config.enabled {
+            return Ok(input);
+        }
+
+        // エンベロープを進める
+        self.advance(delta_time);
+
+        // 制御コマンドを生成
+        let control_commands = self.generate_control_commands();
+
+        let control_data = if !control_commands.is_empty() {
+            Some(ControlData::MultiControl {
+                commands: control_commands,
+            })
+        } else {
+            input.control_data
+        };
+
+        Ok(FrameData {
+            control_data,
+            ..input
+        })
+    }
+
+    fn get_properties(&self) -> NodeProperties {
+        self.properties.clone()
+    }
+
+    fn set_parameter(&mut self, key: &str, value: Value) -> Result<()> {
+        self.config.parameters.insert(key.to_string(), value);
+        Ok(())
+    }
+
+    fn get_parameter(&self, key: &str) -> Option<Value> {
+        self.config.parameters.get(key).cloned()
+    }
+}
+
+impl ControllerNode for EnvelopeController {
+    fn add_mapping(&mut self, mapping: ControlMapping) {
+        self.controller_config.mappings.push(mapping);
+    }
+
+    fn remove_mapping(&mut self, source_parameter: &str) {
+        self.controller_config
+            .mappings
+            .retain(|m| m.source_parameter != source_parameter);
+    }
+
+    fn get_control_value(&self, parameter: &str) -> Option<f32> {
+        if parameter == "output" || parameter == "envelope" {
+            Some(self.current_value)
+        } else {
+            None
+        }
+    }
+
+    fn generate_control_commands(&self) -> Vec<ControlCommand> {
+        let mut control_values = HashMap::new();
+        control_values.insert("output".to_string(), self.current_value);
+        control_values.insert("envelope".to_string(), self.current_value);
+
+        apply_mappings(&self.controller_config.mappings, &control_values)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn create_envelope() -> EnvelopeController {
+        let config = NodeConfig {
+            parameters: HashMap::new(),
+        };
+        let mut envelope = EnvelopeController::new(Uuid::new_v4(), config).unwrap();
+        envelope.attack = 1.0;
+        envelope.decay = 1.0;
+        envelope.sustain = 0.5;
+        envelope.release = 2.0;
+        envelope
+    }
+
+    fn assert_level(envelope: &EnvelopeController, expected: f32) {
+        let value = envelope.get_control_value("output").unwrap();
+        assert!(
+            (value - expected).abs() < 1e-4,
+            "expected {expected}, got {value}"
+        );
+    }
+
+    #[test]
+    fn test_envelope_adsr_piecewise() {
+        let mut envelope = create_envelope();
+        assert_level(&envelope, 0.0);
+
+        envelope.note_on();
+        envelope.advance(0.5);
+        assert_eq!(envelope.stage(), EnvelopeStage::Attack);
+        assert_level(&envelope, 0.5);
+
+        envelope.advance(0.5);
+        assert_eq!(envelope.stage(), EnvelopeStage::Decay);
+        assert_level(&envelope, 1.0);
+
+        envelope.advance(0.5);
+        assert_level(&envelope, 0.75);
+
+        envelope.advance(1.0);
+        assert_eq!(envelope.stage(), EnvelopeStage::Sustain);
+        assert_level(&envelope, 0.5);
+
+        envelope.note_off();
+        envelope.advance(1.0);
+        assert_eq!(envelope.stage(), EnvelopeStage::Release);
+        assert_level(&envelope, 0.25);
+
+        envelope.advance(1.5);
+        assert_eq!(envelope.stage(), EnvelopeStage::Idle);
+        assert_level(&envelope, 0.0);
+    }
+
+    #[test]
+    fn test_envelope_note_off_during_attack() {
+        let mut envelope = create_envelope();
+
+        envelope.note_on();
+        envelope.advance(0.4);
+        assert_level(&envelope, 0.4);
+
+        // アタック途中のレベルからリリース
+        envelope.note_off();
+        envelope.advance(1.0);
+        assert_eq!(envelope.stage(), EnvelopeStage::Release);
+        assert_level(&envelope, 0.2);
+    }
+
+    #[test]
+    fn test_envelope_retrigger_during_release() {
+        let mut envelope = create_envelope();
+
+        envelope.note_on();
+        envelope.advance(3.0);
+        envelope.note_off();
+        envelope.advance(1.0);
+        assert_level(&envelope, 0.25);
+
+        // リリース途中のレベルから再アタック
+        envelope.note_on();
+        envelope.advance(0.5);
+        assert_eq!(envelope.stage(), EnvelopeStage::Attack);
+        assert_level(&envelope, 0.625);
+    }
+
+    #[test]
+    fn test_envelope_trigger_parameter_generates_commands() {
+        let mut envelope = create_envelope();
+        let target = Uuid::new_v4();
+        envelope.add_mapping(ControlMapping::new(
+            "envelope".to_string(),
+            target,
+            "brightness".to_string(),
+        ));
+
+        envelope
+            .set_parameter("trigger", Value::Bool(true))
+            .unwrap();
+        envelope.update_parameters();
+        assert_eq!(envelope.stage(), EnvelopeStage::Attack);
+
+        envelope.advance(envelope.attack);
+        let commands = envelope.generate_control_commands();
+        assert_eq!(commands.len(), 1);
+        assert_eq!(commands[0].target_node_id, target);
+    }
+}
@@ -0,0 +1,416 @@
+/*
+ * Constellation Studio - Professional Real-time Video Processing
+ * Copyright (c) 2025 MACHIKO LAB
+ *
+ * This program is free software: you can redistribute it and/or modify
+ * it under the terms of the GNU Affero General Public License as published by
+ * the Free Software Foundation, either version 3 of the License, or
+ * (at your option) any later version.
+ *
+ * This program is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+ * GNU Affero General Public License for more details.
+ *
+ * You should have received a copy of the GNU Affero General Public License
+ * along with this program. If not, see <https://www.gnu.org/licenses/>.
+ */
+
+//! 数式コントローラ用の算術式パーサーと評価器
+
+use anyhow::{anyhow, bail, Result};
+
+/// 解析済みの算術式
+#[derive(Debug, Clone, PartialEq)]
+pub enum Expression {
+    Number(f32),
+    Variable(String),
+    Negate(Box<Expression>),
+    Binary {
+        op: BinaryOp,
+        lhs: Box<Expression>,
+        rhs: Box<Expression>,
+    },
+    Call {
+        function: Function,
+        args: Vec<Expression>,
+    },
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum BinaryOp {
+    Add,
+    Subtract,
+    Multiply,
+    Divide,
+}
+
+/// 組み込み関数
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Function {
+    Sin,
+    Cos,
+    Abs,
+    Sqrt,
+    Pow,
+    Min,
+    Max,
+    Clamp,
+}
+
+impl Function {
+    fn from_name(name: &str) -> Option<Self> {
+        Some(match name {
+            "sin" => Function::Sin,
+            "cos" => Function::Cos,
+            "abs" => Function::Abs,
+            "sqrt" => Function::Sqrt,
+            "pow" => Function::Pow,
+            "min" => Function::Min,
+            "max" => Function::Max,
+            "clamp" => Function::Clamp,
+            _ => return None,
+        })
+    }
+
+    fn arity(self) -> usize {
+        match self {
+            Function::Sin | Function::Cos | Function::Abs | Function::Sqrt => 1,
+            Function::Pow | Function::Min | Function::Max => 2,
+            Function::Clamp => 3,
+        }
+    }
+
+    fn apply(self, args: &[f32]) -> f32 {
+        match self {
+            Function::Sin => args[0].sin(),
+            Function::Cos => args[0].cos(),
+            Function::Abs => args[0].abs(),
+            Function::Sqrt => args[0].sqrt(),
+            Function::Pow => args[0].powf(args[1]),
+            Function::Min => args[0].min(args[1]),
+            Function::Max => args[0].max(args[1]),
+            // 上下限が逆でもパニックしないようにmax/minで丸める
+            Function::Clamp => args[0].max(args[1]).min(args[2]),
+        }
+    }
+}
+
+impl Expression {
+    /// 数式文字列を解析する
+    pub fn parse(source: &str) -> Result<Self> {
+        let tokens = tokenize(source)?;
+        let mut parser = Parser {
+            tokens: &tokens,
+            position: 0,
+        };
+        let expression = parser.parse_sum()?;
+        match parser.peek() {
+            None => Ok(expression),
+            Some(token) => bail!("Unexpected {:?} after end of expression", token),
+        }
+    }
+
+    /// 変数を`lookup`で解決して評価する。未定義の変数はエラー
+    pub fn evaluate(&self, lookup: &dyn Fn(&str) -> Option<f32>) -> Result<f32> {
+        Ok(match self {
+            Expression::Number(value) => *value,
+            Expression::Variable(name) => {
+                lookup(name).ok_or_else(|| anyhow!("Unknown variable '{}'", name))?
+            }
+            Expression::Negate(inner) => -inner.evaluate(lookup)?,
+            Expression::Binary { op, lhs, rhs } => {
+                let (lhs, rhs) = (lhs.evaluate(lookup)?, rhs.evaluate(lookup)?);
+                match op {
+                    BinaryOp::Add => lhs + rhs,
+                    BinaryOp::Subtract => lhs - rhs,
+                    BinaryOp::Multiply => lhs * rhs,
+                    BinaryOp::Divide => lhs / rhs,
+                }
+            }
+            Expression::Call { function, args } => {
+                let values = args
+                    .iter()
+                    .map(|arg| arg.evaluate(lookup))
+                    .collect::<Result<Vec<_>>>()?;
+                function.apply(&values)
+            }
+        })
+    }
+
+    /// 式中で参照している変数名
+    pub fn variables(&self) -> Vec<&str> {
+        let mut names = Vec::new();
+        self.collect_variables(&mut names);
+        names
+    }
+
+    fn collect_variables<'a>(&'a self, names: &mut Vec<&'a str>) {
+        match self {
+            Expression::Number(_) => {}
+            Expression::Variable(name) => {
+                if !names.contains(&name.as_str()) {
+                    names.push(name);
+                }
+            }
+            Expression::Negate(inner) => inner.collect_variables(names),
+            Expression::Binary { lhs, rhs, .. } => {
+                lhs.collect_variables(names);
+                rhs.collect_variables(names);
+            }
+            Expression::Call { args, .. } => {
+                for arg in args {
+                    arg.collect_variables(names);
+                }
+            }
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+enum Token {
+    Number(f32),
+    Identifier(String),
+    Plus,
+    Minus,
+    Star,
+    Slash,
+    LeftParen,
+    RightParen,
+    Comma,
+}
+
+fn tokenize(source: &str) -> Result<Vec<Token>> {
+    let mut tokens = Vec::new();
+    let mut chars = source.char_indices().peekable();
+
+    while let Some(&(start, c)) = chars.peek() {
+        match c {
+            c if c.is_whitespace() => {
+                chars.next();
+            }
+            '0'..='9' | '.' => {
+                let mut end = start;
+                while let Some(&(i, c)) = chars.peek() {
+                    if !(c.is_ascii_digit() || c == '.') {
+                        break;
+                    }
+                    end = i + c.len_utf8();
+                    chars.next();
+                }
+                let literal = &source[start..end];
+                let value = literal
+                    .parse::<f32>()
+                    .map_err(|_| anyhow!("Invalid number '{}' at position {}", literal, start))?;
+                tokens.push(Token::Number(value));
+            }
+            c if c.is_alphabetic() || c == '_' => {
+                let mut end = start;
+                while let Some(&(i, c)) = chars.peek() {
+                    if !(c.is_alphanumeric() || c == '_') {
+                        break;
+                    }
+                    end = i + c.len_utf8();
+                    chars.next();
+                }
+                tokens.push(Token::Identifier(source[start..end].to_string()));
+            }
+            _ => {
+                tokens.push(match c {
+                    '+' => Token::Plus,
+                    '-' => Token::Minus,
+                    '*' => Token::Star,
+                    '/' => Token::Slash,
+                    '(' => Token::LeftParen,
+                    ')' => Token::RightParen,
+                    ',' => Token::Comma,
+                    _ => bail!("Unexpected character '{}' at position {}", c, start),
+                });
+                chars.next();
+            }
+        }
+    }
+
+    Ok(tokens)
+}
+
+/// 再帰下降パーサー（優先順位: 加減 < 乗除 < 単項マイナス < 関数呼び出し・括弧）
+struct Parser<'a> {
+    tokens: &'a [Token],
+    position: usize,
+}
+
+impl Parser<'_> {
+    fn peek(&self) -> Option<&Token> {
+        self.tokens.get(self.position)
+    }
+
+    fn next(&mut self) -> Option<&Token> {
+        let token = self.tokens.get(self.position);
+        self.position += 1;
+        token
+    }
+
+    fn expect(&mut self, expected: Token) -> Result<()> {
+        match self.next() {
+            Some(token) if *token == expected => Ok(()),
+            Some(token) => bail!("Expected {:?}, found {:?}", expected, token),
+            None => bail!("Expected {:?}, found end of expression", expected),
+        }
+    }
+
+    fn parse_sum(&mut self) -> Result<Expression> {
+        let mut lhs = self.parse_product()?;
+        loop {
+            let op = match self.peek() {
+                Some(Token::Plus) => BinaryOp::Add,
+                Some(Token::Minus) => BinaryOp::Subtract,
+                _ => return Ok(lhs),
+            };
+            self.position += 1;
+            let rhs = self.parse_product()?;
+            lhs = Expression::Binary {
+                op,
+                lhs: Box::new(lhs),
+                rhs: Box::new(rhs),
+            };
+        }
+    }
+
+    fn parse_product(&mut self) -> Result<Expression> {
+        let mut lhs = self.parse_unary()?;
+        loop {
+            let op = match self.peek() {
+                Some(Token::Star) => BinaryOp::Multiply,
+                Some(Token::Slash) => BinaryOp::Divide,
+                _ => return Ok(lhs),
+            };
+            self.position += 1;
+            let rhs = self.parse_unary()?;
+            lhs = Expression::Binary {
+                op,
+                lhs: Box::new(lhs),
+                rhs: Box::new(rhs),
+            };
+        }
+    }
+
+    fn parse_unary(&mut self) -> Result<Expression> {
+        match self.peek() {
+            Some(Token::Minus) => {
+                self.position += 1;
+                Ok(Expression::Negate(Box::new(self.parse_unary()?)))
+            }
+            Some(Token::Plus) => {
+                self.position += 1;
+                self.parse_unary()
+            }
+            _ => self.parse_primary(),
+        }
+    }
+
+    fn parse_primary(&mut self) -> Result<Expression> {
+        match self.next().cloned() {
+            Some(Token::Number(value)) => Ok(Expression::Number(value)),
+            Some(Token::LeftParen) => {
+                let inner = self.parse_sum()?;
+                self.expect(Token::RightParen)?;
+                Ok(inner)
+            }
+            Some(Token::Identifier(name)) => {
+                if self.peek() != Some(&Token::LeftParen) {
+                    return Ok(Expression::Variable(name));
+                }
+
+                let function = Function::from_name(&name)
+                    .ok_or_else(|| anyhow!("Unknown function '{}'", name))?;
+                self.position += 1;
+
+                let mut args = Vec::new();
+                if self.peek() != Some(&Token::RightParen) {
+                    loop {
+                        args.push(self.parse_sum()?);
+                        if self.peek() != Some(&Token::Comma) {
+                            break;
+                        }
+                        self.position += 1;
+                    }
+                }
+                self.expect(Token::RightParen)?;
+
+                if args.len() != function.arity() {
+                    bail!(
+                        "Function '{}' takes {} argument(s), got {}",
+                        name,
+                        function.arity(),
+                        args.len()
+                    );
+                }
+                Ok(Expression::Call { function, args })
+            }
+            Some(token) => bail!("Unexpected {:?}", token),
+            None => bail!("Unexpected end of expression"),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn evaluate(source: &str, variables: &[(&str, f32)]) -> f32 {
+        Expression::parse(source)
+            .unwrap()
+            .evaluate(&|name| {
+                variables
+                    .iter()
+                    .find(|(key, _)| *key == name)
+                    .map(|(_, value)| *value)
+            })
+            .unwrap()
+    }
+
+    #[test]
+    fn test_operator_precedence() {
+        assert_eq!(evaluate("1 + 2 * 3", &[]), 7.0);
+        assert_eq!(evaluate("(1 + 2) * 3", &[]), 9.0);
+        assert_eq!(evaluate("8 / 4 / 2", &[]), 1.0);
+        assert_eq!(evaluate("10 - 4 - 3", &[]), 3.0);
+        assert_eq!(evaluate("-2 * -3", &[]), 6.0);
+    }
+
+    #[test]
+    fn test_functions_and_variables() {
+        let clamped = |a, b| evaluate("clamp(a*2+b, 0, 1)", &[("a", a), ("b", b)]);
+        assert!((clamped(0.2, 0.3) - 0.7).abs() < 1e-6);
+        assert_eq!(clamped(2.0, 0.5), 1.0);
+        assert_eq!(clamped(-1.0, 0.5), 0.0);
+
+        assert_eq!(
+            evaluate("min(a, b) + max(a, b)", &[("a", 3.0), ("b", 5.0)]),
+            8.0
+        );
+        assert!((evaluate("sin(x) + cos(x)", &[("x", 0.0)]) - 1.0).abs() < 1e-6);
+        assert_eq!(evaluate("pow(2, 3) + sqrt(16) + abs(-1)", &[]), 13.0);
+    }
+
+    #[test]
+    fn test_parse_errors() {
+        assert!(Expression::parse("1 +").is_err());
+        assert!(Expression::parse("(1 + 2").is_err());
+        assert!(Expression::parse("foo(1)").is_err());
+        assert!(Expression::parse("clamp(1, 2)").is_err());
+        assert!(Expression::parse("1 $ 2").is_err());
+        assert!(Expression::parse("1 2").is_err());
+    }
+
+    #[test]
+    fn test_unknown_variable_is_an_error() {
+        let expression = Expression::parse("a + missing").unwrap();
+        assert_eq!(expression.variables(), vec!["a", "missing"]);
+        assert!(expression.evaluate(&|_| Some(1.0)).is_ok());
+        assert!(expression
+            .evaluate(&|name| (name == "a").then_some(1.0))
+            .is_err());
+    }
+}
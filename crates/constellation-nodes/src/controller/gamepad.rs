@@ -0,0 +1,613 @@
+/*
+ * Constellation Studio - Professional Real-time Video Processing
+ * Copyright (c) 2025 MACHIKO LAB
+ *
+ * This program is free software: you can redistribute it and/or modify
+ * it under the terms of the GNU Affero General Public License as published by
+ * the Free Software Foundation, either version 3 of the License, or
+ * (at your option) any later version.
+ *
+ * This program is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+ * GNU Affero General Public License for more details.
+ *
+ * You should have received a copy of the GNU Affero General Public License
+ * along with this program. If not, see <https://www.gnu.org/licenses/>.
+ */
+
+use crate::controller::{apply_mappings, ControllerConfig, ControllerNode};
+use crate::{NodeProcessor, NodeProperties, ParameterDefinition, ParameterType};
+use anyhow::Result;
+use constellation_core::*;
+use serde_json::Value;
+use std::collections::HashMap;
+use std::sync::mpsc::{self, Receiver, Sender};
+use uuid::Uuid;
+
+/// ゲームパッドのアナログ軸
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum GamepadAxis {
+    LeftStickX,
+    LeftStickY,
+    RightStickX,
+    RightStickY,
+    LeftTrigger,
+    RightTrigger,
+    DPadX,
+    DPadY,
+}
+
+impl GamepadAxis {
+    /// ControlMappingのソースパラメータ名
+    pub fn source_name(&self) -> &'static str {
+        match self {
+            Self::LeftStickX => "left_stick_x",
+            Self::LeftStickY => "left_stick_y",
+            Self::RightStickX => "right_stick_x",
+            Self::RightStickY => "right_stick_y",
+            Self::LeftTrigger => "left_trigger",
+            Self::RightTrigger => "right_trigger",
+            Self::DPadX => "dpad_x",
+            Self::DPadY => "dpad_y",
+        }
+    }
+}
+
+/// ゲームパッドのボタン（配置はXboxレイアウト基準）
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum GamepadButton {
+    A,
+    B,
+    X,
+    Y,
+    LeftBumper,
+    RightBumper,
+    Select,
+    Start,
+    Mode,
+    LeftThumb,
+    RightThumb,
+    DPadUp,
+    DPadDown,
+    DPadLeft,
+    DPadRight,
+}
+
+impl GamepadButton {
+    /// ControlMappingのソースパラメータ名
+    pub fn source_name(&self) -> &'static str {
+        match self {
+            Self::A => "button_a",
+            Self::B => "button_b",
+            Self::X => "button_x",
+            Self::Y => "button_y",
+            Self::LeftBumper => "left_bumper",
+            Self::RightBumper => "right_bumper",
+            Self::Select => "button_select",
+            Self::Start => "button_start",
+            Self::Mode => "button_mode",
+            Self::LeftThumb => "left_thumb",
+            Self::RightThumb => "right_thumb",
+            Self::DPadUp => "dpad_up",
+            Self::DPadDown => "dpad_down",
+            Self::DPadLeft => "dpad_left",
+            Self::DPadRight => "dpad_right",
+        }
+    }
+}
+
+/// ゲームパッド入力イベント
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum GamepadEvent {
+    /// 軸の値（スティックは-1.0〜1.0、トリガーは0.0〜1.0）
+    Axis {
+        gamepad: usize,
+        axis: GamepadAxis,
+        value: f32,
+    },
+    /// ボタンの値（デジタルボタンは0.0/1.0）
+    Button {
+        gamepad: usize,
+        button: GamepadButton,
+        value: f32,
+    },
+}
+
+impl GamepadEvent {
+    fn gamepad(&self) -> usize {
+        match self {
+            Self::Axis { gamepad, .. } | Self::Button { gamepad, .. } => *gamepad,
+        }
+    }
+}
+
+/// デッドゾーンを適用し、残りの範囲を0.0〜1.0に再スケール（符号は保持）
+pub fn apply_deadzone(value: f32, deadzone: f32) -> f32 {
+    let deadzone = deadzone.clamp(0.0, 0.99);
+    let magnitude = value.abs();
+    if magnitude <= deadzone {
+        0.0
+    } else {
+        value.signum() * ((magnitude - deadzone) / (1.0 - deadzone)).min(1.0)
+    }
+}
+
+/// ゲームパッドコントローラ - スティック・ボタン入力を制御値に変換
+pub struct GamepadController {
+    id: Uuid,
+    config: NodeConfig,
+    properties: NodeProperties,
+    controller_config: ControllerConfig,
+
+    // ゲームパッド設定
+    gamepad_filter: Option<usize>, // Noneなら全ゲームパッド
+    deadzone: f32,
+
+    // イベント受信
+    event_sender: Sender<GamepadEvent>,
+    event_receiver: Receiver<GamepadEvent>,
+    #[cfg(feature = "gamepad")]
+    poller: Option<GamepadPoller>,
+
+    // 現在の値
+    control_values: HashMap<String, f32>,
+}
+
+impl GamepadController {
+    pub fn new(id: Uuid, config: NodeConfig) -> Result<Self> {
+        let mut parameters = HashMap::new();
+
+        parameters.insert(
+            "gamepad".to_string(),
+            ParameterDefinition {
+                name: "Gamepad".to_string(),
+                parameter_type: ParameterType::Integer,
+                default_value: Value::from(-1),
+                min_value: Some(Value::from(-1)),
+                max_value: Some(Value::from(15)),
+                description: "Gamepad index to listen to (-1 = any)".to_string(),
+            },
+        );
+
+        parameters.insert(
+            "deadzone".to_string(),
+            ParameterDefinition {
+                name: "Deadzone".to_string(),
+                parameter_type: ParameterType::Float,
+                default_value: Value::from(0.1),
+                min_value: Some(Value::from(0.0)),
+                max_value: Some(Value::from(0.9)),
+                description: "Axis values below this magnitude are treated as zero".to_string(),
+            },
+        );
+
+        parameters.insert(
+            "enabled".to_string(),
+            ParameterDefinition {
+                name: "Enabled".to_string(),
+                parameter_type: ParameterType::Boolean,
+                default_value: Value::Bool(true),
+                min_value: None,
+                max_value: None,
+                description: "Enable/disable gamepad controller".to_string(),
+            },
+        );
+
+        let properties = NodeProperties {
+            id,
+            name: "Gamepad Controller".to_string(),
+            node_type: NodeType::Control(ControlType::GamepadController),
+            input_types: vec![], // ゲームパッドは外部入力のみ
+            output_types: vec![ConnectionType::Control],
+            parameters,
+            enabled: true,
+        };
+
+        let (event_sender, event_receiver) = mpsc::channel();
+
+        Ok(Self {
+            id,
+            config,
+            properties,
+            controller_config: ControllerConfig::default(),
+            gamepad_filter: None,
+            deadzone: 0.1,
+            event_sender,
+            event_receiver,
+            #[cfg(feature = "gamepad")]
+            poller: None,
+            control_values: HashMap::new(),
+        })
+    }
+
+    /// 外部からゲームパッドイベントを注入するための送信側を取得
+    pub fn event_sender(&self) -> Sender<GamepadEvent> {
+        self.event_sender.clone()
+    }
+
+    /// 受信済みイベントを反映
+    fn drain_events(&mut self) {
+        while let Ok(event) = self.event_receiver.try_recv() {
+            if let Some(gamepad) = self.gamepad_filter {
+                if event.gamepad() != gamepad {
+                    continue;
+                }
+            }
+
+            let (source, value) = match event {
+                GamepadEvent::Axis { axis, value, .. } => {
+                    (axis.source_name(), apply_deadzone(value, self.deadzone))
+                }
+                GamepadEvent::Button { button, value, .. } => {
+                    (button.source_name(), value.clamp(0.0, 1.0))
+                }
+            };
+            self.control_values.insert(source.to_string(), value);
+        }
+    }
+
+    /// パラメータを更新
+    fn update_parameters(&mut self) {
+        self.gamepad_filter = self
+            .get_parameter("gamepad")
+            .and_then(|v| v.as_i64())
+            .filter(|&index| index >= 0)
+            .map(|index| index as usize);
+
+        self.deadzone = self
+            .get_parameter("deadzone")
+            .and_then(|v| v.as_f64())
+            .unwrap_or(0.1) as f32;
+
+        self.controller_config.enabled = self
+            .get_parameter("enabled")
+            .and_then(|v| v.as_bool())
+            .unwrap_or(true);
+
+        #[cfg(feature = "gamepad")]
+        if self.controller_config.enabled && self.poller.is_none() {
+            match GamepadPoller::spawn(self.event_sender.clone()) {
+                Ok(poller) => self.poller = Some(poller),
+                Err(e) => tracing::warn!("Gamepad input unavailable: {}", e),
+            }
+        }
+    }
+}
+
+/// gilrsのイベントをバックグラウンドスレッドでポーリングする
+#[cfg(feature = "gamepad")]
+struct GamepadPoller {
+    running: std::sync::Arc<std::sync::atomic::AtomicBool>,
+    thread: Option<std::thread::JoinHandle<()>>,
+}
+
+#[cfg(feature = "gamepad")]
+impl GamepadPoller {
+    fn spawn(sender: Sender<GamepadEvent>) -> Result<Self> {
+        use std::sync::atomic::{AtomicBool, Ordering};
+        use std::sync::Arc;
+
+        let running = Arc::new(AtomicBool::new(true));
+        let thread_running = running.clone();
+        let (ready_sender, ready_receiver) = mpsc::channel();
+
+        // Gilrsはスレッド間で移動できないため、ポーリングスレッド内で生成する
+        let thread = std::thread::spawn(move || {
+            let mut gilrs = match gilrs::Gilrs::new() {
+                Ok(gilrs) => {
+                    let _ = ready_sender.send(Ok(()));
+                    gilrs
+                }
+                Err(e) => {
+                    let _ = ready_sender.send(Err(e.to_string()));
+                    return;
+                }
+            };
+
+            while thread_running.load(Ordering::Relaxed) {
+                while let Some(gilrs::Event { id, event, .. }) = gilrs.next_event() {
+                    if let Some(event) = Self::convert_event(usize::from(id), event) {
+                        if sender.send(event).is_err() {
+                            return;
+                        }
+                    }
+                }
+                std::thread::sleep(std::time::Duration::from_millis(4));
+            }
+        });
+
+        ready_receiver
+            .recv()
+            .map_err(|_| anyhow::anyhow!("Gamepad polling thread exited unexpectedly"))?
+            .map_err(|e| anyhow::anyhow!("Failed to initialize gilrs: {}", e))?;
+
+        tracing::info!("Gamepad polling started");
+        Ok(Self {
+            running,
+            thread: Some(thread),
+        })
+    }
+
+    fn convert_event(gamepad: usize, event: gilrs::EventType) -> Option<GamepadEvent> {
+        use gilrs::EventType;
+
+        match event {
+            EventType::AxisChanged(axis, value, _) => Some(GamepadEvent::Axis {
+                gamepad,
+                axis: Self::convert_axis(axis)?,
+                value,
+            }),
+            EventType::ButtonChanged(button, value, _) => {
+                // アナログトリガーはボタンとして報告されるため軸として扱う
+                match button {
+                    gilrs::Button::LeftTrigger2 => Some(GamepadEvent::Axis {
+                        gamepad,
+                        axis: GamepadAxis::LeftTrigger,
+                        value,
+                    }),
+                    gilrs::Button::RightTrigger2 => Some(GamepadEvent::Axis {
+                        gamepad,
+                        axis: GamepadAxis::RightTrigger,
+                        value,
+                    }),
+                    other => Some(GamepadEvent::Button {
+                        gamepad,
+                        button: Self::convert_button(other)?,
+                        value,
+                    }),
+                }
+            }
+            _ => None,
+        }
+    }
+
+    fn convert_axis(axis: gilrs::Axis) -> Option<GamepadAxis> {
+        use gilrs::Axis;
+
+        match axis {
+            Axis::LeftStickX => Some(GamepadAxis::LeftStickX),
+            Axis::LeftStickY => Some(GamepadAxis::LeftStickY),
+            Axis::RightStickX => Some(GamepadAxis::RightStickX),
+            Axis::RightStickY => Some(GamepadAxis::RightStickY),
+            Axis::LeftZ => Some(GamepadAxis::LeftTrigger),
+            Axis::RightZ => Some(GamepadAxis::RightTrigger),
+            Axis::DPadX => Some(GamepadAxis::DPadX),
+            Axis::DPadY => Some(GamepadAxis::DPadY),
+            _ => None,
+        }
+    }
+
+    fn convert_button(button: gilrs::Button) -> Option<GamepadButton> {
+        use gilrs::Button;
+
+        match button {
+            Button::South => Some(GamepadButton::A),
+            Button::East => Some(GamepadButton::B),
+            Button::West => Some(GamepadButton::X),
+            Button::North => Some(GamepadButton::Y),
+            Button::LeftTrigger => Some(GamepadButton::LeftBumper),
+            Button::RightTrigger => Some(GamepadButton::RightBumper),
+            Button::Select => Some(GamepadButton::Select),
+            Button::Start => Some(GamepadButton::Start),
+            Button::Mode => Some(GamepadButton::Mode),
+            Button::LeftThumb => Some(GamepadButton::LeftThumb),
+            Button::RightThumb => Some(GamepadButton::RightThumb),
+            Button::DPadUp => Some(GamepadButton::DPadUp),
+            Button::DPadDown => Some(GamepadButton::DPadDown),
+            Button::DPadLeft => Some(GamepadButton::DPadLeft),
+            Button::DPadRight => Some(GamepadButton::DPadRight),
+            _ => None,
+        }
+    }
+}
+
+#[cfg(feature = "gamepad")]
+impl Drop for GamepadPoller {
+    fn drop(&mut self) {
+        self.running
+            .store(false, std::sync::atomic::Ordering::Relaxed);
+        if let Some(thread) = self.thread.take() {
+            let _ = thread.join();
+        }
+    }
+}
+
+impl NodeProcessor for GamepadController {
+    fn process(&mut self, input: FrameData) -> Result<FrameData> {
+        // パラメータを更新
+        self.update_parameters();
+
+        // 無効なら入力をそのまま通す
+        if !self.controller_config.enabled {
+            return Ok(input);
+        }
+
+        // 受信済みイベントを反映
+        self.drain_events();
+
+        // 制御コマンドを生成
+        let control_commands = self.generate_control_commands();
+
+        let control_data = if !control_commands.is_empty() {
+            Some(ControlData::MultiControl {
+                commands: control_commands,
+            })
+        } else {
+            input.control_data
+        };
+
+        Ok(FrameData {
+            control_data,
+            ..input
+        })
+    }
+
+    fn get_properties(&self) -> NodeProperties {
+        self.properties.clone()
+    }
+
+    fn set_parameter(&mut self, key: &str, value: Value) -> Result<()> {
+        self.config.parameters.insert(key.to_string(), value);
+        Ok(())
+    }
+
+    fn get_parameter(&self, key: &str) -> Option<Value> {
+        self.config.parameters.get(key).cloned()
+    }
+}
+
+impl ControllerNode for GamepadController {
+    fn add_mapping(&mut self, mapping: ControlMapping) {
+        self.controller_config.mappings.push(mapping);
+    }
+
+    fn remove_mapping(&mut self, source_parameter: &str) {
+        self.controller_config
+            .mappings
+            .retain(|m| m.source_parameter != source_parameter);
+    }
+
+    fn get_control_value(&self, parameter: &str) -> Option<f32> {
+        self.control_values.get(parameter).copied()
+    }
+
+    fn generate_control_commands(&self) -> Vec<ControlCommand> {
+        apply_mappings(&self.controller_config.mappings, &self.control_values)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn create_controller(parameters: HashMap<String, Value>) -> GamepadController {
+        GamepadController::new(Uuid::new_v4(), NodeConfig { parameters }).unwrap()
+    }
+
+    fn command_value(output: &FrameData, parameter: &str) -> f32 {
+        match &output.control_data {
+            Some(ControlData::MultiControl { commands }) => commands
+                .iter()
+                .find(|command| command.parameter_name == parameter)
+                .map(|command| match command.value {
+                    ParameterValue::Float(value) => value,
+                    ref other => panic!("expected float, got {:?}", other),
+                })
+                .unwrap(),
+            other => panic!("expected MultiControl, got {:?}", other),
+        }
+    }
+
+    #[test]
+    fn test_deadzone() {
+        assert_eq!(apply_deadzone(0.05, 0.1), 0.0);
+        assert_eq!(apply_deadzone(-0.1, 0.1), 0.0);
+        assert!((apply_deadzone(0.55, 0.1) - 0.5).abs() < 1e-6);
+        assert!((apply_deadzone(-1.0, 0.1) + 1.0).abs() < 1e-6);
+        assert_eq!(apply_deadzone(0.3, 0.0), 0.3);
+    }
+
+    #[test]
+    fn test_axis_events_map_through_deadzone_and_curve() {
+        let mut parameters = HashMap::new();
+        parameters.insert("deadzone".to_string(), Value::from(0.2));
+        let mut controller = create_controller(parameters);
+
+        let target = Uuid::new_v4();
+        let mut pan = ControlMapping::new("left_stick_x".to_string(), target, "pan".to_string());
+        pan.value_range = (-1.0, 1.0);
+        pan.target_range = (-90.0, 90.0);
+        controller.add_mapping(pan);
+
+        let mut zoom = ControlMapping::new("right_trigger".to_string(), target, "zoom".to_string());
+        zoom.response_curve = ResponseCurve::Exponential(2.0);
+        controller.add_mapping(zoom);
+
+        let sender = controller.event_sender();
+        sender
+            .send(GamepadEvent::Axis {
+                gamepad: 0,
+                axis: GamepadAxis::LeftStickX,
+                value: -0.6,
+            })
+            .unwrap();
+        sender
+            .send(GamepadEvent::Axis {
+                gamepad: 0,
+                axis: GamepadAxis::RightTrigger,
+                value: 0.6,
+            })
+            .unwrap();
+
+        let output = controller.process(FrameData::empty()).unwrap();
+
+        // (0.6 - 0.2) / 0.8 = 0.5
+        assert!((controller.get_control_value("left_stick_x").unwrap() + 0.5).abs() < 1e-6);
+        assert!((command_value(&output, "pan") + 45.0).abs() < 1e-4);
+        assert!((command_value(&output, "zoom") - 0.25).abs() < 1e-6);
+
+        // Small stick movements inside the deadzone snap back to center
+        sender
+            .send(GamepadEvent::Axis {
+                gamepad: 0,
+                axis: GamepadAxis::LeftStickX,
+                value: 0.15,
+            })
+            .unwrap();
+        let output = controller.process(FrameData::empty()).unwrap();
+        assert!(command_value(&output, "pan").abs() < 1e-4);
+    }
+
+    #[test]
+    fn test_button_events_and_gamepad_filter() {
+        let mut parameters = HashMap::new();
+        parameters.insert("gamepad".to_string(), Value::from(1));
+        let mut controller = create_controller(parameters);
+
+        let sender = controller.event_sender();
+        sender
+            .send(GamepadEvent::Button {
+                gamepad: 1,
+                button: GamepadButton::A,
+                value: 1.0,
+            })
+            .unwrap();
+        sender
+            .send(GamepadEvent::Button {
+                gamepad: 0,
+                button: GamepadButton::B,
+                value: 1.0,
+            })
+            .unwrap();
+
+        controller.process(FrameData::empty()).unwrap();
+
+        assert_eq!(controller.get_control_value("button_a"), Some(1.0));
+        assert_eq!(controller.get_control_value("button_b"), None);
+    }
+
+    #[test]
+    fn test_disabled_controller_passes_input_through() {
+        let mut parameters = HashMap::new();
+        parameters.insert("enabled".to_string(), Value::Bool(false));
+        let mut controller = create_controller(parameters);
+        controller.add_mapping(ControlMapping::new(
+            "button_a".to_string(),
+            Uuid::new_v4(),
+            "opacity".to_string(),
+        ));
+
+        controller
+            .event_sender()
+            .send(GamepadEvent::Button {
+                gamepad: 0,
+                button: GamepadButton::A,
+                value: 1.0,
+            })
+            .unwrap();
+
+        let output = controller.process(FrameData::empty()).unwrap();
+        assert!(output.control_data.is_none());
+    }
+}
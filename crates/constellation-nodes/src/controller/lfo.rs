@@ -32,10 +32,26 @@ pub enum Waveform {
     Square,
     Triangle,
     Sawtooth,
+    RampDown, // 下降ノコギリ波
     Noise,
     Custom(Vec<f32>), // カスタム波形テーブル
 }
 
+impl Waveform {
+    /// パラメータ文字列から波形を取得（Customは除く）
+    pub fn from_name(name: &str) -> Option<Self> {
+        match name {
+            "Sine" => Some(Waveform::Sine),
+            "Square" => Some(Waveform::Square),
+            "Triangle" => Some(Waveform::Triangle),
+            "Sawtooth" => Some(Waveform::Sawtooth),
+            "RampDown" => Some(Waveform::RampDown),
+            "Noise" => Some(Waveform::Noise),
+            _ => None,
+        }
+    }
+}
+
 /// LFO (Low Frequency Oscillator) コントローラ
 pub struct LFOController {
     id: Uuid,
@@ -44,11 +60,12 @@ pub struct LFOController {
     controller_config: ControllerConfig,
 
     // LFO設定
-    frequency: f32,     // 周波数 (Hz)
+    frequency: f32,     // 周波数 (Hz、負の値で逆再生)
     amplitude: f32,     // 振幅 (0.0-1.0)
     offset: f32,        // DCオフセット
     waveform: Waveform, // 波形タイプ
     phase: f32,         // 位相オフセット (0.0-1.0)
+    duty_cycle: f32,    // 矩形波のデューティ比 (0.0-1.0)
 
     // 時間管理
     start_time: Instant,
@@ -72,9 +89,10 @@ impl LFOController {
                 name: "Frequency".to_string(),
                 parameter_type: ParameterType::Float,
                 default_value: Value::from(1.0),
-                min_value: Some(Value::from(0.001)),
+                min_value: Some(Value::from(-100.0)),
                 max_value: Some(Value::from(100.0)),
-                description: "LFO frequency in Hz".to_string(),
+                description: "LFO frequency in Hz (negative runs the waveform in reverse)"
+                    .to_string(),
             },
         );
 
@@ -111,6 +129,7 @@ impl LFOController {
                     "Square".to_string(),
                     "Triangle".to_string(),
                     "Sawtooth".to_string(),
+                    "RampDown".to_string(),
                     "Noise".to_string(),
                 ]),
                 default_value: Value::String("Sine".to_string()),
@@ -132,6 +151,18 @@ impl LFOController {
             },
         );
 
+        parameters.insert(
+            "duty_cycle".to_string(),
+            ParameterDefinition {
+                name: "Duty Cycle".to_string(),
+                parameter_type: ParameterType::Float,
+                default_value: Value::from(0.5),
+                min_value: Some(Value::from(0.0)),
+                max_value: Some(Value::from(1.0)),
+                description: "Fraction of the period the square wave stays high".to_string(),
+            },
+        );
+
         parameters.insert(
             "enabled".to_string(),
             ParameterDefinition {
@@ -151,6 +182,7 @@ impl LFOController {
             input_types: vec![], // LFOは入力なし
             output_types: vec![ConnectionType::Control],
             parameters,
+            enabled: true,
         };
 
         let now = Instant::now();
@@ -165,6 +197,7 @@ impl LFOController {
             offset: 0.0,
             waveform: Waveform::Sine,
             phase: 0.0,
+            duty_cycle: 0.5,
             start_time: now,
             last_update: now,
             current_value: 0.0,
@@ -174,35 +207,29 @@ impl LFOController {
 
     /// 現在の時間に基づいてLFO値を計算
     fn calculate_lfo_value(&mut self, elapsed_time: f32) -> f32 {
-        // フェーズ調整された時間を計算
-        let phase_adjusted_time = elapsed_time + (self.phase * (1.0 / self.frequency));
+        // 周期内の位置 (0.0-1.0)。負の周波数では逆方向に進む
+        let cycle = (elapsed_time * self.frequency + self.phase).rem_euclid(1.0);
 
         // 基本波形値を計算
         let base_value = match &self.waveform {
-            Waveform::Sine => {
-                let angle = phase_adjusted_time * self.frequency * 2.0 * std::f32::consts::PI;
-                angle.sin()
-            }
+            Waveform::Sine => (cycle * 2.0 * std::f32::consts::PI).sin(),
             Waveform::Square => {
-                let phase = (phase_adjusted_time * self.frequency) % 1.0;
-                if phase < 0.5 {
+                // デューティ比0は常にLow、1は常にHigh
+                if cycle < self.duty_cycle.clamp(0.0, 1.0) {
                     1.0
                 } else {
                     -1.0
                 }
             }
             Waveform::Triangle => {
-                let phase = (phase_adjusted_time * self.frequency) % 1.0;
-                if phase < 0.5 {
-                    4.0 * phase - 1.0
+                if cycle < 0.5 {
+                    4.0 * cycle - 1.0
                 } else {
-                    3.0 - 4.0 * phase
+                    3.0 - 4.0 * cycle
                 }
             }
-            Waveform::Sawtooth => {
-                let phase = (phase_adjusted_time * self.frequency) % 1.0;
-                2.0 * phase - 1.0
-            }
+            Waveform::Sawtooth => 2.0 * cycle - 1.0,
+            Waveform::RampDown => 1.0 - 2.0 * cycle,
             Waveform::Noise => {
                 // Simple pseudo-random noise
                 self.noise_seed = self.noise_seed.wrapping_mul(1103515245).wrapping_add(12345);
@@ -212,8 +239,7 @@ impl LFOController {
                 if samples.is_empty() {
                     0.0
                 } else {
-                    let phase = (phase_adjusted_time * self.frequency) % 1.0;
-                    let index = (phase * samples.len() as f32) as usize;
+                    let index = (cycle * samples.len() as f32) as usize;
                     samples[index.min(samples.len() - 1)]
                 }
             }
@@ -248,18 +274,17 @@ impl LFOController {
             .and_then(|v| v.as_f64())
             .unwrap_or(0.0) as f32;
 
+        self.duty_cycle = self
+            .get_parameter("duty_cycle")
+            .and_then(|v| v.as_f64())
+            .unwrap_or(0.5) as f32;
+
         // 波形タイプを更新
-        if let Some(waveform_value) = self.get_parameter("waveform") {
-            if let Some(waveform_str) = waveform_value.as_str() {
-                self.waveform = match waveform_str {
-                    "Sine" => Waveform::Sine,
-                    "Square" => Waveform::Square,
-                    "Triangle" => Waveform::Triangle,
-                    "Sawtooth" => Waveform::Sawtooth,
-                    "Noise" => Waveform::Noise,
-                    _ => Waveform::Sine,
-                };
-            }
+        if let Some(waveform_str) = self
+            .get_parameter("waveform")
+            .and_then(|v| v.as_str().map(String::from))
+        {
+            self.waveform = Waveform::from_name(&waveform_str).unwrap_or(Waveform::Sine);
         }
 
         // コントローラ有効状態を更新
@@ -301,10 +326,8 @@ impl NodeProcessor for LFOController {
         self.last_update = now;
 
         Ok(FrameData {
-            render_data: input.render_data,
-            audio_data: input.audio_data,
             control_data,
-            tally_metadata: input.tally_metadata,
+            ..input
         })
     }
 
@@ -408,6 +431,95 @@ mod tests {
         assert_eq!(value_half, -1.0); // Second half of square wave
     }
 
+    fn sample_quarters(controller: &mut LFOController) -> [f32; 4] {
+        [0.0, 0.25, 0.5, 0.75].map(|t| controller.calculate_lfo_value(t))
+    }
+
+    fn assert_samples(actual: [f32; 4], expected: [f32; 4]) {
+        for (a, e) in actual.iter().zip(expected.iter()) {
+            assert!(
+                (a - e).abs() < 1e-4,
+                "expected {:?}, got {:?}",
+                expected,
+                actual
+            );
+        }
+    }
+
+    fn controller_with_waveform(waveform: Waveform) -> LFOController {
+        let config = NodeConfig {
+            parameters: HashMap::new(),
+        };
+        let mut controller = LFOController::new(Uuid::new_v4(), config).unwrap();
+        controller.waveform = waveform;
+        controller
+    }
+
+    #[test]
+    fn test_lfo_waveforms_at_quarter_periods() {
+        let cases = [
+            (Waveform::Sine, [0.0, 1.0, 0.0, -1.0]),
+            (Waveform::Triangle, [-1.0, 0.0, 1.0, 0.0]),
+            (Waveform::Square, [1.0, 1.0, -1.0, -1.0]),
+            (Waveform::Sawtooth, [-1.0, -0.5, 0.0, 0.5]),
+            (Waveform::RampDown, [1.0, 0.5, 0.0, -0.5]),
+        ];
+
+        for (waveform, expected) in cases {
+            let mut controller = controller_with_waveform(waveform);
+            assert_samples(sample_quarters(&mut controller), expected);
+        }
+    }
+
+    #[test]
+    fn test_lfo_square_duty_cycle() {
+        let mut controller = controller_with_waveform(Waveform::Square);
+
+        controller.duty_cycle = 0.25;
+        assert_samples(sample_quarters(&mut controller), [1.0, -1.0, -1.0, -1.0]);
+
+        // デューティ比0/1はそれぞれ常にLow/High
+        controller.duty_cycle = 0.0;
+        assert_samples(sample_quarters(&mut controller), [-1.0; 4]);
+        controller.duty_cycle = 1.0;
+        assert_samples(sample_quarters(&mut controller), [1.0; 4]);
+    }
+
+    #[test]
+    fn test_lfo_phase_offset() {
+        let mut controller = controller_with_waveform(Waveform::Sawtooth);
+        controller.phase = 0.25;
+        assert_samples(sample_quarters(&mut controller), [-0.5, 0.0, 0.5, -1.0]);
+    }
+
+    #[test]
+    fn test_lfo_negative_frequency_reverses() {
+        let mut controller = controller_with_waveform(Waveform::Sawtooth);
+        controller.frequency = -1.0;
+        assert_samples(sample_quarters(&mut controller), [-1.0, 0.5, 0.0, -0.5]);
+
+        let mut controller = controller_with_waveform(Waveform::Sine);
+        controller.frequency = -1.0;
+        assert_samples(sample_quarters(&mut controller), [0.0, -1.0, 0.0, 1.0]);
+    }
+
+    #[test]
+    fn test_lfo_waveform_parameter() {
+        let mut controller = controller_with_waveform(Waveform::Sine);
+        controller
+            .set_parameter("waveform", Value::String("RampDown".to_string()))
+            .unwrap();
+        controller
+            .set_parameter("frequency", Value::from(2.0))
+            .unwrap();
+        controller.update_parameters();
+
+        assert!(matches!(controller.waveform, Waveform::RampDown));
+        assert_eq!(controller.frequency, 2.0);
+        // 2Hzでは0.125秒が1/4周期
+        assert!((controller.calculate_lfo_value(0.125) - 0.5).abs() < 1e-4);
+    }
+
     #[test]
     fn test_lfo_amplitude_scaling() {
         let id = Uuid::new_v4();
@@ -16,9 +16,10 @@
  * along with this program. If not, see <https://www.gnu.org/licenses/>.
  */
 
+use crate::controller::expression::Expression;
 use crate::controller::{apply_mappings, ControllerConfig, ControllerNode};
 use crate::{NodeProcessor, NodeProperties, ParameterDefinition, ParameterType};
-use anyhow::Result;
+use anyhow::{anyhow, Result};
 use constellation_core::*;
 use serde_json::Value;
 use std::collections::HashMap;
@@ -34,6 +35,8 @@ pub struct MathController {
 
     // 数式設定
     expression: String,
+    // 解析済みの数式と、その元になった文字列
+    compiled: Option<(String, Expression)>,
     variables: HashMap<String, f32>,
 
     // 現在の値
@@ -57,7 +60,8 @@ impl MathController {
                 default_value: Value::String("sin(t)".to_string()),
                 min_value: None,
                 max_value: None,
-                description: "Math expression (supports: t, sin, cos, abs, sqrt, pow, etc.)"
+                description: "Math expression over t, a, b, c, pi and incoming control parameters \
+                     (supports: + - * /, sin, cos, abs, sqrt, pow, min, max, clamp)"
                     .to_string(),
             },
         );
@@ -130,8 +134,19 @@ impl MathController {
             input_types: vec![ConnectionType::Control], // 他のコントローラからの入力可能
             output_types: vec![ConnectionType::Control],
             parameters,
+            enabled: true,
         };
 
+        // 設定で与えられた数式は生成時に検証する
+        let expression = match config.parameters.get("expression") {
+            Some(value) => value
+                .as_str()
+                .ok_or_else(|| anyhow!("Expression must be a string, got {}", value))?
+                .to_string(),
+            None => "sin(t)".to_string(),
+        };
+        let compiled = Self::compile(&expression)?;
+
         let now = Instant::now();
         let mut variables = HashMap::new();
         variables.insert("a".to_string(), 1.0);
@@ -143,7 +158,8 @@ impl MathController {
             config,
             properties,
             controller_config: ControllerConfig::default(),
-            expression: "sin(t)".to_string(),
+            compiled: Some((expression.clone(), compiled)),
+            expression,
             variables,
             current_value: 0.0,
             start_time: now,
@@ -151,72 +167,52 @@ impl MathController {
         })
     }
 
+    /// 数式を解析し、エラーなら内容を添えて返す
+    fn compile(expression: &str) -> Result<Expression> {
+        Expression::parse(expression)
+            .map_err(|e| anyhow!("Invalid expression '{}': {}", expression, e))
+    }
+
     /// 数式を評価
+    ///
+    /// 変数は t（経過時間）、pi、a/b/c（var_a〜var_c）と、このノードに送られた
+    /// 制御パラメータ（定義外のキー）として解決する。評価できなければ直前の値を保持する。
     fn evaluate_expression(&mut self, time: f32) -> f32 {
-        // 基本的な数式評価器（簡略版）
-        // 実際の実装では、より高度な数式パーサーを使用
-
-        // 時間とユーザー変数を設定
-        let mut context = HashMap::new();
-        context.insert("t".to_string(), time);
-        context.insert(
-            "a".to_string(),
-            self.variables.get("a").copied().unwrap_or(1.0),
-        );
-        context.insert(
-            "b".to_string(),
-            self.variables.get("b").copied().unwrap_or(0.0),
-        );
-        context.insert(
-            "c".to_string(),
-            self.variables.get("c").copied().unwrap_or(0.0),
-        );
+        if self
+            .compiled
+            .as_ref()
+            .is_none_or(|(source, _)| *source != self.expression)
+        {
+            self.compiled = match Self::compile(&self.expression) {
+                Ok(compiled) => Some((self.expression.clone(), compiled)),
+                Err(e) => {
+                    tracing::warn!("{}", e);
+                    None
+                }
+            };
+        }
+        let Some((_, expression)) = &self.compiled else {
+            return self.current_value;
+        };
 
-        // 簡単な数式の評価（基本的な関数のみサポート）
-        self.evaluate_simple_expression(&self.expression, &context)
-    }
+        let lookup = |name: &str| match name {
+            "t" => Some(time),
+            "pi" => Some(std::f32::consts::PI),
+            "a" | "b" | "c" => self.variables.get(name).copied(),
+            _ if self.properties.parameters.contains_key(name) => None,
+            _ => self
+                .config
+                .parameters
+                .get(name)
+                .and_then(|v| v.as_f64())
+                .map(|v| v as f32),
+        };
 
-    /// 簡単な数式評価（基本実装）
-    fn evaluate_simple_expression(&self, expr: &str, context: &HashMap<String, f32>) -> f32 {
-        // 非常に基本的な数式評価
-        // 実際のプロダクションでは、もっと堅牢な数式パーサーを使用
-
-        match expr {
-            "sin(t)" => (context.get("t").unwrap_or(&0.0) * std::f32::consts::PI).sin(),
-            "cos(t)" => (context.get("t").unwrap_or(&0.0) * std::f32::consts::PI).cos(),
-            "sin(t * a)" => {
-                let t = context.get("t").unwrap_or(&0.0);
-                let a = context.get("a").unwrap_or(&1.0);
-                (t * a * std::f32::consts::PI).sin()
-            }
-            "cos(t * a)" => {
-                let t = context.get("t").unwrap_or(&0.0);
-                let a = context.get("a").unwrap_or(&1.0);
-                (t * a * std::f32::consts::PI).cos()
-            }
-            "a * sin(t) + b" => {
-                let t = context.get("t").unwrap_or(&0.0);
-                let a = context.get("a").unwrap_or(&1.0);
-                let b = context.get("b").unwrap_or(&0.0);
-                a * (t * std::f32::consts::PI).sin() + b
-            }
-            "a * cos(t) + b" => {
-                let t = context.get("t").unwrap_or(&0.0);
-                let a = context.get("a").unwrap_or(&1.0);
-                let b = context.get("b").unwrap_or(&0.0);
-                a * (t * std::f32::consts::PI).cos() + b
-            }
-            "abs(sin(t))" => {
-                let t = context.get("t").unwrap_or(&0.0);
-                (t * std::f32::consts::PI).sin().abs()
-            }
-            "t" => *context.get("t").unwrap_or(&0.0),
-            "a" => *context.get("a").unwrap_or(&1.0),
-            "b" => *context.get("b").unwrap_or(&0.0),
-            "c" => *context.get("c").unwrap_or(&0.0),
-            _ => {
-                // フォールバック: 数値として解析を試行
-                expr.parse::<f32>().unwrap_or(0.0)
+        match expression.evaluate(&lookup) {
+            Ok(value) => value,
+            Err(e) => {
+                tracing::warn!("Failed to evaluate '{}': {}", self.expression, e);
+                self.current_value
             }
         }
     }
@@ -294,10 +290,8 @@ impl NodeProcessor for MathController {
         self.last_update = now;
 
         Ok(FrameData {
-            render_data: input.render_data,
-            audio_data: input.audio_data,
             control_data,
-            tally_metadata: input.tally_metadata,
+            ..input
         })
     }
 
@@ -306,6 +300,15 @@ impl NodeProcessor for MathController {
     }
 
     fn set_parameter(&mut self, key: &str, value: Value) -> Result<()> {
+        if key == "expression" {
+            let expression = value
+                .as_str()
+                .ok_or_else(|| anyhow!("Expression must be a string, got {}", value))?;
+            let compiled = Self::compile(expression)?;
+            self.compiled = Some((expression.to_string(), compiled));
+            self.expression = expression.to_string();
+        }
+
         self.config.parameters.insert(key.to_string(), value);
         Ok(())
     }
@@ -377,7 +380,7 @@ mod tests {
         let value = controller.evaluate_expression(0.0);
         assert!((value - 0.0).abs() < 0.01); // sin(0) = 0
 
-        let value = controller.evaluate_expression(0.5);
+        let value = controller.evaluate_expression(std::f32::consts::FRAC_PI_2);
         assert!((value - 1.0).abs() < 0.01); // sin(π/2) = 1
     }
 
@@ -396,7 +399,7 @@ mod tests {
         let value = controller.evaluate_expression(0.0);
         assert!((value - 1.0).abs() < 0.01); // 2 * sin(0) + 1 = 1
 
-        let value = controller.evaluate_expression(0.5);
+        let value = controller.evaluate_expression(std::f32::consts::FRAC_PI_2);
         assert!((value - 3.0).abs() < 0.01); // 2 * sin(π/2) + 1 = 3
     }
 
@@ -414,4 +417,77 @@ mod tests {
         let value = controller.evaluate_expression(0.0);
         assert!((value - 42.0).abs() < 0.01);
     }
+
+    #[test]
+    fn test_math_clamp_expression_with_variables() {
+        let config = NodeConfig {
+            parameters: HashMap::new(),
+        };
+        let mut controller = MathController::new(Uuid::new_v4(), config).unwrap();
+        controller
+            .set_parameter("expression", Value::from("clamp(a*2+b, 0, 1)"))
+            .unwrap();
+
+        for (a, b, expected) in [(0.2, 0.3, 0.7), (1.0, 0.5, 1.0), (-1.0, 0.5, 0.0)] {
+            controller.variables.insert("a".to_string(), a);
+            controller.variables.insert("b".to_string(), b);
+            let value = controller.evaluate_expression(0.0);
+            assert!((value - expected).abs() < 1e-6, "a={a}, b={b}: {value}");
+        }
+    }
+
+    #[test]
+    fn test_math_invalid_expression_is_rejected() {
+        let config = NodeConfig {
+            parameters: HashMap::new(),
+        };
+        let mut controller = MathController::new(Uuid::new_v4(), config).unwrap();
+
+        let error = controller
+            .set_parameter("expression", Value::from("clamp(a, 0"))
+            .unwrap_err();
+        assert!(error.to_string().contains("Invalid expression"));
+        // 直前の有効な数式が残る
+        assert_eq!(controller.expression, "sin(t)");
+        assert!(controller.get_parameter("expression").is_none());
+
+        let mut parameters = HashMap::new();
+        parameters.insert("expression".to_string(), Value::from("max(1, )"));
+        assert!(MathController::new(Uuid::new_v4(), NodeConfig { parameters }).is_err());
+    }
+
+    #[test]
+    fn test_math_control_inputs_feed_mappings() {
+        let config = NodeConfig {
+            parameters: HashMap::new(),
+        };
+        let mut controller = MathController::new(Uuid::new_v4(), config).unwrap();
+        controller
+            .set_parameter("expression", Value::from("max(level, floor) * 2"))
+            .unwrap();
+        // 他のコントローラから届いた制御パラメータが変数になる
+        controller.set_parameter("level", Value::from(0.3)).unwrap();
+        controller.set_parameter("floor", Value::from(0.1)).unwrap();
+
+        let target = Uuid::new_v4();
+        controller.add_mapping(ControlMapping {
+            target_range: (0.0, 2.0),
+            ..ControlMapping::new("output".to_string(), target, "brightness".to_string())
+        });
+
+        let output = controller.process(FrameData::empty()).unwrap();
+
+        assert!((controller.get_control_value("output").unwrap() - 0.6).abs() < 1e-6);
+        match output.control_data {
+            Some(ControlData::MultiControl { commands }) => {
+                assert_eq!(commands.len(), 1);
+                assert_eq!(commands[0].target_node_id, target);
+                match commands[0].value {
+                    ParameterValue::Float(value) => assert!((value - 1.2).abs() < 1e-5),
+                    ref other => panic!("Floatを期待したが {:?}", other),
+                }
+            }
+            other => panic!("MultiControlを期待したが {:?}", other),
+        }
+    }
 }
@@ -0,0 +1,554 @@
+/*
+ * Constellation Studio - Professional Real-time Video Processing
+ * Copyright (c) 2025 MACHIKO LAB
+ *
+ * This program is free software: you can redistribute it and/or modify
+ * it under the terms of the GNU Affero General Public License as published by
+ * the Free Software Foundation, either version 3 of the License, or
+ * (at your option) any later version.
+ *
+ * This program is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+ * GNU Affero General Public License for more details.
+ *
+ * You should have received a copy of the GNU Affero General Public License
+ * along with this program. If not, see <https://www.gnu.org/licenses/>.
+ */
+
+use crate::controller::{apply_mappings, ControllerConfig, ControllerNode};
+use crate::{NodeProcessor, NodeProperties, ParameterDefinition, ParameterType};
+use anyhow::{anyhow, bail, Result};
+use constellation_core::*;
+use serde_json::Value;
+use std::collections::HashMap;
+use std::sync::mpsc::{self, Receiver, Sender};
+use std::time::{Duration, Instant};
+use uuid::Uuid;
+
+/// MIDI Control Change イベント
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct MidiCcEvent {
+    pub channel: u8,    // 0-15
+    pub controller: u8, // CC番号 0-127
+    pub value: u8,      // 0-127
+}
+
+impl MidiCcEvent {
+    /// 生のMIDIメッセージからCCイベントを解析（CC以外はNone）
+    pub fn parse(message: &[u8]) -> Option<Self> {
+        match message {
+            [status, controller, value, ..] if status & 0xF0 == 0xB0 => Some(Self {
+                channel: status & 0x0F,
+                controller: controller & 0x7F,
+                value: value & 0x7F,
+            }),
+            _ => None,
+        }
+    }
+
+    /// 0.0-1.0に正規化した値
+    pub fn normalized_value(&self) -> f32 {
+        self.value as f32 / 127.0
+    }
+}
+
+/// 見つからない・接続できないMIDIポートを再試行する間隔
+const MIDI_PORT_RETRY: Duration = Duration::from_secs(2);
+
+/// MIDIコントローラ - MIDI CCを制御値に変換
+pub struct MidiController {
+    id: Uuid,
+    config: NodeConfig,
+    properties: NodeProperties,
+    controller_config: ControllerConfig,
+
+    // MIDI設定
+    channel_filter: Option<u8>,   // Noneなら全チャンネル
+    cc_map: HashMap<u8, String>,  // CC番号 -> ソースパラメータ名
+    current_port: Option<String>, // 設定されたポート名
+    port_connected: bool,
+    retry_at: Option<Instant>, // 接続失敗後の再試行時刻
+    port_failure_logged: bool, // 失敗の警告は再接続まで1回だけ出す
+
+    // イベント受信
+    event_sender: Sender<MidiCcEvent>,
+    event_receiver: Receiver<MidiCcEvent>,
+    #[cfg(feature = "midi")]
+    connection: Option<midir::MidiInputConnection<()>>,
+
+    // 現在の値
+    control_values: HashMap<String, f32>,
+}
+
+impl MidiController {
+    pub fn new(id: Uuid, config: NodeConfig) -> Result<Self> {
+        let mut parameters = HashMap::new();
+
+        parameters.insert(
+            "port".to_string(),
+            ParameterDefinition {
+                name: "MIDI Port".to_string(),
+                parameter_type: ParameterType::String,
+                default_value: Value::String(String::new()),
+                min_value: None,
+                max_value: None,
+                description: "MIDI input port name (substring match, empty = disconnected)"
+                    .to_string(),
+            },
+        );
+
+        parameters.insert(
+            "channel".to_string(),
+            ParameterDefinition {
+                name: "Channel".to_string(),
+                parameter_type: ParameterType::Integer,
+                default_value: Value::from(0),
+                min_value: Some(Value::from(0)),
+                max_value: Some(Value::from(16)),
+                description: "MIDI channel 1-16 (0 = omni)".to_string(),
+            },
+        );
+
+        parameters.insert(
+            "cc_map".to_string(),
+            ParameterDefinition {
+                name: "CC Mapping".to_string(),
+                parameter_type: ParameterType::String,
+                default_value: Value::String("{}".to_string()),
+                min_value: None,
+                max_value: None,
+                description:
+                    "JSON table of CC number to source parameter, e.g. {\"7\": \"volume\"}"
+                        .to_string(),
+            },
+        );
+
+        parameters.insert(
+            "enabled".to_string(),
+            ParameterDefinition {
+                name: "Enabled".to_string(),
+                parameter_type: ParameterType::Boolean,
+                default_value: Value::Bool(true),
+                min_value: None,
+                max_value: None,
+                description: "Enable/disable MIDI controller".to_string(),
+            },
+        );
+
+        let properties = NodeProperties {
+            id,
+            name: "MIDI Controller".to_string(),
+            node_type: NodeType::Control(ControlType::MidiController),
+            input_types: vec![], // MIDIは外部入力のみ
+            output_types: vec![ConnectionType::Control],
+            parameters,
+            enabled: true,
+        };
+
+        let (event_sender, event_receiver) = mpsc::channel();
+
+        Ok(Self {
+            id,
+            config,
+            properties,
+            controller_config: ControllerConfig::default(),
+            channel_filter: None,
+            cc_map: HashMap::new(),
+            current_port: None,
+            port_connected: false,
+            retry_at: None,
+            port_failure_logged: false,
+            event_sender,
+            event_receiver,
+            #[cfg(feature = "midi")]
+            connection: None,
+            control_values: HashMap::new(),
+        })
+    }
+
+    /// 外部からCCイベントを注入するための送信側を取得
+    pub fn event_sender(&self) -> Sender<MidiCcEvent> {
+        self.event_sender.clone()
+    }
+
+    /// 利用可能なMIDI入力ポート名一覧
+    #[cfg(feature = "midi")]
+    pub fn available_ports() -> Result<Vec<String>> {
+        let midi_in = midir::MidiInput::new("Constellation Studio")?;
+        Ok(midi_in
+            .ports()
+            .iter()
+            .filter_map(|port| midi_in.port_name(port).ok())
+            .collect())
+    }
+
+    #[cfg(not(feature = "midi"))]
+    pub fn available_ports() -> Result<Vec<String>> {
+        Ok(Vec::new())
+    }
+
+    /// CC番号に対応するソースパラメータ名（未マッピングなら"ccN"）
+    fn source_parameter_for(&self, controller: u8) -> String {
+        self.cc_map
+            .get(&controller)
+            .cloned()
+            .unwrap_or_else(|| format!("cc{controller}"))
+    }
+
+    /// 受信済みCCイベントを反映
+    fn drain_events(&mut self) {
+        while let Ok(event) = self.event_receiver.try_recv() {
+            if let Some(channel) = self.channel_filter {
+                if event.channel != channel {
+                    continue;
+                }
+            }
+
+            let source = self.source_parameter_for(event.controller);
+            self.control_values.insert(source, event.normalized_value());
+        }
+    }
+
+    /// パラメータを更新
+    fn update_parameters(&mut self) -> Result<()> {
+        self.channel_filter = match self.get_parameter("channel").and_then(|v| v.as_u64()) {
+            Some(channel @ 1..=16) => Some(channel as u8 - 1),
+            _ => None,
+        };
+
+        // 不正なJSONはset_parameterで弾かれる
+        self.cc_map = self
+            .get_parameter("cc_map")
+            .and_then(|v| parse_cc_map(&v).ok())
+            .unwrap_or_default();
+
+        self.controller_config.enabled = self
+            .get_parameter("enabled")
+            .and_then(|v| v.as_bool())
+            .unwrap_or(true);
+
+        let port = self
+            .get_parameter("port")
+            .and_then(|v| v.as_str().map(str::to_string))
+            .filter(|port| !port.is_empty());
+
+        if port != self.current_port {
+            self.current_port = port;
+            self.port_connected = false;
+            self.retry_at = None;
+            self.port_failure_logged = false;
+        }
+        self.ensure_port();
+
+        Ok(())
+    }
+
+    /// 設定されたポートへ接続する。見つからない・接続できない場合は警告を1回だけ出し、
+    /// `MIDI_PORT_RETRY` ごとに再試行する（その間、入力はそのまま通す）
+    fn ensure_port(&mut self) {
+        if self.port_connected || self.retry_at.is_some_and(|at| Instant::now() < at) {
+            return;
+        }
+
+        let port = self.current_port.clone();
+        match self.connect_port(port.as_deref()) {
+            Ok(()) => {
+                self.port_connected = true;
+                self.retry_at = None;
+                self.port_failure_logged = false;
+            }
+            Err(e) => {
+                if !self.port_failure_logged {
+                    tracing::warn!("MIDI input unavailable, retrying in background: {:#}", e);
+                    self.port_failure_logged = true;
+                }
+                self.retry_at = Some(Instant::now() + MIDI_PORT_RETRY);
+            }
+        }
+    }
+
+    #[cfg(feature = "midi")]
+    fn connect_port(&mut self, port_name: Option<&str>) -> Result<()> {
+        // 既存接続を閉じる
+        self.connection = None;
+
+        let Some(port_name) = port_name else {
+            return Ok(());
+        };
+
+        let midi_in = midir::MidiInput::new("Constellation Studio")?;
+        let port = midi_in
+            .ports()
+            .into_iter()
+            .find(|port| {
+                midi_in
+                    .port_name(port)
+                    .map(|name| name.contains(port_name))
+                    .unwrap_or(false)
+            })
+            .ok_or_else(|| anyhow::anyhow!("MIDI input port not found: {}", port_name))?;
+
+        let sender = self.event_sender.clone();
+        let connection = midi_in
+            .connect(
+                &port,
+                "constellation-midi-in",
+                move |_timestamp, message, _| {
+                    if let Some(event) = MidiCcEvent::parse(message) {
+                        let _ = sender.send(event);
+                    }
+                },
+                (),
+            )
+            .map_err(|e| anyhow::anyhow!("Failed to connect MIDI port {}: {}", port_name, e))?;
+
+        tracing::info!("Connected MIDI input port: {}", port_name);
+        self.connection = Some(connection);
+        Ok(())
+    }
+
+    #[cfg(not(feature = "midi"))]
+    fn connect_port(&mut self, port_name: Option<&str>) -> Result<()> {
+        if let Some(port_name) = port_name {
+            tracing::warn!(
+                "MIDI port {} requested but constellation-nodes was built without the `midi` feature",
+                port_name
+            );
+        }
+        Ok(())
+    }
+}
+
+/// `cc_map`パラメータ（`{"CC番号": "ソースパラメータ名"}` 形式のJSON文字列）を解析する
+fn parse_cc_map(value: &Value) -> Result<HashMap<u8, String>> {
+    let json = value
+        .as_str()
+        .ok_or_else(|| anyhow!("cc_map must be a JSON string, got {}", value))?;
+    serde_json::from_str::<HashMap<String, String>>(json)
+        .map_err(|e| anyhow!("Invalid cc_map JSON: {}", e))?
+        .into_iter()
+        .map(|(cc, name)| match cc.parse::<u8>() {
+            Ok(cc) if cc < 128 => Ok((cc, name)),
+            _ => bail!("Invalid CC number in cc_map: {}", cc),
+        })
+        .collect()
+}
+
+impl NodeProcessor for MidiController {
+    fn process(&mut self, input: FrameData) -> Result<FrameData> {
+        // パラメータを更新
+        self.update_parameters()?;
+
+        // 無効、または設定したポートにまだ接続できていなければ入力をそのまま通す
+        if !self.controller_config.enabled || (self.current_port.is_some() && !self.port_connected)
+        {
+            return Ok(input);
+        }
+
+        // 受信済みイベントを反映
+        self.drain_events();
+
+        // 制御コマンドを生成
+        let control_commands = self.generate_control_commands();
+
+        let control_data = if !control_commands.is_empty() {
+            Some(ControlData::MultiControl {
+                commands: control_commands,
+            })
+        } else {
+            input.control_data
+        };
+
+        Ok(FrameData {
+            control_data,
+            ..input
+        })
+    }
+
+    fn get_properties(&self) -> NodeProperties {
+        self.properties.clone()
+    }
+
+    fn set_parameter(&mut self, key: &str, value: Value) -> Result<()> {
+        if key == "cc_map" {
+            parse_cc_map(&value)?;
+        }
+        self.config.parameters.insert(key.to_string(), value);
+        Ok(())
+    }
+
+    fn get_parameter(&self, key: &str) -> Option<Value> {
+        self.config.parameters.get(key).cloned()
+    }
+}
+
+impl ControllerNode for MidiController {
+    fn add_mapping(&mut self, mapping: ControlMapping) {
+        self.controller_config.mappings.push(mapping);
+    }
+
+    fn remove_mapping(&mut self, source_parameter: &str) {
+        self.controller_config
+            .mappings
+            .retain(|m| m.source_parameter != source_parameter);
+    }
+
+    fn get_control_value(&self, parameter: &str) -> Option<f32> {
+        self.control_values.get(parameter).copied()
+    }
+
+    fn generate_control_commands(&self) -> Vec<ControlCommand> {
+        apply_mappings(&self.controller_config.mappings, &self.control_values)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn create_controller(parameters: HashMap<String, Value>) -> MidiController {
+        MidiController::new(Uuid::new_v4(), NodeConfig { parameters }).unwrap()
+    }
+
+    #[test]
+    fn test_parse_cc_message() {
+        let event = MidiCcEvent::parse(&[0xB2, 7, 100]).unwrap();
+        assert_eq!(event.channel, 2);
+        assert_eq!(event.controller, 7);
+        assert_eq!(event.value, 100);
+
+        // Note On is not a CC message
+        assert!(MidiCcEvent::parse(&[0x90, 60, 127]).is_none());
+        assert!(MidiCcEvent::parse(&[0xB0]).is_none());
+    }
+
+    #[test]
+    fn test_cc_events_produce_control_commands() {
+        let mut parameters = HashMap::new();
+        parameters.insert(
+            "cc_map".to_string(),
+            Value::String(r#"{"7": "volume"}"#.to_string()),
+        );
+        let mut controller = create_controller(parameters);
+
+        let target = Uuid::new_v4();
+        let mut mapping = ControlMapping::new("volume".to_string(), target, "opacity".to_string());
+        mapping.target_range = (0.0, 10.0);
+        controller.add_mapping(mapping);
+
+        let sender = controller.event_sender();
+        sender
+            .send(MidiCcEvent {
+                channel: 0,
+                controller: 7,
+                value: 127,
+            })
+            .unwrap();
+        sender
+            .send(MidiCcEvent {
+                channel: 0,
+                controller: 10,
+                value: 0,
+            })
+            .unwrap();
+
+        let output = controller.process(FrameData::empty()).unwrap();
+
+        assert_eq!(controller.get_control_value("volume"), Some(1.0));
+        assert_eq!(controller.get_control_value("cc10"), Some(0.0));
+
+        match output.control_data {
+            Some(ControlData::MultiControl { commands }) => {
+                assert_eq!(commands.len(), 1);
+                assert_eq!(commands[0].target_node_id, target);
+                assert_eq!(commands[0].parameter_name, "opacity");
+                match commands[0].value {
+                    ParameterValue::Float(value) => assert!((value - 10.0).abs() < 1e-6),
+                    ref other => panic!("Unexpected value: {other:?}"),
+                }
+            }
+            other => panic!("Expected MultiControl, got {other:?}"),
+        }
+    }
+
+    #[test]
+    fn test_missing_port_passes_input_through() {
+        let mut parameters = HashMap::new();
+        parameters.insert(
+            "port".to_string(),
+            Value::String("no-such-midi-port".to_string()),
+        );
+        let mut controller = create_controller(parameters);
+
+        let input = FrameData {
+            control_data: Some(ControlData::Parameter {
+                target_node_id: Uuid::new_v4(),
+                parameter_name: "opacity".to_string(),
+                value: ParameterValue::Float(0.5),
+            }),
+            ..FrameData::empty()
+        };
+        for _ in 0..3 {
+            let output = controller.process(input.clone()).unwrap();
+            assert!(matches!(
+                output.control_data,
+                Some(ControlData::Parameter { ref parameter_name, .. }) if parameter_name == "opacity"
+            ));
+        }
+    }
+
+    #[test]
+    fn test_channel_filter() {
+        let mut parameters = HashMap::new();
+        parameters.insert("channel".to_string(), Value::from(2));
+        let mut controller = create_controller(parameters);
+
+        let sender = controller.event_sender();
+        sender
+            .send(MidiCcEvent {
+                channel: 0,
+                controller: 1,
+                value: 64,
+            })
+            .unwrap();
+        sender
+            .send(MidiCcEvent {
+                channel: 1,
+                controller: 2,
+                value: 127,
+            })
+            .unwrap();
+
+        controller.process(FrameData::empty()).unwrap();
+
+        assert_eq!(controller.get_control_value("cc1"), None);
+        assert_eq!(controller.get_control_value("cc2"), Some(1.0));
+    }
+
+    #[test]
+    fn test_cc_map_passes_parameter_validation() {
+        let mut controller = create_controller(HashMap::new());
+        let definition = controller.get_properties().parameters["cc_map"].clone();
+
+        for value in [
+            definition.default_value.clone(),
+            Value::String(r#"{"7": "volume", "10": "pan"}"#.to_string()),
+        ] {
+            crate::validate_parameter(&definition, value.clone(), Default::default()).unwrap();
+            controller.set_parameter("cc_map", value).unwrap();
+        }
+        controller.process(FrameData::empty()).unwrap();
+        assert_eq!(controller.cc_map.get(&10).map(String::as_str), Some("pan"));
+
+        // 不正なJSONやCC番号は拒否され、直前の設定が残る
+        assert!(controller
+            .set_parameter("cc_map", Value::String("{".to_string()))
+            .is_err());
+        assert!(controller
+            .set_parameter("cc_map", Value::String(r#"{"200": "x"}"#.to_string()))
+            .is_err());
+        controller.process(FrameData::empty()).unwrap();
+        assert_eq!(controller.cc_map.len(), 2);
+    }
+}
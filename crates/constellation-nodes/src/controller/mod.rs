@@ -21,13 +21,31 @@ use constellation_core::*;
 use std::collections::HashMap;
 use std::time::Instant;
 
+pub mod api;
+pub mod audio_reactive;
+pub mod envelope;
+pub mod expression;
+pub mod gamepad;
 pub mod lfo;
 pub mod math;
+pub mod midi;
+pub mod osc;
 pub mod timeline;
-
+pub mod video_analysis;
+pub mod websocket;
+
+pub use api::APIController;
+pub use audio_reactive::{AudioReactiveController, LevelMode};
+pub use envelope::{EnvelopeController, EnvelopeStage};
+pub use expression::Expression;
+pub use gamepad::{GamepadAxis, GamepadButton, GamepadController, GamepadEvent};
 pub use lfo::LFOController;
 pub use math::MathController;
-pub use timeline::TimelineController;
+pub use midi::{MidiCcEvent, MidiController};
+pub use osc::{OSCReceiver, OscControlEvent};
+pub use timeline::{KeyframeTrack, TimelineController};
+pub use video_analysis::{RegionOfInterest, VideoAnalysisController};
+pub use websocket::{WebSocketControlEvent, WebSocketController};
 
 /// コントローラノードの共通特性
 pub trait ControllerNode: NodeProcessor {
@@ -0,0 +1,580 @@
+/*
+ * Constellation Studio - Professional Real-time Video Processing
+ * Copyright (c) 2025 MACHIKO LAB
+ *
+ * This program is free software: you can redistribute it and/or modify
+ * it under the terms of the GNU Affero General Public License as published by
+ * the Free Software Foundation, either version 3 of the License, or
+ * (at your option) any later version.
+ *
+ * This program is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+ * GNU Affero General Public License for more details.
+ *
+ * You should have received a copy of the GNU Affero General Public License
+ * along with this program. If not, see <https://www.gnu.org/licenses/>.
+ */
+
+use crate::controller::{apply_mappings, ControllerConfig, ControllerNode};
+use crate::{NodeProcessor, NodeProperties, ParameterDefinition, ParameterType};
+use anyhow::{anyhow, Result};
+use constellation_core::*;
+use rosc::address::{Matcher, OscAddress};
+use rosc::{OscPacket, OscType};
+use serde_json::Value;
+use std::collections::{BTreeMap, HashMap};
+use std::net::{SocketAddr, UdpSocket};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::mpsc::{self, Receiver, Sender};
+use std::sync::Arc;
+use std::thread::JoinHandle;
+use std::time::Duration;
+use uuid::Uuid;
+
+const DEFAULT_OSC_PORT: u16 = 9000;
+const MAX_PACKET_SIZE: usize = 65_536;
+
+/// 数値引数を持つOSCメッセージ
+#[derive(Debug, Clone, PartialEq)]
+pub struct OscControlEvent {
+    pub address: String,
+    pub value: f32,
+}
+
+impl OscControlEvent {
+    /// UDPパケットを解析し、数値引数を持つメッセージを取り出す（バンドルは展開）
+    pub fn parse(packet: &[u8]) -> Result<Vec<Self>> {
+        let (_, packet) = rosc::decoder::decode_udp(packet)
+            .map_err(|e| anyhow::anyhow!("Malformed OSC packet: {}", e))?;
+
+        let mut events = Vec::new();
+        Self::collect(packet, &mut events);
+        Ok(events)
+    }
+
+    fn collect(packet: OscPacket, events: &mut Vec<Self>) {
+        match packet {
+            OscPacket::Message(message) => {
+                // 最初の数値引数を制御値として使う
+                if let Some(value) = message.args.iter().find_map(Self::numeric_value) {
+                    events.push(Self {
+                        address: message.addr,
+                        value,
+                    });
+                }
+            }
+            OscPacket::Bundle(bundle) => {
+                for packet in bundle.content {
+                    Self::collect(packet, events);
+                }
+            }
+        }
+    }
+
+    fn numeric_value(arg: &OscType) -> Option<f32> {
+        match arg {
+            OscType::Float(value) => Some(*value),
+            OscType::Double(value) => Some(*value as f32),
+            OscType::Int(value) => Some(*value as f32),
+            OscType::Long(value) => Some(*value as f32),
+            OscType::Bool(value) => Some(if *value { 1.0 } else { 0.0 }),
+            _ => None,
+        }
+    }
+}
+
+/// UDPソケットを受信スレッドで監視する
+struct OscListener {
+    local_addr: SocketAddr,
+    running: Arc<AtomicBool>,
+    thread: Option<JoinHandle<()>>,
+}
+
+impl OscListener {
+    fn bind(address: &str, sender: Sender<OscControlEvent>) -> Result<Self> {
+        let socket = UdpSocket::bind(address)
+            .map_err(|e| anyhow::anyhow!("Failed to bind OSC socket {}: {}", address, e))?;
+        // 停止要求を確認できるよう受信をタイムアウトさせる
+        socket.set_read_timeout(Some(Duration::from_millis(100)))?;
+        let local_addr = socket.local_addr()?;
+
+        let running = Arc::new(AtomicBool::new(true));
+        let thread_running = running.clone();
+        let thread = std::thread::spawn(move || {
+            let mut buffer = vec![0u8; MAX_PACKET_SIZE];
+            while thread_running.load(Ordering::Relaxed) {
+                let (size, peer) = match socket.recv_from(&mut buffer) {
+                    Ok(received) => received,
+                    Err(e)
+                        if matches!(
+                            e.kind(),
+                            std::io::ErrorKind::WouldBlock | std::io::ErrorKind::TimedOut
+                        ) =>
+                    {
+                        continue;
+                    }
+                    Err(e) => {
+                        tracing::error!("OSC socket error: {}", e);
+                        break;
+                    }
+                };
+
+                match OscControlEvent::parse(&buffer[..size]) {
+                    Ok(events) => {
+                        for event in events {
+                            if sender.send(event).is_err() {
+                                return;
+                            }
+                        }
+                    }
+                    Err(e) => tracing::warn!("Dropping OSC packet from {}: {}", peer, e),
+                }
+            }
+        });
+
+        tracing::info!("Listening for OSC on {}", local_addr);
+        Ok(Self {
+            local_addr,
+            running,
+            thread: Some(thread),
+        })
+    }
+}
+
+impl Drop for OscListener {
+    fn drop(&mut self) {
+        self.running.store(false, Ordering::Relaxed);
+        if let Some(thread) = self.thread.take() {
+            let _ = thread.join();
+        }
+    }
+}
+
+/// OSCレシーバ - OSCメッセージを制御値に変換
+pub struct OSCReceiver {
+    id: Uuid,
+    config: NodeConfig,
+    properties: NodeProperties,
+    controller_config: ControllerConfig,
+
+    // OSC設定
+    address_map: Vec<(Matcher, String)>, // アドレスパターン -> ソースパラメータ名
+    current_bind: Option<String>,        // 待ち受け中のアドレス
+    listener: Option<OscListener>,
+
+    // イベント受信
+    event_sender: Sender<OscControlEvent>,
+    event_receiver: Receiver<OscControlEvent>,
+
+    // 現在の値
+    control_values: HashMap<String, f32>,
+}
+
+impl OSCReceiver {
+    pub fn new(id: Uuid, config: NodeConfig) -> Result<Self> {
+        let mut parameters = HashMap::new();
+
+        parameters.insert(
+            "port".to_string(),
+            ParameterDefinition {
+                name: "Port".to_string(),
+                parameter_type: ParameterType::Integer,
+                default_value: Value::from(DEFAULT_OSC_PORT),
+                min_value: Some(Value::from(0)),
+                max_value: Some(Value::from(65535)),
+                description: "UDP port to receive OSC on (0 = any free port)".to_string(),
+            },
+        );
+
+        parameters.insert(
+            "bind_address".to_string(),
+            ParameterDefinition {
+                name: "Bind Address".to_string(),
+                parameter_type: ParameterType::String,
+                default_value: Value::String("0.0.0.0".to_string()),
+                min_value: None,
+                max_value: None,
+                description: "Network interface to listen on".to_string(),
+            },
+        );
+
+        parameters.insert(
+            "address_map".to_string(),
+            ParameterDefinition {
+                name: "Address Mapping".to_string(),
+                parameter_type: ParameterType::String,
+                default_value: Value::String("{}".to_string()),
+                min_value: None,
+                max_value: None,
+                description: "JSON table of OSC address pattern to source parameter, \
+                              e.g. {\"/fader/*\": \"volume\"}"
+                    .to_string(),
+            },
+        );
+
+        parameters.insert(
+            "enabled".to_string(),
+            ParameterDefinition {
+                name: "Enabled".to_string(),
+                parameter_type: ParameterType::Boolean,
+                default_value: Value::Bool(true),
+                min_value: None,
+                max_value: None,
+                description: "Enable/disable OSC receiver".to_string(),
+            },
+        );
+
+        let properties = NodeProperties {
+            id,
+            name: "OSC Receiver".to_string(),
+            node_type: NodeType::Control(ControlType::OSCReceiver),
+            input_types: vec![], // OSCは外部入力のみ
+            output_types: vec![ConnectionType::Control],
+            parameters,
+            enabled: true,
+        };
+
+        let (event_sender, event_receiver) = mpsc::channel();
+
+        Ok(Self {
+            id,
+            config,
+            properties,
+            controller_config: ControllerConfig::default(),
+            address_map: Vec::new(),
+            current_bind: None,
+            listener: None,
+            event_sender,
+            event_receiver,
+            control_values: HashMap::new(),
+        })
+    }
+
+    /// 外部からOSCイベントを注入するための送信側を取得
+    pub fn event_sender(&self) -> Sender<OscControlEvent> {
+        self.event_sender.clone()
+    }
+
+    /// 待ち受け中のソケットアドレス
+    pub fn local_addr(&self) -> Option<SocketAddr> {
+        self.listener.as_ref().map(|listener| listener.local_addr)
+    }
+
+    /// OSCアドレスに対応するソースパラメータ名（未マッピングならアドレスそのもの）
+    fn source_parameter_for(&self, address: &str) -> String {
+        OscAddress::new(address.to_string())
+            .ok()
+            .and_then(|address| {
+                self.address_map
+                    .iter()
+                    .find(|(matcher, _)| matcher.match_address(&address))
+                    .map(|(_, source)| source.clone())
+            })
+            .unwrap_or_else(|| address.to_string())
+    }
+
+    /// 受信済みOSCイベントを反映
+    fn drain_events(&mut self) {
+        while let Ok(event) = self.event_receiver.try_recv() {
+            let source = self.source_parameter_for(&event.address);
+            self.control_values.insert(source, event.value);
+        }
+    }
+
+    /// パラメータを更新
+    fn update_parameters(&mut self) -> Result<()> {
+        // 不正なJSONやパターンはset_parameterで弾かれる
+        self.address_map = self
+            .get_parameter("address_map")
+            .and_then(|v| parse_address_map(&v).ok())
+            .unwrap_or_default();
+
+        self.controller_config.enabled = self
+            .get_parameter("enabled")
+            .and_then(|v| v.as_bool())
+            .unwrap_or(true);
+
+        let bind = if self.controller_config.enabled {
+            let host = self
+                .get_parameter("bind_address")
+                .and_then(|v| v.as_str().map(str::to_string))
+                .unwrap_or_else(|| "0.0.0.0".to_string());
+            let port = self
+                .get_parameter("port")
+                .and_then(|v| v.as_u64())
+                .unwrap_or(DEFAULT_OSC_PORT as u64);
+            Some(format!("{host}:{port}"))
+        } else {
+            None
+        };
+
+        if bind != self.current_bind {
+            // 既存ソケットを閉じてから再バインド
+            self.listener = None;
+            self.current_bind = bind.clone();
+            if let Some(bind) = bind {
+                self.listener = Some(OscListener::bind(&bind, self.event_sender.clone())?);
+            }
+        }
+
+        Ok(())
+    }
+}
+
+/// `address_map`パラメータ（`{"アドレスパターン": "ソースパラメータ名"}` 形式のJSON文字列）を解析する
+///
+/// パターンはキー順に照合される。
+fn parse_address_map(value: &Value) -> Result<Vec<(Matcher, String)>> {
+    let json = value
+        .as_str()
+        .ok_or_else(|| anyhow!("address_map must be a JSON string, got {}", value))?;
+    serde_json::from_str::<BTreeMap<String, String>>(json)
+        .map_err(|e| anyhow!("Invalid address_map JSON: {}", e))?
+        .into_iter()
+        .map(|(pattern, source)| {
+            let matcher = Matcher::new(&pattern)
+                .map_err(|e| anyhow!("Invalid OSC address pattern {}: {}", pattern, e))?;
+            Ok((matcher, source))
+        })
+        .collect()
+}
+
+impl NodeProcessor for OSCReceiver {
+    fn process(&mut self, input: FrameData) -> Result<FrameData> {
+        // パラメータを更新
+        self.update_parameters()?;
+
+        // 無効なら入力をそのまま通す
+        if !self.controller_config.enabled {
+            return Ok(input);
+        }
+
+        // 受信済みイベントを反映
+        self.drain_events();
+
+        // 制御コマンドを生成
+        let control_commands = self.generate_control_commands();
+
+        let control_data = if !control_commands.is_empty() {
+            Some(ControlData::MultiControl {
+                commands: control_commands,
+            })
+        } else {
+            input.control_data
+        };
+
+        Ok(FrameData {
+            control_data,
+            ..input
+        })
+    }
+
+    fn get_properties(&self) -> NodeProperties {
+        self.properties.clone()
+    }
+
+    fn set_parameter(&mut self, key: &str, value: Value) -> Result<()> {
+        if key == "address_map" {
+            parse_address_map(&value)?;
+        }
+        self.config.parameters.insert(key.to_string(), value);
+        Ok(())
+    }
+
+    fn get_parameter(&self, key: &str) -> Option<Value> {
+        self.config.parameters.get(key).cloned()
+    }
+}
+
+impl ControllerNode for OSCReceiver {
+    fn add_mapping(&mut self, mapping: ControlMapping) {
+        self.controller_config.mappings.push(mapping);
+    }
+
+    fn remove_mapping(&mut self, source_parameter: &str) {
+        self.controller_config
+            .mappings
+            .retain(|m| m.source_parameter != source_parameter);
+    }
+
+    fn get_control_value(&self, parameter: &str) -> Option<f32> {
+        self.control_values.get(parameter).copied()
+    }
+
+    fn generate_control_commands(&self) -> Vec<ControlCommand> {
+        apply_mappings(&self.controller_config.mappings, &self.control_values)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rosc::{OscBundle, OscMessage, OscTime};
+    use std::time::Instant;
+
+    fn create_receiver(parameters: HashMap<String, Value>) -> OSCReceiver {
+        OSCReceiver::new(Uuid::new_v4(), NodeConfig { parameters }).unwrap()
+    }
+
+    fn loopback_parameters() -> HashMap<String, Value> {
+        let mut parameters = HashMap::new();
+        parameters.insert(
+            "bind_address".to_string(),
+            Value::String("127.0.0.1".to_string()),
+        );
+        parameters.insert("port".to_string(), Value::from(0));
+        parameters
+    }
+
+    fn encode_message(addr: &str, args: Vec<OscType>) -> Vec<u8> {
+        rosc::encoder::encode(&OscPacket::Message(OscMessage {
+            addr: addr.to_string(),
+            args,
+        }))
+        .unwrap()
+    }
+
+    #[test]
+    fn test_parse_float_int_and_bundle() {
+        let events = OscControlEvent::parse(&encode_message(
+            "/fader/1",
+            vec![OscType::String("label".to_string()), OscType::Float(0.25)],
+        ))
+        .unwrap();
+        assert_eq!(
+            events,
+            vec![OscControlEvent {
+                address: "/fader/1".to_string(),
+                value: 0.25
+            }]
+        );
+
+        let bundle = rosc::encoder::encode(&OscPacket::Bundle(OscBundle {
+            timetag: OscTime::from((0, 1)),
+            content: vec![
+                OscPacket::Message(OscMessage {
+                    addr: "/knob".to_string(),
+                    args: vec![OscType::Int(3)],
+                }),
+                OscPacket::Message(OscMessage {
+                    addr: "/text".to_string(),
+                    args: vec![OscType::String("ignored".to_string())],
+                }),
+            ],
+        }))
+        .unwrap();
+        let events = OscControlEvent::parse(&bundle).unwrap();
+        assert_eq!(events.len(), 1);
+        assert_eq!(events[0].address, "/knob");
+        assert_eq!(events[0].value, 3.0);
+
+        assert!(OscControlEvent::parse(b"not an osc packet").is_err());
+    }
+
+    #[test]
+    fn test_udp_packet_updates_control_value() {
+        let mut parameters = loopback_parameters();
+        parameters.insert(
+            "address_map".to_string(),
+            Value::String(r#"{"/mixer/fader/[0-9]": "volume"}"#.to_string()),
+        );
+        let mut receiver = create_receiver(parameters);
+
+        let target = Uuid::new_v4();
+        let mut mapping = ControlMapping::new("volume".to_string(), target, "opacity".to_string());
+        mapping.target_range = (0.0, 10.0);
+        receiver.add_mapping(mapping);
+
+        // 最初の処理でソケットがバインドされる
+        receiver.process(FrameData::empty()).unwrap();
+        let address = receiver.local_addr().expect("receiver should be listening");
+
+        let sender = UdpSocket::bind("127.0.0.1:0").unwrap();
+        // 不正なパケットは破棄され、受信は継続する
+        sender.send_to(b"garbage", address).unwrap();
+        sender
+            .send_to(
+                &encode_message("/mixer/fader/3", vec![OscType::Float(0.5)]),
+                address,
+            )
+            .unwrap();
+
+        let deadline = Instant::now() + Duration::from_secs(2);
+        let output = loop {
+            let output = receiver.process(FrameData::empty()).unwrap();
+            if receiver.get_control_value("volume").is_some() || Instant::now() > deadline {
+                break output;
+            }
+            std::thread::sleep(Duration::from_millis(5));
+        };
+
+        assert_eq!(receiver.get_control_value("volume"), Some(0.5));
+        match output.control_data {
+            Some(ControlData::MultiControl { commands }) => {
+                assert_eq!(commands.len(), 1);
+                assert_eq!(commands[0].target_node_id, target);
+                assert_eq!(commands[0].parameter_name, "opacity");
+                assert!(matches!(commands[0].value, ParameterValue::Float(v) if v == 5.0));
+            }
+            other => panic!("expected MultiControl, got {:?}", other),
+        }
+    }
+
+    #[test]
+    fn test_unmapped_address_uses_address_as_source() {
+        let mut receiver = create_receiver(loopback_parameters());
+        receiver
+            .event_sender()
+            .send(OscControlEvent {
+                address: "/camera/zoom".to_string(),
+                value: 2.0,
+            })
+            .unwrap();
+
+        receiver.process(FrameData::empty()).unwrap();
+        assert_eq!(receiver.get_control_value("/camera/zoom"), Some(2.0));
+    }
+
+    #[test]
+    fn test_disabling_closes_socket() {
+        let mut receiver = create_receiver(loopback_parameters());
+        receiver.process(FrameData::empty()).unwrap();
+        assert!(receiver.local_addr().is_some());
+
+        receiver
+            .set_parameter("enabled", Value::Bool(false))
+            .unwrap();
+        receiver.process(FrameData::empty()).unwrap();
+        assert!(receiver.local_addr().is_none());
+    }
+
+    #[test]
+    fn test_address_map_passes_parameter_validation() {
+        let mut receiver = create_receiver(loopback_parameters());
+        let definition = receiver.get_properties().parameters["address_map"].clone();
+
+        for value in [
+            definition.default_value.clone(),
+            Value::String(r#"{"/fader/*": "volume"}"#.to_string()),
+        ] {
+            crate::validate_parameter(&definition, value.clone(), Default::default()).unwrap();
+            receiver.set_parameter("address_map", value).unwrap();
+        }
+        receiver.process(FrameData::empty()).unwrap();
+        assert_eq!(receiver.source_parameter_for("/fader/3"), "volume");
+
+        // 不正なJSONやパターンは拒否され、直前の設定が残る
+        assert!(receiver
+            .set_parameter("address_map", Value::String("[]".to_string()))
+            .is_err());
+        assert!(receiver
+            .set_parameter(
+                "address_map",
+                Value::String(r#"{"fader": "x"}"#.to_string())
+            )
+            .is_err());
+        receiver.process(FrameData::empty()).unwrap();
+        assert_eq!(receiver.source_parameter_for("/fader/3"), "volume");
+    }
+}
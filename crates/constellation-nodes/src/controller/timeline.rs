@@ -25,6 +25,37 @@ use std::collections::HashMap;
 use std::time::Instant;
 use uuid::Uuid;
 
+/// 1つのターゲットパラメータを駆動するキーフレーム列
+#[derive(Debug, Clone)]
+pub struct KeyframeTrack {
+    pub target_node_id: Uuid,
+    pub parameter_name: String,
+    pub keyframes: Vec<Keyframe>,
+}
+
+impl KeyframeTrack {
+    pub fn new(target_node_id: Uuid, parameter_name: String) -> Self {
+        Self {
+            target_node_id,
+            parameter_name,
+            keyframes: Vec::new(),
+        }
+    }
+
+    /// キーフレームを時間順を保って追加
+    pub fn add_keyframe(&mut self, keyframe: Keyframe) {
+        let index = self
+            .keyframes
+            .partition_point(|existing| existing.time <= keyframe.time);
+        self.keyframes.insert(index, keyframe);
+    }
+
+    /// 指定時間でのトラックの値
+    pub fn value_at(&self, time: f32) -> ParameterValue {
+        evaluate_keyframes(&self.keyframes, time)
+    }
+}
+
 /// タイムラインコントローラ - キーフレーム制御
 pub struct TimelineController {
     id: Uuid,
@@ -34,6 +65,7 @@ pub struct TimelineController {
 
     // タイムライン設定
     keyframes: Vec<Keyframe>,
+    tracks: Vec<KeyframeTrack>,
     current_time: f32,
     duration: f32,
     is_playing: bool,
@@ -43,6 +75,9 @@ pub struct TimelineController {
     // 現在の値
     current_value: f32,
 
+    // 最後に適用したシーク位置（"time"パラメータが変わったときだけシークする）
+    last_seek: Option<f32>,
+
     // 時間管理
     start_time: Instant,
     last_update: Instant,
@@ -132,6 +167,7 @@ impl TimelineController {
             input_types: vec![],
             output_types: vec![ConnectionType::Control],
             parameters,
+            enabled: true,
         };
 
         let now = Instant::now();
@@ -142,18 +178,20 @@ impl TimelineController {
             properties,
             controller_config: ControllerConfig::default(),
             keyframes: Vec::new(),
+            tracks: Vec::new(),
             current_time: 0.0,
             duration: 10.0,
             is_playing: false,
             loop_enabled: true,
             playback_speed: 1.0,
             current_value: 0.0,
+            last_seek: None,
             start_time: now,
             last_update: now,
         })
     }
 
-    /// キーフレームを追加
+    /// キーフレームを追加（"output"/"value"としてマッピング経由で出力される）
     pub fn add_keyframe(&mut self, keyframe: Keyframe) {
         self.keyframes.push(keyframe);
         // 時間でソート
@@ -166,82 +204,77 @@ impl TimelineController {
         self.keyframes.clear();
     }
 
-    /// 指定時間での値を補間
-    fn interpolate_value_at_time(&self, time: f32) -> f32 {
-        if self.keyframes.is_empty() {
-            return 0.0;
+    /// ターゲットパラメータのトラックにキーフレームを追加（トラックがなければ作成）
+    pub fn add_track_keyframe(
+        &mut self,
+        target_node_id: Uuid,
+        parameter_name: &str,
+        keyframe: Keyframe,
+    ) {
+        let position = self.tracks.iter().position(|track| {
+            track.target_node_id == target_node_id && track.parameter_name == parameter_name
+        });
+        let track = match position {
+            Some(index) => &mut self.tracks[index],
+            None => {
+                self.tracks.push(KeyframeTrack::new(
+                    target_node_id,
+                    parameter_name.to_string(),
+                ));
+                self.tracks.last_mut().unwrap()
+            }
+        };
+        track.add_keyframe(keyframe);
+    }
+
+    /// トラックを丸ごと設定（既存の同じターゲットのトラックは置き換える）
+    pub fn set_track(&mut self, track: KeyframeTrack) {
+        self.tracks.retain(|existing| {
+            existing.target_node_id != track.target_node_id
+                || existing.parameter_name != track.parameter_name
+        });
+        let mut sorted = KeyframeTrack::new(track.target_node_id, track.parameter_name);
+        for keyframe in track.keyframes {
+            sorted.add_keyframe(keyframe);
         }
+        self.tracks.push(sorted);
+    }
 
-        // 時間をクランプ
-        let clamped_time = time.max(0.0).min(self.duration);
+    /// 全トラックを削除
+    pub fn clear_tracks(&mut self) {
+        self.tracks.clear();
+    }
 
-        // 周囲のキーフレームを検索
-        let mut before_keyframe = None;
-        let mut after_keyframe = None;
+    pub fn tracks(&self) -> &[KeyframeTrack] {
+        &self.tracks
+    }
 
-        for keyframe in &self.keyframes {
-            if keyframe.time <= clamped_time {
-                before_keyframe = Some(keyframe);
-            } else {
-                after_keyframe = Some(keyframe);
-                break;
-            }
-        }
+    /// 再生位置（秒）
+    pub fn current_time(&self) -> f32 {
+        self.current_time
+    }
 
-        match (before_keyframe, after_keyframe) {
-            (Some(before), Some(after)) => {
-                // 2つのキーフレーム間で補間
-                let t = (clamped_time - before.time) / (after.time - before.time);
-                let smooth_t = self.apply_interpolation(t, &before.interpolation);
-
-                match (&before.value, &after.value) {
-                    (ParameterValue::Float(f1), ParameterValue::Float(f2)) => {
-                        f1 + (f2 - f1) * smooth_t
-                    }
-                    _ => 0.0, // 現在はFloatのみサポート
-                }
-            }
-            (Some(keyframe), None) => {
-                // 最後のキーフレーム
-                match &keyframe.value {
-                    ParameterValue::Float(f) => *f,
-                    _ => 0.0,
-                }
-            }
-            (None, Some(keyframe)) => {
-                // 最初のキーフレーム
-                match &keyframe.value {
-                    ParameterValue::Float(f) => *f,
-                    _ => 0.0,
-                }
-            }
-            (None, None) => 0.0,
-        }
+    pub fn is_playing(&self) -> bool {
+        self.is_playing
     }
 
-    /// 補間カーブを適用
-    fn apply_interpolation(&self, t: f32, interpolation: &InterpolationType) -> f32 {
-        match interpolation {
-            InterpolationType::Linear => t,
-            InterpolationType::EaseIn => t * t,
-            InterpolationType::EaseOut => 1.0 - (1.0 - t).powi(2),
-            InterpolationType::EaseInOut => {
-                if t < 0.5 {
-                    2.0 * t * t
-                } else {
-                    1.0 - 2.0 * (1.0 - t).powi(2)
-                }
-            }
-            InterpolationType::Bezier(p1, p2, p3, p4) => {
-                // 簡略化されたベジェ補間
-                let t2 = t * t;
-                let t3 = t2 * t;
-                let mt = 1.0 - t;
-                let mt2 = mt * mt;
-                let mt3 = mt2 * mt;
-
-                mt3 * p1 + 3.0 * mt2 * t * p2 + 3.0 * mt * t2 * p3 + t3 * p4
-            }
+    /// 再生位置を移動（タイムライン範囲にクランプ）
+    pub fn seek(&mut self, time: f32) {
+        self.current_time = time.clamp(0.0, self.duration);
+    }
+
+    /// 経過時間だけトランスポートを進め、現在位置の値を評価する
+    pub fn advance(&mut self, delta_time: f32) {
+        self.update_time(delta_time);
+        self.current_value = self.interpolate_value_at_time(self.current_time);
+    }
+
+    /// 指定時間での値を補間
+    fn interpolate_value_at_time(&self, time: f32) -> f32 {
+        let clamped_time = time.max(0.0).min(self.duration);
+        match evaluate_keyframes(&self.keyframes, clamped_time) {
+            ParameterValue::Float(value) => value,
+            _ => 0.0, // マッピング出力はFloatのみサポート
         }
     }
 
@@ -283,9 +316,14 @@ impl TimelineController {
             .and_then(|v| v.as_f64())
             .unwrap_or(10.0) as f32;
 
-        // 手動時間オーバーライド
-        if let Some(manual_time) = self.get_parameter("time").and_then(|v| v.as_f64()) {
-            self.current_time = manual_time as f32;
+        // 手動時間オーバーライド（値が変わったときだけシーク）
+        let seek_time = self
+            .get_parameter("time")
+            .and_then(|v| v.as_f64())
+            .map(|v| v as f32);
+        if seek_time.is_some() && seek_time != self.last_seek {
+            self.last_seek = seek_time;
+            self.seek(seek_time.unwrap_or(0.0));
         }
 
         self.controller_config.enabled = self
@@ -305,13 +343,10 @@ impl NodeProcessor for TimelineController {
             return Ok(input);
         }
 
-        // 時間を更新
+        // トランスポートを進めて現在の値を補間
         let now = Instant::now();
         let delta_time = now.duration_since(self.last_update).as_secs_f32();
-        self.update_time(delta_time);
-
-        // 現在の値を補間
-        self.current_value = self.interpolate_value_at_time(self.current_time);
+        self.advance(delta_time);
 
         // 制御コマンドを生成
         let control_commands = self.generate_control_commands();
@@ -327,10 +362,8 @@ impl NodeProcessor for TimelineController {
         self.last_update = now;
 
         Ok(FrameData {
-            render_data: input.render_data,
-            audio_data: input.audio_data,
             control_data,
-            tally_metadata: input.tally_metadata,
+            ..input
         })
     }
 
@@ -375,7 +408,23 @@ impl ControllerNode for TimelineController {
         control_values.insert("time".to_string(), self.current_time);
         control_values.insert("progress".to_string(), self.current_time / self.duration);
 
-        apply_mappings(&self.controller_config.mappings, &control_values)
+        let mut commands = apply_mappings(&self.controller_config.mappings, &control_values);
+
+        // 各トラックを現在位置で評価してターゲットへ送る
+        let timestamp = Instant::now();
+        commands.extend(
+            self.tracks
+                .iter()
+                .filter(|track| !track.keyframes.is_empty())
+                .map(|track| ControlCommand {
+                    target_node_id: track.target_node_id,
+                    parameter_name: track.parameter_name.clone(),
+                    value: track.value_at(self.current_time),
+                    timestamp,
+                }),
+        );
+
+        commands
     }
 }
 
@@ -464,4 +513,114 @@ mod tests {
         assert_eq!(controller.current_time, 1.0); // 6.0 % 5.0 = 1.0
         assert!(controller.is_playing);
     }
+
+    fn float_keyframe(time: f32, value: f32) -> Keyframe {
+        Keyframe {
+            time,
+            value: ParameterValue::Float(value),
+            interpolation: InterpolationType::Linear,
+        }
+    }
+
+    fn command_value(commands: &[ControlCommand], target: Uuid, parameter: &str) -> f32 {
+        let command = commands
+            .iter()
+            .find(|c| c.target_node_id == target && c.parameter_name == parameter)
+            .expect("トラックのコマンドが見つからない");
+        match command.value {
+            ParameterValue::Float(value) => value,
+            ref other => panic!("Floatを期待したが {:?}", other),
+        }
+    }
+
+    #[test]
+    fn test_timeline_tracks_emit_interpolated_commands() {
+        let config = NodeConfig {
+            parameters: HashMap::new(),
+        };
+        let mut controller = TimelineController::new(Uuid::new_v4(), config).unwrap();
+        controller.is_playing = true;
+        controller.duration = 4.0;
+
+        let target = Uuid::new_v4();
+        controller.add_track_keyframe(target, "brightness", float_keyframe(0.0, 0.0));
+        controller.add_track_keyframe(target, "brightness", float_keyframe(2.0, 1.0));
+        controller.add_track_keyframe(target, "contrast", float_keyframe(0.0, 1.0));
+        controller.add_track_keyframe(target, "contrast", float_keyframe(4.0, 3.0));
+        assert_eq!(controller.tracks().len(), 2);
+
+        controller.advance(1.0);
+        let commands = controller.generate_control_commands();
+        assert_eq!(commands.len(), 2);
+        assert_eq!(command_value(&commands, target, "brightness"), 0.5);
+        assert_eq!(command_value(&commands, target, "contrast"), 1.5);
+
+        // 最後のキーフレーム以降は端の値で止まる
+        controller.advance(2.0);
+        let commands = controller.generate_control_commands();
+        assert_eq!(command_value(&commands, target, "brightness"), 1.0);
+        assert_eq!(command_value(&commands, target, "contrast"), 2.5);
+    }
+
+    #[test]
+    fn test_timeline_tracks_loop_and_seek() {
+        let config = NodeConfig {
+            parameters: HashMap::new(),
+        };
+        let mut controller = TimelineController::new(Uuid::new_v4(), config).unwrap();
+        controller.is_playing = true;
+        controller.loop_enabled = true;
+        controller.duration = 2.0;
+
+        let target = Uuid::new_v4();
+        controller.add_track_keyframe(target, "opacity", float_keyframe(0.0, 0.0));
+        controller.add_track_keyframe(target, "opacity", float_keyframe(2.0, 1.0));
+
+        // 2.5秒進めると0.5秒地点へループ
+        controller.advance(2.5);
+        assert_eq!(controller.current_time(), 0.5);
+        let commands = controller.generate_control_commands();
+        assert_eq!(command_value(&commands, target, "opacity"), 0.25);
+
+        // "time"パラメータで一度だけシークし、その後は再生が進む
+        controller.set_parameter("play", Value::Bool(true)).unwrap();
+        controller
+            .set_parameter("duration", Value::from(2.0))
+            .unwrap();
+        controller.set_parameter("time", Value::from(1.5)).unwrap();
+        controller.update_parameters();
+        assert_eq!(controller.current_time(), 1.5);
+
+        controller.advance(0.25);
+        controller.update_parameters();
+        assert_eq!(controller.current_time(), 1.75);
+        let commands = controller.generate_control_commands();
+        assert_eq!(command_value(&commands, target, "opacity"), 0.875);
+    }
+
+    #[test]
+    fn test_timeline_pause_holds_position() {
+        let config = NodeConfig {
+            parameters: HashMap::new(),
+        };
+        let mut controller = TimelineController::new(Uuid::new_v4(), config).unwrap();
+        let target = Uuid::new_v4();
+        controller.add_track_keyframe(target, "brightness", float_keyframe(0.0, 0.0));
+        controller.add_track_keyframe(target, "brightness", float_keyframe(10.0, 1.0));
+
+        controller.set_parameter("play", Value::Bool(true)).unwrap();
+        controller.update_parameters();
+        controller.advance(3.0);
+
+        controller
+            .set_parameter("play", Value::Bool(false))
+            .unwrap();
+        controller.update_parameters();
+        controller.advance(3.0);
+
+        assert!(!controller.is_playing());
+        assert_eq!(controller.current_time(), 3.0);
+        let commands = controller.generate_control_commands();
+        assert!((command_value(&commands, target, "brightness") - 0.3).abs() < 1e-6);
+    }
 }
@@ -0,0 +1,443 @@
+/*
+ * Constellation Studio - Professional Real-time Video Processing
+ * Copyright (c) 2025 MACHIKO LAB
+ *
+ * This program is free software: you can redistribute it and/or modify
+ * it under the terms of the GNU Affero General Public License as published by
+ * the Free Software Foundation, either version 3 of the License, or
+ * (at your option) any later version.
+ *
+ * This program is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+ * GNU Affero General Public License for more details.
+ *
+ * You should have received a copy of the GNU Affero General Public License
+ * along with this program. If not, see <https://www.gnu.org/licenses/>.
+ */
+
+use crate::controller::{apply_mappings, ControllerConfig, ControllerNode};
+use crate::{NodeProcessor, NodeProperties, ParameterDefinition, ParameterType};
+use anyhow::Result;
+use constellation_core::*;
+use serde_json::Value;
+use std::collections::HashMap;
+use uuid::Uuid;
+
+/// 動き量（0.0〜1.0）を出力するソースパラメータ名
+pub const MOTION_PARAMETER: &str = "motion";
+/// 動き量がしきい値以上なら1.0、未満なら0.0を出力するソースパラメータ名
+pub const MOTION_DETECTED_PARAMETER: &str = "motion_detected";
+
+/// 正規化座標（0.0〜1.0）で指定する解析領域
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct RegionOfInterest {
+    pub x: f32,
+    pub y: f32,
+    pub width: f32,
+    pub height: f32,
+}
+
+impl RegionOfInterest {
+    pub const FULL_FRAME: Self = Self {
+        x: 0.0,
+        y: 0.0,
+        width: 1.0,
+        height: 1.0,
+    };
+
+    /// フレーム内のピクセル範囲（x0, y0, x1, y1）に変換する。空になる場合はNone
+    fn pixel_bounds(&self, width: u32, height: u32) -> Option<(usize, usize, usize, usize)> {
+        let to_pixels = |start: f32, extent: f32, size: u32| {
+            let start = start.clamp(0.0, 1.0);
+            let end = (start + extent.max(0.0)).min(1.0);
+            (
+                (start * size as f32).floor() as usize,
+                (end * size as f32).ceil() as usize,
+            )
+        };
+        let (x0, x1) = to_pixels(self.x, self.width, width);
+        let (y0, y1) = to_pixels(self.y, self.height, height);
+        (x1 > x0 && y1 > y0).then_some((x0, y0, x1, y1))
+    }
+}
+
+/// 映像解析コントローラ - 連続するフレームの差分から動き量を制御値に変換
+pub struct VideoAnalysisController {
+    id: Uuid,
+    config: NodeConfig,
+    properties: NodeProperties,
+    controller_config: ControllerConfig,
+
+    // 解析設定
+    roi: RegionOfInterest,
+    threshold: f32,
+
+    // 直前フレームの解析領域の輝度（フレームサイズと組で保持）
+    previous_luma: Option<(u32, u32, Vec<u8>)>,
+
+    // 現在の値
+    control_values: HashMap<String, f32>,
+}
+
+impl VideoAnalysisController {
+    pub fn new(id: Uuid, config: NodeConfig) -> Result<Self> {
+        let mut parameters = HashMap::new();
+
+        parameters.insert(
+            "roi".to_string(),
+            ParameterDefinition {
+                name: "Region of Interest".to_string(),
+                parameter_type: ParameterType::Vector4,
+                default_value: Value::Array(vec![
+                    Value::from(0.0),
+                    Value::from(0.0),
+                    Value::from(1.0),
+                    Value::from(1.0),
+                ]),
+                min_value: Some(Value::from(0.0)),
+                max_value: Some(Value::from(1.0)),
+                description: "Analysed area as normalized [x, y, width, height]".to_string(),
+            },
+        );
+
+        parameters.insert(
+            "threshold".to_string(),
+            ParameterDefinition {
+                name: "Threshold".to_string(),
+                parameter_type: ParameterType::Float,
+                default_value: Value::from(0.05),
+                min_value: Some(Value::from(0.0)),
+                max_value: Some(Value::from(1.0)),
+                description: "Motion level at which motion_detected switches to 1".to_string(),
+            },
+        );
+
+        parameters.insert(
+            "enabled".to_string(),
+            ParameterDefinition {
+                name: "Enabled".to_string(),
+                parameter_type: ParameterType::Boolean,
+                default_value: Value::Bool(true),
+                min_value: None,
+                max_value: None,
+                description: "Enable/disable video analysis".to_string(),
+            },
+        );
+
+        let properties = NodeProperties {
+            id,
+            name: "Video Analysis".to_string(),
+            node_type: NodeType::Control(ControlType::VideoAnalysis),
+            input_types: vec![ConnectionType::RenderData],
+            output_types: vec![ConnectionType::Control],
+            parameters,
+            enabled: true,
+        };
+
+        let mut control_values = HashMap::new();
+        control_values.insert(MOTION_PARAMETER.to_string(), 0.0);
+        control_values.insert(MOTION_DETECTED_PARAMETER.to_string(), 0.0);
+
+        Ok(Self {
+            id,
+            config,
+            properties,
+            controller_config: ControllerConfig::default(),
+            roi: RegionOfInterest::FULL_FRAME,
+            threshold: 0.05,
+            previous_luma: None,
+            control_values,
+        })
+    }
+
+    /// 直前に解析した動き量
+    pub fn motion(&self) -> f32 {
+        self.control_values
+            .get(MOTION_PARAMETER)
+            .copied()
+            .unwrap_or(0.0)
+    }
+
+    /// フレームの解析領域を輝度に変換し、直前のフレームとの差分から動き量を更新
+    fn analyze(&mut self, frame: &VideoFrame) {
+        let Some(luma) = roi_luma(frame, &self.roi) else {
+            return;
+        };
+
+        // 最初のフレームやサイズが変わった直後は比較対象がないため動きなしとする
+        let motion = match &self.previous_luma {
+            Some((width, height, previous))
+                if *width == frame.width
+                    && *height == frame.height
+                    && previous.len() == luma.len() =>
+            {
+                let total: u64 = previous
+                    .iter()
+                    .zip(&luma)
+                    .map(|(a, b)| a.abs_diff(*b) as u64)
+                    .sum();
+                total as f32 / (luma.len() as f32 * 255.0)
+            }
+            _ => 0.0,
+        };
+
+        self.previous_luma = Some((frame.width, frame.height, luma));
+        self.control_values
+            .insert(MOTION_PARAMETER.to_string(), motion);
+        self.control_values.insert(
+            MOTION_DETECTED_PARAMETER.to_string(),
+            if motion >= self.threshold { 1.0 } else { 0.0 },
+        );
+    }
+
+    /// パラメータを更新
+    fn update_parameters(&mut self) {
+        if let Some(roi) = self.get_parameter("roi").and_then(|v| {
+            let components: Vec<f32> = v
+                .as_array()?
+                .iter()
+                .map(|c| c.as_f64().map(|c| c as f32))
+                .collect::<Option<_>>()?;
+            match components[..] {
+                [x, y, width, height] => Some(RegionOfInterest {
+                    x,
+                    y,
+                    width,
+                    height,
+                }),
+                _ => None,
+            }
+        }) {
+            if roi != self.roi {
+                // 領域が変わったら差分の基準を取り直す
+                self.roi = roi;
+                self.previous_luma = None;
+            }
+        }
+
+        if let Some(threshold) = self.get_parameter("threshold").and_then(|v| v.as_f64()) {
+            self.threshold = threshold.clamp(0.0, 1.0) as f32;
+        }
+
+        self.controller_config.enabled = self
+            .get_parameter("enabled")
+            .and_then(|v| v.as_bool())
+            .unwrap_or(true);
+    }
+}
+
+/// 解析領域の各ピクセルのBT.709輝度。パックされたRGB系以外はNone
+fn roi_luma(frame: &VideoFrame, roi: &RegionOfInterest) -> Option<Vec<u8>> {
+    let layout = frame.format.pixel_layout()?;
+    let (x0, y0, x1, y1) = roi.pixel_bounds(frame.width, frame.height)?;
+    let width = frame.width as usize;
+    if frame.data.len() < width * frame.height as usize * layout.bytes_per_pixel {
+        return None;
+    }
+
+    let mut luma = Vec::with_capacity((x1 - x0) * (y1 - y0));
+    for y in y0..y1 {
+        for x in x0..x1 {
+            let offset = (y * width + x) * layout.bytes_per_pixel;
+            let [r, g, b] = layout.rgb(&frame.data[offset..offset + layout.bytes_per_pixel]);
+            let value = 0.2126 * r as f32 + 0.7152 * g as f32 + 0.0722 * b as f32;
+            luma.push(value.round() as u8);
+        }
+    }
+    Some(luma)
+}
+
+impl NodeProcessor for VideoAnalysisController {
+    fn process(&mut self, input: FrameData) -> Result<FrameData> {
+        // パラメータを更新
+        self.update_parameters();
+
+        // 無効なら入力をそのまま通す
+        if !self.controller_config.enabled {
+            return Ok(input);
+        }
+
+        if let Some(RenderData::Raster2D(ref frame)) = input.render_data {
+            self.analyze(frame);
+        }
+
+        // 制御コマンドを生成
+        let control_commands = self.generate_control_commands();
+
+        let control_data = if !control_commands.is_empty() {
+            Some(ControlData::MultiControl {
+                commands: control_commands,
+            })
+        } else {
+            input.control_data
+        };
+
+        Ok(FrameData {
+            control_data,
+            ..input
+        })
+    }
+
+    fn get_properties(&self) -> NodeProperties {
+        self.properties.clone()
+    }
+
+    fn set_parameter(&mut self, key: &str, value: Value) -> Result<()> {
+        self.config.parameters.insert(key.to_string(), value);
+        Ok(())
+    }
+
+    fn get_parameter(&self, key: &str) -> Option<Value> {
+        self.config.parameters.get(key).cloned()
+    }
+}
+
+impl ControllerNode for VideoAnalysisController {
+    fn add_mapping(&mut self, mapping: ControlMapping) {
+        self.controller_config.mappings.push(mapping);
+    }
+
+    fn remove_mapping(&mut self, source_parameter: &str) {
+        self.controller_config
+            .mappings
+            .retain(|m| m.source_parameter != source_parameter);
+    }
+
+    fn get_control_value(&self, parameter: &str) -> Option<f32> {
+        self.control_values.get(parameter).copied()
+    }
+
+    fn generate_control_commands(&self) -> Vec<ControlCommand> {
+        apply_mappings(&self.controller_config.mappings, &self.control_values)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn create_controller(parameters: HashMap<String, Value>) -> VideoAnalysisController {
+        VideoAnalysisController::new(Uuid::new_v4(), NodeConfig { parameters }).unwrap()
+    }
+
+    fn solid_frame(width: u32, height: u32, value: u8) -> VideoFrame {
+        VideoFrame {
+            width,
+            height,
+            format: VideoFormat::Rgba8,
+            data: [value, value, value, 255].repeat((width * height) as usize),
+        }
+    }
+
+    fn frame_data(frame: VideoFrame) -> FrameData {
+        FrameData::from_raster(frame)
+    }
+
+    /// マッピング経由で出力された制御値を取り出す
+    fn mapped_value(output: &FrameData) -> f32 {
+        match &output.control_data {
+            Some(ControlData::MultiControl { commands }) => match commands[0].value {
+                ParameterValue::Float(value) => value,
+                ref other => panic!("Expected float control value, got {other:?}"),
+            },
+            other => panic!("Expected control commands, got {other:?}"),
+        }
+    }
+
+    #[test]
+    fn test_identical_frames_report_no_motion() {
+        let mut controller = create_controller(HashMap::new());
+        controller.add_mapping(ControlMapping::new(
+            MOTION_PARAMETER.to_string(),
+            Uuid::new_v4(),
+            "opacity".to_string(),
+        ));
+
+        controller
+            .process(frame_data(solid_frame(16, 16, 120)))
+            .unwrap();
+        let output = controller
+            .process(frame_data(solid_frame(16, 16, 120)))
+            .unwrap();
+
+        assert_eq!(mapped_value(&output), 0.0);
+        assert_eq!(
+            controller.get_control_value(MOTION_DETECTED_PARAMETER),
+            Some(0.0)
+        );
+    }
+
+    #[test]
+    fn test_very_different_frames_report_high_motion() {
+        let mut controller = create_controller(HashMap::new());
+        controller.add_mapping(ControlMapping::new(
+            MOTION_PARAMETER.to_string(),
+            Uuid::new_v4(),
+            "opacity".to_string(),
+        ));
+
+        controller
+            .process(frame_data(solid_frame(16, 16, 0)))
+            .unwrap();
+        let output = controller
+            .process(frame_data(solid_frame(16, 16, 255)))
+            .unwrap();
+
+        assert!(mapped_value(&output) > 0.99);
+        assert_eq!(
+            controller.get_control_value(MOTION_DETECTED_PARAMETER),
+            Some(1.0)
+        );
+    }
+
+    #[test]
+    fn test_motion_outside_roi_is_ignored() {
+        let mut parameters = HashMap::new();
+        // 左半分だけを解析する
+        parameters.insert("roi".to_string(), serde_json::json!([0.0, 0.0, 0.5, 1.0]));
+        let mut controller = create_controller(parameters);
+
+        controller
+            .process(frame_data(solid_frame(16, 16, 0)))
+            .unwrap();
+
+        // 右半分だけが白くなったフレーム
+        let mut changed = solid_frame(16, 16, 0);
+        for pixel in changed.data.chunks_exact_mut(4 * 8).skip(1).step_by(2) {
+            pixel.fill(255);
+        }
+        controller.process(frame_data(changed.clone())).unwrap();
+        assert_eq!(controller.motion(), 0.0);
+
+        // 全体を解析すると右半分の変化が半分の動き量として現れる
+        controller
+            .set_parameter("roi", serde_json::json!([0.0, 0.0, 1.0, 1.0]))
+            .unwrap();
+        controller
+            .process(frame_data(solid_frame(16, 16, 0)))
+            .unwrap();
+        controller.process(frame_data(changed)).unwrap();
+        assert!((controller.motion() - 0.5).abs() < 0.01);
+    }
+
+    #[test]
+    fn test_threshold_gates_motion_detected() {
+        let mut parameters = HashMap::new();
+        parameters.insert("threshold".to_string(), Value::from(0.5));
+        let mut controller = create_controller(parameters);
+
+        controller
+            .process(frame_data(solid_frame(8, 8, 100)))
+            .unwrap();
+        controller
+            .process(frame_data(solid_frame(8, 8, 150)))
+            .unwrap();
+
+        assert!(controller.motion() > 0.1);
+        assert_eq!(
+            controller.get_control_value(MOTION_DETECTED_PARAMETER),
+            Some(0.0)
+        );
+    }
+}
@@ -0,0 +1,716 @@
+/*
+ * Constellation Studio - Professional Real-time Video Processing
+ * Copyright (c) 2025 MACHIKO LAB
+ *
+ * This program is free software: you can redistribute it and/or modify
+ * it under the terms of the GNU Affero General Public License as published by
+ * the Free Software Foundation, either version 3 of the License, or
+ * (at your option) any later version.
+ *
+ * This program is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+ * GNU Affero General Public License for more details.
+ *
+ * You should have received a copy of the GNU Affero General Public License
+ * along with this program. If not, see <https://www.gnu.org/licenses/>.
+ */
+
+use crate::controller::{apply_mappings, ControllerConfig, ControllerNode};
+use crate::{NodeProcessor, NodeProperties, ParameterDefinition, ParameterType};
+use anyhow::Result;
+use constellation_core::*;
+use futures::StreamExt;
+use serde::Deserialize;
+use serde_json::Value;
+use std::collections::HashMap;
+use std::net::SocketAddr;
+use std::sync::mpsc::{self, Receiver, Sender};
+use std::thread::JoinHandle;
+use std::time::Duration;
+use tokio::io::{AsyncRead, AsyncWrite};
+use tokio::sync::watch;
+use tokio_tungstenite::tungstenite::Message;
+use tokio_tungstenite::WebSocketStream;
+use uuid::Uuid;
+
+const DEFAULT_URL: &str = "ws://127.0.0.1:8765";
+const DEFAULT_BIND_ADDRESS: &str = "127.0.0.1:8765";
+const DEFAULT_RECONNECT_INTERVAL_MS: u64 = 500;
+const DEFAULT_MAX_RECONNECT_INTERVAL_MS: u64 = 10_000;
+
+/// WebSocketで受信した制御メッセージ
+#[derive(Debug, Clone, PartialEq)]
+pub struct WebSocketControlEvent {
+    pub parameter: String,
+    pub value: f32,
+}
+
+#[derive(Deserialize)]
+struct RawControlMessage {
+    parameter: String,
+    value: f64,
+}
+
+impl WebSocketControlEvent {
+    /// `{ "parameter": string, "value": number }` 形式のメッセージを検証して解析
+    pub fn parse(text: &str) -> Result<Self> {
+        let message: RawControlMessage = serde_json::from_str(text)
+            .map_err(|e| anyhow::anyhow!("Invalid control message: {}", e))?;
+
+        if message.parameter.trim().is_empty() {
+            anyhow::bail!("Invalid control message: empty parameter name");
+        }
+        if !message.value.is_finite() {
+            anyhow::bail!("Invalid control message: value must be finite");
+        }
+
+        Ok(Self {
+            parameter: message.parameter,
+            value: message.value as f32,
+        })
+    }
+}
+
+/// 接続方式
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum WebSocketEndpoint {
+    Client { url: String },  // 外部サーバーへ接続
+    Server { bind: String }, // 自身で待ち受け
+}
+
+/// 再接続間隔（指数バックオフ）
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+struct ReconnectBackoff {
+    initial: Duration,
+    max: Duration,
+}
+
+impl ReconnectBackoff {
+    fn next(&self, current: Duration) -> Duration {
+        (current * 2).min(self.max)
+    }
+}
+
+/// 受信用スレッド（内部でtokioランタイムを動かす）
+struct WebSocketWorker {
+    local_addr: Option<SocketAddr>,
+    shutdown: watch::Sender<bool>,
+    thread: Option<JoinHandle<()>>,
+}
+
+impl WebSocketWorker {
+    fn spawn(
+        endpoint: WebSocketEndpoint,
+        backoff: ReconnectBackoff,
+        sender: Sender<WebSocketControlEvent>,
+    ) -> Result<Self> {
+        let runtime = tokio::runtime::Builder::new_current_thread()
+            .enable_all()
+            .build()?;
+        let (shutdown, shutdown_receiver) = watch::channel(false);
+
+        // サーバーモードはバインドエラーを即座に返せるよう同期的にバインド
+        let (listener, local_addr) = match &endpoint {
+            WebSocketEndpoint::Server { bind } => {
+                let listener = std::net::TcpListener::bind(bind).map_err(|e| {
+                    anyhow::anyhow!("Failed to bind WebSocket server {}: {}", bind, e)
+                })?;
+                listener.set_nonblocking(true)?;
+                let local_addr = listener.local_addr()?;
+                tracing::info!("WebSocket controller listening on {}", local_addr);
+                (Some(listener), Some(local_addr))
+            }
+            WebSocketEndpoint::Client { .. } => (None, None),
+        };
+
+        let thread = std::thread::spawn(move || {
+            runtime.block_on(async move {
+                match (endpoint, listener) {
+                    (WebSocketEndpoint::Server { .. }, Some(listener)) => {
+                        match tokio::net::TcpListener::from_std(listener) {
+                            Ok(listener) => run_server(listener, sender, shutdown_receiver).await,
+                            Err(e) => tracing::error!("WebSocket server error: {}", e),
+                        }
+                    }
+                    (WebSocketEndpoint::Client { url }, _) => {
+                        run_client(url, backoff, sender, shutdown_receiver).await
+                    }
+                    _ => {}
+                }
+            });
+        });
+
+        Ok(Self {
+            local_addr,
+            shutdown,
+            thread: Some(thread),
+        })
+    }
+}
+
+impl Drop for WebSocketWorker {
+    fn drop(&mut self) {
+        let _ = self.shutdown.send(true);
+        if let Some(thread) = self.thread.take() {
+            let _ = thread.join();
+        }
+    }
+}
+
+/// 切断されたら指数バックオフで再接続し続ける
+async fn run_client(
+    url: String,
+    backoff: ReconnectBackoff,
+    sender: Sender<WebSocketControlEvent>,
+    mut shutdown: watch::Receiver<bool>,
+) {
+    let mut delay = backoff.initial;
+
+    loop {
+        let connection = tokio::select! {
+            result = tokio_tungstenite::connect_async(url.as_str()) => result,
+            _ = shutdown.changed() => return,
+        };
+
+        match connection {
+            Ok((stream, _)) => {
+                tracing::info!("WebSocket controller connected to {}", url);
+                delay = backoff.initial;
+                if !read_messages(stream, &sender, &mut shutdown).await {
+                    return;
+                }
+                tracing::warn!(
+                    "WebSocket controller disconnected from {}, reconnecting in {:?}",
+                    url,
+                    delay
+                );
+            }
+            Err(e) => {
+                tracing::warn!(
+                    "WebSocket controller failed to connect to {}: {}, retrying in {:?}",
+                    url,
+                    e,
+                    delay
+                );
+            }
+        }
+
+        tokio::select! {
+            _ = tokio::time::sleep(delay) => {}
+            _ = shutdown.changed() => return,
+        }
+        delay = backoff.next(delay);
+    }
+}
+
+/// 接続してきたクライアントからのメッセージを受け付ける
+async fn run_server(
+    listener: tokio::net::TcpListener,
+    sender: Sender<WebSocketControlEvent>,
+    mut shutdown: watch::Receiver<bool>,
+) {
+    loop {
+        let accepted = tokio::select! {
+            accepted = listener.accept() => accepted,
+            _ = shutdown.changed() => return,
+        };
+
+        match accepted {
+            Ok((stream, peer)) => {
+                let sender = sender.clone();
+                let mut shutdown = shutdown.clone();
+                tokio::spawn(async move {
+                    match tokio_tungstenite::accept_async(stream).await {
+                        Ok(stream) => {
+                            tracing::info!("WebSocket control client connected: {}", peer);
+                            read_messages(stream, &sender, &mut shutdown).await;
+                        }
+                        Err(e) => tracing::warn!("WebSocket handshake with {} failed: {}", peer, e),
+                    }
+                });
+            }
+            Err(e) => tracing::warn!("Failed to accept WebSocket connection: {}", e),
+        }
+    }
+}
+
+/// ストリームが閉じるまでメッセージを読む。停止要求があればfalseを返す
+async fn read_messages<S>(
+    mut stream: WebSocketStream<S>,
+    sender: &Sender<WebSocketControlEvent>,
+    shutdown: &mut watch::Receiver<bool>,
+) -> bool
+where
+    S: AsyncRead + AsyncWrite + Unpin,
+{
+    loop {
+        let message = tokio::select! {
+            message = stream.next() => message,
+            _ = shutdown.changed() => return false,
+        };
+
+        let text = match message {
+            Some(Ok(Message::Text(text))) => text,
+            Some(Ok(Message::Binary(data))) => match String::from_utf8(data) {
+                Ok(text) => text,
+                Err(_) => {
+                    tracing::warn!("Dropping non-UTF-8 WebSocket control message");
+                    continue;
+                }
+            },
+            Some(Ok(Message::Close(_))) | None => return true,
+            Some(Ok(_)) => continue,
+            Some(Err(e)) => {
+                tracing::warn!("WebSocket control stream error: {}", e);
+                return true;
+            }
+        };
+
+        match WebSocketControlEvent::parse(&text) {
+            Ok(event) => {
+                if sender.send(event).is_err() {
+                    return false;
+                }
+            }
+            Err(e) => tracing::warn!("Dropping WebSocket control message: {}", e),
+        }
+    }
+}
+
+/// WebSocketコントローラ - ブラウザ等からのJSONメッセージを制御値に変換
+pub struct WebSocketController {
+    id: Uuid,
+    config: NodeConfig,
+    properties: NodeProperties,
+    controller_config: ControllerConfig,
+
+    // 接続状態
+    current_endpoint: Option<(WebSocketEndpoint, ReconnectBackoff)>,
+    worker: Option<WebSocketWorker>,
+
+    // イベント受信
+    event_sender: Sender<WebSocketControlEvent>,
+    event_receiver: Receiver<WebSocketControlEvent>,
+
+    // 現在の値
+    control_values: HashMap<String, f32>,
+}
+
+impl WebSocketController {
+    pub fn new(id: Uuid, config: NodeConfig) -> Result<Self> {
+        let mut parameters = HashMap::new();
+
+        parameters.insert(
+            "mode".to_string(),
+            ParameterDefinition {
+                name: "Mode".to_string(),
+                parameter_type: ParameterType::Enum(vec![
+                    "client".to_string(),
+                    "server".to_string(),
+                ]),
+                default_value: Value::String("client".to_string()),
+                min_value: None,
+                max_value: None,
+                description: "Connect to a WebSocket endpoint or host one".to_string(),
+            },
+        );
+
+        parameters.insert(
+            "url".to_string(),
+            ParameterDefinition {
+                name: "URL".to_string(),
+                parameter_type: ParameterType::String,
+                default_value: Value::String(DEFAULT_URL.to_string()),
+                min_value: None,
+                max_value: None,
+                description: "WebSocket endpoint to connect to in client mode".to_string(),
+            },
+        );
+
+        parameters.insert(
+            "bind_address".to_string(),
+            ParameterDefinition {
+                name: "Bind Address".to_string(),
+                parameter_type: ParameterType::String,
+                default_value: Value::String(DEFAULT_BIND_ADDRESS.to_string()),
+                min_value: None,
+                max_value: None,
+                description: "Address to listen on in server mode".to_string(),
+            },
+        );
+
+        parameters.insert(
+            "reconnect_interval".to_string(),
+            ParameterDefinition {
+                name: "Reconnect Interval".to_string(),
+                parameter_type: ParameterType::Integer,
+                default_value: Value::from(DEFAULT_RECONNECT_INTERVAL_MS),
+                min_value: Some(Value::from(10)),
+                max_value: Some(Value::from(60_000)),
+                description: "Initial reconnect delay in milliseconds".to_string(),
+            },
+        );
+
+        parameters.insert(
+            "max_reconnect_interval".to_string(),
+            ParameterDefinition {
+                name: "Max Reconnect Interval".to_string(),
+                parameter_type: ParameterType::Integer,
+                default_value: Value::from(DEFAULT_MAX_RECONNECT_INTERVAL_MS),
+                min_value: Some(Value::from(10)),
+                max_value: Some(Value::from(300_000)),
+                description: "Upper bound for the reconnect backoff in milliseconds".to_string(),
+            },
+        );
+
+        parameters.insert(
+            "enabled".to_string(),
+            ParameterDefinition {
+                name: "Enabled".to_string(),
+                parameter_type: ParameterType::Boolean,
+                default_value: Value::Bool(true),
+                min_value: None,
+                max_value: None,
+                description: "Enable/disable WebSocket controller".to_string(),
+            },
+        );
+
+        let properties = NodeProperties {
+            id,
+            name: "WebSocket Controller".to_string(),
+            node_type: NodeType::Control(ControlType::WebSocketController),
+            input_types: vec![], // WebSocketは外部入力のみ
+            output_types: vec![ConnectionType::Control],
+            parameters,
+            enabled: true,
+        };
+
+        let (event_sender, event_receiver) = mpsc::channel();
+
+        Ok(Self {
+            id,
+            config,
+            properties,
+            controller_config: ControllerConfig::default(),
+            current_endpoint: None,
+            worker: None,
+            event_sender,
+            event_receiver,
+            control_values: HashMap::new(),
+        })
+    }
+
+    /// 外部から制御イベントを注入するための送信側を取得
+    pub fn event_sender(&self) -> Sender<WebSocketControlEvent> {
+        self.event_sender.clone()
+    }
+
+    /// サーバーモードで待ち受け中のアドレス
+    pub fn local_addr(&self) -> Option<SocketAddr> {
+        self.worker.as_ref().and_then(|worker| worker.local_addr)
+    }
+
+    /// 受信済みイベントを反映
+    fn drain_events(&mut self) {
+        while let Ok(event) = self.event_receiver.try_recv() {
+            self.control_values.insert(event.parameter, event.value);
+        }
+    }
+
+    fn string_parameter(&self, key: &str, default: &str) -> String {
+        self.get_parameter(key)
+            .and_then(|v| v.as_str().map(str::to_string))
+            .unwrap_or_else(|| default.to_string())
+    }
+
+    /// パラメータを更新
+    fn update_parameters(&mut self) -> Result<()> {
+        self.controller_config.enabled = self
+            .get_parameter("enabled")
+            .and_then(|v| v.as_bool())
+            .unwrap_or(true);
+
+        let endpoint = match self.string_parameter("mode", "client").as_str() {
+            "client" => WebSocketEndpoint::Client {
+                url: self.string_parameter("url", DEFAULT_URL),
+            },
+            "server" => WebSocketEndpoint::Server {
+                bind: self.string_parameter("bind_address", DEFAULT_BIND_ADDRESS),
+            },
+            other => anyhow::bail!("Unknown WebSocket controller mode: {}", other),
+        };
+
+        let initial = self
+            .get_parameter("reconnect_interval")
+            .and_then(|v| v.as_u64())
+            .unwrap_or(DEFAULT_RECONNECT_INTERVAL_MS)
+            .max(1);
+        let max = self
+            .get_parameter("max_reconnect_interval")
+            .and_then(|v| v.as_u64())
+            .unwrap_or(DEFAULT_MAX_RECONNECT_INTERVAL_MS)
+            .max(initial);
+        let backoff = ReconnectBackoff {
+            initial: Duration::from_millis(initial),
+            max: Duration::from_millis(max),
+        };
+
+        let desired = self
+            .controller_config
+            .enabled
+            .then_some((endpoint, backoff));
+
+        if desired != self.current_endpoint {
+            // 既存の接続を閉じてから張り直す
+            self.worker = None;
+            self.current_endpoint = desired.clone();
+            if let Some((endpoint, backoff)) = desired {
+                self.worker = Some(WebSocketWorker::spawn(
+                    endpoint,
+                    backoff,
+                    self.event_sender.clone(),
+                )?);
+            }
+        }
+
+        Ok(())
+    }
+}
+
+impl NodeProcessor for WebSocketController {
+    fn process(&mut self, input: FrameData) -> Result<FrameData> {
+        // パラメータを更新
+        self.update_parameters()?;
+
+        // 無効なら入力をそのまま通す
+        if !self.controller_config.enabled {
+            return Ok(input);
+        }
+
+        // 受信済みイベントを反映
+        self.drain_events();
+
+        // 制御コマンドを生成
+        let control_commands = self.generate_control_commands();
+
+        let control_data = if !control_commands.is_empty() {
+            Some(ControlData::MultiControl {
+                commands: control_commands,
+            })
+        } else {
+            input.control_data
+        };
+
+        Ok(FrameData {
+            control_data,
+            ..input
+        })
+    }
+
+    fn get_properties(&self) -> NodeProperties {
+        self.properties.clone()
+    }
+
+    fn set_parameter(&mut self, key: &str, value: Value) -> Result<()> {
+        self.config.parameters.insert(key.to_string(), value);
+        Ok(())
+    }
+
+    fn get_parameter(&self, key: &str) -> Option<Value> {
+        self.config.parameters.get(key).cloned()
+    }
+}
+
+impl ControllerNode for WebSocketController {
+    fn add_mapping(&mut self, mapping: ControlMapping) {
+        self.controller_config.mappings.push(mapping);
+    }
+
+    fn remove_mapping(&mut self, source_parameter: &str) {
+        self.controller_config
+            .mappings
+            .retain(|m| m.source_parameter != source_parameter);
+    }
+
+    fn get_control_value(&self, parameter: &str) -> Option<f32> {
+        self.control_values.get(parameter).copied()
+    }
+
+    fn generate_control_commands(&self) -> Vec<ControlCommand> {
+        apply_mappings(&self.controller_config.mappings, &self.control_values)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use futures::SinkExt;
+    use std::time::Instant;
+
+    fn create_controller(parameters: HashMap<String, Value>) -> WebSocketController {
+        WebSocketController::new(Uuid::new_v4(), NodeConfig { parameters }).unwrap()
+    }
+
+    /// 条件を満たすまでprocessを繰り返す
+    fn process_until(
+        controller: &mut WebSocketController,
+        condition: impl Fn(&WebSocketController) -> bool,
+    ) -> FrameData {
+        let deadline = Instant::now() + Duration::from_secs(5);
+        loop {
+            let output = controller.process(FrameData::empty()).unwrap();
+            if condition(controller) || Instant::now() > deadline {
+                return output;
+            }
+            std::thread::sleep(Duration::from_millis(5));
+        }
+    }
+
+    fn float_command(commands: &[ControlCommand], parameter: &str) -> Option<f32> {
+        commands
+            .iter()
+            .find(|c| c.parameter_name == parameter)
+            .and_then(|c| match c.value {
+                ParameterValue::Float(v) => Some(v),
+                _ => None,
+            })
+    }
+
+    #[test]
+    fn test_parse_validates_schema() {
+        assert_eq!(
+            WebSocketControlEvent::parse(r#"{"parameter": "fader", "value": 0.5}"#).unwrap(),
+            WebSocketControlEvent {
+                parameter: "fader".to_string(),
+                value: 0.5
+            }
+        );
+        assert!(WebSocketControlEvent::parse(r#"{"parameter": "fader"}"#).is_err());
+        assert!(WebSocketControlEvent::parse(r#"{"parameter": "fader", "value": "x"}"#).is_err());
+        assert!(WebSocketControlEvent::parse(r#"{"parameter": "", "value": 1}"#).is_err());
+        assert!(WebSocketControlEvent::parse("not json").is_err());
+    }
+
+    #[test]
+    fn test_client_receives_messages_and_reconnects() {
+        let runtime = tokio::runtime::Runtime::new().unwrap();
+        let listener = runtime
+            .block_on(tokio::net::TcpListener::bind("127.0.0.1:0"))
+            .unwrap();
+        let address = listener.local_addr().unwrap();
+
+        // 1回目の接続で数件送って切断し、再接続後にもう1件送るサーバー
+        runtime.spawn(async move {
+            let (stream, _) = listener.accept().await.unwrap();
+            let mut ws = tokio_tungstenite::accept_async(stream).await.unwrap();
+            for text in [
+                r#"{"parameter": "opacity", "value": 0.25}"#,
+                r#"{"parameter": "opacity"}"#,
+                r#"{"parameter": "zoom", "value": 2}"#,
+            ] {
+                ws.send(Message::Text(text.to_string())).await.unwrap();
+            }
+            ws.close(None).await.unwrap();
+
+            let (stream, _) = listener.accept().await.unwrap();
+            let mut ws = tokio_tungstenite::accept_async(stream).await.unwrap();
+            ws.send(Message::Text(
+                r#"{"parameter": "opacity", "value": 0.75}"#.to_string(),
+            ))
+            .await
+            .unwrap();
+            // クライアントが切断するまで接続を保持
+            while ws.next().await.is_some() {}
+        });
+
+        let mut parameters = HashMap::new();
+        parameters.insert(
+            "url".to_string(),
+            Value::String(format!("ws://{}", address)),
+        );
+        parameters.insert("reconnect_interval".to_string(), Value::from(20));
+        let mut controller = create_controller(parameters);
+
+        let target = Uuid::new_v4();
+        controller.add_mapping(ControlMapping::new(
+            "opacity".to_string(),
+            target,
+            "opacity".to_string(),
+        ));
+        let mut zoom = ControlMapping::new("zoom".to_string(), target, "scale".to_string());
+        zoom.target_range = (0.0, 4.0);
+        zoom.value_range = (0.0, 4.0);
+        controller.add_mapping(zoom);
+
+        let output = process_until(&mut controller, |c| c.get_control_value("zoom").is_some());
+        assert_eq!(controller.get_control_value("opacity"), Some(0.25));
+        assert_eq!(controller.get_control_value("zoom"), Some(2.0));
+        match output.control_data {
+            Some(ControlData::MultiControl { commands }) => {
+                assert_eq!(commands.len(), 2);
+                assert!(commands.iter().all(|c| c.target_node_id == target));
+                assert_eq!(float_command(&commands, "opacity"), Some(0.25));
+                assert_eq!(float_command(&commands, "scale"), Some(2.0));
+            }
+            other => panic!("expected MultiControl, got {:?}", other),
+        }
+
+        // 切断後に再接続して次の値を受信する
+        let output = process_until(&mut controller, |c| {
+            c.get_control_value("opacity") == Some(0.75)
+        });
+        assert_eq!(controller.get_control_value("opacity"), Some(0.75));
+        match output.control_data {
+            Some(ControlData::MultiControl { commands }) => {
+                assert_eq!(float_command(&commands, "opacity"), Some(0.75));
+            }
+            other => panic!("expected MultiControl, got {:?}", other),
+        }
+
+        // ワーカーを止めてからランタイムを破棄
+        drop(controller);
+    }
+
+    #[test]
+    fn test_server_mode_accepts_clients() {
+        let mut parameters = HashMap::new();
+        parameters.insert("mode".to_string(), Value::String("server".to_string()));
+        parameters.insert(
+            "bind_address".to_string(),
+            Value::String("127.0.0.1:0".to_string()),
+        );
+        let mut controller = create_controller(parameters);
+        controller.process(FrameData::empty()).unwrap();
+        let address = controller.local_addr().expect("server should be listening");
+
+        let runtime = tokio::runtime::Runtime::new().unwrap();
+        runtime.spawn(async move {
+            let (mut ws, _) = tokio_tungstenite::connect_async(format!("ws://{}", address))
+                .await
+                .unwrap();
+            ws.send(Message::Text(
+                r#"{"parameter": "gain", "value": 0.6}"#.to_string(),
+            ))
+            .await
+            .unwrap();
+            while ws.next().await.is_some() {}
+        });
+
+        process_until(&mut controller, |c| c.get_control_value("gain").is_some());
+        assert_eq!(controller.get_control_value("gain"), Some(0.6));
+    }
+
+    #[test]
+    fn test_backoff_doubles_up_to_max() {
+        let backoff = ReconnectBackoff {
+            initial: Duration::from_millis(100),
+            max: Duration::from_millis(350),
+        };
+        let second = backoff.next(backoff.initial);
+        let third = backoff.next(second);
+        assert_eq!(second, Duration::from_millis(200));
+        assert_eq!(third, Duration::from_millis(350));
+        assert_eq!(backoff.next(third), Duration::from_millis(350));
+    }
+}
@@ -37,10 +37,10 @@ impl ColorCorrectionNode {
             ParameterDefinition {
                 name: "Brightness".to_string(),
                 parameter_type: ParameterType::Float,
-                default_value: Value::from(1.0),
-                min_value: Some(Value::from(0.0)),
-                max_value: Some(Value::from(3.0)),
-                description: "Brightness adjustment".to_string(),
+                default_value: Value::from(0.0),
+                min_value: Some(Value::from(-1.0)),
+                max_value: Some(Value::from(1.0)),
+                description: "Additive brightness offset".to_string(),
             },
         );
         parameters.insert(
@@ -51,7 +51,7 @@ impl ColorCorrectionNode {
                 default_value: Value::from(1.0),
                 min_value: Some(Value::from(0.0)),
                 max_value: Some(Value::from(3.0)),
-                description: "Contrast adjustment".to_string(),
+                description: "Contrast around mid-gray".to_string(),
             },
         );
         parameters.insert(
@@ -62,7 +62,7 @@ impl ColorCorrectionNode {
                 default_value: Value::from(1.0),
                 min_value: Some(Value::from(0.0)),
                 max_value: Some(Value::from(3.0)),
-                description: "Saturation adjustment".to_string(),
+                description: "Luma-preserving saturation".to_string(),
             },
         );
         parameters.insert(
@@ -84,6 +84,7 @@ impl ColorCorrectionNode {
             input_types: vec![ConnectionType::RenderData],
             output_types: vec![ConnectionType::RenderData],
             parameters,
+            enabled: true,
         };
 
         Ok(Self {
@@ -107,7 +108,7 @@ impl NodeProcessor for ColorCorrectionNode {
             let brightness = self
                 .get_parameter("brightness")
                 .and_then(|v| v.as_f64())
-                .unwrap_or(1.0) as f32;
+                .unwrap_or(0.0) as f32;
             let contrast = self
                 .get_parameter("contrast")
                 .and_then(|v| v.as_f64())
@@ -139,32 +140,41 @@ impl NodeProcessor for ColorCorrectionNode {
     fn get_parameter(&self, key: &str) -> Option<Value> {
         self.config.parameters.get(key).cloned()
     }
+
+    fn input_video_format(&self) -> Option<VideoFormat> {
+        Some(VideoFormat::Rgba8)
+    }
+
+    // 16-bit frames are corrected at full precision instead of being truncated
+    fn accepts_video_format(&self, format: &VideoFormat) -> bool {
+        matches!(format, VideoFormat::Rgba8 | VideoFormat::Rgba16)
+    }
 }
 
 impl ColorCorrectionNode {
+    // Control values arrive as a continuous stream, so out-of-range values are
+    // clamped here; the pipeline has already applied its own policy upstream
     fn process_control_data(&mut self, control_data: &ControlData) -> Result<()> {
         match control_data {
             ControlData::Parameter {
                 target_node_id,
                 parameter_name,
                 value,
-            } => {
-                if *target_node_id == self.id {
-                    let json_value = match value {
-                        ParameterValue::Float(f) => Value::from(*f),
-                        ParameterValue::Integer(i) => Value::from(*i),
-                        ParameterValue::Boolean(b) => Value::Bool(*b),
-                        ParameterValue::String(s) => Value::String(s.clone()),
-                        ParameterValue::Color(c) => Value::Array(vec![
-                            Value::from(c[0]),
-                            Value::from(c[1]),
-                            Value::from(c[2]),
-                            Value::from(c[3]),
-                        ]),
-                        _ => return Ok(()), // Skip unsupported types
-                    };
-                    self.set_parameter(parameter_name, json_value)?;
-                }
+            } if *target_node_id == self.id => {
+                let json_value = match value {
+                    ParameterValue::Float(f) => Value::from(*f),
+                    ParameterValue::Integer(i) => Value::from(*i),
+                    ParameterValue::Boolean(b) => Value::Bool(*b),
+                    ParameterValue::String(s) => Value::String(s.clone()),
+                    ParameterValue::Color(c) => Value::Array(vec![
+                        Value::from(c[0]),
+                        Value::from(c[1]),
+                        Value::from(c[2]),
+                        Value::from(c[3]),
+                    ]),
+                    _ => return Ok(()), // Skip unsupported types
+                };
+                self.set_validated_parameter(parameter_name, json_value, OutOfRangePolicy::Clamp)?;
             }
             ControlData::MultiControl { commands } => {
                 for command in commands {
@@ -182,7 +192,11 @@ impl ColorCorrectionNode {
                             ]),
                             _ => continue, // Skip unsupported types
                         };
-                        self.set_parameter(&command.parameter_name, json_value)?;
+                        self.set_validated_parameter(
+                            &command.parameter_name,
+                            json_value,
+                            OutOfRangePolicy::Clamp,
+                        )?;
                     }
                 }
             }
@@ -197,46 +211,14 @@ impl ColorCorrectionNode {
         brightness: f32,
         contrast: f32,
         saturation: f32,
-        hue: f32,
+        _hue: f32,
     ) {
-        let pixel_count = (frame.width * frame.height) as usize;
-        let bytes_per_pixel = match frame.format {
-            VideoFormat::Rgba8 | VideoFormat::Bgra8 => 4,
-            VideoFormat::Rgb8 | VideoFormat::Bgr8 => 3,
-            _ => 4,
+        let adjustment = ColorAdjustment {
+            brightness,
+            contrast,
+            saturation,
         };
-
-        for i in 0..pixel_count {
-            let pixel_offset = i * bytes_per_pixel;
-            if pixel_offset + 2 < frame.data.len() {
-                let r = frame.data[pixel_offset] as f32 / 255.0;
-                let g = frame.data[pixel_offset + 1] as f32 / 255.0;
-                let b = frame.data[pixel_offset + 2] as f32 / 255.0;
-
-                let (r_adj, g_adj, b_adj) =
-                    self.adjust_pixel((r, g, b), brightness, contrast, saturation, hue);
-
-                frame.data[pixel_offset] = (r_adj * 255.0).clamp(0.0, 255.0) as u8;
-                frame.data[pixel_offset + 1] = (g_adj * 255.0).clamp(0.0, 255.0) as u8;
-                frame.data[pixel_offset + 2] = (b_adj * 255.0).clamp(0.0, 255.0) as u8;
-            }
-        }
-    }
-
-    fn adjust_pixel(
-        &self,
-        rgb: (f32, f32, f32),
-        brightness: f32,
-        contrast: f32,
-        _saturation: f32,
-        _hue: f32,
-    ) -> (f32, f32, f32) {
-        let (r, g, b) = rgb;
-        let r_adj = ((r - 0.5) * contrast + 0.5) * brightness;
-        let g_adj = ((g - 0.5) * contrast + 0.5) * brightness;
-        let b_adj = ((b - 0.5) * contrast + 0.5) * brightness;
-
-        (r_adj, g_adj, b_adj)
+        adjustment.apply(frame);
     }
 }
 
@@ -283,6 +265,7 @@ impl BlurNode {
             input_types: vec![ConnectionType::RenderData],
             output_types: vec![ConnectionType::RenderData],
             parameters,
+            enabled: true,
         };
 
         Ok(Self {
@@ -321,6 +304,10 @@ impl NodeProcessor for BlurNode {
     fn get_parameter(&self, key: &str) -> Option<Value> {
         self.config.parameters.get(key).cloned()
     }
+
+    fn input_video_format(&self) -> Option<VideoFormat> {
+        Some(VideoFormat::Rgba8)
+    }
 }
 
 impl BlurNode {
@@ -328,75 +315,97 @@ impl BlurNode {
         if radius <= 0.0 {
             return Ok(());
         }
+        let Some(layout) = packed_layout(frame) else {
+            return Ok(());
+        };
 
-        let width = frame.width as usize;
-        let height = frame.height as usize;
-        let channels = 4; // RGBA
-
-        // Simple box blur implementation
         let blur_radius = (radius.round() as usize).max(1);
-        let mut temp_data = frame.data.clone();
-
-        // Horizontal pass
-        for y in 0..height {
-            for x in 0..width {
-                let mut r_sum = 0.0f32;
-                let mut g_sum = 0.0f32;
-                let mut b_sum = 0.0f32;
-                let mut count = 0;
-
-                for dx in 0..=(blur_radius * 2) {
-                    let sample_x = x as i32 + dx as i32 - blur_radius as i32;
-                    if sample_x >= 0 && sample_x < width as i32 {
-                        let idx = (y * width + sample_x as usize) * channels;
-                        r_sum += frame.data[idx] as f32;
-                        g_sum += frame.data[idx + 1] as f32;
-                        b_sum += frame.data[idx + 2] as f32;
-                        count += 1;
+        frame.data = box_blur(
+            &frame.data,
+            frame.width as usize,
+            frame.height as usize,
+            &layout,
+            blur_radius,
+        );
+
+        Ok(())
+    }
+}
+
+/// Pixel layout of a packed RGB-family frame whose data covers every pixel
+fn packed_layout(frame: &VideoFrame) -> Option<PixelLayout> {
+    let layout = frame.format.pixel_layout()?;
+    let required = frame.width as usize * frame.height as usize * layout.bytes_per_pixel;
+    (frame.data.len() >= required).then_some(layout)
+}
+
+/// Separable box blur over the color channels of packed RGB-family data
+/// (alpha is left unchanged)
+fn box_blur(
+    data: &[u8],
+    width: usize,
+    height: usize,
+    layout: &PixelLayout,
+    radius: usize,
+) -> Vec<u8> {
+    let bytes_per_pixel = layout.bytes_per_pixel;
+    let color_offsets = layout.color_offsets();
+    let mut temp_data = data.to_vec();
+    let mut output = data.to_vec();
+
+    // Horizontal pass
+    for y in 0..height {
+        for x in 0..width {
+            let mut sums = [0.0f32; 3];
+            let mut count = 0;
+
+            for dx in 0..=(radius * 2) {
+                let sample_x = x as i32 + dx as i32 - radius as i32;
+                if sample_x >= 0 && sample_x < width as i32 {
+                    let idx = (y * width + sample_x as usize) * bytes_per_pixel;
+                    for (sum, c) in sums.iter_mut().zip(color_offsets) {
+                        *sum += data[idx + c] as f32;
                     }
+                    count += 1;
                 }
+            }
 
-                if count > 0 {
-                    let idx = (y * width + x) * channels;
-                    temp_data[idx] = (r_sum / count as f32) as u8;
-                    temp_data[idx + 1] = (g_sum / count as f32) as u8;
-                    temp_data[idx + 2] = (b_sum / count as f32) as u8;
-                    // Keep alpha unchanged
+            if count > 0 {
+                let idx = (y * width + x) * bytes_per_pixel;
+                for (sum, c) in sums.iter().zip(color_offsets) {
+                    temp_data[idx + c] = (sum / count as f32) as u8;
                 }
             }
         }
+    }
 
-        // Vertical pass
-        for y in 0..height {
-            for x in 0..width {
-                let mut r_sum = 0.0f32;
-                let mut g_sum = 0.0f32;
-                let mut b_sum = 0.0f32;
-                let mut count = 0;
-
-                for dy in 0..=(blur_radius * 2) {
-                    let sample_y = y as i32 + dy as i32 - blur_radius as i32;
-                    if sample_y >= 0 && sample_y < height as i32 {
-                        let idx = (sample_y as usize * width + x) * channels;
-                        r_sum += temp_data[idx] as f32;
-                        g_sum += temp_data[idx + 1] as f32;
-                        b_sum += temp_data[idx + 2] as f32;
-                        count += 1;
+    // Vertical pass
+    for y in 0..height {
+        for x in 0..width {
+            let mut sums = [0.0f32; 3];
+            let mut count = 0;
+
+            for dy in 0..=(radius * 2) {
+                let sample_y = y as i32 + dy as i32 - radius as i32;
+                if sample_y >= 0 && sample_y < height as i32 {
+                    let idx = (sample_y as usize * width + x) * bytes_per_pixel;
+                    for (sum, c) in sums.iter_mut().zip(color_offsets) {
+                        *sum += temp_data[idx + c] as f32;
                     }
+                    count += 1;
                 }
+            }
 
-                if count > 0 {
-                    let idx = (y * width + x) * channels;
-                    frame.data[idx] = (r_sum / count as f32) as u8;
-                    frame.data[idx + 1] = (g_sum / count as f32) as u8;
-                    frame.data[idx + 2] = (b_sum / count as f32) as u8;
-                    // Keep alpha unchanged
+            if count > 0 {
+                let idx = (y * width + x) * bytes_per_pixel;
+                for (sum, c) in sums.iter().zip(color_offsets) {
+                    output[idx + c] = (sum / count as f32) as u8;
                 }
             }
         }
-
-        Ok(())
     }
+
+    output
 }
 
 pub struct SharpenNode {
@@ -415,10 +424,21 @@ impl SharpenNode {
                 parameter_type: ParameterType::Float,
                 default_value: Value::from(1.0),
                 min_value: Some(Value::from(0.0)),
-                max_value: Some(Value::from(5.0)),
+                max_value: Some(Value::from(3.0)),
                 description: "Sharpening strength".to_string(),
             },
         );
+        parameters.insert(
+            "radius".to_string(),
+            ParameterDefinition {
+                name: "Radius".to_string(),
+                parameter_type: ParameterType::Integer,
+                default_value: Value::from(1),
+                min_value: Some(Value::from(1)),
+                max_value: Some(Value::from(10)),
+                description: "Unsharp mask blur radius in pixels".to_string(),
+            },
+        );
 
         let properties = NodeProperties {
             id,
@@ -427,6 +447,7 @@ impl SharpenNode {
             input_types: vec![ConnectionType::RenderData],
             output_types: vec![ConnectionType::RenderData],
             parameters,
+            enabled: true,
         };
 
         Ok(Self {
@@ -445,9 +466,17 @@ impl NodeProcessor for SharpenNode {
                 .parameters
                 .get("strength")
                 .and_then(|v| v.as_f64())
-                .unwrap_or(1.0) as f32;
+                .unwrap_or(1.0)
+                .clamp(0.0, 3.0) as f32;
+            let radius = self
+                .config
+                .parameters
+                .get("radius")
+                .and_then(|v| v.as_u64())
+                .unwrap_or(1)
+                .clamp(1, 10) as usize;
 
-            self.apply_sharpen(video_data, strength)?;
+            self.apply_sharpen(video_data, strength, radius)?;
         }
 
         Ok(input)
@@ -465,64 +494,260 @@ impl NodeProcessor for SharpenNode {
     fn get_parameter(&self, key: &str) -> Option<Value> {
         self.config.parameters.get(key).cloned()
     }
+
+    fn input_video_format(&self) -> Option<VideoFormat> {
+        Some(VideoFormat::Rgba8)
+    }
 }
 
 impl SharpenNode {
-    fn apply_sharpen(&self, frame: &mut VideoFrame, strength: f32) -> Result<()> {
+    /// Unsharp mask: original + strength * (original - blurred)
+    fn apply_sharpen(&self, frame: &mut VideoFrame, strength: f32, radius: usize) -> Result<()> {
         if strength <= 0.0 {
             return Ok(());
         }
 
-        let width = frame.width as usize;
-        let height = frame.height as usize;
-        let channels = 4; // RGBA
-
-        let mut result_data = frame.data.clone();
-
-        // Unsharp mask kernel (3x3 sharpening kernel)
-        let kernel = [
-            0.0,
-            -strength,
-            0.0,
-            -strength,
-            1.0 + 4.0 * strength,
-            -strength,
-            0.0,
-            -strength,
-            0.0,
-        ];
-
-        for y in 1..(height - 1) {
-            for x in 1..(width - 1) {
-                let mut r_sum = 0.0f32;
-                let mut g_sum = 0.0f32;
-                let mut b_sum = 0.0f32;
-
-                // Apply kernel
-                for ky in 0..3 {
-                    for kx in 0..3 {
-                        let sample_x = x + kx - 1;
-                        let sample_y = y + ky - 1;
-                        let idx = (sample_y * width + sample_x) * channels;
-                        let kernel_val = kernel[ky * 3 + kx];
-
-                        r_sum += frame.data[idx] as f32 * kernel_val;
-                        g_sum += frame.data[idx + 1] as f32 * kernel_val;
-                        b_sum += frame.data[idx + 2] as f32 * kernel_val;
-                    }
-                }
+        let Some(layout) = packed_layout(frame) else {
+            return Ok(());
+        };
+        let blurred = box_blur(
+            &frame.data,
+            frame.width as usize,
+            frame.height as usize,
+            &layout,
+            radius,
+        );
+
+        for (pixel, blurred_pixel) in frame
+            .data
+            .chunks_exact_mut(layout.bytes_per_pixel)
+            .zip(blurred.chunks_exact(layout.bytes_per_pixel))
+        {
+            for c in layout.color_offsets() {
+                let original = pixel[c] as f32;
+                let detail = original - blurred_pixel[c] as f32;
+                pixel[c] = (original + strength * detail).round().clamp(0.0, 255.0) as u8;
+            }
+            // Keep alpha unchanged
+        }
+
+        Ok(())
+    }
+}
+
+pub struct GrayscaleNode {
+    id: Uuid,
+    config: NodeConfig,
+    properties: NodeProperties,
+}
+
+impl GrayscaleNode {
+    pub fn new(id: Uuid, config: NodeConfig) -> Result<Self> {
+        let mut parameters = HashMap::new();
+        parameters.insert(
+            "amount".to_string(),
+            ParameterDefinition {
+                name: "Amount".to_string(),
+                parameter_type: ParameterType::Float,
+                default_value: Value::from(1.0),
+                min_value: Some(Value::from(0.0)),
+                max_value: Some(Value::from(1.0)),
+                description: "Desaturation amount (1.0 is fully grayscale)".to_string(),
+            },
+        );
+
+        let properties = NodeProperties {
+            id,
+            name: "Grayscale".to_string(),
+            node_type: NodeType::Effect(EffectType::Grayscale),
+            input_types: vec![ConnectionType::RenderData],
+            output_types: vec![ConnectionType::RenderData],
+            parameters,
+            enabled: true,
+        };
+
+        Ok(Self {
+            id,
+            config,
+            properties,
+        })
+    }
+}
+
+impl NodeProcessor for GrayscaleNode {
+    fn process(&mut self, mut input: FrameData) -> Result<FrameData> {
+        if let Some(RenderData::Raster2D(ref mut video_data)) = input.render_data {
+            let amount = self
+                .config
+                .parameters
+                .get("amount")
+                .and_then(|v| v.as_f64())
+                .unwrap_or(1.0)
+                .clamp(0.0, 1.0) as f32;
+
+            // BT.709 luma with channel order taken from the frame format,
+            // so RGBA and BGRA sources are converted in place
+            ColorAdjustment {
+                saturation: 1.0 - amount,
+                ..Default::default()
+            }
+            .apply(video_data);
+        }
+
+        Ok(input)
+    }
+
+    fn get_properties(&self) -> NodeProperties {
+        self.properties.clone()
+    }
+
+    fn set_parameter(&mut self, key: &str, value: Value) -> Result<()> {
+        self.config.parameters.insert(key.to_string(), value);
+        Ok(())
+    }
+
+    fn get_parameter(&self, key: &str) -> Option<Value> {
+        self.config.parameters.get(key).cloned()
+    }
+}
+
+pub struct DeinterlaceNode {
+    id: Uuid,
+    config: NodeConfig,
+    properties: NodeProperties,
+}
+
+impl DeinterlaceNode {
+    pub fn new(id: Uuid, config: NodeConfig) -> Result<Self> {
+        let mut parameters = HashMap::new();
+        parameters.insert(
+            "mode".to_string(),
+            ParameterDefinition {
+                name: "Mode".to_string(),
+                parameter_type: ParameterType::Enum(vec!["Blend".to_string(), "Bob".to_string()]),
+                default_value: Value::String("Blend".to_string()),
+                min_value: None,
+                max_value: None,
+                description:
+                    "Blend averages both fields; Bob rebuilds the frame from the first field"
+                        .to_string(),
+            },
+        );
+        parameters.insert(
+            "field_order".to_string(),
+            ParameterDefinition {
+                name: "Field Order".to_string(),
+                parameter_type: ParameterType::Enum(vec![
+                    "Top Field First".to_string(),
+                    "Bottom Field First".to_string(),
+                ]),
+                default_value: Value::String("Top Field First".to_string()),
+                min_value: None,
+                max_value: None,
+                description: "Which field of the source was captured first".to_string(),
+            },
+        );
+
+        let properties = NodeProperties {
+            id,
+            name: "Deinterlace".to_string(),
+            node_type: NodeType::Effect(EffectType::Deinterlace),
+            input_types: vec![ConnectionType::RenderData],
+            output_types: vec![ConnectionType::RenderData],
+            parameters,
+            enabled: true,
+        };
+
+        Ok(Self {
+            id,
+            config,
+            properties,
+        })
+    }
+
+    fn string_parameter(&self, key: &str) -> Option<&str> {
+        self.config.parameters.get(key).and_then(|v| v.as_str())
+    }
+}
 
-                let idx = (y * width + x) * channels;
-                result_data[idx] = r_sum.clamp(0.0, 255.0) as u8;
-                result_data[idx + 1] = g_sum.clamp(0.0, 255.0) as u8;
-                result_data[idx + 2] = b_sum.clamp(0.0, 255.0) as u8;
-                // Keep alpha unchanged
+impl NodeProcessor for DeinterlaceNode {
+    fn process(&mut self, mut input: FrameData) -> Result<FrameData> {
+        if let Some(RenderData::Raster2D(ref mut video_data)) = input.render_data {
+            let bob = self.string_parameter("mode") == Some("Bob");
+            let top_field_first =
+                self.string_parameter("field_order") != Some("Bottom Field First");
+
+            if let Some(layout) = packed_layout(video_data) {
+                let row_bytes = video_data.width as usize * layout.bytes_per_pixel;
+                let height = video_data.height as usize;
+                let data = &mut video_data.data[..row_bytes * height];
+                if bob {
+                    bob_deinterlace(data, row_bytes, height, top_field_first);
+                } else {
+                    blend_deinterlace(data, row_bytes, height);
+                }
             }
         }
 
-        frame.data = result_data;
+        Ok(input)
+    }
+
+    fn get_properties(&self) -> NodeProperties {
+        self.properties.clone()
+    }
+
+    fn set_parameter(&mut self, key: &str, value: Value) -> Result<()> {
+        self.config.parameters.insert(key.to_string(), value);
         Ok(())
     }
+
+    fn get_parameter(&self, key: &str) -> Option<Value> {
+        self.config.parameters.get(key).cloned()
+    }
+}
+
+/// Keep the lines of the first field and rebuild the other field by averaging
+/// the kept lines above and below
+fn bob_deinterlace(data: &mut [u8], row_bytes: usize, height: usize, top_field_first: bool) {
+    if height < 2 {
+        return;
+    }
+    let kept_parity = if top_field_first { 0 } else { 1 };
+
+    for y in (0..height).filter(|y| y % 2 != kept_parity) {
+        let above = y.checked_sub(1);
+        let below = (y + 1 < height).then_some(y + 1);
+        let (first, second) = match (above, below) {
+            (Some(above), Some(below)) => (above, below),
+            (Some(line), None) | (None, Some(line)) => (line, line),
+            (None, None) => continue,
+        };
+
+        for x in 0..row_bytes {
+            let sum = data[first * row_bytes + x] as u16 + data[second * row_bytes + x] as u16;
+            data[y * row_bytes + x] = sum.div_ceil(2) as u8;
+        }
+    }
+}
+
+/// Vertical [1, 2, 1] filter that mixes every line with its neighbours from the
+/// opposite field, removing combing at the cost of some vertical detail
+fn blend_deinterlace(data: &mut [u8], row_bytes: usize, height: usize) {
+    if height < 2 {
+        return;
+    }
+
+    let source = data.to_vec();
+    for y in 0..height {
+        let above = y.saturating_sub(1);
+        let below = (y + 1).min(height - 1);
+        for x in 0..row_bytes {
+            let sum = source[above * row_bytes + x] as u16
+                + 2 * source[y * row_bytes + x] as u16
+                + source[below * row_bytes + x] as u16;
+            data[y * row_bytes + x] = ((sum + 2) / 4) as u8;
+        }
+    }
 }
 
 pub struct TransformNode {
@@ -567,6 +792,17 @@ impl TransformNode {
                 description: "Rotation angle in degrees".to_string(),
             },
         );
+        parameters.insert(
+            "pivot".to_string(),
+            ParameterDefinition {
+                name: "Pivot".to_string(),
+                parameter_type: ParameterType::Vector2,
+                default_value: Value::Array(vec![Value::from(0.5), Value::from(0.5)]),
+                min_value: Some(Value::Array(vec![Value::from(0.0), Value::from(0.0)])),
+                max_value: Some(Value::Array(vec![Value::from(1.0), Value::from(1.0)])),
+                description: "Pivot point normalized to the image size (X, Y)".to_string(),
+            },
+        );
 
         let properties = NodeProperties {
             id,
@@ -575,6 +811,7 @@ impl TransformNode {
             input_types: vec![ConnectionType::RenderData],
             output_types: vec![ConnectionType::RenderData],
             parameters,
+            enabled: true,
         };
 
         Ok(Self {
@@ -586,7 +823,24 @@ impl TransformNode {
 }
 
 impl NodeProcessor for TransformNode {
-    fn process(&mut self, input: FrameData) -> Result<FrameData> {
+    fn process(&mut self, mut input: FrameData) -> Result<FrameData> {
+        if let Some(RenderData::Raster2D(ref mut frame)) = input.render_data {
+            let mut transform = self.transform();
+
+            // Transform control data overrides the static parameters
+            if let Some(ControlData::Transform {
+                position,
+                rotation,
+                scale,
+                ..
+            }) = &input.control_data
+            {
+                transform.apply_control(position.as_ref(), rotation.as_ref(), scale.as_ref());
+            }
+
+            *frame = transform.apply(frame);
+        }
+
         Ok(input)
     }
 
@@ -602,6 +856,36 @@ impl NodeProcessor for TransformNode {
     fn get_parameter(&self, key: &str) -> Option<Value> {
         self.config.parameters.get(key).cloned()
     }
+
+    fn input_video_format(&self) -> Option<VideoFormat> {
+        Some(VideoFormat::Rgba8)
+    }
+}
+
+impl TransformNode {
+    fn transform(&self) -> Transform2D {
+        let defaults = Transform2D::default();
+        Transform2D {
+            translation: self.vec2_parameter("position", defaults.translation),
+            rotation_degrees: self
+                .get_parameter("rotation")
+                .and_then(|v| v.as_f64())
+                .map(|v| v as f32)
+                .unwrap_or(defaults.rotation_degrees),
+            scale: self.vec2_parameter("scale", defaults.scale),
+            pivot: self.vec2_parameter("pivot", defaults.pivot),
+        }
+    }
+
+    fn vec2_parameter(&self, key: &str, default: [f32; 2]) -> [f32; 2] {
+        match self.get_parameter(key).as_ref().and_then(|v| v.as_array()) {
+            Some(values) if values.len() >= 2 => [
+                values[0].as_f64().map(|v| v as f32).unwrap_or(default[0]),
+                values[1].as_f64().map(|v| v as f32).unwrap_or(default[1]),
+            ],
+            _ => default,
+        }
+    }
 }
 
 pub struct CompositeNode {
@@ -642,6 +926,30 @@ impl CompositeNode {
                 description: "Opacity level".to_string(),
             },
         );
+        parameters.insert(
+            "premultiplied".to_string(),
+            ParameterDefinition {
+                name: "Premultiplied Alpha".to_string(),
+                parameter_type: ParameterType::Boolean,
+                default_value: Value::Bool(false),
+                min_value: None,
+                max_value: None,
+                description: "Inputs and output use premultiplied alpha".to_string(),
+            },
+        );
+        parameters.insert(
+            "foreground_input".to_string(),
+            ParameterDefinition {
+                name: "Foreground Input".to_string(),
+                parameter_type: ParameterType::String,
+                default_value: Value::String(String::new()),
+                min_value: None,
+                max_value: None,
+                description:
+                    "Node ID of the upstream node drawn on top (empty = first connected input)"
+                        .to_string(),
+            },
+        );
 
         let properties = NodeProperties {
             id,
@@ -650,6 +958,7 @@ impl CompositeNode {
             input_types: vec![ConnectionType::RenderData, ConnectionType::RenderData],
             output_types: vec![ConnectionType::RenderData],
             parameters,
+            enabled: true,
         };
 
         Ok(Self {
@@ -662,9 +971,28 @@ impl CompositeNode {
 
 impl NodeProcessor for CompositeNode {
     fn process(&mut self, input: FrameData) -> Result<FrameData> {
+        // A single input has nothing to composite against
         Ok(input)
     }
 
+    // `foreground_input` picks the layer drawn on top; the other input is the background
+    fn process_multi(&mut self, mut inputs: Vec<(Uuid, FrameData)>) -> Result<FrameData> {
+        if let Some(index) = self
+            .foreground_input()
+            .and_then(|id| inputs.iter().position(|(source, _)| *source == id))
+        {
+            let foreground = inputs.remove(index);
+            inputs.insert(0, foreground);
+        }
+
+        let mut frames = inputs.into_iter().map(|(_, frame)| frame);
+        match (frames.next(), frames.next()) {
+            (Some(foreground), Some(background)) => self.composite(foreground, background),
+            (Some(foreground), None) => self.process(foreground),
+            (None, _) => anyhow::bail!("Composite requires a foreground input"),
+        }
+    }
+
     fn get_properties(&self) -> NodeProperties {
         self.properties.clone()
     }
@@ -677,4 +1005,1030 @@ impl NodeProcessor for CompositeNode {
     fn get_parameter(&self, key: &str) -> Option<Value> {
         self.config.parameters.get(key).cloned()
     }
+
+    fn input_video_format(&self) -> Option<VideoFormat> {
+        Some(VideoFormat::Rgba8)
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum BlendMode {
+    Normal,
+    Multiply,
+    Screen,
+    Overlay,
+    Add,
+    Subtract,
+}
+
+impl BlendMode {
+    pub fn from_name(name: &str) -> Option<Self> {
+        match name {
+            "Normal" => Some(Self::Normal),
+            "Multiply" => Some(Self::Multiply),
+            "Screen" => Some(Self::Screen),
+            "Overlay" => Some(Self::Overlay),
+            "Add" => Some(Self::Add),
+            "Subtract" => Some(Self::Subtract),
+            _ => None,
+        }
+    }
+
+    /// Blend a straight (non-premultiplied) source channel onto a backdrop channel
+    fn blend(self, backdrop: f32, source: f32) -> f32 {
+        match self {
+            Self::Normal => source,
+            Self::Multiply => backdrop * source,
+            Self::Screen => backdrop + source - backdrop * source,
+            Self::Overlay => {
+                if backdrop <= 0.5 {
+                    2.0 * backdrop * source
+                } else {
+                    1.0 - 2.0 * (1.0 - backdrop) * (1.0 - source)
+                }
+            }
+            Self::Add => (backdrop + source).min(1.0),
+            Self::Subtract => (backdrop - source).max(0.0),
+        }
+    }
+}
+
+impl CompositeNode {
+    /// Upstream node whose frame is the foreground. Without it the first
+    /// connected input is used, so reconnecting cables can swap the layers
+    fn foreground_input(&self) -> Option<Uuid> {
+        self.get_parameter("foreground_input")
+            .and_then(|v| v.as_str().and_then(|id| Uuid::parse_str(id).ok()))
+    }
+
+    /// Composite `foreground` over `background`.
+    ///
+    /// The output takes the background's resolution and non-video data; the
+    /// foreground is bilinearly resized when the dimensions differ.
+    pub fn composite(&mut self, foreground: FrameData, background: FrameData) -> Result<FrameData> {
+        let mut output = background;
+
+        let Some(RenderData::Raster2D(fg_frame)) = foreground.render_data else {
+            return Ok(output);
+        };
+        let Some(RenderData::Raster2D(ref mut bg_frame)) = output.render_data else {
+            // No backdrop: the foreground becomes the output
+            output.render_data = Some(RenderData::Raster2D(fg_frame));
+            return Ok(output);
+        };
+
+        if fg_frame.format != VideoFormat::Rgba8 || bg_frame.format != VideoFormat::Rgba8 {
+            return Err(anyhow::anyhow!(
+                "Composite requires Rgba8 inputs, got {:?} over {:?}",
+                fg_frame.format,
+                bg_frame.format
+            ));
+        }
+
+        let mode_name = self
+            .get_parameter("blend_mode")
+            .and_then(|v| v.as_str().map(str::to_string))
+            .unwrap_or_else(|| "Normal".to_string());
+        let mode = BlendMode::from_name(&mode_name)
+            .ok_or_else(|| anyhow::anyhow!("Unknown blend mode: {}", mode_name))?;
+        let opacity = self
+            .get_parameter("opacity")
+            .and_then(|v| v.as_f64())
+            .unwrap_or(1.0)
+            .clamp(0.0, 1.0) as f32;
+        let premultiplied = self
+            .get_parameter("premultiplied")
+            .and_then(|v| v.as_bool())
+            .unwrap_or(false);
+
+        let foreground = resize_premultiplied(
+            &to_premultiplied(&fg_frame.data, premultiplied),
+            fg_frame.width as usize,
+            fg_frame.height as usize,
+            bg_frame.width as usize,
+            bg_frame.height as usize,
+        );
+
+        for (bg, fg) in bg_frame
+            .data
+            .chunks_exact_mut(4)
+            .zip(foreground.chunks_exact(4))
+        {
+            let bg_alpha = bg[3] as f32 / 255.0;
+            let bg_premul: [f32; 3] = if premultiplied {
+                [bg[0], bg[1], bg[2]].map(|c| c as f32 / 255.0)
+            } else {
+                [bg[0], bg[1], bg[2]].map(|c| c as f32 / 255.0 * bg_alpha)
+            };
+
+            let fg_alpha = fg[3] * opacity;
+            let fg_premul = [fg[0], fg[1], fg[2]].map(|c| c * opacity);
+
+            // Separable blend (W3C compositing): the blended color only applies
+            // where both layers are present
+            let out_alpha = fg_alpha + bg_alpha * (1.0 - fg_alpha);
+            let mut out = [0.0f32; 3];
+            for c in 0..3 {
+                let backdrop = unpremultiply(bg_premul[c], bg_alpha);
+                let source = unpremultiply(fg_premul[c], fg_alpha);
+                out[c] = (1.0 - bg_alpha) * fg_premul[c]
+                    + (1.0 - fg_alpha) * bg_premul[c]
+                    + fg_alpha * bg_alpha * mode.blend(backdrop, source);
+            }
+
+            for c in 0..3 {
+                let value = if premultiplied {
+                    out[c]
+                } else {
+                    unpremultiply(out[c], out_alpha)
+                };
+                bg[c] = (value.clamp(0.0, 1.0) * 255.0).round() as u8;
+            }
+            bg[3] = (out_alpha.clamp(0.0, 1.0) * 255.0).round() as u8;
+        }
+
+        Ok(output)
+    }
+}
+
+/// Keys out a solid backdrop color from the foreground and composites the
+/// result over an optional background input.
+pub struct ChromaKeyNode {
+    id: Uuid,
+    config: NodeConfig,
+    properties: NodeProperties,
+    // Normal-mode compositor used to lay the keyed foreground over the background
+    compositor: CompositeNode,
+}
+
+impl ChromaKeyNode {
+    pub fn new(id: Uuid, config: NodeConfig) -> Result<Self> {
+        let mut parameters = HashMap::new();
+        parameters.insert(
+            "key_color".to_string(),
+            ParameterDefinition {
+                name: "Key Color".to_string(),
+                parameter_type: ParameterType::Color,
+                default_value: Value::Array(vec![
+                    Value::from(0.0),
+                    Value::from(1.0),
+                    Value::from(0.0),
+                ]),
+                min_value: None,
+                max_value: None,
+                description: "Backdrop color to remove (RGB)".to_string(),
+            },
+        );
+        parameters.insert(
+            "tolerance".to_string(),
+            ParameterDefinition {
+                name: "Tolerance".to_string(),
+                parameter_type: ParameterType::Float,
+                default_value: Value::from(0.2),
+                min_value: Some(Value::from(0.0)),
+                max_value: Some(Value::from(1.0)),
+                description: "Chroma distance from the key color that becomes fully transparent"
+                    .to_string(),
+            },
+        );
+        parameters.insert(
+            "softness".to_string(),
+            ParameterDefinition {
+                name: "Softness".to_string(),
+                parameter_type: ParameterType::Float,
+                default_value: Value::from(0.1),
+                min_value: Some(Value::from(0.0)),
+                max_value: Some(Value::from(1.0)),
+                description: "Width of the partially transparent edge beyond the tolerance"
+                    .to_string(),
+            },
+        );
+        parameters.insert(
+            "spill_suppression".to_string(),
+            ParameterDefinition {
+                name: "Spill Suppression".to_string(),
+                parameter_type: ParameterType::Float,
+                default_value: Value::from(0.5),
+                min_value: Some(Value::from(0.0)),
+                max_value: Some(Value::from(1.0)),
+                description: "How strongly key-colored light reflected onto the subject is removed"
+                    .to_string(),
+            },
+        );
+
+        let properties = NodeProperties {
+            id,
+            name: "Chroma Key".to_string(),
+            node_type: NodeType::Effect(EffectType::ChromaKey),
+            input_types: vec![ConnectionType::RenderData, ConnectionType::RenderData],
+            output_types: vec![ConnectionType::RenderData],
+            parameters,
+            enabled: true,
+        };
+
+        let compositor = CompositeNode::new(
+            id,
+            NodeConfig {
+                parameters: HashMap::new(),
+            },
+        )?;
+
+        Ok(Self {
+            id,
+            config,
+            properties,
+            compositor,
+        })
+    }
+
+    fn float_parameter(&self, key: &str, default: f64) -> f32 {
+        self.get_parameter(key)
+            .and_then(|v| v.as_f64())
+            .unwrap_or(default)
+            .clamp(0.0, 1.0) as f32
+    }
+
+    /// Key color as normalized RGB, from a component array or `#rrggbb`
+    fn key_color(&self) -> [f32; 3] {
+        let default = [0.0, 1.0, 0.0];
+        match self.get_parameter("key_color") {
+            Some(Value::Array(components)) => {
+                let component = |i: usize| {
+                    components
+                        .get(i)
+                        .and_then(|v| v.as_f64())
+                        .map(|v| v.clamp(0.0, 1.0) as f32)
+                };
+                match (component(0), component(1), component(2)) {
+                    (Some(r), Some(g), Some(b)) => [r, g, b],
+                    _ => default,
+                }
+            }
+            Some(Value::String(hex)) => {
+                let channel = |i: usize| {
+                    hex.strip_prefix('#')
+                        .and_then(|hex| hex.get(i * 2..i * 2 + 2))
+                        .and_then(|pair| u8::from_str_radix(pair, 16).ok())
+                        .map(|v| v as f32 / 255.0)
+                };
+                match (channel(0), channel(1), channel(2)) {
+                    (Some(r), Some(g), Some(b)) => [r, g, b],
+                    _ => default,
+                }
+            }
+            _ => default,
+        }
+    }
+
+    /// Replace the frame's alpha with the key matte and suppress spill in place
+    pub fn apply_key(&self, frame: &mut VideoFrame) -> Result<()> {
+        let Some(layout) = packed_layout(frame) else {
+            anyhow::bail!("Chroma key cannot process {:?} frames", frame.format);
+        };
+        if layout.alpha.is_none() {
+            anyhow::bail!("Chroma key requires a frame with an alpha channel");
+        }
+
+        let key = self.key_color();
+        let key_chroma = chroma(key);
+        let tolerance = self.float_parameter("tolerance", 0.2);
+        let softness = self.float_parameter("softness", 0.1);
+        let spill = self.float_parameter("spill_suppression", 0.5);
+
+        // Spill shows up in the key's dominant channel
+        let dominant = (0..3)
+            .max_by(|&a, &b| key[a].total_cmp(&key[b]))
+            .unwrap_or(1);
+
+        for pixel in frame.pixel_iter_mut() {
+            let [r, g, b, a] = layout.rgba(pixel);
+            let mut rgb = [r, g, b].map(|c| c as f32 / 255.0);
+
+            let (cb, cr) = chroma(rgb);
+            let distance = ((cb - key_chroma.0).powi(2) + (cr - key_chroma.1).powi(2)).sqrt();
+            let matte = if distance <= tolerance {
+                0.0
+            } else if softness <= 0.0 || distance >= tolerance + softness {
+                1.0
+            } else {
+                (distance - tolerance) / softness
+            };
+
+            if spill > 0.0 {
+                let others: f32 = (0..3).filter(|&c| c != dominant).map(|c| rgb[c]).sum();
+                let limit = others / 2.0;
+                if rgb[dominant] > limit {
+                    rgb[dominant] -= spill * (rgb[dominant] - limit);
+                }
+            }
+
+            let [r, g, b] = rgb.map(|c| (c.clamp(0.0, 1.0) * 255.0).round() as u8);
+            let alpha = (a as f32 * matte).round() as u8;
+            layout.set_rgba(pixel, [r, g, b, alpha]);
+        }
+
+        Ok(())
+    }
+
+    /// Key `foreground` and composite it over `background`
+    pub fn composite(
+        &mut self,
+        mut foreground: FrameData,
+        background: FrameData,
+    ) -> Result<FrameData> {
+        if let Some(RenderData::Raster2D(ref mut frame)) = foreground.render_data {
+            self.apply_key(frame)?;
+        }
+        self.compositor.composite(foreground, background)
+    }
+}
+
+/// BT.709 blue- and red-difference chroma of normalized RGB
+fn chroma([r, g, b]: [f32; 3]) -> (f32, f32) {
+    let luma = 0.2126 * r + 0.7152 * g + 0.0722 * b;
+    ((b - luma) / 1.8556, (r - luma) / 1.5748)
+}
+
+impl NodeProcessor for ChromaKeyNode {
+    fn process(&mut self, mut input: FrameData) -> Result<FrameData> {
+        // Without a background the keyed foreground is output with its matte
+        if let Some(RenderData::Raster2D(ref mut frame)) = input.render_data {
+            self.apply_key(frame)?;
+        }
+
+        Ok(input)
+    }
+
+    // The first connected input is the foreground, the second the background
+    fn process_multi(&mut self, inputs: Vec<(Uuid, FrameData)>) -> Result<FrameData> {
+        let mut frames = inputs.into_iter().map(|(_, frame)| frame);
+        match (frames.next(), frames.next()) {
+            (Some(foreground), Some(background)) => self.composite(foreground, background),
+            (Some(foreground), None) => self.process(foreground),
+            (None, _) => anyhow::bail!("Chroma key requires a foreground input"),
+        }
+    }
+
+    fn get_properties(&self) -> NodeProperties {
+        self.properties.clone()
+    }
+
+    fn set_parameter(&mut self, key: &str, value: Value) -> Result<()> {
+        self.config.parameters.insert(key.to_string(), value);
+        Ok(())
+    }
+
+    fn get_parameter(&self, key: &str) -> Option<Value> {
+        self.config.parameters.get(key).cloned()
+    }
+
+    fn input_video_format(&self) -> Option<VideoFormat> {
+        Some(VideoFormat::Rgba8)
+    }
+}
+
+/// Transition style used by `TransitionNode`
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TransitionMode {
+    Crossfade,
+    Wipe(WipeDirection),
+    DipToColor,
+}
+
+/// Edge a wipe starts from; input B is revealed from that side
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum WipeDirection {
+    LeftToRight,
+    RightToLeft,
+    TopToBottom,
+    BottomToTop,
+}
+
+impl WipeDirection {
+    pub fn from_name(name: &str) -> Option<Self> {
+        match name {
+            "Left to Right" => Some(Self::LeftToRight),
+            "Right to Left" => Some(Self::RightToLeft),
+            "Top to Bottom" => Some(Self::TopToBottom),
+            "Bottom to Top" => Some(Self::BottomToTop),
+            _ => None,
+        }
+    }
+
+    /// Whether the pixel at (x, y) already shows input B at `progress`
+    fn reveals(self, x: usize, y: usize, width: usize, height: usize, progress: f32) -> bool {
+        let (position, extent) = match self {
+            Self::LeftToRight => (x, width),
+            Self::RightToLeft => (width - 1 - x, width),
+            Self::TopToBottom => (y, height),
+            Self::BottomToTop => (height - 1 - y, height),
+        };
+        // Compare pixel centers so half the frame switches at progress 0.5
+        (position as f32 + 0.5) < progress * extent as f32
+    }
+}
+
+/// Switcher transition between two sources.
+///
+/// The first connected input is A (program), the second B (preview). At
+/// `progress` 0 the output is A, at 1 it is B; in between the frames are
+/// mixed according to `mode`.
+pub struct TransitionNode {
+    id: Uuid,
+    config: NodeConfig,
+    properties: NodeProperties,
+}
+
+impl TransitionNode {
+    pub fn new(id: Uuid, config: NodeConfig) -> Result<Self> {
+        let mut parameters = HashMap::new();
+        parameters.insert(
+            "progress".to_string(),
+            ParameterDefinition {
+                name: "Progress".to_string(),
+                parameter_type: ParameterType::Float,
+                default_value: Value::from(0.0),
+                min_value: Some(Value::from(0.0)),
+                max_value: Some(Value::from(1.0)),
+                description: "Transition position from input A (0) to input B (1)".to_string(),
+            },
+        );
+        parameters.insert(
+            "mode".to_string(),
+            ParameterDefinition {
+                name: "Mode".to_string(),
+                parameter_type: ParameterType::Enum(vec![
+                    "Crossfade".to_string(),
+                    "Wipe".to_string(),
+                    "Dip to Color".to_string(),
+                ]),
+                default_value: Value::String("Crossfade".to_string()),
+                min_value: None,
+                max_value: None,
+                description: "Transition style".to_string(),
+            },
+        );
+        parameters.insert(
+            "wipe_direction".to_string(),
+            ParameterDefinition {
+                name: "Wipe Direction".to_string(),
+                parameter_type: ParameterType::Enum(vec![
+                    "Left to Right".to_string(),
+                    "Right to Left".to_string(),
+                    "Top to Bottom".to_string(),
+                    "Bottom to Top".to_string(),
+                ]),
+                default_value: Value::String("Left to Right".to_string()),
+                min_value: None,
+                max_value: None,
+                description: "Edge the wipe starts from".to_string(),
+            },
+        );
+        parameters.insert(
+            "dip_color".to_string(),
+            ParameterDefinition {
+                name: "Dip Color".to_string(),
+                parameter_type: ParameterType::Color,
+                default_value: Value::Array(vec![
+                    Value::from(0.0),
+                    Value::from(0.0),
+                    Value::from(0.0),
+                ]),
+                min_value: None,
+                max_value: None,
+                description: "Color passed through halfway in Dip to Color mode (RGB)".to_string(),
+            },
+        );
+
+        let properties = NodeProperties {
+            id,
+            name: "Transition".to_string(),
+            node_type: NodeType::Effect(EffectType::Transition),
+            input_types: vec![ConnectionType::RenderData, ConnectionType::RenderData],
+            output_types: vec![ConnectionType::RenderData],
+            parameters,
+            enabled: true,
+        };
+
+        Ok(Self {
+            id,
+            config,
+            properties,
+        })
+    }
+
+    fn progress(&self) -> f32 {
+        self.get_parameter("progress")
+            .and_then(|v| v.as_f64())
+            .unwrap_or(0.0)
+            .clamp(0.0, 1.0) as f32
+    }
+
+    pub fn mode(&self) -> Result<TransitionMode> {
+        let name = |key: &str, default: &str| {
+            self.get_parameter(key)
+                .and_then(|v| v.as_str().map(str::to_string))
+                .unwrap_or_else(|| default.to_string())
+        };
+
+        match name("mode", "Crossfade").as_str() {
+            "Crossfade" => Ok(TransitionMode::Crossfade),
+            "Dip to Color" => Ok(TransitionMode::DipToColor),
+            "Wipe" => {
+                let direction = name("wipe_direction", "Left to Right");
+                WipeDirection::from_name(&direction)
+                    .map(TransitionMode::Wipe)
+                    .ok_or_else(|| anyhow::anyhow!("Unknown wipe direction: {}", direction))
+            }
+            other => Err(anyhow::anyhow!("Unknown transition mode: {}", other)),
+        }
+    }
+
+    /// Dip color as opaque premultiplied RGBA
+    fn dip_color(&self) -> [f32; 4] {
+        let component = |i: usize| match self.get_parameter("dip_color") {
+            Some(Value::Array(components)) => components
+                .get(i)
+                .and_then(|v| v.as_f64())
+                .map(|v| v.clamp(0.0, 1.0) as f32)
+                .unwrap_or(0.0),
+            _ => 0.0,
+        };
+        [component(0), component(1), component(2), 1.0]
+    }
+
+    /// Mix `a` into `b` at the current progress.
+    ///
+    /// The output takes A's resolution and non-video data; B is bilinearly
+    /// resized when the dimensions differ. Tally from both inputs is kept since
+    /// both sources are on air during the transition.
+    pub fn transition(&mut self, a: FrameData, b: FrameData) -> Result<FrameData> {
+        let progress = self.progress();
+        let mode = self.mode()?;
+        if progress >= 1.0 {
+            let mut output = b;
+            output.tally_metadata.merge_with(&a.tally_metadata);
+            return Ok(output);
+        }
+
+        let mut output = a;
+        output.tally_metadata.merge_with(&b.tally_metadata);
+        if progress <= 0.0 {
+            return Ok(output);
+        }
+
+        let Some(RenderData::Raster2D(b_frame)) = b.render_data else {
+            return Ok(output);
+        };
+        let Some(RenderData::Raster2D(ref mut a_frame)) = output.render_data else {
+            // Nothing to transition from: B is the only picture
+            output.render_data = Some(RenderData::Raster2D(b_frame));
+            return Ok(output);
+        };
+
+        if a_frame.format != VideoFormat::Rgba8 || b_frame.format != VideoFormat::Rgba8 {
+            return Err(anyhow::anyhow!(
+                "Transition requires Rgba8 inputs, got {:?} and {:?}",
+                a_frame.format,
+                b_frame.format
+            ));
+        }
+
+        let width = a_frame.width as usize;
+        let height = a_frame.height as usize;
+        let a_pixels = to_premultiplied(&a_frame.data, false);
+        let b_pixels = resize_premultiplied(
+            &to_premultiplied(&b_frame.data, false),
+            b_frame.width as usize,
+            b_frame.height as usize,
+            width,
+            height,
+        );
+        let dip_color = self.dip_color();
+
+        for (index, (out, (a, b))) in a_frame
+            .data
+            .chunks_exact_mut(4)
+            .zip(a_pixels.chunks_exact(4).zip(b_pixels.chunks_exact(4)))
+            .enumerate()
+        {
+            let mix = |from: &[f32], to: &[f32], t: f32| -> [f32; 4] {
+                [0, 1, 2, 3].map(|c| from[c] + (to[c] - from[c]) * t)
+            };
+            let pixel = match mode {
+                TransitionMode::Crossfade => mix(a, b, progress),
+                TransitionMode::Wipe(direction) => {
+                    let (x, y) = (index % width, index / width);
+                    if direction.reveals(x, y, width, height, progress) {
+                        [b[0], b[1], b[2], b[3]]
+                    } else {
+                        [a[0], a[1], a[2], a[3]]
+                    }
+                }
+                // A fades to the color over the first half, B fades in over the second
+                TransitionMode::DipToColor if progress < 0.5 => mix(a, &dip_color, progress * 2.0),
+                TransitionMode::DipToColor => mix(&dip_color, b, progress * 2.0 - 1.0),
+            };
+
+            let alpha = pixel[3];
+            for c in 0..3 {
+                out[c] = (unpremultiply(pixel[c], alpha).clamp(0.0, 1.0) * 255.0).round() as u8;
+            }
+            out[3] = (alpha.clamp(0.0, 1.0) * 255.0).round() as u8;
+        }
+
+        Ok(output)
+    }
+}
+
+impl NodeProcessor for TransitionNode {
+    fn process(&mut self, input: FrameData) -> Result<FrameData> {
+        // With a single source there is nothing to transition to
+        Ok(input)
+    }
+
+    // The first connected input is A, the second B
+    fn process_multi(&mut self, inputs: Vec<(Uuid, FrameData)>) -> Result<FrameData> {
+        let mut frames = inputs.into_iter().map(|(_, frame)| frame);
+        match (frames.next(), frames.next()) {
+            (Some(a), Some(b)) => self.transition(a, b),
+            (Some(a), None) => self.process(a),
+            (None, _) => anyhow::bail!("Transition requires at least one input"),
+        }
+    }
+
+    fn get_properties(&self) -> NodeProperties {
+        self.properties.clone()
+    }
+
+    fn set_parameter(&mut self, key: &str, value: Value) -> Result<()> {
+        self.config.parameters.insert(key.to_string(), value);
+        Ok(())
+    }
+
+    fn get_parameter(&self, key: &str) -> Option<Value> {
+        self.config.parameters.get(key).cloned()
+    }
+
+    fn input_video_format(&self) -> Option<VideoFormat> {
+        Some(VideoFormat::Rgba8)
+    }
+}
+
+/// 3D color lookup table, as loaded from an Adobe/Resolve `.cube` file
+#[derive(Debug, Clone, PartialEq)]
+pub struct Lut3d {
+    pub size: usize,
+    pub domain_min: [f32; 3],
+    pub domain_max: [f32; 3],
+    /// `size`³ output colors, red index varying fastest
+    pub table: Vec<[f32; 3]>,
+}
+
+impl Lut3d {
+    /// Largest `LUT_3D_SIZE` accepted; 256³ entries is already 200 MB of floats
+    pub const MAX_SIZE: usize = 256;
+
+    /// Table that maps every color to itself
+    pub fn identity(size: usize) -> Self {
+        let step = 1.0 / (size - 1) as f32;
+        let table = (0..size * size * size)
+            .map(|i| {
+                [i % size, i / size % size, i / (size * size)].map(|index| index as f32 * step)
+            })
+            .collect();
+        Self {
+            size,
+            domain_min: [0.0; 3],
+            domain_max: [1.0; 3],
+            table,
+        }
+    }
+
+    pub fn load_cube(path: impl AsRef<std::path::Path>) -> Result<Self> {
+        let path = path.as_ref();
+        let text = std::fs::read_to_string(path)
+            .map_err(|e| anyhow::anyhow!("Failed to read LUT {}: {}", path.display(), e))?;
+        Self::parse_cube(&text)
+            .map_err(|e| anyhow::anyhow!("Invalid LUT {}: {}", path.display(), e))
+    }
+
+    /// Parse the text of a `.cube` file, validating its dimensions
+    pub fn parse_cube(text: &str) -> Result<Self> {
+        let mut size = None;
+        let mut domain_min = [0.0; 3];
+        let mut domain_max = [1.0; 3];
+        let mut table = Vec::new();
+
+        for (index, line) in text.lines().enumerate() {
+            let line_number = index + 1;
+            let line = line.trim();
+            if line.is_empty() || line.starts_with('#') {
+                continue;
+            }
+
+            let mut fields = line.split_whitespace();
+            let keyword = fields.next().unwrap_or_default();
+            let triple = |fields: std::str::SplitWhitespace| -> Result<[f32; 3]> {
+                let values = fields
+                    .map(|field| field.parse::<f32>())
+                    .collect::<std::result::Result<Vec<_>, _>>()
+                    .map_err(|e| anyhow::anyhow!("line {}: {}", line_number, e))?;
+                <[f32; 3]>::try_from(values).map_err(|values| {
+                    anyhow::anyhow!(
+                        "line {}: expected 3 values, found {}",
+                        line_number,
+                        values.len()
+                    )
+                })
+            };
+
+            match keyword {
+                "TITLE" => {}
+                "LUT_3D_SIZE" => {
+                    let value = fields
+                        .next()
+                        .and_then(|field| field.parse::<usize>().ok())
+                        .filter(|size| (2..=Self::MAX_SIZE).contains(size))
+                        .ok_or_else(|| {
+                            anyhow::anyhow!(
+                                "line {}: LUT_3D_SIZE must be between 2 and {}",
+                                line_number,
+                                Self::MAX_SIZE
+                            )
+                        })?;
+                    size = Some(value);
+                }
+                "LUT_1D_SIZE" => anyhow::bail!("1D LUTs are not supported"),
+                "DOMAIN_MIN" => domain_min = triple(fields)?,
+                "DOMAIN_MAX" => domain_max = triple(fields)?,
+                _ if keyword.starts_with(|c: char| c.is_ascii_digit() || c == '-' || c == '.') => {
+                    table.push(triple(line.split_whitespace())?);
+                }
+                // Vendor keywords such as LUT_3D_INPUT_RANGE are not needed
+                _ => {}
+            }
+        }
+
+        let Some(size) = size else {
+            anyhow::bail!("missing LUT_3D_SIZE");
+        };
+        if table.len() != size * size * size {
+            anyhow::bail!(
+                "LUT_3D_SIZE {} needs {} entries, found {}",
+                size,
+                size * size * size,
+                table.len()
+            );
+        }
+        if (0..3).any(|c| domain_max[c] <= domain_min[c]) {
+            anyhow::bail!("DOMAIN_MAX must be greater than DOMAIN_MIN");
+        }
+
+        Ok(Self {
+            size,
+            domain_min,
+            domain_max,
+            table,
+        })
+    }
+
+    /// Look up a color with trilinear interpolation; inputs outside the
+    /// domain are clamped to its edge
+    pub fn sample(&self, rgb: [f32; 3]) -> [f32; 3] {
+        let max_index = (self.size - 1) as f32;
+        let mut base = [0usize; 3];
+        let mut fraction = [0.0f32; 3];
+        for c in 0..3 {
+            let normalized =
+                (rgb[c] - self.domain_min[c]) / (self.domain_max[c] - self.domain_min[c]);
+            let position = normalized.clamp(0.0, 1.0) * max_index;
+            // Keep one cell to interpolate across at the upper edge
+            base[c] = (position.floor() as usize).min(self.size - 2);
+            fraction[c] = position - base[c] as f32;
+        }
+
+        let entry = |r: usize, g: usize, b: usize| {
+            self.table
+                [(base[2] + b) * self.size * self.size + (base[1] + g) * self.size + base[0] + r]
+        };
+        let lerp = |a: [f32; 3], b: [f32; 3], t: f32| [0, 1, 2].map(|c| a[c] + (b[c] - a[c]) * t);
+
+        let [fr, fg, fb] = fraction;
+        let front = lerp(
+            lerp(entry(0, 0, 0), entry(1, 0, 0), fr),
+            lerp(entry(0, 1, 0), entry(1, 1, 0), fr),
+            fg,
+        );
+        let back = lerp(
+            lerp(entry(0, 0, 1), entry(1, 0, 1), fr),
+            lerp(entry(0, 1, 1), entry(1, 1, 1), fr),
+            fg,
+        );
+        lerp(front, back, fb)
+    }
+}
+
+/// Grades the picture through a 3D LUT loaded from a `.cube` file
+pub struct LutNode {
+    id: Uuid,
+    config: NodeConfig,
+    properties: NodeProperties,
+    // Parsed table and the path it was loaded from
+    lut: Option<(String, Lut3d)>,
+}
+
+impl LutNode {
+    pub fn new(id: Uuid, config: NodeConfig) -> Result<Self> {
+        let mut parameters = HashMap::new();
+        parameters.insert(
+            "file_path".to_string(),
+            ParameterDefinition {
+                name: "LUT File".to_string(),
+                parameter_type: ParameterType::String,
+                default_value: Value::String(String::new()),
+                min_value: None,
+                max_value: None,
+                description: "Path to a .cube 3D LUT (empty = pass through)".to_string(),
+            },
+        );
+        parameters.insert(
+            "intensity".to_string(),
+            ParameterDefinition {
+                name: "Intensity".to_string(),
+                parameter_type: ParameterType::Float,
+                default_value: Value::from(1.0),
+                min_value: Some(Value::from(0.0)),
+                max_value: Some(Value::from(1.0)),
+                description: "Blend between the original (0) and the graded picture (1)"
+                    .to_string(),
+            },
+        );
+
+        let properties = NodeProperties {
+            id,
+            name: "LUT".to_string(),
+            node_type: NodeType::Effect(EffectType::Lut),
+            input_types: vec![ConnectionType::RenderData],
+            output_types: vec![ConnectionType::RenderData],
+            parameters,
+            enabled: true,
+        };
+
+        Ok(Self {
+            id,
+            config,
+            properties,
+            lut: None,
+        })
+    }
+
+    /// The LUT for the current `file_path`, loading it on first use
+    fn current_lut(&mut self) -> Result<Option<&Lut3d>> {
+        let path = self
+            .get_parameter("file_path")
+            .and_then(|v| v.as_str().map(str::to_string))
+            .unwrap_or_default();
+        if path.is_empty() {
+            self.lut = None;
+            return Ok(None);
+        }
+
+        if self.lut.as_ref().map(|(loaded, _)| loaded) != Some(&path) {
+            let lut = Lut3d::load_cube(&path)?;
+            self.lut = Some((path, lut));
+        }
+        Ok(self.lut.as_ref().map(|(_, lut)| lut))
+    }
+
+    /// Apply `lut` to the color channels in place, keeping alpha
+    pub fn apply_lut(frame: &mut VideoFrame, lut: &Lut3d, intensity: f32) -> Result<()> {
+        let Some(layout) = packed_layout(frame) else {
+            anyhow::bail!("LUT cannot process {:?} frames", frame.format);
+        };
+
+        for pixel in frame.pixel_iter_mut() {
+            let rgb = layout.rgb(pixel).map(|c| c as f32 / 255.0);
+            let graded = lut.sample(rgb);
+            let rgb = [0, 1, 2].map(|c| {
+                let value = rgb[c] + (graded[c] - rgb[c]) * intensity;
+                (value.clamp(0.0, 1.0) * 255.0).round() as u8
+            });
+            layout.set_rgb(pixel, rgb);
+        }
+
+        Ok(())
+    }
+}
+
+impl NodeProcessor for LutNode {
+    fn process(&mut self, mut input: FrameData) -> Result<FrameData> {
+        let intensity = self
+            .get_parameter("intensity")
+            .and_then(|v| v.as_f64())
+            .unwrap_or(1.0)
+            .clamp(0.0, 1.0) as f32;
+
+        if let Some(RenderData::Raster2D(ref mut frame)) = input.render_data {
+            if intensity > 0.0 {
+                if let Some(lut) = self.current_lut()? {
+                    Self::apply_lut(frame, lut, intensity)?;
+                }
+            }
+        }
+
+        Ok(input)
+    }
+
+    fn get_properties(&self) -> NodeProperties {
+        self.properties.clone()
+    }
+
+    fn on_start(&mut self) -> Result<()> {
+        // Surface a bad file when the pipeline starts rather than on the first frame
+        self.current_lut().map(|_| ())
+    }
+
+    fn set_parameter(&mut self, key: &str, value: Value) -> Result<()> {
+        self.config.parameters.insert(key.to_string(), value);
+        Ok(())
+    }
+
+    fn get_parameter(&self, key: &str) -> Option<Value> {
+        self.config.parameters.get(key).cloned()
+    }
+
+    fn input_video_format(&self) -> Option<VideoFormat> {
+        Some(VideoFormat::Rgba8)
+    }
+}
+
+fn unpremultiply(value: f32, alpha: f32) -> f32 {
+    if alpha > 0.0 {
+        value / alpha
+    } else {
+        0.0
+    }
+}
+
+/// Convert RGBA8 data to normalized premultiplied floats
+fn to_premultiplied(data: &[u8], already_premultiplied: bool) -> Vec<f32> {
+    data.chunks_exact(4)
+        .flat_map(|p| {
+            let alpha = p[3] as f32 / 255.0;
+            let scale = if already_premultiplied { 1.0 } else { alpha };
+            [
+                p[0] as f32 / 255.0 * scale,
+                p[1] as f32 / 255.0 * scale,
+                p[2] as f32 / 255.0 * scale,
+                alpha,
+            ]
+        })
+        .collect()
+}
+
+/// Bilinear resize of premultiplied RGBA floats (premultiplied to avoid dark fringes)
+fn resize_premultiplied(
+    data: &[f32],
+    src_width: usize,
+    src_height: usize,
+    dst_width: usize,
+    dst_height: usize,
+) -> Vec<f32> {
+    if src_width == dst_width && src_height == dst_height {
+        return data.to_vec();
+    }
+
+    let mut output = vec![0.0f32; dst_width * dst_height * 4];
+    if src_width == 0 || src_height == 0 {
+        return output;
+    }
+
+    let scale_x = src_width as f32 / dst_width as f32;
+    let scale_y = src_height as f32 / dst_height as f32;
+
+    for y in 0..dst_height {
+        // Sample at pixel centers
+        let sy = ((y as f32 + 0.5) * scale_y - 0.5).clamp(0.0, (src_height - 1) as f32);
+        let y0 = sy.floor() as usize;
+        let y1 = (y0 + 1).min(src_height - 1);
+        let fy = sy - y0 as f32;
+
+        for x in 0..dst_width {
+            let sx = ((x as f32 + 0.5) * scale_x - 0.5).clamp(0.0, (src_width - 1) as f32);
+            let x0 = sx.floor() as usize;
+            let x1 = (x0 + 1).min(src_width - 1);
+            let fx = sx - x0 as f32;
+
+            let dst = (y * dst_width + x) * 4;
+            for c in 0..4 {
+                let sample = |sx: usize, sy: usize| data[(sy * src_width + sx) * 4 + c];
+                let top = sample(x0, y0) * (1.0 - fx) + sample(x1, y0) * fx;
+                let bottom = sample(x0, y1) * (1.0 - fx) + sample(x1, y1) * fx;
+                output[dst + c] = top * (1.0 - fy) + bottom * fy;
+            }
+        }
+    }
+
+    output
 }
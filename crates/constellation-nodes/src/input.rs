@@ -23,6 +23,7 @@ use anyhow::Result;
 use constellation_core::*;
 use serde_json::Value;
 use std::collections::HashMap;
+use std::time::Duration;
 use tracing::{debug, error, info};
 use uuid::Uuid;
 
@@ -81,6 +82,7 @@ impl CameraInputNode {
             input_types: vec![],
             output_types: vec![ConnectionType::RenderData, ConnectionType::Audio],
             parameters,
+            enabled: true,
         };
 
         Ok(Self {
@@ -120,6 +122,9 @@ impl NodeProcessor for CameraInputNode {
                             }),
                             control_data: None,
                             tally_metadata: TallyMetadata::new(),
+                            timestamp: Duration::ZERO,
+                            sequence: 0,
+                            checksum: None,
                         });
                     }
                 }
@@ -153,6 +158,9 @@ impl NodeProcessor for CameraInputNode {
             }),
             control_data: None,
             tally_metadata: TallyMetadata::new(),
+            timestamp: Duration::ZERO,
+            sequence: 0,
+            checksum: None,
         })
     }
 
@@ -160,6 +168,28 @@ impl NodeProcessor for CameraInputNode {
         self.properties.clone()
     }
 
+    fn on_start(&mut self) -> Result<()> {
+        if self.camera_capture.is_none() {
+            self.initialize_camera()?;
+        }
+        if let Some(ref mut camera) = self.camera_capture {
+            if !camera.is_running() {
+                camera.start_capture()?;
+                info!("Camera capture started successfully");
+            }
+        }
+        Ok(())
+    }
+
+    fn on_stop(&mut self) -> Result<()> {
+        if let Some(mut camera) = self.camera_capture.take() {
+            if camera.is_running() {
+                camera.stop_capture()?;
+            }
+        }
+        Ok(())
+    }
+
     fn set_parameter(&mut self, key: &str, value: Value) -> Result<()> {
         self.config.parameters.insert(key.to_string(), value);
         // Reset camera capture to apply new parameters
@@ -304,6 +334,7 @@ impl VideoFileInputNode {
             input_types: vec![],
             output_types: vec![ConnectionType::RenderData, ConnectionType::Audio],
             parameters,
+            enabled: true,
         };
 
         Ok(Self {
@@ -326,6 +357,7 @@ impl NodeProcessor for VideoFileInputNode {
         }
 
         // Read frame from video file
+        let mut timing = (Duration::ZERO, 0);
         let (video_frame, audio_frame) = if let Some(ref mut reader) = self.video_reader {
             match reader.read_frame() {
                 Ok((video, audio)) => {
@@ -333,6 +365,7 @@ impl NodeProcessor for VideoFileInputNode {
                         "Successfully read frame from video file: {}x{}",
                         video.width, video.height
                     );
+                    timing = (reader.last_frame_timestamp(), reader.last_frame_sequence());
                     (Some(video), audio)
                 }
                 Err(e) => {
@@ -361,6 +394,9 @@ impl NodeProcessor for VideoFileInputNode {
             }),
             control_data: None,
             tally_metadata: TallyMetadata::new(),
+            timestamp: timing.0,
+            sequence: timing.1,
+            checksum: None,
         })
     }
 
@@ -368,6 +404,18 @@ impl NodeProcessor for VideoFileInputNode {
         self.properties.clone()
     }
 
+    fn on_start(&mut self) -> Result<()> {
+        if self.video_reader.is_none() {
+            self.initialize_video_reader()?;
+        }
+        Ok(())
+    }
+
+    fn on_stop(&mut self) -> Result<()> {
+        self.video_reader = None;
+        Ok(())
+    }
+
     fn set_parameter(&mut self, key: &str, value: Value) -> Result<()> {
         self.config.parameters.insert(key.to_string(), value);
         // Reset video reader to apply new parameters
@@ -463,10 +511,40 @@ impl VideoFileInputNode {
     }
 }
 
+/// 75% SMPTE color bars, left to right (gray, yellow, cyan, green, magenta, red, blue)
+const SMPTE_TOP_BARS: [[u8; 4]; 7] = [
+    [191, 191, 191, 255],
+    [191, 191, 0, 255],
+    [0, 191, 191, 255],
+    [0, 191, 0, 255],
+    [191, 0, 191, 255],
+    [191, 0, 0, 255],
+    [0, 0, 191, 255],
+];
+/// Reverse-order castellations under the main bars (blue, black, magenta, black, cyan, black, gray)
+const SMPTE_MIDDLE_BARS: [[u8; 4]; 7] = [
+    [0, 0, 191, 255],
+    SMPTE_BLACK,
+    [191, 0, 191, 255],
+    SMPTE_BLACK,
+    [0, 191, 191, 255],
+    SMPTE_BLACK,
+    [191, 191, 191, 255],
+];
+/// 7.5 IRE setup black
+const SMPTE_BLACK: [u8; 4] = [19, 19, 19, 255];
+const SMPTE_MINUS_I: [u8; 4] = [0, 33, 76, 255];
+const SMPTE_WHITE: [u8; 4] = [255, 255, 255, 255];
+const SMPTE_PLUS_Q: [u8; 4] = [50, 0, 106, 255];
+/// PLUGE strips: below black, black, above black
+const SMPTE_PLUGE: [[u8; 4]; 3] = [[9, 9, 9, 255], SMPTE_BLACK, [29, 29, 29, 255]];
+
 pub struct TestPatternNode {
     id: Uuid,
     config: NodeConfig,
     properties: NodeProperties,
+    // Frames generated so far; drives the moving gradient deterministically
+    frame_count: u64,
 }
 
 impl TestPatternNode {
@@ -478,14 +556,16 @@ impl TestPatternNode {
                 name: "Pattern Type".to_string(),
                 parameter_type: ParameterType::Enum(vec![
                     "Color Bars".to_string(),
-                    "Gradient".to_string(),
+                    "Grayscale Ramp".to_string(),
+                    "Checkerboard".to_string(),
+                    "Moving Gradient".to_string(),
                     "Solid Color".to_string(),
                     "Noise".to_string(),
                 ]),
                 default_value: Value::String("Color Bars".to_string()),
                 min_value: None,
                 max_value: None,
-                description: "Test pattern type".to_string(),
+                description: "Test pattern type (Color Bars are SMPTE 75% bars)".to_string(),
             },
         );
         parameters.insert(
@@ -504,6 +584,39 @@ impl TestPatternNode {
                 description: "Pattern color (RGBA)".to_string(),
             },
         );
+        parameters.insert(
+            "width".to_string(),
+            ParameterDefinition {
+                name: "Width".to_string(),
+                parameter_type: ParameterType::Integer,
+                default_value: Value::from(1920),
+                min_value: Some(Value::from(16)),
+                max_value: Some(Value::from(7680)),
+                description: "Pattern width in pixels".to_string(),
+            },
+        );
+        parameters.insert(
+            "height".to_string(),
+            ParameterDefinition {
+                name: "Height".to_string(),
+                parameter_type: ParameterType::Integer,
+                default_value: Value::from(1080),
+                min_value: Some(Value::from(16)),
+                max_value: Some(Value::from(4320)),
+                description: "Pattern height in pixels".to_string(),
+            },
+        );
+        parameters.insert(
+            "checker_size".to_string(),
+            ParameterDefinition {
+                name: "Checker Size".to_string(),
+                parameter_type: ParameterType::Integer,
+                default_value: Value::from(64),
+                min_value: Some(Value::from(1)),
+                max_value: Some(Value::from(1024)),
+                description: "Checkerboard square size in pixels".to_string(),
+            },
+        );
 
         let properties = NodeProperties {
             id,
@@ -512,14 +625,47 @@ impl TestPatternNode {
             input_types: vec![],
             output_types: vec![ConnectionType::RenderData],
             parameters,
+            enabled: true,
         };
 
         Ok(Self {
             id,
             config,
             properties,
+            frame_count: 0,
         })
     }
+
+    fn integer_parameter(&self, key: &str, default: u32) -> u32 {
+        self.get_parameter(key)
+            .and_then(|v| v.as_u64())
+            .map(|v| v.clamp(1, u32::MAX as u64) as u32)
+            .unwrap_or(default)
+    }
+
+    fn frame_size(&self) -> (u32, u32) {
+        (
+            self.integer_parameter("width", 1920),
+            self.integer_parameter("height", 1080),
+        )
+    }
+}
+
+/// Build an RGBA frame by evaluating `pixel` at every coordinate
+fn render_pattern(width: u32, height: u32, pixel: impl Fn(u32, u32) -> [u8; 4]) -> VideoFrame {
+    let mut data = Vec::with_capacity((width * height * 4) as usize);
+    for y in 0..height {
+        for x in 0..width {
+            data.extend_from_slice(&pixel(x, y));
+        }
+    }
+
+    VideoFrame {
+        width,
+        height,
+        format: VideoFormat::Rgba8,
+        data,
+    }
 }
 
 impl NodeProcessor for TestPatternNode {
@@ -531,17 +677,24 @@ impl NodeProcessor for TestPatternNode {
 
         let frame_data = match pattern_type.as_str() {
             "Color Bars" => self.generate_color_bars(),
-            "Gradient" => self.generate_gradient(),
+            // "Gradient" is the pre-existing name of the grayscale ramp
+            "Grayscale Ramp" | "Gradient" => self.generate_grayscale_ramp(),
+            "Checkerboard" => self.generate_checkerboard(),
+            "Moving Gradient" => self.generate_moving_gradient(),
             "Solid Color" => self.generate_solid_color(),
             "Noise" => self.generate_noise(),
             _ => self.generate_color_bars(),
         };
+        self.frame_count += 1;
 
         Ok(FrameData {
             render_data: Some(RenderData::Raster2D(frame_data)),
             audio_data: None,
             control_data: None,
             tally_metadata: TallyMetadata::new(),
+            timestamp: Duration::ZERO,
+            sequence: 0,
+            checksum: None,
         })
     }
 
@@ -560,131 +713,98 @@ impl NodeProcessor for TestPatternNode {
 }
 
 impl TestPatternNode {
+    /// SMPTE EG 1 style bars: 75% bars over 2/3 of the height, castellations
+    /// for 1/12, and the -I / white / +Q / PLUGE row at the bottom
     fn generate_color_bars(&self) -> VideoFrame {
-        const WIDTH: u32 = 1920;
-        const HEIGHT: u32 = 1080;
-        let mut data = vec![0u8; (WIDTH * HEIGHT * 4) as usize];
-
-        let colors = [
-            [255, 255, 255, 255], // White
-            [255, 255, 0, 255],   // Yellow
-            [0, 255, 255, 255],   // Cyan
-            [0, 255, 0, 255],     // Green
-            [255, 0, 255, 255],   // Magenta
-            [255, 0, 0, 255],     // Red
-            [0, 0, 255, 255],     // Blue
-            [0, 0, 0, 255],       // Black
-        ];
-
-        let bar_width = WIDTH / colors.len() as u32;
-
-        for y in 0..HEIGHT {
-            for x in 0..WIDTH {
-                let bar_index = (x / bar_width).min(colors.len() as u32 - 1) as usize;
-                let pixel_index = ((y * WIDTH + x) * 4) as usize;
-
-                data[pixel_index] = colors[bar_index][0]; // R
-                data[pixel_index + 1] = colors[bar_index][1]; // G
-                data[pixel_index + 2] = colors[bar_index][2]; // B
-                data[pixel_index + 3] = colors[bar_index][3]; // A
+        let (width, height) = self.frame_size();
+        let top_end = height * 2 / 3;
+        let middle_end = height * 3 / 4;
+
+        render_pattern(width, height, |x, y| {
+            // Position in units of one top bar (7 bars across)
+            let bars = x as f32 * 7.0 / width as f32;
+            let bar = (bars as usize).min(6);
+
+            if y < top_end {
+                SMPTE_TOP_BARS[bar]
+            } else if y < middle_end {
+                SMPTE_MIDDLE_BARS[bar]
+            } else if bars < 5.0 {
+                // Four blocks, each 5/4 of a bar wide
+                match (bars / 1.25) as usize {
+                    0 => SMPTE_MINUS_I,
+                    1 => SMPTE_WHITE,
+                    2 => SMPTE_PLUS_Q,
+                    _ => SMPTE_BLACK,
+                }
+            } else if bars < 6.0 {
+                SMPTE_PLUGE[(((bars - 5.0) * 3.0) as usize).min(2)]
+            } else {
+                SMPTE_BLACK
             }
-        }
-
-        VideoFrame {
-            width: WIDTH,
-            height: HEIGHT,
-            format: VideoFormat::Rgba8,
-            data,
-        }
+        })
     }
 
-    fn generate_gradient(&self) -> VideoFrame {
-        const WIDTH: u32 = 1920;
-        const HEIGHT: u32 = 1080;
-        let mut data = vec![0u8; (WIDTH * HEIGHT * 4) as usize];
+    fn generate_grayscale_ramp(&self) -> VideoFrame {
+        let (width, height) = self.frame_size();
+        let span = width.saturating_sub(1).max(1) as f32;
 
-        for y in 0..HEIGHT {
-            for x in 0..WIDTH {
-                let pixel_index = ((y * WIDTH + x) * 4) as usize;
-                let intensity = (x as f32 / WIDTH as f32 * 255.0) as u8;
+        render_pattern(width, height, |x, _| {
+            let intensity = (x as f32 / span * 255.0).round() as u8;
+            [intensity, intensity, intensity, 255]
+        })
+    }
 
-                data[pixel_index] = intensity; // R
-                data[pixel_index + 1] = intensity; // G
-                data[pixel_index + 2] = intensity; // B
-                data[pixel_index + 3] = 255; // A
+    fn generate_checkerboard(&self) -> VideoFrame {
+        let (width, height) = self.frame_size();
+        let size = self.integer_parameter("checker_size", 64);
+
+        render_pattern(width, height, |x, y| {
+            if (x / size + y / size).is_multiple_of(2) {
+                [255, 255, 255, 255]
+            } else {
+                [0, 0, 0, 255]
             }
-        }
+        })
+    }
 
-        VideoFrame {
-            width: WIDTH,
-            height: HEIGHT,
-            format: VideoFormat::Rgba8,
-            data,
-        }
+    /// Diagonal color gradient that scrolls a fixed amount per generated frame
+    fn generate_moving_gradient(&self) -> VideoFrame {
+        let (width, height) = self.frame_size();
+        let offset = (self.frame_count * 8 % width as u64) as u32;
+
+        render_pattern(width, height, |x, y| {
+            let u = ((x + offset) % width) as f32 / width as f32;
+            let v = y as f32 / height as f32;
+            [
+                (u * 255.0) as u8,
+                (v * 255.0) as u8,
+                ((1.0 - u) * 255.0) as u8,
+                255,
+            ]
+        })
     }
 
     fn generate_solid_color(&self) -> VideoFrame {
-        const WIDTH: u32 = 1920;
-        const HEIGHT: u32 = 1080;
-        let mut data = vec![0u8; (WIDTH * HEIGHT * 4) as usize];
+        let (width, height) = self.frame_size();
 
         let color = self
             .get_parameter("color")
             .and_then(|v| v.as_array().cloned())
-            .unwrap_or_else(|| {
-                vec![
-                    Value::from(1.0),
-                    Value::from(1.0),
-                    Value::from(1.0),
-                    Value::from(1.0),
-                ]
-            });
-
-        let r = (color.first().and_then(|v| v.as_f64()).unwrap_or(1.0) * 255.0) as u8;
-        let g = (color.get(1).and_then(|v| v.as_f64()).unwrap_or(1.0) * 255.0) as u8;
-        let b = (color.get(2).and_then(|v| v.as_f64()).unwrap_or(1.0) * 255.0) as u8;
-        let a = (color.get(3).and_then(|v| v.as_f64()).unwrap_or(1.0) * 255.0) as u8;
-
-        for y in 0..HEIGHT {
-            for x in 0..WIDTH {
-                let pixel_index = ((y * WIDTH + x) * 4) as usize;
-                data[pixel_index] = r;
-                data[pixel_index + 1] = g;
-                data[pixel_index + 2] = b;
-                data[pixel_index + 3] = a;
-            }
-        }
+            .unwrap_or_default();
+        let channel =
+            |i: usize| (color.get(i).and_then(|v| v.as_f64()).unwrap_or(1.0) * 255.0) as u8;
+        let rgba = [channel(0), channel(1), channel(2), channel(3)];
 
-        VideoFrame {
-            width: WIDTH,
-            height: HEIGHT,
-            format: VideoFormat::Rgba8,
-            data,
-        }
+        render_pattern(width, height, |_, _| rgba)
     }
 
     fn generate_noise(&self) -> VideoFrame {
-        const WIDTH: u32 = 1920;
-        const HEIGHT: u32 = 1080;
-        let mut data = vec![0u8; (WIDTH * HEIGHT * 4) as usize];
-
-        for y in 0..HEIGHT {
-            for x in 0..WIDTH {
-                let pixel_index = ((y * WIDTH + x) * 4) as usize;
-                let noise = ((x + y) * 123456789) % 256;
-
-                data[pixel_index] = noise as u8;
-                data[pixel_index + 1] = noise as u8;
-                data[pixel_index + 2] = noise as u8;
-                data[pixel_index + 3] = 255;
-            }
-        }
+        let (width, height) = self.frame_size();
 
-        VideoFrame {
-            width: WIDTH,
-            height: HEIGHT,
-            format: VideoFormat::Rgba8,
-            data,
-        }
+        render_pattern(width, height, |x, y| {
+            let noise = ((x + y) as u64 * 123456789 % 256) as u8;
+            [noise, noise, noise, 255]
+        })
     }
 }
@@ -29,32 +29,113 @@ use anyhow::Result;
 use constellation_core::*;
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
+use std::time::Duration;
 use uuid::Uuid;
 
+pub mod audio_device;
+pub mod audio_file;
 pub mod camera;
 pub mod capture;
 pub mod controller;
 pub mod effects;
 pub mod input;
+#[cfg(feature = "ndi")]
+pub mod ndi;
 pub mod output;
+#[cfg(feature = "srt")]
+pub mod srt;
 pub mod video_file;
 pub mod virtual_camera;
 
+pub use audio_device::{DeviceConfig, DeviceStream, SampleRing};
+pub use audio_file::{AudioFileInputNode, AudioFileOutputNode};
 pub use capture::{ScreenCaptureNode, WindowCaptureNode};
+pub use constellation_core::{
+    validate_parameter, OutOfRangePolicy, ParameterDefinition, ParameterType,
+};
 pub use controller::*;
 pub use effects::*;
 pub use input::*;
+#[cfg(feature = "ndi")]
+pub use ndi::NdiOutputNode;
 pub use output::*;
+#[cfg(feature = "srt")]
+pub use srt::SrtOutputNode;
 
 // Export types needed for tests
 pub use constellation_core::NodeConfig;
 
 pub trait NodeProcessor: Send {
     fn process(&mut self, input: FrameData) -> Result<FrameData>;
+
+    // 複数の上流ノードから入力を受け取る（送信元ノードIDと出力の組、接続順）
+    // デフォルト実装: 先頭の入力に欠けている要素を後続の入力で補って1フレームにまとめ、processへ渡す
+    fn process_multi(&mut self, inputs: Vec<(Uuid, FrameData)>) -> Result<FrameData> {
+        let mut frames = inputs.into_iter().map(|(_, frame)| frame);
+        let Some(mut merged) = frames.next() else {
+            anyhow::bail!("process_multi requires at least one input");
+        };
+        for frame in frames {
+            merged.render_data = merged.render_data.or(frame.render_data);
+            merged.audio_data = merged.audio_data.or(frame.audio_data);
+            merged.control_data = merged.control_data.or(frame.control_data);
+            merged.tally_metadata.merge_with(&frame.tally_metadata);
+        }
+        self.process(merged)
+    }
     fn get_properties(&self) -> NodeProperties;
     fn set_parameter(&mut self, key: &str, value: serde_json::Value) -> Result<()>;
     fn get_parameter(&self, key: &str) -> Option<serde_json::Value>;
 
+    // ノードが加える処理遅延（パイプラインが映像・音声のずれを補正するのに使う）
+    fn processing_latency(&self) -> Duration {
+        Duration::ZERO
+    }
+
+    // 最初のフレームを処理する前に一度だけ呼ばれる（デバイスのオープンなど）
+    // エラーを返すとパイプラインの開始が中止される
+    fn on_start(&mut self) -> Result<()> {
+        Ok(())
+    }
+
+    // パイプライン停止時に一度だけ呼ばれる（デバイス・スレッド・ファイルなどの後始末）
+    fn on_stop(&mut self) -> Result<()> {
+        Ok(())
+    }
+
+    // ParameterDefinitionで検証してからset_parameterへ渡し、受け付けた値を返す
+    // 範囲外の数値は`policy`に従って拒否するか丸める。定義のないキーはノード固有の設定としてそのまま渡す
+    fn set_validated_parameter(
+        &mut self,
+        key: &str,
+        value: serde_json::Value,
+        policy: OutOfRangePolicy,
+    ) -> Result<serde_json::Value> {
+        let value = match self.get_properties().parameters.get(key) {
+            Some(definition) => validate_parameter(definition, value, policy).map_err(|e| {
+                ConstellationError::ParameterValidationFailed {
+                    parameter: key.to_string(),
+                    reason: e.to_string(),
+                }
+            })?,
+            None => value,
+        };
+        self.set_parameter(key, value.clone())?;
+        Ok(value)
+    }
+
+    // 入力として期待するRaster2Dフォーマット（Noneなら任意のフォーマットを受け付ける）
+    fn input_video_format(&self) -> Option<VideoFormat> {
+        None
+    }
+
+    // 変換せずにそのまま処理できるフォーマットか（16bitを8bitに落とさずに通すノード向け）
+    // デフォルト実装: input_video_formatと一致するものだけ
+    fn accepts_video_format(&self, format: &VideoFormat) -> bool {
+        self.input_video_format()
+            .is_none_or(|expected| expected == *format)
+    }
+
     // Tally自動伝播システム
     fn process_tally_metadata(&mut self, metadata: &TallyMetadata) -> TallyMetadata {
         // デフォルト実装: 変更なしで伝播
@@ -82,29 +163,13 @@ pub struct NodeProperties {
     pub input_types: Vec<ConnectionType>,
     pub output_types: Vec<ConnectionType>,
     pub parameters: HashMap<String, ParameterDefinition>,
+    /// False while the node is bypassed; the pipeline then passes frames through untouched
+    #[serde(default = "default_enabled")]
+    pub enabled: bool,
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
-pub struct ParameterDefinition {
-    pub name: String,
-    pub parameter_type: ParameterType,
-    pub default_value: serde_json::Value,
-    pub min_value: Option<serde_json::Value>,
-    pub max_value: Option<serde_json::Value>,
-    pub description: String,
-}
-
-#[derive(Debug, Clone, Serialize, Deserialize)]
-pub enum ParameterType {
-    Float,
-    Integer,
-    Boolean,
-    String,
-    Color,
-    Vector2,
-    Vector3,
-    Vector4,
-    Enum(Vec<String>),
+fn default_enabled() -> bool {
+    true
 }
 
 pub fn create_node_processor(
@@ -123,19 +188,40 @@ pub fn create_node_processor(
         NodeType::Output(output_type) => match output_type {
             OutputType::VirtualWebcam => Ok(Box::new(VirtualWebcamNode::new(id, config)?)),
             OutputType::Preview => Ok(Box::new(PreviewNode::new(id, config)?)),
+            OutputType::Recorder => Ok(Box::new(RecorderNode::new(id, config)?)),
+            OutputType::Scope => Ok(Box::new(ScopeNode::new(id, config)?)),
+            #[cfg(feature = "ndi")]
+            OutputType::Ndi => Ok(Box::new(NdiOutputNode::new(id, config)?)),
+            #[cfg(not(feature = "ndi"))]
+            OutputType::Ndi => Err(anyhow::anyhow!(
+                "NDI output requires building with the `ndi` feature"
+            )),
+            #[cfg(feature = "srt")]
+            OutputType::Srt => Ok(Box::new(SrtOutputNode::new(id, config)?)),
+            #[cfg(not(feature = "srt"))]
+            OutputType::Srt => Err(anyhow::anyhow!(
+                "SRT output requires building with the `srt` feature"
+            )),
         },
         NodeType::Effect(effect_type) => match effect_type {
             EffectType::ColorCorrection => Ok(Box::new(ColorCorrectionNode::new(id, config)?)),
             EffectType::Blur => Ok(Box::new(BlurNode::new(id, config)?)),
             EffectType::Sharpen => Ok(Box::new(SharpenNode::new(id, config)?)),
+            EffectType::Grayscale => Ok(Box::new(GrayscaleNode::new(id, config)?)),
             EffectType::Transform => Ok(Box::new(TransformNode::new(id, config)?)),
             EffectType::Composite => Ok(Box::new(CompositeNode::new(id, config)?)),
+            EffectType::Deinterlace => Ok(Box::new(DeinterlaceNode::new(id, config)?)),
+            EffectType::ChromaKey => Ok(Box::new(ChromaKeyNode::new(id, config)?)),
+            EffectType::Transition => Ok(Box::new(TransitionNode::new(id, config)?)),
+            EffectType::Lut => Ok(Box::new(LutNode::new(id, config)?)),
         },
         NodeType::Audio(audio_type) => match audio_type {
             AudioType::Input => Ok(Box::new(AudioInputNode::new(id, config)?)),
             AudioType::Mixer => Ok(Box::new(AudioMixerNode::new(id, config)?)),
             AudioType::Effect => Ok(Box::new(AudioEffectNode::new(id, config)?)),
             AudioType::Output => Ok(Box::new(AudioOutputNode::new(id, config)?)),
+            AudioType::FileInput => Ok(Box::new(AudioFileInputNode::new(id, config)?)),
+            AudioType::FileOutput => Ok(Box::new(AudioFileOutputNode::new(id, config)?)),
         },
         NodeType::Tally(tally_type) => match tally_type {
             TallyType::Generator => Ok(Box::new(TallyGeneratorNode::new(id, config)?)),
@@ -147,9 +233,13 @@ pub fn create_node_processor(
             ControlType::Lfo => Ok(Box::new(LFOController::new(id, config)?)),
             ControlType::Timeline => Ok(Box::new(TimelineController::new(id, config)?)),
             ControlType::MathController => Ok(Box::new(MathController::new(id, config)?)),
-            ControlType::MidiController => {
-                Err(anyhow::anyhow!("MIDI controller not yet implemented"))
-            }
+            ControlType::MidiController => Ok(Box::new(MidiController::new(id, config)?)),
+            ControlType::Envelope => Ok(Box::new(EnvelopeController::new(id, config)?)),
+            ControlType::GamepadController => Ok(Box::new(GamepadController::new(id, config)?)),
+            ControlType::OSCReceiver => Ok(Box::new(OSCReceiver::new(id, config)?)),
+            ControlType::WebSocketController => Ok(Box::new(WebSocketController::new(id, config)?)),
+            ControlType::VideoAnalysis => Ok(Box::new(VideoAnalysisController::new(id, config)?)),
+            ControlType::APIController => Ok(Box::new(APIController::new(id, config)?)),
             _ => Err(anyhow::anyhow!(
                 "Controller type not yet implemented: {:?}",
                 control_type
@@ -189,4 +279,46 @@ mod tests {
         assert_eq!(param.name, "brightness");
         assert_eq!(param.default_value, serde_json::Value::from(1.0));
     }
+
+    #[test]
+    fn test_set_validated_parameter_rejects_out_of_range() {
+        let config = NodeConfig {
+            parameters: HashMap::new(),
+        };
+        let mut node = ColorCorrectionNode::new(Uuid::new_v4(), config).unwrap();
+
+        assert!(node
+            .set_validated_parameter(
+                "brightness",
+                serde_json::json!(5.0),
+                OutOfRangePolicy::Reject
+            )
+            .is_err());
+        assert_eq!(node.get_parameter("brightness"), None);
+
+        node.set_validated_parameter(
+            "brightness",
+            serde_json::json!(0.5),
+            OutOfRangePolicy::Reject,
+        )
+        .unwrap();
+        assert_eq!(
+            node.get_parameter("brightness"),
+            Some(serde_json::json!(0.5))
+        );
+
+        let max = node.get_properties().parameters["brightness"]
+            .max_value
+            .clone()
+            .unwrap();
+        let accepted = node
+            .set_validated_parameter(
+                "brightness",
+                serde_json::json!(5.0),
+                OutOfRangePolicy::Clamp,
+            )
+            .unwrap();
+        assert_eq!(accepted, max);
+        assert_eq!(node.get_parameter("brightness"), Some(max));
+    }
 }
@@ -0,0 +1,485 @@
+/*
+ * Constellation Studio - Professional Real-time Video Processing
+ * Copyright (c) 2025 MACHIKO LAB
+ *
+ * This program is free software: you can redistribute it and/or modify
+ * it under the terms of the GNU Affero General Public License as published by
+ * the Free Software Foundation, either version 3 of the License, or
+ * (at your option) any later version.
+ *
+ * This program is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+ * GNU Affero General Public License for more details.
+ *
+ * You should have received a copy of the GNU Affero General Public License
+ * along with this program. If not, see <https://www.gnu.org/licenses/>.
+ */
+
+//! NDI network output
+//!
+//! The NDI runtime is loaded dynamically the first time a sender is created,
+//! as the NDI SDK license requires, so building with the `ndi` feature does not
+//! need the SDK installed. Frame conversion does not touch the runtime at all.
+
+use crate::virtual_camera::conversion;
+use crate::{NodeProcessor, NodeProperties, ParameterDefinition, ParameterType};
+use anyhow::{bail, Context, Result};
+use constellation_core::*;
+use serde_json::Value;
+use std::collections::HashMap;
+use tracing::{info, warn};
+use uuid::Uuid;
+
+/// Pixel layouts an NDI video frame can be sent in
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum NdiFrameFormat {
+    Bgra,
+    Rgba,
+    /// Packed 4:2:2, NDI's native format and the cheapest to send
+    Uyvy,
+}
+
+impl NdiFrameFormat {
+    pub fn from_name(name: &str) -> Option<Self> {
+        match name {
+            "BGRA" => Some(Self::Bgra),
+            "RGBA" => Some(Self::Rgba),
+            "UYVY" => Some(Self::Uyvy),
+            _ => None,
+        }
+    }
+
+    /// The `NDIlib_FourCC_video_type_e` value for this layout
+    pub fn fourcc(&self) -> u32 {
+        let code = match self {
+            Self::Bgra => b"BGRA",
+            Self::Rgba => b"RGBA",
+            Self::Uyvy => b"UYVY",
+        };
+        u32::from_le_bytes(*code)
+    }
+
+    /// Bytes per row of a tightly packed frame
+    pub fn line_stride(&self, width: u32) -> u32 {
+        match self {
+            Self::Bgra | Self::Rgba => width * 4,
+            Self::Uyvy => width.div_ceil(2) * 4,
+        }
+    }
+}
+
+/// A frame converted to the layout NDI expects
+#[derive(Debug, Clone)]
+pub struct NdiVideoFrame {
+    pub width: u32,
+    pub height: u32,
+    pub format: NdiFrameFormat,
+    pub line_stride: u32,
+    pub data: Vec<u8>,
+}
+
+/// Convert a pipeline frame to a tightly packed NDI frame
+pub fn convert_frame(frame: &VideoFrame, format: NdiFrameFormat) -> Result<NdiVideoFrame> {
+    if frame.width == 0 || frame.height == 0 {
+        bail!(
+            "Cannot send an empty {}x{} frame",
+            frame.width,
+            frame.height
+        );
+    }
+    if format == NdiFrameFormat::Uyvy && !frame.width.is_multiple_of(2) {
+        bail!("UYVY frames need an even width, got {}", frame.width);
+    }
+
+    let rgba = conversion::frame_to_rgba(frame)?;
+    let data = match format {
+        NdiFrameFormat::Rgba => rgba,
+        NdiFrameFormat::Bgra => rgba
+            .chunks_exact(4)
+            .flat_map(|p| [p[2], p[1], p[0], p[3]])
+            .collect(),
+        NdiFrameFormat::Uyvy => conversion::rgba_to_uyvy(&rgba, frame.width, frame.height),
+    };
+
+    Ok(NdiVideoFrame {
+        width: frame.width,
+        height: frame.height,
+        format,
+        line_stride: format.line_stride(frame.width),
+        data,
+    })
+}
+
+/// Whether the NDI runtime library could be found and initialized
+pub fn runtime_available() -> bool {
+    runtime::library().is_ok()
+}
+
+mod runtime {
+    use super::NdiVideoFrame;
+    use anyhow::{anyhow, bail, Result};
+    use libloading::Library;
+    use std::ffi::{c_char, c_int, c_void, CString};
+    use std::path::PathBuf;
+    use std::ptr::{self, NonNull};
+    use std::sync::OnceLock;
+
+    #[repr(C)]
+    struct SendCreate {
+        p_ndi_name: *const c_char,
+        p_groups: *const c_char,
+        clock_video: bool,
+        clock_audio: bool,
+    }
+
+    #[repr(C)]
+    struct VideoFrameV2 {
+        xres: c_int,
+        yres: c_int,
+        fourcc: u32,
+        frame_rate_n: c_int,
+        frame_rate_d: c_int,
+        picture_aspect_ratio: f32,
+        frame_format_type: c_int,
+        timecode: i64,
+        p_data: *const u8,
+        line_stride_in_bytes: c_int,
+        p_metadata: *const c_char,
+        timestamp: i64,
+    }
+
+    const FRAME_FORMAT_PROGRESSIVE: c_int = 1;
+    const TIMECODE_SYNTHESIZE: i64 = i64::MAX;
+
+    type InitializeFn = unsafe extern "C" fn() -> bool;
+    type SendCreateFn = unsafe extern "C" fn(*const SendCreate) -> *mut c_void;
+    type SendDestroyFn = unsafe extern "C" fn(*mut c_void);
+    type SendVideoFn = unsafe extern "C" fn(*mut c_void, *const VideoFrameV2);
+    type ConnectionsFn = unsafe extern "C" fn(*mut c_void, u32) -> c_int;
+
+    pub(super) struct NdiLibrary {
+        send_create: SendCreateFn,
+        send_destroy: SendDestroyFn,
+        send_video: SendVideoFn,
+        connections: ConnectionsFn,
+        // Keeps the function pointers above valid
+        _library: Library,
+    }
+
+    fn candidate_paths() -> Vec<PathBuf> {
+        #[cfg(target_os = "windows")]
+        let names: &[&str] = &["Processing.NDI.Lib.x64.dll"];
+        #[cfg(target_os = "macos")]
+        let names: &[&str] = &["libndi.dylib"];
+        #[cfg(not(any(target_os = "windows", target_os = "macos")))]
+        let names: &[&str] = &["libndi.so.6", "libndi.so.5", "libndi.so"];
+
+        let mut paths = Vec::new();
+        for variable in ["NDI_RUNTIME_DIR_V6", "NDI_RUNTIME_DIR_V5"] {
+            if let Some(dir) = std::env::var_os(variable) {
+                paths.extend(names.iter().map(|name| PathBuf::from(&dir).join(name)));
+            }
+        }
+        #[cfg(target_os = "macos")]
+        paths.push(PathBuf::from("/usr/local/lib/libndi.dylib"));
+        // Finally let the system loader search its default paths
+        paths.extend(names.iter().map(PathBuf::from));
+        paths
+    }
+
+    fn load() -> Result<NdiLibrary> {
+        let mut errors = Vec::new();
+        for path in candidate_paths() {
+            // SAFETY: the NDI runtime has no library constructors with preconditions
+            match unsafe { Library::new(&path) } {
+                Ok(library) => return unsafe { NdiLibrary::bind(library) },
+                Err(e) => errors.push(format!("{}: {}", path.display(), e)),
+            }
+        }
+        bail!("NDI runtime not found ({})", errors.join("; "))
+    }
+
+    impl NdiLibrary {
+        /// # Safety
+        /// `library` must be an NDI runtime exporting the v5 send API.
+        unsafe fn bind(library: Library) -> Result<Self> {
+            let initialize: InitializeFn = *library.get(b"NDIlib_initialize\0")?;
+            let ndi = Self {
+                send_create: *library.get(b"NDIlib_send_create\0")?,
+                send_destroy: *library.get(b"NDIlib_send_destroy\0")?,
+                send_video: *library.get(b"NDIlib_send_send_video_v2\0")?,
+                connections: *library.get(b"NDIlib_send_get_no_connections\0")?,
+                _library: library,
+            };
+            if !initialize() {
+                bail!("NDI runtime failed to initialize (unsupported CPU?)");
+            }
+            Ok(ndi)
+        }
+    }
+
+    /// The process-wide runtime, loaded and initialized on first use
+    pub(super) fn library() -> Result<&'static NdiLibrary> {
+        static LIBRARY: OnceLock<std::result::Result<NdiLibrary, String>> = OnceLock::new();
+        LIBRARY
+            .get_or_init(|| load().map_err(|e| e.to_string()))
+            .as_ref()
+            .map_err(|e| anyhow!("{}", e))
+    }
+
+    /// An NDI source visible on the network
+    pub(super) struct NdiSender {
+        library: &'static NdiLibrary,
+        instance: NonNull<c_void>,
+    }
+
+    // SAFETY: NDI sender instances may be used from any thread, one call at a time
+    unsafe impl Send for NdiSender {}
+
+    impl NdiSender {
+        pub(super) fn new(source_name: &str, groups: &str) -> Result<Self> {
+            let library = library()?;
+            let name = CString::new(source_name)?;
+            let groups = (!groups.is_empty())
+                .then(|| CString::new(groups))
+                .transpose()?;
+            let settings = SendCreate {
+                p_ndi_name: name.as_ptr(),
+                p_groups: groups.as_ref().map_or(ptr::null(), |g| g.as_ptr()),
+                // The pipeline already paces frames
+                clock_video: false,
+                clock_audio: false,
+            };
+
+            // SAFETY: settings and the strings it points to outlive the call
+            let instance = unsafe { (library.send_create)(&settings) };
+            let instance = NonNull::new(instance)
+                .ok_or_else(|| anyhow!("NDI refused to create source \"{}\"", source_name))?;
+            Ok(Self { library, instance })
+        }
+
+        pub(super) fn send(&mut self, frame: &NdiVideoFrame, frame_rate: (i32, i32)) -> Result<()> {
+            let expected = frame.line_stride as usize * frame.height as usize;
+            if frame.data.len() != expected {
+                bail!(
+                    "NDI frame is {} bytes, expected {} for {}x{}",
+                    frame.data.len(),
+                    expected,
+                    frame.width,
+                    frame.height
+                );
+            }
+
+            let video = VideoFrameV2 {
+                xres: c_int::try_from(frame.width)?,
+                yres: c_int::try_from(frame.height)?,
+                fourcc: frame.format.fourcc(),
+                frame_rate_n: frame_rate.0,
+                frame_rate_d: frame_rate.1,
+                // Zero means square pixels
+                picture_aspect_ratio: 0.0,
+                frame_format_type: FRAME_FORMAT_PROGRESSIVE,
+                timecode: TIMECODE_SYNTHESIZE,
+                p_data: frame.data.as_ptr(),
+                line_stride_in_bytes: c_int::try_from(frame.line_stride)?,
+                p_metadata: ptr::null(),
+                timestamp: 0,
+            };
+
+            // SAFETY: the synchronous send returns once NDI no longer needs the buffer
+            unsafe { (self.library.send_video)(self.instance.as_ptr(), &video) };
+            Ok(())
+        }
+
+        /// Number of receivers currently connected to this source
+        pub(super) fn connections(&self) -> usize {
+            // SAFETY: the instance is valid until drop
+            let count = unsafe { (self.library.connections)(self.instance.as_ptr(), 0) };
+            count.max(0) as usize
+        }
+    }
+
+    impl Drop for NdiSender {
+        fn drop(&mut self) {
+            // SAFETY: the instance was created by this library and is not used again
+            unsafe { (self.library.send_destroy)(self.instance.as_ptr()) };
+        }
+    }
+}
+
+/// Sends the pipeline's video over the network as an NDI source
+pub struct NdiOutputNode {
+    id: Uuid,
+    config: NodeConfig,
+    properties: NodeProperties,
+    sender: Option<runtime::NdiSender>,
+    frames_sent: u64,
+}
+
+impl NdiOutputNode {
+    pub fn new(id: Uuid, config: NodeConfig) -> Result<Self> {
+        let mut parameters = HashMap::new();
+        parameters.insert(
+            "source_name".to_string(),
+            ParameterDefinition {
+                name: "Source Name".to_string(),
+                parameter_type: ParameterType::String,
+                default_value: Value::String("Constellation Studio".to_string()),
+                min_value: None,
+                max_value: None,
+                description: "Name receivers see for this NDI source".to_string(),
+            },
+        );
+        parameters.insert(
+            "groups".to_string(),
+            ParameterDefinition {
+                name: "Groups".to_string(),
+                parameter_type: ParameterType::String,
+                default_value: Value::String(String::new()),
+                min_value: None,
+                max_value: None,
+                description: "Comma separated NDI groups (empty = public)".to_string(),
+            },
+        );
+        parameters.insert(
+            "frame_format".to_string(),
+            ParameterDefinition {
+                name: "Frame Format".to_string(),
+                parameter_type: ParameterType::Enum(vec![
+                    "BGRA".to_string(),
+                    "RGBA".to_string(),
+                    "UYVY".to_string(),
+                ]),
+                default_value: Value::String("BGRA".to_string()),
+                min_value: None,
+                max_value: None,
+                description: "Pixel format sent over the network".to_string(),
+            },
+        );
+        parameters.insert(
+            "frame_rate".to_string(),
+            ParameterDefinition {
+                name: "Frame Rate".to_string(),
+                parameter_type: ParameterType::Float,
+                default_value: Value::from(30.0),
+                min_value: Some(Value::from(1.0)),
+                max_value: Some(Value::from(240.0)),
+                description: "Frame rate advertised to receivers".to_string(),
+            },
+        );
+
+        let properties = NodeProperties {
+            id,
+            name: "NDI Output".to_string(),
+            node_type: NodeType::Output(OutputType::Ndi),
+            input_types: vec![ConnectionType::RenderData],
+            output_types: vec![],
+            parameters,
+            enabled: true,
+        };
+
+        Ok(Self {
+            id,
+            config,
+            properties,
+            sender: None,
+            frames_sent: 0,
+        })
+    }
+
+    fn string_parameter(&self, key: &str, default: &str) -> String {
+        self.get_parameter(key)
+            .and_then(|v| v.as_str().map(str::to_string))
+            .unwrap_or_else(|| default.to_string())
+    }
+
+    pub fn frame_format(&self) -> Result<NdiFrameFormat> {
+        let name = self.string_parameter("frame_format", "BGRA");
+        NdiFrameFormat::from_name(&name)
+            .with_context(|| format!("Unknown NDI frame format: {}", name))
+    }
+
+    /// Frame rate as the numerator/denominator pair NDI expects
+    pub fn frame_rate(&self) -> (i32, i32) {
+        let fps = self
+            .get_parameter("frame_rate")
+            .and_then(|v| v.as_f64())
+            .filter(|fps| *fps > 0.0)
+            .unwrap_or(30.0);
+        ((fps * 1000.0).round() as i32, 1000)
+    }
+
+    pub fn frames_sent(&self) -> u64 {
+        self.frames_sent
+    }
+
+    /// Receivers connected to the source, or None before it is created
+    pub fn connections(&self) -> Option<usize> {
+        self.sender.as_ref().map(|sender| sender.connections())
+    }
+
+    fn ensure_sender(&mut self) -> Result<&mut runtime::NdiSender> {
+        if self.sender.is_none() {
+            let source_name = self.string_parameter("source_name", "Constellation Studio");
+            let groups = self.string_parameter("groups", "");
+            let sender = runtime::NdiSender::new(&source_name, &groups)
+                .with_context(|| format!("Failed to create NDI source \"{}\"", source_name))?;
+            info!("NDI source \"{}\" created", source_name);
+            self.sender = Some(sender);
+        }
+        Ok(self.sender.as_mut().expect("sender was just created"))
+    }
+}
+
+impl NodeProcessor for NdiOutputNode {
+    fn process(&mut self, input: FrameData) -> Result<FrameData> {
+        if let Some(RenderData::Raster2D(ref frame)) = input.render_data {
+            let ndi_frame = convert_frame(frame, self.frame_format()?)?;
+            let frame_rate = self.frame_rate();
+            let result = self
+                .ensure_sender()
+                .and_then(|sender| sender.send(&ndi_frame, frame_rate));
+            if let Err(e) = result {
+                warn!("NDI send failed: {:#}", e);
+                return Err(e.context("NDI send failed"));
+            }
+            self.frames_sent += 1;
+        }
+
+        Ok(input)
+    }
+
+    fn get_properties(&self) -> NodeProperties {
+        self.properties.clone()
+    }
+
+    fn on_start(&mut self) -> Result<()> {
+        self.ensure_sender().map(|_| ())
+    }
+
+    fn on_stop(&mut self) -> Result<()> {
+        self.sender = None;
+        Ok(())
+    }
+
+    fn set_parameter(&mut self, key: &str, value: Value) -> Result<()> {
+        if key == "frame_format" {
+            if let Some(name) = value.as_str() {
+                if NdiFrameFormat::from_name(name).is_none() {
+                    bail!("Unknown NDI frame format: {}", name);
+                }
+            }
+        }
+        // The source is announced under its name and groups, so re-create it
+        if key == "source_name" || key == "groups" {
+            self.sender = None;
+        }
+        self.config.parameters.insert(key.to_string(), value);
+        Ok(())
+    }
+
+    fn get_parameter(&self, key: &str) -> Option<Value> {
+        self.config.parameters.get(key).cloned()
+    }
+}
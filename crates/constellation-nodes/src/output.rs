@@ -16,12 +16,19 @@
  * along with this program. If not, see <https://www.gnu.org/licenses/>.
  */
 
+use crate::audio_device::{self, DeviceConfig, DeviceStream};
 use crate::virtual_camera::VirtualWebcamBackend;
 use crate::{NodeProcessor, NodeProperties, ParameterDefinition, ParameterType};
-use anyhow::Result;
+use anyhow::{bail, Result};
+use constellation_audio::{AudioProcessor, StreamResampler};
 use constellation_core::*;
+use image::{codecs::png::PngEncoder, ExtendedColorType, ImageEncoder};
+use serde::{Deserialize, Serialize};
 use serde_json::Value;
-use std::collections::HashMap;
+use std::borrow::Cow;
+use std::collections::{HashMap, VecDeque};
+use std::path::{Path, PathBuf};
+use std::time::{Duration, Instant};
 use uuid::Uuid;
 
 #[cfg(target_os = "linux")]
@@ -86,6 +93,7 @@ impl VirtualWebcamNode {
             input_types: vec![ConnectionType::RenderData, ConnectionType::Audio],
             output_types: vec![],
             parameters,
+            enabled: true,
         };
 
         Ok(Self {
@@ -194,6 +202,8 @@ pub struct PreviewNode {
     id: Uuid,
     config: NodeConfig,
     properties: NodeProperties,
+    // Most recent input frame, downscaled for the web preview
+    preview_frame: Option<VideoFrame>,
 }
 
 impl PreviewNode {
@@ -221,6 +231,28 @@ impl PreviewNode {
                 description: "Show performance statistics".to_string(),
             },
         );
+        parameters.insert(
+            "preview_width".to_string(),
+            ParameterDefinition {
+                name: "Preview Width".to_string(),
+                parameter_type: ParameterType::Integer,
+                default_value: Value::from(640),
+                min_value: Some(Value::from(16)),
+                max_value: Some(Value::from(3840)),
+                description: "Width of the letterboxed preview frame".to_string(),
+            },
+        );
+        parameters.insert(
+            "preview_height".to_string(),
+            ParameterDefinition {
+                name: "Preview Height".to_string(),
+                parameter_type: ParameterType::Integer,
+                default_value: Value::from(360),
+                min_value: Some(Value::from(16)),
+                max_value: Some(Value::from(2160)),
+                description: "Height of the letterboxed preview frame".to_string(),
+            },
+        );
 
         let properties = NodeProperties {
             id,
@@ -229,18 +261,376 @@ impl PreviewNode {
             input_types: vec![ConnectionType::RenderData, ConnectionType::Audio],
             output_types: vec![],
             parameters,
+            enabled: true,
         };
 
         Ok(Self {
             id,
             config,
             properties,
+            preview_frame: None,
         })
     }
+
+    /// Latest preview frame, scaled to fit `preview_width` x `preview_height`
+    pub fn get_preview_frame(&self) -> Option<VideoFrame> {
+        self.preview_frame.clone()
+    }
+
+    fn preview_size(&self) -> (u32, u32) {
+        let dimension = |key: &str, default: u64| {
+            self.get_parameter(key)
+                .and_then(|v| v.as_u64())
+                .unwrap_or(default)
+                .clamp(1, u32::MAX as u64) as u32
+        };
+        (
+            dimension("preview_width", 640),
+            dimension("preview_height", 360),
+        )
+    }
 }
 
 impl NodeProcessor for PreviewNode {
     fn process(&mut self, input: FrameData) -> Result<FrameData> {
+        if let Some(RenderData::Raster2D(ref frame)) = input.render_data {
+            let (width, height) = self.preview_size();
+            if let Some(preview) = scale_to_fit(frame, width, height) {
+                self.preview_frame = Some(preview);
+            }
+        }
+
+        Ok(input)
+    }
+
+    fn get_properties(&self) -> NodeProperties {
+        self.properties.clone()
+    }
+
+    fn set_parameter(&mut self, key: &str, value: Value) -> Result<()> {
+        self.config.parameters.insert(key.to_string(), value);
+        Ok(())
+    }
+
+    fn get_parameter(&self, key: &str) -> Option<Value> {
+        self.config.parameters.get(key).cloned()
+    }
+
+    // The web layer consumes RGBA previews
+    fn input_video_format(&self) -> Option<VideoFormat> {
+        Some(VideoFormat::Rgba8)
+    }
+}
+
+/// Bilinearly scale a packed frame into a `width` x `height` box, letterboxing
+/// with opaque black to preserve the source aspect ratio.
+///
+/// Returns `None` for formats that are not packed RGB(A)/BGR(A).
+pub fn scale_to_fit(frame: &VideoFrame, width: u32, height: u32) -> Option<VideoFrame> {
+    let channels = match frame.format {
+        VideoFormat::Rgba8 | VideoFormat::Bgra8 => 4,
+        VideoFormat::Rgb8 | VideoFormat::Bgr8 => 3,
+        _ => return None,
+    };
+    let (src_width, src_height) = (frame.width as usize, frame.height as usize);
+    if src_width == 0
+        || src_height == 0
+        || width == 0
+        || height == 0
+        || frame.data.len() < src_width * src_height * channels
+    {
+        return None;
+    }
+
+    let (dst_width, dst_height) = (width as usize, height as usize);
+    let scale = (dst_width as f32 / src_width as f32).min(dst_height as f32 / src_height as f32);
+    let content_width = ((src_width as f32 * scale).round() as usize).clamp(1, dst_width);
+    let content_height = ((src_height as f32 * scale).round() as usize).clamp(1, dst_height);
+    let offset_x = (dst_width - content_width) / 2;
+    let offset_y = (dst_height - content_height) / 2;
+
+    let mut background = vec![0u8; channels];
+    if channels == 4 {
+        background[3] = 255;
+    }
+    let mut data = background.repeat(dst_width * dst_height);
+
+    let x_ratio = src_width as f32 / content_width as f32;
+    let y_ratio = src_height as f32 / content_height as f32;
+    let pixel =
+        |x: usize, y: usize, c: usize| frame.data[(y * src_width + x) * channels + c] as f32;
+
+    for y in 0..content_height {
+        let src_y = ((y as f32 + 0.5) * y_ratio - 0.5).clamp(0.0, (src_height - 1) as f32);
+        let y0 = src_y.floor() as usize;
+        let y1 = (y0 + 1).min(src_height - 1);
+        let fy = src_y - y0 as f32;
+
+        for x in 0..content_width {
+            let src_x = ((x as f32 + 0.5) * x_ratio - 0.5).clamp(0.0, (src_width - 1) as f32);
+            let x0 = src_x.floor() as usize;
+            let x1 = (x0 + 1).min(src_width - 1);
+            let fx = src_x - x0 as f32;
+
+            let out = ((offset_y + y) * dst_width + offset_x + x) * channels;
+            for c in 0..channels {
+                let top = pixel(x0, y0, c) * (1.0 - fx) + pixel(x1, y0, c) * fx;
+                let bottom = pixel(x0, y1, c) * (1.0 - fx) + pixel(x1, y1, c) * fx;
+                data[out + c] = (top * (1.0 - fy) + bottom * fy).round() as u8;
+            }
+        }
+    }
+
+    Some(VideoFrame {
+        width,
+        height,
+        format: frame.format.clone(),
+        data,
+    })
+}
+
+/// Keeps the last `duration` seconds of raster frames in memory and writes
+/// them to disk when the `dump` parameter is triggered.
+pub struct RecorderNode {
+    id: Uuid,
+    config: NodeConfig,
+    properties: NodeProperties,
+    // Oldest frame at the front; capacity is duration * frame_rate
+    buffer: VecDeque<VideoFrame>,
+    last_dump: Option<PathBuf>,
+}
+
+impl RecorderNode {
+    pub fn new(id: Uuid, config: NodeConfig) -> Result<Self> {
+        let mut parameters = HashMap::new();
+        parameters.insert(
+            "duration".to_string(),
+            ParameterDefinition {
+                name: "Duration".to_string(),
+                parameter_type: ParameterType::Float,
+                default_value: Value::from(10.0),
+                min_value: Some(Value::from(0.1)),
+                max_value: Some(Value::from(300.0)),
+                description: "Seconds of video kept in the buffer".to_string(),
+            },
+        );
+        parameters.insert(
+            "frame_rate".to_string(),
+            ParameterDefinition {
+                name: "Frame Rate".to_string(),
+                parameter_type: ParameterType::Float,
+                default_value: Value::from(30.0),
+                min_value: Some(Value::from(1.0)),
+                max_value: Some(Value::from(120.0)),
+                description: "Expected input frame rate, used to size the buffer".to_string(),
+            },
+        );
+        parameters.insert(
+            "max_width".to_string(),
+            ParameterDefinition {
+                name: "Max Width".to_string(),
+                parameter_type: ParameterType::Integer,
+                default_value: Value::from(1920),
+                min_value: Some(Value::from(16)),
+                max_value: Some(Value::from(7680)),
+                description: "Frames wider than this are downscaled before buffering".to_string(),
+            },
+        );
+        parameters.insert(
+            "max_height".to_string(),
+            ParameterDefinition {
+                name: "Max Height".to_string(),
+                parameter_type: ParameterType::Integer,
+                default_value: Value::from(1080),
+                min_value: Some(Value::from(16)),
+                max_value: Some(Value::from(4320)),
+                description: "Frames taller than this are downscaled before buffering".to_string(),
+            },
+        );
+        parameters.insert(
+            "output_directory".to_string(),
+            ParameterDefinition {
+                name: "Output Directory".to_string(),
+                parameter_type: ParameterType::String,
+                default_value: Value::String(
+                    std::env::temp_dir()
+                        .join("constellation-recordings")
+                        .to_string_lossy()
+                        .into_owned(),
+                ),
+                min_value: None,
+                max_value: None,
+                description: "Directory that dumps are written into".to_string(),
+            },
+        );
+        parameters.insert(
+            "dump_format".to_string(),
+            ParameterDefinition {
+                name: "Dump Format".to_string(),
+                parameter_type: ParameterType::Enum(vec![
+                    "PNG Sequence".to_string(),
+                    "Raw".to_string(),
+                ]),
+                default_value: Value::String("PNG Sequence".to_string()),
+                min_value: None,
+                max_value: None,
+                description: "File format of dumped frames".to_string(),
+            },
+        );
+        parameters.insert(
+            "dump".to_string(),
+            ParameterDefinition {
+                name: "Dump".to_string(),
+                parameter_type: ParameterType::Boolean,
+                default_value: Value::Bool(false),
+                min_value: None,
+                max_value: None,
+                description: "Write the buffered frames to disk when set".to_string(),
+            },
+        );
+
+        let properties = NodeProperties {
+            id,
+            name: "Recorder".to_string(),
+            node_type: NodeType::Output(OutputType::Recorder),
+            input_types: vec![ConnectionType::RenderData, ConnectionType::Audio],
+            output_types: vec![],
+            parameters,
+            enabled: true,
+        };
+
+        Ok(Self {
+            id,
+            config,
+            properties,
+            buffer: VecDeque::new(),
+            last_dump: None,
+        })
+    }
+
+    /// Number of frames currently held in the ring buffer
+    pub fn buffered_frames(&self) -> usize {
+        self.buffer.len()
+    }
+
+    /// Maximum number of frames the buffer holds for the configured duration
+    pub fn capacity(&self) -> usize {
+        let number = |key: &str, default: f64| {
+            self.get_parameter(key)
+                .and_then(|v| v.as_f64())
+                .filter(|v| v.is_finite() && *v > 0.0)
+                .unwrap_or(default)
+        };
+        ((number("duration", 10.0) * number("frame_rate", 30.0)).ceil() as usize).max(1)
+    }
+
+    /// Directory written by the most recent dump
+    pub fn last_dump_path(&self) -> Option<&Path> {
+        self.last_dump.as_deref()
+    }
+
+    /// Write every buffered frame into a new directory under
+    /// `output_directory` and return that directory.
+    pub fn dump(&mut self) -> Result<PathBuf> {
+        let output_directory = self
+            .get_parameter("output_directory")
+            .and_then(|v| v.as_str().map(PathBuf::from))
+            .unwrap_or_else(|| std::env::temp_dir().join("constellation-recordings"));
+        let raw = self
+            .get_parameter("dump_format")
+            .and_then(|v| v.as_str().map(String::from))
+            == Some("Raw".to_string());
+
+        let millis = std::time::SystemTime::now()
+            .duration_since(std::time::UNIX_EPOCH)
+            .unwrap_or_default()
+            .as_millis();
+        let mut directory = output_directory.join(format!("recording-{millis}"));
+        let mut suffix = 1;
+        while directory.exists() {
+            directory = output_directory.join(format!("recording-{millis}-{suffix}"));
+            suffix += 1;
+        }
+        std::fs::create_dir_all(&directory)?;
+
+        for (index, frame) in self.buffer.iter().enumerate() {
+            if raw {
+                std::fs::write(directory.join(format!("frame_{index:05}.raw")), &frame.data)?;
+            } else {
+                std::fs::write(
+                    directory.join(format!("frame_{index:05}.png")),
+                    encode_png(frame)?,
+                )?;
+            }
+        }
+
+        // Raw dumps carry no header, so record the geometry alongside them
+        if raw {
+            let manifest = self
+                .buffer
+                .iter()
+                .enumerate()
+                .map(|(index, frame)| {
+                    serde_json::json!({
+                        "file": format!("frame_{index:05}.raw"),
+                        "width": frame.width,
+                        "height": frame.height,
+                        "format": format!("{:?}", frame.format),
+                    })
+                })
+                .collect::<Vec<_>>();
+            std::fs::write(
+                directory.join("manifest.json"),
+                serde_json::to_vec_pretty(&manifest)?,
+            )?;
+        }
+
+        tracing::info!(
+            "Recorder {} dumped {} frames to {}",
+            self.id,
+            self.buffer.len(),
+            directory.display()
+        );
+        self.last_dump = Some(directory.clone());
+        Ok(directory)
+    }
+
+    fn push_frame(&mut self, frame: &VideoFrame) {
+        let dimension = |key: &str, default: u64| {
+            self.get_parameter(key)
+                .and_then(|v| v.as_u64())
+                .unwrap_or(default)
+                .clamp(1, u32::MAX as u64) as u32
+        };
+        let (max_width, max_height) = (dimension("max_width", 1920), dimension("max_height", 1080));
+
+        let frame = if frame.width > max_width || frame.height > max_height {
+            let scale = (max_width as f32 / frame.width as f32)
+                .min(max_height as f32 / frame.height as f32);
+            let width = ((frame.width as f32 * scale).round() as u32).max(1);
+            let height = ((frame.height as f32 * scale).round() as u32).max(1);
+            match scale_to_fit(frame, width, height) {
+                Some(scaled) => scaled,
+                None => return,
+            }
+        } else {
+            frame.clone()
+        };
+
+        let capacity = self.capacity();
+        while self.buffer.len() >= capacity {
+            self.buffer.pop_front();
+        }
+        self.buffer.push_back(frame);
+    }
+}
+
+impl NodeProcessor for RecorderNode {
+    fn process(&mut self, input: FrameData) -> Result<FrameData> {
+        if let Some(RenderData::Raster2D(ref frame)) = input.render_data {
+            self.push_frame(frame);
+        }
+
         Ok(input)
     }
 
@@ -249,6 +639,17 @@ impl NodeProcessor for PreviewNode {
     }
 
     fn set_parameter(&mut self, key: &str, value: Value) -> Result<()> {
+        // `dump` acts as a trigger and never stays set
+        if key == "dump" {
+            if value.as_bool() == Some(true) {
+                self.dump()?;
+            }
+            self.config
+                .parameters
+                .insert(key.to_string(), Value::Bool(false));
+            return Ok(());
+        }
+
         self.config.parameters.insert(key.to_string(), value);
         Ok(())
     }
@@ -258,10 +659,255 @@ impl NodeProcessor for PreviewNode {
     }
 }
 
+/// Encode a packed RGB(A)/BGR(A) frame as PNG
+pub fn encode_png(frame: &VideoFrame) -> Result<Vec<u8>> {
+    let (color_type, channels, swap_red_blue) = match frame.format {
+        VideoFormat::Rgba8 => (ExtendedColorType::Rgba8, 4, false),
+        VideoFormat::Bgra8 => (ExtendedColorType::Rgba8, 4, true),
+        VideoFormat::Rgb8 => (ExtendedColorType::Rgb8, 3, false),
+        VideoFormat::Bgr8 => (ExtendedColorType::Rgb8, 3, true),
+        VideoFormat::Png => return Ok(frame.data.clone()),
+        ref format => bail!("Cannot encode {:?} frames as PNG", format),
+    };
+
+    let expected_len = frame.width as usize * frame.height as usize * channels;
+    if frame.data.len() != expected_len {
+        bail!(
+            "Frame data is {} bytes, expected {} for {}x{}",
+            frame.data.len(),
+            expected_len,
+            frame.width,
+            frame.height
+        );
+    }
+
+    let pixels = if swap_red_blue {
+        let mut swapped = frame.data.clone();
+        for pixel in swapped.chunks_exact_mut(channels) {
+            pixel.swap(0, 2);
+        }
+        Cow::Owned(swapped)
+    } else {
+        Cow::Borrowed(&frame.data)
+    };
+
+    let mut png = Vec::new();
+    PngEncoder::new(&mut png).write_image(&pixels, frame.width, frame.height, color_type)?;
+    Ok(png)
+}
+
+/// Luma statistics for one vertical slice of a frame
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct WaveformColumn {
+    pub min: u8,
+    pub max: u8,
+    pub mean: f32,
+}
+
+/// Per-channel histograms plus a luma waveform summary of one frame
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct FrameHistogram {
+    pub width: u32,
+    pub height: u32,
+    pub red: Vec<u32>,
+    pub green: Vec<u32>,
+    pub blue: Vec<u32>,
+    /// Rec. 709 luma
+    pub luma: Vec<u32>,
+    /// Left to right, one entry per column group
+    pub waveform: Vec<WaveformColumn>,
+}
+
+impl FrameHistogram {
+    pub fn bin_count(&self) -> usize {
+        self.luma.len()
+    }
+}
+
+const LUMA_WEIGHTS: [f32; 3] = [0.2126, 0.7152, 0.0722];
+
+/// Histogram `bins` equal-width buckets per channel over 0..=255 and summarize
+/// luma in `waveform_columns` vertical slices.
+///
+/// Returns `None` for formats that are not packed RGB(A)/BGR(A) or when the
+/// frame is empty.
+pub fn compute_histogram(
+    frame: &VideoFrame,
+    bins: usize,
+    waveform_columns: usize,
+) -> Option<FrameHistogram> {
+    let layout = frame.format.pixel_layout()?;
+    let (width, height) = (frame.width as usize, frame.height as usize);
+    if width == 0
+        || height == 0
+        || bins == 0
+        || frame.data.len() < width * height * layout.bytes_per_pixel
+    {
+        return None;
+    }
+
+    let columns = waveform_columns.clamp(1, width);
+    let bin_of = |value: u8| value as usize * bins / 256;
+    let mut red = vec![0u32; bins];
+    let mut green = vec![0u32; bins];
+    let mut blue = vec![0u32; bins];
+    let mut luma = vec![0u32; bins];
+    let mut column_min = vec![u8::MAX; columns];
+    let mut column_max = vec![u8::MIN; columns];
+    let mut column_sum = vec![0u64; columns];
+    let mut column_count = vec![0u64; columns];
+
+    for (index, pixel) in frame.pixel_iter().enumerate() {
+        let [r, g, b] = layout.rgb(pixel);
+        red[bin_of(r)] += 1;
+        green[bin_of(g)] += 1;
+        blue[bin_of(b)] += 1;
+
+        let y =
+            (LUMA_WEIGHTS[0] * r as f32 + LUMA_WEIGHTS[1] * g as f32 + LUMA_WEIGHTS[2] * b as f32)
+                .round()
+                .clamp(0.0, 255.0) as u8;
+        luma[bin_of(y)] += 1;
+
+        let column = (index % width) * columns / width;
+        column_min[column] = column_min[column].min(y);
+        column_max[column] = column_max[column].max(y);
+        column_sum[column] += y as u64;
+        column_count[column] += 1;
+    }
+
+    let waveform = (0..columns)
+        .map(|column| WaveformColumn {
+            min: column_min[column],
+            max: column_max[column],
+            mean: column_sum[column] as f32 / column_count[column] as f32,
+        })
+        .collect();
+
+    Some(FrameHistogram {
+        width: frame.width,
+        height: frame.height,
+        red,
+        green,
+        blue,
+        luma,
+        waveform,
+    })
+}
+
+/// Analysis output that measures incoming frames for scopes (histogram and
+/// luma waveform). Frames pass through unchanged.
+pub struct ScopeNode {
+    id: Uuid,
+    config: NodeConfig,
+    properties: NodeProperties,
+    histogram: Option<FrameHistogram>,
+}
+
+impl ScopeNode {
+    pub fn new(id: Uuid, config: NodeConfig) -> Result<Self> {
+        let mut parameters = HashMap::new();
+        parameters.insert(
+            "bins".to_string(),
+            ParameterDefinition {
+                name: "Bins".to_string(),
+                parameter_type: ParameterType::Integer,
+                default_value: Value::from(256),
+                min_value: Some(Value::from(2)),
+                max_value: Some(Value::from(256)),
+                description: "Number of histogram buckets per channel".to_string(),
+            },
+        );
+        parameters.insert(
+            "waveform_columns".to_string(),
+            ParameterDefinition {
+                name: "Waveform Columns".to_string(),
+                parameter_type: ParameterType::Integer,
+                default_value: Value::from(128),
+                min_value: Some(Value::from(1)),
+                max_value: Some(Value::from(1920)),
+                description: "Horizontal resolution of the luma waveform".to_string(),
+            },
+        );
+
+        let properties = NodeProperties {
+            id,
+            name: "Scope".to_string(),
+            node_type: NodeType::Output(OutputType::Scope),
+            input_types: vec![ConnectionType::RenderData],
+            output_types: vec![],
+            parameters,
+            enabled: true,
+        };
+
+        Ok(Self {
+            id,
+            config,
+            properties,
+            histogram: None,
+        })
+    }
+
+    /// Analysis of the most recent raster frame
+    pub fn histogram(&self) -> Option<&FrameHistogram> {
+        self.histogram.as_ref()
+    }
+
+    fn integer_parameter(&self, key: &str, default: u64, max: u64) -> usize {
+        self.get_parameter(key)
+            .and_then(|v| v.as_u64())
+            .unwrap_or(default)
+            .clamp(1, max) as usize
+    }
+}
+
+impl NodeProcessor for ScopeNode {
+    fn process(&mut self, input: FrameData) -> Result<FrameData> {
+        if let Some(RenderData::Raster2D(ref frame)) = input.render_data {
+            let bins = self.integer_parameter("bins", 256, 256).max(2);
+            let columns = self.integer_parameter("waveform_columns", 128, 1920);
+            match compute_histogram(frame, bins, columns) {
+                Some(histogram) => self.histogram = Some(histogram),
+                None => {
+                    tracing::debug!("Scope {} cannot analyze {:?} frames", self.id, frame.format)
+                }
+            }
+        }
+
+        Ok(input)
+    }
+
+    fn get_properties(&self) -> NodeProperties {
+        self.properties.clone()
+    }
+
+    fn set_parameter(&mut self, key: &str, value: Value) -> Result<()> {
+        self.config.parameters.insert(key.to_string(), value);
+        Ok(())
+    }
+
+    fn get_parameter(&self, key: &str) -> Option<Value> {
+        self.config.parameters.get(key).cloned()
+    }
+}
+
+/// How long a failed or disconnected audio device waits before it is reopened
+const AUDIO_DEVICE_RETRY: Duration = Duration::from_secs(2);
+
+/// Capture ring size; the node drains it every frame
+const AUDIO_DEVICE_BUFFER: Duration = Duration::from_millis(500);
+/// Silent frames emitted per process call while no input device is open
+const AUDIO_INPUT_SILENT_FRAMES: usize = 512;
+
 pub struct AudioInputNode {
     id: Uuid,
     config: NodeConfig,
     properties: NodeProperties,
+    stream: Option<DeviceStream>,
+    // Earliest time to try opening the device again after it failed or went away
+    retry_at: Option<Instant>,
+    // Converts the device format across blocks without clicks at block edges
+    resampler: StreamResampler,
 }
 
 impl AudioInputNode {
@@ -272,10 +918,33 @@ impl AudioInputNode {
             ParameterDefinition {
                 name: "Device ID".to_string(),
                 parameter_type: ParameterType::String,
-                default_value: Value::String("default".to_string()),
+                default_value: Value::String(audio_device::DEFAULT_DEVICE.to_string()),
                 min_value: None,
                 max_value: None,
-                description: "Audio input device".to_string(),
+                description: "Audio input device (name substring, \"default\" = system default)"
+                    .to_string(),
+            },
+        );
+        parameters.insert(
+            "channels".to_string(),
+            ParameterDefinition {
+                name: "Channels".to_string(),
+                parameter_type: ParameterType::Integer,
+                default_value: Value::from(2),
+                min_value: Some(Value::from(1)),
+                max_value: Some(Value::from(8)),
+                description: "Channel count delivered to the pipeline".to_string(),
+            },
+        );
+        parameters.insert(
+            "sample_rate".to_string(),
+            ParameterDefinition {
+                name: "Sample Rate".to_string(),
+                parameter_type: ParameterType::Integer,
+                default_value: Value::from(48000),
+                min_value: Some(Value::from(8000)),
+                max_value: Some(Value::from(192000)),
+                description: "Pipeline sample rate the capture is converted to".to_string(),
             },
         );
 
@@ -286,27 +955,133 @@ impl AudioInputNode {
             input_types: vec![],
             output_types: vec![ConnectionType::Audio],
             parameters,
+            enabled: true,
         };
 
         Ok(Self {
             id,
             config,
             properties,
+            stream: None,
+            retry_at: None,
+            resampler: StreamResampler::new(),
         })
     }
+
+    fn device_id(&self) -> String {
+        self.get_parameter("device_id")
+            .and_then(|v| v.as_str().map(str::to_string))
+            .unwrap_or_else(|| audio_device::DEFAULT_DEVICE.to_string())
+    }
+
+    pub fn channels(&self) -> u16 {
+        self.get_parameter("channels")
+            .and_then(|v| v.as_u64())
+            .unwrap_or(2)
+            .clamp(1, 8) as u16
+    }
+
+    pub fn sample_rate(&self) -> u32 {
+        self.get_parameter("sample_rate")
+            .and_then(|v| v.as_u64())
+            .unwrap_or(48000)
+            .clamp(8000, 192000) as u32
+    }
+
+    /// True while a capture stream is open
+    pub fn is_capturing(&self) -> bool {
+        self.stream.is_some()
+    }
+
+    /// Format of the open capture device
+    pub fn device_config(&self) -> Option<DeviceConfig> {
+        self.stream.as_ref().map(|stream| stream.config().clone())
+    }
+
+    /// Open the device if needed; a failed or disconnected device is retried
+    /// after `AUDIO_DEVICE_RETRY`
+    fn ensure_stream(&mut self) {
+        if let Some(stream) = &self.stream {
+            if !stream.is_disconnected() {
+                return;
+            }
+            tracing::warn!(
+                "Audio input device {} disconnected",
+                stream.config().device_name
+            );
+            self.close_stream();
+            self.retry_at = Some(Instant::now() + AUDIO_DEVICE_RETRY);
+        }
+        if self.retry_at.is_some_and(|at| Instant::now() < at) {
+            return;
+        }
+
+        match DeviceStream::open_input(&self.device_id(), AUDIO_DEVICE_BUFFER) {
+            Ok(stream) => {
+                self.stream = Some(stream);
+                self.retry_at = None;
+            }
+            Err(e) => {
+                tracing::warn!("Audio input unavailable: {:#}", e);
+                self.retry_at = Some(Instant::now() + AUDIO_DEVICE_RETRY);
+            }
+        }
+    }
+
+    fn close_stream(&mut self) {
+        self.stream = None;
+        self.resampler.reset();
+    }
+
+    /// Everything captured since the last call, converted to the pipeline format
+    fn read_captured(&mut self, channels: u16, sample_rate: u32) -> Result<Vec<f32>> {
+        let Some(stream) = &self.stream else {
+            // No device: keep downstream running on silence
+            return Ok(vec![0.0; AUDIO_INPUT_SILENT_FRAMES * channels as usize]);
+        };
+
+        let config = stream.config();
+        let ring = stream.ring();
+        let available = ring.len();
+        let captured = ring.drain(available - available % config.channels as usize);
+        if captured.is_empty() {
+            return Ok(Vec::new());
+        }
+
+        let frame = AudioFrame {
+            sample_rate: config.sample_rate,
+            channels: config.channels,
+            samples: captured,
+        };
+        if frame.sample_rate == sample_rate && frame.channels == channels {
+            return Ok(frame.samples);
+        }
+        Ok(self
+            .resampler
+            .resample(&frame, sample_rate, channels)?
+            .samples)
+    }
 }
 
 impl NodeProcessor for AudioInputNode {
     fn process(&mut self, _input: FrameData) -> Result<FrameData> {
+        self.ensure_stream();
+        let channels = self.channels();
+        let sample_rate = self.sample_rate();
+        let samples = self.read_captured(channels, sample_rate)?;
+
         Ok(FrameData {
             render_data: None,
             audio_data: Some(UnifiedAudioData::Stereo {
-                sample_rate: 48000,
-                channels: 2,
-                samples: vec![0.0; 1024],
+                sample_rate,
+                channels,
+                samples,
             }),
             control_data: None,
             tally_metadata: TallyMetadata::new(),
+            timestamp: Duration::ZERO,
+            sequence: 0,
+            checksum: None,
         })
     }
 
@@ -314,7 +1089,18 @@ impl NodeProcessor for AudioInputNode {
         self.properties.clone()
     }
 
+    fn on_stop(&mut self) -> Result<()> {
+        self.close_stream();
+        self.retry_at = None;
+        Ok(())
+    }
+
     fn set_parameter(&mut self, key: &str, value: Value) -> Result<()> {
+        if key == "device_id" {
+            // Switch devices on the next frame
+            self.close_stream();
+            self.retry_at = None;
+        }
         self.config.parameters.insert(key.to_string(), value);
         Ok(())
     }
@@ -324,24 +1110,154 @@ impl NodeProcessor for AudioInputNode {
     }
 }
 
+/// Mixer inputs that get gain/pan parameter definitions; later inputs still
+/// accept `input_<n>_gain` / `input_<n>_pan` but without range validation
+const MIXER_INPUT_PARAMETERS: usize = 8;
+
+/// Level where the master soft limiter starts bending the signal
+const SOFT_LIMIT_KNEE: f32 = 0.5;
+
 pub struct AudioMixerNode {
     id: Uuid,
     config: NodeConfig,
     properties: NodeProperties,
+    // Sidechain detector envelope (linear amplitude)
+    duck_envelope: f32,
+    // Gain applied to the ducked input at the end of the last block
+    duck_gain: f32,
+    // Per-input conversion to the mix format for inputs that don't match it
+    resamplers: HashMap<Uuid, StreamResampler>,
 }
 
 impl AudioMixerNode {
     pub fn new(id: Uuid, config: NodeConfig) -> Result<Self> {
         let mut parameters = HashMap::new();
         parameters.insert(
-            "master_volume".to_string(),
+            "master_volume".to_string(),
+            ParameterDefinition {
+                name: "Master Volume".to_string(),
+                parameter_type: ParameterType::Float,
+                default_value: Value::from(1.0),
+                min_value: Some(Value::from(0.0)),
+                max_value: Some(Value::from(2.0)),
+                description: "Master volume level".to_string(),
+            },
+        );
+        parameters.insert(
+            "master_limiter".to_string(),
+            ParameterDefinition {
+                name: "Master Limiter".to_string(),
+                parameter_type: ParameterType::Boolean,
+                default_value: Value::Bool(false),
+                min_value: None,
+                max_value: None,
+                description: "Soft-limit the master so peaks never reach full scale".to_string(),
+            },
+        );
+        for input in 0..MIXER_INPUT_PARAMETERS {
+            parameters.insert(
+                format!("input_{input}_gain"),
+                ParameterDefinition {
+                    name: format!("Input {} Gain", input + 1),
+                    parameter_type: ParameterType::Float,
+                    default_value: Value::from(0.0),
+                    min_value: Some(Value::from(-60.0)),
+                    max_value: Some(Value::from(12.0)),
+                    description: format!("Gain in dB for mixer input {}", input + 1),
+                },
+            );
+            parameters.insert(
+                format!("input_{input}_pan"),
+                ParameterDefinition {
+                    name: format!("Input {} Pan", input + 1),
+                    parameter_type: ParameterType::Float,
+                    default_value: Value::from(0.0),
+                    min_value: Some(Value::from(-1.0)),
+                    max_value: Some(Value::from(1.0)),
+                    description: format!(
+                        "Stereo position for mixer input {} (-1 left, 1 right)",
+                        input + 1
+                    ),
+                },
+            );
+        }
+        parameters.insert(
+            "ducking".to_string(),
+            ParameterDefinition {
+                name: "Ducking".to_string(),
+                parameter_type: ParameterType::Boolean,
+                default_value: Value::Bool(false),
+                min_value: None,
+                max_value: None,
+                description: "Duck the target input while the sidechain is active".to_string(),
+            },
+        );
+        parameters.insert(
+            "sidechain_channel".to_string(),
+            ParameterDefinition {
+                name: "Sidechain Channel".to_string(),
+                parameter_type: ParameterType::Integer,
+                default_value: Value::from(0),
+                min_value: Some(Value::from(0)),
+                max_value: Some(Value::from(63)),
+                description: "Mixer input (in connection order) that triggers ducking".to_string(),
+            },
+        );
+        parameters.insert(
+            "target_channel".to_string(),
+            ParameterDefinition {
+                name: "Target Channel".to_string(),
+                parameter_type: ParameterType::Integer,
+                default_value: Value::from(1),
+                min_value: Some(Value::from(0)),
+                max_value: Some(Value::from(63)),
+                description: "Mixer input (in connection order) that gets attenuated".to_string(),
+            },
+        );
+        parameters.insert(
+            "duck_threshold".to_string(),
+            ParameterDefinition {
+                name: "Duck Threshold".to_string(),
+                parameter_type: ParameterType::Float,
+                default_value: Value::from(-30.0),
+                min_value: Some(Value::from(-60.0)),
+                max_value: Some(Value::from(0.0)),
+                description: "Sidechain level in dBFS above which ducking starts".to_string(),
+            },
+        );
+        parameters.insert(
+            "duck_ratio".to_string(),
+            ParameterDefinition {
+                name: "Duck Ratio".to_string(),
+                parameter_type: ParameterType::Float,
+                default_value: Value::from(4.0),
+                min_value: Some(Value::from(1.0)),
+                max_value: Some(Value::from(20.0)),
+                description:
+                    "How strongly sidechain level over the threshold attenuates the target"
+                        .to_string(),
+            },
+        );
+        parameters.insert(
+            "duck_attack".to_string(),
             ParameterDefinition {
-                name: "Master Volume".to_string(),
+                name: "Duck Attack".to_string(),
                 parameter_type: ParameterType::Float,
-                default_value: Value::from(1.0),
-                min_value: Some(Value::from(0.0)),
-                max_value: Some(Value::from(2.0)),
-                description: "Master volume level".to_string(),
+                default_value: Value::from(10.0),
+                min_value: Some(Value::from(0.1)),
+                max_value: Some(Value::from(500.0)),
+                description: "Time in milliseconds for ducking to engage".to_string(),
+            },
+        );
+        parameters.insert(
+            "duck_release".to_string(),
+            ParameterDefinition {
+                name: "Duck Release".to_string(),
+                parameter_type: ParameterType::Float,
+                default_value: Value::from(300.0),
+                min_value: Some(Value::from(1.0)),
+                max_value: Some(Value::from(5000.0)),
+                description: "Time in milliseconds for the target to recover".to_string(),
             },
         );
 
@@ -352,19 +1268,230 @@ impl AudioMixerNode {
             input_types: vec![ConnectionType::Audio],
             output_types: vec![ConnectionType::Audio],
             parameters,
+            enabled: true,
         };
 
         Ok(Self {
             id,
             config,
             properties,
+            duck_envelope: 0.0,
+            duck_gain: 1.0,
+            resamplers: HashMap::new(),
+        })
+    }
+
+    /// Linear gain currently applied to the ducked input (1.0 when not ducking)
+    pub fn ducking_gain(&self) -> f32 {
+        self.duck_gain
+    }
+}
+
+impl AudioMixerNode {
+    fn float_parameter(&self, key: &str, default: f32) -> f32 {
+        self.get_parameter(key)
+            .and_then(|v| v.as_f64())
+            .map(|v| v as f32)
+            .unwrap_or(default)
+    }
+
+    fn master_volume(&self) -> f32 {
+        self.float_parameter("master_volume", 1.0)
+    }
+
+    /// Per-channel gain for one input: its dB gain, plus equal-power panning for
+    /// stereo. The pan law is normalized so a centered input keeps unity gain.
+    fn channel_gains(&self, input: usize, channels: usize) -> Vec<f32> {
+        let gain = db_to_linear(self.float_parameter(&format!("input_{input}_gain"), 0.0));
+        if channels != 2 {
+            return vec![gain; channels];
+        }
+
+        let pan = self
+            .float_parameter(&format!("input_{input}_pan"), 0.0)
+            .clamp(-1.0, 1.0);
+        let angle = (pan + 1.0) * std::f32::consts::FRAC_PI_4;
+        let gain = gain * std::f32::consts::SQRT_2;
+        vec![gain * angle.cos(), gain * angle.sin()]
+    }
+
+    /// Per-frame gains for the target input, driven by the sidechain input's envelope
+    ///
+    /// Returns the target input index and one gain per interleaved sample frame,
+    /// or `None` when ducking is disabled.
+    fn ducking_gains(
+        &mut self,
+        sources: &[(usize, &[f32])],
+        sample_rate: u32,
+        channels: usize,
+        length: usize,
+    ) -> Option<(usize, Vec<f32>)> {
+        let enabled = self
+            .get_parameter("ducking")
+            .and_then(|v| v.as_bool())
+            .unwrap_or(false);
+        if !enabled || channels == 0 || sample_rate == 0 {
+            self.duck_envelope = 0.0;
+            self.duck_gain = 1.0;
+            return None;
+        }
+
+        let index_parameter = |key: &str, default: u64| {
+            self.get_parameter(key)
+                .and_then(|v| v.as_u64())
+                .unwrap_or(default) as usize
+        };
+        let sidechain_channel = index_parameter("sidechain_channel", 0);
+        let target_channel = index_parameter("target_channel", 1);
+
+        let threshold_db = self.float_parameter("duck_threshold", -30.0);
+        let slope = 1.0 - 1.0 / self.float_parameter("duck_ratio", 4.0).max(1.0);
+        let attack = smoothing_coefficient(self.float_parameter("duck_attack", 10.0), sample_rate);
+        let release =
+            smoothing_coefficient(self.float_parameter("duck_release", 300.0), sample_rate);
+
+        // A missing sidechain counts as silence so the target recovers
+        let sidechain: &[f32] = sources
+            .iter()
+            .find(|(index, _)| *index == sidechain_channel)
+            .map(|(_, samples)| *samples)
+            .unwrap_or(&[]);
+
+        let gains: Vec<f32> = (0..length.div_ceil(channels))
+            .map(|frame| {
+                let level = sidechain
+                    .iter()
+                    .skip(frame * channels)
+                    .take(channels)
+                    .fold(0.0f32, |peak, sample| peak.max(sample.abs()));
+                let coefficient = if level > self.duck_envelope {
+                    attack
+                } else {
+                    release
+                };
+                self.duck_envelope = level + coefficient * (self.duck_envelope - level);
+
+                let over_db = linear_to_db(self.duck_envelope) - threshold_db;
+                let gain_db = if over_db > 0.0 { -over_db * slope } else { 0.0 };
+                db_to_linear(gain_db)
+            })
+            .collect();
+
+        if let Some(&gain) = gains.last() {
+            self.duck_gain = gain;
+        }
+        Some((target_channel, gains))
+    }
+
+    /// Sum every stereo input, converted to the first one's sample rate and
+    /// channel count, after its gain, pan and ducking, then apply the master
+    /// volume and optional soft limiter
+    fn mix(&mut self, inputs: &[(Uuid, FrameData)]) -> Option<UnifiedAudioData> {
+        let mut format = None;
+        let mut converted: Vec<(usize, Cow<[f32]>)> = Vec::new();
+
+        // Drop the conversion state of inputs that went away
+        self.resamplers
+            .retain(|source_id, _| inputs.iter().any(|(id, _)| id == source_id));
+        for (index, (source_id, frame)) in inputs.iter().enumerate() {
+            let Some(UnifiedAudioData::Stereo {
+                sample_rate,
+                channels,
+                samples,
+            }) = &frame.audio_data
+            else {
+                continue;
+            };
+
+            let (mix_rate, mix_channels) = *format.get_or_insert((*sample_rate, *channels));
+            if (*sample_rate, *channels) == (mix_rate, mix_channels) {
+                self.resamplers.remove(source_id);
+                converted.push((index, Cow::Borrowed(samples)));
+                continue;
+            }
+
+            let frame = AudioFrame {
+                sample_rate: *sample_rate,
+                channels: *channels,
+                samples: samples.clone(),
+            };
+            match self.resamplers.entry(*source_id).or_default().resample(
+                &frame,
+                mix_rate,
+                mix_channels,
+            ) {
+                Ok(frame) => converted.push((index, Cow::Owned(frame.samples))),
+                Err(e) => tracing::warn!("Skipping mixer input {}: {:#}", source_id, e),
+            }
+        }
+        let sources: Vec<(usize, &[f32])> = converted
+            .iter()
+            .map(|(index, samples)| (*index, samples.as_ref()))
+            .collect();
+
+        let (sample_rate, channels) = format?;
+        let length = sources
+            .iter()
+            .map(|(_, samples)| samples.len())
+            .max()
+            .unwrap_or(0);
+        let ducking = self.ducking_gains(&sources, sample_rate, channels as usize, length);
+
+        let channel_count = (channels as usize).max(1);
+        let mut mixed = vec![0.0; length];
+        for (index, samples) in &sources {
+            let channel_gains = self.channel_gains(*index, channel_count);
+            let duck_gains = ducking
+                .as_ref()
+                .filter(|(target, _)| target == index)
+                .map(|(_, gains)| gains);
+
+            for (position, (out, sample)) in mixed.iter_mut().zip(*samples).enumerate() {
+                let mut gain = channel_gains[position % channel_count];
+                if let Some(duck_gains) = duck_gains {
+                    gain *= duck_gains[position / channel_count];
+                }
+                *out += sample * gain;
+            }
+        }
+
+        let volume = self.master_volume();
+        let limiter = self
+            .get_parameter("master_limiter")
+            .and_then(|v| v.as_bool())
+            .unwrap_or(false);
+        for sample in &mut mixed {
+            *sample *= volume;
+            if limiter {
+                *sample = soft_limit(*sample);
+            }
+        }
+
+        Some(UnifiedAudioData::Stereo {
+            sample_rate,
+            channels,
+            samples: mixed,
         })
     }
 }
 
 impl NodeProcessor for AudioMixerNode {
     fn process(&mut self, input: FrameData) -> Result<FrameData> {
-        Ok(input)
+        let id = self.id;
+        self.process_multi(vec![(id, input)])
+    }
+
+    fn process_multi(&mut self, inputs: Vec<(Uuid, FrameData)>) -> Result<FrameData> {
+        let audio_data = self.mix(&inputs);
+
+        // Everything other than audio comes from the first input
+        let Some((_, mut output)) = inputs.into_iter().next() else {
+            anyhow::bail!("Audio mixer needs at least one input");
+        };
+        if audio_data.is_some() {
+            output.audio_data = audio_data;
+        }
+        Ok(output)
     }
 
     fn get_properties(&self) -> NodeProperties {
@@ -381,34 +1508,264 @@ impl NodeProcessor for AudioMixerNode {
     }
 }
 
+/// Output ceiling of the brick-wall limiter, just under 0 dBFS
+const LIMITER_CEILING: f32 = 0.989; // -0.1 dBFS
+
+/// Dynamics processor (compressor with an optional brick-wall limiter)
 pub struct AudioEffectNode {
     id: Uuid,
     config: NodeConfig,
     properties: NodeProperties,
+    // Per-channel detector envelope (linear amplitude)
+    envelopes: Vec<f32>,
+    // Per-channel limiter gain, recovering towards unity at the release rate
+    limiter_gains: Vec<f32>,
+    // Collects incoming audio into fixed-size blocks when `block_size` is set
+    blocks: AudioProcessor,
+    // Converts a remainder left over from the previous format to the current one
+    remainder_resampler: StreamResampler,
 }
 
 impl AudioEffectNode {
     pub fn new(id: Uuid, config: NodeConfig) -> Result<Self> {
+        let mut parameters = HashMap::new();
+        parameters.insert(
+            "threshold".to_string(),
+            ParameterDefinition {
+                name: "Threshold".to_string(),
+                parameter_type: ParameterType::Float,
+                default_value: Value::from(-18.0),
+                min_value: Some(Value::from(-60.0)),
+                max_value: Some(Value::from(0.0)),
+                description: "Level in dBFS above which gain reduction starts".to_string(),
+            },
+        );
+        parameters.insert(
+            "ratio".to_string(),
+            ParameterDefinition {
+                name: "Ratio".to_string(),
+                parameter_type: ParameterType::Float,
+                default_value: Value::from(4.0),
+                min_value: Some(Value::from(1.0)),
+                max_value: Some(Value::from(20.0)),
+                description: "Input:output ratio applied above the threshold".to_string(),
+            },
+        );
+        parameters.insert(
+            "attack".to_string(),
+            ParameterDefinition {
+                name: "Attack".to_string(),
+                parameter_type: ParameterType::Float,
+                default_value: Value::from(10.0),
+                min_value: Some(Value::from(0.1)),
+                max_value: Some(Value::from(200.0)),
+                description: "Envelope attack time in milliseconds".to_string(),
+            },
+        );
+        parameters.insert(
+            "release".to_string(),
+            ParameterDefinition {
+                name: "Release".to_string(),
+                parameter_type: ParameterType::Float,
+                default_value: Value::from(100.0),
+                min_value: Some(Value::from(1.0)),
+                max_value: Some(Value::from(2000.0)),
+                description: "Envelope release time in milliseconds".to_string(),
+            },
+        );
+        parameters.insert(
+            "makeup_gain".to_string(),
+            ParameterDefinition {
+                name: "Makeup Gain".to_string(),
+                parameter_type: ParameterType::Float,
+                default_value: Value::from(0.0),
+                min_value: Some(Value::from(0.0)),
+                max_value: Some(Value::from(24.0)),
+                description: "Gain in dB applied after compression".to_string(),
+            },
+        );
+        parameters.insert(
+            "limiter".to_string(),
+            ParameterDefinition {
+                name: "Limiter".to_string(),
+                parameter_type: ParameterType::Boolean,
+                default_value: Value::Bool(false),
+                min_value: None,
+                max_value: None,
+                description: "Brick-wall limit the output below 0 dBFS".to_string(),
+            },
+        );
+        parameters.insert(
+            "block_size".to_string(),
+            ParameterDefinition {
+                name: "Block Size".to_string(),
+                parameter_type: ParameterType::Integer,
+                default_value: Value::from(0),
+                min_value: Some(Value::from(0)),
+                max_value: Some(Value::from(16384)),
+                description:
+                    "Frames per channel processed at once (0 = process each frame as it arrives)"
+                        .to_string(),
+            },
+        );
+
         let properties = NodeProperties {
             id,
             name: "Audio Effect".to_string(),
             node_type: NodeType::Audio(AudioType::Effect),
             input_types: vec![ConnectionType::Audio],
             output_types: vec![ConnectionType::Audio],
-            parameters: HashMap::new(),
+            parameters,
+            enabled: true,
         };
 
         Ok(Self {
             id,
             config,
             properties,
+            envelopes: Vec::new(),
+            limiter_gains: Vec::new(),
+            blocks: AudioProcessor::new(48000, 2),
+            remainder_resampler: StreamResampler::new(),
         })
     }
+
+    fn float_parameter(&self, key: &str, default: f32) -> f32 {
+        self.get_parameter(key)
+            .and_then(|v| v.as_f64())
+            .map(|v| v as f32)
+            .unwrap_or(default)
+    }
+
+    /// Compress interleaved samples in place, following each channel's envelope separately
+    fn process_samples(&mut self, samples: &mut [f32], sample_rate: u32, channels: usize) {
+        if channels == 0 || sample_rate == 0 {
+            return;
+        }
+
+        let threshold_db = self.float_parameter("threshold", -18.0);
+        let ratio = self.float_parameter("ratio", 4.0).max(1.0);
+        let attack_ms = self.float_parameter("attack", 10.0);
+        let release_ms = self.float_parameter("release", 100.0);
+        let makeup = db_to_linear(self.float_parameter("makeup_gain", 0.0));
+        let limiter = self
+            .get_parameter("limiter")
+            .and_then(|v| v.as_bool())
+            .unwrap_or(false);
+
+        let attack = smoothing_coefficient(attack_ms, sample_rate);
+        let release = smoothing_coefficient(release_ms, sample_rate);
+        let slope = 1.0 - 1.0 / ratio;
+
+        // Channel layout changes reset the detector state
+        if self.envelopes.len() != channels {
+            self.envelopes = vec![0.0; channels];
+            self.limiter_gains = vec![1.0; channels];
+        }
+
+        for frame in samples.chunks_mut(channels) {
+            for (channel, sample) in frame.iter_mut().enumerate() {
+                let level = sample.abs();
+                let envelope = &mut self.envelopes[channel];
+                let coefficient = if level > *envelope { attack } else { release };
+                *envelope = level + coefficient * (*envelope - level);
+
+                let over_db = linear_to_db(*envelope) - threshold_db;
+                let gain_db = if over_db > 0.0 { -over_db * slope } else { 0.0 };
+                let mut output = *sample * db_to_linear(gain_db) * makeup;
+
+                if limiter {
+                    // Instant attack so no sample passes the ceiling, smoothed recovery
+                    let limiter_gain = &mut self.limiter_gains[channel];
+                    *limiter_gain = 1.0 + release * (*limiter_gain - 1.0);
+                    let peak = output.abs() * *limiter_gain;
+                    if peak > LIMITER_CEILING {
+                        *limiter_gain *= LIMITER_CEILING / peak;
+                    }
+                    output = (output * *limiter_gain).clamp(-LIMITER_CEILING, LIMITER_CEILING);
+                }
+
+                *sample = output;
+            }
+        }
+    }
+}
+
+/// Pass levels below the knee untouched and bend everything above it
+/// asymptotically towards full scale
+fn soft_limit(sample: f32) -> f32 {
+    let level = sample.abs();
+    if level <= SOFT_LIMIT_KNEE {
+        return sample;
+    }
+    let headroom = 1.0 - SOFT_LIMIT_KNEE;
+    let limited = SOFT_LIMIT_KNEE + headroom * ((level - SOFT_LIMIT_KNEE) / headroom).tanh();
+    limited.copysign(sample)
+}
+
+fn db_to_linear(db: f32) -> f32 {
+    10f32.powf(db / 20.0)
+}
+
+fn linear_to_db(level: f32) -> f32 {
+    20.0 * level.max(1e-9).log10()
+}
+
+/// One-pole smoothing coefficient for a time constant in milliseconds
+fn smoothing_coefficient(time_ms: f32, sample_rate: u32) -> f32 {
+    let samples = time_ms.max(0.001) * 0.001 * sample_rate as f32;
+    (-1.0 / samples).exp()
 }
 
 impl NodeProcessor for AudioEffectNode {
     fn process(&mut self, input: FrameData) -> Result<FrameData> {
-        Ok(input)
+        let mut output = input;
+
+        if let Some(UnifiedAudioData::Stereo {
+            sample_rate,
+            channels,
+            ref mut samples,
+        }) = output.audio_data
+        {
+            let block_size = self.get_parameter("block_size").and_then(|v| v.as_u64());
+            self.blocks.set_block_size(block_size.unwrap_or(0) as usize);
+
+            let input = AudioFrame {
+                sample_rate,
+                channels,
+                samples: std::mem::take(samples),
+            };
+            for mut block in self.blocks.process_blocks(&input)? {
+                self.process_samples(
+                    &mut block.samples,
+                    block.sample_rate,
+                    block.channels as usize,
+                );
+                if (block.sample_rate, block.channels) != (sample_rate, channels) {
+                    // Remainder of the previous format: one frame carries one layout
+                    block = self
+                        .remainder_resampler
+                        .resample(&block, sample_rate, channels)?;
+                }
+                samples.extend(block.samples);
+            }
+        }
+
+        Ok(output)
+    }
+
+    fn on_stop(&mut self) -> Result<()> {
+        // Nothing downstream plays a partial block after stop; drop it so a
+        // restart doesn't begin with stale audio from the previous run
+        if let Some(tail) = self.blocks.flush() {
+            tracing::debug!(
+                "Audio effect {} dropped {} buffered samples on stop",
+                self.id,
+                tail.samples.len()
+            );
+        }
+        self.remainder_resampler.reset();
+        Ok(())
     }
 
     fn get_properties(&self) -> NodeProperties {
@@ -429,29 +1786,167 @@ pub struct AudioOutputNode {
     id: Uuid,
     config: NodeConfig,
     properties: NodeProperties,
+    stream: Option<DeviceStream>,
+    // Earliest time to try opening the device again after it failed or went away
+    retry_at: Option<Instant>,
+    // Converts to the device format without clicks at block edges
+    resampler: StreamResampler,
 }
 
 impl AudioOutputNode {
     pub fn new(id: Uuid, config: NodeConfig) -> Result<Self> {
+        let mut parameters = HashMap::new();
+        parameters.insert(
+            "device_id".to_string(),
+            ParameterDefinition {
+                name: "Device ID".to_string(),
+                parameter_type: ParameterType::String,
+                default_value: Value::String(audio_device::DEFAULT_DEVICE.to_string()),
+                min_value: None,
+                max_value: None,
+                description: "Audio output device (name substring, \"default\" = system default)"
+                    .to_string(),
+            },
+        );
+        parameters.insert(
+            "buffer_ms".to_string(),
+            ParameterDefinition {
+                name: "Buffer (ms)".to_string(),
+                parameter_type: ParameterType::Integer,
+                default_value: Value::from(200),
+                min_value: Some(Value::from(20)),
+                max_value: Some(Value::from(2000)),
+                description: "Jitter buffer size; playback starts once half of it is filled"
+                    .to_string(),
+            },
+        );
+
         let properties = NodeProperties {
             id,
             name: "Audio Output".to_string(),
             node_type: NodeType::Audio(AudioType::Output),
             input_types: vec![ConnectionType::Audio],
             output_types: vec![],
-            parameters: HashMap::new(),
+            parameters,
+            enabled: true,
         };
 
         Ok(Self {
             id,
             config,
             properties,
+            stream: None,
+            retry_at: None,
+            resampler: StreamResampler::new(),
         })
     }
+
+    fn device_id(&self) -> String {
+        self.get_parameter("device_id")
+            .and_then(|v| v.as_str().map(str::to_string))
+            .unwrap_or_else(|| audio_device::DEFAULT_DEVICE.to_string())
+    }
+
+    fn buffer_duration(&self) -> Duration {
+        let ms = self
+            .get_parameter("buffer_ms")
+            .and_then(|v| v.as_u64())
+            .unwrap_or(200)
+            .clamp(20, 2000);
+        Duration::from_millis(ms)
+    }
+
+    /// True while a playback stream is open
+    pub fn is_playing(&self) -> bool {
+        self.stream.is_some()
+    }
+
+    /// Format of the open playback device
+    pub fn device_config(&self) -> Option<DeviceConfig> {
+        self.stream.as_ref().map(|stream| stream.config().clone())
+    }
+
+    /// Samples waiting in the jitter buffer
+    pub fn queued_samples(&self) -> usize {
+        self.stream.as_ref().map_or(0, |stream| stream.ring().len())
+    }
+
+    /// Times the device ran out of queued audio after playback started
+    pub fn underruns(&self) -> u64 {
+        self.stream
+            .as_ref()
+            .map_or(0, |stream| stream.ring().underruns())
+    }
+
+    /// Open the device if needed; a failed or disconnected device is retried
+    /// after `AUDIO_DEVICE_RETRY`
+    fn ensure_stream(&mut self) {
+        if let Some(stream) = &self.stream {
+            if !stream.is_disconnected() {
+                return;
+            }
+            tracing::warn!(
+                "Audio output device {} disconnected",
+                stream.config().device_name
+            );
+            self.stream = None;
+            self.retry_at = Some(Instant::now() + AUDIO_DEVICE_RETRY);
+        }
+        if self.retry_at.is_some_and(|at| Instant::now() < at) {
+            return;
+        }
+
+        match DeviceStream::open_output(&self.device_id(), self.buffer_duration()) {
+            Ok(stream) => {
+                self.stream = Some(stream);
+                self.retry_at = None;
+            }
+            Err(e) => {
+                tracing::warn!("Audio output unavailable: {:#}", e);
+                self.retry_at = Some(Instant::now() + AUDIO_DEVICE_RETRY);
+            }
+        }
+    }
+
+    /// Convert a block to the device format and queue it for playback
+    fn play(&mut self, frame: AudioFrame) -> Result<()> {
+        self.ensure_stream();
+        let Some(stream) = &self.stream else {
+            return Ok(());
+        };
+
+        let config = stream.config();
+        let frame = if frame.sample_rate == config.sample_rate && frame.channels == config.channels
+        {
+            frame
+        } else {
+            self.resampler
+                .resample(&frame, config.sample_rate, config.channels)?
+        };
+        if stream.ring().push(&frame.samples) > 0 {
+            tracing::debug!("Audio output buffer full, dropped the oldest samples");
+        }
+        Ok(())
+    }
 }
 
 impl NodeProcessor for AudioOutputNode {
     fn process(&mut self, input: FrameData) -> Result<FrameData> {
+        if let Some(UnifiedAudioData::Stereo {
+            sample_rate,
+            channels,
+            ref samples,
+        }) = input.audio_data
+        {
+            if !samples.is_empty() {
+                self.play(AudioFrame {
+                    sample_rate,
+                    channels,
+                    samples: samples.clone(),
+                })?;
+            }
+        }
+
         Ok(input)
     }
 
@@ -459,7 +1954,19 @@ impl NodeProcessor for AudioOutputNode {
         self.properties.clone()
     }
 
+    fn on_stop(&mut self) -> Result<()> {
+        self.stream = None;
+        self.retry_at = None;
+        self.resampler.reset();
+        Ok(())
+    }
+
     fn set_parameter(&mut self, key: &str, value: Value) -> Result<()> {
+        if key == "device_id" || key == "buffer_ms" {
+            // Reopen with the new settings on the next block
+            self.stream = None;
+            self.retry_at = None;
+        }
         self.config.parameters.insert(key.to_string(), value);
         Ok(())
     }
@@ -484,6 +1991,7 @@ impl TallyGeneratorNode {
             input_types: vec![],
             output_types: vec![ConnectionType::Control],
             parameters: HashMap::new(),
+            enabled: true,
         };
 
         Ok(Self {
@@ -505,6 +2013,9 @@ impl NodeProcessor for TallyGeneratorNode {
                 value: ParameterValue::Boolean(true),
             }),
             tally_metadata: TallyMetadata::new().with_program_tally(true),
+            timestamp: Duration::ZERO,
+            sequence: 0,
+            checksum: None,
         })
     }
 
@@ -542,6 +2053,7 @@ impl TallyMonitorNode {
             input_types: vec![ConnectionType::Control],
             output_types: vec![ConnectionType::Control],
             parameters: HashMap::new(),
+            enabled: true,
         };
 
         Ok(Self {
@@ -571,29 +2083,152 @@ impl NodeProcessor for TallyMonitorNode {
     }
 }
 
+/// Boolean operation applied by `TallyLogicNode` across its tally sources
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TallyLogicOperation {
+    And,
+    Or,
+    Xor,
+    /// NOR over all sources: active only while no source is active
+    Not,
+}
+
+impl TallyLogicOperation {
+    pub fn from_name(name: &str) -> Option<Self> {
+        match name.to_ascii_uppercase().as_str() {
+            "AND" => Some(Self::And),
+            "OR" => Some(Self::Or),
+            "XOR" => Some(Self::Xor),
+            "NOT" => Some(Self::Not),
+            _ => None,
+        }
+    }
+
+    /// Combine the flags of every source. With no sources only `Not` is active
+    pub fn evaluate(&self, values: &[bool]) -> bool {
+        match self {
+            Self::And => !values.is_empty() && values.iter().all(|&v| v),
+            Self::Or => values.iter().any(|&v| v),
+            Self::Xor => values.iter().filter(|&&v| v).count() % 2 == 1,
+            Self::Not => !values.iter().any(|&v| v),
+        }
+    }
+}
+
 pub struct TallyLogicNode {
     id: Uuid,
     config: NodeConfig,
     properties: NodeProperties,
+    // Latest tally state received from each upstream source
+    source_states: HashMap<Uuid, TallyMetadata>,
 }
 
 impl TallyLogicNode {
     pub fn new(id: Uuid, config: NodeConfig) -> Result<Self> {
+        let mut parameters = HashMap::new();
+
+        parameters.insert(
+            "operation".to_string(),
+            ParameterDefinition {
+                name: "Operation".to_string(),
+                parameter_type: ParameterType::Enum(vec![
+                    "AND".to_string(),
+                    "OR".to_string(),
+                    "XOR".to_string(),
+                    "NOT".to_string(),
+                ]),
+                default_value: Value::String("OR".to_string()),
+                min_value: None,
+                max_value: None,
+                description: "Logic operation applied across upstream tally sources".to_string(),
+            },
+        );
+
+        parameters.insert(
+            "watched_keys".to_string(),
+            ParameterDefinition {
+                name: "Watched Keys".to_string(),
+                parameter_type: ParameterType::String,
+                default_value: Value::String(String::new()),
+                min_value: None,
+                max_value: None,
+                description: "Comma-separated custom tally keys to combine".to_string(),
+            },
+        );
+
         let properties = NodeProperties {
             id,
             name: "Tally Logic".to_string(),
             node_type: NodeType::Tally(TallyType::Logic),
             input_types: vec![ConnectionType::Control],
             output_types: vec![ConnectionType::Control],
-            parameters: HashMap::new(),
+            parameters,
+            enabled: true,
         };
 
         Ok(Self {
             id,
             config,
             properties,
+            source_states: HashMap::new(),
         })
     }
+
+    pub fn operation(&self) -> TallyLogicOperation {
+        self.get_parameter("operation")
+            .and_then(|v| v.as_str().and_then(TallyLogicOperation::from_name))
+            .unwrap_or(TallyLogicOperation::Or)
+    }
+
+    /// Custom tally keys to combine, given as a comma-separated string or a JSON array
+    pub fn watched_keys(&self) -> Vec<String> {
+        match self.get_parameter("watched_keys") {
+            Some(Value::String(keys)) => keys
+                .split(',')
+                .map(str::trim)
+                .filter(|key| !key.is_empty())
+                .map(String::from)
+                .collect(),
+            Some(Value::Array(keys)) => keys
+                .iter()
+                .filter_map(|key| key.as_str().map(String::from))
+                .collect(),
+            _ => Vec::new(),
+        }
+    }
+
+    /// Forget all recorded upstream states
+    pub fn clear_sources(&mut self) {
+        self.source_states.clear();
+    }
+
+    // Sources are identified by their explicit origin, falling back to the first node on the path
+    fn source_id(metadata: &TallyMetadata) -> Uuid {
+        metadata
+            .propagation_source
+            .or_else(|| metadata.propagation_path.first().copied())
+            .unwrap_or(Uuid::nil())
+    }
+
+    fn combined_state(&self) -> TallyMetadata {
+        let operation = self.operation();
+        let combine = |flag: &dyn Fn(&TallyMetadata) -> bool| {
+            let values: Vec<bool> = self.source_states.values().map(flag).collect();
+            operation.evaluate(&values)
+        };
+
+        let mut result = TallyMetadata::new()
+            .with_program_tally(combine(&|m| m.program_tally))
+            .with_preview_tally(combine(&|m| m.preview_tally));
+
+        for key in self.watched_keys() {
+            let value = combine(&|m| m.custom_tally.get(&key).copied().unwrap_or(false));
+            result.custom_tally.insert(key, value);
+        }
+
+        result.propagation_source = Some(self.id);
+        result
+    }
 }
 
 impl NodeProcessor for TallyLogicNode {
@@ -601,6 +2236,24 @@ impl NodeProcessor for TallyLogicNode {
         Ok(input)
     }
 
+    fn process_tally_metadata(&mut self, metadata: &TallyMetadata) -> TallyMetadata {
+        self.source_states
+            .insert(Self::source_id(metadata), metadata.clone());
+
+        let mut result = self.combined_state();
+        result.propagation_path = metadata.propagation_path.clone();
+        result.add_to_path(self.id);
+        result
+    }
+
+    fn should_propagate_tally(&self, metadata: &TallyMetadata) -> bool {
+        !metadata.has_visited(self.id)
+    }
+
+    fn generate_tally_state(&self) -> TallyMetadata {
+        self.combined_state()
+    }
+
     fn get_properties(&self) -> NodeProperties {
         self.properties.clone()
     }
@@ -619,25 +2272,96 @@ pub struct TallyRouterNode {
     id: Uuid,
     config: NodeConfig,
     properties: NodeProperties,
+    // Most recent upstream tally state
+    input_state: TallyMetadata,
 }
 
 impl TallyRouterNode {
     pub fn new(id: Uuid, config: NodeConfig) -> Result<Self> {
+        let mut parameters = HashMap::new();
+
+        parameters.insert(
+            "routes".to_string(),
+            ParameterDefinition {
+                name: "Routes".to_string(),
+                parameter_type: ParameterType::String,
+                default_value: Value::String(String::new()),
+                min_value: None,
+                max_value: None,
+                description: "Comma-separated source=destination pairs, e.g. \"program=gpio_1\". \
+                              Sources are \"program\", \"preview\" or a custom tally key"
+                    .to_string(),
+            },
+        );
+
         let properties = NodeProperties {
             id,
             name: "Tally Router".to_string(),
             node_type: NodeType::Tally(TallyType::Router),
             input_types: vec![ConnectionType::Control],
             output_types: vec![ConnectionType::Control],
-            parameters: HashMap::new(),
+            parameters,
+            enabled: true,
         };
 
         Ok(Self {
             id,
             config,
             properties,
+            input_state: TallyMetadata::new(),
         })
     }
+
+    /// Routing table as (source key, destination key) pairs.
+    ///
+    /// Accepts either a `"src=dst, ..."` string or a JSON object mapping each
+    /// source to a destination name or an array of destination names.
+    pub fn routes(&self) -> Vec<(String, String)> {
+        match self.get_parameter("routes") {
+            Some(Value::String(routes)) => routes
+                .split(',')
+                .filter_map(|route| {
+                    let (source, destination) = route.split_once('=')?;
+                    let (source, destination) = (source.trim(), destination.trim());
+                    (!source.is_empty() && !destination.is_empty())
+                        .then(|| (source.to_string(), destination.to_string()))
+                })
+                .collect(),
+            Some(Value::Object(routes)) => routes
+                .iter()
+                .flat_map(|(source, destinations)| {
+                    let destinations: Vec<&str> = match destinations {
+                        Value::String(destination) => vec![destination.as_str()],
+                        Value::Array(items) => items.iter().filter_map(Value::as_str).collect(),
+                        _ => Vec::new(),
+                    };
+                    destinations
+                        .into_iter()
+                        .map(move |destination| (source.clone(), destination.to_string()))
+                })
+                .collect(),
+            _ => Vec::new(),
+        }
+    }
+
+    fn source_value(metadata: &TallyMetadata, source: &str) -> bool {
+        match source {
+            "program" => metadata.program_tally,
+            "preview" => metadata.preview_tally,
+            key => metadata.custom_tally.get(key).copied().unwrap_or(false),
+        }
+    }
+
+    fn routed_state(&self) -> TallyMetadata {
+        let mut result = TallyMetadata::new();
+        for (source, destination) in self.routes() {
+            // Several sources routed to the same destination are OR-ed together
+            let value = Self::source_value(&self.input_state, &source);
+            *result.custom_tally.entry(destination).or_insert(false) |= value;
+        }
+        result.propagation_source = Some(self.id);
+        result
+    }
 }
 
 impl NodeProcessor for TallyRouterNode {
@@ -645,6 +2369,23 @@ impl NodeProcessor for TallyRouterNode {
         Ok(input)
     }
 
+    fn process_tally_metadata(&mut self, metadata: &TallyMetadata) -> TallyMetadata {
+        self.input_state = metadata.clone();
+
+        let mut result = self.routed_state();
+        result.propagation_path = metadata.propagation_path.clone();
+        result.add_to_path(self.id);
+        result
+    }
+
+    fn should_propagate_tally(&self, metadata: &TallyMetadata) -> bool {
+        !metadata.has_visited(self.id)
+    }
+
+    fn generate_tally_state(&self) -> TallyMetadata {
+        self.routed_state()
+    }
+
     fn get_properties(&self) -> NodeProperties {
         self.properties.clone()
     }
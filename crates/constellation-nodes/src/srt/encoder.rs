@@ -0,0 +1,200 @@
+/*
+ * Constellation Studio - Professional Real-time Video Processing
+ * Copyright (c) 2025 MACHIKO LAB
+ *
+ * This program is free software: you can redistribute it and/or modify
+ * it under the terms of the GNU Affero General Public License as published by
+ * the Free Software Foundation, either version 3 of the License, or
+ * (at your option) any later version.
+ *
+ * This program is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+ * GNU Affero General Public License for more details.
+ *
+ * You should have received a copy of the GNU Affero General Public License
+ * along with this program. If not, see <https://www.gnu.org/licenses/>.
+ */
+
+//! FFmpeg-backed H.264 encoding and MPEG-TS muxing for `SrtOutputNode`
+
+use super::{EncoderSettings, SrtConfig, SrtSink};
+use crate::virtual_camera::conversion;
+use anyhow::{anyhow, Context as _, Result};
+use constellation_core::VideoFrame;
+use ffmpeg::format::Pixel;
+use ffmpeg::software::scaling::{context::Context as Scaler, flag::Flags};
+use ffmpeg::{codec, encoder, format, frame, Codec, Dictionary, Packet, Rational};
+use ffmpeg_next as ffmpeg;
+use tracing::info;
+
+/// Prefer x264 for its low-latency tuning, fall back to any H.264 encoder
+fn find_h264_encoder() -> Result<Codec> {
+    encoder::find_by_name("libx264")
+        .or_else(|| encoder::find(codec::Id::H264))
+        .ok_or_else(|| anyhow!("FFmpeg was built without an H.264 encoder"))
+}
+
+/// Open an H.264 encoder tuned for live streaming (no B-frames, fixed GOP)
+pub fn open_h264_encoder(
+    settings: &EncoderSettings,
+    global_header: bool,
+) -> Result<encoder::Video> {
+    ffmpeg::init().context("Failed to initialize FFmpeg")?;
+
+    let mut video = codec::context::Context::new_with_codec(find_h264_encoder()?)
+        .encoder()
+        .video()?;
+    video.set_width(settings.width);
+    video.set_height(settings.height);
+    video.set_format(Pixel::YUV420P);
+    video.set_time_base(Rational(1, settings.frame_rate as i32));
+    video.set_frame_rate(Some(Rational(settings.frame_rate as i32, 1)));
+    video.set_bit_rate(settings.bitrate_kbps as usize * 1000);
+    video.set_max_bit_rate(settings.bitrate_kbps as usize * 1000);
+    video.set_gop(settings.gop);
+    // B-frames add a frame of latency for every reference they wait on
+    video.set_max_b_frames(0);
+    if global_header {
+        video.set_flags(codec::Flags::GLOBAL_HEADER);
+    }
+
+    // Ignored by encoders other than x264
+    let mut options = Dictionary::new();
+    options.set("preset", "veryfast");
+    options.set("tune", "zerolatency");
+
+    video
+        .open_with(options)
+        .context("Failed to open H.264 encoder")
+}
+
+/// An SRT connection carrying H.264 in MPEG-TS
+pub struct FfmpegSrtSink {
+    output: format::context::Output,
+    encoder: encoder::Video,
+    encoder_time_base: Rational,
+    stream_time_base: Rational,
+    // Source dimensions the scaler was built for
+    scaler: Option<((u32, u32), Scaler)>,
+    next_pts: i64,
+}
+
+impl FfmpegSrtSink {
+    /// Connect to the peer and write the stream header
+    pub fn connect(config: &SrtConfig, width: u32, height: u32) -> Result<Self> {
+        ffmpeg::init().context("Failed to initialize FFmpeg")?;
+
+        let mut options = Dictionary::new();
+        for (key, value) in config.protocol_options() {
+            options.set(key, &value);
+        }
+        let mut output = format::output_as_with(&config.url, "mpegts", options)
+            .with_context(|| format!("Failed to open SRT connection to {}", config.url))?;
+
+        let settings = EncoderSettings::new(config, width, height);
+        let global_header = output
+            .format()
+            .flags()
+            .contains(format::Flags::GLOBAL_HEADER);
+        let encoder = open_h264_encoder(&settings, global_header)?;
+        let encoder_time_base = Rational(1, settings.frame_rate as i32);
+
+        let mut stream = output.add_stream(find_h264_encoder()?)?;
+        stream.set_parameters(&encoder);
+        stream.set_time_base(encoder_time_base);
+
+        output
+            .write_header()
+            .context("Failed to write MPEG-TS header")?;
+        let stream_time_base = output
+            .stream(0)
+            .ok_or_else(|| anyhow!("MPEG-TS output has no video stream"))?
+            .time_base();
+
+        info!(
+            "SRT stream to {}: {}x{}@{} H.264 at {} kbit/s",
+            config.url, settings.width, settings.height, settings.frame_rate, settings.bitrate_kbps
+        );
+
+        Ok(Self {
+            output,
+            encoder,
+            encoder_time_base,
+            stream_time_base,
+            scaler: None,
+            next_pts: 0,
+        })
+    }
+
+    fn convert_frame(&mut self, frame: &VideoFrame) -> Result<frame::Video> {
+        let rgba = conversion::frame_to_rgba(frame)?;
+        let mut source = frame::Video::new(Pixel::RGBA, frame.width, frame.height);
+        let row_bytes = frame.width as usize * 4;
+        let stride = source.stride(0);
+        for (dst, src) in source
+            .data_mut(0)
+            .chunks_mut(stride)
+            .zip(rgba.chunks_exact(row_bytes))
+        {
+            dst[..row_bytes].copy_from_slice(src);
+        }
+
+        // Frames of a different size are scaled to the encoder size
+        let size = (frame.width, frame.height);
+        if self.scaler.as_ref().map(|(built_for, _)| *built_for) != Some(size) {
+            let scaler = Scaler::get(
+                Pixel::RGBA,
+                frame.width,
+                frame.height,
+                Pixel::YUV420P,
+                self.encoder.width(),
+                self.encoder.height(),
+                Flags::BILINEAR,
+            )
+            .context("Failed to create YUV scaler")?;
+            self.scaler = Some((size, scaler));
+        }
+
+        let mut yuv = frame::Video::empty();
+        if let Some((_, scaler)) = self.scaler.as_mut() {
+            scaler
+                .run(&source, &mut yuv)
+                .context("Failed to convert frame to YUV")?;
+        }
+        Ok(yuv)
+    }
+
+    fn write_packets(&mut self) -> Result<()> {
+        let mut packet = Packet::empty();
+        while self.encoder.receive_packet(&mut packet).is_ok() {
+            packet.set_stream(0);
+            packet.rescale_ts(self.encoder_time_base, self.stream_time_base);
+            packet
+                .write_interleaved(&mut self.output)
+                .context("Failed to write to SRT connection")?;
+        }
+        Ok(())
+    }
+}
+
+impl SrtSink for FfmpegSrtSink {
+    fn send_frame(&mut self, frame: &VideoFrame) -> Result<()> {
+        let mut yuv = self.convert_frame(frame)?;
+        yuv.set_pts(Some(self.next_pts));
+        self.next_pts += 1;
+
+        self.encoder
+            .send_frame(&yuv)
+            .context("H.264 encoding failed")?;
+        self.write_packets()
+    }
+
+    fn finish(&mut self) -> Result<()> {
+        self.encoder.send_eof()?;
+        self.write_packets()?;
+        self.output
+            .write_trailer()
+            .context("Failed to finish MPEG-TS stream")
+    }
+}
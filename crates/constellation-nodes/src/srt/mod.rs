@@ -0,0 +1,500 @@
+/*
+ * Constellation Studio - Professional Real-time Video Processing
+ * Copyright (c) 2025 MACHIKO LAB
+ *
+ * This program is free software: you can redistribute it and/or modify
+ * it under the terms of the GNU Affero General Public License as published by
+ * the Free Software Foundation, either version 3 of the License, or
+ * (at your option) any later version.
+ *
+ * This program is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+ * GNU Affero General Public License for more details.
+ *
+ * You should have received a copy of the GNU Affero General Public License
+ * along with this program. If not, see <https://www.gnu.org/licenses/>.
+ */
+
+//! SRT streaming output
+//!
+//! Frames are handed to a sender thread that encodes them to H.264, muxes
+//! MPEG-TS and writes it to the SRT connection, so a slow or lost peer never
+//! stalls the pipeline. While the connection is down, frames are buffered up
+//! to `buffer_frames` (oldest dropped first) and the thread reconnects with
+//! exponential backoff.
+
+use crate::{NodeProcessor, NodeProperties, ParameterDefinition, ParameterType};
+use anyhow::{bail, Context, Result};
+use constellation_core::*;
+use serde_json::Value;
+use std::collections::{HashMap, VecDeque};
+use std::sync::atomic::{AtomicBool, AtomicU64, Ordering};
+use std::sync::mpsc::{self, RecvTimeoutError, SyncSender, TrySendError};
+use std::sync::Arc;
+use std::thread::JoinHandle;
+use std::time::{Duration, Instant};
+use tracing::{error, info, warn};
+use uuid::Uuid;
+
+mod encoder;
+
+pub use encoder::{open_h264_encoder, FfmpegSrtSink};
+
+/// First reconnect delay after a connection failure
+const INITIAL_RETRY_DELAY: Duration = Duration::from_millis(250);
+/// Upper bound for the exponential reconnect backoff
+const MAX_RETRY_DELAY: Duration = Duration::from_secs(8);
+
+/// Validated SRT output settings
+#[derive(Debug, Clone, PartialEq)]
+pub struct SrtConfig {
+    /// `srt://host:port[?options]`; an empty host listens instead of calling
+    pub url: String,
+    pub latency: Duration,
+    pub passphrase: Option<String>,
+    pub bitrate_kbps: u32,
+    pub frame_rate: u32,
+    /// Frames held while disconnected before the oldest are dropped
+    pub buffer_frames: usize,
+}
+
+impl SrtConfig {
+    pub fn from_parameters(parameters: &HashMap<String, Value>) -> Result<Self> {
+        let integer = |key: &str, default: u64, min: u64, max: u64| -> Result<u64> {
+            let value = match parameters.get(key) {
+                None | Some(Value::Null) => default,
+                Some(value) => value
+                    .as_u64()
+                    .with_context(|| format!("{} must be a positive integer", key))?,
+            };
+            if !(min..=max).contains(&value) {
+                bail!("{} must be between {} and {}, got {}", key, min, max, value);
+            }
+            Ok(value)
+        };
+
+        let url = parameters
+            .get("url")
+            .and_then(|v| v.as_str())
+            .unwrap_or_default()
+            .trim()
+            .to_string();
+        Self::validate_url(&url)?;
+
+        let passphrase = parameters
+            .get("passphrase")
+            .and_then(|v| v.as_str())
+            .filter(|p| !p.is_empty())
+            .map(str::to_string);
+        if let Some(passphrase) = &passphrase {
+            // Length limits imposed by libsrt
+            if !(10..=79).contains(&passphrase.chars().count()) {
+                bail!("SRT passphrase must be 10 to 79 characters");
+            }
+        }
+
+        Ok(Self {
+            url,
+            latency: Duration::from_millis(integer("latency_ms", 120, 20, 8000)?),
+            passphrase,
+            bitrate_kbps: integer("bitrate_kbps", 6000, 100, 100_000)? as u32,
+            frame_rate: integer("frame_rate", 30, 1, 240)? as u32,
+            buffer_frames: integer("buffer_frames", 90, 1, 3600)? as usize,
+        })
+    }
+
+    fn validate_url(url: &str) -> Result<()> {
+        if url.is_empty() {
+            bail!("No SRT URL specified");
+        }
+        let Some(rest) = url.strip_prefix("srt://") else {
+            bail!("SRT URL must start with srt://, got {}", url);
+        };
+        let authority = rest.split(['?', '/']).next().unwrap_or_default();
+        let Some((_host, port)) = authority.rsplit_once(':') else {
+            bail!("SRT URL is missing a port: {}", url);
+        };
+        match port.parse::<u16>() {
+            Ok(port) if port > 0 => Ok(()),
+            _ => bail!("Invalid SRT port \"{}\" in {}", port, url),
+        }
+    }
+
+    /// Options passed to FFmpeg's libsrt protocol when opening the connection
+    pub fn protocol_options(&self) -> Vec<(&'static str, String)> {
+        // FFmpeg takes the SRT latency in microseconds
+        let mut options = vec![("latency", self.latency.as_micros().to_string())];
+        if let Some(passphrase) = &self.passphrase {
+            options.push(("passphrase", passphrase.clone()));
+            options.push(("pbkeylen", "16".to_string()));
+        }
+        options
+    }
+
+    /// Keyframe interval: two seconds, so receivers joining late recover quickly
+    pub fn gop(&self) -> u32 {
+        self.frame_rate * 2
+    }
+}
+
+/// H.264 encoder parameters for a connection
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct EncoderSettings {
+    pub width: u32,
+    pub height: u32,
+    pub frame_rate: u32,
+    pub bitrate_kbps: u32,
+    pub gop: u32,
+}
+
+impl EncoderSettings {
+    /// Settings for a stream whose first frame is `width` x `height`
+    pub fn new(config: &SrtConfig, width: u32, height: u32) -> Self {
+        // 4:2:0 chroma subsampling needs even dimensions
+        Self {
+            width: (width & !1).max(2),
+            height: (height & !1).max(2),
+            frame_rate: config.frame_rate,
+            bitrate_kbps: config.bitrate_kbps,
+            gop: config.gop(),
+        }
+    }
+}
+
+/// An open connection that accepts frames, driven from the sender thread
+pub trait SrtSink {
+    fn send_frame(&mut self, frame: &VideoFrame) -> Result<()>;
+
+    /// Flush buffered packets and close the connection
+    fn finish(&mut self) -> Result<()> {
+        Ok(())
+    }
+}
+
+/// Opens a connection for a stream of `width` x `height` frames.
+/// Called on the sender thread, so sinks need not be `Send`.
+pub type SrtConnector = Arc<dyn Fn(&SrtConfig, u32, u32) -> Result<Box<dyn SrtSink>> + Send + Sync>;
+
+/// Counters shared between the node and its sender thread
+#[derive(Debug, Default)]
+struct SrtStats {
+    connected: AtomicBool,
+    frames_sent: AtomicU64,
+    dropped_frames: AtomicU64,
+    connection_losses: AtomicU64,
+}
+
+struct SenderThread {
+    frames: SyncSender<VideoFrame>,
+    handle: JoinHandle<()>,
+}
+
+impl SenderThread {
+    fn spawn(config: SrtConfig, connector: SrtConnector, stats: Arc<SrtStats>) -> Result<Self> {
+        let (frames, receiver) = mpsc::sync_channel(config.buffer_frames);
+        let handle = std::thread::Builder::new()
+            .name("srt-output".to_string())
+            .spawn(move || {
+                let mut backlog = VecDeque::new();
+                let mut sink: Option<Box<dyn SrtSink>> = None;
+                let mut retry_at = Instant::now();
+                let mut retry_delay = INITIAL_RETRY_DELAY;
+
+                let buffer = |backlog: &mut VecDeque<VideoFrame>, frame| {
+                    if backlog.len() >= config.buffer_frames {
+                        backlog.pop_front();
+                        stats.dropped_frames.fetch_add(1, Ordering::Relaxed);
+                    }
+                    backlog.push_back(frame);
+                };
+
+                loop {
+                    // Block when idle; while disconnected, wake up for the next retry
+                    let received = if backlog.is_empty() {
+                        receiver.recv().map_err(|_| RecvTimeoutError::Disconnected)
+                    } else if sink.is_none() {
+                        receiver.recv_timeout(retry_at.saturating_duration_since(Instant::now()))
+                    } else {
+                        receiver.try_recv().map_err(|e| match e {
+                            mpsc::TryRecvError::Empty => RecvTimeoutError::Timeout,
+                            mpsc::TryRecvError::Disconnected => RecvTimeoutError::Disconnected,
+                        })
+                    };
+                    let closing = match received {
+                        Ok(frame) => {
+                            buffer(&mut backlog, frame);
+                            false
+                        }
+                        Err(RecvTimeoutError::Timeout) => false,
+                        Err(RecvTimeoutError::Disconnected) => true,
+                    };
+                    while let Ok(frame) = receiver.try_recv() {
+                        buffer(&mut backlog, frame);
+                    }
+
+                    if sink.is_none() && !closing && Instant::now() >= retry_at {
+                        if let Some(frame) = backlog.front() {
+                            match connector(&config, frame.width, frame.height) {
+                                Ok(connected) => {
+                                    info!("SRT connected to {}", config.url);
+                                    sink = Some(connected);
+                                    stats.connected.store(true, Ordering::Relaxed);
+                                    retry_delay = INITIAL_RETRY_DELAY;
+                                }
+                                Err(e) => {
+                                    warn!(
+                                        "SRT connection to {} failed, retrying in {:?}: {:#}",
+                                        config.url, retry_delay, e
+                                    );
+                                    retry_at = Instant::now() + retry_delay;
+                                    retry_delay = (retry_delay * 2).min(MAX_RETRY_DELAY);
+                                }
+                            }
+                        }
+                    }
+
+                    if let Some(connected) = sink.as_mut() {
+                        while let Some(frame) = backlog.front() {
+                            if let Err(e) = connected.send_frame(frame) {
+                                // Keep the frame for the next connection
+                                warn!("SRT connection to {} lost: {:#}", config.url, e);
+                                sink = None;
+                                stats.connected.store(false, Ordering::Relaxed);
+                                stats.connection_losses.fetch_add(1, Ordering::Relaxed);
+                                retry_at = Instant::now();
+                                break;
+                            }
+                            backlog.pop_front();
+                            stats.frames_sent.fetch_add(1, Ordering::Relaxed);
+                        }
+                    }
+
+                    if closing {
+                        break;
+                    }
+                }
+
+                if let Some(mut connected) = sink {
+                    if let Err(e) = connected.finish() {
+                        error!("Failed to close SRT connection: {:#}", e);
+                    }
+                }
+                stats.connected.store(false, Ordering::Relaxed);
+            })?;
+
+        Ok(Self { frames, handle })
+    }
+
+    fn stop(self) {
+        // Dropping the sender lets the thread flush and exit
+        drop(self.frames);
+        if self.handle.join().is_err() {
+            error!("SRT sender thread panicked");
+        }
+    }
+}
+
+/// Streams the pipeline's video to an SRT peer as H.264 in MPEG-TS
+pub struct SrtOutputNode {
+    id: Uuid,
+    config: NodeConfig,
+    properties: NodeProperties,
+    connector: SrtConnector,
+    sender: Option<SenderThread>,
+    stats: Arc<SrtStats>,
+}
+
+impl SrtOutputNode {
+    pub fn new(id: Uuid, config: NodeConfig) -> Result<Self> {
+        Self::with_connector(
+            id,
+            config,
+            Arc::new(|config, width, height| {
+                Ok(Box::new(FfmpegSrtSink::connect(config, width, height)?) as Box<dyn SrtSink>)
+            }),
+        )
+    }
+
+    /// Create the node with a custom connection factory
+    pub fn with_connector(id: Uuid, config: NodeConfig, connector: SrtConnector) -> Result<Self> {
+        let mut parameters = HashMap::new();
+        parameters.insert(
+            "url".to_string(),
+            ParameterDefinition {
+                name: "URL".to_string(),
+                parameter_type: ParameterType::String,
+                default_value: Value::String(String::new()),
+                min_value: None,
+                max_value: None,
+                description: "SRT destination, e.g. srt://host:9000 (srt://:9000 to listen)"
+                    .to_string(),
+            },
+        );
+        parameters.insert(
+            "latency_ms".to_string(),
+            ParameterDefinition {
+                name: "Latency".to_string(),
+                parameter_type: ParameterType::Integer,
+                default_value: Value::from(120),
+                min_value: Some(Value::from(20)),
+                max_value: Some(Value::from(8000)),
+                description: "SRT receive latency in milliseconds".to_string(),
+            },
+        );
+        parameters.insert(
+            "passphrase".to_string(),
+            ParameterDefinition {
+                name: "Passphrase".to_string(),
+                parameter_type: ParameterType::String,
+                default_value: Value::String(String::new()),
+                min_value: None,
+                max_value: None,
+                description: "AES encryption passphrase, 10-79 characters (empty = unencrypted)"
+                    .to_string(),
+            },
+        );
+        parameters.insert(
+            "bitrate_kbps".to_string(),
+            ParameterDefinition {
+                name: "Bitrate".to_string(),
+                parameter_type: ParameterType::Integer,
+                default_value: Value::from(6000),
+                min_value: Some(Value::from(100)),
+                max_value: Some(Value::from(100_000)),
+                description: "H.264 video bitrate in kbit/s".to_string(),
+            },
+        );
+        parameters.insert(
+            "frame_rate".to_string(),
+            ParameterDefinition {
+                name: "Frame Rate".to_string(),
+                parameter_type: ParameterType::Integer,
+                default_value: Value::from(30),
+                min_value: Some(Value::from(1)),
+                max_value: Some(Value::from(240)),
+                description: "Frame rate the stream is encoded at".to_string(),
+            },
+        );
+        parameters.insert(
+            "buffer_frames".to_string(),
+            ParameterDefinition {
+                name: "Reconnect Buffer".to_string(),
+                parameter_type: ParameterType::Integer,
+                default_value: Value::from(90),
+                min_value: Some(Value::from(1)),
+                max_value: Some(Value::from(3600)),
+                description: "Frames kept while reconnecting before the oldest are dropped"
+                    .to_string(),
+            },
+        );
+
+        let properties = NodeProperties {
+            id,
+            name: "SRT Output".to_string(),
+            node_type: NodeType::Output(OutputType::Srt),
+            input_types: vec![ConnectionType::RenderData],
+            output_types: vec![],
+            parameters,
+            enabled: true,
+        };
+
+        Ok(Self {
+            id,
+            config,
+            properties,
+            connector,
+            sender: None,
+            stats: Arc::new(SrtStats::default()),
+        })
+    }
+
+    pub fn srt_config(&self) -> Result<SrtConfig> {
+        SrtConfig::from_parameters(&self.config.parameters)
+    }
+
+    pub fn is_connected(&self) -> bool {
+        self.stats.connected.load(Ordering::Relaxed)
+    }
+
+    pub fn frames_sent(&self) -> u64 {
+        self.stats.frames_sent.load(Ordering::Relaxed)
+    }
+
+    /// Frames discarded because the reconnect buffer was full
+    pub fn dropped_frames(&self) -> u64 {
+        self.stats.dropped_frames.load(Ordering::Relaxed)
+    }
+
+    pub fn connection_losses(&self) -> u64 {
+        self.stats.connection_losses.load(Ordering::Relaxed)
+    }
+
+    fn ensure_sender(&mut self) -> Result<&SenderThread> {
+        if self.sender.is_none() {
+            let config = self.srt_config()?;
+            self.sender = Some(SenderThread::spawn(
+                config,
+                self.connector.clone(),
+                self.stats.clone(),
+            )?);
+        }
+        Ok(self.sender.as_ref().expect("sender was just started"))
+    }
+
+    fn stop_sender(&mut self) {
+        if let Some(sender) = self.sender.take() {
+            sender.stop();
+        }
+    }
+}
+
+impl NodeProcessor for SrtOutputNode {
+    fn process(&mut self, input: FrameData) -> Result<FrameData> {
+        if let Some(RenderData::Raster2D(ref frame)) = input.render_data {
+            let stats = self.stats.clone();
+            match self.ensure_sender()?.frames.try_send(frame.clone()) {
+                Ok(()) => {}
+                Err(TrySendError::Full(_)) => {
+                    stats.dropped_frames.fetch_add(1, Ordering::Relaxed);
+                }
+                Err(TrySendError::Disconnected(_)) => {
+                    self.sender = None;
+                    bail!("SRT sender thread exited");
+                }
+            }
+        }
+
+        Ok(input)
+    }
+
+    fn get_properties(&self) -> NodeProperties {
+        self.properties.clone()
+    }
+
+    fn on_start(&mut self) -> Result<()> {
+        self.ensure_sender().map(|_| ())
+    }
+
+    fn on_stop(&mut self) -> Result<()> {
+        self.stop_sender();
+        Ok(())
+    }
+
+    fn set_parameter(&mut self, key: &str, value: Value) -> Result<()> {
+        self.config.parameters.insert(key.to_string(), value);
+        // Every parameter is part of the connection, so start a new one
+        self.stop_sender();
+        Ok(())
+    }
+
+    fn get_parameter(&self, key: &str) -> Option<Value> {
+        self.config.parameters.get(key).cloned()
+    }
+}
+
+impl Drop for SrtOutputNode {
+    fn drop(&mut self) {
+        self.stop_sender();
+    }
+}
@@ -0,0 +1,375 @@
+/*
+ * Constellation Studio - Professional Real-time Video Processing
+ * Copyright (c) 2025 MACHIKO LAB
+ *
+ * This program is free software: you can redistribute it and/or modify
+ * it under the terms of the GNU Affero General Public License as published by
+ * the Free Software Foundation, either version 3 of the License, or
+ * (at your option) any later version.
+ *
+ * This program is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+ * GNU Affero General Public License for more details.
+ *
+ * You should have received a copy of the GNU Affero General Public License
+ * along with this program. If not, see <https://www.gnu.org/licenses/>.
+ */
+
+//! FFmpeg-backed demuxing and decoding for `VideoFileReader`
+
+use anyhow::{anyhow, Context as _, Result};
+use constellation_core::{AudioFrame, VideoFormat, VideoFrame};
+use ffmpeg::format::{Pixel, Sample};
+use ffmpeg::media::Type as MediaType;
+use ffmpeg::software::scaling::{context::Context as Scaler, flag::Flags};
+use ffmpeg::{codec, frame, rescale, Packet, Rational, Rescale};
+use ffmpeg_next as ffmpeg;
+use std::path::Path;
+use std::time::Duration;
+use tracing::{debug, warn};
+
+/// Container metadata probed when the file is opened
+pub(super) struct StreamInfo {
+    pub width: u32,
+    pub height: u32,
+    pub fps: f64,
+    pub total_frames: Option<u64>,
+    pub duration: Option<Duration>,
+}
+
+/// The swscale context is only ever used through `&mut self`, so moving it
+/// between threads together with the decoder is sound.
+struct SendScaler(Scaler);
+
+// SAFETY: SwsContext has no thread affinity; it is never shared, only moved.
+unsafe impl Send for SendScaler {}
+
+struct AudioDecoder {
+    stream_index: usize,
+    decoder: ffmpeg::decoder::Audio,
+    sample_rate: u32,
+    channels: u16,
+}
+
+pub(super) struct FfmpegDecoder {
+    input: ffmpeg::format::context::Input,
+    video_stream_index: usize,
+    video_time_base: Rational,
+    video_start_pts: i64,
+    video_decoder: ffmpeg::decoder::Video,
+    scaler: SendScaler,
+    audio: Option<AudioDecoder>,
+    // Interleaved f32 samples decoded since the last returned video frame
+    pending_audio: Vec<f32>,
+    fps: f64,
+    // Index of the next video frame `next_frame` will return
+    next_index: u64,
+    // Frames before this pts are dropped after a seek (seeks land on keyframes)
+    skip_until_pts: Option<i64>,
+    eof: bool,
+}
+
+impl FfmpegDecoder {
+    pub(super) fn open(path: &Path) -> Result<(Self, StreamInfo)> {
+        ffmpeg::init().context("Failed to initialize FFmpeg")?;
+
+        let input = ffmpeg::format::input(path)
+            .with_context(|| format!("FFmpeg could not open {}", path.display()))?;
+
+        let (video_stream_index, video_time_base, video_start_pts, frame_rate, stream_info) = {
+            let stream = input
+                .streams()
+                .best(MediaType::Video)
+                .ok_or_else(|| anyhow!("No video stream in {}", path.display()))?;
+
+            let time_base = stream.time_base();
+            let frame_rate = if stream.avg_frame_rate().numerator() > 0 {
+                stream.avg_frame_rate()
+            } else {
+                stream.rate()
+            };
+            let start_pts = match stream.start_time() {
+                ffmpeg::ffi::AV_NOPTS_VALUE => 0,
+                start => start,
+            };
+
+            let duration = if stream.duration() > 0 {
+                Some(stream.duration() as f64 * f64::from(time_base))
+            } else if input.duration() > 0 {
+                Some(input.duration() as f64 / f64::from(rescale::TIME_BASE.invert()))
+            } else {
+                None
+            };
+
+            (
+                stream.index(),
+                time_base,
+                start_pts,
+                frame_rate,
+                (stream.frames(), duration, stream.parameters()),
+            )
+        };
+        let (stream_frames, duration_secs, parameters) = stream_info;
+
+        let fps = if frame_rate.numerator() > 0 && frame_rate.denominator() > 0 {
+            f64::from(frame_rate)
+        } else {
+            warn!("Unknown frame rate in {}, assuming 30fps", path.display());
+            30.0
+        };
+
+        let video_decoder = codec::context::Context::from_parameters(parameters)?
+            .decoder()
+            .video()
+            .context("Failed to open video decoder")?;
+
+        let scaler = Scaler::get(
+            video_decoder.format(),
+            video_decoder.width(),
+            video_decoder.height(),
+            Pixel::RGBA,
+            video_decoder.width(),
+            video_decoder.height(),
+            Flags::BILINEAR,
+        )
+        .context("Failed to create RGBA scaler")?;
+
+        let audio = input.streams().best(MediaType::Audio).and_then(|stream| {
+            let decoder = codec::context::Context::from_parameters(stream.parameters())
+                .and_then(|context| context.decoder().audio());
+            match decoder {
+                Ok(decoder) => Some(AudioDecoder {
+                    stream_index: stream.index(),
+                    sample_rate: decoder.rate(),
+                    channels: decoder.channels(),
+                    decoder,
+                }),
+                Err(e) => {
+                    warn!("Audio stream present but not decodable, ignoring: {}", e);
+                    None
+                }
+            }
+        });
+
+        let info = StreamInfo {
+            width: video_decoder.width(),
+            height: video_decoder.height(),
+            fps,
+            total_frames: if stream_frames > 0 {
+                Some(stream_frames as u64)
+            } else {
+                duration_secs.map(|secs| (secs * fps).round() as u64)
+            },
+            duration: duration_secs.map(Duration::from_secs_f64),
+        };
+
+        Ok((
+            Self {
+                input,
+                video_stream_index,
+                video_time_base,
+                video_start_pts,
+                video_decoder,
+                scaler: SendScaler(scaler),
+                audio,
+                pending_audio: Vec::new(),
+                fps,
+                next_index: 0,
+                skip_until_pts: None,
+                eof: false,
+            },
+            info,
+        ))
+    }
+
+    pub(super) fn next_index(&self) -> u64 {
+        self.next_index
+    }
+
+    /// Decode the next video frame as RGBA, together with the audio decoded alongside it
+    pub(super) fn next_frame(&mut self) -> Result<Option<(VideoFrame, Option<AudioFrame>)>> {
+        let Some(decoded) = self.decode_video_frame()? else {
+            return Ok(None);
+        };
+
+        let video = self.convert_to_rgba(&decoded)?;
+        let audio = self.take_audio();
+        Ok(Some((video, audio)))
+    }
+
+    /// Decode and drop a frame without color conversion (used to catch up playback)
+    pub(super) fn skip_frame(&mut self) -> Result<bool> {
+        let skipped = self.decode_video_frame()?.is_some();
+        self.pending_audio.clear();
+        Ok(skipped)
+    }
+
+    pub(super) fn seek_to_frame(&mut self, frame_number: u64) -> Result<()> {
+        let seconds = frame_number as f64 / self.fps;
+        let target_pts =
+            self.video_start_pts + (seconds / f64::from(self.video_time_base)).round() as i64;
+
+        // Seek the demuxer to the keyframe at or before the target
+        let timestamp = target_pts.rescale(self.video_time_base, rescale::TIME_BASE);
+        self.input
+            .seek(timestamp, ..timestamp + 1)
+            .with_context(|| format!("Failed to seek to frame {frame_number}"))?;
+
+        self.video_decoder.flush();
+        if let Some(audio) = self.audio.as_mut() {
+            audio.decoder.flush();
+        }
+        self.pending_audio.clear();
+        self.skip_until_pts = Some(target_pts);
+        self.next_index = frame_number;
+        self.eof = false;
+
+        debug!("FFmpeg seek to frame {} (pts {})", frame_number, target_pts);
+        Ok(())
+    }
+
+    fn decode_video_frame(&mut self) -> Result<Option<frame::Video>> {
+        loop {
+            let mut decoded = frame::Video::empty();
+            if self.video_decoder.receive_frame(&mut decoded).is_ok() {
+                if let (Some(skip_until), Some(pts)) = (self.skip_until_pts, decoded.timestamp()) {
+                    if pts < skip_until {
+                        continue;
+                    }
+                }
+                self.skip_until_pts = None;
+                self.next_index += 1;
+                return Ok(Some(decoded));
+            }
+
+            if self.eof {
+                return Ok(None);
+            }
+
+            let mut packet = Packet::empty();
+            match packet.read(&mut self.input) {
+                Ok(()) => self.send_packet(&packet)?,
+                Err(ffmpeg::Error::Eof) => {
+                    self.eof = true;
+                    self.video_decoder.send_eof()?;
+                    if let Some(audio) = self.audio.as_mut() {
+                        audio.decoder.send_eof()?;
+                    }
+                    self.drain_audio();
+                }
+                Err(e) => return Err(anyhow!("Failed to read packet: {}", e)),
+            }
+        }
+    }
+
+    fn send_packet(&mut self, packet: &Packet) -> Result<()> {
+        if packet.stream() == self.video_stream_index {
+            self.video_decoder
+                .send_packet(packet)
+                .context("Failed to decode video packet")?;
+        } else if let Some(audio) = self.audio.as_mut() {
+            if packet.stream() == audio.stream_index {
+                if let Err(e) = audio.decoder.send_packet(packet) {
+                    warn!("Dropping undecodable audio packet: {}", e);
+                }
+                self.drain_audio();
+            }
+        }
+        Ok(())
+    }
+
+    fn drain_audio(&mut self) {
+        let Some(audio) = self.audio.as_mut() else {
+            return;
+        };
+
+        let mut decoded = frame::Audio::empty();
+        while audio.decoder.receive_frame(&mut decoded).is_ok() {
+            // Drop audio belonging to frames skipped after a seek
+            if self.skip_until_pts.is_some() {
+                continue;
+            }
+            match interleaved_f32(&decoded) {
+                Some(samples) => self.pending_audio.extend_from_slice(&samples),
+                None => warn!("Unsupported audio sample format {:?}", decoded.format()),
+            }
+        }
+    }
+
+    fn take_audio(&mut self) -> Option<AudioFrame> {
+        let audio = self.audio.as_ref()?;
+        if self.pending_audio.is_empty() {
+            return None;
+        }
+
+        Some(AudioFrame {
+            sample_rate: audio.sample_rate,
+            channels: audio.channels,
+            samples: std::mem::take(&mut self.pending_audio),
+        })
+    }
+
+    fn convert_to_rgba(&mut self, decoded: &frame::Video) -> Result<VideoFrame> {
+        let mut rgba = frame::Video::empty();
+        self.scaler
+            .0
+            .run(decoded, &mut rgba)
+            .context("Failed to convert frame to RGBA")?;
+
+        // Strip per-row padding
+        let (width, height) = (rgba.width(), rgba.height());
+        let row_bytes = width as usize * 4;
+        let stride = rgba.stride(0);
+        let mut data = Vec::with_capacity(row_bytes * height as usize);
+        for row in rgba.data(0).chunks(stride).take(height as usize) {
+            data.extend_from_slice(&row[..row_bytes]);
+        }
+
+        Ok(VideoFrame {
+            width,
+            height,
+            format: VideoFormat::Rgba8,
+            data,
+        })
+    }
+}
+
+/// Convert a decoded audio frame of any common sample format to interleaved f32
+fn interleaved_f32(frame: &frame::Audio) -> Option<Vec<f32>> {
+    match frame.format() {
+        Sample::U8(_) => Some(interleave::<u8>(frame, |v| (v as f32 - 128.0) / 128.0)),
+        Sample::I16(_) => Some(interleave::<i16>(frame, |v| v as f32 / 32_768.0)),
+        Sample::I32(_) => Some(interleave::<i32>(frame, |v| v as f32 / 2_147_483_648.0)),
+        Sample::F32(_) => Some(interleave::<f32>(frame, |v| v)),
+        Sample::F64(_) => Some(interleave::<f64>(frame, |v| v as f32)),
+        Sample::I64(_) | Sample::None => None,
+    }
+}
+
+fn interleave<T: frame::audio::Sample + Copy>(
+    frame: &frame::Audio,
+    to_f32: fn(T) -> f32,
+) -> Vec<f32> {
+    let channels = frame.channels() as usize;
+    let samples = frame.samples();
+
+    if frame.is_packed() {
+        // `plane()` only spans one channel's worth of samples for packed
+        // layouts, so read the whole interleaved buffer directly
+        let data = frame.data(0);
+        let count = (samples * channels).min(data.len() / std::mem::size_of::<T>());
+        // SAFETY: FFmpeg allocates sample buffers suitably aligned for the
+        // sample type, and `count` stays within the plane's byte length.
+        let values = unsafe { std::slice::from_raw_parts(data.as_ptr() as *const T, count) };
+        values.iter().map(|&v| to_f32(v)).collect()
+    } else {
+        let mut output = vec![0.0f32; samples * channels];
+        for channel in 0..channels {
+            for (i, &value) in frame.plane::<T>(channel).iter().enumerate() {
+                output[i * channels + channel] = to_f32(value);
+            }
+        }
+        output
+    }
+}
@@ -22,6 +22,9 @@ use std::path::{Path, PathBuf};
 use std::time::{Duration, Instant};
 use tracing::{debug, error, info, warn};
 
+#[cfg(feature = "ffmpeg")]
+mod decoder;
+
 pub struct VideoFileReader {
     file_path: PathBuf,
     is_open: bool,
@@ -33,6 +36,13 @@ pub struct VideoFileReader {
     duration: Option<Duration>,
     loop_playback: bool,
     playback_start: Option<Instant>,
+    // Sequence number for the next delivered frame, counting from 1 (0 means unset
+    // in FrameData); skipped frames consume numbers
+    next_sequence: u64,
+    last_frame_timestamp: Duration,
+    last_frame_sequence: u64,
+    #[cfg(feature = "ffmpeg")]
+    decoder: Option<decoder::FfmpegDecoder>,
 }
 
 impl VideoFileReader {
@@ -57,6 +67,11 @@ impl VideoFileReader {
             duration: None,
             loop_playback: false,
             playback_start: None,
+            next_sequence: 1,
+            last_frame_timestamp: Duration::ZERO,
+            last_frame_sequence: 0,
+            #[cfg(feature = "ffmpeg")]
+            decoder: None,
         })
     }
 
@@ -67,8 +82,19 @@ impl VideoFileReader {
 
         info!("Opening video file: {}", self.file_path.display());
 
-        // TODO: Implement actual FFmpeg video file opening
-        // For now, simulate opening and getting metadata
+        #[cfg(feature = "ffmpeg")]
+        {
+            let (decoder, info) = decoder::FfmpegDecoder::open(&self.file_path)?;
+            self.width = info.width;
+            self.height = info.height;
+            self.fps = info.fps;
+            self.total_frames = info.total_frames;
+            self.duration = info.duration;
+            self.decoder = Some(decoder);
+        }
+
+        // Without FFmpeg, simulate opening and getting metadata
+        #[cfg(not(feature = "ffmpeg"))]
         self.analyze_file()?;
 
         self.is_open = true;
@@ -88,11 +114,15 @@ impl VideoFileReader {
 
         info!("Closing video file: {}", self.file_path.display());
 
-        // TODO: Implement actual FFmpeg cleanup
+        #[cfg(feature = "ffmpeg")]
+        {
+            self.decoder = None;
+        }
 
         self.is_open = false;
         self.current_frame = 0;
         self.playback_start = None;
+        self.next_sequence = 1;
 
         Ok(())
     }
@@ -110,6 +140,7 @@ impl VideoFileReader {
             // Skip frames if we're behind, or wait if we're ahead
             match expected_frame.cmp(&self.current_frame) {
                 std::cmp::Ordering::Greater => {
+                    self.next_sequence += expected_frame - self.current_frame;
                     self.current_frame = expected_frame;
                 }
                 std::cmp::Ordering::Less => {
@@ -124,21 +155,15 @@ impl VideoFileReader {
         // Check if we've reached the end and loop if needed
         if let Some(total) = self.total_frames {
             if self.current_frame >= total {
-                if self.loop_playback {
-                    self.current_frame = 0;
-                    self.playback_start = Some(Instant::now());
-                    info!("Looping video playback");
-                } else {
-                    return Err(anyhow::anyhow!("End of video file reached"));
-                }
+                self.restart_or_finish()?;
             }
         }
 
-        // TODO: Implement actual FFmpeg frame reading
-        // For now, generate a test pattern with frame counter
-        let video_frame = self.generate_test_frame()?;
-        let audio_frame = self.generate_test_audio()?;
+        let (video_frame, audio_frame) = self.decode_current_frame()?;
 
+        self.last_frame_timestamp = Duration::from_secs_f64(self.current_frame as f64 / self.fps);
+        self.last_frame_sequence = self.next_sequence;
+        self.next_sequence += 1;
         self.current_frame += 1;
 
         debug!(
@@ -146,6 +171,64 @@ impl VideoFileReader {
             self.current_frame, self.total_frames
         );
 
+        Ok((video_frame, audio_frame))
+    }
+
+    fn restart_or_finish(&mut self) -> Result<()> {
+        if !self.loop_playback {
+            return Err(anyhow::anyhow!("End of video file reached"));
+        }
+
+        #[cfg(feature = "ffmpeg")]
+        if let Some(decoder) = self.decoder.as_mut() {
+            decoder.seek_to_frame(0)?;
+        }
+
+        self.current_frame = 0;
+        self.playback_start = Some(Instant::now());
+        info!("Looping video playback");
+        Ok(())
+    }
+
+    #[cfg(feature = "ffmpeg")]
+    fn decode_current_frame(&mut self) -> Result<(VideoFrame, Option<AudioFrame>)> {
+        let mut restarted = false;
+        loop {
+            let decoder = self
+                .decoder
+                .as_mut()
+                .ok_or_else(|| anyhow::anyhow!("Video decoder not initialized"))?;
+
+            // Decode and drop frames the real-time pacing skipped over
+            let mut exhausted = false;
+            while decoder.next_index() < self.current_frame {
+                if !decoder.skip_frame()? {
+                    exhausted = true;
+                    break;
+                }
+            }
+
+            if !exhausted {
+                if let Some(frame) = decoder.next_frame()? {
+                    return Ok(frame);
+                }
+            }
+
+            // The container held fewer frames than its metadata claimed
+            if restarted {
+                return Err(anyhow::anyhow!("No decodable frames in video file"));
+            }
+            self.total_frames = Some(decoder.next_index());
+            self.restart_or_finish()?;
+            restarted = true;
+        }
+    }
+
+    #[cfg(not(feature = "ffmpeg"))]
+    fn decode_current_frame(&mut self) -> Result<(VideoFrame, Option<AudioFrame>)> {
+        // Without FFmpeg, generate a test pattern with frame counter
+        let video_frame = self.generate_test_frame()?;
+        let audio_frame = self.generate_test_audio()?;
         Ok((video_frame, Some(audio_frame)))
     }
 
@@ -164,7 +247,11 @@ impl VideoFileReader {
             }
         }
 
-        // TODO: Implement actual FFmpeg seeking
+        #[cfg(feature = "ffmpeg")]
+        if let Some(decoder) = self.decoder.as_mut() {
+            decoder.seek_to_frame(frame_number)?;
+        }
+
         self.current_frame = frame_number;
 
         // Reset timing for accurate playback after seek
@@ -197,8 +284,7 @@ impl VideoFileReader {
     }
 
     fn analyze_file(&mut self) -> Result<()> {
-        // TODO: Implement actual FFmpeg file analysis
-        // For now, simulate based on file extension and create reasonable defaults
+        // Simulate based on file extension and create reasonable defaults
 
         let extension = self
             .file_path
@@ -314,6 +400,17 @@ impl VideoFileReader {
     pub fn fps(&self) -> f64 {
         self.fps
     }
+
+    /// Presentation time of the most recently read frame within the file
+    pub fn last_frame_timestamp(&self) -> Duration {
+        self.last_frame_timestamp
+    }
+
+    /// Sequence number of the most recently read frame, starting at 1 (0 before the
+    /// first read). Gaps mean frames were skipped
+    pub fn last_frame_sequence(&self) -> u64 {
+        self.last_frame_sequence
+    }
 }
 
 impl Drop for VideoFileReader {
@@ -368,6 +465,7 @@ mod tests {
     }
 
     #[test]
+    #[cfg(not(feature = "ffmpeg"))] // Dummy files are not decodable
     fn test_video_file_reader_open_close() {
         let path = create_test_file("test_video2", "mp4").unwrap();
         let mut reader = VideoFileReader::new(&path).unwrap();
@@ -387,6 +485,7 @@ mod tests {
     }
 
     #[test]
+    #[cfg(not(feature = "ffmpeg"))] // Dummy files are not decodable
     fn test_video_file_reader_metadata() {
         let path = create_test_file("test_video3", "webm").unwrap();
         let mut reader = VideoFileReader::new(&path).unwrap();
@@ -404,6 +503,7 @@ mod tests {
     }
 
     #[test]
+    #[cfg(not(feature = "ffmpeg"))] // Dummy files are not decodable
     fn test_video_file_reader_frame_reading() {
         let path = create_test_file("test_video4", "mp4").unwrap();
         let mut reader = VideoFileReader::new(&path).unwrap();
@@ -427,6 +527,7 @@ mod tests {
     }
 
     #[test]
+    #[cfg(not(feature = "ffmpeg"))] // Dummy files are not decodable
     fn test_video_file_reader_seeking() {
         let path = create_test_file("test_video5", "mp4").unwrap();
         let mut reader = VideoFileReader::new(&path).unwrap();
@@ -446,6 +547,7 @@ mod tests {
     }
 
     #[test]
+    #[cfg(not(feature = "ffmpeg"))] // Dummy files are not decodable
     fn test_video_file_reader_loop_playback() {
         let path = create_test_file("test_video6", "mp4").unwrap();
         let mut reader = VideoFileReader::new(&path).unwrap();
@@ -459,10 +561,39 @@ mod tests {
         let frame_result = reader.read_frame(); // Should read last frame
         assert!(frame_result.is_ok());
 
+        let last_sequence = reader.last_frame_sequence();
+
         let frame_result = reader.read_frame(); // Should loop back to frame 0
         assert!(frame_result.is_ok());
         assert_eq!(reader.current_frame(), 1); // Should be at frame 1 after reading frame 0
 
+        // Presentation time restarts with the loop, the sequence keeps counting
+        assert_eq!(reader.last_frame_timestamp(), Duration::ZERO);
+        assert!(reader.last_frame_sequence() > last_sequence);
+
+        // Clean up
+        let _ = std::fs::remove_file(&path);
+    }
+
+    #[test]
+    #[cfg(not(feature = "ffmpeg"))] // Dummy files are not decodable
+    fn test_video_file_reader_frame_timing() {
+        let path = create_test_file("test_video7", "mp4").unwrap();
+        let mut reader = VideoFileReader::new(&path).unwrap();
+
+        reader.open().unwrap();
+
+        let mut previous: Option<(Duration, u64)> = None;
+        for _ in 0..5 {
+            reader.read_frame().unwrap();
+            let timing = (reader.last_frame_timestamp(), reader.last_frame_sequence());
+            if let Some((timestamp, sequence)) = previous {
+                assert!(timing.0 > timestamp);
+                assert!(timing.1 > sequence);
+            }
+            previous = Some(timing);
+        }
+
         // Clean up
         let _ = std::fs::remove_file(&path);
     }
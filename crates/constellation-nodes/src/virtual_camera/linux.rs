@@ -16,7 +16,7 @@
  * along with this program. If not, see <https://www.gnu.org/licenses/>.
  */
 
-use super::{VideoFormat, VirtualWebcamBackend};
+use super::{conversion, VideoFormat, VirtualWebcamBackend};
 use anyhow::{anyhow, Result};
 use constellation_core::VideoFrame;
 use std::fs::{File, OpenOptions};
@@ -148,6 +148,19 @@ impl VirtualWebcamBackend for LinuxVirtualWebcam {
         self.fps = fps;
         Ok(())
     }
+
+    fn format(&self) -> VideoFormat {
+        self.format
+    }
+
+    fn set_format(&mut self, format: VideoFormat) -> Result<()> {
+        if self.is_active.load(Ordering::Relaxed) {
+            return Err(anyhow!("Cannot change format while active"));
+        }
+
+        self.format = format;
+        Ok(())
+    }
 }
 
 impl LinuxVirtualWebcam {
@@ -236,13 +249,15 @@ impl LinuxVirtualWebcam {
 
         // Set video format
         let format_cmd = Command::new("v4l2-ctl")
-            .args(&[
+            .args([
                 "--device",
-                &device_path,
+                device_path,
                 "--set-fmt-video",
                 &format!(
-                    "width={},height={},pixelformat=YU12",
-                    self.width, self.height
+                    "width={},height={},pixelformat={}",
+                    self.width,
+                    self.height,
+                    v4l2_fourcc(self.format)
                 ),
             ])
             .output();
@@ -263,12 +278,7 @@ impl LinuxVirtualWebcam {
 
         // Set frame rate
         let fps_cmd = Command::new("v4l2-ctl")
-            .args(&[
-                "--device",
-                &device_path,
-                "--set-parm",
-                &self.fps.to_string(),
-            ])
+            .args(["--device", device_path, "--set-parm", &self.fps.to_string()])
             .output();
 
         match fps_cmd {
@@ -296,48 +306,19 @@ impl LinuxVirtualWebcam {
     }
 
     /// Convert VideoFrame to V4L2-compatible format
-    fn convert_frame_for_v4l2(&self, frame: &VideoFrame) -> Result<Vec<u8>> {
-        // Convert frame data to YUV420 format for V4L2
-        // This is a simplified implementation
-
-        let expected_size = (self.width * self.height * 3 / 2) as usize;
-        let mut yuv_data = vec![0u8; expected_size];
-
-        // Placeholder conversion - in practice would implement proper RGB->YUV conversion
-        // For now, create a test pattern
-        self.create_test_pattern(&mut yuv_data);
-
-        Ok(yuv_data)
+    pub fn convert_frame_for_v4l2(&self, frame: &VideoFrame) -> Result<Vec<u8>> {
+        // Convert to the configured V4L2 pixel format (YUV420 by default)
+        conversion::convert_frame(frame, self.format)
     }
+}
 
-    /// Create test pattern for debugging
-    fn create_test_pattern(&self, buffer: &mut [u8]) {
-        let y_size = (self.width * self.height) as usize;
-        let uv_size = y_size / 4;
-
-        // Y plane (luminance) - create gradient
-        for y in 0..self.height {
-            for x in 0..self.width {
-                let idx = (y * self.width + x) as usize;
-                if idx < y_size {
-                    buffer[idx] = ((x + y) % 256) as u8;
-                }
-            }
-        }
-
-        // U plane (chroma)
-        for i in 0..uv_size {
-            if y_size + i < buffer.len() {
-                buffer[y_size + i] = 128; // Neutral chroma
-            }
-        }
-
-        // V plane (chroma)
-        for i in 0..uv_size {
-            if y_size + uv_size + i < buffer.len() {
-                buffer[y_size + uv_size + i] = 128; // Neutral chroma
-            }
-        }
+/// V4L2 pixel format code for an output format
+fn v4l2_fourcc(format: VideoFormat) -> &'static str {
+    match format {
+        VideoFormat::RGB24 => "RGB3",
+        VideoFormat::BGRA32 => "AR24",
+        VideoFormat::YUV420 => "YU12",
+        VideoFormat::NV12 => "NV12",
     }
 }
 
@@ -16,7 +16,7 @@
  * along with this program. If not, see <https://www.gnu.org/licenses/>.
  */
 
-use super::{VideoFormat, VirtualWebcamBackend};
+use super::{conversion, VideoFormat, VirtualWebcamBackend};
 use anyhow::{anyhow, Result};
 use constellation_core::VideoFrame;
 use std::sync::atomic::{AtomicBool, Ordering};
@@ -126,6 +126,19 @@ impl VirtualWebcamBackend for MacOSVirtualWebcam {
         self.fps = fps;
         Ok(())
     }
+
+    fn format(&self) -> VideoFormat {
+        self.format
+    }
+
+    fn set_format(&mut self, format: VideoFormat) -> Result<()> {
+        if self.is_active.load(Ordering::Relaxed) {
+            return Err(anyhow!("Cannot change format while active"));
+        }
+
+        self.format = format;
+        Ok(())
+    }
 }
 
 impl MacOSVirtualWebcam {
@@ -217,12 +230,18 @@ impl MacOSVirtualWebcam {
             }
         }
 
-        // Convert frame data if necessary
-        let processed_data = if frame.format == constellation_core::VideoFormat::Rgba8 {
-            self.convert_rgba_to_bgra(&frame.data)
-        } else {
-            frame.data.clone()
-        };
+        // Convert to the negotiated output format (BGRA32 or NV12)
+        let processed_data = conversion::convert_frame(frame, self.format)?;
+        if self.format == VideoFormat::NV12 {
+            // Phase 2 will copy these into the two CVPixelBuffer planes
+            let (y_plane, uv_plane) =
+                conversion::split_nv12(&processed_data, self.width, self.height)?;
+            tracing::trace!(
+                "NV12 planes: Y={} bytes, UV={} bytes",
+                y_plane.len(),
+                uv_plane.len()
+            );
+        }
 
         // Increment frame counter for timing reference
         self.frame_count += 1;
@@ -241,23 +260,6 @@ impl MacOSVirtualWebcam {
         Ok(processed_data)
     }
 
-    /// Convert RGBA to BGRA format for macOS compatibility
-    fn convert_rgba_to_bgra(&self, rgba_data: &[u8]) -> Vec<u8> {
-        let mut bgra_data = Vec::with_capacity(rgba_data.len());
-
-        for chunk in rgba_data.chunks_exact(4) {
-            if chunk.len() == 4 {
-                // RGBA -> BGRA: swap R and B channels
-                bgra_data.push(chunk[2]); // B
-                bgra_data.push(chunk[1]); // G
-                bgra_data.push(chunk[0]); // R
-                bgra_data.push(chunk[3]); // A
-            }
-        }
-
-        bgra_data
-    }
-
     /// Send processed frame data to virtual device
     /// Phase 1: Simulate frame delivery to virtual webcam
     fn send_processed_frame(&self, frame_data: Vec<u8>) -> Result<()> {
@@ -387,4 +389,23 @@ mod tests {
         };
         assert!(webcam.process_frame(&invalid_alignment_frame).is_err());
     }
+
+    #[test]
+    fn test_nv12_output() {
+        use constellation_core::{VideoFormat as CoreVideoFormat, VideoFrame};
+
+        let mut webcam = MacOSVirtualWebcam::new("Test Camera".to_string(), 4, 2, 30).unwrap();
+        webcam.set_format(VideoFormat::NV12).unwrap();
+
+        let frame = VideoFrame {
+            width: 4,
+            height: 2,
+            format: CoreVideoFormat::Rgba8,
+            data: vec![255u8; 4 * 2 * 4],
+        };
+        let nv12 = webcam.process_frame(&frame).unwrap();
+        assert_eq!(nv12.len(), VideoFormat::NV12.frame_size(4, 2));
+        assert_eq!(&nv12[..8], &[235; 8]);
+        assert_eq!(&nv12[8..], &[128; 4]);
+    }
 }
@@ -16,7 +16,7 @@
  * along with this program. If not, see <https://www.gnu.org/licenses/>.
  */
 
-use anyhow::Result;
+use anyhow::{anyhow, Result};
 use constellation_core::VideoFrame;
 
 #[cfg(target_os = "windows")]
@@ -61,10 +61,34 @@ pub trait VirtualWebcamBackend: Send + Sync {
 
     /// Update frame rate (if supported)
     fn set_fps(&mut self, fps: u32) -> Result<()>;
+
+    /// Get the pixel format frames are converted to before delivery
+    fn format(&self) -> VideoFormat;
+
+    /// Change the output pixel format (only while stopped)
+    fn set_format(&mut self, format: VideoFormat) -> Result<()>;
+
+    /// Format a consumer (OBS, Zoom, ...) switched the device to since the
+    /// last call, if any. A pixel format change is reported through `format()`.
+    /// Backends without consumer negotiation never report one.
+    fn poll_format_change(&mut self) -> Option<ConsumerFormat> {
+        None
+    }
+}
+
+/// Output format as negotiated by a consumer of the virtual device
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ConsumerFormat {
+    pub width: u32,
+    pub height: u32,
+    pub fps: u32,
 }
 
+/// Called with the new negotiated configuration after a consumer-driven change
+pub type FormatChangeCallback = Box<dyn FnMut(&VirtualWebcamConfig) + Send + Sync>;
+
 /// Virtual webcam configuration
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, PartialEq)]
 pub struct VirtualWebcamConfig {
     pub device_name: String,
     pub width: u32,
@@ -113,7 +137,11 @@ impl VideoFormat {
         match self {
             VideoFormat::RGB24 => (width * height * 3) as usize,
             VideoFormat::BGRA32 => (width * height * 4) as usize,
-            VideoFormat::YUV420 | VideoFormat::NV12 => (width * height * 3 / 2) as usize,
+            VideoFormat::YUV420 | VideoFormat::NV12 => {
+                // Chroma planes are rounded up for odd dimensions
+                let chroma = width.div_ceil(2) * height.div_ceil(2);
+                (width * height + chroma * 2) as usize
+            }
         }
     }
 
@@ -138,9 +166,11 @@ pub type PlatformVirtualWebcam = macos::MacOSVirtualWebcam;
 pub type PlatformVirtualWebcam = linux::LinuxVirtualWebcam;
 
 /// Cross-platform virtual webcam manager
-pub struct VirtualWebcam {
-    backend: PlatformVirtualWebcam,
+pub struct VirtualWebcam<B: VirtualWebcamBackend = PlatformVirtualWebcam> {
+    backend: B,
     config: VirtualWebcamConfig,
+    platform: PlatformInfo,
+    format_change_callbacks: Vec<FormatChangeCallback>,
 }
 
 impl VirtualWebcam {
@@ -155,14 +185,44 @@ impl VirtualWebcam {
 
     /// Create a new virtual webcam with custom configuration
     pub fn new_with_config(config: VirtualWebcamConfig) -> Result<Self> {
-        let backend = PlatformVirtualWebcam::new(
-            config.device_name.clone(),
-            config.width,
-            config.height,
-            config.fps,
-        )?;
+        let platform = PlatformInfo::current();
+        let (width, height) = platform.clamp_resolution(config.width, config.height);
+        let backend =
+            PlatformVirtualWebcam::new(config.device_name.clone(), width, height, config.fps)?;
+
+        Self::with_backend(
+            backend,
+            VirtualWebcamConfig {
+                width,
+                height,
+                ..config
+            },
+            platform,
+        )
+    }
+
+    /// Get platform information
+    pub fn platform_info() -> PlatformInfo {
+        PlatformInfo::current()
+    }
+}
 
-        Ok(Self { backend, config })
+impl<B: VirtualWebcamBackend> VirtualWebcam<B> {
+    /// Wrap an already constructed backend, negotiating the output format
+    /// against the given platform capabilities
+    pub fn with_backend(
+        mut backend: B,
+        config: VirtualWebcamConfig,
+        platform: PlatformInfo,
+    ) -> Result<Self> {
+        backend.set_format(platform.output_format())?;
+
+        Ok(Self {
+            backend,
+            config,
+            platform,
+            format_change_callbacks: Vec::new(),
+        })
     }
 
     /// Start the virtual webcam
@@ -175,11 +235,61 @@ impl VirtualWebcam {
         self.backend.stop()
     }
 
-    /// Send a frame to the virtual webcam
+    /// Pixel format negotiated with the platform backend
+    pub fn output_format(&self) -> VideoFormat {
+        self.backend.format()
+    }
+
+    /// Send a frame to the virtual webcam.
+    ///
+    /// Consumer format changes are picked up first, so callbacks registered
+    /// with `on_format_change` run before the frame is delivered.
     pub fn send_frame(&mut self, frame: &VideoFrame) -> Result<()> {
+        self.poll_format_change();
         self.backend.send_frame(frame)
     }
 
+    /// Resolution, frame rate and pixel format currently delivered to consumers
+    pub fn negotiated_format(&self) -> VirtualWebcamConfig {
+        VirtualWebcamConfig {
+            format: self.backend.format(),
+            ..self.config.clone()
+        }
+    }
+
+    /// Register a callback for consumer-driven format changes, e.g. to let the
+    /// pipeline render at the resolution the consumer asked for
+    pub fn on_format_change(
+        &mut self,
+        callback: impl FnMut(&VirtualWebcamConfig) + Send + Sync + 'static,
+    ) {
+        self.format_change_callbacks.push(Box::new(callback));
+    }
+
+    /// Apply a format change reported by the backend, notifying callbacks.
+    /// Returns the new negotiated configuration if the backend reported one.
+    pub fn poll_format_change(&mut self) -> Option<VirtualWebcamConfig> {
+        let change = self.backend.poll_format_change()?;
+        self.config.width = change.width;
+        self.config.height = change.height;
+        self.config.fps = change.fps;
+
+        let negotiated = self.negotiated_format();
+
+        tracing::info!(
+            "Consumer switched {} to {}x{} @ {} fps ({:?})",
+            negotiated.device_name,
+            negotiated.width,
+            negotiated.height,
+            negotiated.fps,
+            negotiated.format
+        );
+        for callback in &mut self.format_change_callbacks {
+            callback(&negotiated);
+        }
+        Some(negotiated)
+    }
+
     /// Check if the virtual webcam is active
     pub fn is_active(&self) -> bool {
         self.backend.is_active()
@@ -190,25 +300,57 @@ impl VirtualWebcam {
         &self.config
     }
 
-    /// Update resolution dynamically
+    /// Capabilities the resolution and format negotiation is checked against
+    pub fn platform(&self) -> &PlatformInfo {
+        &self.platform
+    }
+
+    /// Update resolution dynamically.
+    ///
+    /// Sizes beyond the platform maximum are scaled down to fit. While the
+    /// webcam is running, backends that cannot renegotiate in place are
+    /// restarted with the new size.
     pub fn set_resolution(&mut self, width: u32, height: u32) -> Result<()> {
-        self.backend.set_resolution(width, height)?;
+        if width == 0 || height == 0 {
+            return Err(anyhow!("Invalid resolution {}x{}", width, height));
+        }
+
+        let (width, height) = self.platform.clamp_resolution(width, height);
+        if !self.backend.is_active() {
+            self.backend.set_resolution(width, height)?;
+        } else if self.platform.supports_dynamic_resolution {
+            if let Err(e) = self.backend.set_resolution(width, height) {
+                tracing::warn!(
+                    "Backend refused live resolution change to {}x{} ({}), restarting",
+                    width,
+                    height,
+                    e
+                );
+                self.restart_with_resolution(width, height)?;
+            }
+        } else {
+            self.restart_with_resolution(width, height)?;
+        }
+
         self.config.width = width;
         self.config.height = height;
         Ok(())
     }
 
+    fn restart_with_resolution(&mut self, width: u32, height: u32) -> Result<()> {
+        self.backend.stop()?;
+        let result = self.backend.set_resolution(width, height);
+        // Bring the device back up even if the new size was rejected
+        self.backend.start()?;
+        result
+    }
+
     /// Update frame rate dynamically
     pub fn set_fps(&mut self, fps: u32) -> Result<()> {
         self.backend.set_fps(fps)?;
         self.config.fps = fps;
         Ok(())
     }
-
-    /// Get platform information
-    pub fn platform_info() -> PlatformInfo {
-        PlatformInfo::current()
-    }
 }
 
 /// Platform information for virtual webcam capabilities
@@ -267,6 +409,47 @@ impl PlatformInfo {
         width <= self.max_resolution.0 && height <= self.max_resolution.1
     }
 
+    /// Scale a resolution down to fit within `max_resolution`, keeping its
+    /// aspect ratio. Supported resolutions are returned unchanged.
+    pub fn clamp_resolution(&self, width: u32, height: u32) -> (u32, u32) {
+        if self.supports_resolution(width, height) {
+            return (width, height);
+        }
+
+        let (max_width, max_height) = self.max_resolution;
+        let (w, h) = (width as u64, height as u64);
+        let clamped = if w * max_height as u64 > h * max_width as u64 {
+            (max_width, ((h * max_width as u64) / w).max(1) as u32)
+        } else {
+            (((w * max_height as u64) / h).max(1) as u32, max_height)
+        };
+
+        tracing::warn!(
+            "Resolution {}x{} exceeds {} maximum {}x{}, using {}x{}",
+            width,
+            height,
+            self.platform,
+            max_width,
+            max_height,
+            clamped.0,
+            clamped.1
+        );
+        clamped
+    }
+
+    /// Pick the format frames are delivered in: NV12 whenever the platform
+    /// prefers it, otherwise the platform's first preferred format
+    pub fn output_format(&self) -> VideoFormat {
+        if self.preferred_formats.contains(&VideoFormat::NV12) {
+            VideoFormat::NV12
+        } else {
+            self.preferred_formats
+                .first()
+                .copied()
+                .unwrap_or(VideoFormat::RGB24)
+        }
+    }
+
     /// Check if a frame rate is supported
     pub fn supports_fps(&self, fps: u32) -> bool {
         self.supported_fps.contains(&fps)
@@ -285,40 +468,79 @@ impl PlatformInfo {
 /// Frame conversion utilities
 pub mod conversion {
     use super::*;
+    use anyhow::bail;
+    use constellation_core::convert::rgba_to_format;
+    use constellation_core::VideoFormat as CoreVideoFormat;
+
+    // BT.709 limited range, shared with the pipeline's format negotiation
+    pub use constellation_core::convert::{
+        frame_to_rgba, rgba_to_nv12, rgba_to_uyvy, rgba_to_yuv420, yuv420_to_rgba,
+    };
 
     /// Convert VideoFrame to the specified format for virtual webcam
     pub fn convert_frame(frame: &VideoFrame, target_format: VideoFormat) -> Result<Vec<u8>> {
-        // Simplified conversion for now - in practice would use proper color space conversion
-        match target_format {
-            VideoFormat::RGB24 => convert_to_rgb24(frame),
-            VideoFormat::BGRA32 => convert_to_bgra32(frame),
-            VideoFormat::YUV420 => convert_to_yuv420(frame),
-            VideoFormat::NV12 => convert_to_nv12(frame),
+        let converted = match target_format {
+            VideoFormat::RGB24 => convert_to_rgb24(frame)?,
+            VideoFormat::BGRA32 => convert_to_bgra32(frame)?,
+            VideoFormat::YUV420 => convert_to_yuv420(frame)?,
+            VideoFormat::NV12 => convert_to_nv12(frame)?,
+        };
+
+        let expected = target_format.frame_size(frame.width, frame.height);
+        if converted.len() != expected {
+            bail!(
+                "Converted {:?} frame is {} bytes, expected {} for {}x{}",
+                target_format,
+                converted.len(),
+                expected,
+                frame.width,
+                frame.height
+            );
         }
+
+        Ok(converted)
+    }
+
+    /// Split an NV12 buffer into its Y plane and interleaved UV plane,
+    /// validating the total size against `frame_size`
+    pub fn split_nv12(data: &[u8], width: u32, height: u32) -> Result<(&[u8], &[u8])> {
+        let expected = VideoFormat::NV12.frame_size(width, height);
+        if data.len() != expected {
+            bail!(
+                "NV12 buffer is {} bytes, expected {} for {}x{}",
+                data.len(),
+                expected,
+                width,
+                height
+            );
+        }
+
+        Ok(data.split_at((width * height) as usize))
     }
 
     fn convert_to_rgb24(frame: &VideoFrame) -> Result<Vec<u8>> {
-        // Placeholder implementation - would implement proper conversion
-        let size = (frame.width * frame.height * 3) as usize;
-        Ok(vec![0u8; size])
+        let rgba = frame_to_rgba(frame)?;
+        rgba_to_format(&rgba, frame.width, frame.height, &CoreVideoFormat::Rgb8)
     }
 
     fn convert_to_bgra32(frame: &VideoFrame) -> Result<Vec<u8>> {
-        // Placeholder implementation - would implement proper conversion
-        let size = (frame.width * frame.height * 4) as usize;
-        Ok(vec![0u8; size])
+        let rgba = frame_to_rgba(frame)?;
+        rgba_to_format(&rgba, frame.width, frame.height, &CoreVideoFormat::Bgra8)
     }
 
     fn convert_to_yuv420(frame: &VideoFrame) -> Result<Vec<u8>> {
-        // Placeholder implementation - would implement proper conversion
-        let size = (frame.width * frame.height * 3 / 2) as usize;
-        Ok(vec![0u8; size])
+        if frame.format == CoreVideoFormat::Yuv420p {
+            return Ok(
+                frame.data[..VideoFormat::YUV420.frame_size(frame.width, frame.height)].to_vec(),
+            );
+        }
+        let rgba = frame_to_rgba(frame)?;
+        Ok(rgba_to_yuv420(&rgba, frame.width, frame.height))
     }
 
     fn convert_to_nv12(frame: &VideoFrame) -> Result<Vec<u8>> {
-        // Placeholder implementation - would implement proper conversion
-        let size = (frame.width * frame.height * 3 / 2) as usize;
-        Ok(vec![0u8; size])
+        let rgba = frame_to_rgba(frame)?;
+        Ok(rgba_to_nv12(&rgba, frame.width, frame.height))
     }
 }
 
@@ -334,6 +556,199 @@ mod tests {
         assert_eq!(VideoFormat::BGRA32.stride(1920), 7680);
     }
 
+    fn gradient_frame(width: u32, height: u32) -> VideoFrame {
+        let data = (0..height)
+            .flat_map(|y| {
+                (0..width).flat_map(move |x| {
+                    [
+                        (x * 4) as u8,
+                        (y * 4) as u8,
+                        (255 - x * 2 - y * 2) as u8,
+                        255,
+                    ]
+                })
+            })
+            .collect();
+
+        VideoFrame {
+            width,
+            height,
+            format: constellation_core::VideoFormat::Rgba8,
+            data,
+        }
+    }
+
+    fn assert_close(actual: &[u8], expected: &[u8], tolerance: i32) {
+        assert_eq!(actual.len(), expected.len());
+        for (i, (a, e)) in actual.iter().zip(expected).enumerate() {
+            assert!(
+                (*a as i32 - *e as i32).abs() <= tolerance,
+                "byte {i}: {a} vs {e}"
+            );
+        }
+    }
+
+    #[test]
+    fn test_yuv420_round_trip() {
+        let frame = gradient_frame(32, 16);
+
+        let yuv = conversion::convert_frame(&frame, VideoFormat::YUV420).unwrap();
+        assert_eq!(yuv.len(), VideoFormat::YUV420.frame_size(32, 16));
+
+        let rgba = conversion::yuv420_to_rgba(&yuv, 32, 16).unwrap();
+        assert_close(&rgba, &frame.data, 6);
+    }
+
+    #[test]
+    fn test_yuv420_odd_dimensions() {
+        let frame = gradient_frame(5, 3);
+
+        let yuv = conversion::convert_frame(&frame, VideoFormat::YUV420).unwrap();
+        // 15 luma samples + 2 * (3 x 2) chroma samples
+        assert_eq!(VideoFormat::YUV420.frame_size(5, 3), 27);
+        assert_eq!(yuv.len(), 27);
+
+        let rgba = conversion::yuv420_to_rgba(&yuv, 5, 3).unwrap();
+        assert_close(&rgba, &frame.data, 6);
+    }
+
+    #[test]
+    fn test_nv12_matches_yuv420_planes() {
+        let frame = gradient_frame(8, 4);
+
+        let yuv = conversion::convert_frame(&frame, VideoFormat::YUV420).unwrap();
+        let nv12 = conversion::convert_frame(&frame, VideoFormat::NV12).unwrap();
+        assert_eq!(nv12.len(), yuv.len());
+
+        let luma_size = 8 * 4;
+        let chroma_size = 4 * 2;
+        assert_eq!(&nv12[..luma_size], &yuv[..luma_size]);
+        for i in 0..chroma_size {
+            assert_eq!(nv12[luma_size + i * 2], yuv[luma_size + i]);
+            assert_eq!(
+                nv12[luma_size + i * 2 + 1],
+                yuv[luma_size + chroma_size + i]
+            );
+        }
+    }
+
+    #[test]
+    fn test_nv12_plane_layout_and_samples() {
+        // Left 2x2 block red, right 2x2 block white
+        let data = (0..2)
+            .flat_map(|_| {
+                [[255u8, 0, 0, 255], [255, 0, 0, 255], [255; 4], [255; 4]]
+                    .into_iter()
+                    .flatten()
+            })
+            .collect();
+        let frame = VideoFrame {
+            width: 4,
+            height: 2,
+            format: constellation_core::VideoFormat::Rgba8,
+            data,
+        };
+
+        let nv12 = conversion::convert_frame(&frame, VideoFormat::NV12).unwrap();
+        assert_eq!(nv12.len(), VideoFormat::NV12.frame_size(4, 2));
+
+        let (y_plane, uv_plane) = conversion::split_nv12(&nv12, 4, 2).unwrap();
+        assert_eq!(y_plane.len(), 8);
+        assert_eq!(uv_plane.len(), 4);
+        assert_eq!(y_plane, &[63, 63, 235, 235, 63, 63, 235, 235]);
+        // Interleaved Cb/Cr: red block then white block
+        assert_eq!(uv_plane, &[102, 240, 128, 128]);
+
+        assert!(conversion::split_nv12(&nv12[..11], 4, 2).is_err());
+    }
+
+    #[test]
+    fn test_uyvy_pairs_share_chroma() {
+        // Red pair, white pair, then an odd red column paired with itself
+        let rgba = [
+            [255u8, 0, 0, 255],
+            [255, 0, 0, 255],
+            [255; 4],
+            [255; 4],
+            [255, 0, 0, 255],
+        ]
+        .concat();
+
+        let uyvy = conversion::rgba_to_uyvy(&rgba, 5, 1);
+        assert_eq!(
+            uyvy,
+            vec![102, 63, 240, 63, 128, 235, 128, 235, 102, 63, 240, 63]
+        );
+    }
+
+    #[test]
+    fn test_nv12_odd_dimension_planes() {
+        let frame = gradient_frame(5, 3);
+        let nv12 = conversion::convert_frame(&frame, VideoFormat::NV12).unwrap();
+
+        let (y_plane, uv_plane) = conversion::split_nv12(&nv12, 5, 3).unwrap();
+        assert_eq!(y_plane.len(), 15);
+        // 3 x 2 chroma samples, two bytes each
+        assert_eq!(uv_plane.len(), 12);
+    }
+
+    #[test]
+    fn test_output_format_prefers_nv12() {
+        let info = PlatformInfo {
+            platform: "Test".to_string(),
+            supports_dynamic_resolution: true,
+            supports_dynamic_fps: true,
+            preferred_formats: vec![VideoFormat::BGRA32, VideoFormat::NV12],
+            max_resolution: (3840, 2160),
+            supported_fps: vec![30],
+        };
+        assert_eq!(info.output_format(), VideoFormat::NV12);
+
+        let info = PlatformInfo {
+            preferred_formats: vec![VideoFormat::YUV420, VideoFormat::RGB24],
+            ..info
+        };
+        assert_eq!(info.output_format(), VideoFormat::YUV420);
+    }
+
+    #[test]
+    fn test_bt709_reference_colors() {
+        let frame = VideoFrame {
+            width: 2,
+            height: 2,
+            format: constellation_core::VideoFormat::Rgba8,
+            data: [255u8, 255, 255, 255].repeat(4),
+        };
+        let yuv = conversion::convert_frame(&frame, VideoFormat::YUV420).unwrap();
+        assert_eq!(yuv, vec![235, 235, 235, 235, 128, 128]);
+
+        let frame = VideoFrame {
+            data: [255u8, 0, 0, 255].repeat(4),
+            ..frame
+        };
+        let yuv = conversion::convert_frame(&frame, VideoFormat::YUV420).unwrap();
+        // BT.709 red: Y=63, Cb=102, Cr=240
+        assert_eq!(yuv, vec![63, 63, 63, 63, 102, 240]);
+    }
+
+    #[test]
+    fn test_packed_conversions() {
+        let frame = VideoFrame {
+            width: 1,
+            height: 1,
+            format: constellation_core::VideoFormat::Rgba8,
+            data: vec![10, 20, 30, 40],
+        };
+        assert_eq!(
+            conversion::convert_frame(&frame, VideoFormat::RGB24).unwrap(),
+            vec![10, 20, 30]
+        );
+        assert_eq!(
+            conversion::convert_frame(&frame, VideoFormat::BGRA32).unwrap(),
+            vec![30, 20, 10, 40]
+        );
+    }
+
     #[test]
     fn test_default_config() {
         let config = VirtualWebcamConfig::default();
@@ -399,8 +814,225 @@ mod tests {
         assert!(webcam.is_ok());
 
         let webcam = webcam.unwrap();
+        assert_eq!(
+            webcam.output_format(),
+            PlatformInfo::current().output_format()
+        );
         assert_eq!(webcam.config().width, 1280);
         assert_eq!(webcam.config().height, 720);
         assert_eq!(webcam.config().fps, 60);
     }
+
+    /// Backend that records lifecycle calls and, like the real backends,
+    /// refuses resolution changes while active
+    #[derive(Default)]
+    struct MockBackend {
+        name: String,
+        width: u32,
+        height: u32,
+        format: Option<VideoFormat>,
+        active: bool,
+        starts: u32,
+        stops: u32,
+        pending_change: Option<ConsumerFormat>,
+    }
+
+    impl MockBackend {
+        /// Act like a consumer renegotiating the device
+        fn simulate_consumer_change(&mut self, change: ConsumerFormat, format: VideoFormat) {
+            self.width = change.width;
+            self.height = change.height;
+            self.format = Some(format);
+            self.pending_change = Some(change);
+        }
+    }
+
+    impl VirtualWebcamBackend for MockBackend {
+        fn new(device_name: String, width: u32, height: u32, _fps: u32) -> Result<Self> {
+            Ok(Self {
+                name: device_name,
+                width,
+                height,
+                ..Default::default()
+            })
+        }
+
+        fn start(&mut self) -> Result<()> {
+            self.active = true;
+            self.starts += 1;
+            Ok(())
+        }
+
+        fn stop(&mut self) -> Result<()> {
+            self.active = false;
+            self.stops += 1;
+            Ok(())
+        }
+
+        fn send_frame(&mut self, _frame: &VideoFrame) -> Result<()> {
+            Ok(())
+        }
+
+        fn is_active(&self) -> bool {
+            self.active
+        }
+
+        fn get_device_name(&self) -> &str {
+            &self.name
+        }
+
+        fn set_resolution(&mut self, width: u32, height: u32) -> Result<()> {
+            if self.active {
+                return Err(anyhow!("Cannot change resolution while active"));
+            }
+            self.width = width;
+            self.height = height;
+            Ok(())
+        }
+
+        fn set_fps(&mut self, _fps: u32) -> Result<()> {
+            Ok(())
+        }
+
+        fn format(&self) -> VideoFormat {
+            self.format.unwrap_or(VideoFormat::RGB24)
+        }
+
+        fn set_format(&mut self, format: VideoFormat) -> Result<()> {
+            self.format = Some(format);
+            Ok(())
+        }
+
+        fn poll_format_change(&mut self) -> Option<ConsumerFormat> {
+            self.pending_change.take()
+        }
+    }
+
+    fn mock_webcam(supports_dynamic_resolution: bool) -> VirtualWebcam<MockBackend> {
+        let platform = PlatformInfo {
+            platform: "Mock".to_string(),
+            supports_dynamic_resolution,
+            supports_dynamic_fps: true,
+            preferred_formats: vec![VideoFormat::BGRA32],
+            max_resolution: (1920, 1080),
+            supported_fps: vec![30],
+        };
+        let config = VirtualWebcamConfig {
+            width: 1280,
+            height: 720,
+            ..Default::default()
+        };
+        let backend = MockBackend::new("Mock".to_string(), 1280, 720, 30).unwrap();
+        VirtualWebcam::with_backend(backend, config, platform).unwrap()
+    }
+
+    #[test]
+    fn test_clamp_resolution_keeps_aspect_ratio() {
+        let webcam = mock_webcam(true);
+        let platform = webcam.platform();
+
+        assert_eq!(platform.clamp_resolution(1280, 720), (1280, 720));
+        assert_eq!(platform.clamp_resolution(3840, 2160), (1920, 1080));
+        assert_eq!(platform.clamp_resolution(4096, 1024), (1920, 480));
+        assert_eq!(platform.clamp_resolution(1080, 1920), (607, 1080));
+    }
+
+    #[test]
+    fn test_set_resolution_clamps_to_platform_maximum() {
+        let mut webcam = mock_webcam(true);
+        assert_eq!(webcam.output_format(), VideoFormat::BGRA32);
+
+        webcam.set_resolution(3840, 2160).unwrap();
+        assert_eq!(
+            (webcam.config().width, webcam.config().height),
+            (1920, 1080)
+        );
+        assert_eq!((webcam.backend.width, webcam.backend.height), (1920, 1080));
+        assert_eq!(webcam.backend.starts, 0);
+
+        assert!(webcam.set_resolution(0, 720).is_err());
+        assert_eq!(webcam.config().width, 1920);
+    }
+
+    #[test]
+    fn test_set_resolution_restarts_without_dynamic_support() {
+        let mut webcam = mock_webcam(false);
+        webcam.start().unwrap();
+
+        webcam.set_resolution(640, 480).unwrap();
+        assert!(webcam.is_active());
+        assert_eq!(webcam.backend.stops, 1);
+        assert_eq!(webcam.backend.starts, 2);
+        assert_eq!((webcam.backend.width, webcam.backend.height), (640, 480));
+        assert_eq!((webcam.config().width, webcam.config().height), (640, 480));
+    }
+
+    #[test]
+    fn test_set_resolution_falls_back_to_restart_when_refused() {
+        let mut webcam = mock_webcam(true);
+        webcam.start().unwrap();
+
+        webcam.set_resolution(2560, 1440).unwrap();
+        assert!(webcam.is_active());
+        assert_eq!(webcam.backend.stops, 1);
+        assert_eq!((webcam.backend.width, webcam.backend.height), (1920, 1080));
+        assert_eq!(webcam.config().height, 1080);
+    }
+
+    #[test]
+    fn test_negotiated_format_reports_backend_format() {
+        let webcam = mock_webcam(true);
+        let negotiated = webcam.negotiated_format();
+
+        assert_eq!((negotiated.width, negotiated.height), (1280, 720));
+        assert_eq!(negotiated.fps, 30);
+        // Platform negotiation overrides the preferred RGB24
+        assert_eq!(webcam.config().format, VideoFormat::RGB24);
+        assert_eq!(negotiated.format, VideoFormat::BGRA32);
+    }
+
+    #[test]
+    fn test_consumer_format_change_fires_callback() {
+        use std::sync::{Arc, Mutex};
+
+        let mut webcam = mock_webcam(true);
+        let seen = Arc::new(Mutex::new(Vec::new()));
+        let recorder = seen.clone();
+        webcam.on_format_change(move |config| recorder.lock().unwrap().push(config.clone()));
+        webcam.start().unwrap();
+
+        let frame = VideoFrame {
+            width: 2,
+            height: 2,
+            format: constellation_core::VideoFormat::Rgba8,
+            data: vec![0; 16],
+        };
+        webcam.send_frame(&frame).unwrap();
+        assert!(seen.lock().unwrap().is_empty());
+
+        webcam.backend.simulate_consumer_change(
+            ConsumerFormat {
+                width: 640,
+                height: 480,
+                fps: 15,
+            },
+            VideoFormat::NV12,
+        );
+        webcam.send_frame(&frame).unwrap();
+
+        let expected = VirtualWebcamConfig {
+            device_name: "Constellation Studio".to_string(),
+            width: 640,
+            height: 480,
+            fps: 15,
+            format: VideoFormat::NV12,
+        };
+        assert_eq!(*seen.lock().unwrap(), vec![expected.clone()]);
+        assert_eq!(webcam.negotiated_format(), expected);
+
+        // Reported once; later frames don't re-fire the callback
+        webcam.send_frame(&frame).unwrap();
+        assert!(webcam.poll_format_change().is_none());
+        assert_eq!(seen.lock().unwrap().len(), 1);
+    }
 }
@@ -16,7 +16,7 @@
  * along with this program. If not, see <https://www.gnu.org/licenses/>.
  */
 
-use super::{VideoFormat, VirtualWebcamBackend};
+use super::{conversion, VideoFormat, VirtualWebcamBackend};
 use anyhow::{anyhow, Result};
 use constellation_core::VideoFrame;
 use std::sync::atomic::{AtomicBool, Ordering};
@@ -133,6 +133,19 @@ impl VirtualWebcamBackend for WindowsVirtualWebcam {
         self.fps = fps;
         Ok(())
     }
+
+    fn format(&self) -> VideoFormat {
+        self.format
+    }
+
+    fn set_format(&mut self, format: VideoFormat) -> Result<()> {
+        if self.is_active.load(Ordering::Relaxed) {
+            return Err(anyhow!("Cannot change format while active"));
+        }
+
+        self.format = format;
+        Ok(())
+    }
 }
 
 impl WindowsVirtualWebcam {
@@ -218,10 +231,15 @@ impl WindowsVirtualWebcam {
         // 2. Set appropriate timestamps
         // 3. Deliver sample through source filter output pin
 
+        // Convert to the negotiated output format; size is validated against frame_size
+        let sample = conversion::convert_frame(frame, self.format)?;
+
         tracing::trace!(
-            "Delivering frame to DirectShow ({}x{})",
+            "Delivering frame to DirectShow ({}x{} {:?}, {} bytes)",
             frame.width,
-            frame.height
+            frame.height,
+            self.format,
+            sample.len()
         );
 
         // Placeholder implementation
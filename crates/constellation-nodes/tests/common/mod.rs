@@ -0,0 +1,59 @@
+/*
+ * Constellation Studio - Professional Real-time Video Processing
+ * Copyright (c) 2025 MACHIKO LAB
+ *
+ * This program is free software: you can redistribute it and/or modify
+ * it under the terms of the GNU Affero General Public License as published by
+ * the Free Software Foundation, either version 3 of the License, or
+ * (at your option) any later version.
+ *
+ * This program is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+ * GNU Affero General Public License for more details.
+ *
+ * You should have received a copy of the GNU Affero General Public License
+ * along with this program. If not, see <https://www.gnu.org/licenses/>.
+ */
+
+//! Fixtures shared by the node integration tests
+
+// Each test binary uses only some of these helpers
+#![allow(dead_code)]
+
+use constellation_core::{FrameData, NodeConfig, RenderData, VideoFormat, VideoFrame};
+use serde_json::Value;
+
+/// Node config holding the given parameters
+pub fn node_config(parameters: &[(&str, Value)]) -> NodeConfig {
+    NodeConfig {
+        parameters: parameters
+            .iter()
+            .map(|(key, value)| (key.to_string(), value.clone()))
+            .collect(),
+    }
+}
+
+/// RGBA8 frame filled with one color
+pub fn solid_frame(width: u32, height: u32, color: [u8; 4]) -> VideoFrame {
+    VideoFrame {
+        width,
+        height,
+        format: VideoFormat::Rgba8,
+        data: color.repeat((width * height) as usize),
+    }
+}
+
+/// The Raster2D frame a node produced
+pub fn raster(frame: FrameData) -> VideoFrame {
+    match frame.render_data {
+        Some(RenderData::Raster2D(frame)) => frame,
+        _ => panic!("Expected Raster2D output"),
+    }
+}
+
+/// RGBA8 pixel at (x, y)
+pub fn pixel_at(frame: &VideoFrame, x: u32, y: u32) -> [u8; 4] {
+    let offset = ((y * frame.width + x) * 4) as usize;
+    frame.data[offset..offset + 4].try_into().unwrap()
+}
@@ -0,0 +1,236 @@
+/*
+ * Constellation Studio - Professional Real-time Video Processing
+ * Copyright (c) 2025 MACHIKO LAB
+ *
+ * This program is free software: you can redistribute it and/or modify
+ * it under the terms of the GNU Affero General Public License as published by
+ * the Free Software Foundation, either version 3 of the License, or
+ * (at your option) any later version.
+ *
+ * This program is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+ * GNU Affero General Public License for more details.
+ *
+ * You should have received a copy of the GNU Affero General Public License
+ * along with this program. If not, see <https://www.gnu.org/licenses/>.
+ */
+
+mod common;
+
+use common::node_config;
+use constellation_core::*;
+use constellation_nodes::{AudioInputNode, AudioOutputNode, NodeProcessor, SampleRing};
+use serde_json::Value;
+use std::time::Duration;
+use uuid::Uuid;
+
+fn audio_frame(sample_rate: u32, channels: u16, samples: Vec<f32>) -> FrameData {
+    FrameData {
+        render_data: None,
+        audio_data: Some(UnifiedAudioData::Stereo {
+            sample_rate,
+            channels,
+            samples,
+        }),
+        control_data: None,
+        tally_metadata: TallyMetadata::new(),
+        timestamp: Duration::ZERO,
+        sequence: 0,
+        checksum: None,
+    }
+}
+
+/// Interleaved stereo 440 Hz tone starting at frame `offset`
+fn tone(sample_rate: u32, frames: usize, offset: usize) -> Vec<f32> {
+    (offset..offset + frames)
+        .flat_map(|frame| {
+            let value =
+                (frame as f32 * 440.0 * std::f32::consts::TAU / sample_rate as f32).sin() * 0.1;
+            [value, value]
+        })
+        .collect()
+}
+
+fn create_output(parameters: &[(&str, Value)]) -> AudioOutputNode {
+    AudioOutputNode::new(Uuid::new_v4(), node_config(parameters)).unwrap()
+}
+
+fn create_input(parameters: &[(&str, Value)]) -> AudioInputNode {
+    AudioInputNode::new(Uuid::new_v4(), node_config(parameters)).unwrap()
+}
+
+#[test]
+fn test_ring_waits_for_prefill_before_playing() {
+    let ring = SampleRing::new(8, 4);
+    let mut out = [1.0; 2];
+
+    ring.push(&[0.1, 0.2]);
+    assert_eq!(ring.pop_into(&mut out), 0);
+    assert_eq!(out, [0.0, 0.0]);
+    assert_eq!(ring.underruns(), 0);
+
+    ring.push(&[0.3, 0.4]);
+    assert_eq!(ring.pop_into(&mut out), 2);
+    assert_eq!(out, [0.1, 0.2]);
+    assert_eq!(ring.len(), 2);
+}
+
+#[test]
+fn test_ring_counts_underrun_and_refills() {
+    let ring = SampleRing::new(8, 2);
+    ring.push(&[0.1, 0.2, 0.3]);
+
+    let mut out = [1.0; 4];
+    assert_eq!(ring.pop_into(&mut out), 3);
+    assert_eq!(out, [0.1, 0.2, 0.3, 0.0]);
+    assert_eq!(ring.underruns(), 1);
+
+    // After running dry playback waits for the prefill again
+    ring.push(&[0.5]);
+    assert_eq!(ring.pop_into(&mut out[..1]), 0);
+    assert_eq!(ring.underruns(), 1);
+}
+
+#[test]
+fn test_ring_drops_oldest_when_full() {
+    let ring = SampleRing::new(4, 0);
+    assert_eq!(ring.push(&[1.0, 2.0, 3.0]), 0);
+    assert_eq!(ring.push(&[4.0, 5.0, 6.0]), 2);
+    assert_eq!(ring.overruns(), 1);
+    assert_eq!(ring.drain(usize::MAX), vec![3.0, 4.0, 5.0, 6.0]);
+    assert!(ring.is_empty());
+}
+
+#[test]
+fn test_output_exposes_device_selection() {
+    let node = create_output(&[]);
+    let properties = node.get_properties();
+    assert!(properties.parameters.contains_key("device_id"));
+    assert!(properties.parameters.contains_key("buffer_ms"));
+    assert_eq!(properties.input_types, vec![ConnectionType::Audio]);
+}
+
+#[test]
+fn test_output_without_device_passes_audio_through() {
+    let mut node = create_output(&[("device_id", Value::from("no-such-device"))]);
+
+    let samples = tone(48000, 480, 0);
+    let output = node
+        .process(audio_frame(48000, 2, samples.clone()))
+        .unwrap();
+    assert!(!node.is_playing());
+    match output.audio_data {
+        Some(UnifiedAudioData::Stereo {
+            samples: passed, ..
+        }) => assert_eq!(passed, samples),
+        _ => panic!("expected stereo audio"),
+    }
+}
+
+#[cfg(feature = "audio-device")]
+#[test]
+fn test_output_plays_to_default_device_without_underrun() {
+    const RATE: u32 = 48000;
+    // 10 ms blocks, sent slightly faster than real time so the buffer never drains
+    const BLOCK: usize = 480;
+    const SEND_EVERY: Duration = Duration::from_millis(9);
+
+    let mut node = create_output(&[("buffer_ms", Value::from(200))]);
+    node.process(audio_frame(RATE, 2, tone(RATE, BLOCK, 0)))
+        .unwrap();
+    if !node.is_playing() {
+        eprintln!("Skipping: no audio output device available");
+        return;
+    }
+
+    let config = node.device_config().unwrap();
+    assert!(config.sample_rate > 0);
+    assert!(config.channels > 0);
+    assert!(node.queued_samples() > 0);
+
+    for block in 1..50 {
+        std::thread::sleep(SEND_EVERY);
+        node.process(audio_frame(RATE, 2, tone(RATE, BLOCK, block * BLOCK)))
+            .unwrap();
+    }
+
+    assert!(node.is_playing());
+    assert!(node.queued_samples() > 0);
+    assert_eq!(node.underruns(), 0);
+    node.on_stop().unwrap();
+    assert!(!node.is_playing());
+}
+
+#[test]
+fn test_input_without_device_yields_configured_silence() {
+    let mut node = create_input(&[
+        ("device_id", Value::from("no-such-device")),
+        ("channels", Value::from(1)),
+        ("sample_rate", Value::from(44100)),
+    ]);
+
+    let output = node.process(FrameData::empty()).unwrap();
+    assert!(!node.is_capturing());
+    match output.audio_data {
+        Some(UnifiedAudioData::Stereo {
+            sample_rate,
+            channels,
+            samples,
+        }) => {
+            assert_eq!(sample_rate, 44100);
+            assert_eq!(channels, 1);
+            assert!(!samples.is_empty());
+            assert!(samples.iter().all(|&sample| sample == 0.0));
+        }
+        _ => panic!("expected stereo audio"),
+    }
+}
+
+#[test]
+fn test_input_clamps_channel_parameter() {
+    let node = create_input(&[("channels", Value::from(32))]);
+    assert_eq!(node.channels(), 8);
+    let properties = node.get_properties();
+    assert!(properties.parameters.contains_key("device_id"));
+    assert!(properties.parameters.contains_key("channels"));
+    assert_eq!(properties.output_types, vec![ConnectionType::Audio]);
+}
+
+#[cfg(feature = "audio-device")]
+#[test]
+fn test_input_captures_from_default_device() {
+    let mut node = create_input(&[("channels", Value::from(1))]);
+    node.process(FrameData::empty()).unwrap();
+    if !node.is_capturing() {
+        eprintln!("Skipping: no audio input device available");
+        return;
+    }
+
+    let config = node.device_config().unwrap();
+    assert!(config.sample_rate > 0);
+    assert!(config.channels > 0);
+
+    let mut captured = 0;
+    for _ in 0..30 {
+        std::thread::sleep(Duration::from_millis(10));
+        let output = node.process(FrameData::empty()).unwrap();
+        match output.audio_data {
+            Some(UnifiedAudioData::Stereo {
+                sample_rate,
+                channels,
+                samples,
+            }) => {
+                assert_eq!(sample_rate, 48000);
+                assert_eq!(channels, 1);
+                captured += samples.len();
+            }
+            _ => panic!("expected stereo audio"),
+        }
+    }
+
+    assert!(node.is_capturing());
+    assert!(captured > 0);
+    node.on_stop().unwrap();
+    assert!(!node.is_capturing());
+}
@@ -0,0 +1,176 @@
+/*
+ * Constellation Studio - Professional Real-time Video Processing
+ * Copyright (c) 2025 MACHIKO LAB
+ *
+ * This program is free software: you can redistribute it and/or modify
+ * it under the terms of the GNU Affero General Public License as published by
+ * the Free Software Foundation, either version 3 of the License, or
+ * (at your option) any later version.
+ *
+ * This program is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+ * GNU Affero General Public License for more details.
+ *
+ * You should have received a copy of the GNU Affero General Public License
+ * along with this program. If not, see <https://www.gnu.org/licenses/>.
+ */
+
+use constellation_core::*;
+use constellation_nodes::{AudioEffectNode, NodeConfig, NodeProcessor};
+use serde_json::Value;
+use std::collections::HashMap;
+use std::time::Duration;
+use uuid::Uuid;
+
+const SAMPLE_RATE: u32 = 48000;
+
+fn create_compressor(parameters: &[(&str, Value)]) -> AudioEffectNode {
+    let parameters = parameters
+        .iter()
+        .map(|(key, value)| (key.to_string(), value.clone()))
+        .collect::<HashMap<_, _>>();
+    AudioEffectNode::new(Uuid::new_v4(), NodeConfig { parameters }).unwrap()
+}
+
+/// Run one block of interleaved stereo samples through the node
+fn process_stereo(node: &mut AudioEffectNode, samples: Vec<f32>) -> Vec<f32> {
+    let input = FrameData {
+        render_data: None,
+        audio_data: Some(UnifiedAudioData::Stereo {
+            sample_rate: SAMPLE_RATE,
+            channels: 2,
+            samples,
+        }),
+        control_data: None,
+        tally_metadata: TallyMetadata::new(),
+        timestamp: Duration::ZERO,
+        sequence: 0,
+        checksum: None,
+    };
+
+    match node.process(input).unwrap().audio_data {
+        Some(UnifiedAudioData::Stereo { samples, .. }) => samples,
+        _ => panic!("Expected stereo audio data"),
+    }
+}
+
+fn stereo_block(left: f32, right: f32, frames: usize) -> Vec<f32> {
+    (0..frames).flat_map(|_| [left, right]).collect()
+}
+
+fn to_db(level: f32) -> f32 {
+    20.0 * level.abs().log10()
+}
+
+#[test]
+fn test_gain_reduction_matches_ratio() {
+    let mut node = create_compressor(&[
+        ("threshold", Value::from(-18.0)),
+        ("ratio", Value::from(4.0)),
+        ("attack", Value::from(1.0)),
+        ("release", Value::from(50.0)),
+    ]);
+
+    // A steady -6 dBFS signal sits 12 dB over the threshold
+    let input_level = 0.5;
+    let output = process_stereo(&mut node, stereo_block(input_level, -input_level, 4800));
+
+    let over_db = to_db(input_level) + 18.0;
+    let expected_reduction = over_db * (1.0 - 1.0 / 4.0);
+    for sample in &output[output.len() - 2..] {
+        let reduction = to_db(input_level) - to_db(*sample);
+        assert!(
+            (reduction - expected_reduction).abs() < 0.1,
+            "expected {expected_reduction} dB of gain reduction, got {reduction}"
+        );
+    }
+}
+
+#[test]
+fn test_channels_follow_their_own_envelope() {
+    let mut node = create_compressor(&[
+        ("threshold", Value::from(-18.0)),
+        ("ratio", Value::from(2.0)),
+        ("attack", Value::from(1.0)),
+    ]);
+
+    // Only the loud left channel should be compressed
+    let output = process_stereo(&mut node, stereo_block(0.5, 0.05, 4800));
+    let left = output[output.len() - 2];
+    let right = output[output.len() - 1];
+
+    let expected_left_db = -18.0 + (to_db(0.5) + 18.0) / 2.0;
+    assert!((to_db(left) - expected_left_db).abs() < 0.1);
+    assert!((right - 0.05).abs() < 1e-6);
+}
+
+#[test]
+fn test_signal_below_threshold_passes_through() {
+    let mut node = create_compressor(&[
+        ("threshold", Value::from(-6.0)),
+        ("ratio", Value::from(8.0)),
+    ]);
+
+    let input = stereo_block(0.25, -0.25, 1024);
+    let output = process_stereo(&mut node, input.clone());
+    for (out, inp) in output.iter().zip(&input) {
+        assert!((out - inp).abs() < 1e-6);
+    }
+}
+
+#[test]
+fn test_limiter_keeps_output_below_full_scale() {
+    let mut node = create_compressor(&[
+        ("threshold", Value::from(0.0)),
+        ("ratio", Value::from(1.0)),
+        ("makeup_gain", Value::from(12.0)),
+        ("limiter", Value::Bool(true)),
+    ]);
+
+    // Hot square wave that would clip badly after the makeup gain
+    let samples = (0..4800)
+        .flat_map(|i| {
+            let level = if (i / 24) % 2 == 0 { 1.5 } else { -1.5 };
+            [level, level * 0.5]
+        })
+        .collect();
+    let output = process_stereo(&mut node, samples);
+
+    let peak = output.iter().fold(0.0f32, |peak, s| peak.max(s.abs()));
+    assert!(peak < 1.0, "limiter let a peak of {peak} through");
+    assert!(peak > 0.9);
+}
+
+#[test]
+fn test_limiter_disabled_allows_clipping() {
+    let mut node = create_compressor(&[
+        ("threshold", Value::from(0.0)),
+        ("ratio", Value::from(1.0)),
+        ("makeup_gain", Value::from(12.0)),
+    ]);
+
+    let output = process_stereo(&mut node, stereo_block(0.5, 0.5, 256));
+    assert!(output.iter().any(|s| s.abs() >= 1.0));
+}
+
+#[test]
+fn test_block_size_emits_fixed_blocks_and_drops_tail_on_stop() {
+    // Unity settings so the output samples are the input samples
+    let mut node = create_compressor(&[
+        ("threshold", Value::from(0.0)),
+        ("block_size", Value::from(64)),
+    ]);
+
+    let input: Vec<f32> = (0..200).map(|i| i as f32 / 1000.0).collect();
+    let mut output = process_stereo(&mut node, input[..100].to_vec());
+    assert_eq!(output.len(), 0);
+    output.extend(process_stereo(&mut node, input[100..].to_vec()));
+    // 100 frames arrived: one 64-frame block out, 36 frames held back
+    assert_eq!(output.len(), 128);
+    assert_eq!(output, input[..128]);
+
+    node.on_stop().unwrap();
+    let restarted = process_stereo(&mut node, stereo_block(0.1, 0.1, 64));
+    assert_eq!(restarted, stereo_block(0.1, 0.1, 64));
+}
@@ -0,0 +1,261 @@
+/*
+ * Constellation Studio - Professional Real-time Video Processing
+ * Copyright (c) 2025 MACHIKO LAB
+ *
+ * This program is free software: you can redistribute it and/or modify
+ * it under the terms of the GNU Affero General Public License as published by
+ * the Free Software Foundation, either version 3 of the License, or
+ * (at your option) any later version.
+ *
+ * This program is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+ * GNU Affero General Public License for more details.
+ *
+ * You should have received a copy of the GNU Affero General Public License
+ * along with this program. If not, see <https://www.gnu.org/licenses/>.
+ */
+
+mod common;
+
+use common::node_config;
+use constellation_core::*;
+use constellation_nodes::audio_file::read_wav;
+use constellation_nodes::{AudioFileInputNode, AudioFileOutputNode, NodeProcessor};
+use serde_json::Value;
+use std::path::{Path, PathBuf};
+use std::time::Duration;
+use uuid::Uuid;
+
+fn temp_wav(name: &str) -> PathBuf {
+    std::env::temp_dir().join(format!("constellation-{}-{}.wav", name, Uuid::new_v4()))
+}
+
+fn audio_frame(sample_rate: u32, channels: u16, samples: Vec<f32>) -> FrameData {
+    FrameData {
+        audio_data: Some(UnifiedAudioData::Stereo {
+            sample_rate,
+            channels,
+            samples,
+        }),
+        ..FrameData::empty()
+    }
+}
+
+fn block_samples(frame: &FrameData) -> Option<&[f32]> {
+    match &frame.audio_data {
+        Some(UnifiedAudioData::Stereo { samples, .. }) => Some(samples),
+        _ => None,
+    }
+}
+
+/// Stereo ramp with distinct left and right values
+fn ramp(frames: usize) -> Vec<f32> {
+    (0..frames)
+        .flat_map(|i| {
+            let value = i as f32 / frames as f32 - 0.5;
+            [value, -value]
+        })
+        .collect()
+}
+
+/// Record `blocks` through an output node, then read them back with an input node
+fn record(path: &Path, sample_format: &str, blocks: &[Vec<f32>]) {
+    let mut output = AudioFileOutputNode::new(
+        Uuid::new_v4(),
+        node_config(&[
+            (
+                "file_path",
+                Value::from(path.to_string_lossy().into_owned()),
+            ),
+            ("sample_format", Value::from(sample_format)),
+        ]),
+    )
+    .unwrap();
+    for block in blocks {
+        let passed = output
+            .process(audio_frame(48000, 2, block.clone()))
+            .unwrap();
+        assert_eq!(block_samples(&passed), Some(block.as_slice()));
+    }
+    output.on_stop().unwrap();
+}
+
+fn input_node(path: &Path, parameters: &[(&str, Value)]) -> AudioFileInputNode {
+    let mut parameters = parameters.to_vec();
+    parameters.push((
+        "file_path",
+        Value::from(path.to_string_lossy().into_owned()),
+    ));
+    AudioFileInputNode::new(Uuid::new_v4(), node_config(&parameters)).unwrap()
+}
+
+#[test]
+fn test_float_recording_round_trips_exactly() {
+    let path = temp_wav("float");
+    let samples = ramp(1000);
+    record(
+        &path,
+        "Float 32",
+        &[samples[..800].to_vec(), samples[800..].to_vec()],
+    );
+
+    let file = read_wav(&path).unwrap();
+    assert_eq!((file.sample_rate, file.channels), (48000, 2));
+    assert_eq!(file.samples, samples);
+
+    let mut input = input_node(&path, &[("block_size", Value::from(600))]);
+    input.on_start().unwrap();
+    let first = input.process(FrameData::empty()).unwrap();
+    let second = input.process(FrameData::empty()).unwrap();
+    let end = input.process(FrameData::empty()).unwrap();
+
+    let mut played = block_samples(&first).unwrap().to_vec();
+    assert_eq!(played.len(), 1200);
+    // The last block is partial when not looping
+    played.extend_from_slice(block_samples(&second).unwrap());
+    assert_eq!(played, samples);
+    // Source sequences count from 1
+    assert_eq!((first.sequence, second.sequence), (1, 2));
+    assert_eq!(second.timestamp, Duration::from_secs_f64(600.0 / 48000.0));
+    assert!(end.audio_data.is_none());
+
+    std::fs::remove_file(&path).unwrap();
+}
+
+#[test]
+fn test_pcm16_recording_round_trips_within_quantization() {
+    let path = temp_wav("pcm16");
+    let samples = ramp(512);
+    record(&path, "PCM 16", std::slice::from_ref(&samples));
+
+    let file = read_wav(&path).unwrap();
+    assert_eq!(file.samples.len(), samples.len());
+    for (read, written) in file.samples.iter().zip(&samples) {
+        assert!(
+            (read - written).abs() < 1.0 / 16384.0,
+            "{read} != {written}"
+        );
+    }
+
+    std::fs::remove_file(&path).unwrap();
+}
+
+#[test]
+fn test_format_change_is_resampled_without_seams() {
+    let path = temp_wav("resampled");
+    let mut output = AudioFileOutputNode::new(
+        Uuid::new_v4(),
+        node_config(&[(
+            "file_path",
+            Value::from(path.to_string_lossy().into_owned()),
+        )]),
+    )
+    .unwrap();
+
+    // The first block fixes the file at 48kHz; a 44.1kHz ramp follows in small blocks
+    output.process(audio_frame(48000, 2, vec![0.0; 8])).unwrap();
+    let ramp_44k = ramp(441);
+    for block in ramp_44k.chunks(63 * 2) {
+        output
+            .process(audio_frame(44100, 2, block.to_vec()))
+            .unwrap();
+    }
+    output.on_stop().unwrap();
+
+    let file = read_wav(&path).unwrap();
+    let left: Vec<f32> = file.samples[8..].iter().step_by(2).copied().collect();
+    // 10ms at 48kHz, less the final frame still waiting for more input
+    assert!(
+        (478..=480).contains(&left.len()),
+        "got {} frames",
+        left.len()
+    );
+
+    // Every step of the ramp is the same size, including across block edges
+    let step = 1.0 / 441.0 * 44100.0 / 48000.0;
+    for pair in left.windows(2) {
+        let delta = pair[1] - pair[0];
+        assert!((delta - step).abs() < step * 0.01, "step {delta} != {step}");
+    }
+
+    std::fs::remove_file(&path).unwrap();
+}
+
+#[test]
+fn test_looping_input_wraps_to_the_start() {
+    let path = temp_wav("loop");
+    let samples = ramp(100);
+    record(&path, "Float 32", std::slice::from_ref(&samples));
+
+    let mut input = input_node(
+        &path,
+        &[("loop", Value::Bool(true)), ("block_size", Value::from(64))],
+    );
+    let mut played = Vec::new();
+    for _ in 0..4 {
+        let block = input.process(FrameData::empty()).unwrap();
+        let block = block_samples(&block).unwrap();
+        assert_eq!(block.len(), 128);
+        played.extend_from_slice(block);
+    }
+
+    let expected = samples
+        .iter()
+        .cycle()
+        .take(played.len())
+        .copied()
+        .collect::<Vec<_>>();
+    assert_eq!(played, expected);
+
+    std::fs::remove_file(&path).unwrap();
+}
+
+#[test]
+fn test_input_converts_to_pipeline_rate_and_stereo() {
+    let path = temp_wav("mono24k");
+    let spec = hound::WavSpec {
+        channels: 1,
+        sample_rate: 24000,
+        bits_per_sample: 16,
+        sample_format: hound::SampleFormat::Int,
+    };
+    let mut writer = hound::WavWriter::create(&path, spec).unwrap();
+    for _ in 0..480 {
+        writer.write_sample(i16::MAX / 2).unwrap();
+    }
+    writer.finalize().unwrap();
+
+    let mut input = input_node(
+        &path,
+        &[
+            ("sample_rate", Value::from(48000)),
+            ("block_size", Value::from(4096)),
+        ],
+    );
+    let output = input.process(FrameData::empty()).unwrap();
+    match output.audio_data {
+        Some(UnifiedAudioData::Stereo {
+            sample_rate,
+            channels,
+            samples,
+        }) => {
+            assert_eq!((sample_rate, channels), (48000, 2));
+            // 480 frames at 24 kHz become 960 stereo frames at 48 kHz
+            assert_eq!(samples.len(), 960 * 2);
+            for sample in samples {
+                assert!((sample - 0.5).abs() < 1e-3, "{sample}");
+            }
+        }
+        _ => panic!("Expected stereo audio"),
+    }
+
+    std::fs::remove_file(&path).unwrap();
+}
+
+#[test]
+fn test_missing_file_is_an_error() {
+    let mut input = input_node(&temp_wav("missing"), &[]);
+    assert!(input.on_start().is_err());
+    assert!(input.process(FrameData::empty()).is_err());
+}
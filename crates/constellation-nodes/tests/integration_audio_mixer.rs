@@ -0,0 +1,275 @@
+/*
+ * Constellation Studio - Professional Real-time Video Processing
+ * Copyright (c) 2025 MACHIKO LAB
+ *
+ * This program is free software: you can redistribute it and/or modify
+ * it under the terms of the GNU Affero General Public License as published by
+ * the Free Software Foundation, either version 3 of the License, or
+ * (at your option) any later version.
+ *
+ * This program is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+ * GNU Affero General Public License for more details.
+ *
+ * You should have received a copy of the GNU Affero General Public License
+ * along with this program. If not, see <https://www.gnu.org/licenses/>.
+ */
+
+use constellation_core::*;
+use constellation_nodes::{AudioMixerNode, NodeConfig, NodeProcessor};
+use serde_json::Value;
+use std::collections::HashMap;
+use std::time::Duration;
+use uuid::Uuid;
+
+const SAMPLE_RATE: u32 = 48000;
+const BLOCK_FRAMES: usize = 4800; // 100 ms
+
+fn create_ducking_mixer(parameters: &[(&str, Value)]) -> AudioMixerNode {
+    let mut parameters = parameters
+        .iter()
+        .map(|(key, value)| (key.to_string(), value.clone()))
+        .collect::<HashMap<_, _>>();
+    parameters.insert("ducking".to_string(), Value::Bool(true));
+    AudioMixerNode::new(Uuid::new_v4(), NodeConfig { parameters }).unwrap()
+}
+
+fn stereo_frame(samples: Vec<f32>) -> FrameData {
+    FrameData {
+        render_data: None,
+        audio_data: Some(UnifiedAudioData::Stereo {
+            sample_rate: SAMPLE_RATE,
+            channels: 2,
+            samples,
+        }),
+        control_data: None,
+        tally_metadata: TallyMetadata::new(),
+        timestamp: Duration::ZERO,
+        sequence: 0,
+        checksum: None,
+    }
+}
+
+/// Mix one block of a constant-level voice (input 0) over constant-level music (input 1)
+fn mix_block(mixer: &mut AudioMixerNode, voice: f32, music: f32) -> Vec<f32> {
+    let inputs = vec![
+        (Uuid::new_v4(), stereo_frame(vec![voice; BLOCK_FRAMES * 2])),
+        (Uuid::new_v4(), stereo_frame(vec![music; BLOCK_FRAMES * 2])),
+    ];
+
+    match mixer.process_multi(inputs).unwrap().audio_data {
+        Some(UnifiedAudioData::Stereo { samples, .. }) => samples,
+        _ => panic!("Expected stereo audio data"),
+    }
+}
+
+#[test]
+fn test_loud_sidechain_ducks_music_then_recovers() {
+    let mut mixer = create_ducking_mixer(&[
+        ("duck_threshold", Value::from(-30.0)),
+        ("duck_ratio", Value::from(4.0)),
+        ("duck_attack", Value::from(5.0)),
+        ("duck_release", Value::from(100.0)),
+    ]);
+    let music = 0.25;
+
+    // Quiet voice: music passes at full level
+    let output = mix_block(&mut mixer, 0.0, music);
+    assert!((output[output.len() - 1] - music).abs() < 1e-6);
+    assert!((mixer.ducking_gain() - 1.0).abs() < 1e-6);
+
+    // A -6 dBFS voice sits 24 dB over the threshold: 18 dB of reduction at 4:1
+    let voice = 0.5;
+    let output = mix_block(&mut mixer, voice, music);
+    let expected_gain = 10f32.powf(-18.0 / 20.0);
+    assert!(
+        (mixer.ducking_gain() - expected_gain).abs() < 0.01,
+        "gain {} should settle near {}",
+        mixer.ducking_gain(),
+        expected_gain
+    );
+    let ducked_music = output[output.len() - 1] - voice;
+    assert!(ducked_music < music * 0.2);
+
+    // Once the voice stops the music recovers through the release
+    let mut gains = Vec::new();
+    for _ in 0..10 {
+        mix_block(&mut mixer, 0.0, music);
+        gains.push(mixer.ducking_gain());
+    }
+    assert!(gains.windows(2).all(|pair| pair[1] >= pair[0]));
+    assert!(gains[0] < 1.0);
+    assert!((gains[gains.len() - 1] - 1.0).abs() < 1e-6);
+}
+
+#[test]
+fn test_attack_engages_gradually() {
+    let mut mixer = create_ducking_mixer(&[("duck_attack", Value::from(200.0))]);
+
+    // After 100 ms of a 200 ms attack the envelope is still rising
+    mix_block(&mut mixer, 0.5, 0.25);
+    let partial = mixer.ducking_gain();
+    mix_block(&mut mixer, 0.5, 0.25);
+    assert!(mixer.ducking_gain() < partial);
+    assert!(partial < 1.0);
+}
+
+#[test]
+fn test_sidechain_below_threshold_leaves_music_untouched() {
+    let mut mixer = create_ducking_mixer(&[("duck_threshold", Value::from(-20.0))]);
+
+    // -26 dBFS voice stays under a -20 dBFS threshold
+    let output = mix_block(&mut mixer, 0.05, 0.25);
+    assert!((output[output.len() - 1] - 0.3).abs() < 1e-6);
+    assert!((mixer.ducking_gain() - 1.0).abs() < 1e-6);
+}
+
+#[test]
+fn test_target_channel_selects_ducked_input() {
+    // Swap roles: input 1 drives the sidechain and input 0 is ducked
+    let mut mixer = create_ducking_mixer(&[
+        ("sidechain_channel", Value::from(1)),
+        ("target_channel", Value::from(0)),
+        ("duck_attack", Value::from(1.0)),
+    ]);
+
+    let output = mix_block(&mut mixer, 0.25, 0.5);
+    let ducked = output[output.len() - 1] - 0.5;
+    assert!(ducked > 0.0 && ducked < 0.25 * 0.2);
+}
+
+#[test]
+fn test_ducking_disabled_sums_inputs() {
+    let mut mixer = AudioMixerNode::new(
+        Uuid::new_v4(),
+        NodeConfig {
+            parameters: HashMap::new(),
+        },
+    )
+    .unwrap();
+
+    let output = mix_block(&mut mixer, 0.5, 0.25);
+    assert!(output.iter().all(|sample| (sample - 0.75).abs() < 1e-6));
+    assert_eq!(mixer.ducking_gain(), 1.0);
+}
+
+#[test]
+fn test_mono_input_is_upmixed_to_the_mix_format() {
+    let mut mixer = create_mixer(&[]);
+    let mono = FrameData {
+        audio_data: Some(UnifiedAudioData::Stereo {
+            sample_rate: SAMPLE_RATE,
+            channels: 1,
+            samples: vec![0.25; BLOCK_FRAMES],
+        }),
+        ..FrameData::empty()
+    };
+    let inputs = vec![
+        (
+            Uuid::new_v4(),
+            stereo_frame([0.5, 0.0].repeat(BLOCK_FRAMES)),
+        ),
+        (Uuid::new_v4(), mono),
+    ];
+
+    let samples = match mixer.process_multi(inputs).unwrap().audio_data {
+        Some(UnifiedAudioData::Stereo {
+            channels, samples, ..
+        }) => {
+            assert_eq!(channels, 2);
+            samples
+        }
+        _ => panic!("Expected stereo audio data"),
+    };
+    assert_eq!(samples.len(), BLOCK_FRAMES * 2);
+    assert!((samples[0] - 0.75).abs() < 1e-6);
+    assert!((samples[1] - 0.25).abs() < 1e-6);
+}
+
+fn create_mixer(parameters: &[(&str, Value)]) -> AudioMixerNode {
+    let parameters = parameters
+        .iter()
+        .map(|(key, value)| (key.to_string(), value.clone()))
+        .collect::<HashMap<_, _>>();
+    AudioMixerNode::new(Uuid::new_v4(), NodeConfig { parameters }).unwrap()
+}
+
+/// Mix a single stereo block through the mixer
+fn mix_single(mixer: &mut AudioMixerNode, samples: Vec<f32>) -> Vec<f32> {
+    match mixer
+        .process_multi(vec![(Uuid::new_v4(), stereo_frame(samples))])
+        .unwrap()
+        .audio_data
+    {
+        Some(UnifiedAudioData::Stereo { samples, .. }) => samples,
+        _ => panic!("Expected stereo audio data"),
+    }
+}
+
+#[test]
+fn test_hard_left_pan_silences_right_channel() {
+    let mut mixer = create_mixer(&[("input_0_pan", Value::from(-1.0))]);
+
+    let output = mix_single(&mut mixer, vec![0.5; 512]);
+    for frame in output.chunks(2) {
+        assert!(frame[0] > 0.5);
+        assert!(frame[1].abs() < 1e-6);
+    }
+}
+
+#[test]
+fn test_equal_power_pan_keeps_center_at_unity() {
+    let mut mixer = create_mixer(&[]);
+    let output = mix_single(&mut mixer, vec![0.5; 512]);
+    assert!(output.iter().all(|sample| (sample - 0.5).abs() < 1e-6));
+
+    // Left and right power add up to the same total anywhere in the field
+    for pan in [-0.5, 0.25, 0.8] {
+        let mut mixer = create_mixer(&[("input_0_pan", Value::from(pan))]);
+        let output = mix_single(&mut mixer, vec![0.5; 2]);
+        let power = output[0].powi(2) + output[1].powi(2);
+        assert!((power - 0.5).abs() < 1e-5, "pan {} power {}", pan, power);
+    }
+}
+
+#[test]
+fn test_input_gain_in_db_scales_amplitude() {
+    let mut mixer = create_mixer(&[
+        ("input_0_gain", Value::from(-6.0)),
+        ("input_1_gain", Value::from(-20.0)),
+    ]);
+
+    let inputs = vec![
+        (Uuid::new_v4(), stereo_frame(vec![0.5, -0.5])),
+        (Uuid::new_v4(), stereo_frame(vec![0.0, 0.0])),
+    ];
+    let output = match mixer.process_multi(inputs).unwrap().audio_data {
+        Some(UnifiedAudioData::Stereo { samples, .. }) => samples,
+        _ => panic!("Expected stereo audio data"),
+    };
+    let expected = 0.5 * 10f32.powf(-6.0 / 20.0);
+    assert!((output[0] - expected).abs() < 1e-5);
+    assert!((output[1] + expected).abs() < 1e-5);
+
+    // Only the second input is attenuated by 20 dB
+    let output = mix_block(&mut mixer, 0.0, 0.5);
+    assert!((output[0] - 0.05).abs() < 1e-5);
+}
+
+#[test]
+fn test_master_soft_limiter_prevents_clipping() {
+    let mut mixer = create_mixer(&[("master_limiter", Value::Bool(true))]);
+
+    let output = mix_block(&mut mixer, 0.9, 0.9);
+    assert!(output.iter().all(|sample| sample.abs() < 1.0));
+
+    // Quiet material is untouched by the limiter
+    let output = mix_block(&mut mixer, 0.1, 0.2);
+    assert!(output.iter().all(|sample| (sample - 0.3).abs() < 1e-6));
+
+    // Without the limiter the same sum exceeds full scale
+    let mut mixer = create_mixer(&[]);
+    let output = mix_block(&mut mixer, 0.9, 0.9);
+    assert!(output.iter().all(|sample| *sample > 1.0));
+}
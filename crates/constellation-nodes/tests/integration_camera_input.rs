@@ -21,6 +21,7 @@ use constellation_nodes::camera::CameraCapture;
 use constellation_nodes::input::CameraInputNode;
 use constellation_nodes::{NodeConfig, NodeProcessor, ParameterType};
 use std::collections::HashMap;
+use std::time::Duration;
 use uuid::Uuid;
 
 // TiDD Test Suite: Camera Input Integration Tests
@@ -144,6 +145,9 @@ fn test_camera_input_node_frame_processing_without_camera() {
         audio_data: None,
         control_data: None,
         tally_metadata: TallyMetadata::new(),
+        timestamp: Duration::ZERO,
+        sequence: 0,
+        checksum: None,
     };
 
     // Should return fallback frame when no camera is available
@@ -185,6 +189,9 @@ fn test_camera_input_node_resolution_parsing() {
         audio_data: None,
         control_data: None,
         tally_metadata: TallyMetadata::new(),
+        timestamp: Duration::ZERO,
+        sequence: 0,
+        checksum: None,
     };
 
     let result = node.process(input_frame);
@@ -300,6 +307,9 @@ fn test_camera_input_with_valid_parameters() {
         audio_data: None,
         control_data: None,
         tally_metadata: TallyMetadata::new(),
+        timestamp: Duration::ZERO,
+        sequence: 0,
+        checksum: None,
     };
 
     let result = node.process(input_frame);
@@ -333,6 +343,9 @@ fn test_camera_input_parameter_reset_behavior() {
         audio_data: None,
         control_data: None,
         tally_metadata: TallyMetadata::new(),
+        timestamp: Duration::ZERO,
+        sequence: 0,
+        checksum: None,
     };
 
     let _ = node.process(input_frame.clone());
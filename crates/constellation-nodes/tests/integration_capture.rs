@@ -19,6 +19,7 @@
 use constellation_core::{FrameData, NodeConfig, RenderData, TallyMetadata};
 use constellation_nodes::{NodeProcessor, ScreenCaptureNode, WindowCaptureNode};
 use std::collections::HashMap;
+use std::time::Duration;
 use uuid::Uuid;
 
 #[test]
@@ -126,6 +127,9 @@ fn test_capture_processing_flow() {
         audio_data: None,
         control_data: None,
         tally_metadata: TallyMetadata::new(),
+        timestamp: Duration::ZERO,
+        sequence: 0,
+        checksum: None,
     };
 
     // Try to process a frame - this will either succeed (on systems with displays)
@@ -0,0 +1,139 @@
+/*
+ * Constellation Studio - Professional Real-time Video Processing
+ * Copyright (c) 2025 MACHIKO LAB
+ *
+ * This program is free software: you can redistribute it and/or modify
+ * it under the terms of the GNU Affero General Public License as published by
+ * the Free Software Foundation, either version 3 of the License, or
+ * (at your option) any later version.
+ *
+ * This program is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+ * GNU Affero General Public License for more details.
+ *
+ * You should have received a copy of the GNU Affero General Public License
+ * along with this program. If not, see <https://www.gnu.org/licenses/>.
+ */
+
+mod common;
+
+use common::{node_config, pixel_at, raster};
+use constellation_core::*;
+use constellation_nodes::{ChromaKeyNode, NodeProcessor};
+use serde_json::Value;
+use uuid::Uuid;
+
+const GREEN: [u8; 4] = [0, 255, 0, 255];
+const SKIN: [u8; 4] = [230, 180, 150, 255];
+
+fn create_chroma_key(parameters: &[(&str, Value)]) -> ChromaKeyNode {
+    ChromaKeyNode::new(Uuid::new_v4(), node_config(parameters)).unwrap()
+}
+
+/// Left half solid green backdrop, right half a skin-toned subject
+fn green_screen_frame(width: u32, height: u32) -> VideoFrame {
+    let mut data = Vec::with_capacity((width * height * 4) as usize);
+    for _ in 0..height {
+        for x in 0..width {
+            data.extend_from_slice(if x < width / 2 { &GREEN } else { &SKIN });
+        }
+    }
+    VideoFrame {
+        width,
+        height,
+        format: VideoFormat::Rgba8,
+        data,
+    }
+}
+
+#[test]
+fn test_green_region_becomes_transparent() {
+    let mut node = create_chroma_key(&[]);
+    let output = raster(
+        node.process(FrameData::from_raster(green_screen_frame(8, 4)))
+            .unwrap(),
+    );
+
+    for y in 0..4 {
+        for x in 0..4 {
+            assert_eq!(pixel_at(&output, x, y)[3], 0, "backdrop at ({x}, {y})");
+        }
+        for x in 4..8 {
+            assert_eq!(pixel_at(&output, x, y)[3], 255, "subject at ({x}, {y})");
+        }
+    }
+}
+
+#[test]
+fn test_keyed_foreground_composites_over_background() {
+    let mut node = create_chroma_key(&[("spill_suppression", Value::from(0.0))]);
+    let background = VideoFrame {
+        width: 8,
+        height: 4,
+        format: VideoFormat::Rgba8,
+        data: [0, 0, 255, 255].repeat(32),
+    };
+
+    let output = raster(
+        node.process_multi(vec![
+            (
+                Uuid::new_v4(),
+                FrameData::from_raster(green_screen_frame(8, 4)),
+            ),
+            (Uuid::new_v4(), FrameData::from_raster(background)),
+        ])
+        .unwrap(),
+    );
+
+    assert_eq!(pixel_at(&output, 1, 1), [0, 0, 255, 255]);
+    assert_eq!(pixel_at(&output, 6, 2), SKIN);
+}
+
+#[test]
+fn test_softness_gives_partial_alpha_near_the_key() {
+    let mut node = create_chroma_key(&[
+        ("tolerance", Value::from(0.05)),
+        ("softness", Value::from(0.5)),
+    ]);
+    // A desaturated green sits between the key and neutral colors
+    let frame = VideoFrame {
+        width: 1,
+        height: 1,
+        format: VideoFormat::Rgba8,
+        data: vec![80, 200, 80, 255],
+    };
+
+    let alpha = raster(node.process(FrameData::from_raster(frame)).unwrap()).data[3];
+    assert!(alpha > 0 && alpha < 255, "alpha {alpha}");
+}
+
+#[test]
+fn test_spill_suppression_pulls_green_towards_neutral() {
+    let mut node = create_chroma_key(&[("spill_suppression", Value::from(1.0))]);
+    // Subject lit with green bounce light, far enough from the key to stay opaque
+    let frame = VideoFrame {
+        width: 1,
+        height: 1,
+        format: VideoFormat::Rgba8,
+        data: vec![100, 160, 100, 255],
+    };
+
+    let pixel = raster(node.process(FrameData::from_raster(frame)).unwrap()).data;
+    assert_eq!(pixel, vec![100, 100, 100, 255]);
+}
+
+#[test]
+fn test_custom_key_color_from_hex() {
+    let mut node = create_chroma_key(&[("key_color", Value::String("#0000ff".to_string()))]);
+    let frame = VideoFrame {
+        width: 2,
+        height: 1,
+        format: VideoFormat::Rgba8,
+        data: [[0, 0, 255, 255], GREEN].concat(),
+    };
+
+    let output = raster(node.process(FrameData::from_raster(frame)).unwrap());
+    assert_eq!(pixel_at(&output, 0, 0)[3], 0);
+    assert_eq!(pixel_at(&output, 1, 0)[3], 255);
+}
@@ -0,0 +1,142 @@
+/*
+ * Constellation Studio - Professional Real-time Video Processing
+ * Copyright (c) 2025 MACHIKO LAB
+ *
+ * This program is free software: you can redistribute it and/or modify
+ * it under the terms of the GNU Affero General Public License as published by
+ * the Free Software Foundation, either version 3 of the License, or
+ * (at your option) any later version.
+ *
+ * This program is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+ * GNU Affero General Public License for more details.
+ *
+ * You should have received a copy of the GNU Affero General Public License
+ * along with this program. If not, see <https://www.gnu.org/licenses/>.
+ */
+
+use constellation_core::*;
+use constellation_nodes::{DeinterlaceNode, NodeConfig, NodeProcessor};
+use serde_json::Value;
+use std::collections::HashMap;
+use std::time::Duration;
+use uuid::Uuid;
+
+const WIDTH: u32 = 32;
+const HEIGHT: u32 = 16;
+
+fn create_deinterlacer(mode: &str, field_order: &str) -> DeinterlaceNode {
+    let mut parameters = HashMap::new();
+    parameters.insert("mode".to_string(), Value::String(mode.to_string()));
+    parameters.insert(
+        "field_order".to_string(),
+        Value::String(field_order.to_string()),
+    );
+    DeinterlaceNode::new(Uuid::new_v4(), NodeConfig { parameters }).unwrap()
+}
+
+/// A vertical bar that moved 8 pixels between the two fields: even lines
+/// show it on the left, odd lines on the right, giving classic combing
+fn combed_frame(format: VideoFormat, bytes_per_pixel: usize) -> VideoFrame {
+    let mut data = Vec::with_capacity(WIDTH as usize * HEIGHT as usize * bytes_per_pixel);
+    for y in 0..HEIGHT {
+        let bar = if y % 2 == 0 { 4..12 } else { 12..20 };
+        for x in 0..WIDTH {
+            let value = if bar.contains(&x) { 255 } else { 0 };
+            data.extend_from_slice(&[value, value, value, 255][..bytes_per_pixel]);
+        }
+    }
+    VideoFrame {
+        width: WIDTH,
+        height: HEIGHT,
+        format,
+        data,
+    }
+}
+
+fn process(node: &mut DeinterlaceNode, frame: VideoFrame) -> VideoFrame {
+    let input = FrameData {
+        render_data: Some(RenderData::Raster2D(frame)),
+        audio_data: None,
+        control_data: None,
+        tally_metadata: TallyMetadata::new(),
+        timestamp: Duration::ZERO,
+        sequence: 0,
+        checksum: None,
+    };
+    match node.process(input).unwrap().render_data {
+        Some(RenderData::Raster2D(frame)) => frame,
+        _ => panic!("Expected Raster2D output"),
+    }
+}
+
+/// Mean absolute difference between each line and the next, the energy that
+/// interlaced combing adds on top of real vertical detail
+fn comb_energy(frame: &VideoFrame, bytes_per_pixel: usize) -> f64 {
+    let row_bytes = frame.width as usize * bytes_per_pixel;
+    let rows: Vec<&[u8]> = frame.data.chunks_exact(row_bytes).collect();
+    let total: u64 = rows
+        .windows(2)
+        .flat_map(|pair| pair[0].iter().zip(pair[1]))
+        .map(|(a, b)| (*a as i32 - *b as i32).unsigned_abs() as u64)
+        .sum();
+    total as f64 / (row_bytes * (rows.len() - 1)) as f64
+}
+
+fn value_at(frame: &VideoFrame, x: u32, y: u32, bytes_per_pixel: usize) -> u8 {
+    frame.data[(y * frame.width + x) as usize * bytes_per_pixel]
+}
+
+#[test]
+fn test_blend_reduces_combing() {
+    let input = combed_frame(VideoFormat::Rgba8, 4);
+    let before = comb_energy(&input, 4);
+
+    let mut node = create_deinterlacer("Blend", "Top Field First");
+    let output = process(&mut node, input);
+    let after = comb_energy(&output, 4);
+
+    assert!(
+        after < before * 0.5,
+        "comb energy went from {before} to {after}"
+    );
+    // Alpha stays opaque
+    assert!(output.data.chunks_exact(4).all(|pixel| pixel[3] == 255));
+}
+
+#[test]
+fn test_bob_top_field_first_keeps_even_lines() {
+    let mut node = create_deinterlacer("Bob", "Top Field First");
+    let output = process(&mut node, combed_frame(VideoFormat::Rgba8, 4));
+
+    assert_eq!(comb_energy(&output, 4), 0.0);
+    for y in 0..HEIGHT {
+        assert_eq!(value_at(&output, 6, y, 4), 255);
+        assert_eq!(value_at(&output, 16, y, 4), 0);
+    }
+}
+
+#[test]
+fn test_bob_bottom_field_first_keeps_odd_lines() {
+    let mut node = create_deinterlacer("Bob", "Bottom Field First");
+    let output = process(&mut node, combed_frame(VideoFormat::Rgba8, 4));
+
+    assert_eq!(comb_energy(&output, 4), 0.0);
+    for y in 0..HEIGHT {
+        assert_eq!(value_at(&output, 6, y, 4), 0);
+        assert_eq!(value_at(&output, 16, y, 4), 255);
+    }
+}
+
+#[test]
+fn test_deinterlace_handles_rgb_frames() {
+    let input = combed_frame(VideoFormat::Rgb8, 3);
+    let before = comb_energy(&input, 3);
+
+    let mut node = create_deinterlacer("Blend", "Top Field First");
+    let output = process(&mut node, input);
+
+    assert_eq!(output.data.len(), (WIDTH * HEIGHT * 3) as usize);
+    assert!(comb_energy(&output, 3) < before * 0.5);
+}
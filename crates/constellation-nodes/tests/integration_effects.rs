@@ -17,9 +17,12 @@
  */
 
 use constellation_core::*;
-use constellation_nodes::effects::{BlurNode, ColorCorrectionNode, SharpenNode};
+use constellation_nodes::effects::{
+    BlurNode, ColorCorrectionNode, CompositeNode, GrayscaleNode, SharpenNode, TransformNode,
+};
 use constellation_nodes::{NodeConfig, NodeProcessor, ParameterType};
 use std::collections::HashMap;
+use std::time::Duration;
 use uuid::Uuid;
 
 // TiDD Test Suite: Effects Processing Integration Tests
@@ -52,6 +55,9 @@ fn create_test_frame_data(width: u32, height: u32) -> FrameData {
         audio_data: None,
         control_data: None,
         tally_metadata: TallyMetadata::new(),
+        timestamp: Duration::ZERO,
+        sequence: 0,
+        checksum: None,
     }
 }
 
@@ -104,14 +110,14 @@ fn test_color_correction_node_parameter_defaults() {
         brightness_param.parameter_type,
         ParameterType::Float
     ));
-    assert_eq!(brightness_param.default_value, serde_json::Value::from(1.0));
+    assert_eq!(brightness_param.default_value, serde_json::Value::from(0.0));
     assert_eq!(
         brightness_param.min_value,
-        Some(serde_json::Value::from(0.0))
+        Some(serde_json::Value::from(-1.0))
     );
     assert_eq!(
         brightness_param.max_value,
-        Some(serde_json::Value::from(3.0))
+        Some(serde_json::Value::from(1.0))
     );
 
     // Test contrast parameter
@@ -163,17 +169,16 @@ fn test_color_correction_brightness_adjustment() {
         parameters: HashMap::new(),
     };
 
-    // Set brightness to 2.0 (double brightness)
+    // Brightness is an additive offset: 0.1 * 255 = 25.5
     config
         .parameters
-        .insert("brightness".to_string(), serde_json::Value::from(2.0));
+        .insert("brightness".to_string(), serde_json::Value::from(0.1));
 
     let mut node = ColorCorrectionNode::new(node_id, config).unwrap();
     let input_frame = create_test_frame_data(4, 4);
 
-    // Get original pixel for comparison
-    let original_pixel = match input_frame.render_data.as_ref().unwrap() {
-        RenderData::Raster2D(frame) => frame.data[0],
+    let original = match input_frame.render_data.as_ref().unwrap() {
+        RenderData::Raster2D(frame) => frame.data.clone(),
         _ => panic!("Expected Raster2D render data"),
     };
 
@@ -185,10 +190,56 @@ fn test_color_correction_brightness_adjustment() {
         RenderData::Raster2D(frame) => frame,
         _ => panic!("Expected Raster2D render data"),
     };
-    let adjusted_pixel = video_frame.data[0];
 
-    // Brightness should increase pixel values (clamped to 255)
-    assert!(adjusted_pixel >= original_pixel);
+    // Every color channel shifts by the same amount, alpha is untouched
+    for (adjusted, original) in video_frame
+        .data
+        .chunks_exact(4)
+        .zip(original.chunks_exact(4))
+    {
+        for c in 0..3 {
+            let expected = (original[c] as f32 + 25.5).min(255.0);
+            assert!((adjusted[c] as f32 - expected).abs() <= 1.0);
+        }
+        assert_eq!(adjusted[3], original[3]);
+    }
+}
+
+#[test]
+fn test_color_correction_zero_saturation_produces_luma() {
+    let node_id = Uuid::new_v4();
+    let mut config = NodeConfig {
+        parameters: HashMap::new(),
+    };
+    config
+        .parameters
+        .insert("saturation".to_string(), serde_json::Value::from(0.0));
+
+    let mut node = ColorCorrectionNode::new(node_id, config).unwrap();
+    let input_frame = create_test_frame_data(8, 8);
+
+    let original = match input_frame.render_data.as_ref().unwrap() {
+        RenderData::Raster2D(frame) => frame.data.clone(),
+        _ => panic!("Expected Raster2D render data"),
+    };
+
+    let output = node.process(input_frame).unwrap();
+    let video_frame = match output.render_data.unwrap() {
+        RenderData::Raster2D(frame) => frame,
+        _ => panic!("Expected Raster2D render data"),
+    };
+
+    for (gray, original) in video_frame
+        .data
+        .chunks_exact(4)
+        .zip(original.chunks_exact(4))
+    {
+        let luma =
+            0.2126 * original[0] as f32 + 0.7152 * original[1] as f32 + 0.0722 * original[2] as f32;
+        assert_eq!(gray[0], gray[1]);
+        assert_eq!(gray[1], gray[2]);
+        assert!((gray[0] as f32 - luma).abs() <= 1.0);
+    }
 }
 
 #[test]
@@ -321,7 +372,15 @@ fn test_sharpen_node_creation_and_properties() {
     ));
     assert_eq!(strength_param.default_value, serde_json::Value::from(1.0));
     assert_eq!(strength_param.min_value, Some(serde_json::Value::from(0.0)));
-    assert_eq!(strength_param.max_value, Some(serde_json::Value::from(5.0)));
+    assert_eq!(strength_param.max_value, Some(serde_json::Value::from(3.0)));
+
+    // Verify radius parameter exists
+    let radius_param = &properties.parameters["radius"];
+    assert!(matches!(
+        radius_param.parameter_type,
+        ParameterType::Integer
+    ));
+    assert_eq!(radius_param.default_value, serde_json::Value::from(1));
 }
 
 #[test]
@@ -388,6 +447,194 @@ fn test_sharpen_node_zero_strength() {
     assert_eq!(video_frame.data, original_data);
 }
 
+fn create_edge_frame_data(width: u32, height: u32) -> FrameData {
+    // Left half dark, right half bright
+    let mut data = vec![0u8; (width * height * 4) as usize];
+    for y in 0..height {
+        for x in 0..width {
+            let idx = ((y * width + x) * 4) as usize;
+            let value = if x < width / 2 { 64 } else { 192 };
+            data[idx..idx + 4].copy_from_slice(&[value, value, value, 255]);
+        }
+    }
+
+    FrameData {
+        render_data: Some(RenderData::Raster2D(VideoFrame {
+            width,
+            height,
+            format: VideoFormat::Rgba8,
+            data,
+        })),
+        audio_data: None,
+        control_data: None,
+        tally_metadata: TallyMetadata::new(),
+        timestamp: Duration::ZERO,
+        sequence: 0,
+        checksum: None,
+    }
+}
+
+fn sharpen_edge_contrast(strength: f64) -> i32 {
+    let (width, height) = (16, 4);
+    let mut config = NodeConfig {
+        parameters: HashMap::new(),
+    };
+    config
+        .parameters
+        .insert("strength".to_string(), serde_json::Value::from(strength));
+    config
+        .parameters
+        .insert("radius".to_string(), serde_json::Value::from(2));
+
+    let mut node = SharpenNode::new(Uuid::new_v4(), config).unwrap();
+    let output = node.process(create_edge_frame_data(width, height)).unwrap();
+    let frame = match output.render_data.unwrap() {
+        RenderData::Raster2D(frame) => frame,
+        _ => panic!("Expected Raster2D render data"),
+    };
+
+    // Contrast between the pixels on either side of the edge (row 1)
+    let left = ((width + width / 2 - 1) * 4) as usize;
+    let right = ((width + width / 2) * 4) as usize;
+    assert_eq!(frame.data[left + 3], 255, "alpha must be preserved");
+    frame.data[right] as i32 - frame.data[left] as i32
+}
+
+#[test]
+fn test_sharpen_increases_edge_contrast() {
+    let original = sharpen_edge_contrast(0.0);
+    let mild = sharpen_edge_contrast(0.5);
+    let strong = sharpen_edge_contrast(2.0);
+
+    assert_eq!(original, 128);
+    assert!(mild > original, "mild {mild} <= original {original}");
+    assert!(strong > mild, "strong {strong} <= mild {mild}");
+}
+
+#[test]
+fn test_sharpen_passes_through_non_raster_data() {
+    let mut node = SharpenNode::new(
+        Uuid::new_v4(),
+        NodeConfig {
+            parameters: HashMap::new(),
+        },
+    )
+    .unwrap();
+
+    let input_frame = FrameData {
+        render_data: Some(RenderData::Intermediate {
+            gpu_buffers: vec![1, 2],
+            render_state: "pending".to_string(),
+            transform_matrix: [0.0; 16],
+        }),
+        audio_data: None,
+        control_data: None,
+        tally_metadata: TallyMetadata::new(),
+        timestamp: Duration::ZERO,
+        sequence: 0,
+        checksum: None,
+    };
+
+    let output = node.process(input_frame).unwrap();
+    assert!(matches!(
+        output.render_data,
+        Some(RenderData::Intermediate { .. })
+    ));
+}
+
+fn grayscale(frame: VideoFrame, amount: Option<f64>) -> VideoFrame {
+    let mut config = NodeConfig {
+        parameters: HashMap::new(),
+    };
+    if let Some(amount) = amount {
+        config
+            .parameters
+            .insert("amount".to_string(), serde_json::Value::from(amount));
+    }
+    let mut node = GrayscaleNode::new(Uuid::new_v4(), config).unwrap();
+
+    let output = node
+        .process(FrameData {
+            render_data: Some(RenderData::Raster2D(frame)),
+            audio_data: None,
+            control_data: None,
+            tally_metadata: TallyMetadata::new(),
+            timestamp: Duration::ZERO,
+            sequence: 0,
+            checksum: None,
+        })
+        .unwrap();
+    match output.render_data.unwrap() {
+        RenderData::Raster2D(frame) => frame,
+        _ => panic!("Expected Raster2D render data"),
+    }
+}
+
+#[test]
+fn test_grayscale_node_properties() {
+    let node = GrayscaleNode::new(
+        Uuid::new_v4(),
+        NodeConfig {
+            parameters: HashMap::new(),
+        },
+    )
+    .unwrap();
+
+    let properties = node.get_properties();
+    assert_eq!(properties.name, "Grayscale");
+    assert_eq!(
+        properties.node_type,
+        NodeType::Effect(EffectType::Grayscale)
+    );
+    assert!(matches!(
+        properties.parameters["amount"].parameter_type,
+        ParameterType::Float
+    ));
+}
+
+#[test]
+fn test_grayscale_rgba_uses_bt709_luma() {
+    let input = create_test_video_frame(8, 8);
+    let original = input.data.clone();
+    let output = grayscale(input, None);
+
+    for (gray, original) in output.data.chunks_exact(4).zip(original.chunks_exact(4)) {
+        let luma =
+            0.2126 * original[0] as f32 + 0.7152 * original[1] as f32 + 0.0722 * original[2] as f32;
+        assert_eq!(gray[0], gray[1]);
+        assert_eq!(gray[1], gray[2]);
+        assert!((gray[0] as f32 - luma).abs() <= 1.0);
+        assert_eq!(gray[3], original[3]);
+    }
+}
+
+#[test]
+fn test_grayscale_bgra_uses_bt709_luma() {
+    // Pure red, green and blue pixels stored as BGRA
+    let frame = VideoFrame {
+        width: 3,
+        height: 1,
+        format: VideoFormat::Bgra8,
+        data: vec![0, 0, 255, 255, 0, 255, 0, 255, 255, 0, 0, 200],
+    };
+    let output = grayscale(frame, None);
+
+    let expected = [0.2126 * 255.0, 0.7152 * 255.0, 0.0722 * 255.0];
+    for (pixel, expected) in output.data.chunks_exact(4).zip(expected) {
+        assert_eq!(pixel[0], pixel[1]);
+        assert_eq!(pixel[1], pixel[2]);
+        assert!((pixel[0] as f32 - expected).abs() <= 1.0);
+    }
+    assert_eq!(output.data[11], 200);
+}
+
+#[test]
+fn test_grayscale_zero_amount_is_identity() {
+    let input = create_test_video_frame(4, 4);
+    let original = input.data.clone();
+    assert_eq!(grayscale(input, Some(0.0)).data, original);
+}
+
 #[test]
 fn test_effects_chain_processing() {
     // Test chaining multiple effects together
@@ -400,7 +647,7 @@ fn test_effects_chain_processing() {
     };
     config1
         .parameters
-        .insert("brightness".to_string(), serde_json::Value::from(1.2));
+        .insert("brightness".to_string(), serde_json::Value::from(0.2));
     let mut color_node = ColorCorrectionNode::new(node_id1, config1).unwrap();
 
     let mut config2 = NodeConfig {
@@ -453,11 +700,11 @@ fn test_effects_parameter_updates() {
     let mut node = ColorCorrectionNode::new(node_id, config).unwrap();
 
     // Test parameter updates
-    let result = node.set_parameter("brightness", serde_json::Value::from(2.5));
+    let result = node.set_parameter("brightness", serde_json::Value::from(0.5));
     assert!(result.is_ok());
     assert_eq!(
         node.get_parameter("brightness"),
-        Some(serde_json::Value::from(2.5))
+        Some(serde_json::Value::from(0.5))
     );
 
     let result = node.set_parameter("contrast", serde_json::Value::from(1.8));
@@ -494,6 +741,9 @@ fn test_effects_preserve_non_video_data() {
         }),
         control_data: None,
         tally_metadata: TallyMetadata::new().with_program_tally(true),
+        timestamp: Duration::ZERO,
+        sequence: 0,
+        checksum: None,
     };
 
     let result = node.process(input_frame);
@@ -543,6 +793,9 @@ fn test_effects_with_no_video_data() {
         }),
         control_data: None,
         tally_metadata: TallyMetadata::new(),
+        timestamp: Duration::ZERO,
+        sequence: 0,
+        checksum: None,
     };
 
     let result = node.process(input_frame);
@@ -568,3 +821,418 @@ fn test_effects_with_no_video_data() {
         _ => panic!("Expected stereo audio data"),
     }
 }
+
+fn solid_frame_data(width: u32, height: u32, pixel: [u8; 4]) -> FrameData {
+    FrameData {
+        render_data: Some(RenderData::Raster2D(VideoFrame {
+            width,
+            height,
+            format: VideoFormat::Rgba8,
+            data: pixel.repeat((width * height) as usize),
+        })),
+        audio_data: None,
+        control_data: None,
+        tally_metadata: TallyMetadata::new(),
+        timestamp: Duration::ZERO,
+        sequence: 0,
+        checksum: None,
+    }
+}
+
+fn composite_pixel(
+    parameters: &[(&str, serde_json::Value)],
+    foreground: [u8; 4],
+    background: [u8; 4],
+) -> [u8; 4] {
+    let mut config = NodeConfig {
+        parameters: HashMap::new(),
+    };
+    for (key, value) in parameters {
+        config.parameters.insert(key.to_string(), value.clone());
+    }
+
+    let mut node = CompositeNode::new(Uuid::new_v4(), config).unwrap();
+    let output = node
+        .composite(
+            solid_frame_data(2, 2, foreground),
+            solid_frame_data(2, 2, background),
+        )
+        .unwrap();
+
+    match output.render_data.unwrap() {
+        RenderData::Raster2D(frame) => [frame.data[0], frame.data[1], frame.data[2], frame.data[3]],
+        _ => panic!("Expected Raster2D render data"),
+    }
+}
+
+fn blend_mode(mode: &str) -> (&'static str, serde_json::Value) {
+    ("blend_mode", serde_json::Value::from(mode))
+}
+
+#[test]
+fn test_composite_normal_with_opacity() {
+    let opaque = composite_pixel(
+        &[blend_mode("Normal")],
+        [200, 100, 0, 255],
+        [0, 0, 200, 255],
+    );
+    assert_eq!(opaque, [200, 100, 0, 255]);
+
+    // Half opacity mixes the two layers evenly
+    let half = composite_pixel(
+        &[
+            blend_mode("Normal"),
+            ("opacity", serde_json::Value::from(0.5)),
+        ],
+        [200, 100, 0, 255],
+        [0, 0, 200, 255],
+    );
+    assert_eq!(half, [100, 50, 100, 255]);
+}
+
+#[test]
+fn test_composite_add() {
+    let pixel = composite_pixel(
+        &[blend_mode("Add")],
+        [100, 200, 0, 255],
+        [100, 100, 50, 255],
+    );
+    assert_eq!(pixel, [200, 255, 50, 255]);
+}
+
+#[test]
+fn test_composite_multiply() {
+    let pixel = composite_pixel(
+        &[blend_mode("Multiply")],
+        [255, 128, 0, 255],
+        [100, 100, 100, 255],
+    );
+    // 255*100/255 = 100, 128*100/255 ≈ 50, 0
+    assert_eq!(pixel, [100, 50, 0, 255]);
+}
+
+#[test]
+fn test_composite_screen() {
+    let pixel = composite_pixel(
+        &[blend_mode("Screen")],
+        [0, 128, 255, 255],
+        [100, 128, 100, 255],
+    );
+    // 1 - (1-a)(1-b): 100, 128+128-64.25 ≈ 192, 255
+    assert_eq!(pixel, [100, 192, 255, 255]);
+}
+
+#[test]
+fn test_composite_straight_vs_premultiplied_alpha() {
+    // Straight alpha: half-transparent white over opaque black
+    let straight = composite_pixel(
+        &[blend_mode("Normal")],
+        [255, 255, 255, 128],
+        [0, 0, 0, 255],
+    );
+    assert_eq!(straight, [128, 128, 128, 255]);
+
+    // The same pixel expressed premultiplied gives the same result
+    let premultiplied = composite_pixel(
+        &[
+            blend_mode("Normal"),
+            ("premultiplied", serde_json::Value::Bool(true)),
+        ],
+        [128, 128, 128, 128],
+        [0, 0, 0, 255],
+    );
+    assert_eq!(premultiplied, [128, 128, 128, 255]);
+
+    // Over a transparent backdrop the straight color is kept
+    let over_clear = composite_pixel(&[blend_mode("Multiply")], [200, 100, 50, 128], [0, 0, 0, 0]);
+    assert_eq!(over_clear, [200, 100, 50, 128]);
+}
+
+#[test]
+fn test_composite_foreground_input_ignores_connection_order() {
+    let red = Uuid::new_v4();
+    let blue = Uuid::new_v4();
+    let mut node = CompositeNode::new(
+        Uuid::new_v4(),
+        NodeConfig {
+            parameters: HashMap::from([(
+                "foreground_input".to_string(),
+                serde_json::Value::from(blue.to_string()),
+            )]),
+        },
+    )
+    .unwrap();
+
+    for inputs in [
+        vec![
+            (red, solid_frame_data(2, 2, [255, 0, 0, 255])),
+            (blue, solid_frame_data(2, 2, [0, 0, 255, 255])),
+        ],
+        vec![
+            (blue, solid_frame_data(2, 2, [0, 0, 255, 255])),
+            (red, solid_frame_data(2, 2, [255, 0, 0, 255])),
+        ],
+    ] {
+        match node.process_multi(inputs).unwrap().render_data {
+            Some(RenderData::Raster2D(frame)) => assert_eq!(frame.data[..4], [0, 0, 255, 255]),
+            _ => panic!("Expected Raster2D render data"),
+        }
+    }
+}
+
+#[test]
+fn test_composite_resizes_foreground_to_background() {
+    let mut node = CompositeNode::new(
+        Uuid::new_v4(),
+        NodeConfig {
+            parameters: HashMap::new(),
+        },
+    )
+    .unwrap();
+
+    let output = node
+        .composite(
+            solid_frame_data(2, 2, [10, 20, 30, 255]),
+            solid_frame_data(8, 4, [0, 0, 0, 255]),
+        )
+        .unwrap();
+
+    let frame = match output.render_data.unwrap() {
+        RenderData::Raster2D(frame) => frame,
+        _ => panic!("Expected Raster2D render data"),
+    };
+    assert_eq!((frame.width, frame.height), (8, 4));
+    assert_eq!(frame.data.len(), 8 * 4 * 4);
+    for pixel in frame.data.chunks_exact(4) {
+        assert_eq!(pixel, [10, 20, 30, 255]);
+    }
+}
+
+fn transform_output(node: &mut TransformNode, input: FrameData) -> VideoFrame {
+    match node.process(input).unwrap().render_data.unwrap() {
+        RenderData::Raster2D(frame) => frame,
+        _ => panic!("Expected Raster2D render data"),
+    }
+}
+
+#[test]
+fn test_transform_identity_preserves_frame() {
+    let mut node = TransformNode::new(
+        Uuid::new_v4(),
+        NodeConfig {
+            parameters: HashMap::new(),
+        },
+    )
+    .unwrap();
+
+    let input = create_test_video_frame(16, 8);
+    let output = transform_output(&mut node, create_test_frame_data(16, 8));
+    assert_eq!(output.data, input.data);
+}
+
+#[test]
+fn test_transform_rotation_from_control_data() {
+    let mut node = TransformNode::new(
+        Uuid::new_v4(),
+        NodeConfig {
+            parameters: HashMap::new(),
+        },
+    )
+    .unwrap();
+
+    // Asymmetric 3x3 pattern: the red channel holds the pixel index
+    let data: Vec<u8> = (0..9u8).flat_map(|i| [i * 20, 0, 0, 255]).collect();
+    let half_angle = std::f32::consts::FRAC_PI_4; // 90 degrees around Z
+    let input = FrameData {
+        render_data: Some(RenderData::Raster2D(VideoFrame {
+            width: 3,
+            height: 3,
+            format: VideoFormat::Rgba8,
+            data,
+        })),
+        audio_data: None,
+        control_data: Some(ControlData::Transform {
+            target_node_id: None,
+            position: None,
+            rotation: Some(Quaternion {
+                x: 0.0,
+                y: 0.0,
+                z: half_angle.sin(),
+                w: half_angle.cos(),
+            }),
+            scale: None,
+        }),
+        tally_metadata: TallyMetadata::new(),
+        timestamp: Duration::ZERO,
+        sequence: 0,
+        checksum: None,
+    };
+
+    let output = transform_output(&mut node, input);
+    let red_at = |x: usize, y: usize| output.data[(y * 3 + x) * 4];
+
+    // Clockwise on screen: top-left -> top-right -> bottom-right -> bottom-left
+    assert_eq!(red_at(2, 0), 0);
+    assert_eq!(red_at(2, 2), 40);
+    assert_eq!(red_at(0, 2), 160);
+    assert_eq!(red_at(0, 0), 120);
+    assert_eq!(red_at(1, 1), 80);
+}
+
+/// Packed 3-byte frame with the same gradient as `create_test_video_frame`,
+/// stored in the channel order of `format`
+fn create_three_byte_frame(width: u32, height: u32, format: VideoFormat) -> VideoFrame {
+    let bgr = format == VideoFormat::Bgr8;
+    let data = create_test_video_frame(width, height)
+        .data
+        .chunks_exact(4)
+        .flat_map(|p| {
+            if bgr {
+                [p[2], p[1], p[0]]
+            } else {
+                [p[0], p[1], p[2]]
+            }
+        })
+        .collect();
+
+    VideoFrame {
+        width,
+        height,
+        format,
+        data,
+    }
+}
+
+fn process_raster(node: &mut dyn NodeProcessor, frame: VideoFrame) -> VideoFrame {
+    let input = FrameData {
+        render_data: Some(RenderData::Raster2D(frame)),
+        audio_data: None,
+        control_data: None,
+        tally_metadata: TallyMetadata::new(),
+        timestamp: Duration::ZERO,
+        sequence: 0,
+        checksum: None,
+    };
+    match node.process(input).unwrap().render_data.unwrap() {
+        RenderData::Raster2D(frame) => frame,
+        _ => panic!("Expected Raster2D render data"),
+    }
+}
+
+#[test]
+fn test_color_correction_rgb8_brightness_per_pixel() {
+    let mut config = NodeConfig {
+        parameters: HashMap::new(),
+    };
+    config
+        .parameters
+        .insert("brightness".to_string(), serde_json::Value::from(0.1));
+    let mut node = ColorCorrectionNode::new(Uuid::new_v4(), config).unwrap();
+
+    let input = create_three_byte_frame(8, 8, VideoFormat::Rgb8);
+    let output = process_raster(&mut node, input.clone());
+
+    assert_eq!(output.format, VideoFormat::Rgb8);
+    assert_eq!(output.data.len(), 8 * 8 * 3);
+    for (adjusted, original) in output.data.iter().zip(&input.data) {
+        let expected = (*original as f32 + 25.5).min(255.0);
+        assert!((*adjusted as f32 - expected).abs() <= 1.0);
+    }
+}
+
+#[test]
+fn test_color_correction_bgr8_saturation_respects_channel_order() {
+    let mut config = NodeConfig {
+        parameters: HashMap::new(),
+    };
+    config
+        .parameters
+        .insert("saturation".to_string(), serde_json::Value::from(0.0));
+    let mut node = ColorCorrectionNode::new(Uuid::new_v4(), config).unwrap();
+
+    let input = create_three_byte_frame(8, 8, VideoFormat::Bgr8);
+    let output = process_raster(&mut node, input.clone());
+
+    for (gray, original) in output.data.chunks_exact(3).zip(input.data.chunks_exact(3)) {
+        // BGR order: weights apply to index 2 (red) and 0 (blue)
+        let luma =
+            0.2126 * original[2] as f32 + 0.7152 * original[1] as f32 + 0.0722 * original[0] as f32;
+        assert_eq!(gray[0], gray[1]);
+        assert_eq!(gray[1], gray[2]);
+        assert!((gray[0] as f32 - luma).abs() <= 1.0);
+    }
+}
+
+#[test]
+fn test_sharpen_and_blur_handle_rgb8() {
+    let mut sharpen = SharpenNode::new(
+        Uuid::new_v4(),
+        NodeConfig {
+            parameters: HashMap::new(),
+        },
+    )
+    .unwrap();
+    let mut blur = BlurNode::new(
+        Uuid::new_v4(),
+        NodeConfig {
+            parameters: HashMap::new(),
+        },
+    )
+    .unwrap();
+
+    // Uniform rows stay uniform; only the 3-byte stride has to be right
+    let frame = VideoFrame {
+        width: 5,
+        height: 3,
+        format: VideoFormat::Rgb8,
+        data: [40u8, 120, 200].repeat(15),
+    };
+    for node in [&mut sharpen as &mut dyn NodeProcessor, &mut blur] {
+        let output = process_raster(node, frame.clone());
+        assert_eq!(output.format, VideoFormat::Rgb8);
+        assert_eq!(output.data, frame.data);
+    }
+
+    // A hard edge is still sharpened on 3-byte data
+    let mut edge = vec![0u8; 8 * 3];
+    edge[12..].fill(255);
+    let edge_frame = VideoFrame {
+        width: 8,
+        height: 1,
+        format: VideoFormat::Rgb8,
+        data: edge,
+    };
+    let sharpened = process_raster(&mut sharpen, edge_frame.clone());
+    assert_eq!(sharpened.data.len(), edge_frame.data.len());
+    assert_eq!(&sharpened.data[..3], &[0, 0, 0]);
+    assert_eq!(&sharpened.data[21..], &[255, 255, 255]);
+}
+
+#[test]
+fn test_transform_translates_rgb8_and_fills_black() {
+    let mut config = NodeConfig {
+        parameters: HashMap::new(),
+    };
+    config.parameters.insert(
+        "position".to_string(),
+        serde_json::Value::from(vec![1.0, 0.0]),
+    );
+    let mut node = TransformNode::new(Uuid::new_v4(), config).unwrap();
+
+    let input = create_three_byte_frame(4, 2, VideoFormat::Bgr8);
+    let output = process_raster(&mut node, input.clone());
+
+    assert_eq!(output.format, VideoFormat::Bgr8);
+    assert_eq!(output.data.len(), 4 * 2 * 3);
+    for y in 0..2 {
+        let row = |frame: &VideoFrame, x: usize| {
+            let offset = (y * 4 + x) * 3;
+            frame.data[offset..offset + 3].to_vec()
+        };
+        // The uncovered first column is black, the rest shifts right by one
+        assert_eq!(row(&output, 0), vec![0, 0, 0]);
+        for x in 1..4 {
+            assert_eq!(row(&output, x), row(&input, x - 1));
+        }
+    }
+}
@@ -0,0 +1,183 @@
+/*
+ * Constellation Studio - Professional Real-time Video Processing
+ * Copyright (c) 2025 MACHIKO LAB
+ *
+ * This program is free software: you can redistribute it and/or modify
+ * it under the terms of the GNU Affero General Public License as published by
+ * the Free Software Foundation, either version 3 of the License, or
+ * (at your option) any later version.
+ *
+ * This program is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+ * GNU Affero General Public License for more details.
+ *
+ * You should have received a copy of the GNU Affero General Public License
+ * along with this program. If not, see <https://www.gnu.org/licenses/>.
+ */
+
+use constellation_core::*;
+use constellation_nodes::{Lut3d, LutNode, NodeConfig, NodeProcessor};
+use serde_json::Value;
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+use std::time::Duration;
+use uuid::Uuid;
+
+fn temp_cube(name: &str, contents: &str) -> PathBuf {
+    let path = std::env::temp_dir().join(format!("constellation-{}-{}.cube", name, Uuid::new_v4()));
+    std::fs::write(&path, contents).unwrap();
+    path
+}
+
+/// Write a `.cube` file with `size`³ entries produced by `map`
+fn cube_file(name: &str, size: usize, map: impl Fn([f32; 3]) -> [f32; 3]) -> PathBuf {
+    let mut contents = format!("# generated\nTITLE \"{}\"\nLUT_3D_SIZE {}\n", name, size);
+    for entry in Lut3d::identity(size).table {
+        let [r, g, b] = map(entry);
+        contents.push_str(&format!("{:.6} {:.6} {:.6}\n", r, g, b));
+    }
+    temp_cube(name, &contents)
+}
+
+/// Every 8-bit level on each channel, with a varying alpha
+fn gradient_frame() -> FrameData {
+    let data = (0..256u32)
+        .flat_map(|i| {
+            [
+                i as u8,
+                (255 - i) as u8,
+                (i * 7 % 256) as u8,
+                (i * 3 % 256) as u8,
+            ]
+        })
+        .collect();
+    FrameData {
+        render_data: Some(RenderData::Raster2D(VideoFrame {
+            width: 16,
+            height: 16,
+            format: VideoFormat::Rgba8,
+            data,
+        })),
+        audio_data: None,
+        control_data: None,
+        tally_metadata: TallyMetadata::new(),
+        timestamp: Duration::ZERO,
+        sequence: 0,
+        checksum: None,
+    }
+}
+
+fn create_lut_node(path: &Path, intensity: f64) -> LutNode {
+    let parameters = HashMap::from([
+        (
+            "file_path".to_string(),
+            Value::String(path.to_string_lossy().into_owned()),
+        ),
+        ("intensity".to_string(), Value::from(intensity)),
+    ]);
+    LutNode::new(Uuid::new_v4(), NodeConfig { parameters }).unwrap()
+}
+
+fn pixels(frame: &FrameData) -> Vec<u8> {
+    match &frame.render_data {
+        Some(RenderData::Raster2D(frame)) => frame.data.clone(),
+        _ => panic!("expected a raster frame"),
+    }
+}
+
+#[test]
+fn test_identity_lut_leaves_frame_unchanged() {
+    let path = cube_file("identity", 17, |rgb| rgb);
+    let mut node = create_lut_node(&path, 1.0);
+
+    let input = gradient_frame();
+    let output = node.process(input.clone()).unwrap();
+    assert_eq!(pixels(&output), pixels(&input));
+
+    std::fs::remove_file(&path).unwrap();
+}
+
+#[test]
+fn test_invert_lut_inverts_every_pixel() {
+    let path = cube_file("invert", 2, |rgb| rgb.map(|c| 1.0 - c));
+    let mut node = create_lut_node(&path, 1.0);
+
+    let input = pixels(&gradient_frame());
+    let output = pixels(&node.process(gradient_frame()).unwrap());
+    for (before, after) in input.chunks_exact(4).zip(output.chunks_exact(4)) {
+        assert_eq!(
+            after,
+            [255 - before[0], 255 - before[1], 255 - before[2], before[3]]
+        );
+    }
+
+    std::fs::remove_file(&path).unwrap();
+}
+
+#[test]
+fn test_intensity_blends_with_original() {
+    let path = cube_file("half", 2, |rgb| rgb.map(|c| 1.0 - c));
+    let mut node = create_lut_node(&path, 0.5);
+
+    let output = pixels(&node.process(gradient_frame()).unwrap());
+    // Halfway between x and 255 - x is mid grey for every channel
+    for pixel in output.chunks_exact(4) {
+        for &channel in &pixel[..3] {
+            assert!((127..=128).contains(&channel), "got {}", channel);
+        }
+    }
+
+    std::fs::remove_file(&path).unwrap();
+}
+
+#[test]
+fn test_trilinear_interpolation_between_entries() {
+    // Only red is remapped: 0 -> 0.2, 1 -> 0.6
+    let lut = Lut3d::parse_cube(
+        "LUT_3D_SIZE 2\n\
+         0.2 0 0\n0.6 0 0\n0.2 1 0\n0.6 1 0\n\
+         0.2 0 1\n0.6 0 1\n0.2 1 1\n0.6 1 1\n",
+    )
+    .unwrap();
+    let [r, g, b] = lut.sample([0.5, 0.25, 0.75]);
+    assert!((r - 0.4).abs() < 1e-6);
+    assert!((g - 0.25).abs() < 1e-6);
+    assert!((b - 0.75).abs() < 1e-6);
+}
+
+#[test]
+fn test_invalid_cube_files_are_rejected() {
+    // Too few entries for the declared size
+    assert!(Lut3d::parse_cube("LUT_3D_SIZE 2\n0 0 0\n1 1 1\n").is_err());
+    // Size out of range
+    assert!(Lut3d::parse_cube("LUT_3D_SIZE 1\n0 0 0\n").is_err());
+    // Missing size
+    assert!(Lut3d::parse_cube("0 0 0\n").is_err());
+    // Wrong number of components
+    let mut two_components = "LUT_3D_SIZE 2\n".to_string();
+    two_components.push_str(&"0 0\n".repeat(8));
+    assert!(Lut3d::parse_cube(&two_components).is_err());
+    // 1D LUTs are not supported
+    assert!(Lut3d::parse_cube("LUT_1D_SIZE 2\n0 0 0\n1 1 1\n").is_err());
+
+    let path = temp_cube("broken", "LUT_3D_SIZE 3\n0 0 0\n");
+    let mut node = create_lut_node(&path, 1.0);
+    assert!(node.on_start().is_err());
+    assert!(node.process(gradient_frame()).is_err());
+    std::fs::remove_file(&path).unwrap();
+}
+
+#[test]
+fn test_empty_path_passes_through() {
+    let mut node = LutNode::new(
+        Uuid::new_v4(),
+        NodeConfig {
+            parameters: HashMap::new(),
+        },
+    )
+    .unwrap();
+    let input = gradient_frame();
+    let output = node.process(input.clone()).unwrap();
+    assert_eq!(pixels(&output), pixels(&input));
+}
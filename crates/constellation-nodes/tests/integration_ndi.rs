@@ -0,0 +1,133 @@
+/*
+ * Constellation Studio - Professional Real-time Video Processing
+ * Copyright (c) 2025 MACHIKO LAB
+ *
+ * This program is free software: you can redistribute it and/or modify
+ * it under the terms of the GNU Affero General Public License as published by
+ * the Free Software Foundation, either version 3 of the License, or
+ * (at your option) any later version.
+ *
+ * This program is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+ * GNU Affero General Public License for more details.
+ *
+ * You should have received a copy of the GNU Affero General Public License
+ * along with this program. If not, see <https://www.gnu.org/licenses/>.
+ */
+
+#![cfg(feature = "ndi")]
+
+mod common;
+
+use common::{node_config, solid_frame};
+use constellation_core::*;
+use constellation_nodes::ndi::{self, NdiFrameFormat};
+use constellation_nodes::{create_node_processor, NdiOutputNode, NodeConfig, NodeProcessor};
+use serde_json::Value;
+use std::collections::HashMap;
+use std::time::Duration;
+use uuid::Uuid;
+
+fn create_ndi_output(parameters: &[(&str, Value)]) -> NdiOutputNode {
+    NdiOutputNode::new(Uuid::new_v4(), node_config(parameters)).unwrap()
+}
+
+#[test]
+fn test_frame_conversion_shape() {
+    let frame = solid_frame(6, 4, [255, 0, 0, 128]);
+
+    let bgra = ndi::convert_frame(&frame, NdiFrameFormat::Bgra).unwrap();
+    assert_eq!((bgra.width, bgra.height, bgra.line_stride), (6, 4, 24));
+    assert_eq!(bgra.data.len(), 24 * 4);
+    assert_eq!(&bgra.data[..4], &[0, 0, 255, 128]);
+
+    let rgba = ndi::convert_frame(&frame, NdiFrameFormat::Rgba).unwrap();
+    assert_eq!(rgba.data, frame.data);
+
+    let uyvy = ndi::convert_frame(&frame, NdiFrameFormat::Uyvy).unwrap();
+    assert_eq!(uyvy.line_stride, 12);
+    assert_eq!(uyvy.data.len(), 12 * 4);
+    // BT.709 red: Cb=102, Y=63, Cr=240
+    assert_eq!(&uyvy.data[..4], &[102, 63, 240, 63]);
+}
+
+#[test]
+fn test_frame_conversion_rejects_unsendable_frames() {
+    assert!(ndi::convert_frame(&solid_frame(5, 2, [0; 4]), NdiFrameFormat::Uyvy).is_err());
+    assert!(ndi::convert_frame(&solid_frame(0, 0, [0; 4]), NdiFrameFormat::Bgra).is_err());
+
+    let compressed = VideoFrame {
+        format: VideoFormat::Jpeg,
+        ..solid_frame(2, 2, [0; 4])
+    };
+    assert!(ndi::convert_frame(&compressed, NdiFrameFormat::Bgra).is_err());
+}
+
+#[test]
+fn test_fourcc_matches_ndi_sdk_values() {
+    // NDI_LIB_FOURCC packs the characters little-endian
+    assert_eq!(NdiFrameFormat::Uyvy.fourcc(), 0x5956_5955);
+    assert_eq!(NdiFrameFormat::Bgra.fourcc(), 0x4152_4742);
+    assert_eq!(
+        NdiFrameFormat::from_name("RGBA"),
+        Some(NdiFrameFormat::Rgba)
+    );
+    assert_eq!(NdiFrameFormat::from_name("NV12"), None);
+}
+
+#[test]
+fn test_node_construction_and_parameters() {
+    let mut node = create_ndi_output(&[
+        ("frame_format", Value::from("UYVY")),
+        ("frame_rate", Value::from(29.97)),
+    ]);
+
+    let properties = node.get_properties();
+    assert_eq!(properties.node_type, NodeType::Output(OutputType::Ndi));
+    assert!(properties.parameters.contains_key("source_name"));
+    assert_eq!(node.frame_format().unwrap(), NdiFrameFormat::Uyvy);
+    assert_eq!(node.frame_rate(), (29970, 1000));
+    assert_eq!(node.connections(), None);
+
+    assert!(node
+        .set_parameter("frame_format", Value::from("YUY2"))
+        .is_err());
+
+    assert!(create_node_processor(
+        NodeType::Output(OutputType::Ndi),
+        Uuid::new_v4(),
+        NodeConfig {
+            parameters: HashMap::new(),
+        },
+    )
+    .is_ok());
+}
+
+#[test]
+fn test_send_reports_errors_without_runtime() {
+    let mut node = create_ndi_output(&[]);
+    let result = node.process(FrameData::from_raster(solid_frame(4, 4, [0, 255, 0, 255])));
+
+    if ndi::runtime_available() {
+        result.unwrap();
+        assert_eq!(node.frames_sent(), 1);
+        assert_eq!(node.connections(), Some(0));
+    } else {
+        println!("Skipping NDI send: runtime not installed");
+        assert!(result.is_err());
+        assert_eq!(node.frames_sent(), 0);
+    }
+}
+
+#[test]
+fn test_frames_without_video_pass_through() {
+    let mut node = create_ndi_output(&[]);
+    let input = FrameData {
+        render_data: None,
+        ..FrameData::from_raster(solid_frame(1, 1, [0; 4]))
+    };
+    let output = node.process(input).unwrap();
+    assert!(output.render_data.is_none());
+    assert_eq!(node.frames_sent(), 0);
+}
@@ -0,0 +1,108 @@
+/*
+ * Constellation Studio - Professional Real-time Video Processing
+ * Copyright (c) 2025 MACHIKO LAB
+ *
+ * This program is free software: you can redistribute it and/or modify
+ * it under the terms of the GNU Affero General Public License as published by
+ * the Free Software Foundation, either version 3 of the License, or
+ * (at your option) any later version.
+ *
+ * This program is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+ * GNU Affero General Public License for more details.
+ *
+ * You should have received a copy of the GNU Affero General Public License
+ * along with this program. If not, see <https://www.gnu.org/licenses/>.
+ */
+
+mod common;
+
+use common::{pixel_at, solid_frame};
+use constellation_core::*;
+use constellation_nodes::{scale_to_fit, NodeConfig, NodeProcessor, PreviewNode};
+use serde_json::Value;
+use std::collections::HashMap;
+use uuid::Uuid;
+
+fn create_preview_node(width: u32, height: u32) -> PreviewNode {
+    let mut parameters = HashMap::new();
+    parameters.insert("preview_width".to_string(), Value::from(width));
+    parameters.insert("preview_height".to_string(), Value::from(height));
+    PreviewNode::new(Uuid::new_v4(), NodeConfig { parameters }).unwrap()
+}
+
+#[test]
+fn test_preview_is_empty_until_a_frame_arrives() {
+    let node = create_preview_node(640, 360);
+    assert!(node.get_preview_frame().is_none());
+}
+
+#[test]
+fn test_preview_downscales_full_hd_to_requested_size() {
+    let mut node = create_preview_node(640, 360);
+    let output = node
+        .process(FrameData::from_raster(solid_frame(
+            1920,
+            1080,
+            [200, 100, 50, 255],
+        )))
+        .unwrap();
+
+    // The original frame passes through untouched
+    match output.render_data {
+        Some(RenderData::Raster2D(frame)) => assert_eq!((frame.width, frame.height), (1920, 1080)),
+        _ => panic!("Expected Raster2D output"),
+    }
+
+    let preview = node.get_preview_frame().unwrap();
+    assert_eq!((preview.width, preview.height), (640, 360));
+    assert_eq!(preview.data.len(), 640 * 360 * 4);
+    // Same 16:9 aspect, so no letterbox bars
+    assert_eq!(pixel_at(&preview, 0, 0), [200, 100, 50, 255]);
+    assert_eq!(pixel_at(&preview, 639, 359), [200, 100, 50, 255]);
+}
+
+#[test]
+fn test_preview_letterboxes_to_preserve_aspect() {
+    // A 4:3 box leaves bars above and below a 16:9 image
+    let mut node = create_preview_node(400, 300);
+    node.process(FrameData::from_raster(solid_frame(
+        1920,
+        1080,
+        [255, 255, 255, 255],
+    )))
+    .unwrap();
+
+    let preview = node.get_preview_frame().unwrap();
+    assert_eq!((preview.width, preview.height), (400, 300));
+
+    // 1920x1080 scaled by 400/1920 is 400x225, centred with 37px bars
+    assert_eq!(pixel_at(&preview, 200, 10), [0, 0, 0, 255]);
+    assert_eq!(pixel_at(&preview, 200, 290), [0, 0, 0, 255]);
+    assert_eq!(pixel_at(&preview, 200, 37), [255, 255, 255, 255]);
+    assert_eq!(pixel_at(&preview, 200, 150), [255, 255, 255, 255]);
+    assert_eq!(pixel_at(&preview, 200, 261), [255, 255, 255, 255]);
+    assert_eq!(pixel_at(&preview, 200, 262), [0, 0, 0, 255]);
+}
+
+#[test]
+fn test_bilinear_sampling_blends_neighbours() {
+    // Left half black, right half white; downscaling blends at the edge
+    let mut frame = solid_frame(4, 1, [0, 0, 0, 255]);
+    frame.data[8..].copy_from_slice(&[255, 255, 255, 255, 255, 255, 255, 255]);
+
+    let scaled = scale_to_fit(&frame, 2, 1).unwrap();
+    assert_eq!(pixel_at(&scaled, 0, 0), [0, 0, 0, 255]);
+    assert_eq!(pixel_at(&scaled, 1, 0), [255, 255, 255, 255]);
+
+    let blended = scale_to_fit(&frame, 3, 1).unwrap();
+    let middle = pixel_at(&blended, 1, 0);
+    assert!(middle[0] > 0 && middle[0] < 255);
+}
+
+#[test]
+fn test_preview_requests_rgba_input() {
+    let node = create_preview_node(640, 360);
+    assert_eq!(node.input_video_format(), Some(VideoFormat::Rgba8));
+}
@@ -0,0 +1,148 @@
+/*
+ * Constellation Studio - Professional Real-time Video Processing
+ * Copyright (c) 2025 MACHIKO LAB
+ *
+ * This program is free software: you can redistribute it and/or modify
+ * it under the terms of the GNU Affero General Public License as published by
+ * the Free Software Foundation, either version 3 of the License, or
+ * (at your option) any later version.
+ *
+ * This program is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+ * GNU Affero General Public License for more details.
+ *
+ * You should have received a copy of the GNU Affero General Public License
+ * along with this program. If not, see <https://www.gnu.org/licenses/>.
+ */
+
+mod common;
+
+use common::{node_config, solid_frame};
+use constellation_core::*;
+use constellation_nodes::{NodeProcessor, RecorderNode};
+use serde_json::Value;
+use std::path::{Path, PathBuf};
+use std::time::Duration;
+use uuid::Uuid;
+
+fn temp_output_dir() -> PathBuf {
+    std::env::temp_dir().join(format!("constellation-recorder-test-{}", Uuid::new_v4()))
+}
+
+fn create_recorder(output_dir: &Path, parameters: &[(&str, Value)]) -> RecorderNode {
+    let mut config = node_config(parameters);
+    config.parameters.insert(
+        "output_directory".to_string(),
+        Value::String(output_dir.to_string_lossy().into_owned()),
+    );
+    RecorderNode::new(Uuid::new_v4(), config).unwrap()
+}
+
+fn frame_data(width: u32, height: u32, value: u8, sequence: u64) -> FrameData {
+    FrameData {
+        timestamp: Duration::from_millis(sequence * 33),
+        sequence,
+        ..FrameData::from_raster(solid_frame(width, height, [value, value, value, 255]))
+    }
+}
+
+fn files_with_extension(dir: &Path, extension: &str) -> Vec<PathBuf> {
+    let mut files = std::fs::read_dir(dir)
+        .unwrap()
+        .map(|entry| entry.unwrap().path())
+        .filter(|path| path.extension().is_some_and(|ext| ext == extension))
+        .collect::<Vec<_>>();
+    files.sort();
+    files
+}
+
+#[test]
+fn test_dump_writes_png_sequence_of_buffered_frames() {
+    let output_dir = temp_output_dir();
+    let mut node = create_recorder(&output_dir, &[]);
+
+    for sequence in 0..5 {
+        node.process(frame_data(8, 4, sequence as u8 * 10, sequence))
+            .unwrap();
+    }
+    node.set_parameter("dump", Value::Bool(true)).unwrap();
+
+    let dump_dir = node.last_dump_path().unwrap().to_path_buf();
+    assert!(dump_dir.starts_with(&output_dir));
+    let files = files_with_extension(&dump_dir, "png");
+    assert_eq!(files.len(), 5);
+    assert!(files[0].ends_with("frame_00000.png"));
+    assert_eq!(&std::fs::read(&files[0]).unwrap()[1..4], b"PNG");
+
+    // The trigger resets so the next control message can fire it again
+    assert_eq!(node.get_parameter("dump"), Some(Value::Bool(false)));
+
+    std::fs::remove_dir_all(&output_dir).unwrap();
+}
+
+#[test]
+fn test_buffer_keeps_only_the_configured_duration() {
+    let output_dir = temp_output_dir();
+    let mut node = create_recorder(
+        &output_dir,
+        &[
+            ("duration", Value::from(0.5)),
+            ("frame_rate", Value::from(10.0)),
+            ("dump_format", Value::String("Raw".to_string())),
+        ],
+    );
+    assert_eq!(node.capacity(), 5);
+
+    for sequence in 0..12 {
+        node.process(frame_data(4, 4, sequence as u8, sequence))
+            .unwrap();
+    }
+    assert_eq!(node.buffered_frames(), 5);
+
+    let dump_dir = node.dump().unwrap();
+    let files = files_with_extension(&dump_dir, "raw");
+    assert_eq!(files.len(), 5);
+
+    // Oldest retained frame is sequence 7
+    let first = std::fs::read(&files[0]).unwrap();
+    assert_eq!(first.len(), 4 * 4 * 4);
+    assert_eq!(first[0], 7);
+    assert!(dump_dir.join("manifest.json").exists());
+
+    std::fs::remove_dir_all(&output_dir).unwrap();
+}
+
+#[test]
+fn test_oversized_frames_are_downscaled_before_buffering() {
+    let output_dir = temp_output_dir();
+    let mut node = create_recorder(
+        &output_dir,
+        &[
+            ("max_width", Value::from(64)),
+            ("max_height", Value::from(64)),
+            ("dump_format", Value::String("Raw".to_string())),
+        ],
+    );
+
+    node.process(frame_data(256, 128, 100, 0)).unwrap();
+    let dump_dir = node.dump().unwrap();
+
+    let files = files_with_extension(&dump_dir, "raw");
+    assert_eq!(files.len(), 1);
+    assert_eq!(std::fs::read(&files[0]).unwrap().len(), 64 * 32 * 4);
+
+    std::fs::remove_dir_all(&output_dir).unwrap();
+}
+
+#[test]
+fn test_dump_without_frames_writes_empty_directory() {
+    let output_dir = temp_output_dir();
+    let mut node = create_recorder(&output_dir, &[]);
+
+    let dump_dir = node.dump().unwrap();
+    assert!(dump_dir.is_dir());
+    assert!(files_with_extension(&dump_dir, "png").is_empty());
+
+    std::fs::remove_dir_all(&output_dir).unwrap();
+}
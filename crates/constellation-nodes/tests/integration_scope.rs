@@ -0,0 +1,137 @@
+/*
+ * Constellation Studio - Professional Real-time Video Processing
+ * Copyright (c) 2025 MACHIKO LAB
+ *
+ * This program is free software: you can redistribute it and/or modify
+ * it under the terms of the GNU Affero General Public License as published by
+ * the Free Software Foundation, either version 3 of the License, or
+ * (at your option) any later version.
+ *
+ * This program is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+ * GNU Affero General Public License for more details.
+ *
+ * You should have received a copy of the GNU Affero General Public License
+ * along with this program. If not, see <https://www.gnu.org/licenses/>.
+ */
+
+use constellation_core::*;
+use constellation_nodes::{compute_histogram, NodeConfig, NodeProcessor, ScopeNode};
+use serde_json::Value;
+use std::collections::HashMap;
+use uuid::Uuid;
+
+fn create_scope_node(bins: u32, waveform_columns: u32) -> ScopeNode {
+    let mut parameters = HashMap::new();
+    parameters.insert("bins".to_string(), Value::from(bins));
+    parameters.insert(
+        "waveform_columns".to_string(),
+        Value::from(waveform_columns),
+    );
+    ScopeNode::new(Uuid::new_v4(), NodeConfig { parameters }).unwrap()
+}
+
+/// Each column x holds `pixel(x)`; every row is identical
+fn horizontal_ramp(
+    width: u32,
+    height: u32,
+    format: VideoFormat,
+    pixel: impl Fn(u32) -> Vec<u8>,
+) -> VideoFrame {
+    let row: Vec<u8> = (0..width).flat_map(&pixel).collect();
+    VideoFrame {
+        width,
+        height,
+        format,
+        data: row.repeat(height as usize),
+    }
+}
+
+fn gray_ramp(height: u32) -> VideoFrame {
+    horizontal_ramp(256, height, VideoFormat::Rgba8, |x| {
+        vec![x as u8, x as u8, x as u8, 255]
+    })
+}
+
+#[test]
+fn test_flat_gradient_fills_bins_uniformly() {
+    let histogram = compute_histogram(&gray_ramp(4), 16, 8).unwrap();
+
+    assert_eq!(histogram.bin_count(), 16);
+    // 256 levels over 16 bins, 4 rows each: 16 * 4 pixels per bin
+    for channel in [
+        &histogram.red,
+        &histogram.green,
+        &histogram.blue,
+        &histogram.luma,
+    ] {
+        assert_eq!(channel, &vec![64; 16]);
+    }
+}
+
+#[test]
+fn test_waveform_tracks_luma_per_column_group() {
+    let histogram = compute_histogram(&gray_ramp(2), 256, 8).unwrap();
+
+    assert_eq!(histogram.waveform.len(), 8);
+    for (index, column) in histogram.waveform.iter().enumerate() {
+        let start = index as u8 * 32;
+        assert_eq!(column.min, start);
+        assert_eq!(column.max, start + 31);
+        assert!((column.mean - (start as f32 + 15.5)).abs() < 1e-3);
+    }
+}
+
+#[test]
+fn test_channels_are_read_in_format_order() {
+    // Pure red ramp stored as BGRA
+    let frame = horizontal_ramp(256, 1, VideoFormat::Bgra8, |x| vec![0, 0, x as u8, 255]);
+    let histogram = compute_histogram(&frame, 4, 1).unwrap();
+
+    assert_eq!(histogram.red, vec![64; 4]);
+    assert_eq!(histogram.green, vec![256, 0, 0, 0]);
+    assert_eq!(histogram.blue, vec![256, 0, 0, 0]);
+    // Red contributes at most 0.2126 * 255 ≈ 54 to luma
+    assert_eq!(histogram.luma[0], 256);
+}
+
+#[test]
+fn test_scope_node_passes_frames_through_and_keeps_histogram() {
+    let mut node = create_scope_node(8, 4);
+    assert!(node.histogram().is_none());
+
+    let output = node.process(FrameData::from_raster(gray_ramp(2))).unwrap();
+    match output.render_data {
+        Some(RenderData::Raster2D(frame)) => {
+            assert_eq!((frame.width, frame.height), (256, 2));
+            assert_eq!(frame.data, gray_ramp(2).data);
+        }
+        _ => panic!("Expected Raster2D output"),
+    }
+
+    let histogram = node.histogram().unwrap();
+    assert_eq!((histogram.width, histogram.height), (256, 2));
+    assert_eq!(histogram.luma, vec![64; 8]);
+    assert_eq!(histogram.waveform.len(), 4);
+
+    // Bin count follows the parameter on the next frame
+    node.set_parameter("bins", Value::from(2)).unwrap();
+    node.process(FrameData::from_raster(gray_ramp(2))).unwrap();
+    assert_eq!(node.histogram().unwrap().luma, vec![256, 256]);
+}
+
+#[test]
+fn test_scope_ignores_unsupported_formats() {
+    let frame = VideoFrame {
+        width: 2,
+        height: 2,
+        format: VideoFormat::Yuv420p,
+        data: vec![0; 6],
+    };
+    assert!(compute_histogram(&frame, 16, 4).is_none());
+
+    let mut node = create_scope_node(16, 4);
+    node.process(FrameData::from_raster(frame)).unwrap();
+    assert!(node.histogram().is_none());
+}
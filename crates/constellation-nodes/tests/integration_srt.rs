@@ -0,0 +1,268 @@
+/*
+ * Constellation Studio - Professional Real-time Video Processing
+ * Copyright (c) 2025 MACHIKO LAB
+ *
+ * This program is free software: you can redistribute it and/or modify
+ * it under the terms of the GNU Affero General Public License as published by
+ * the Free Software Foundation, either version 3 of the License, or
+ * (at your option) any later version.
+ *
+ * This program is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+ * GNU Affero General Public License for more details.
+ *
+ * You should have received a copy of the GNU Affero General Public License
+ * along with this program. If not, see <https://www.gnu.org/licenses/>.
+ */
+
+#![cfg(feature = "srt")]
+
+use anyhow::{bail, Result};
+use constellation_core::*;
+use constellation_nodes::srt::{
+    open_h264_encoder, EncoderSettings, SrtConfig, SrtConnector, SrtOutputNode, SrtSink,
+};
+use constellation_nodes::{NodeConfig, NodeProcessor};
+use serde_json::Value;
+use std::collections::HashMap;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+use uuid::Uuid;
+
+fn parameters(values: &[(&str, Value)]) -> HashMap<String, Value> {
+    values
+        .iter()
+        .map(|(key, value)| (key.to_string(), value.clone()))
+        .collect()
+}
+
+fn frame_data(sequence: u8) -> FrameData {
+    FrameData {
+        render_data: Some(RenderData::Raster2D(VideoFrame {
+            width: 4,
+            height: 2,
+            format: VideoFormat::Rgba8,
+            data: [sequence, 0, 0, 255].repeat(8),
+        })),
+        audio_data: None,
+        control_data: None,
+        tally_metadata: TallyMetadata::new(),
+        timestamp: Duration::ZERO,
+        sequence: sequence as u64,
+        checksum: None,
+    }
+}
+
+/// Records the first byte of every frame; fails once after `fail_after` frames
+struct MockSink {
+    received: Arc<Mutex<Vec<u8>>>,
+    fail_after: Option<usize>,
+}
+
+impl SrtSink for MockSink {
+    fn send_frame(&mut self, frame: &VideoFrame) -> Result<()> {
+        let mut received = self.received.lock().unwrap();
+        if self.fail_after == Some(received.len()) {
+            bail!("peer went away");
+        }
+        received.push(frame.data[0]);
+        Ok(())
+    }
+}
+
+/// Connector refusing the first `refusals` attempts; the first connection
+/// drops after `fail_after` frames
+fn mock_connector(
+    refusals: usize,
+    fail_after: Option<usize>,
+    received: Arc<Mutex<Vec<u8>>>,
+) -> SrtConnector {
+    let attempts = AtomicUsize::new(0);
+    let connections = AtomicUsize::new(0);
+    Arc::new(move |_config, _width, _height| {
+        if attempts.fetch_add(1, Ordering::SeqCst) < refusals {
+            bail!("connection refused");
+        }
+        let first = connections.fetch_add(1, Ordering::SeqCst) == 0;
+        Ok(Box::new(MockSink {
+            received: received.clone(),
+            fail_after: if first { fail_after } else { None },
+        }) as Box<dyn SrtSink>)
+    })
+}
+
+fn srt_node(values: &[(&str, Value)], connector: SrtConnector) -> SrtOutputNode {
+    SrtOutputNode::with_connector(
+        Uuid::new_v4(),
+        NodeConfig {
+            parameters: parameters(values),
+        },
+        connector,
+    )
+    .unwrap()
+}
+
+fn wait_until(condition: impl Fn() -> bool) {
+    let deadline = Instant::now() + Duration::from_secs(10);
+    while !condition() {
+        assert!(
+            Instant::now() < deadline,
+            "timed out waiting for SRT sender"
+        );
+        std::thread::sleep(Duration::from_millis(5));
+    }
+}
+
+#[test]
+fn test_config_parsing_defaults_and_protocol_options() {
+    let config = SrtConfig::from_parameters(&parameters(&[(
+        "url",
+        Value::from("srt://example.com:9000"),
+    )]))
+    .unwrap();
+    assert_eq!(config.latency, Duration::from_millis(120));
+    assert_eq!(config.passphrase, None);
+    assert_eq!((config.bitrate_kbps, config.frame_rate), (6000, 30));
+    assert_eq!(config.buffer_frames, 90);
+    assert_eq!(config.gop(), 60);
+    assert_eq!(
+        config.protocol_options(),
+        vec![("latency", "120000".to_string())]
+    );
+
+    let config = SrtConfig::from_parameters(&parameters(&[
+        ("url", Value::from("srt://:9000?mode=listener")),
+        ("latency_ms", Value::from(500)),
+        ("passphrase", Value::from("correct horse battery")),
+    ]))
+    .unwrap();
+    assert_eq!(
+        config.protocol_options(),
+        vec![
+            ("latency", "500000".to_string()),
+            ("passphrase", "correct horse battery".to_string()),
+            ("pbkeylen", "16".to_string()),
+        ]
+    );
+}
+
+#[test]
+fn test_config_parsing_rejects_invalid_settings() {
+    let invalid: &[&[(&str, Value)]] = &[
+        &[],
+        &[("url", Value::from("udp://example.com:9000"))],
+        &[("url", Value::from("srt://example.com"))],
+        &[("url", Value::from("srt://example.com:0"))],
+        &[("url", Value::from("srt://example.com:port"))],
+        &[
+            ("url", Value::from("srt://example.com:9000")),
+            ("passphrase", Value::from("short")),
+        ],
+        &[
+            ("url", Value::from("srt://example.com:9000")),
+            ("latency_ms", Value::from(5)),
+        ],
+        &[
+            ("url", Value::from("srt://example.com:9000")),
+            ("bitrate_kbps", Value::from("fast")),
+        ],
+    ];
+    for values in invalid {
+        assert!(
+            SrtConfig::from_parameters(&parameters(values)).is_err(),
+            "accepted {:?}",
+            values
+        );
+    }
+}
+
+#[test]
+fn test_encoder_setup() {
+    let config = SrtConfig::from_parameters(&parameters(&[
+        ("url", Value::from("srt://127.0.0.1:9000")),
+        ("bitrate_kbps", Value::from(2500)),
+        ("frame_rate", Value::from(25)),
+    ]))
+    .unwrap();
+
+    // Odd dimensions are rounded down for 4:2:0
+    let settings = EncoderSettings::new(&config, 641, 361);
+    assert_eq!(
+        settings,
+        EncoderSettings {
+            width: 640,
+            height: 360,
+            frame_rate: 25,
+            bitrate_kbps: 2500,
+            gop: 50,
+        }
+    );
+
+    match open_h264_encoder(&settings, false) {
+        Ok(encoder) => {
+            assert_eq!((encoder.width(), encoder.height()), (640, 360));
+        }
+        Err(e) if e.to_string().contains("without an H.264 encoder") => {
+            println!("Skipping encoder check: {}", e);
+        }
+        Err(e) => panic!("Failed to open H.264 encoder: {:#}", e),
+    }
+}
+
+#[test]
+fn test_reconnects_and_resends_after_connection_loss() {
+    let received = Arc::new(Mutex::new(Vec::new()));
+    let mut node = srt_node(
+        &[("url", Value::from("srt://127.0.0.1:9000"))],
+        mock_connector(1, Some(2), received.clone()),
+    );
+
+    for sequence in 0..6 {
+        let output = node.process(frame_data(sequence)).unwrap();
+        assert_eq!(output.sequence, sequence as u64);
+    }
+
+    wait_until(|| received.lock().unwrap().len() == 6);
+    assert_eq!(*received.lock().unwrap(), vec![0, 1, 2, 3, 4, 5]);
+    assert!(node.is_connected());
+    assert_eq!(node.frames_sent(), 6);
+    assert_eq!(node.connection_losses(), 1);
+    assert_eq!(node.dropped_frames(), 0);
+
+    node.on_stop().unwrap();
+    assert!(!node.is_connected());
+}
+
+#[test]
+fn test_buffer_drops_oldest_frames_while_disconnected() {
+    let received = Arc::new(Mutex::new(Vec::new()));
+    let mut node = srt_node(
+        &[
+            ("url", Value::from("srt://127.0.0.1:9000")),
+            ("buffer_frames", Value::from(3)),
+        ],
+        mock_connector(usize::MAX, None, received.clone()),
+    );
+
+    for sequence in 0..10 {
+        node.process(frame_data(sequence)).unwrap();
+    }
+    node.on_stop().unwrap();
+
+    // Only the newest three frames were still held when the node stopped
+    assert_eq!(node.dropped_frames(), 7);
+    assert_eq!(node.frames_sent(), 0);
+    assert!(received.lock().unwrap().is_empty());
+}
+
+#[test]
+fn test_invalid_configuration_fails_processing() {
+    let mut node = srt_node(
+        &[("url", Value::from("srt://example.com"))],
+        mock_connector(0, None, Arc::new(Mutex::new(Vec::new()))),
+    );
+    assert!(node.process(frame_data(0)).is_err());
+    assert!(node.on_start().is_err());
+}
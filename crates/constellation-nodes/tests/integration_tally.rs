@@ -0,0 +1,207 @@
+/*
+ * Constellation Studio - Professional Real-time Video Processing
+ * Copyright (c) 2025 MACHIKO LAB
+ *
+ * This program is free software: you can redistribute it and/or modify
+ * it under the terms of the GNU Affero General Public License as published by
+ * the Free Software Foundation, either version 3 of the License, or
+ * (at your option) any later version.
+ *
+ * This program is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+ * GNU Affero General Public License for more details.
+ *
+ * You should have received a copy of the GNU Affero General Public License
+ * along with this program. If not, see <https://www.gnu.org/licenses/>.
+ */
+
+use constellation_core::*;
+use constellation_nodes::{
+    NodeConfig, NodeProcessor, TallyLogicNode, TallyLogicOperation, TallyRouterNode,
+};
+use serde_json::Value;
+use std::collections::HashMap;
+use uuid::Uuid;
+
+fn create_logic_node(operation: &str) -> TallyLogicNode {
+    let mut parameters = HashMap::new();
+    parameters.insert(
+        "operation".to_string(),
+        Value::String(operation.to_string()),
+    );
+    TallyLogicNode::new(Uuid::new_v4(), NodeConfig { parameters }).unwrap()
+}
+
+fn source_tally(source: Uuid, program: bool, preview: bool) -> TallyMetadata {
+    let mut metadata = TallyMetadata::new()
+        .with_program_tally(program)
+        .with_preview_tally(preview);
+    metadata.propagation_source = Some(source);
+    metadata
+}
+
+/// Feed one tally state per source and return the combined result
+fn combine(node: &mut TallyLogicNode, states: &[(bool, bool)]) -> TallyMetadata {
+    node.clear_sources();
+    for &(program, preview) in states {
+        node.process_tally_metadata(&source_tally(Uuid::new_v4(), program, preview));
+    }
+    node.generate_tally_state()
+}
+
+#[test]
+fn test_tally_logic_truth_tables() {
+    let inputs = [(false, false), (false, true), (true, false), (true, true)];
+    let cases = [
+        ("AND", [false, false, false, true]),
+        ("OR", [false, true, true, true]),
+        ("XOR", [false, true, true, false]),
+        ("NOT", [true, false, false, false]),
+    ];
+
+    for (operation, expected) in cases {
+        let mut node = create_logic_node(operation);
+        for ((a, b), expected) in inputs.iter().zip(expected) {
+            // program: a ⊙ b, preview: b ⊙ a for the same pair
+            let result = combine(&mut node, &[(*a, *b), (*b, *a)]);
+            assert_eq!(
+                result.program_tally, expected,
+                "{} program for ({}, {})",
+                operation, a, b
+            );
+            assert_eq!(
+                result.preview_tally, expected,
+                "{} preview for ({}, {})",
+                operation, a, b
+            );
+        }
+    }
+}
+
+#[test]
+fn test_tally_logic_program_and_preview_are_independent() {
+    let mut node = create_logic_node("AND");
+    let result = combine(&mut node, &[(true, true), (true, false), (true, true)]);
+    assert!(result.program_tally);
+    assert!(!result.preview_tally);
+}
+
+#[test]
+fn test_tally_logic_latest_state_per_source_wins() {
+    let mut node = create_logic_node("AND");
+    let (camera_a, camera_b) = (Uuid::new_v4(), Uuid::new_v4());
+
+    node.process_tally_metadata(&source_tally(camera_a, true, false));
+    let result = node.process_tally_metadata(&source_tally(camera_b, true, false));
+    assert!(result.program_tally);
+    assert!(result.has_visited(node.get_properties().id));
+
+    // Camera A drops out of program
+    node.process_tally_metadata(&source_tally(camera_a, false, false));
+    assert!(!node.generate_tally_state().program_tally);
+}
+
+#[test]
+fn test_tally_logic_watched_custom_keys() {
+    let mut node = create_logic_node("XOR");
+    node.set_parameter("watched_keys", Value::String("iso, record".to_string()))
+        .unwrap();
+
+    for (iso, record) in [(true, true), (false, true)] {
+        let mut metadata = TallyMetadata::new();
+        metadata.propagation_source = Some(Uuid::new_v4());
+        metadata.custom_tally.insert("iso".to_string(), iso);
+        metadata.custom_tally.insert("record".to_string(), record);
+        metadata.custom_tally.insert("ignored".to_string(), true);
+        node.process_tally_metadata(&metadata);
+    }
+
+    let result = node.generate_tally_state();
+    assert_eq!(result.custom_tally.get("iso"), Some(&true));
+    assert_eq!(result.custom_tally.get("record"), Some(&false));
+    assert!(!result.custom_tally.contains_key("ignored"));
+}
+
+#[test]
+fn test_tally_logic_without_sources() {
+    assert!(!TallyLogicOperation::And.evaluate(&[]));
+    assert!(!TallyLogicOperation::Or.evaluate(&[]));
+    assert!(!TallyLogicOperation::Xor.evaluate(&[]));
+    assert!(TallyLogicOperation::Not.evaluate(&[]));
+
+    let node = create_logic_node("unknown");
+    assert_eq!(node.operation(), TallyLogicOperation::Or);
+    assert!(!node.generate_tally_state().program_tally);
+}
+
+fn create_router_node(routes: Value) -> TallyRouterNode {
+    let mut parameters = HashMap::new();
+    parameters.insert("routes".to_string(), routes);
+    TallyRouterNode::new(Uuid::new_v4(), NodeConfig { parameters }).unwrap()
+}
+
+#[test]
+fn test_tally_router_renames_signals() {
+    let mut router = create_router_node(Value::String(
+        "program=gpio_1, preview=gpio_2, iso=gpio_3".to_string(),
+    ));
+
+    let mut metadata = TallyMetadata::new().with_program_tally(true);
+    metadata.custom_tally.insert("iso".to_string(), true);
+    let result = router.process_tally_metadata(&metadata);
+
+    assert_eq!(result.custom_tally.get("gpio_1"), Some(&true));
+    assert_eq!(result.custom_tally.get("gpio_2"), Some(&false));
+    assert_eq!(result.custom_tally.get("gpio_3"), Some(&true));
+    assert!(!result.custom_tally.contains_key("iso"));
+    assert_eq!(result.custom_tally.len(), 3);
+    assert_eq!(
+        router.generate_tally_state().custom_tally,
+        result.custom_tally
+    );
+}
+
+#[test]
+fn test_tally_router_duplicates_one_source_to_many_destinations() {
+    let routes = serde_json::json!({
+        "program": ["gpio_1", "stage_light"],
+        "preview": "gpio_2",
+    });
+    let mut router = create_router_node(routes);
+
+    router.process_tally_metadata(&TallyMetadata::new().with_program_tally(true));
+    let result = router.generate_tally_state();
+    assert_eq!(result.custom_tally.get("gpio_1"), Some(&true));
+    assert_eq!(result.custom_tally.get("stage_light"), Some(&true));
+    assert_eq!(result.custom_tally.get("gpio_2"), Some(&false));
+
+    // The same duplication expressed as a route string
+    let mut router = create_router_node(Value::String(
+        "program=gpio_1,program=stage_light".to_string(),
+    ));
+    router.process_tally_metadata(&TallyMetadata::new().with_program_tally(true));
+    let result = router.generate_tally_state();
+    assert_eq!(result.custom_tally.get("gpio_1"), Some(&true));
+    assert_eq!(result.custom_tally.get("stage_light"), Some(&true));
+}
+
+#[test]
+fn test_tally_router_merges_sources_sharing_a_destination() {
+    let mut router = create_router_node(Value::String(
+        "program=red_light, preview=red_light, malformed, =empty".to_string(),
+    ));
+    assert_eq!(router.routes().len(), 2);
+
+    router.process_tally_metadata(&TallyMetadata::new().with_preview_tally(true));
+    assert_eq!(
+        router.generate_tally_state().custom_tally.get("red_light"),
+        Some(&true)
+    );
+
+    router.process_tally_metadata(&TallyMetadata::new());
+    assert_eq!(
+        router.generate_tally_state().custom_tally.get("red_light"),
+        Some(&false)
+    );
+}
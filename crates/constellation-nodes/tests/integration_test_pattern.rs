@@ -0,0 +1,147 @@
+/*
+ * Constellation Studio - Professional Real-time Video Processing
+ * Copyright (c) 2025 MACHIKO LAB
+ *
+ * This program is free software: you can redistribute it and/or modify
+ * it under the terms of the GNU Affero General Public License as published by
+ * the Free Software Foundation, either version 3 of the License, or
+ * (at your option) any later version.
+ *
+ * This program is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+ * GNU Affero General Public License for more details.
+ *
+ * You should have received a copy of the GNU Affero General Public License
+ * along with this program. If not, see <https://www.gnu.org/licenses/>.
+ */
+
+mod common;
+
+use common::{node_config, pixel_at, raster};
+use constellation_core::*;
+use constellation_nodes::{NodeProcessor, TestPatternNode};
+use serde_json::Value;
+use uuid::Uuid;
+
+fn create_pattern_node(pattern: &str, width: u32, height: u32) -> TestPatternNode {
+    TestPatternNode::new(
+        Uuid::new_v4(),
+        node_config(&[
+            ("pattern_type", Value::from(pattern)),
+            ("width", Value::from(width)),
+            ("height", Value::from(height)),
+        ]),
+    )
+    .unwrap()
+}
+
+fn render(node: &mut TestPatternNode) -> VideoFrame {
+    raster(node.process(FrameData::empty()).unwrap())
+}
+
+#[test]
+fn test_smpte_bars_at_known_columns() {
+    let mut node = create_pattern_node("Color Bars", 700, 120);
+    let frame = render(&mut node);
+
+    // Each of the seven top bars is 100px wide; sample the centre of each
+    let expected_top = [
+        [191, 191, 191, 255],
+        [191, 191, 0, 255],
+        [0, 191, 191, 255],
+        [0, 191, 0, 255],
+        [191, 0, 191, 255],
+        [191, 0, 0, 255],
+        [0, 0, 191, 255],
+    ];
+    for (bar, expected) in expected_top.iter().enumerate() {
+        assert_eq!(pixel_at(&frame, bar as u32 * 100 + 50, 10), *expected);
+    }
+
+    // Castellations run in reverse order below the main bars
+    assert_eq!(pixel_at(&frame, 50, 85), [0, 0, 191, 255]);
+    assert_eq!(pixel_at(&frame, 150, 85), [19, 19, 19, 255]);
+    assert_eq!(pixel_at(&frame, 650, 85), [191, 191, 191, 255]);
+
+    // Bottom row: -I, white, +Q at 125px each, then black and PLUGE
+    assert_eq!(pixel_at(&frame, 60, 110), [0, 33, 76, 255]);
+    assert_eq!(pixel_at(&frame, 180, 110), [255, 255, 255, 255]);
+    assert_eq!(pixel_at(&frame, 310, 110), [50, 0, 106, 255]);
+    assert_eq!(pixel_at(&frame, 450, 110), [19, 19, 19, 255]);
+    assert_eq!(pixel_at(&frame, 515, 110), [9, 9, 9, 255]);
+    assert_eq!(pixel_at(&frame, 550, 110), [19, 19, 19, 255]);
+    assert_eq!(pixel_at(&frame, 585, 110), [29, 29, 29, 255]);
+    assert_eq!(pixel_at(&frame, 650, 110), [19, 19, 19, 255]);
+}
+
+#[test]
+fn test_pattern_honours_dimension_parameters() {
+    for pattern in [
+        "Color Bars",
+        "Grayscale Ramp",
+        "Checkerboard",
+        "Moving Gradient",
+        "Solid Color",
+        "Noise",
+    ] {
+        let mut node = create_pattern_node(pattern, 320, 240);
+        let frame = render(&mut node);
+        assert_eq!((frame.width, frame.height), (320, 240), "{pattern}");
+        assert_eq!(frame.data.len(), 320 * 240 * 4, "{pattern}");
+    }
+}
+
+#[test]
+fn test_default_dimensions_are_full_hd() {
+    let mut node = TestPatternNode::new(Uuid::new_v4(), node_config(&[])).unwrap();
+    let frame = render(&mut node);
+    assert_eq!((frame.width, frame.height), (1920, 1080));
+}
+
+#[test]
+fn test_grayscale_ramp_spans_black_to_white() {
+    let mut node = create_pattern_node("Grayscale Ramp", 256, 16);
+    let frame = render(&mut node);
+
+    assert_eq!(pixel_at(&frame, 0, 0), [0, 0, 0, 255]);
+    assert_eq!(pixel_at(&frame, 255, 15), [255, 255, 255, 255]);
+    let middle = pixel_at(&frame, 128, 8)[0];
+    assert!((127..=129).contains(&middle));
+}
+
+#[test]
+fn test_checkerboard_alternates_squares() {
+    let mut node = TestPatternNode::new(
+        Uuid::new_v4(),
+        node_config(&[
+            ("pattern_type", Value::from("Checkerboard")),
+            ("width", Value::from(64)),
+            ("height", Value::from(64)),
+            ("checker_size", Value::from(16)),
+        ]),
+    )
+    .unwrap();
+    let frame = render(&mut node);
+
+    assert_eq!(pixel_at(&frame, 0, 0), [255, 255, 255, 255]);
+    assert_eq!(pixel_at(&frame, 16, 0), [0, 0, 0, 255]);
+    assert_eq!(pixel_at(&frame, 0, 16), [0, 0, 0, 255]);
+    assert_eq!(pixel_at(&frame, 16, 16), [255, 255, 255, 255]);
+}
+
+#[test]
+fn test_moving_gradient_is_deterministic_per_frame() {
+    let mut first = create_pattern_node("Moving Gradient", 128, 32);
+    let mut second = create_pattern_node("Moving Gradient", 128, 32);
+
+    let a0 = render(&mut first);
+    let b0 = render(&mut second);
+    assert_eq!(a0.data, b0.data);
+
+    // The gradient scrolls between frames, identically for both nodes
+    let a1 = render(&mut first);
+    let b1 = render(&mut second);
+    assert_ne!(a0.data, a1.data);
+    assert_eq!(a1.data, b1.data);
+}
@@ -0,0 +1,177 @@
+/*
+ * Constellation Studio - Professional Real-time Video Processing
+ * Copyright (c) 2025 MACHIKO LAB
+ *
+ * This program is free software: you can redistribute it and/or modify
+ * it under the terms of the GNU Affero General Public License as published by
+ * the Free Software Foundation, either version 3 of the License, or
+ * (at your option) any later version.
+ *
+ * This program is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+ * GNU Affero General Public License for more details.
+ *
+ * You should have received a copy of the GNU Affero General Public License
+ * along with this program. If not, see <https://www.gnu.org/licenses/>.
+ */
+
+mod common;
+
+use common::{node_config, pixel_at, raster, solid_frame};
+use constellation_core::*;
+use constellation_nodes::{NodeProcessor, TransitionNode};
+use serde_json::Value;
+use uuid::Uuid;
+
+const RED: [u8; 4] = [200, 0, 0, 255];
+const BLUE: [u8; 4] = [0, 0, 100, 255];
+
+fn create_transition(parameters: &[(&str, Value)]) -> TransitionNode {
+    TransitionNode::new(Uuid::new_v4(), node_config(parameters)).unwrap()
+}
+
+/// Run a 4x4 red (A) to blue (B) transition at `progress`
+fn transition_at(parameters: &[(&str, Value)], progress: f64) -> VideoFrame {
+    let mut node = create_transition(parameters);
+    node.set_parameter("progress", Value::from(progress))
+        .unwrap();
+    let output = node
+        .process_multi(vec![
+            (
+                Uuid::new_v4(),
+                FrameData::from_raster(solid_frame(4, 4, RED)),
+            ),
+            (
+                Uuid::new_v4(),
+                FrameData::from_raster(solid_frame(4, 4, BLUE)),
+            ),
+        ])
+        .unwrap();
+    raster(output)
+}
+
+fn assert_solid(frame: &VideoFrame, color: [u8; 4]) {
+    for pixel in frame.data.chunks_exact(4) {
+        assert_eq!(pixel, color);
+    }
+}
+
+fn wipe(direction: &str) -> Vec<(&'static str, Value)> {
+    vec![
+        ("mode", Value::from("Wipe")),
+        ("wipe_direction", Value::from(direction.to_string())),
+    ]
+}
+
+#[test]
+fn test_crossfade_blends_inputs() {
+    let crossfade = [("mode", Value::from("Crossfade"))];
+
+    assert_solid(&transition_at(&crossfade, 0.0), RED);
+    assert_solid(&transition_at(&crossfade, 0.5), [100, 0, 50, 255]);
+    assert_solid(&transition_at(&crossfade, 1.0), BLUE);
+}
+
+#[test]
+fn test_wipe_reveals_b_from_the_chosen_edge() {
+    let left_to_right = wipe("Left to Right");
+    assert_solid(&transition_at(&left_to_right, 0.0), RED);
+    assert_solid(&transition_at(&left_to_right, 1.0), BLUE);
+
+    let half = transition_at(&left_to_right, 0.5);
+    for y in 0..4 {
+        assert_eq!(pixel_at(&half, 0, y), BLUE);
+        assert_eq!(pixel_at(&half, 1, y), BLUE);
+        assert_eq!(pixel_at(&half, 2, y), RED);
+        assert_eq!(pixel_at(&half, 3, y), RED);
+    }
+
+    let half = transition_at(&wipe("Right to Left"), 0.5);
+    assert_eq!(pixel_at(&half, 0, 0), RED);
+    assert_eq!(pixel_at(&half, 3, 0), BLUE);
+
+    let half = transition_at(&wipe("Top to Bottom"), 0.5);
+    assert_eq!(pixel_at(&half, 0, 1), BLUE);
+    assert_eq!(pixel_at(&half, 0, 2), RED);
+
+    let half = transition_at(&wipe("Bottom to Top"), 0.5);
+    assert_eq!(pixel_at(&half, 0, 1), RED);
+    assert_eq!(pixel_at(&half, 0, 2), BLUE);
+}
+
+#[test]
+fn test_dip_to_color_passes_through_the_dip_color() {
+    let dip_to_white = [
+        ("mode", Value::from("Dip to Color")),
+        (
+            "dip_color",
+            Value::Array(vec![Value::from(1.0), Value::from(1.0), Value::from(1.0)]),
+        ),
+    ];
+
+    assert_solid(&transition_at(&dip_to_white, 0.0), RED);
+    assert_solid(&transition_at(&dip_to_white, 0.5), [255, 255, 255, 255]);
+    assert_solid(&transition_at(&dip_to_white, 1.0), BLUE);
+
+    // Halfway into the dip A is mixed with the color, B is not visible yet
+    assert_solid(&transition_at(&dip_to_white, 0.25), [228, 128, 128, 255]);
+
+    // Black is the default dip color
+    assert_solid(
+        &transition_at(&[("mode", Value::from("Dip to Color"))], 0.5),
+        [0, 0, 0, 255],
+    );
+}
+
+#[test]
+fn test_b_is_resized_to_a_and_tally_is_merged() {
+    let mut node = create_transition(&[("progress", Value::from(0.5))]);
+    let mut a = FrameData::from_raster(solid_frame(4, 4, RED));
+    let mut b = FrameData::from_raster(solid_frame(2, 2, BLUE));
+    a.tally_metadata.program_tally = true;
+    b.tally_metadata.preview_tally = true;
+
+    let output = node
+        .process_multi(vec![(Uuid::new_v4(), a), (Uuid::new_v4(), b)])
+        .unwrap();
+
+    assert!(output.tally_metadata.program_tally);
+    assert!(output.tally_metadata.preview_tally);
+    match output.render_data {
+        Some(RenderData::Raster2D(frame)) => {
+            assert_eq!((frame.width, frame.height), (4, 4));
+            assert_solid(&frame, [100, 0, 50, 255]);
+        }
+        _ => panic!("Expected Raster2D output"),
+    }
+}
+
+#[test]
+fn test_single_input_passes_through_and_unknown_mode_is_rejected() {
+    let mut node = create_transition(&[("progress", Value::from(1.0))]);
+    let output = node
+        .process(FrameData::from_raster(solid_frame(2, 2, RED)))
+        .unwrap();
+    match output.render_data {
+        Some(RenderData::Raster2D(frame)) => assert_solid(&frame, RED),
+        _ => panic!("Expected Raster2D output"),
+    }
+
+    let mut node = create_transition(&[
+        ("mode", Value::from("Spin")),
+        ("progress", Value::from(0.5)),
+    ]);
+    assert!(node
+        .process_multi(vec![
+            (
+                Uuid::new_v4(),
+                FrameData::from_raster(solid_frame(2, 2, RED))
+            ),
+            (
+                Uuid::new_v4(),
+                FrameData::from_raster(solid_frame(2, 2, BLUE))
+            ),
+        ])
+        .is_err());
+}
@@ -117,6 +117,9 @@ fn test_video_file_input_node_without_file_path() {
         audio_data: None,
         control_data: None,
         tally_metadata: TallyMetadata::new(),
+        timestamp: Duration::ZERO,
+        sequence: 0,
+        checksum: None,
     };
 
     // Should return fallback frame when no file path is set
@@ -174,6 +177,9 @@ fn test_video_file_input_node_with_valid_file() {
         audio_data: None,
         control_data: None,
         tally_metadata: TallyMetadata::new(),
+        timestamp: Duration::ZERO,
+        sequence: 0,
+        checksum: None,
     };
 
     let result = node.process(input_frame);
@@ -239,6 +245,9 @@ fn test_video_file_input_node_with_loop_enabled() {
         audio_data: None,
         control_data: None,
         tally_metadata: TallyMetadata::new(),
+        timestamp: Duration::ZERO,
+        sequence: 0,
+        checksum: None,
     };
 
     let result = node.process(input_frame);
@@ -295,6 +304,7 @@ fn test_video_file_input_node_parameter_updates() {
 }
 
 #[test]
+#[cfg(not(feature = "ffmpeg"))] // Dummy files are not decodable
 fn test_video_file_reader_creation_and_lifecycle() {
     let test_file = create_test_video_file("test_reader_lifecycle", "mp4");
 
@@ -348,6 +358,7 @@ fn test_video_file_reader_creation_and_lifecycle() {
 }
 
 #[test]
+#[cfg(not(feature = "ffmpeg"))] // Dummy files are not decodable
 fn test_video_file_reader_seeking() {
     let test_file = create_test_video_file("test_reader_seeking", "mp4");
 
@@ -373,6 +384,7 @@ fn test_video_file_reader_seeking() {
 }
 
 #[test]
+#[cfg(not(feature = "ffmpeg"))] // Dummy files are not decodable
 fn test_video_file_reader_loop_playback() {
     let test_file = create_test_video_file("test_reader_loop", "mp4");
 
@@ -402,6 +414,7 @@ fn test_video_file_reader_loop_playback() {
 }
 
 #[test]
+#[cfg(not(feature = "ffmpeg"))] // Dummy files are not decodable
 fn test_video_file_reader_different_formats() {
     // Test MP4 format
     let mp4_file = create_test_video_file("test_format_mp4", "mp4");
@@ -463,6 +476,9 @@ fn test_video_file_input_node_parameter_reset_behavior() {
         audio_data: None,
         control_data: None,
         tally_metadata: TallyMetadata::new(),
+        timestamp: Duration::ZERO,
+        sequence: 0,
+        checksum: None,
     };
 
     // Process with first file (MP4)
@@ -496,3 +512,29 @@ fn test_video_file_input_node_parameter_reset_behavior() {
     let _ = std::fs::remove_file(&test_file1);
     let _ = std::fs::remove_file(&test_file2);
 }
+
+#[test]
+#[cfg(feature = "ffmpeg")]
+fn test_video_file_reader_decodes_bundled_sample() {
+    // 16x8 @ 25fps, 5 frames of YUV4MPEG2 with increasing luma
+    let sample = PathBuf::from(env!("CARGO_MANIFEST_DIR")).join("tests/data/sample_16x8.y4m");
+
+    let mut reader = VideoFileReader::new(&sample).unwrap();
+    reader.open().unwrap();
+
+    let metadata = reader.get_metadata();
+    assert_eq!(metadata.width, 16);
+    assert_eq!(metadata.height, 8);
+    assert!((metadata.fps - 25.0).abs() < 0.01);
+
+    let (first, _) = reader.read_frame().unwrap();
+    assert_eq!(first.width, metadata.width);
+    assert_eq!(first.height, metadata.height);
+    assert_eq!(first.format, VideoFormat::Rgba8);
+    assert_eq!(first.data.len(), (16 * 8 * 4) as usize);
+
+    // Frames get brighter, so a later frame decodes to a brighter image
+    reader.seek_to_frame(4).unwrap();
+    let (last, _) = reader.read_frame().unwrap();
+    assert!(last.data[0] > first.data[0]);
+}
@@ -22,6 +22,7 @@ use constellation_core::*;
 use constellation_nodes::virtual_camera::{VideoFormat, VirtualWebcamBackend, VirtualWebcamConfig};
 use constellation_nodes::*;
 use std::collections::HashMap;
+use std::time::Duration;
 use uuid::Uuid;
 
 #[cfg(target_os = "linux")]
@@ -158,6 +159,9 @@ fn test_virtual_webcam_frame_processing() -> Result<()> {
         audio_data: None,
         control_data: None,
         tally_metadata: TallyMetadata::new(),
+        timestamp: Duration::ZERO,
+        sequence: 0,
+        checksum: None,
     };
 
     // Process frame - should not fail even if virtual webcam can't actually start
@@ -0,0 +1,235 @@
+/*
+ * Constellation Studio - Professional Real-time Video Processing
+ * Copyright (c) 2025 MACHIKO LAB
+ *
+ * This program is free software: you can redistribute it and/or modify
+ * it under the terms of the GNU Affero General Public License as published by
+ * the Free Software Foundation, either version 3 of the License, or
+ * (at your option) any later version.
+ *
+ * This program is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+ * GNU Affero General Public License for more details.
+ *
+ * You should have received a copy of the GNU Affero General Public License
+ * along with this program. If not, see <https://www.gnu.org/licenses/>.
+ */
+
+//! フレームスループットのベンチマーク
+//!
+//! テストパターン → 色補正 → ブラー → プレビューのグラフを `PipelineProcessor` で回し、
+//! 解像度ごとのフレームレートとノードごとの平均処理時間を表示する。
+//! ノードはCPU実装で動くため、Vulkanのない環境でも実行できる。
+//!
+//! ```text
+//! cargo bench -p constellation-pipeline --bench throughput
+//! cargo bench -p constellation-pipeline --bench throughput -- 1080p --frames 300 --min-fps 30
+//! ```
+//!
+//! `--min-fps` を下回るケースがあれば終了コード1で終わるので、回帰検出に使える。
+
+use anyhow::{bail, Context, Result};
+use constellation_core::*;
+use constellation_nodes::create_node_processor;
+use constellation_pipeline::PipelineProcessor;
+use serde_json::Value;
+use std::collections::HashMap;
+use std::time::{Duration, Instant};
+use uuid::Uuid;
+
+struct BenchCase {
+    name: &'static str,
+    width: u32,
+    height: u32,
+}
+
+const CASES: &[BenchCase] = &[
+    BenchCase {
+        name: "1080p",
+        width: 1920,
+        height: 1080,
+    },
+    BenchCase {
+        name: "4k",
+        width: 3840,
+        height: 2160,
+    },
+];
+
+struct Options {
+    frames: u32,
+    warmup: u32,
+    min_fps: Option<f64>,
+    filter: Option<String>,
+}
+
+impl Options {
+    fn parse() -> Result<Self> {
+        let mut options = Options {
+            frames: 120,
+            warmup: 10,
+            min_fps: None,
+            filter: None,
+        };
+
+        let mut args = std::env::args().skip(1);
+        while let Some(arg) = args.next() {
+            let mut value = |name: &str| {
+                args.next()
+                    .with_context(|| format!("{name} requires a value"))
+            };
+            match arg.as_str() {
+                "--frames" => options.frames = value("--frames")?.parse()?,
+                "--warmup" => options.warmup = value("--warmup")?.parse()?,
+                "--min-fps" => options.min_fps = Some(value("--min-fps")?.parse()?),
+                // cargo bench が渡すフラグ
+                "--bench" => {}
+                other if other.starts_with("--") => bail!("Unknown option: {other}"),
+                other => options.filter = Some(other.to_string()),
+            }
+        }
+
+        if options.frames == 0 {
+            bail!("--frames must be at least 1");
+        }
+        Ok(options)
+    }
+}
+
+/// 計測対象のグラフを組み立て、実行順のノード名とIDを返す
+fn build_pipeline(case: &BenchCase) -> Result<(PipelineProcessor, Vec<(&'static str, Uuid)>)> {
+    let chain: [(&'static str, NodeType, Vec<(&str, Value)>); 4] = [
+        (
+            "test_pattern",
+            NodeType::Input(InputType::TestPattern),
+            vec![
+                ("width", Value::from(case.width)),
+                ("height", Value::from(case.height)),
+            ],
+        ),
+        (
+            "color_correction",
+            NodeType::Effect(EffectType::ColorCorrection),
+            vec![
+                ("brightness", Value::from(0.1)),
+                ("contrast", Value::from(1.2)),
+                ("saturation", Value::from(0.9)),
+            ],
+        ),
+        (
+            "blur",
+            NodeType::Effect(EffectType::Blur),
+            vec![("radius", Value::from(2.0))],
+        ),
+        ("preview", NodeType::Output(OutputType::Preview), vec![]),
+    ];
+
+    let mut graph = NodeGraph::new();
+    let mut pipeline = PipelineProcessor::new();
+    let mut nodes = Vec::new();
+
+    for (name, node_type, parameters) in chain {
+        let id = Uuid::new_v4();
+        let config = NodeConfig {
+            parameters: parameters
+                .into_iter()
+                .map(|(key, value)| (key.to_string(), value))
+                .collect::<HashMap<_, _>>(),
+        };
+        graph.add_node(Node::new(id, node_type.clone(), config.clone()));
+        pipeline.add_node(id, create_node_processor(node_type, id, config)?);
+
+        if let Some(&(_, previous)) = nodes.last() {
+            graph.connect_nodes(previous, id, ConnectionType::RenderData)?;
+        }
+        nodes.push((name, id));
+    }
+
+    pipeline.set_graph(&graph)?;
+    Ok((pipeline, nodes))
+}
+
+fn empty_frame(sequence: u64) -> FrameData {
+    FrameData {
+        render_data: None,
+        audio_data: None,
+        control_data: None,
+        tally_metadata: TallyMetadata::new(),
+        timestamp: Duration::ZERO,
+        sequence,
+        checksum: None,
+    }
+}
+
+/// 1ケース分を計測し、フレームレートを返す
+fn run_case(case: &BenchCase, options: &Options) -> Result<f64> {
+    let (mut pipeline, nodes) = build_pipeline(case)?;
+
+    for sequence in 0..options.warmup {
+        pipeline.process_frame(empty_frame(sequence as u64))?;
+    }
+
+    let mut node_totals: HashMap<Uuid, Duration> = HashMap::new();
+    let started = Instant::now();
+    for sequence in 0..options.frames {
+        let output = pipeline.process_frame(empty_frame(sequence as u64))?;
+        std::hint::black_box(output);
+        for (id, timing) in pipeline.node_metrics() {
+            *node_totals.entry(*id).or_default() += timing.last();
+        }
+    }
+    let elapsed = started.elapsed();
+    let fps = options.frames as f64 / elapsed.as_secs_f64();
+
+    println!(
+        "{:<6} {}x{}: {:.1} fps ({:.2} ms/frame over {} frames)",
+        case.name,
+        case.width,
+        case.height,
+        fps,
+        elapsed.as_secs_f64() * 1000.0 / options.frames as f64,
+        options.frames
+    );
+    for (name, id) in &nodes {
+        let total = node_totals.get(id).copied().unwrap_or_default();
+        println!(
+            "    {:<18} {:>8.3} ms",
+            name,
+            total.as_secs_f64() * 1000.0 / options.frames as f64
+        );
+    }
+
+    Ok(fps)
+}
+
+fn main() -> Result<()> {
+    let options = Options::parse()?;
+
+    let mut regressions = Vec::new();
+    for case in CASES {
+        if let Some(filter) = &options.filter {
+            if !case.name.contains(filter.as_str()) {
+                continue;
+            }
+        }
+
+        let fps = run_case(case, &options)?;
+        if let Some(min_fps) = options.min_fps {
+            if fps < min_fps {
+                regressions.push(format!(
+                    "{}: {:.1} fps < {:.1} fps",
+                    case.name, fps, min_fps
+                ));
+            }
+        }
+    }
+
+    if !regressions.is_empty() {
+        for regression in &regressions {
+            eprintln!("Below minimum frame rate: {regression}");
+        }
+        std::process::exit(1);
+    }
+    Ok(())
+}
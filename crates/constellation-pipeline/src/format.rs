@@ -0,0 +1,58 @@
+/*
+ * Constellation Studio - Professional Real-time Video Processing
+ * Copyright (c) 2025 MACHIKO LAB
+ *
+ * This program is free software: you can redistribute it and/or modify
+ * it under the terms of the GNU Affero General Public License as published by
+ * the Free Software Foundation, either version 3 of the License, or
+ * (at your option) any later version.
+ *
+ * This program is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+ * GNU Affero General Public License for more details.
+ *
+ * You should have received a copy of the GNU Affero General Public License
+ * along with this program. If not, see <https://www.gnu.org/licenses/>.
+ */
+
+//! ノード間のRaster2Dフォーマット変換
+//!
+//! 変換本体は出力ノードと共有するため `constellation_core::convert` にある
+
+use anyhow::Result;
+use constellation_core::{RenderData, VideoFormat};
+
+pub use constellation_core::convert::{convert_video_frame, expected_data_len};
+
+/// RenderDataを指定フォーマットに変換する（Raster2D以外はそのまま返す）
+pub fn convert_render_data(render_data: RenderData, target: &VideoFormat) -> Result<RenderData> {
+    match render_data {
+        RenderData::Raster2D(frame) => {
+            Ok(RenderData::Raster2D(convert_video_frame(frame, target)?))
+        }
+        other => Ok(other),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use constellation_core::VideoFrame;
+
+    #[test]
+    fn test_raster_render_data_is_converted() {
+        let input = RenderData::Raster2D(VideoFrame {
+            width: 1,
+            height: 1,
+            format: VideoFormat::Bgra8,
+            data: vec![10, 20, 30, 40],
+        });
+        let RenderData::Raster2D(output) = convert_render_data(input, &VideoFormat::Rgba8).unwrap()
+        else {
+            panic!("expected Raster2D");
+        };
+        assert_eq!(output.format, VideoFormat::Rgba8);
+        assert_eq!(output.data, vec![30, 20, 10, 40]);
+    }
+}
@@ -0,0 +1,166 @@
+/*
+ * Constellation Studio - Professional Real-time Video Processing
+ * Copyright (c) 2025 MACHIKO LAB
+ *
+ * This program is free software: you can redistribute it and/or modify
+ * it under the terms of the GNU Affero General Public License as published by
+ * the Free Software Foundation, either version 3 of the License, or
+ * (at your option) any later version.
+ *
+ * This program is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+ * GNU Affero General Public License for more details.
+ *
+ * You should have received a copy of the GNU Affero General Public License
+ * along with this program. If not, see <https://www.gnu.org/licenses/>.
+ */
+
+//! 映像・音声経路間の遅延補正
+
+use anyhow::{bail, Result};
+use constellation_core::*;
+use std::collections::VecDeque;
+use std::time::Duration;
+
+/// 映像経路・音声経路それぞれで累積した処理遅延
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct PathLatency {
+    pub video: Duration,
+    pub audio: Duration,
+}
+
+impl PathLatency {
+    /// 速い方の経路に挿入すべき遅延（映像, 音声）
+    pub fn compensation(&self) -> (Duration, Duration) {
+        (
+            self.audio.saturating_sub(self.video),
+            self.video.saturating_sub(self.audio),
+        )
+    }
+}
+
+/// 遅延補正の既定フレームレート
+pub const DEFAULT_FRAME_RATE: f64 = 30.0;
+
+/// 遅延の少ない経路を遅らせて出力時に映像と音声を揃える
+#[derive(Debug)]
+pub struct LatencyCompensator {
+    video: VideoDelayLine,
+    audio: AudioDelayLine,
+    // 1フレームの長さ（映像の遅延をフレーム数に換算する）
+    frame_interval: Duration,
+}
+
+impl Default for LatencyCompensator {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl LatencyCompensator {
+    pub fn new() -> Self {
+        Self {
+            video: VideoDelayLine::default(),
+            audio: AudioDelayLine::default(),
+            frame_interval: Duration::from_secs_f64(1.0 / DEFAULT_FRAME_RATE),
+        }
+    }
+
+    /// フレームが届く周期を設定する
+    pub fn set_frame_rate(&mut self, fps: f64) -> Result<()> {
+        if !fps.is_finite() || fps <= 0.0 {
+            bail!("Invalid frame rate: {}", fps);
+        }
+        self.frame_interval = Duration::from_secs_f64(1.0 / fps);
+        Ok(())
+    }
+
+    pub fn apply(&mut self, mut frame: FrameData, latency: PathLatency) -> FrameData {
+        let (video_delay, audio_delay) = latency.compensation();
+
+        // タイムスタンプは呼び出し側が付けるとは限らないため、フレーム数で遅らせる
+        let delay_frames =
+            (video_delay.as_secs_f64() / self.frame_interval.as_secs_f64()).round() as usize;
+        frame.render_data = self.video.process(frame.render_data.take(), delay_frames);
+
+        if let Some(UnifiedAudioData::Stereo {
+            sample_rate,
+            channels,
+            ref mut samples,
+        }) = frame.audio_data
+        {
+            self.audio
+                .process(samples, sample_rate, channels, audio_delay);
+        }
+
+        frame
+    }
+}
+
+/// フレーム単位で映像を遅らせる
+#[derive(Debug, Default)]
+struct VideoDelayLine {
+    // 映像のないフレームも1フレームとして数える
+    queue: VecDeque<Option<RenderData>>,
+}
+
+impl VideoDelayLine {
+    fn process(
+        &mut self,
+        render_data: Option<RenderData>,
+        delay_frames: usize,
+    ) -> Option<RenderData> {
+        if delay_frames == 0 {
+            self.queue.clear();
+            return render_data;
+        }
+
+        self.queue.push_back(render_data);
+
+        // 遅延量が減った場合は古いフレームを捨てて追いつく
+        let mut released = None;
+        while self.queue.len() > delay_frames {
+            released = self.queue.pop_front().flatten();
+        }
+        released
+    }
+}
+
+/// サンプル単位で音声を遅らせるディレイライン（インターリーブ形式）
+#[derive(Debug, Default)]
+struct AudioDelayLine {
+    buffer: VecDeque<f32>,
+    delay_samples: usize,
+    sample_rate: u32,
+    channels: u16,
+}
+
+impl AudioDelayLine {
+    fn process(&mut self, samples: &mut [f32], sample_rate: u32, channels: u16, delay: Duration) {
+        let delay_frames = (delay.as_secs_f64() * sample_rate as f64).round() as usize;
+        let delay_samples = delay_frames * channels as usize;
+
+        // 遅延量やフォーマットが変わったら無音で埋め直す
+        if delay_samples != self.delay_samples
+            || sample_rate != self.sample_rate
+            || channels != self.channels
+        {
+            self.buffer.clear();
+            self.buffer.resize(delay_samples, 0.0);
+            self.delay_samples = delay_samples;
+            self.sample_rate = sample_rate;
+            self.channels = channels;
+        }
+
+        if delay_samples == 0 {
+            return;
+        }
+
+        let len = samples.len();
+        self.buffer.extend(samples.iter().copied());
+        for (sample, delayed) in samples.iter_mut().zip(self.buffer.drain(..len)) {
+            *sample = delayed;
+        }
+    }
+}
@@ -20,12 +20,53 @@ use anyhow::Result;
 use constellation_core::*;
 use constellation_nodes::*;
 use serde_json::Value;
-use std::collections::HashMap;
+use std::collections::{HashMap, HashSet};
+use std::sync::Arc;
+use std::time::Instant;
 use uuid::Uuid;
 
+pub mod format;
+pub mod latency;
+pub mod metrics;
+
+pub use format::{convert_render_data, convert_video_frame};
+pub use latency::{LatencyCompensator, PathLatency};
+pub use metrics::NodeTiming;
+
 pub struct PipelineProcessor {
     nodes: HashMap<Uuid, Box<dyn NodeProcessor + Send>>,
+    // 追加時に控えたノード種別（スパンのため毎フレーム get_properties を呼ばない）
+    node_types: HashMap<Uuid, NodeType>,
     execution_order: Vec<Uuid>,
+    // NodeGraphから取得した依存順（未設定時は追加順不定）
+    graph_order: Option<Vec<Uuid>>,
+    // ノードごとの上流ノードとその接続種別（接続順・送信元の重複なし）。グラフ設定時のみ使用
+    graph_inputs: HashMap<Uuid, Vec<(Uuid, Vec<ConnectionType>)>>,
+    // 映像・音声経路の遅延差を出力で揃える
+    latency_compensator: LatencyCompensator,
+    // ノードごとの process 所要時間
+    node_metrics: HashMap<Uuid, NodeTiming>,
+    // on_start 済みのノード
+    started_nodes: HashSet<Uuid>,
+    // ノードが前フレームで出力した制御データ（Control線は実行順を縛らないため、
+    // 送信元が後に実行される場合は1フレーム遅れで届ける）
+    control_outputs: HashMap<Uuid, ControlData>,
+    // バイパス中のノード（process を呼ばずフレームを素通しする）
+    bypassed_nodes: HashSet<Uuid>,
+    // デバッグ用: ノード間で受け渡す映像バッファをCRC32で検証する
+    frame_checksums: bool,
+    // 検証で不一致だった回数
+    checksum_mismatches: u64,
+    // 不一致をセッション統計に載せる記録先
+    telemetry: Option<Arc<TelemetryManager>>,
+    // パラメータ定義の範囲外の値を拒否するか丸めるか
+    parameter_policy: OutOfRangePolicy,
+    // ノードが直近のフレームで出力した映像（監視中のノードのみ）
+    raster_outputs: HashMap<Uuid, VideoFrame>,
+    // 出力映像を控えるノードと、その控えを必要としている利用者の数（スナップショット・プレビュー）
+    output_watchers: HashMap<Uuid, usize>,
+    // process_frame を完了したフレーム数
+    processed_frames: u64,
 }
 
 impl Default for PipelineProcessor {
@@ -38,47 +79,661 @@ impl PipelineProcessor {
     pub fn new() -> Self {
         Self {
             nodes: HashMap::new(),
+            node_types: HashMap::new(),
             execution_order: Vec::new(),
+            graph_order: None,
+            graph_inputs: HashMap::new(),
+            latency_compensator: LatencyCompensator::new(),
+            node_metrics: HashMap::new(),
+            started_nodes: HashSet::new(),
+            control_outputs: HashMap::new(),
+            bypassed_nodes: HashSet::new(),
+            frame_checksums: false,
+            checksum_mismatches: 0,
+            telemetry: None,
+            parameter_policy: OutOfRangePolicy::default(),
+            raster_outputs: HashMap::new(),
+            output_watchers: HashMap::new(),
+            processed_frames: 0,
         }
     }
 
     pub fn add_node(&mut self, id: Uuid, processor: Box<dyn NodeProcessor + Send>) {
+        self.node_types
+            .insert(id, processor.get_properties().node_type);
         self.nodes.insert(id, processor);
         self.rebuild_execution_order();
     }
 
     pub fn remove_node(&mut self, id: &Uuid) {
         self.nodes.remove(id);
+        self.node_types.remove(id);
+        self.node_metrics.remove(id);
+        self.started_nodes.remove(id);
+        self.control_outputs.remove(id);
+        self.bypassed_nodes.remove(id);
+        self.raster_outputs.remove(id);
+        self.graph_inputs.remove(id);
+        for sources in self.graph_inputs.values_mut() {
+            sources.retain(|(source, _)| source != id);
+        }
         self.execution_order.retain(|&node_id| node_id != *id);
     }
 
+    /// NodeGraphのトポロジカル順序で実行順を決定し、各ノードの上流を記録する
+    ///
+    /// 設定後は各ノードが上流ノードの出力のうち接続種別に対応するデータだけを受け取り、
+    /// 複数ある場合は `NodeProcessor::process_multi` で処理される。
+    pub fn set_graph(&mut self, graph: &NodeGraph) -> Result<()> {
+        self.graph_order = Some(graph.topological_order()?);
+
+        self.graph_inputs.clear();
+        for (source, target, connection_type) in graph.get_connections() {
+            let sources = self.graph_inputs.entry(*target).or_default();
+            match sources.iter_mut().find(|(id, _)| id == source) {
+                Some((_, types)) => {
+                    if !types.contains(connection_type) {
+                        types.push(connection_type.clone());
+                    }
+                }
+                None => sources.push((*source, vec![connection_type.clone()])),
+            }
+        }
+
+        self.rebuild_execution_order();
+        Ok(())
+    }
+
+    pub fn execution_order(&self) -> &[Uuid] {
+        &self.execution_order
+    }
+
+    /// 登録済みノードのパラメータ値
+    pub fn node_parameter(&self, id: &Uuid, key: &str) -> Option<Value> {
+        self.nodes
+            .get(id)
+            .and_then(|processor| processor.get_parameter(key))
+    }
+
+    /// ノードのパラメータをParameterDefinitionで検証して設定し、受け付けた値を返す
+    pub fn set_node_parameter(&mut self, id: &Uuid, key: &str, value: Value) -> Result<Value> {
+        let processor = self
+            .nodes
+            .get_mut(id)
+            .ok_or(ConstellationError::NodeNotFound { node_id: *id })?;
+        processor.set_validated_parameter(key, value, self.parameter_policy)
+    }
+
+    pub fn parameter_policy(&self) -> OutOfRangePolicy {
+        self.parameter_policy
+    }
+
+    /// パラメータ設定・制御データで範囲外の値を拒否するか丸めるかを切り替える
+    pub fn set_parameter_policy(&mut self, policy: OutOfRangePolicy) {
+        self.parameter_policy = policy;
+    }
+
+    pub fn node_properties(&self, id: &Uuid) -> Option<NodeProperties> {
+        self.nodes.get(id).map(|processor| NodeProperties {
+            enabled: !self.bypassed_nodes.contains(id),
+            ..processor.get_properties()
+        })
+    }
+
+    /// ノードの有効/バイパスを切り替える。未登録のノードなら `false` を返す
+    ///
+    /// バイパス中のノードは `process` を呼ばれず、入力フレームがそのまま下流へ渡る
+    /// （Tallyメタデータは通常どおり伝播する）。
+    pub fn set_node_enabled(&mut self, id: &Uuid, enabled: bool) -> bool {
+        if !self.nodes.contains_key(id) {
+            return false;
+        }
+        if enabled {
+            self.bypassed_nodes.remove(id);
+        } else {
+            self.bypassed_nodes.insert(*id);
+        }
+        true
+    }
+
+    pub fn is_node_enabled(&self, id: &Uuid) -> bool {
+        !self.bypassed_nodes.contains(id)
+    }
+
+    /// フレーム破損検出（デバッグ用）の有効/無効を切り替える。既定は無効
+    ///
+    /// 有効にすると各ノードの出力に映像バッファのCRC32を付け、受け取る側のノードの
+    /// 処理前に照合する。不一致は警告ログに出し `checksum_mismatches` に数える。
+    /// パイプラインへの入力フレームに `checksum` があれば最初のノードで同様に照合する。
+    /// フレームごとに映像全体を走査するため、通常運用では無効のままにする。
+    pub fn set_frame_checksums(&mut self, enabled: bool) {
+        self.frame_checksums = enabled;
+    }
+
+    pub fn frame_checksums_enabled(&self) -> bool {
+        self.frame_checksums
+    }
+
+    /// 検証でCRC32が一致しなかったフレームの累計
+    pub fn checksum_mismatches(&self) -> u64 {
+        self.checksum_mismatches
+    }
+
+    /// チェックサム不一致を `frame_checksum_mismatches` として記録するテレメトリを設定する
+    pub fn set_telemetry(&mut self, telemetry: Arc<TelemetryManager>) {
+        self.telemetry = Some(telemetry);
+    }
+
+    /// ノードが直近のフレームで出力した映像（`RenderData::Raster2D`）
+    ///
+    /// `watch_node_output` で監視中のノードだけが控えを持つ。監視を始めてから
+    /// まだ処理されていないノードや、直近の出力に映像を含まないノードは `None`。
+    pub fn node_output(&self, id: &Uuid) -> Option<&VideoFrame> {
+        self.raster_outputs.get(id)
+    }
+
+    /// 以降のフレームでノードの出力映像を控える（`unwatch_node_output` と対で呼ぶ）
+    ///
+    /// 控えはフレームごとのコピーになるため、スナップショット待ちやプレビュー中の
+    /// ノードに限って取る。同じノードを複数の利用者が監視してもよい。
+    pub fn watch_node_output(&mut self, id: Uuid) {
+        *self.output_watchers.entry(id).or_default() += 1;
+    }
+
+    /// `watch_node_output` を1回分取り消す。監視者がいなくなれば控えも捨てる
+    pub fn unwatch_node_output(&mut self, id: &Uuid) {
+        let Some(watchers) = self.output_watchers.get_mut(id) else {
+            return;
+        };
+        *watchers -= 1;
+        if *watchers == 0 {
+            self.output_watchers.remove(id);
+            self.raster_outputs.remove(id);
+        }
+    }
+
+    /// `process_frame` を完了したフレーム数
+    pub fn processed_frames(&self) -> u64 {
+        self.processed_frames
+    }
+
+    /// 処理済みノードの処理時間（移動平均）
+    pub fn node_metrics(&self) -> &HashMap<Uuid, NodeTiming> {
+        &self.node_metrics
+    }
+
+    /// 入力から出力までの経路のうち、映像・音声それぞれで最も遅いものの処理遅延
+    ///
+    /// グラフ設定時は接続種別ごとに上流の累積遅延の最大を引き継ぐため、
+    /// 並列の枝の遅延は合計されない。未設定時は全ノードが一本の経路になる。
+    pub fn path_latency(&self) -> PathLatency {
+        let mut accumulated: HashMap<Uuid, PathLatency> = HashMap::new();
+        let mut chain = PathLatency::default();
+        let mut latency = PathLatency::default();
+
+        for &node_id in &self.execution_order {
+            let Some(processor) = self.nodes.get(&node_id) else {
+                continue;
+            };
+
+            let mut node_path = if self.graph_order.is_some() {
+                let mut upstream = PathLatency::default();
+                for (source, connection_types) in
+                    self.graph_inputs.get(&node_id).into_iter().flatten()
+                {
+                    let Some(source_path) = accumulated.get(source) else {
+                        continue;
+                    };
+                    if connection_types.contains(&ConnectionType::RenderData) {
+                        upstream.video = upstream.video.max(source_path.video);
+                    }
+                    if connection_types.contains(&ConnectionType::Audio) {
+                        upstream.audio = upstream.audio.max(source_path.audio);
+                    }
+                }
+                upstream
+            } else {
+                chain
+            };
+
+            let node_latency = processor.processing_latency();
+            if !node_latency.is_zero() {
+                let properties = processor.get_properties();
+                let carries = |connection: ConnectionType| {
+                    properties.input_types.contains(&connection)
+                        || properties.output_types.contains(&connection)
+                };
+                if carries(ConnectionType::RenderData) {
+                    node_path.video += node_latency;
+                }
+                if carries(ConnectionType::Audio) {
+                    node_path.audio += node_latency;
+                }
+            }
+
+            latency.video = latency.video.max(node_path.video);
+            latency.audio = latency.audio.max(node_path.audio);
+            chain = node_path;
+            accumulated.insert(node_id, node_path);
+        }
+
+        latency
+    }
+
+    /// 遅延補正で映像をフレーム単位で遅らせるときのフレームレート
+    ///
+    /// 既定は30fps。`process_frame` を呼ぶ周期と合わせる。
+    pub fn set_frame_rate(&mut self, fps: f64) -> Result<()> {
+        self.latency_compensator.set_frame_rate(fps)
+    }
+
+    /// まだ開始していないノードの `NodeProcessor::on_start` を実行順に呼ぶ
+    ///
+    /// `process_frame` が最初のフレームの前に自動で呼ぶ。失敗した場合は
+    /// 今回開始したノードを `on_stop` で戻し、エラーを返して開始を中止する。
+    pub fn start(&mut self) -> Result<()> {
+        let mut started_now = Vec::new();
+
+        for &node_id in &self.execution_order {
+            if self.started_nodes.contains(&node_id) {
+                continue;
+            }
+            let Some(processor) = self.nodes.get_mut(&node_id) else {
+                continue;
+            };
+
+            if let Err(e) = processor.on_start() {
+                for started_id in started_now {
+                    if let Some(processor) = self.nodes.get_mut(&started_id) {
+                        if let Err(stop_error) = processor.on_stop() {
+                            tracing::warn!("Failed to stop node {}: {}", started_id, stop_error);
+                        }
+                    }
+                }
+                return Err(e.context(format!("Failed to start node {}", node_id)));
+            }
+            started_now.push(node_id);
+        }
+
+        self.started_nodes.extend(started_now);
+        Ok(())
+    }
+
+    /// 全ノードの `NodeProcessor::on_stop` を実行順に呼ぶ
+    ///
+    /// 途中で失敗したノードがあっても残りのノードは停止させ、最初のエラーを返す。
+    pub fn stop(&mut self) -> Result<()> {
+        let mut first_error = None;
+
+        for node_id in &self.execution_order {
+            let Some(processor) = self.nodes.get_mut(node_id) else {
+                continue;
+            };
+            if let Err(e) = processor.on_stop() {
+                tracing::warn!("Failed to stop node {}: {}", node_id, e);
+                first_error.get_or_insert(e);
+            }
+        }
+
+        self.started_nodes.clear();
+
+        match first_error {
+            Some(e) => Err(e),
+            None => Ok(()),
+        }
+    }
+
     pub fn process_frame(&mut self, input: FrameData) -> Result<FrameData> {
-        let mut current_frame = input;
+        // 新しく追加されたノードも含め、未開始のノードを開始する
+        if self.started_nodes.len() < self.nodes.len() {
+            self.start()?;
+        }
 
         // Control線の配信を先に処理（borrowing問題回避）
-        if let Some(ref control_data) = current_frame.control_data {
+        if let Some(ref control_data) = input.control_data {
             self.distribute_control_commands(control_data)?;
         }
 
+        let output = if self.graph_order.is_some() {
+            self.process_graph(input)?
+        } else {
+            self.process_chain(input)?
+        };
+
+        // 遅延の少ない経路を遅らせて映像と音声を揃える
+        let latency = self.path_latency();
+        let mut output = self.latency_compensator.apply(output, latency);
+        if self.frame_checksums {
+            // 遅延で差し替わった映像に合わせて付け直す
+            output.stamp_checksum();
+        }
+        self.processed_frames += 1;
+        Ok(output)
+    }
+
+    /// 受け取ったフレームのCRC32を照合し、不一致なら警告して数える
+    fn verify_checksum(
+        node_id: Uuid,
+        frame: &FrameData,
+        mismatches: &mut u64,
+        telemetry: Option<&TelemetryManager>,
+    ) {
+        if let Some((expected, actual)) = frame.checksum_mismatch() {
+            *mismatches += 1;
+            if let Some(telemetry) = telemetry {
+                telemetry.record_checksum_mismatch();
+            }
+            tracing::warn!(
+                node_id = %node_id,
+                "Frame {} checksum mismatch before node {}: expected {:08x}, got {:08x}",
+                frame.sequence,
+                node_id,
+                expected,
+                actual
+            );
+        }
+    }
+
+    /// 監視中のノードの出力に映像があれば控え、なければ前回の控えを消す
+    ///
+    /// 毎フレーム呼ばれるため、既存のバッファを再利用してコピーする。
+    fn record_raster_output(
+        raster_outputs: &mut HashMap<Uuid, VideoFrame>,
+        output_watchers: &HashMap<Uuid, usize>,
+        node_id: Uuid,
+        frame: &FrameData,
+    ) {
+        if !output_watchers.contains_key(&node_id) {
+            return;
+        }
+        let Some(RenderData::Raster2D(video_frame)) = &frame.render_data else {
+            raster_outputs.remove(&node_id);
+            return;
+        };
+        match raster_outputs.get_mut(&node_id) {
+            Some(recorded) => {
+                recorded.width = video_frame.width;
+                recorded.height = video_frame.height;
+                recorded.format = video_frame.format.clone();
+                recorded.data.clone_from(&video_frame.data);
+            }
+            None => {
+                raster_outputs.insert(node_id, video_frame.clone());
+            }
+        }
+    }
+
+    /// グラフ未設定時: 実行順にフレームを1本で受け渡す
+    fn process_chain(&mut self, input: FrameData) -> Result<FrameData> {
+        let mut current_frame = input;
+
         for &node_id in &self.execution_order {
             if let Some(processor) = self.nodes.get_mut(&node_id) {
-                // Tally伝播処理
-                if processor.should_propagate_tally(&current_frame.tally_metadata) {
-                    let processed_tally =
-                        processor.process_tally_metadata(&current_frame.tally_metadata);
-                    current_frame.tally_metadata.merge_with(&processed_tally);
+                if self.frame_checksums {
+                    Self::verify_checksum(
+                        node_id,
+                        &current_frame,
+                        &mut self.checksum_mismatches,
+                        self.telemetry.as_deref(),
+                    );
                 }
+                let inputs = vec![(node_id, current_frame)];
+                current_frame = if self.bypassed_nodes.contains(&node_id) {
+                    Self::bypass_node(processor.as_mut(), inputs)?
+                } else {
+                    let node_type = &self.node_types[&node_id];
+                    let timing = self.node_metrics.entry(node_id).or_default();
+                    Self::run_node(node_id, node_type, processor.as_mut(), timing, inputs)?
+                };
+                if self.frame_checksums {
+                    current_frame.stamp_checksum();
+                }
+                Self::record_raster_output(
+                    &mut self.raster_outputs,
+                    &self.output_watchers,
+                    node_id,
+                    &current_frame,
+                );
+            }
+        }
+
+        Ok(current_frame)
+    }
+
+    /// グラフ設定時: 上流ノードの出力を接続種別ごとに振り分けて各ノードに渡す
+    ///
+    /// 上流のないノードはパイプラインへの入力フレームを受け取る（送信元IDは `Uuid::nil()`）。
+    /// 戻り値は実行順で最後のノードの出力で、欠けている要素は他の終端ノードの出力で補う
+    /// （音声系統と映像系統が別々に終わる場合も1フレームにまとまる）。
+    fn process_graph(&mut self, input: FrameData) -> Result<FrameData> {
+        let mut outputs: HashMap<Uuid, FrameData> = HashMap::new();
+        let mut last_node = None;
+
+        for &node_id in &self.execution_order {
+            let Some(processor) = self.nodes.get_mut(&node_id) else {
+                continue;
+            };
 
-                // メインフレーム処理
-                current_frame = processor.process(current_frame)?;
+            let mut inputs: Vec<(Uuid, FrameData)> = self
+                .graph_inputs
+                .get(&node_id)
+                .into_iter()
+                .flatten()
+                .filter_map(|(source, connection_types)| {
+                    let frame = match outputs.get(source) {
+                        Some(frame) => Self::route_frame(frame, connection_types),
+                        None if connection_types.contains(&ConnectionType::Control) => FrameData {
+                            render_data: None,
+                            audio_data: None,
+                            control_data: Some(self.control_outputs.get(source)?.clone()),
+                            tally_metadata: TallyMetadata::new(),
+                            timestamp: input.timestamp,
+                            sequence: input.sequence,
+                            checksum: None,
+                        },
+                        None => return None,
+                    };
+                    Some((*source, frame))
+                })
+                .collect();
+            if inputs.is_empty() {
+                inputs.push((Uuid::nil(), input.clone()));
+            }
+            if self.frame_checksums {
+                for (_, frame) in &inputs {
+                    Self::verify_checksum(
+                        node_id,
+                        frame,
+                        &mut self.checksum_mismatches,
+                        self.telemetry.as_deref(),
+                    );
+                }
+            }
 
-                // ノード固有のTally状態を生成・追加
-                let node_tally = processor.generate_tally_state();
-                current_frame.tally_metadata.merge_with(&node_tally);
+            let mut output = if self.bypassed_nodes.contains(&node_id) {
+                Self::bypass_node(processor.as_mut(), inputs)?
+            } else {
+                let node_type = &self.node_types[&node_id];
+                let timing = self.node_metrics.entry(node_id).or_default();
+                Self::run_node(node_id, node_type, processor.as_mut(), timing, inputs)?
+            };
+            if self.frame_checksums {
+                output.stamp_checksum();
             }
+            Self::record_raster_output(
+                &mut self.raster_outputs,
+                &self.output_watchers,
+                node_id,
+                &output,
+            );
+            match &output.control_data {
+                Some(control_data) => {
+                    self.control_outputs.insert(node_id, control_data.clone());
+                }
+                None => {
+                    self.control_outputs.remove(&node_id);
+                }
+            }
+            outputs.insert(node_id, output);
+            last_node = Some(node_id);
         }
 
-        Ok(current_frame)
+        let Some(mut output) = last_node.and_then(|node_id| outputs.remove(&node_id)) else {
+            return Ok(input);
+        };
+
+        // 下流を持たない他の終端ノードの出力で合流させる（後に実行されたものを優先）
+        let has_downstream: HashSet<Uuid> = self
+            .graph_inputs
+            .values()
+            .flatten()
+            .map(|(source, _)| *source)
+            .collect();
+        for node_id in self.execution_order.iter().rev() {
+            if has_downstream.contains(node_id) {
+                continue;
+            }
+            if let Some(frame) = outputs.remove(node_id) {
+                output.render_data = output.render_data.or(frame.render_data);
+                output.audio_data = output.audio_data.or(frame.audio_data);
+                output.control_data = output.control_data.or(frame.control_data);
+                output.tally_metadata.merge_with(&frame.tally_metadata);
+            }
+        }
+
+        Ok(output)
+    }
+
+    /// エッジの接続種別が運ぶデータだけを残したフレームを作る
+    ///
+    /// Tallyメタデータ・タイムスタンプ・シーケンス番号は種別に関係なく引き継ぐ。
+    /// CRC32は映像を運ぶ接続でだけ引き継ぐ。
+    fn route_frame(frame: &FrameData, connection_types: &[ConnectionType]) -> FrameData {
+        let carries = |connection_type: ConnectionType| connection_types.contains(&connection_type);
+        FrameData {
+            render_data: carries(ConnectionType::RenderData)
+                .then(|| frame.render_data.clone())
+                .flatten(),
+            audio_data: carries(ConnectionType::Audio)
+                .then(|| frame.audio_data.clone())
+                .flatten(),
+            control_data: carries(ConnectionType::Control)
+                .then(|| frame.control_data.clone())
+                .flatten(),
+            tally_metadata: frame.tally_metadata.clone(),
+            timestamp: frame.timestamp,
+            sequence: frame.sequence,
+            checksum: carries(ConnectionType::RenderData)
+                .then_some(frame.checksum)
+                .flatten(),
+        }
+    }
+
+    /// 入力フレームのTallyをノードの規則で伝播させる
+    fn propagate_tally(
+        processor: &mut (dyn NodeProcessor + Send),
+        inputs: &mut [(Uuid, FrameData)],
+    ) {
+        for (_, frame) in inputs {
+            if processor.should_propagate_tally(&frame.tally_metadata) {
+                let processed_tally = processor.process_tally_metadata(&frame.tally_metadata);
+                frame.tally_metadata.merge_with(&processed_tally);
+            }
+        }
+    }
+
+    /// バイパス中のノード: `process` を呼ばずTally伝播だけ行い、最初の入力をそのまま返す
+    fn bypass_node(
+        processor: &mut (dyn NodeProcessor + Send),
+        mut inputs: Vec<(Uuid, FrameData)>,
+    ) -> Result<FrameData> {
+        Self::propagate_tally(processor, &mut inputs);
+
+        let mut inputs = inputs.into_iter();
+        let Some((_, mut output)) = inputs.next() else {
+            anyhow::bail!("Bypassed node received no input");
+        };
+        // 合流ノードでも他の入力のTallyは失わない
+        for (_, frame) in inputs {
+            output.tally_metadata.merge_with(&frame.tally_metadata);
+        }
+        Ok(output)
+    }
+
+    /// 1ノード分の処理（Tally伝播・フォーマット変換・処理時間計測を含む）
+    ///
+    /// バイパス中のノードには呼ばない（`bypass_node` を使う）。
+    /// 処理全体を `node` スパン（`node_id`・`node_type` フィールド付き）で囲み、
+    /// ノード内のログをノード単位で絞り込めるようにする。所要時間は `duration_us` に記録する。
+    fn run_node(
+        node_id: Uuid,
+        node_type: &NodeType,
+        processor: &mut (dyn NodeProcessor + Send),
+        timing: &mut NodeTiming,
+        mut inputs: Vec<(Uuid, FrameData)>,
+    ) -> Result<FrameData> {
+        let span = tracing::info_span!(
+            "node",
+            node_id = %node_id,
+            node_type = ?node_type,
+            duration_us = tracing::field::Empty,
+        );
+        let _entered = span.enter();
+
+        Self::propagate_tally(processor, &mut inputs);
+
+        let mut inputs = inputs
+            .into_iter()
+            .map(|(source_id, frame)| {
+                // 接続先が期待するフォーマットへ暗黙変換
+                Ok((source_id, Self::negotiate_format(&*processor, frame)?))
+            })
+            .collect::<Result<Vec<_>>>()?;
+
+        // メインフレーム処理（所要時間を計測）
+        let started = Instant::now();
+        let mut output = if inputs.len() == 1 {
+            let (_, frame) = inputs.swap_remove(0);
+            processor.process(frame)?
+        } else {
+            processor.process_multi(inputs)?
+        };
+        let elapsed = started.elapsed();
+        timing.record(elapsed);
+        span.record("duration_us", elapsed.as_micros() as u64);
+
+        // ノード固有のTally状態を生成・追加
+        let node_tally = processor.generate_tally_state();
+        output.tally_metadata.merge_with(&node_tally);
+
+        Ok(output)
+    }
+
+    /// エッジのソース出力とシンク入力のフォーマットが異なる場合に変換を挿入する
+    fn negotiate_format(
+        processor: &(dyn NodeProcessor + Send),
+        mut frame: FrameData,
+    ) -> Result<FrameData> {
+        let Some(sink_format) = processor.input_video_format() else {
+            return Ok(frame);
+        };
+
+        if let Some(RenderData::Raster2D(ref video_frame)) = frame.render_data {
+            if !processor.accepts_video_format(&video_frame.format) {
+                tracing::trace!(
+                    "Converting Raster2D {:?} -> {:?}",
+                    video_frame.format,
+                    sink_format
+                );
+                frame.render_data = frame
+                    .render_data
+                    .take()
+                    .map(|render_data| convert_render_data(render_data, &sink_format))
+                    .transpose()?;
+            }
+        }
+
+        Ok(frame)
     }
 
     fn distribute_control_commands(&mut self, control_data: &ControlData) -> Result<()> {
@@ -90,22 +745,147 @@ impl PipelineProcessor {
             } => {
                 if let Some(processor) = self.nodes.get_mut(target_node_id) {
                     let json_value = Self::parameter_value_to_json(value);
-                    processor.set_parameter(parameter_name, json_value)?;
+                    processor.set_validated_parameter(
+                        parameter_name,
+                        json_value,
+                        self.parameter_policy,
+                    )?;
                 }
             }
             ControlData::MultiControl { commands } => {
                 for command in commands {
                     if let Some(processor) = self.nodes.get_mut(&command.target_node_id) {
                         let json_value = Self::parameter_value_to_json(&command.value);
-                        processor.set_parameter(&command.parameter_name, json_value)?;
+                        processor.set_validated_parameter(
+                            &command.parameter_name,
+                            json_value,
+                            self.parameter_policy,
+                        )?;
+                    }
+                }
+            }
+            ControlData::Transform {
+                target_node_id: Some(target_node_id),
+                position,
+                rotation,
+                scale,
+            } => {
+                self.set_typed_parameters(target_node_id, |kind| {
+                    let mut values = Vec::new();
+                    if let Some(position) = position {
+                        values.push(("position", Self::vector_to_json(position, kind("position"))));
+                    }
+                    if let Some(rotation) = rotation {
+                        values.push((
+                            "rotation",
+                            Self::rotation_to_json(rotation, kind("rotation")),
+                        ));
+                    }
+                    if let Some(scale) = scale {
+                        values.push(("scale", Self::vector_to_json(scale, kind("scale"))));
+                    }
+                    values
+                })?;
+            }
+            ControlData::Camera {
+                target_node_id: Some(target_node_id),
+                position,
+                target,
+                fov,
+                near,
+                far,
+            } => {
+                self.set_typed_parameters(target_node_id, |kind| {
+                    let mut values = Vec::new();
+                    if let Some(position) = position {
+                        values.push(("position", Self::vector_to_json(position, kind("position"))));
+                    }
+                    if let Some(target) = target {
+                        values.push(("target", Self::vector_to_json(target, kind("target"))));
+                    }
+                    for (name, value) in [("fov", fov), ("near", near), ("far", far)] {
+                        if let Some(value) = value {
+                            values.push((name, Value::from(*value)));
+                        }
                     }
+                    values
+                })?;
+            }
+            ControlData::Animation {
+                target_node_id: Some(target_node_id),
+                parameter_name,
+                keyframes,
+                time,
+                ..
+            } => {
+                if let Some(processor) = self.nodes.get_mut(target_node_id) {
+                    let value = evaluate_keyframes(keyframes, *time);
+                    let json_value = Self::parameter_value_to_json(&value);
+                    processor.set_validated_parameter(
+                        parameter_name,
+                        json_value,
+                        self.parameter_policy,
+                    )?;
                 }
             }
-            _ => {} // Other control types don't need distribution
+            // 対象ノードの指定がない制御は、Control線で受け取ったノードが自分で解釈する
+            _ => {}
+        }
+        Ok(())
+    }
+
+    /// 対象ノードのパラメータ定義の型を見て値を作り、検証付きで設定する
+    fn set_typed_parameters<F>(&mut self, node_id: &Uuid, values: F) -> Result<()>
+    where
+        F: FnOnce(&dyn Fn(&str) -> Option<ParameterType>) -> Vec<(&'static str, Value)>,
+    {
+        let Some(processor) = self.nodes.get_mut(node_id) else {
+            return Ok(());
+        };
+
+        let parameters = processor.get_properties().parameters;
+        let kind = |name: &str| {
+            parameters
+                .get(name)
+                .map(|definition| definition.parameter_type.clone())
+        };
+        for (name, value) in values(&kind) {
+            processor.set_validated_parameter(name, value, self.parameter_policy)?;
         }
         Ok(())
     }
 
+    /// Vector3を対象パラメータの型に合わせた配列にする（Vector2ならZを捨てる）
+    fn vector_to_json(vector: &Vector3, parameter_type: Option<ParameterType>) -> Value {
+        match parameter_type {
+            Some(ParameterType::Vector2) => {
+                Value::Array(vec![Value::from(vector.x), Value::from(vector.y)])
+            }
+            _ => Value::Array(vec![
+                Value::from(vector.x),
+                Value::from(vector.y),
+                Value::from(vector.z),
+            ]),
+        }
+    }
+
+    /// 回転を対象パラメータの型に合わせる
+    ///
+    /// Float型ならZ軸周りの角度（度）、それ以外はクォータニオン [x, y, z, w] のまま渡す。
+    fn rotation_to_json(rotation: &Quaternion, parameter_type: Option<ParameterType>) -> Value {
+        match parameter_type {
+            Some(ParameterType::Float) => {
+                Value::from((2.0 * rotation.z.atan2(rotation.w)).to_degrees())
+            }
+            _ => Value::Array(vec![
+                Value::from(rotation.x),
+                Value::from(rotation.y),
+                Value::from(rotation.z),
+                Value::from(rotation.w),
+            ]),
+        }
+    }
+
     fn parameter_value_to_json(value: &ParameterValue) -> Value {
         match value {
             ParameterValue::Float(f) => Value::from(*f),
@@ -128,7 +908,28 @@ impl PipelineProcessor {
     }
 
     fn rebuild_execution_order(&mut self) {
-        self.execution_order = self.nodes.keys().copied().collect();
+        match &self.graph_order {
+            Some(graph_order) => {
+                let mut order: Vec<Uuid> = graph_order
+                    .iter()
+                    .filter(|id| self.nodes.contains_key(id))
+                    .copied()
+                    .collect();
+                // グラフに存在しないノードは末尾に追加
+                let mut unordered: Vec<Uuid> = self
+                    .nodes
+                    .keys()
+                    .filter(|id| !graph_order.contains(id))
+                    .copied()
+                    .collect();
+                unordered.sort();
+                order.extend(unordered);
+                self.execution_order = order;
+            }
+            None => {
+                self.execution_order = self.nodes.keys().copied().collect();
+            }
+        }
     }
 }
 
@@ -136,6 +937,7 @@ impl PipelineProcessor {
 mod tests {
     use super::*;
     use std::collections::HashMap;
+    use std::time::Duration;
 
     #[test]
     fn test_pipeline_processor() {
@@ -158,9 +960,1615 @@ mod tests {
             audio_data: None,
             control_data: None,
             tally_metadata: TallyMetadata::new(),
+            timestamp: Duration::ZERO,
+            sequence: 0,
+            checksum: None,
         };
 
         let result = pipeline.process_frame(input_frame);
         assert!(result.is_ok());
     }
+
+    #[test]
+    fn test_pipeline_converts_bgra_edge_to_rgba() {
+        let mut pipeline = PipelineProcessor::new();
+
+        let node_id = Uuid::new_v4();
+        let mut parameters = HashMap::new();
+        parameters.insert("radius".to_string(), Value::from(0.0));
+        let processor = create_node_processor(
+            NodeType::Effect(EffectType::Blur),
+            node_id,
+            NodeConfig { parameters },
+        )
+        .unwrap();
+        pipeline.add_node(node_id, processor);
+
+        let input_frame = FrameData {
+            render_data: Some(RenderData::Raster2D(VideoFrame {
+                width: 2,
+                height: 1,
+                format: VideoFormat::Bgra8,
+                data: vec![10, 20, 30, 255, 40, 50, 60, 128],
+            })),
+            audio_data: None,
+            control_data: None,
+            tally_metadata: TallyMetadata::new(),
+            timestamp: Duration::ZERO,
+            sequence: 0,
+            checksum: None,
+        };
+
+        let result = pipeline.process_frame(input_frame).unwrap();
+        match result.render_data {
+            Some(RenderData::Raster2D(frame)) => {
+                assert_eq!(frame.format, VideoFormat::Rgba8);
+                assert_eq!(frame.data, vec![30, 20, 10, 255, 60, 50, 40, 128]);
+            }
+            _ => panic!("Expected Raster2D output"),
+        }
+    }
+
+    #[test]
+    fn test_pipeline_keeps_rgba16_for_color_correction() {
+        let mut pipeline = PipelineProcessor::new();
+
+        let node_id = Uuid::new_v4();
+        let processor = create_node_processor(
+            NodeType::Effect(EffectType::ColorCorrection),
+            node_id,
+            NodeConfig {
+                parameters: HashMap::new(),
+            },
+        )
+        .unwrap();
+        pipeline.add_node(node_id, processor);
+
+        let data: Vec<u8> = [0x0102u16, 0x0304, 0x0506, 0xFFFF]
+            .iter()
+            .flat_map(|v| v.to_le_bytes())
+            .collect();
+        let input_frame = FrameData {
+            render_data: Some(RenderData::Raster2D(VideoFrame {
+                width: 1,
+                height: 1,
+                format: VideoFormat::Rgba16,
+                data: data.clone(),
+            })),
+            audio_data: None,
+            control_data: None,
+            tally_metadata: TallyMetadata::new(),
+            timestamp: Duration::ZERO,
+            sequence: 0,
+            checksum: None,
+        };
+
+        // Rgba8へ暗黙変換されず、16bitのまま処理される
+        let result = pipeline.process_frame(input_frame).unwrap();
+        match result.render_data {
+            Some(RenderData::Raster2D(frame)) => {
+                assert_eq!(frame.format, VideoFormat::Rgba16);
+                assert_eq!(frame.data, data);
+            }
+            _ => panic!("Expected Raster2D output"),
+        }
+    }
+
+    #[test]
+    fn test_pipeline_uses_graph_order() {
+        let mut graph = NodeGraph::new();
+        let mut pipeline = PipelineProcessor::new();
+
+        let chain = [
+            NodeType::Input(InputType::TestPattern),
+            NodeType::Effect(EffectType::ColorCorrection),
+            NodeType::Output(OutputType::Preview),
+        ];
+        let ids: Vec<Uuid> = chain.iter().map(|_| Uuid::new_v4()).collect();
+
+        // 逆順で追加してもグラフ順が優先されることを確認
+        for (node_type, &id) in chain.iter().zip(ids.iter()).rev() {
+            let config = NodeConfig {
+                parameters: HashMap::new(),
+            };
+            graph.add_node(Node::new(id, node_type.clone(), config.clone()));
+            pipeline.add_node(
+                id,
+                create_node_processor(node_type.clone(), id, config).unwrap(),
+            );
+        }
+        graph
+            .connect_nodes(ids[1], ids[2], ConnectionType::RenderData)
+            .unwrap();
+        graph
+            .connect_nodes(ids[0], ids[1], ConnectionType::RenderData)
+            .unwrap();
+
+        pipeline.set_graph(&graph).unwrap();
+        assert_eq!(pipeline.execution_order(), ids.as_slice());
+    }
+
+    #[test]
+    fn test_node_metrics_record_each_node() {
+        let mut pipeline = PipelineProcessor::new();
+
+        let pattern_id = Uuid::new_v4();
+        let mut parameters = HashMap::new();
+        parameters.insert("width".to_string(), Value::from(320));
+        parameters.insert("height".to_string(), Value::from(240));
+        pipeline.add_node(
+            pattern_id,
+            create_node_processor(
+                NodeType::Input(InputType::TestPattern),
+                pattern_id,
+                NodeConfig { parameters },
+            )
+            .unwrap(),
+        );
+
+        let correction_id = Uuid::new_v4();
+        pipeline.add_node(
+            correction_id,
+            create_node_processor(
+                NodeType::Effect(EffectType::ColorCorrection),
+                correction_id,
+                NodeConfig {
+                    parameters: HashMap::new(),
+                },
+            )
+            .unwrap(),
+        );
+
+        for sequence in 0..5 {
+            pipeline
+                .process_frame(FrameData {
+                    render_data: None,
+                    audio_data: None,
+                    control_data: None,
+                    tally_metadata: TallyMetadata::new(),
+                    timestamp: Duration::from_millis(sequence * 33),
+                    sequence,
+                    checksum: None,
+                })
+                .unwrap();
+        }
+
+        let metrics = pipeline.node_metrics();
+        assert_eq!(metrics.len(), 2);
+        for node_id in [pattern_id, correction_id] {
+            let timing = &metrics[&node_id];
+            assert_eq!(timing.samples(), 5);
+            assert!(timing.average() > Duration::ZERO);
+            assert!(timing.average() < Duration::from_secs(1));
+        }
+
+        pipeline.remove_node(&pattern_id);
+        assert!(!pipeline.node_metrics().contains_key(&pattern_id));
+    }
+
+    fn control_frame(control_data: ControlData) -> FrameData {
+        FrameData {
+            control_data: Some(control_data),
+            ..FrameData::empty()
+        }
+    }
+
+    fn add_effect(pipeline: &mut PipelineProcessor, effect: EffectType) -> Uuid {
+        let id = Uuid::new_v4();
+        let processor = create_node_processor(
+            NodeType::Effect(effect),
+            id,
+            NodeConfig {
+                parameters: HashMap::new(),
+            },
+        )
+        .unwrap();
+        pipeline.add_node(id, processor);
+        id
+    }
+
+    fn float_parameter(pipeline: &PipelineProcessor, id: &Uuid, key: &str) -> f64 {
+        pipeline
+            .node_parameter(id, key)
+            .and_then(|v| v.as_f64())
+            .unwrap()
+    }
+
+    #[test]
+    fn test_set_node_parameter_follows_parameter_policy() {
+        let mut pipeline = PipelineProcessor::new();
+        let id = add_effect(&mut pipeline, EffectType::ColorCorrection);
+
+        // 既定では範囲外の値を拒否し、元の値が残る
+        assert!(pipeline
+            .set_node_parameter(&id, "brightness", Value::from(0.25))
+            .is_ok());
+        assert!(pipeline
+            .set_node_parameter(&id, "brightness", Value::from(3.0))
+            .is_err());
+        assert_eq!(float_parameter(&pipeline, &id, "brightness"), 0.25);
+
+        // Clampなら最大値に丸めて受け付ける（制御データ経由も同じ）
+        pipeline.set_parameter_policy(OutOfRangePolicy::Clamp);
+        let accepted = pipeline
+            .set_node_parameter(&id, "brightness", Value::from(3.0))
+            .unwrap();
+        assert_eq!(accepted, Value::from(1.0));
+        pipeline
+            .process_frame(control_frame(ControlData::Parameter {
+                target_node_id: id,
+                parameter_name: "brightness".to_string(),
+                value: ParameterValue::Float(-5.0),
+            }))
+            .unwrap();
+        assert_eq!(float_parameter(&pipeline, &id, "brightness"), -1.0);
+
+        assert!(pipeline
+            .set_node_parameter(&Uuid::new_v4(), "brightness", Value::from(0.0))
+            .is_err());
+    }
+
+    #[test]
+    fn test_transform_control_updates_target_parameters() {
+        let mut pipeline = PipelineProcessor::new();
+        let target = add_effect(&mut pipeline, EffectType::Transform);
+        let other = add_effect(&mut pipeline, EffectType::Transform);
+
+        // Z軸周りに90度
+        let half_angle = std::f32::consts::FRAC_PI_4;
+        pipeline
+            .process_frame(control_frame(ControlData::Transform {
+                target_node_id: Some(target),
+                position: Some(Vector3 {
+                    x: 0.25,
+                    y: -0.5,
+                    z: 3.0,
+                }),
+                rotation: Some(Quaternion {
+                    x: 0.0,
+                    y: 0.0,
+                    z: half_angle.sin(),
+                    w: half_angle.cos(),
+                }),
+                scale: Some(Vector3 {
+                    x: 2.0,
+                    y: 3.0,
+                    z: 1.0,
+                }),
+            }))
+            .unwrap();
+
+        // TransformNodeのposition / scaleはVector2なのでZは捨てられる
+        assert_eq!(
+            pipeline.node_parameter(&target, "position"),
+            Some(serde_json::json!([0.25, -0.5]))
+        );
+        assert_eq!(
+            pipeline.node_parameter(&target, "scale"),
+            Some(serde_json::json!([2.0, 3.0]))
+        );
+        assert!((float_parameter(&pipeline, &target, "rotation") - 90.0).abs() < 1e-3);
+        assert_eq!(pipeline.node_parameter(&other, "position"), None);
+
+        // 指定されなかった要素はそのまま
+        pipeline
+            .process_frame(control_frame(ControlData::Transform {
+                target_node_id: Some(target),
+                position: None,
+                rotation: None,
+                scale: Some(Vector3 {
+                    x: 1.0,
+                    y: 1.0,
+                    z: 1.0,
+                }),
+            }))
+            .unwrap();
+        assert_eq!(
+            pipeline.node_parameter(&target, "position"),
+            Some(serde_json::json!([0.25, -0.5]))
+        );
+        assert_eq!(
+            pipeline.node_parameter(&target, "scale"),
+            Some(serde_json::json!([1.0, 1.0]))
+        );
+    }
+
+    #[test]
+    fn test_untargeted_transform_is_left_to_the_receiving_node() {
+        let mut pipeline = PipelineProcessor::new();
+        let node = add_effect(&mut pipeline, EffectType::Transform);
+
+        pipeline
+            .process_frame(control_frame(ControlData::Transform {
+                target_node_id: None,
+                position: Some(Vector3 {
+                    x: 1.0,
+                    y: 1.0,
+                    z: 0.0,
+                }),
+                rotation: None,
+                scale: None,
+            }))
+            .unwrap();
+        assert_eq!(pipeline.node_parameter(&node, "position"), None);
+    }
+
+    #[test]
+    fn test_camera_and_animation_control_update_target_parameters() {
+        let mut pipeline = PipelineProcessor::new();
+        let target = add_effect(&mut pipeline, EffectType::ColorCorrection);
+
+        // 定義のないパラメータにはVector3をそのまま渡す
+        pipeline
+            .process_frame(control_frame(ControlData::Camera {
+                target_node_id: Some(target),
+                position: Some(Vector3 {
+                    x: 1.0,
+                    y: 2.0,
+                    z: 3.0,
+                }),
+                target: None,
+                fov: Some(60.0),
+                near: None,
+                far: Some(100.0),
+            }))
+            .unwrap();
+        assert_eq!(
+            pipeline.node_parameter(&target, "position"),
+            Some(serde_json::json!([1.0, 2.0, 3.0]))
+        );
+        assert_eq!(float_parameter(&pipeline, &target, "fov"), 60.0);
+        assert_eq!(float_parameter(&pipeline, &target, "far"), 100.0);
+        assert_eq!(pipeline.node_parameter(&target, "near"), None);
+
+        let keyframe = |time: f32, value: f32| Keyframe {
+            time,
+            value: ParameterValue::Float(value),
+            interpolation: InterpolationType::Linear,
+        };
+        pipeline
+            .process_frame(control_frame(ControlData::Animation {
+                target_node_id: Some(target),
+                parameter_name: "brightness".to_string(),
+                keyframes: vec![keyframe(0.0, 0.0), keyframe(2.0, 0.4)],
+                time: 0.5,
+                interpolation: InterpolationType::Linear,
+            }))
+            .unwrap();
+        assert!((float_parameter(&pipeline, &target, "brightness") - 0.1).abs() < 1e-6);
+    }
+
+    /// 固定の処理遅延を報告するパススルーノード
+    struct FixedLatencyNode {
+        properties: NodeProperties,
+        latency: Duration,
+    }
+
+    impl FixedLatencyNode {
+        fn new(connection: ConnectionType, latency: Duration) -> Self {
+            Self {
+                properties: NodeProperties {
+                    id: Uuid::new_v4(),
+                    name: "Fixed Latency".to_string(),
+                    node_type: NodeType::Effect(EffectType::ColorCorrection),
+                    input_types: vec![connection.clone()],
+                    output_types: vec![connection],
+                    parameters: HashMap::new(),
+                    enabled: true,
+                },
+                latency,
+            }
+        }
+    }
+
+    impl NodeProcessor for FixedLatencyNode {
+        fn process(&mut self, input: FrameData) -> Result<FrameData> {
+            Ok(input)
+        }
+
+        fn get_properties(&self) -> NodeProperties {
+            self.properties.clone()
+        }
+
+        fn set_parameter(&mut self, _key: &str, _value: Value) -> Result<()> {
+            Ok(())
+        }
+
+        fn get_parameter(&self, _key: &str) -> Option<Value> {
+            None
+        }
+
+        fn processing_latency(&self) -> Duration {
+            self.latency
+        }
+    }
+
+    fn av_frame(timestamp_ms: u64, marker: u8, samples: Vec<f32>) -> FrameData {
+        FrameData {
+            render_data: Some(RenderData::Raster2D(VideoFrame {
+                width: 1,
+                height: 1,
+                format: VideoFormat::Rgba8,
+                data: vec![marker, 0, 0, 255],
+            })),
+            audio_data: Some(UnifiedAudioData::Stereo {
+                sample_rate: 48000,
+                channels: 2,
+                samples,
+            }),
+            control_data: None,
+            tally_metadata: TallyMetadata::new(),
+            timestamp: Duration::from_millis(timestamp_ms),
+            sequence: timestamp_ms,
+            checksum: None,
+        }
+    }
+
+    fn video_marker(frame: &FrameData) -> Option<u8> {
+        match &frame.render_data {
+            Some(RenderData::Raster2D(video)) => Some(video.data[0]),
+            _ => None,
+        }
+    }
+
+    fn audio_samples(frame: FrameData) -> Vec<f32> {
+        match frame.audio_data {
+            Some(UnifiedAudioData::Stereo { samples, .. }) => samples,
+            _ => panic!("Expected stereo audio"),
+        }
+    }
+
+    #[test]
+    fn test_video_latency_delays_audio_branch() {
+        let mut pipeline = PipelineProcessor::new();
+        pipeline.add_node(
+            Uuid::new_v4(),
+            Box::new(FixedLatencyNode::new(
+                ConnectionType::RenderData,
+                Duration::from_millis(10),
+            )),
+        );
+        assert_eq!(
+            pipeline.path_latency(),
+            PathLatency {
+                video: Duration::from_millis(10),
+                audio: Duration::ZERO,
+            }
+        );
+
+        // 10ms @ 48kHz = 480フレーム（ステレオで960サンプル）の遅延
+        let block: Vec<f32> = (1..=2000).map(|i| i as f32).collect();
+        let first = pipeline
+            .process_frame(av_frame(0, 1, block.clone()))
+            .unwrap();
+        assert_eq!(video_marker(&first), Some(1));
+        let first = audio_samples(first);
+        assert!(first[..960].iter().all(|&s| s == 0.0));
+        assert_eq!(&first[960..], &block[..2000 - 960]);
+
+        let second = audio_samples(
+            pipeline
+                .process_frame(av_frame(20, 2, block.clone()))
+                .unwrap(),
+        );
+        assert_eq!(&second[..960], &block[2000 - 960..]);
+    }
+
+    #[test]
+    fn test_audio_latency_delays_video_branch() {
+        let mut pipeline = PipelineProcessor::new();
+        pipeline.add_node(
+            Uuid::new_v4(),
+            Box::new(FixedLatencyNode::new(
+                ConnectionType::Audio,
+                Duration::from_millis(20),
+            )),
+        );
+        pipeline.set_frame_rate(100.0).unwrap();
+
+        let block = vec![0.5; 64];
+        let outputs: Vec<Option<u8>> = (0..4)
+            .map(|i| {
+                let frame = pipeline
+                    .process_frame(av_frame(i * 10, i as u8 + 1, block.clone()))
+                    .unwrap();
+                // 音声はそのまま通る
+                assert_eq!(audio_samples(frame.clone()), block);
+                video_marker(&frame)
+            })
+            .collect();
+
+        // 映像は20ms（2フレーム）遅れて出てくる
+        assert_eq!(outputs, vec![None, None, Some(1), Some(2)]);
+    }
+
+    #[test]
+    fn test_video_delay_does_not_depend_on_timestamps() {
+        let mut pipeline = PipelineProcessor::new();
+        pipeline.add_node(
+            Uuid::new_v4(),
+            Box::new(FixedLatencyNode::new(
+                ConnectionType::Audio,
+                Duration::from_millis(100),
+            )),
+        );
+        pipeline.set_frame_rate(30.0).unwrap();
+
+        // タイムスタンプのない入力でも 100ms @ 30fps = 3フレーム遅れて出てくる
+        let outputs: Vec<Option<u8>> = (0..5)
+            .map(|i| {
+                let mut frame = av_frame(0, i + 1, vec![0.0; 8]);
+                frame.timestamp = Duration::ZERO;
+                video_marker(&pipeline.process_frame(frame).unwrap())
+            })
+            .collect();
+        assert_eq!(outputs, vec![None, None, None, Some(1), Some(2)]);
+    }
+
+    #[test]
+    fn test_path_latency_takes_slowest_route() {
+        let mut graph = NodeGraph::new();
+        let mut pipeline = PipelineProcessor::new();
+
+        let source = add_graph_node(
+            &mut pipeline,
+            &mut graph,
+            NodeType::Input(InputType::TestPattern),
+            Box::new(pixel_source([0, 0, 0, 255])),
+        );
+        let branches: Vec<Uuid> = [10, 30]
+            .into_iter()
+            .map(|ms| {
+                add_graph_node(
+                    &mut pipeline,
+                    &mut graph,
+                    NodeType::Effect(EffectType::ColorCorrection),
+                    Box::new(FixedLatencyNode::new(
+                        ConnectionType::RenderData,
+                        Duration::from_millis(ms),
+                    )),
+                )
+            })
+            .collect();
+        let sink = add_graph_node(
+            &mut pipeline,
+            &mut graph,
+            NodeType::Effect(EffectType::ColorCorrection),
+            Box::new(FixedLatencyNode::new(
+                ConnectionType::RenderData,
+                Duration::from_millis(5),
+            )),
+        );
+        for &branch in &branches {
+            graph
+                .connect_nodes(source, branch, ConnectionType::RenderData)
+                .unwrap();
+            graph
+                .connect_nodes(branch, sink, ConnectionType::RenderData)
+                .unwrap();
+        }
+        pipeline.set_graph(&graph).unwrap();
+
+        // 並列の枝は合計せず、遅い方の枝 + 終端の遅延になる
+        assert_eq!(
+            pipeline.path_latency(),
+            PathLatency {
+                video: Duration::from_millis(35),
+                audio: Duration::ZERO,
+            }
+        );
+    }
+
+    /// 入力を無視して固定の音声または映像を出力するソースノード
+    struct ConstantSourceNode {
+        properties: NodeProperties,
+        output: FrameData,
+    }
+
+    impl ConstantSourceNode {
+        fn new(connection: ConnectionType, output: FrameData) -> Self {
+            Self {
+                properties: NodeProperties {
+                    id: Uuid::new_v4(),
+                    name: "Constant Source".to_string(),
+                    node_type: NodeType::Audio(AudioType::Input),
+                    input_types: vec![],
+                    output_types: vec![connection],
+                    parameters: HashMap::new(),
+                    enabled: true,
+                },
+                output,
+            }
+        }
+
+        fn audio(level: f32) -> Self {
+            let mut frame = av_frame(0, 0, vec![level; 8]);
+            frame.render_data = None;
+            Self::new(ConnectionType::Audio, frame)
+        }
+    }
+
+    impl NodeProcessor for ConstantSourceNode {
+        fn process(&mut self, input: FrameData) -> Result<FrameData> {
+            Ok(FrameData {
+                timestamp: input.timestamp,
+                sequence: input.sequence,
+                ..self.output.clone()
+            })
+        }
+
+        fn get_properties(&self) -> NodeProperties {
+            self.properties.clone()
+        }
+
+        fn set_parameter(&mut self, _key: &str, _value: Value) -> Result<()> {
+            Ok(())
+        }
+
+        fn get_parameter(&self, _key: &str) -> Option<Value> {
+            None
+        }
+    }
+
+    /// ノードをパイプラインとグラフの両方に登録する
+    fn add_graph_node(
+        pipeline: &mut PipelineProcessor,
+        graph: &mut NodeGraph,
+        node_type: NodeType,
+        processor: Box<dyn NodeProcessor + Send>,
+    ) -> Uuid {
+        let id = Uuid::new_v4();
+        graph.add_node(Node::new(
+            id,
+            node_type,
+            NodeConfig {
+                parameters: HashMap::new(),
+            },
+        ));
+        pipeline.add_node(id, processor);
+        id
+    }
+
+    #[test]
+    fn test_mixer_receives_every_audio_source() {
+        let mut graph = NodeGraph::new();
+        let mut pipeline = PipelineProcessor::new();
+
+        let quiet = add_graph_node(
+            &mut pipeline,
+            &mut graph,
+            NodeType::Audio(AudioType::Input),
+            Box::new(ConstantSourceNode::audio(0.25)),
+        );
+        let loud = add_graph_node(
+            &mut pipeline,
+            &mut graph,
+            NodeType::Audio(AudioType::Input),
+            Box::new(ConstantSourceNode::audio(0.5)),
+        );
+        let mixer_id = Uuid::new_v4();
+        let mixer = create_node_processor(
+            NodeType::Audio(AudioType::Mixer),
+            mixer_id,
+            NodeConfig {
+                parameters: HashMap::new(),
+            },
+        )
+        .unwrap();
+        graph.add_node(Node::new(
+            mixer_id,
+            NodeType::Audio(AudioType::Mixer),
+            NodeConfig {
+                parameters: HashMap::new(),
+            },
+        ));
+        pipeline.add_node(mixer_id, mixer);
+
+        graph
+            .connect_nodes(quiet, mixer_id, ConnectionType::Audio)
+            .unwrap();
+        graph
+            .connect_nodes(loud, mixer_id, ConnectionType::Audio)
+            .unwrap();
+        pipeline.set_graph(&graph).unwrap();
+        assert_eq!(pipeline.execution_order().last(), Some(&mixer_id));
+
+        let output = pipeline.process_frame(FrameData::empty()).unwrap();
+        let samples = audio_samples(output);
+        assert_eq!(samples.len(), 8);
+        // 両方のソースが加算される
+        assert!(samples.iter().all(|&s| (s - 0.75).abs() < 1e-6));
+        assert_eq!(pipeline.node_metrics()[&mixer_id].samples(), 1);
+    }
+
+    /// 10ms分の左右で異なる値のステレオ音声を出力するソース
+    fn stereo_source(sample_rate: u32, left: f32, right: f32) -> ConstantSourceNode {
+        let frames = sample_rate as usize / 100;
+        let mut frame = av_frame(0, 0, Vec::new());
+        frame.render_data = None;
+        frame.audio_data = Some(UnifiedAudioData::Stereo {
+            sample_rate,
+            channels: 2,
+            samples: [left, right].repeat(frames),
+        });
+        ConstantSourceNode::new(ConnectionType::Audio, frame)
+    }
+
+    #[test]
+    fn test_mixer_combines_sources_at_different_sample_rates() {
+        let mut graph = NodeGraph::new();
+        let mut pipeline = PipelineProcessor::new();
+
+        // 48kHzは左、44.1kHzは右チャンネルだけに音を出す
+        let source_48k = add_graph_node(
+            &mut pipeline,
+            &mut graph,
+            NodeType::Audio(AudioType::Input),
+            Box::new(stereo_source(48000, 0.5, 0.0)),
+        );
+        let source_44k = add_graph_node(
+            &mut pipeline,
+            &mut graph,
+            NodeType::Audio(AudioType::Input),
+            Box::new(stereo_source(44100, 0.0, 0.25)),
+        );
+        let mixer_id = Uuid::new_v4();
+        let config = NodeConfig {
+            parameters: HashMap::new(),
+        };
+        let mixer =
+            create_node_processor(NodeType::Audio(AudioType::Mixer), mixer_id, config.clone())
+                .unwrap();
+        graph.add_node(Node::new(
+            mixer_id,
+            NodeType::Audio(AudioType::Mixer),
+            config,
+        ));
+        pipeline.add_node(mixer_id, mixer);
+
+        graph
+            .connect_nodes(source_48k, mixer_id, ConnectionType::Audio)
+            .unwrap();
+        graph
+            .connect_nodes(source_44k, mixer_id, ConnectionType::Audio)
+            .unwrap();
+        pipeline.set_graph(&graph).unwrap();
+
+        for _ in 0..3 {
+            let output = pipeline.process_frame(FrameData::empty()).unwrap();
+            let Some(UnifiedAudioData::Stereo {
+                sample_rate,
+                samples,
+                ..
+            }) = output.audio_data
+            else {
+                panic!("Expected stereo audio");
+            };
+            // 最初の入力の形式にそろえて両方のソースが聞こえる
+            assert_eq!(sample_rate, 48000);
+            assert_eq!(samples.len(), 480 * 2);
+            for frame in samples[..470 * 2].chunks_exact(2) {
+                assert!((frame[0] - 0.5).abs() < 1e-6);
+                assert!((frame[1] - 0.25).abs() < 1e-6);
+            }
+        }
+    }
+
+    #[test]
+    fn test_remove_node_drops_it_from_graph_inputs() {
+        let mut graph = NodeGraph::new();
+        let mut pipeline = PipelineProcessor::new();
+
+        let video_source = add_graph_node(
+            &mut pipeline,
+            &mut graph,
+            NodeType::Input(InputType::TestPattern),
+            Box::new(pixel_source([10, 20, 30, 255])),
+        );
+        let audio_source = add_graph_node(
+            &mut pipeline,
+            &mut graph,
+            NodeType::Audio(AudioType::Input),
+            Box::new(ConstantSourceNode::audio(0.5)),
+        );
+        let sink = add_graph_node(
+            &mut pipeline,
+            &mut graph,
+            NodeType::Effect(EffectType::ColorCorrection),
+            Box::new(FixedLatencyNode::new(
+                ConnectionType::RenderData,
+                Duration::ZERO,
+            )),
+        );
+        graph
+            .connect_nodes(video_source, sink, ConnectionType::RenderData)
+            .unwrap();
+        graph
+            .connect_nodes(audio_source, sink, ConnectionType::Audio)
+            .unwrap();
+        pipeline.set_graph(&graph).unwrap();
+
+        pipeline.remove_node(&audio_source);
+        assert_eq!(pipeline.graph_inputs[&sink].len(), 1);
+        assert_eq!(pipeline.graph_inputs[&sink][0].0, video_source);
+
+        pipeline.remove_node(&sink);
+        assert!(!pipeline.graph_inputs.contains_key(&sink));
+
+        let output = pipeline.process_frame(FrameData::empty()).unwrap();
+        assert!(output.audio_data.is_none());
+    }
+
+    #[test]
+    fn test_default_process_multi_merges_render_and_audio() {
+        let mut graph = NodeGraph::new();
+        let mut pipeline = PipelineProcessor::new();
+
+        let mut video = av_frame(0, 7, Vec::new());
+        video.audio_data = None;
+        let video_source = add_graph_node(
+            &mut pipeline,
+            &mut graph,
+            NodeType::Input(InputType::TestPattern),
+            Box::new(ConstantSourceNode::new(ConnectionType::RenderData, video)),
+        );
+        let audio_source = add_graph_node(
+            &mut pipeline,
+            &mut graph,
+            NodeType::Audio(AudioType::Input),
+            Box::new(ConstantSourceNode::audio(0.5)),
+        );
+        // process_multiを実装しないノードは入力をまとめたフレームをprocessで受け取る
+        let sink = add_graph_node(
+            &mut pipeline,
+            &mut graph,
+            NodeType::Effect(EffectType::ColorCorrection),
+            Box::new(FixedLatencyNode::new(
+                ConnectionType::RenderData,
+                Duration::ZERO,
+            )),
+        );
+        graph
+            .connect_nodes(video_source, sink, ConnectionType::RenderData)
+            .unwrap();
+        graph
+            .connect_nodes(audio_source, sink, ConnectionType::Audio)
+            .unwrap();
+        pipeline.set_graph(&graph).unwrap();
+
+        let output = pipeline.process_frame(FrameData::empty()).unwrap();
+        assert_eq!(video_marker(&output), Some(7));
+        assert_eq!(audio_samples(output), vec![0.5; 8]);
+    }
+
+    /// 1x1のRgba8画素を出力する映像ソース
+    fn pixel_source(pixel: [u8; 4]) -> ConstantSourceNode {
+        let mut frame = av_frame(0, 0, Vec::new());
+        frame.audio_data = None;
+        frame.render_data = Some(RenderData::Raster2D(VideoFrame {
+            width: 1,
+            height: 1,
+            format: VideoFormat::Rgba8,
+            data: pixel.to_vec(),
+        }));
+        ConstantSourceNode::new(ConnectionType::RenderData, frame)
+    }
+
+    #[test]
+    fn test_composite_blends_two_graph_sources() {
+        let mut graph = NodeGraph::new();
+        let mut pipeline = PipelineProcessor::new();
+
+        let foreground = add_graph_node(
+            &mut pipeline,
+            &mut graph,
+            NodeType::Input(InputType::TestPattern),
+            Box::new(pixel_source([255, 0, 0, 128])),
+        );
+        let background = add_graph_node(
+            &mut pipeline,
+            &mut graph,
+            NodeType::Input(InputType::TestPattern),
+            Box::new(pixel_source([0, 0, 255, 255])),
+        );
+        let composite_id = Uuid::new_v4();
+        let composite = create_node_processor(
+            NodeType::Effect(EffectType::Composite),
+            composite_id,
+            NodeConfig {
+                parameters: HashMap::new(),
+            },
+        )
+        .unwrap();
+        graph.add_node(Node::new(
+            composite_id,
+            NodeType::Effect(EffectType::Composite),
+            NodeConfig {
+                parameters: HashMap::new(),
+            },
+        ));
+        pipeline.add_node(composite_id, composite);
+
+        // 先に接続した入力が前景、後が背景になる
+        graph
+            .connect_nodes(foreground, composite_id, ConnectionType::RenderData)
+            .unwrap();
+        graph
+            .connect_nodes(background, composite_id, ConnectionType::RenderData)
+            .unwrap();
+        pipeline.set_graph(&graph).unwrap();
+
+        let output = pipeline.process_frame(FrameData::empty()).unwrap();
+        let Some(RenderData::Raster2D(frame)) = output.render_data else {
+            panic!("Expected raster output");
+        };
+        // 半透明の赤が不透明の青に重なる
+        assert_eq!(frame.data[0], 128);
+        assert_eq!(frame.data[1], 0);
+        assert!(frame.data[2].abs_diff(127) <= 1);
+        assert_eq!(frame.data[3], 255);
+    }
+
+    /// 受け取ったフレームが持つデータ種別を記録して素通しするノード
+    struct InspectingNode {
+        properties: NodeProperties,
+        seen: std::sync::Arc<std::sync::Mutex<Vec<Vec<ConnectionType>>>>,
+    }
+
+    impl InspectingNode {
+        fn new(
+            connection: ConnectionType,
+        ) -> (
+            Self,
+            std::sync::Arc<std::sync::Mutex<Vec<Vec<ConnectionType>>>>,
+        ) {
+            let seen = std::sync::Arc::new(std::sync::Mutex::new(Vec::new()));
+            let node = Self {
+                properties: NodeProperties {
+                    id: Uuid::new_v4(),
+                    name: "Inspecting".to_string(),
+                    node_type: NodeType::Effect(EffectType::ColorCorrection),
+                    input_types: vec![connection.clone()],
+                    output_types: vec![connection],
+                    parameters: HashMap::new(),
+                    enabled: true,
+                },
+                seen: seen.clone(),
+            };
+            (node, seen)
+        }
+    }
+
+    impl NodeProcessor for InspectingNode {
+        fn process(&mut self, input: FrameData) -> Result<FrameData> {
+            let mut kinds = Vec::new();
+            if input.render_data.is_some() {
+                kinds.push(ConnectionType::RenderData);
+            }
+            if input.audio_data.is_some() {
+                kinds.push(ConnectionType::Audio);
+            }
+            if input.control_data.is_some() {
+                kinds.push(ConnectionType::Control);
+            }
+            self.seen.lock().unwrap().push(kinds);
+            Ok(input)
+        }
+
+        fn get_properties(&self) -> NodeProperties {
+            self.properties.clone()
+        }
+
+        fn set_parameter(&mut self, _key: &str, _value: Value) -> Result<()> {
+            Ok(())
+        }
+
+        fn get_parameter(&self, _key: &str) -> Option<Value> {
+            None
+        }
+    }
+
+    /// 映像・音声・制御をすべて出力するソース
+    fn av_control_source() -> ConstantSourceNode {
+        let mut frame = av_frame(0, 9, vec![0.5; 8]);
+        frame.control_data = Some(ControlData::Transform {
+            target_node_id: None,
+            position: None,
+            rotation: None,
+            scale: None,
+        });
+        ConstantSourceNode::new(ConnectionType::RenderData, frame)
+    }
+
+    #[test]
+    fn test_parallel_branches_only_see_their_connection_type() {
+        let mut graph = NodeGraph::new();
+        let mut pipeline = PipelineProcessor::new();
+
+        let source = add_graph_node(
+            &mut pipeline,
+            &mut graph,
+            NodeType::Input(InputType::TestPattern),
+            Box::new(av_control_source()),
+        );
+        let (video_node, video_seen) = InspectingNode::new(ConnectionType::RenderData);
+        let video = add_graph_node(
+            &mut pipeline,
+            &mut graph,
+            NodeType::Effect(EffectType::ColorCorrection),
+            Box::new(video_node),
+        );
+        let (audio_node, audio_seen) = InspectingNode::new(ConnectionType::Audio);
+        let audio = add_graph_node(
+            &mut pipeline,
+            &mut graph,
+            NodeType::Audio(AudioType::Effect),
+            Box::new(audio_node),
+        );
+        let (control_node, control_seen) = InspectingNode::new(ConnectionType::Control);
+        let control = add_graph_node(
+            &mut pipeline,
+            &mut graph,
+            NodeType::Control(ControlType::Lfo),
+            Box::new(control_node),
+        );
+        graph
+            .connect_nodes(source, video, ConnectionType::RenderData)
+            .unwrap();
+        graph
+            .connect_nodes(source, audio, ConnectionType::Audio)
+            .unwrap();
+        graph
+            .connect_nodes(source, control, ConnectionType::Control)
+            .unwrap();
+        pipeline.set_graph(&graph).unwrap();
+
+        pipeline.process_frame(FrameData::empty()).unwrap();
+        let output = pipeline.process_frame(FrameData::empty()).unwrap();
+
+        assert_eq!(
+            *video_seen.lock().unwrap(),
+            vec![vec![ConnectionType::RenderData]; 2]
+        );
+        assert_eq!(
+            *audio_seen.lock().unwrap(),
+            vec![vec![ConnectionType::Audio]; 2]
+        );
+        // Control線は実行順を縛らないので、送信元より先に実行されると1フレーム遅れて届く
+        let control_seen = control_seen.lock().unwrap();
+        assert!(control_seen
+            .iter()
+            .all(|kinds| kinds.iter().all(|kind| *kind == ConnectionType::Control)));
+        assert_eq!(control_seen.last(), Some(&vec![ConnectionType::Control]));
+
+        // 別々に終わる系統の出力は1フレームにまとまる
+        assert_eq!(video_marker(&output), Some(9));
+        assert!(output.control_data.is_some());
+        assert_eq!(audio_samples(output), vec![0.5; 8]);
+    }
+
+    #[test]
+    fn test_branches_recombine_at_join_node() {
+        let mut graph = NodeGraph::new();
+        let mut pipeline = PipelineProcessor::new();
+
+        let source = add_graph_node(
+            &mut pipeline,
+            &mut graph,
+            NodeType::Input(InputType::TestPattern),
+            Box::new(av_control_source()),
+        );
+        let (video_node, video_seen) = InspectingNode::new(ConnectionType::RenderData);
+        let video = add_graph_node(
+            &mut pipeline,
+            &mut graph,
+            NodeType::Effect(EffectType::ColorCorrection),
+            Box::new(video_node),
+        );
+        let (audio_node, audio_seen) = InspectingNode::new(ConnectionType::Audio);
+        let audio = add_graph_node(
+            &mut pipeline,
+            &mut graph,
+            NodeType::Audio(AudioType::Effect),
+            Box::new(audio_node),
+        );
+        let (join_node, join_seen) = InspectingNode::new(ConnectionType::RenderData);
+        let join = add_graph_node(
+            &mut pipeline,
+            &mut graph,
+            NodeType::Output(OutputType::Preview),
+            Box::new(join_node),
+        );
+        graph
+            .connect_nodes(source, video, ConnectionType::RenderData)
+            .unwrap();
+        graph
+            .connect_nodes(source, audio, ConnectionType::Audio)
+            .unwrap();
+        graph
+            .connect_nodes(video, join, ConnectionType::RenderData)
+            .unwrap();
+        graph
+            .connect_nodes(audio, join, ConnectionType::Audio)
+            .unwrap();
+        pipeline.set_graph(&graph).unwrap();
+
+        let output = pipeline.process_frame(FrameData::empty()).unwrap();
+
+        assert_eq!(
+            *video_seen.lock().unwrap(),
+            vec![vec![ConnectionType::RenderData]]
+        );
+        assert_eq!(
+            *audio_seen.lock().unwrap(),
+            vec![vec![ConnectionType::Audio]]
+        );
+        // 合流点では両系統がまとまり、制御データはどの経路にも流れない
+        assert_eq!(
+            *join_seen.lock().unwrap(),
+            vec![vec![ConnectionType::RenderData, ConnectionType::Audio]]
+        );
+        assert!(output.control_data.is_none());
+        assert_eq!(video_marker(&output), Some(9));
+    }
+
+    /// on_start / on_stopの呼び出し回数を共有カウンタに記録するノード
+    struct StopTrackingNode {
+        properties: NodeProperties,
+        starts: std::sync::Arc<std::sync::atomic::AtomicUsize>,
+        stops: std::sync::Arc<std::sync::atomic::AtomicUsize>,
+        fail_on_start: bool,
+        fail_on_stop: bool,
+    }
+
+    impl StopTrackingNode {
+        fn new(stops: std::sync::Arc<std::sync::atomic::AtomicUsize>, fail_on_stop: bool) -> Self {
+            Self {
+                properties: NodeProperties {
+                    id: Uuid::new_v4(),
+                    name: "Stop Tracking".to_string(),
+                    node_type: NodeType::Effect(EffectType::ColorCorrection),
+                    input_types: vec![ConnectionType::RenderData],
+                    output_types: vec![ConnectionType::RenderData],
+                    parameters: HashMap::new(),
+                    enabled: true,
+                },
+                starts: Default::default(),
+                stops,
+                fail_on_start: false,
+                fail_on_stop,
+            }
+        }
+
+        fn with_starts(
+            starts: std::sync::Arc<std::sync::atomic::AtomicUsize>,
+            stops: std::sync::Arc<std::sync::atomic::AtomicUsize>,
+            fail_on_start: bool,
+        ) -> Self {
+            Self {
+                starts,
+                fail_on_start,
+                ..Self::new(stops, false)
+            }
+        }
+    }
+
+    impl NodeProcessor for StopTrackingNode {
+        fn process(&mut self, input: FrameData) -> Result<FrameData> {
+            Ok(input)
+        }
+
+        fn get_properties(&self) -> NodeProperties {
+            self.properties.clone()
+        }
+
+        fn set_parameter(&mut self, _key: &str, _value: Value) -> Result<()> {
+            Ok(())
+        }
+
+        fn get_parameter(&self, _key: &str) -> Option<Value> {
+            None
+        }
+
+        fn on_start(&mut self) -> Result<()> {
+            self.starts
+                .fetch_add(1, std::sync::atomic::Ordering::SeqCst);
+            if self.fail_on_start {
+                anyhow::bail!("device not found");
+            }
+            Ok(())
+        }
+
+        fn on_stop(&mut self) -> Result<()> {
+            self.stops.fetch_add(1, std::sync::atomic::Ordering::SeqCst);
+            if self.fail_on_stop {
+                anyhow::bail!("device already closed");
+            }
+            Ok(())
+        }
+    }
+
+    #[test]
+    fn test_stop_calls_on_stop_for_each_node() {
+        let stops = std::sync::Arc::new(std::sync::atomic::AtomicUsize::new(0));
+        let mut pipeline = PipelineProcessor::new();
+        for _ in 0..3 {
+            pipeline.add_node(
+                Uuid::new_v4(),
+                Box::new(StopTrackingNode::new(stops.clone(), false)),
+            );
+        }
+
+        pipeline.process_frame(FrameData::empty()).unwrap();
+        pipeline.stop().unwrap();
+        assert_eq!(stops.load(std::sync::atomic::Ordering::SeqCst), 3);
+    }
+
+    #[test]
+    fn test_stop_failure_still_stops_remaining_nodes() {
+        let stops = std::sync::Arc::new(std::sync::atomic::AtomicUsize::new(0));
+        let mut pipeline = PipelineProcessor::new();
+        pipeline.add_node(
+            Uuid::new_v4(),
+            Box::new(StopTrackingNode::new(stops.clone(), true)),
+        );
+        pipeline.add_node(
+            Uuid::new_v4(),
+            Box::new(StopTrackingNode::new(stops.clone(), false)),
+        );
+
+        let error = pipeline.stop().unwrap_err();
+        assert!(error.to_string().contains("device already closed"));
+        assert_eq!(stops.load(std::sync::atomic::Ordering::SeqCst), 2);
+    }
+
+    #[test]
+    fn test_on_start_called_once_before_first_frame() {
+        let starts = std::sync::Arc::new(std::sync::atomic::AtomicUsize::new(0));
+        let stops = std::sync::Arc::new(std::sync::atomic::AtomicUsize::new(0));
+        let mut pipeline = PipelineProcessor::new();
+        for _ in 0..2 {
+            pipeline.add_node(
+                Uuid::new_v4(),
+                Box::new(StopTrackingNode::with_starts(
+                    starts.clone(),
+                    stops.clone(),
+                    false,
+                )),
+            );
+        }
+
+        for _ in 0..3 {
+            pipeline.process_frame(FrameData::empty()).unwrap();
+        }
+        assert_eq!(starts.load(std::sync::atomic::Ordering::SeqCst), 2);
+
+        // 開始後に追加したノードは次のフレームの前に開始される
+        pipeline.add_node(
+            Uuid::new_v4(),
+            Box::new(StopTrackingNode::with_starts(
+                starts.clone(),
+                stops.clone(),
+                false,
+            )),
+        );
+        pipeline.process_frame(FrameData::empty()).unwrap();
+        assert_eq!(starts.load(std::sync::atomic::Ordering::SeqCst), 3);
+    }
+
+    #[test]
+    fn test_on_start_failure_aborts_start() {
+        let starts = std::sync::Arc::new(std::sync::atomic::AtomicUsize::new(0));
+        let stops = std::sync::Arc::new(std::sync::atomic::AtomicUsize::new(0));
+        let mut pipeline = PipelineProcessor::new();
+        let mut graph = NodeGraph::new();
+        let healthy_id = add_graph_node(
+            &mut pipeline,
+            &mut graph,
+            NodeType::Effect(EffectType::ColorCorrection),
+            Box::new(StopTrackingNode::with_starts(
+                starts.clone(),
+                stops.clone(),
+                false,
+            )),
+        );
+        let failing_id = add_graph_node(
+            &mut pipeline,
+            &mut graph,
+            NodeType::Effect(EffectType::ColorCorrection),
+            Box::new(StopTrackingNode::with_starts(
+                starts.clone(),
+                stops.clone(),
+                true,
+            )),
+        );
+        graph
+            .connect_nodes(healthy_id, failing_id, ConnectionType::RenderData)
+            .unwrap();
+        pipeline.set_graph(&graph).unwrap();
+
+        let error = pipeline.process_frame(FrameData::empty()).unwrap_err();
+        assert!(format!("{:#}", error).contains("device not found"));
+        // 先に開始したノードは停止され、フレームは処理されない
+        assert_eq!(starts.load(std::sync::atomic::Ordering::SeqCst), 2);
+        assert_eq!(stops.load(std::sync::atomic::Ordering::SeqCst), 1);
+        assert!(pipeline.node_metrics().is_empty());
+    }
+
+    fn grayscale_pipeline() -> (PipelineProcessor, Uuid) {
+        let mut pipeline = PipelineProcessor::new();
+        let node_id = Uuid::new_v4();
+        let processor = create_node_processor(
+            NodeType::Effect(EffectType::Grayscale),
+            node_id,
+            NodeConfig {
+                parameters: HashMap::new(),
+            },
+        )
+        .unwrap();
+        pipeline.add_node(node_id, processor);
+        (pipeline, node_id)
+    }
+
+    fn colored_frame() -> FrameData {
+        FrameData {
+            render_data: Some(RenderData::Raster2D(VideoFrame {
+                width: 2,
+                height: 1,
+                format: VideoFormat::Rgba8,
+                data: vec![255, 0, 0, 255, 0, 0, 255, 128],
+            })),
+            tally_metadata: TallyMetadata::new().with_program_tally(true),
+            ..FrameData::empty()
+        }
+    }
+
+    fn raster_data(frame: &FrameData) -> Vec<u8> {
+        match &frame.render_data {
+            Some(RenderData::Raster2D(frame)) => frame.data.clone(),
+            _ => panic!("Expected Raster2D output"),
+        }
+    }
+
+    #[test]
+    fn test_enabled_effect_modifies_frame() {
+        let (mut pipeline, _) = grayscale_pipeline();
+
+        let output = pipeline.process_frame(colored_frame()).unwrap();
+        assert_ne!(raster_data(&output), raster_data(&colored_frame()));
+    }
+
+    #[test]
+    fn test_bypassed_effect_passes_frame_through() {
+        let (mut pipeline, node_id) = grayscale_pipeline();
+        assert!(pipeline.set_node_enabled(&node_id, false));
+        assert!(!pipeline.node_properties(&node_id).unwrap().enabled);
+
+        let output = pipeline.process_frame(colored_frame()).unwrap();
+        assert_eq!(raster_data(&output), raster_data(&colored_frame()));
+        // Tallyはバイパス中も下流へ届き、処理時間は記録されない
+        assert!(output.tally_metadata.program_tally);
+        assert!(pipeline.node_metrics().is_empty());
+
+        // 再度有効にすると処理が戻る
+        assert!(pipeline.set_node_enabled(&node_id, true));
+        assert!(pipeline.node_properties(&node_id).unwrap().enabled);
+        let output = pipeline.process_frame(colored_frame()).unwrap();
+        assert_ne!(raster_data(&output), raster_data(&colored_frame()));
+    }
+
+    #[test]
+    fn test_node_output_keeps_last_raster_frame() {
+        let (mut pipeline, node_id) = grayscale_pipeline();
+        // 監視していないノードの出力は控えない
+        pipeline.process_frame(colored_frame()).unwrap();
+        assert!(pipeline.node_output(&node_id).is_none());
+
+        pipeline.watch_node_output(node_id);
+        let output = pipeline.process_frame(colored_frame()).unwrap();
+        assert_eq!(
+            pipeline.node_output(&node_id).unwrap().data,
+            raster_data(&output)
+        );
+
+        // 映像のないフレームでは控えも消える
+        pipeline.process_frame(FrameData::empty()).unwrap();
+        assert!(pipeline.node_output(&node_id).is_none());
+
+        pipeline.process_frame(colored_frame()).unwrap();
+        pipeline.remove_node(&node_id);
+        assert!(pipeline.node_output(&node_id).is_none());
+        assert_eq!(pipeline.processed_frames(), 4);
+    }
+
+    #[test]
+    fn test_unwatch_node_output_drops_recorded_frame() {
+        let (mut pipeline, node_id) = grayscale_pipeline();
+        pipeline.watch_node_output(node_id);
+        pipeline.watch_node_output(node_id);
+        pipeline.process_frame(colored_frame()).unwrap();
+
+        // 監視者が残っている間は控えを保つ
+        pipeline.unwatch_node_output(&node_id);
+        assert!(pipeline.node_output(&node_id).is_some());
+        pipeline.unwatch_node_output(&node_id);
+        assert!(pipeline.node_output(&node_id).is_none());
+
+        pipeline.process_frame(colored_frame()).unwrap();
+        assert!(pipeline.node_output(&node_id).is_none());
+        // 対応しない取り消しは無視する
+        pipeline.unwatch_node_output(&node_id);
+    }
+
+    #[test]
+    fn test_set_node_enabled_rejects_unknown_node() {
+        let (mut pipeline, _) = grayscale_pipeline();
+        assert!(!pipeline.set_node_enabled(&Uuid::new_v4(), false));
+    }
+
+    /// `node` スパンのフィールドを記録するテスト用レイヤー
+    #[derive(Clone, Default)]
+    struct NodeSpanCapture {
+        spans: std::sync::Arc<std::sync::Mutex<HashMap<u64, HashMap<String, String>>>>,
+    }
+
+    struct FieldVisitor<'a>(&'a mut HashMap<String, String>);
+
+    impl tracing::field::Visit for FieldVisitor<'_> {
+        fn record_debug(&mut self, field: &tracing::field::Field, value: &dyn std::fmt::Debug) {
+            self.0
+                .insert(field.name().to_string(), format!("{:?}", value));
+        }
+    }
+
+    impl<S: tracing::Subscriber> tracing_subscriber::Layer<S> for NodeSpanCapture {
+        fn on_new_span(
+            &self,
+            attrs: &tracing::span::Attributes<'_>,
+            id: &tracing::span::Id,
+            _ctx: tracing_subscriber::layer::Context<'_, S>,
+        ) {
+            if attrs.metadata().name() != "node" {
+                return;
+            }
+            let mut fields = HashMap::new();
+            attrs.record(&mut FieldVisitor(&mut fields));
+            self.spans.lock().unwrap().insert(id.into_u64(), fields);
+        }
+
+        fn on_record(
+            &self,
+            id: &tracing::span::Id,
+            values: &tracing::span::Record<'_>,
+            _ctx: tracing_subscriber::layer::Context<'_, S>,
+        ) {
+            if let Some(fields) = self.spans.lock().unwrap().get_mut(&id.into_u64()) {
+                values.record(&mut FieldVisitor(fields));
+            }
+        }
+    }
+
+    #[test]
+    fn test_node_span_carries_node_fields_and_duration() {
+        use tracing_subscriber::layer::SubscriberExt;
+
+        let capture = NodeSpanCapture::default();
+        let subscriber = tracing_subscriber::registry().with(capture.clone());
+
+        let (mut pipeline, node_id) = grayscale_pipeline();
+        tracing::subscriber::with_default(subscriber, || {
+            pipeline.process_frame(colored_frame()).unwrap();
+        });
+
+        let spans = capture.spans.lock().unwrap();
+        assert_eq!(spans.len(), 1);
+        let fields = spans.values().next().unwrap();
+        assert_eq!(fields["node_id"], node_id.to_string());
+        assert_eq!(fields["node_type"], "Effect(Grayscale)");
+        // process の所要時間がスパンに記録される
+        assert!(fields["duration_us"].parse::<u64>().is_ok());
+    }
+
+    fn checksummed_grayscale_pipeline() -> PipelineProcessor {
+        let (mut pipeline, _) = grayscale_pipeline();
+        pipeline.set_frame_checksums(true);
+        pipeline
+    }
+
+    #[test]
+    fn test_frame_checksums_are_off_by_default() {
+        let (mut pipeline, _) = grayscale_pipeline();
+        assert!(!pipeline.frame_checksums_enabled());
+
+        let output = pipeline.process_frame(colored_frame()).unwrap();
+        assert_eq!(output.checksum, None);
+    }
+
+    #[test]
+    fn test_frame_checksum_passes_through_modifying_nodes() {
+        let mut graph = NodeGraph::new();
+        let mut pipeline = PipelineProcessor::new();
+        pipeline.set_frame_checksums(true);
+
+        let chain = [
+            NodeType::Effect(EffectType::Grayscale),
+            NodeType::Effect(EffectType::ColorCorrection),
+            NodeType::Effect(EffectType::Grayscale),
+        ];
+        let ids: Vec<Uuid> = chain.iter().map(|_| Uuid::new_v4()).collect();
+        for (node_type, &id) in chain.iter().zip(ids.iter()) {
+            let config = NodeConfig {
+                parameters: HashMap::new(),
+            };
+            graph.add_node(Node::new(id, node_type.clone(), config.clone()));
+            pipeline.add_node(
+                id,
+                create_node_processor(node_type.clone(), id, config).unwrap(),
+            );
+        }
+        for pair in ids.windows(2) {
+            graph
+                .connect_nodes(pair[0], pair[1], ConnectionType::RenderData)
+                .unwrap();
+        }
+        pipeline.set_graph(&graph).unwrap();
+
+        // 各ノードが映像を書き換えても、出力ごとに付け直すので不一致にならない
+        let output = pipeline.process_frame(colored_frame()).unwrap();
+        assert_eq!(pipeline.checksum_mismatches(), 0);
+        assert_eq!(output.checksum, output.compute_checksum());
+        assert!(output.checksum.is_some());
+    }
+
+    #[test]
+    fn test_frame_checksum_detects_corruption_between_pipelines() {
+        let mut first = checksummed_grayscale_pipeline();
+        let mut second = checksummed_grayscale_pipeline();
+
+        let mut frame = first.process_frame(colored_frame()).unwrap();
+        assert!(frame.checksum.is_some());
+
+        // 受け渡しの途中で1バイト壊す
+        if let Some(RenderData::Raster2D(ref mut video_frame)) = frame.render_data {
+            video_frame.data[0] ^= 0x01;
+        }
+        second.process_frame(frame).unwrap();
+
+        assert_eq!(first.checksum_mismatches(), 0);
+        assert_eq!(second.checksum_mismatches(), 1);
+    }
+
+    #[test]
+    fn test_frame_checksum_verifies_source_frame() {
+        let mut pipeline = checksummed_grayscale_pipeline();
+
+        // ソース側で付けたCRC32と一致するフレームは通る
+        let mut frame = colored_frame();
+        frame.stamp_checksum();
+        pipeline.process_frame(frame.clone()).unwrap();
+        assert_eq!(pipeline.checksum_mismatches(), 0);
+
+        // 付けた後に書き換わったフレームは最初のノードで検出される
+        if let Some(RenderData::Raster2D(ref mut video_frame)) = frame.render_data {
+            video_frame.data[4] = 1;
+        }
+        pipeline.process_frame(frame).unwrap();
+        assert_eq!(pipeline.checksum_mismatches(), 1);
+    }
+
+    #[test]
+    fn test_frame_checksum_mismatches_are_recorded_in_telemetry() {
+        let telemetry = Arc::new(TelemetryManager::new());
+        let mut pipeline = checksummed_grayscale_pipeline();
+        pipeline.set_telemetry(telemetry.clone());
+
+        let mut frame = colored_frame();
+        frame.stamp_checksum();
+        if let Some(RenderData::Raster2D(ref mut video_frame)) = frame.render_data {
+            video_frame.data[4] = 1;
+        }
+        pipeline.process_frame(frame).unwrap();
+
+        assert_eq!(telemetry.get_session_stats().checksum_mismatches, 1);
+    }
 }
@@ -0,0 +1,90 @@
+/*
+ * Constellation Studio - Professional Real-time Video Processing
+ * Copyright (c) 2025 MACHIKO LAB
+ *
+ * This program is free software: you can redistribute it and/or modify
+ * it under the terms of the GNU Affero General Public License as published by
+ * the Free Software Foundation, either version 3 of the License, or
+ * (at your option) any later version.
+ *
+ * This program is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+ * GNU Affero General Public License for more details.
+ *
+ * You should have received a copy of the GNU Affero General Public License
+ * along with this program. If not, see <https://www.gnu.org/licenses/>.
+ */
+
+//! ノード単位の処理時間計測
+
+use std::collections::VecDeque;
+use std::time::Duration;
+
+/// 移動平均に使う直近サンプル数
+const ROLLING_WINDOW: usize = 60;
+
+/// 1ノードの処理時間（直近 `ROLLING_WINDOW` フレームの移動平均）
+#[derive(Debug, Clone, Default)]
+pub struct NodeTiming {
+    window: VecDeque<Duration>,
+    window_total: Duration,
+    total_samples: u64,
+}
+
+impl NodeTiming {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn record(&mut self, elapsed: Duration) {
+        if self.window.len() == ROLLING_WINDOW {
+            if let Some(oldest) = self.window.pop_front() {
+                self.window_total -= oldest;
+            }
+        }
+        self.window.push_back(elapsed);
+        self.window_total += elapsed;
+        self.total_samples += 1;
+    }
+
+    pub fn average(&self) -> Duration {
+        if self.window.is_empty() {
+            return Duration::ZERO;
+        }
+        self.window_total / self.window.len() as u32
+    }
+
+    pub fn last(&self) -> Duration {
+        self.window.back().copied().unwrap_or_default()
+    }
+
+    /// これまでに計測したフレーム数（窓から外れた分も含む）
+    pub fn samples(&self) -> u64 {
+        self.total_samples
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_average_covers_only_recent_window() {
+        let mut timing = NodeTiming::new();
+        assert_eq!(timing.average(), Duration::ZERO);
+
+        for _ in 0..ROLLING_WINDOW {
+            timing.record(Duration::from_millis(10));
+        }
+        assert_eq!(timing.average(), Duration::from_millis(10));
+
+        // 古いサンプルが窓から押し出される
+        for _ in 0..ROLLING_WINDOW {
+            timing.record(Duration::from_millis(2));
+        }
+        assert_eq!(timing.average(), Duration::from_millis(2));
+        assert_eq!(timing.last(), Duration::from_millis(2));
+        assert_eq!(timing.samples(), 2 * ROLLING_WINDOW as u64);
+    }
+}
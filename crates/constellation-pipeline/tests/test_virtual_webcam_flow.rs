@@ -20,6 +20,7 @@ use constellation_core::*;
 use constellation_nodes::*;
 use constellation_pipeline::*;
 use std::collections::HashMap;
+use std::time::Duration;
 use uuid::Uuid;
 
 #[test]
@@ -100,6 +101,9 @@ fn test_virtual_webcam_flow() {
         audio_data: None,
         control_data: None,
         tally_metadata: TallyMetadata::new(),
+        timestamp: Duration::ZERO,
+        sequence: 0,
+        checksum: None,
     };
 
     // パイプラインで処理
@@ -19,6 +19,9 @@
 use ash::vk;
 use ash::{Device, Entry, Instance};
 use std::collections::{HashMap, VecDeque};
+use std::path::{Path, PathBuf};
+use std::sync::{Arc, Condvar, Mutex, MutexGuard};
+use std::time::{Duration, Instant};
 use thiserror::Error;
 
 /// Vulkan固有のエラー型
@@ -42,6 +45,23 @@ pub enum VulkanError {
 
 pub type VulkanResult<T> = std::result::Result<T, VulkanError>;
 
+/// GPUの静的な情報（ハードウェア互換性チェック用）
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PhysicalDeviceInfo {
+    pub name: String,
+    pub vendor_id: u32,
+    pub device_id: u32,
+    pub device_type: vk::PhysicalDeviceType,
+    /// (major, minor, patch)
+    pub api_version: (u32, u32, u32),
+    /// ベンダー固有のエンコードのまま
+    pub driver_version: u32,
+    /// DEVICE_LOCALヒープの合計
+    pub device_local_memory_bytes: u64,
+    pub features: Vec<String>,
+    pub score: u32,
+}
+
 pub struct VulkanContext {
     pub entry: Entry,
     pub instance: Instance,
@@ -57,14 +77,121 @@ pub struct VulkanContext {
 }
 
 impl VulkanContext {
+    /// 最もスコアの高いGPUでコンテキストを作成する
     pub fn new() -> VulkanResult<Self> {
+        Self::create(None)
+    }
+
+    /// `list_physical_devices` の順序で指定したGPUでコンテキストを作成する
+    pub fn new_with_device_index(device_index: usize) -> VulkanResult<Self> {
+        Self::create(Some(device_index))
+    }
+
+    /// 利用可能なGPUの名前とスコアをスコアの高い順に返す
+    pub fn list_physical_devices() -> Vec<(String, u32)> {
+        let Ok(entry) = (unsafe { Entry::load() }) else {
+            return Vec::new();
+        };
+        let Ok(instance) = Self::create_instance(&entry) else {
+            return Vec::new();
+        };
+
+        let devices = Self::scored_physical_devices(&instance)
+            .map(|devices| {
+                devices
+                    .into_iter()
+                    .map(|(device, score)| (Self::device_name(&instance, device), score))
+                    .collect()
+            })
+            .unwrap_or_default();
+
+        unsafe { instance.destroy_instance(None) };
+        devices
+    }
+
+    /// 利用可能なGPUの詳細をスコアの高い順に返す（Vulkanが使えない場合は空）
+    pub fn describe_physical_devices() -> Vec<PhysicalDeviceInfo> {
+        let Ok(entry) = (unsafe { Entry::load() }) else {
+            return Vec::new();
+        };
+        let Ok(instance) = Self::create_instance(&entry) else {
+            return Vec::new();
+        };
+
+        let devices = Self::scored_physical_devices(&instance)
+            .map(|devices| {
+                devices
+                    .into_iter()
+                    .map(|(device, score)| Self::describe_device(&instance, device, score))
+                    .collect()
+            })
+            .unwrap_or_default();
+
+        unsafe { instance.destroy_instance(None) };
+        devices
+    }
+
+    fn describe_device(
+        instance: &Instance,
+        device: vk::PhysicalDevice,
+        score: u32,
+    ) -> PhysicalDeviceInfo {
+        let properties = unsafe { instance.get_physical_device_properties(device) };
+        let device_features = unsafe { instance.get_physical_device_features(device) };
+
+        let mut features = Vec::new();
+        if device_features.geometry_shader == vk::TRUE {
+            features.push("geometryShader".to_string());
+        }
+        if device_features.tessellation_shader == vk::TRUE {
+            features.push("tessellationShader".to_string());
+        }
+        if device_features.shader_storage_image_write_without_format == vk::TRUE {
+            features.push("shaderStorageImageWriteWithoutFormat".to_string());
+        }
+
+        PhysicalDeviceInfo {
+            name: Self::device_name(instance, device),
+            vendor_id: properties.vendor_id,
+            device_id: properties.device_id,
+            device_type: properties.device_type,
+            api_version: (
+                vk::api_version_major(properties.api_version),
+                vk::api_version_minor(properties.api_version),
+                vk::api_version_patch(properties.api_version),
+            ),
+            driver_version: properties.driver_version,
+            device_local_memory_bytes: Self::device_local_memory(instance, device),
+            features,
+            score,
+        }
+    }
+
+    fn device_local_memory(instance: &Instance, device: vk::PhysicalDevice) -> u64 {
+        let memory_properties = unsafe { instance.get_physical_device_memory_properties(device) };
+        memory_properties
+            .memory_heaps
+            .iter()
+            .take(memory_properties.memory_heap_count as usize)
+            .filter(|heap| heap.flags.contains(vk::MemoryHeapFlags::DEVICE_LOCAL))
+            .map(|heap| heap.size)
+            .sum()
+    }
+
+    fn create(device_index: Option<usize>) -> VulkanResult<Self> {
         let entry = unsafe {
             Entry::load().map_err(|e| VulkanError::InitializationFailed {
                 reason: format!("Failed to load Vulkan library: {e:?}"),
             })?
         };
         let instance = Self::create_instance(&entry)?;
-        let physical_device = Self::select_physical_device(&instance)?;
+        let physical_device = match Self::select_physical_device(&instance, device_index) {
+            Ok(device) => device,
+            Err(e) => {
+                unsafe { instance.destroy_instance(None) };
+                return Err(e);
+            }
+        };
         let (device, queue_family_indices) =
             Self::create_logical_device(&instance, physical_device)?;
 
@@ -130,7 +257,43 @@ impl VulkanContext {
         }
     }
 
-    fn select_physical_device(instance: &Instance) -> VulkanResult<vk::PhysicalDevice> {
+    fn select_physical_device(
+        instance: &Instance,
+        device_index: Option<usize>,
+    ) -> VulkanResult<vk::PhysicalDevice> {
+        let scored_devices = Self::scored_physical_devices(instance)?;
+        if scored_devices.is_empty() {
+            return Err(VulkanError::HardwareNotSupported {
+                hardware: "No suitable GPU found with required features for video processing"
+                    .to_string(),
+            });
+        }
+
+        let index = device_index.unwrap_or(0);
+        let (device, score) =
+            scored_devices
+                .get(index)
+                .ok_or_else(|| VulkanError::HardwareNotSupported {
+                    hardware: format!(
+                        "GPU index {} out of range ({} suitable devices)",
+                        index,
+                        scored_devices.len()
+                    ),
+                })?;
+
+        tracing::info!(
+            "Selected GPU {} with score {}: {}",
+            index,
+            score,
+            Self::device_name(instance, *device)
+        );
+        Ok(*device)
+    }
+
+    /// 適合するGPUをスコアの高い順に並べる
+    fn scored_physical_devices(
+        instance: &Instance,
+    ) -> VulkanResult<Vec<(vk::PhysicalDevice, u32)>> {
         let physical_devices = unsafe {
             instance.enumerate_physical_devices().map_err(|e| {
                 VulkanError::HardwareNotSupported {
@@ -146,27 +309,22 @@ impl VulkanContext {
             .filter(|(_, score)| *score > 0) // Only include suitable devices
             .collect();
 
-        // Sort by score (highest first)
-        scored_devices.sort_by(|a, b| b.1.cmp(&a.1));
+        // Sort by score (highest first); stable so equal scores keep enumeration order
+        scored_devices.sort_by_key(|(_, score)| std::cmp::Reverse(*score));
+        Ok(scored_devices)
+    }
 
-        scored_devices
-            .first()
-            .map(|(device, score)| {
-                tracing::info!("Selected GPU with score {}: {:?}", score, unsafe {
-                    instance.get_physical_device_properties(*device).device_name
-                });
-                *device
-            })
-            .ok_or_else(|| VulkanError::HardwareNotSupported {
-                hardware: "No suitable GPU found with required features for video processing"
-                    .to_string(),
-            })
+    fn device_name(instance: &Instance, device: vk::PhysicalDevice) -> String {
+        let properties = unsafe { instance.get_physical_device_properties(device) };
+        properties
+            .device_name_as_c_str()
+            .map(|name| name.to_string_lossy().into_owned())
+            .unwrap_or_else(|_| "Unknown GPU".to_string())
     }
 
     fn score_device(instance: &Instance, device: vk::PhysicalDevice) -> u32 {
         let properties = unsafe { instance.get_physical_device_properties(device) };
         let features = unsafe { instance.get_physical_device_features(device) };
-        let memory_properties = unsafe { instance.get_physical_device_memory_properties(device) };
 
         let mut score = 0;
 
@@ -183,13 +341,7 @@ impl VulkanContext {
         }
 
         // Memory size scoring (critical for 4K+ video processing)
-        let total_memory: u64 = memory_properties
-            .memory_heaps
-            .iter()
-            .take(memory_properties.memory_heap_count as usize)
-            .filter(|heap| heap.flags.contains(vk::MemoryHeapFlags::DEVICE_LOCAL))
-            .map(|heap| heap.size)
-            .sum();
+        let total_memory = Self::device_local_memory(instance, device);
 
         if total_memory >= 8 * 1024 * 1024 * 1024 {
             // 8GB+
@@ -510,12 +662,13 @@ pub struct FrameSize {
 
 #[derive(Debug, Clone, Hash, PartialEq, Eq)]
 pub enum FrameFormat {
-    Rgba8, // 4 bytes per pixel
-    Bgra8, // 4 bytes per pixel
-    Rgb8,  // 3 bytes per pixel
-    R8,    // 1 byte per pixel
-    R16,   // 2 bytes per pixel
-    R32F,  // 4 bytes per pixel (float)
+    Rgba8,  // 4 bytes per pixel
+    Bgra8,  // 4 bytes per pixel
+    Rgb8,   // 3 bytes per pixel
+    R8,     // 1 byte per pixel
+    R16,    // 2 bytes per pixel
+    R32F,   // 4 bytes per pixel (float)
+    Rgba16, // 8 bytes per pixel (four R16 channels)
 }
 
 impl FrameFormat {
@@ -523,6 +676,7 @@ impl FrameFormat {
         match self {
             FrameFormat::Rgba8 | FrameFormat::Bgra8 | FrameFormat::R32F => 4,
             FrameFormat::Rgb8 => 3,
+            FrameFormat::Rgba16 => 4 * FrameFormat::R16.bytes_per_pixel(),
             FrameFormat::R16 => 2,
             FrameFormat::R8 => 1,
         }
@@ -541,12 +695,126 @@ struct MemoryPool {
     buffer_size: u64,
     #[allow(dead_code)] // Phase 2: Will be used for pool size validation
     buffer_count: u32,
-    free_buffers: VecDeque<u32>, // Buffer indices
+    slots: Arc<PoolSlots>, // Free buffer indices, shared with releasers
     #[allow(dead_code)] // Phase 2: Will be used for sub-allocation within pools
     allocation_offset: u64,
     memory_type_index: u32,
 }
 
+/// Per-pool usage statistics
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct PoolStats {
+    pub buffer_count: u32,
+    pub in_use: u32,
+    pub high_water_mark: u32,
+    pub failed_acquires: u64,
+}
+
+/// Free-list bookkeeping for a pool; shared across threads so buffers can be
+/// released while another thread waits in `acquire_frame_buffer_timeout`
+struct PoolSlots {
+    state: Mutex<PoolSlotState>,
+    available: Condvar,
+}
+
+struct PoolSlotState {
+    free_buffers: VecDeque<u32>, // Buffer indices
+    stats: PoolStats,
+}
+
+impl PoolSlots {
+    fn new(buffer_count: u32) -> Self {
+        Self {
+            state: Mutex::new(PoolSlotState {
+                free_buffers: (0..buffer_count).collect(),
+                stats: PoolStats {
+                    buffer_count,
+                    ..Default::default()
+                },
+            }),
+            available: Condvar::new(),
+        }
+    }
+
+    fn lock(&self) -> MutexGuard<'_, PoolSlotState> {
+        // Bookkeeping stays consistent even if a holder panicked
+        self.state.lock().unwrap_or_else(|e| e.into_inner())
+    }
+
+    fn try_acquire(&self) -> Option<u32> {
+        let mut state = self.lock();
+        let index = state.take_free();
+        if index.is_none() {
+            state.stats.failed_acquires += 1;
+        }
+        index
+    }
+
+    fn acquire_timeout(&self, timeout: Duration) -> Option<u32> {
+        let deadline = Instant::now() + timeout;
+        let mut state = self.lock();
+        loop {
+            if let Some(index) = state.take_free() {
+                return Some(index);
+            }
+
+            let now = Instant::now();
+            if now >= deadline {
+                state.stats.failed_acquires += 1;
+                return None;
+            }
+            state = self
+                .available
+                .wait_timeout(state, deadline - now)
+                .unwrap_or_else(|e| e.into_inner())
+                .0;
+        }
+    }
+
+    fn release(&self, buffer_index: u32) {
+        let mut state = self.lock();
+        state.free_buffers.push_back(buffer_index);
+        state.stats.in_use = state.stats.in_use.saturating_sub(1);
+        drop(state);
+        self.available.notify_one();
+    }
+
+    fn stats(&self) -> PoolStats {
+        self.lock().stats.clone()
+    }
+}
+
+impl PoolSlotState {
+    fn take_free(&mut self) -> Option<u32> {
+        let index = self.free_buffers.pop_front()?;
+        self.stats.in_use += 1;
+        self.stats.high_water_mark = self.stats.high_water_mark.max(self.stats.in_use);
+        Some(index)
+    }
+}
+
+/// Returns pooled buffers from any thread, waking waiters blocked in
+/// `MemoryManager::acquire_frame_buffer_timeout`
+#[derive(Clone)]
+pub struct FrameBufferReleaser {
+    frame_size: FrameSize,
+    slots: Arc<PoolSlots>,
+}
+
+impl FrameBufferReleaser {
+    pub fn release(&self, frame_buffer: PooledFrameBuffer) {
+        if frame_buffer.pool_frame_size != self.frame_size {
+            tracing::warn!(
+                "Frame buffer for {:?} released to pool {:?}, ignoring",
+                frame_buffer.pool_frame_size,
+                self.frame_size
+            );
+            return;
+        }
+        self.slots.release(frame_buffer.buffer_index);
+    }
+}
+
 impl MemoryManager {
     pub fn new(context: &VulkanContext) -> VulkanResult<Self> {
         let memory_properties = unsafe {
@@ -656,16 +924,11 @@ impl MemoryManager {
                 })?
         };
 
-        let mut free_buffers = VecDeque::new();
-        for i in 0..buffer_count {
-            free_buffers.push_back(i);
-        }
-
         let pool = MemoryPool {
             memory,
             buffer_size,
             buffer_count,
-            free_buffers,
+            slots: Arc::new(PoolSlots::new(buffer_count)),
             allocation_offset: 0,
             memory_type_index,
         };
@@ -683,19 +946,43 @@ impl MemoryManager {
         &mut self,
         frame_size: &FrameSize,
     ) -> VulkanResult<PooledFrameBuffer> {
-        let pool = self.frame_pools.get_mut(frame_size).ok_or_else(|| {
-            VulkanError::InsufficientMemory {
-                required_bytes: frame_size.buffer_size(),
-            }
-        })?;
+        self.acquire_pooled(frame_size, |slots| slots.try_acquire())
+    }
 
-        let buffer_index =
-            pool.free_buffers
-                .pop_front()
+    /// Acquire frame buffer, waiting up to `timeout` for another thread to
+    /// release one through a `FrameBufferReleaser` when the pool is exhausted
+    pub fn acquire_frame_buffer_timeout(
+        &mut self,
+        frame_size: &FrameSize,
+        timeout: Duration,
+    ) -> VulkanResult<PooledFrameBuffer> {
+        self.acquire_pooled(frame_size, |slots| slots.acquire_timeout(timeout))
+    }
+
+    fn acquire_pooled(
+        &mut self,
+        frame_size: &FrameSize,
+        take: impl FnOnce(&PoolSlots) -> Option<u32>,
+    ) -> VulkanResult<PooledFrameBuffer> {
+        let pool =
+            self.frame_pools
+                .get(frame_size)
                 .ok_or_else(|| VulkanError::InsufficientMemory {
                     required_bytes: frame_size.buffer_size(),
                 })?;
 
+        let buffer_index = take(&pool.slots).ok_or_else(|| {
+            tracing::debug!(
+                "Frame pool {}x{} {:?} exhausted",
+                frame_size.width,
+                frame_size.height,
+                frame_size.format
+            );
+            VulkanError::InsufficientMemory {
+                required_bytes: frame_size.buffer_size(),
+            }
+        })?;
+
         self.allocation_count += 1;
 
         tracing::trace!(
@@ -718,8 +1005,8 @@ impl MemoryManager {
 
     /// Return frame buffer to pool for reuse
     pub fn release_frame_buffer(&mut self, frame_buffer: PooledFrameBuffer) {
-        if let Some(pool) = self.frame_pools.get_mut(&frame_buffer.pool_frame_size) {
-            pool.free_buffers.push_back(frame_buffer.buffer_index);
+        if let Some(pool) = self.frame_pools.get(&frame_buffer.pool_frame_size) {
+            pool.slots.release(frame_buffer.buffer_index);
 
             tracing::trace!(
                 "Released frame buffer {} to pool {}x{} {:?}",
@@ -763,11 +1050,59 @@ impl MemoryManager {
         Ok(FrameBuffer::new(device_memory, size))
     }
 
+    /// Releaser that can return buffers of this pool from other threads
+    pub fn frame_buffer_releaser(&self, frame_size: &FrameSize) -> Option<FrameBufferReleaser> {
+        self.frame_pools
+            .get(frame_size)
+            .map(|pool| FrameBufferReleaser {
+                frame_size: frame_size.clone(),
+                slots: Arc::clone(&pool.slots),
+            })
+    }
+
+    /// Free every pre-allocated frame pool
+    /// Buffers acquired from the pools must no longer be in use by the GPU
+    pub fn release_frame_pools(&mut self) {
+        for (frame_size, pool) in self.frame_pools.drain() {
+            unsafe {
+                self.device.free_memory(pool.memory, None);
+            }
+            let pool_size = pool.buffer_size * pool.buffer_count as u64;
+            self.total_allocated = self.total_allocated.saturating_sub(pool_size);
+
+            tracing::info!(
+                "Released frame pool {}x{} {:?}",
+                frame_size.width,
+                frame_size.height,
+                frame_size.format
+            );
+        }
+    }
+
+    pub fn pool_stats(&self, frame_size: &FrameSize) -> Option<PoolStats> {
+        self.frame_pools
+            .get(frame_size)
+            .map(|pool| pool.slots.stats())
+    }
+
     pub fn get_memory_usage(&self) -> MemoryUsage {
+        let pool_stats: Vec<(FrameSize, PoolStats)> = self
+            .frame_pools
+            .iter()
+            .map(|(frame_size, pool)| (frame_size.clone(), pool.slots.stats()))
+            .collect();
+
         MemoryUsage {
             total_allocated: self.total_allocated,
             free_blocks: 0, // No longer using legacy free blocks
             total_pools: self.frame_pools.len(),
+            peak_allocation: self.peak_allocation,
+            buffers_in_use: pool_stats.iter().map(|(_, stats)| stats.in_use).sum(),
+            failed_acquires: pool_stats
+                .iter()
+                .map(|(_, stats)| stats.failed_acquires)
+                .sum(),
+            pool_stats,
         }
     }
 }
@@ -790,16 +1125,55 @@ pub struct ComputePipelineManager {
     pipelines: HashMap<VideoOperation, ComputePipeline>,
     descriptor_set_layout: vk::DescriptorSetLayout,
     pipeline_layout: vk::PipelineLayout,
+    descriptor_pool: vk::DescriptorPool,
+    memory_properties: vk::PhysicalDeviceMemoryProperties,
+    // Linear-tiled storage images let us bind pooled frame memory directly
+    linear_storage_supported: bool,
+    shader_dir: PathBuf,
+    // Resources referenced by recorded command buffers, freed once the GPU is done
+    in_flight: Vec<DispatchResources>,
+    // Descriptor sets returned by completed dispatches, reused before allocating new ones
+    recycled_sets: Vec<vk::DescriptorSet>,
 }
 
 /// Individual compute pipeline for specific video processing operations
 pub struct ComputePipeline {
     pipeline: vk::Pipeline,
+    shader_module: vk::ShaderModule,
     workgroup_size: [u32; 3],
     #[allow(dead_code)] // Phase 2: Will be used for pipeline management and validation
     operation_type: VideoOperation,
 }
 
+impl ComputePipeline {
+    /// Whether the pipeline is backed by a real SPIR-V shader
+    pub fn is_loaded(&self) -> bool {
+        self.pipeline != vk::Pipeline::null()
+    }
+
+    pub fn workgroup_size(&self) -> [u32; 3] {
+        self.workgroup_size
+    }
+}
+
+/// Per-dispatch Vulkan objects that must outlive command buffer execution
+/// Created by `ComputePipelineManager::allocate_descriptor_set` and handed back
+/// through `recycle_descriptor_set` once the GPU is done with them.
+pub struct DispatchResources {
+    images: [vk::Image; 2],
+    image_views: [vk::ImageView; 2],
+    params_buffer: vk::Buffer,
+    params_memory: vk::DeviceMemory,
+    descriptor_set: vk::DescriptorSet,
+}
+
+impl DispatchResources {
+    /// Descriptor set with the input image, output image and params UBO bound
+    pub fn descriptor_set(&self) -> vk::DescriptorSet {
+        self.descriptor_set
+    }
+}
+
 /// Supported video processing operations for compute shaders
 #[derive(Debug, Clone, Hash, PartialEq, Eq)]
 pub enum VideoOperation {
@@ -809,12 +1183,99 @@ pub enum VideoOperation {
     Sharpen,              // Sharpening filter
     ColorCorrection,      // Brightness/contrast/saturation
     Flip,                 // Horizontal/vertical flip
+    Grayscale,            // BT.709 luma desaturation
+}
+
+/// Uniform buffer layout for `shaders/blur.comp` (std140)
+#[repr(C)]
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct BlurParams {
+    pub radius: i32,
+    pub sigma: f32,
+    _padding: [u32; 2],
+}
+
+impl BlurParams {
+    /// Create params with sigma derived from the radius (radius / 2)
+    pub fn new(radius: i32) -> Self {
+        Self::with_sigma(radius, (radius.max(1) as f32) / 2.0)
+    }
+
+    pub fn with_sigma(radius: i32, sigma: f32) -> Self {
+        Self {
+            radius,
+            sigma,
+            _padding: [0; 2],
+        }
+    }
+
+    pub fn as_bytes(&self) -> &[u8] {
+        unsafe {
+            std::slice::from_raw_parts(
+                (self as *const Self) as *const u8,
+                std::mem::size_of::<Self>(),
+            )
+        }
+    }
+}
+
+/// Uniform buffer layout for `shaders/grayscale.comp` (std140)
+#[repr(C)]
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct GrayscaleParams {
+    /// Blend between the source (0.0) and full grayscale (1.0)
+    pub amount: f32,
+    /// Non-zero when the image holds BGRA data, so luma weights swap R and B
+    pub swap_red_blue: i32,
+    _padding: [u32; 2],
+}
+
+impl GrayscaleParams {
+    pub fn new(amount: f32, bgra: bool) -> Self {
+        Self {
+            amount: amount.clamp(0.0, 1.0),
+            swap_red_blue: bgra as i32,
+            _padding: [0; 2],
+        }
+    }
+
+    pub fn as_bytes(&self) -> &[u8] {
+        unsafe {
+            std::slice::from_raw_parts(
+                (self as *const Self) as *const u8,
+                std::mem::size_of::<Self>(),
+            )
+        }
+    }
 }
 
+/// Maximum number of dispatches that can be recorded before release_completed_dispatches
+const MAX_DISPATCHES_IN_FLIGHT: u32 = 16;
+
+/// Push-constant bytes available to every compute shader (Vulkan guarantees at least 128)
+pub const MAX_PUSH_CONSTANT_SIZE: u32 = 128;
+
 impl ComputePipelineManager {
     pub fn new(context: &VulkanContext) -> VulkanResult<Self> {
         let descriptor_set_layout = Self::create_descriptor_set_layout(&context.device)?;
         let pipeline_layout = Self::create_pipeline_layout(&context.device, descriptor_set_layout)?;
+        let descriptor_pool = Self::create_descriptor_pool(&context.device)?;
+
+        let memory_properties = unsafe {
+            context
+                .instance
+                .get_physical_device_memory_properties(context.physical_device)
+        };
+
+        let format_properties = unsafe {
+            context.instance.get_physical_device_format_properties(
+                context.physical_device,
+                vk::Format::R8G8B8A8_UNORM,
+            )
+        };
+        let linear_storage_supported = format_properties
+            .linear_tiling_features
+            .contains(vk::FormatFeatureFlags::STORAGE_IMAGE);
 
         tracing::info!("Created compute pipeline manager with base layout");
 
@@ -823,9 +1284,30 @@ impl ComputePipelineManager {
             pipelines: HashMap::new(),
             descriptor_set_layout,
             pipeline_layout,
+            descriptor_pool,
+            memory_properties,
+            linear_storage_supported,
+            shader_dir: Self::default_shader_dir(),
+            in_flight: Vec::new(),
+            recycled_sets: Vec::new(),
         })
     }
 
+    /// Default SPIR-V location: $CONSTELLATION_SHADER_DIR or the crate's shaders directory
+    fn default_shader_dir() -> PathBuf {
+        std::env::var_os("CONSTELLATION_SHADER_DIR")
+            .map(PathBuf::from)
+            .unwrap_or_else(|| PathBuf::from(concat!(env!("CARGO_MANIFEST_DIR"), "/shaders")))
+    }
+
+    pub fn set_shader_dir(&mut self, shader_dir: impl Into<PathBuf>) {
+        self.shader_dir = shader_dir.into();
+    }
+
+    pub fn shader_dir(&self) -> &Path {
+        &self.shader_dir
+    }
+
     fn create_descriptor_set_layout(device: &Device) -> VulkanResult<vk::DescriptorSetLayout> {
         let bindings = [
             // Input image binding
@@ -877,12 +1359,13 @@ impl ComputePipelineManager {
         descriptor_set_layout: vk::DescriptorSetLayout,
     ) -> VulkanResult<vk::PipelineLayout> {
         let set_layouts = [descriptor_set_layout];
+        let push_constant_ranges = [Self::push_constant_range()];
 
         let layout_info = vk::PipelineLayoutCreateInfo {
             set_layout_count: set_layouts.len() as u32,
             p_set_layouts: set_layouts.as_ptr(),
-            push_constant_range_count: 0,
-            p_push_constant_ranges: std::ptr::null(),
+            push_constant_range_count: push_constant_ranges.len() as u32,
+            p_push_constant_ranges: push_constant_ranges.as_ptr(),
             ..Default::default()
         };
 
@@ -895,16 +1378,93 @@ impl ComputePipelineManager {
         }
     }
 
+    /// Push-constant range shared by all pipelines created from this manager
+    pub fn push_constant_range() -> vk::PushConstantRange {
+        vk::PushConstantRange {
+            stage_flags: vk::ShaderStageFlags::COMPUTE,
+            offset: 0,
+            size: MAX_PUSH_CONSTANT_SIZE,
+        }
+    }
+
+    fn create_descriptor_pool(device: &Device) -> VulkanResult<vk::DescriptorPool> {
+        let pool_sizes = [
+            vk::DescriptorPoolSize {
+                ty: vk::DescriptorType::STORAGE_IMAGE,
+                descriptor_count: MAX_DISPATCHES_IN_FLIGHT * 2,
+            },
+            vk::DescriptorPoolSize {
+                ty: vk::DescriptorType::UNIFORM_BUFFER,
+                descriptor_count: MAX_DISPATCHES_IN_FLIGHT,
+            },
+        ];
+
+        let pool_info = vk::DescriptorPoolCreateInfo {
+            flags: vk::DescriptorPoolCreateFlags::FREE_DESCRIPTOR_SET,
+            max_sets: MAX_DISPATCHES_IN_FLIGHT,
+            pool_size_count: pool_sizes.len() as u32,
+            p_pool_sizes: pool_sizes.as_ptr(),
+            ..Default::default()
+        };
+
+        unsafe {
+            device
+                .create_descriptor_pool(&pool_info, None)
+                .map_err(|e| VulkanError::InitializationFailed {
+                    reason: format!("Failed to create descriptor pool: {e:?}"),
+                })
+        }
+    }
+
     /// Create a compute pipeline for specific video operation
-    /// Phase 1: Basic pipeline creation framework
-    /// Phase 2: Will load actual SPIR-V shaders for each operation
+    /// Loads `<shader_dir>/<shader_name>.comp.spv` when available (see scripts/compile-shaders.sh)
+    /// and falls back to a placeholder pipeline otherwise
     pub fn create_pipeline(&mut self, operation: VideoOperation) -> VulkanResult<()> {
         if self.pipelines.contains_key(&operation) {
             return Ok(()); // Pipeline already exists
         }
 
-        // Phase 1: Create placeholder pipeline
-        // Phase 2: Will load actual SPIR-V shader bytecode
+        let shader_path = self
+            .shader_dir
+            .join(format!("{}.comp.spv", operation.shader_name()));
+
+        match std::fs::File::open(&shader_path) {
+            Ok(mut file) => {
+                let code = ash::util::read_spv(&mut file).map_err(|e| {
+                    VulkanError::InitializationFailed {
+                        reason: format!("Failed to read SPIR-V {}: {e}", shader_path.display()),
+                    }
+                })?;
+                self.create_pipeline_from_spirv(operation, &code)
+            }
+            Err(_) => {
+                let workgroup_size = operation.optimal_workgroup_size();
+
+                tracing::warn!(
+                    "SPIR-V shader not found at {}, creating placeholder pipeline for {:?}",
+                    shader_path.display(),
+                    operation
+                );
+
+                let placeholder_pipeline = ComputePipeline {
+                    pipeline: vk::Pipeline::null(),
+                    shader_module: vk::ShaderModule::null(),
+                    workgroup_size,
+                    operation_type: operation.clone(),
+                };
+
+                self.pipelines.insert(operation, placeholder_pipeline);
+                Ok(())
+            }
+        }
+    }
+
+    /// Create a compute pipeline from SPIR-V words, replacing any existing pipeline
+    pub fn create_pipeline_from_spirv(
+        &mut self,
+        operation: VideoOperation,
+        code: &[u32],
+    ) -> VulkanResult<()> {
         let workgroup_size = operation.optimal_workgroup_size();
 
         tracing::info!(
@@ -913,58 +1473,640 @@ impl ComputePipelineManager {
             workgroup_size
         );
 
-        // For Phase 1, create a basic compute pipeline structure
-        // Phase 2 will implement actual shader loading and compilation
-        let placeholder_pipeline = ComputePipeline {
-            pipeline: vk::Pipeline::null(), // Phase 1: Placeholder
+        let module_info = vk::ShaderModuleCreateInfo {
+            code_size: std::mem::size_of_val(code),
+            p_code: code.as_ptr(),
+            ..Default::default()
+        };
+
+        let shader_module = unsafe {
+            self.device
+                .create_shader_module(&module_info, None)
+                .map_err(|e| VulkanError::InitializationFailed {
+                    reason: format!("Failed to create shader module for {operation:?}: {e:?}"),
+                })?
+        };
+
+        let stage = vk::PipelineShaderStageCreateInfo {
+            stage: vk::ShaderStageFlags::COMPUTE,
+            module: shader_module,
+            p_name: c"main".as_ptr(),
+            ..Default::default()
+        };
+
+        let pipeline_info = vk::ComputePipelineCreateInfo {
+            stage,
+            layout: self.pipeline_layout,
+            ..Default::default()
+        };
+
+        let pipeline = unsafe {
+            self.device
+                .create_compute_pipelines(vk::PipelineCache::null(), &[pipeline_info], None)
+                .map_err(|(_, e)| {
+                    self.device.destroy_shader_module(shader_module, None);
+                    VulkanError::InitializationFailed {
+                        reason: format!(
+                            "Failed to create compute pipeline for {operation:?}: {e:?}"
+                        ),
+                    }
+                })?[0]
+        };
+
+        let compute_pipeline = ComputePipeline {
+            pipeline,
+            shader_module,
             workgroup_size,
             operation_type: operation.clone(),
         };
 
-        tracing::info!("Created placeholder compute pipeline for {:?}", operation);
-        self.pipelines.insert(operation, placeholder_pipeline);
+        if let Some(old) = self.pipelines.insert(operation, compute_pipeline) {
+            self.destroy_pipeline(&old);
+        }
+
         Ok(())
     }
 
+    /// Replace the pipeline for `operation` with one built from new SPIR-V bytecode
+    /// Waits for the device to go idle first so no submitted command buffer still
+    /// references the old pipeline. Command buffers recorded but not yet submitted
+    /// must be re-recorded after a reload.
+    pub fn reload_shader(
+        &mut self,
+        operation: VideoOperation,
+        spirv_bytes: &[u8],
+    ) -> VulkanResult<()> {
+        let code = ash::util::read_spv(&mut std::io::Cursor::new(spirv_bytes)).map_err(|e| {
+            VulkanError::InitializationFailed {
+                reason: format!("Invalid SPIR-V for {operation:?}: {e}"),
+            }
+        })?;
+
+        unsafe {
+            self.device
+                .device_wait_idle()
+                .map_err(|e| VulkanError::GpuProcessingFailed {
+                    reason: format!("Failed to wait for device idle before reload: {e:?}"),
+                })?;
+        }
+
+        tracing::info!("Reloading compute shader for {:?}", operation);
+        self.create_pipeline_from_spirv(operation, &code)
+    }
+
+    fn destroy_pipeline(&self, pipeline: &ComputePipeline) {
+        unsafe {
+            if pipeline.pipeline != vk::Pipeline::null() {
+                self.device.destroy_pipeline(pipeline.pipeline, None);
+            }
+            if pipeline.shader_module != vk::ShaderModule::null() {
+                self.device
+                    .destroy_shader_module(pipeline.shader_module, None);
+            }
+        }
+    }
+
     /// Get pipeline for specific video operation
     pub fn get_pipeline(&self, operation: &VideoOperation) -> Option<&ComputePipeline> {
         self.pipelines.get(operation)
     }
 
-    /// Execute compute operation on video frame
-    /// Phase 1: Framework for compute dispatch
-    /// Phase 2: Will implement actual GPU execution
+    /// Record compute operation on video frame into `command_buffer`
+    /// `params` is copied into the operation's uniform buffer (e.g. `BlurParams::as_bytes`).
+    /// `push_constants` holds small per-dispatch values (up to `MAX_PUSH_CONSTANT_SIZE`
+    /// bytes, a multiple of 4) pushed at offset 0; pass an empty slice when unused.
+    /// The caller submits the command buffer and must call `release_completed_dispatches`
+    /// once the GPU has finished executing it.
     pub fn execute_operation(
-        &self,
+        &mut self,
         operation: &VideoOperation,
         input_frame: &PooledFrameBuffer,
         output_frame: &PooledFrameBuffer,
-        _command_buffer: vk::CommandBuffer,
+        params: &[u8],
+        push_constants: &[u8],
+        command_buffer: vk::CommandBuffer,
     ) -> VulkanResult<()> {
+        if push_constants.len() > MAX_PUSH_CONSTANT_SIZE as usize
+            || !push_constants.len().is_multiple_of(4)
+        {
+            return Err(VulkanError::GpuProcessingFailed {
+                reason: format!(
+                    "Push constants must be a multiple of 4 bytes up to {MAX_PUSH_CONSTANT_SIZE}, got {}",
+                    push_constants.len()
+                ),
+            });
+        }
+
         let pipeline =
             self.get_pipeline(operation)
                 .ok_or_else(|| VulkanError::GpuProcessingFailed {
                     reason: format!("Pipeline for {:?} not found", operation),
                 })?;
 
-        // Phase 1: Log the operation for development
+        if !pipeline.is_loaded() {
+            return Err(VulkanError::GpuProcessingFailed {
+                reason: format!("Pipeline for {:?} has no shader loaded", operation),
+            });
+        }
+
+        let pipeline_handle = pipeline.pipeline;
+        let workgroup_size = pipeline.workgroup_size;
+
+        if !self.linear_storage_supported {
+            return Err(VulkanError::HardwareNotSupported {
+                hardware: "Linear tiled R8G8B8A8 storage images".to_string(),
+            });
+        }
+
+        if self.in_flight.len() as u32 >= MAX_DISPATCHES_IN_FLIGHT {
+            return Err(VulkanError::GpuProcessingFailed {
+                reason: "Too many dispatches in flight, call release_completed_dispatches"
+                    .to_string(),
+            });
+        }
+
+        let frame_size = input_frame.frame_size().clone();
+        if frame_size.format != FrameFormat::Rgba8 || output_frame.frame_size() != &frame_size {
+            return Err(VulkanError::GpuProcessingFailed {
+                reason: format!(
+                    "Unsupported frame combination for {:?}: {:?} -> {:?}",
+                    operation,
+                    frame_size,
+                    output_frame.frame_size()
+                ),
+            });
+        }
+
         tracing::debug!(
             "Executing {:?} operation: {} -> {} (workgroup: {:?})",
             operation,
             input_frame.size(),
             output_frame.size(),
-            pipeline.workgroup_size
+            workgroup_size
         );
 
-        // Phase 2 will implement:
-        // - Bind descriptor sets
-        // - Dispatch compute shader
-        // - Memory barriers
-        // - Synchronization
+        let resources = self.allocate_descriptor_set(input_frame, output_frame, params)?;
+
+        unsafe {
+            // Host-written input and undefined output both move to GENERAL for storage access
+            let subresource_range = vk::ImageSubresourceRange {
+                aspect_mask: vk::ImageAspectFlags::COLOR,
+                base_mip_level: 0,
+                level_count: 1,
+                base_array_layer: 0,
+                layer_count: 1,
+            };
 
+            let pre_barriers = [
+                vk::ImageMemoryBarrier {
+                    src_access_mask: vk::AccessFlags::HOST_WRITE,
+                    dst_access_mask: vk::AccessFlags::SHADER_READ,
+                    old_layout: vk::ImageLayout::PREINITIALIZED,
+                    new_layout: vk::ImageLayout::GENERAL,
+                    src_queue_family_index: vk::QUEUE_FAMILY_IGNORED,
+                    dst_queue_family_index: vk::QUEUE_FAMILY_IGNORED,
+                    image: resources.images[0],
+                    subresource_range,
+                    ..Default::default()
+                },
+                vk::ImageMemoryBarrier {
+                    src_access_mask: vk::AccessFlags::empty(),
+                    dst_access_mask: vk::AccessFlags::SHADER_WRITE,
+                    old_layout: vk::ImageLayout::UNDEFINED,
+                    new_layout: vk::ImageLayout::GENERAL,
+                    src_queue_family_index: vk::QUEUE_FAMILY_IGNORED,
+                    dst_queue_family_index: vk::QUEUE_FAMILY_IGNORED,
+                    image: resources.images[1],
+                    subresource_range,
+                    ..Default::default()
+                },
+            ];
+
+            self.device.cmd_pipeline_barrier(
+                command_buffer,
+                vk::PipelineStageFlags::HOST | vk::PipelineStageFlags::TOP_OF_PIPE,
+                vk::PipelineStageFlags::COMPUTE_SHADER,
+                vk::DependencyFlags::empty(),
+                &[],
+                &[],
+                &pre_barriers,
+            );
+
+            self.device.cmd_bind_pipeline(
+                command_buffer,
+                vk::PipelineBindPoint::COMPUTE,
+                pipeline_handle,
+            );
+            self.device.cmd_bind_descriptor_sets(
+                command_buffer,
+                vk::PipelineBindPoint::COMPUTE,
+                self.pipeline_layout,
+                0,
+                &[resources.descriptor_set],
+                &[],
+            );
+            if !push_constants.is_empty() {
+                self.device.cmd_push_constants(
+                    command_buffer,
+                    self.pipeline_layout,
+                    vk::ShaderStageFlags::COMPUTE,
+                    0,
+                    push_constants,
+                );
+            }
+
+            self.device.cmd_dispatch(
+                command_buffer,
+                frame_size.width.div_ceil(workgroup_size[0]),
+                frame_size.height.div_ceil(workgroup_size[1]),
+                1,
+            );
+
+            // Make shader writes visible to host reads of the output frame
+            let post_barrier = vk::ImageMemoryBarrier {
+                src_access_mask: vk::AccessFlags::SHADER_WRITE,
+                dst_access_mask: vk::AccessFlags::HOST_READ,
+                old_layout: vk::ImageLayout::GENERAL,
+                new_layout: vk::ImageLayout::GENERAL,
+                src_queue_family_index: vk::QUEUE_FAMILY_IGNORED,
+                dst_queue_family_index: vk::QUEUE_FAMILY_IGNORED,
+                image: resources.images[1],
+                subresource_range,
+                ..Default::default()
+            };
+
+            self.device.cmd_pipeline_barrier(
+                command_buffer,
+                vk::PipelineStageFlags::COMPUTE_SHADER,
+                vk::PipelineStageFlags::HOST,
+                vk::DependencyFlags::empty(),
+                &[],
+                &[],
+                &[post_barrier],
+            );
+        }
+
+        self.in_flight.push(resources);
         Ok(())
     }
 
+    /// Free per-dispatch resources. Call only after submitted command buffers have completed.
+    pub fn release_completed_dispatches(&mut self) {
+        for resources in std::mem::take(&mut self.in_flight) {
+            self.recycle_descriptor_set(resources);
+        }
+    }
+
+    /// Allocate a descriptor set binding `input_frame` and `output_frame` as storage
+    /// images (bindings 0 and 1) and `params` as the uniform buffer (binding 2)
+    /// Sets returned through `recycle_descriptor_set` are reused before the pool
+    /// is asked for a new one.
+    pub fn allocate_descriptor_set(
+        &mut self,
+        input_frame: &PooledFrameBuffer,
+        output_frame: &PooledFrameBuffer,
+        params: &[u8],
+    ) -> VulkanResult<DispatchResources> {
+        let descriptor_set = match self.recycled_sets.pop() {
+            Some(descriptor_set) => descriptor_set,
+            None => {
+                let set_layouts = [self.descriptor_set_layout];
+                let allocate_info = vk::DescriptorSetAllocateInfo {
+                    descriptor_pool: self.descriptor_pool,
+                    descriptor_set_count: set_layouts.len() as u32,
+                    p_set_layouts: set_layouts.as_ptr(),
+                    ..Default::default()
+                };
+
+                unsafe {
+                    self.device
+                        .allocate_descriptor_sets(&allocate_info)
+                        .map_err(|e| VulkanError::GpuProcessingFailed {
+                            reason: format!("Failed to allocate descriptor set: {e:?}"),
+                        })?[0]
+                }
+            }
+        };
+
+        let mut resources = DispatchResources {
+            images: [vk::Image::null(); 2],
+            image_views: [vk::ImageView::null(); 2],
+            params_buffer: vk::Buffer::null(),
+            params_memory: vk::DeviceMemory::null(),
+            descriptor_set,
+        };
+
+        let result =
+            self.fill_dispatch_resources(&mut resources, input_frame, output_frame, params);
+        if let Err(e) = result {
+            self.recycle_descriptor_set(resources);
+            return Err(e);
+        }
+
+        Ok(resources)
+    }
+
+    /// Destroy the images and params buffer of `resources` and keep its descriptor
+    /// set for the next allocation. Call only once the GPU no longer uses them.
+    pub fn recycle_descriptor_set(&mut self, mut resources: DispatchResources) {
+        let descriptor_set =
+            std::mem::replace(&mut resources.descriptor_set, vk::DescriptorSet::null());
+        self.destroy_dispatch_resources(resources);
+        if descriptor_set != vk::DescriptorSet::null() {
+            self.recycled_sets.push(descriptor_set);
+        }
+    }
+
+    /// Number of descriptor sets waiting to be reused
+    pub fn recycled_descriptor_sets(&self) -> usize {
+        self.recycled_sets.len()
+    }
+
+    fn fill_dispatch_resources(
+        &self,
+        resources: &mut DispatchResources,
+        input_frame: &PooledFrameBuffer,
+        output_frame: &PooledFrameBuffer,
+        params: &[u8],
+    ) -> VulkanResult<()> {
+        let frames = [
+            (input_frame, vk::ImageLayout::PREINITIALIZED),
+            (output_frame, vk::ImageLayout::UNDEFINED),
+        ];
+
+        for (index, (frame, initial_layout)) in frames.iter().enumerate() {
+            resources.images[index] = self.create_frame_image(frame, *initial_layout)?;
+            resources.image_views[index] = self.create_frame_image_view(resources.images[index])?;
+        }
+
+        let (params_buffer, params_memory) = self.create_params_buffer(params)?;
+        resources.params_buffer = params_buffer;
+        resources.params_memory = params_memory;
+
+        let image_infos = [
+            vk::DescriptorImageInfo {
+                sampler: vk::Sampler::null(),
+                image_view: resources.image_views[0],
+                image_layout: vk::ImageLayout::GENERAL,
+            },
+            vk::DescriptorImageInfo {
+                sampler: vk::Sampler::null(),
+                image_view: resources.image_views[1],
+                image_layout: vk::ImageLayout::GENERAL,
+            },
+        ];
+
+        let buffer_info = vk::DescriptorBufferInfo {
+            buffer: resources.params_buffer,
+            offset: 0,
+            range: vk::WHOLE_SIZE,
+        };
+
+        let writes = [
+            vk::WriteDescriptorSet {
+                dst_set: resources.descriptor_set,
+                dst_binding: 0,
+                descriptor_count: 1,
+                descriptor_type: vk::DescriptorType::STORAGE_IMAGE,
+                p_image_info: &image_infos[0],
+                ..Default::default()
+            },
+            vk::WriteDescriptorSet {
+                dst_set: resources.descriptor_set,
+                dst_binding: 1,
+                descriptor_count: 1,
+                descriptor_type: vk::DescriptorType::STORAGE_IMAGE,
+                p_image_info: &image_infos[1],
+                ..Default::default()
+            },
+            vk::WriteDescriptorSet {
+                dst_set: resources.descriptor_set,
+                dst_binding: 2,
+                descriptor_count: 1,
+                descriptor_type: vk::DescriptorType::UNIFORM_BUFFER,
+                p_buffer_info: &buffer_info,
+                ..Default::default()
+            },
+        ];
+
+        unsafe {
+            self.device.update_descriptor_sets(&writes, &[]);
+        }
+
+        Ok(())
+    }
+
+    /// Create a linear storage image aliasing the pooled frame memory
+    fn create_frame_image(
+        &self,
+        frame: &PooledFrameBuffer,
+        initial_layout: vk::ImageLayout,
+    ) -> VulkanResult<vk::Image> {
+        let frame_size = frame.frame_size();
+
+        let image_info = vk::ImageCreateInfo {
+            image_type: vk::ImageType::TYPE_2D,
+            format: vk::Format::R8G8B8A8_UNORM,
+            extent: vk::Extent3D {
+                width: frame_size.width,
+                height: frame_size.height,
+                depth: 1,
+            },
+            mip_levels: 1,
+            array_layers: 1,
+            samples: vk::SampleCountFlags::TYPE_1,
+            tiling: vk::ImageTiling::LINEAR,
+            usage: vk::ImageUsageFlags::STORAGE,
+            sharing_mode: vk::SharingMode::EXCLUSIVE,
+            initial_layout,
+            ..Default::default()
+        };
+
+        unsafe {
+            let image = self.device.create_image(&image_info, None).map_err(|e| {
+                VulkanError::GpuProcessingFailed {
+                    reason: format!("Failed to create frame image: {e:?}"),
+                }
+            })?;
+
+            let requirements = self.device.get_image_memory_requirements(image);
+            let layout = self.device.get_image_subresource_layout(
+                image,
+                vk::ImageSubresource {
+                    aspect_mask: vk::ImageAspectFlags::COLOR,
+                    mip_level: 0,
+                    array_layer: 0,
+                },
+            );
+
+            let row_bytes = (frame_size.width * frame_size.format.bytes_per_pixel()) as u64;
+            let compatible = requirements.size <= frame.size()
+                && frame.offset().is_multiple_of(requirements.alignment)
+                && requirements.memory_type_bits & (1 << frame.memory_type_index()) != 0
+                && layout.offset == 0
+                && layout.row_pitch == row_bytes;
+
+            if !compatible {
+                self.device.destroy_image(image, None);
+                return Err(VulkanError::HardwareNotSupported {
+                    hardware: format!(
+                        "Pooled frame memory incompatible with linear image (size {}, alignment {}, row pitch {})",
+                        requirements.size, requirements.alignment, layout.row_pitch
+                    ),
+                });
+            }
+
+            if let Err(e) = self
+                .device
+                .bind_image_memory(image, frame.memory(), frame.offset())
+            {
+                self.device.destroy_image(image, None);
+                return Err(VulkanError::GpuProcessingFailed {
+                    reason: format!("Failed to bind frame memory: {e:?}"),
+                });
+            }
+
+            Ok(image)
+        }
+    }
+
+    fn create_frame_image_view(&self, image: vk::Image) -> VulkanResult<vk::ImageView> {
+        let view_info = vk::ImageViewCreateInfo {
+            image,
+            view_type: vk::ImageViewType::TYPE_2D,
+            format: vk::Format::R8G8B8A8_UNORM,
+            subresource_range: vk::ImageSubresourceRange {
+                aspect_mask: vk::ImageAspectFlags::COLOR,
+                base_mip_level: 0,
+                level_count: 1,
+                base_array_layer: 0,
+                layer_count: 1,
+            },
+            ..Default::default()
+        };
+
+        unsafe {
+            self.device
+                .create_image_view(&view_info, None)
+                .map_err(|e| VulkanError::GpuProcessingFailed {
+                    reason: format!("Failed to create frame image view: {e:?}"),
+                })
+        }
+    }
+
+    fn create_params_buffer(&self, params: &[u8]) -> VulkanResult<(vk::Buffer, vk::DeviceMemory)> {
+        // Uniform buffers must not be empty
+        let size = params.len().max(16) as u64;
+
+        let buffer_info = vk::BufferCreateInfo {
+            size,
+            usage: vk::BufferUsageFlags::UNIFORM_BUFFER,
+            sharing_mode: vk::SharingMode::EXCLUSIVE,
+            ..Default::default()
+        };
+
+        unsafe {
+            let buffer = self.device.create_buffer(&buffer_info, None).map_err(|e| {
+                VulkanError::GpuProcessingFailed {
+                    reason: format!("Failed to create params buffer: {e:?}"),
+                }
+            })?;
+
+            let requirements = self.device.get_buffer_memory_requirements(buffer);
+            let memory_type_index = match self.find_host_visible_memory_type(requirements) {
+                Some(index) => index,
+                None => {
+                    self.device.destroy_buffer(buffer, None);
+                    return Err(VulkanError::HardwareNotSupported {
+                        hardware: "Host visible memory for uniform buffer".to_string(),
+                    });
+                }
+            };
+
+            let allocate_info = vk::MemoryAllocateInfo {
+                allocation_size: requirements.size,
+                memory_type_index,
+                ..Default::default()
+            };
+
+            let memory = match self.device.allocate_memory(&allocate_info, None) {
+                Ok(memory) => memory,
+                Err(_) => {
+                    self.device.destroy_buffer(buffer, None);
+                    return Err(VulkanError::InsufficientMemory {
+                        required_bytes: requirements.size,
+                    });
+                }
+            };
+
+            let upload = self
+                .device
+                .bind_buffer_memory(buffer, memory, 0)
+                .and_then(|_| {
+                    self.device
+                        .map_memory(memory, 0, size, vk::MemoryMapFlags::empty())
+                });
+
+            match upload {
+                Ok(ptr) => {
+                    std::ptr::write_bytes(ptr as *mut u8, 0, size as usize);
+                    std::ptr::copy_nonoverlapping(params.as_ptr(), ptr as *mut u8, params.len());
+                    self.device.unmap_memory(memory);
+                    Ok((buffer, memory))
+                }
+                Err(e) => {
+                    self.device.destroy_buffer(buffer, None);
+                    self.device.free_memory(memory, None);
+                    Err(VulkanError::GpuProcessingFailed {
+                        reason: format!("Failed to upload params buffer: {e:?}"),
+                    })
+                }
+            }
+        }
+    }
+
+    fn find_host_visible_memory_type(&self, requirements: vk::MemoryRequirements) -> Option<u32> {
+        let required =
+            vk::MemoryPropertyFlags::HOST_VISIBLE | vk::MemoryPropertyFlags::HOST_COHERENT;
+
+        self.memory_properties
+            .memory_types
+            .iter()
+            .take(self.memory_properties.memory_type_count as usize)
+            .enumerate()
+            .find(|(index, memory_type)| {
+                requirements.memory_type_bits & (1 << index) != 0
+                    && memory_type.property_flags.contains(required)
+            })
+            .map(|(index, _)| index as u32)
+    }
+
+    fn destroy_dispatch_resources(&self, resources: DispatchResources) {
+        unsafe {
+            if resources.descriptor_set != vk::DescriptorSet::null() {
+                let _ = self
+                    .device
+                    .free_descriptor_sets(self.descriptor_pool, &[resources.descriptor_set]);
+            }
+            if resources.params_buffer != vk::Buffer::null() {
+                self.device.destroy_buffer(resources.params_buffer, None);
+            }
+            if resources.params_memory != vk::DeviceMemory::null() {
+                self.device.free_memory(resources.params_memory, None);
+            }
+            for view in resources.image_views {
+                if view != vk::ImageView::null() {
+                    self.device.destroy_image_view(view, None);
+                }
+            }
+            for image in resources.images {
+                if image != vk::Image::null() {
+                    self.device.destroy_image(image, None);
+                }
+            }
+        }
+    }
+
     pub fn descriptor_set_layout(&self) -> vk::DescriptorSetLayout {
         self.descriptor_set_layout
     }
@@ -983,6 +2125,20 @@ impl VideoOperation {
             VideoOperation::Sharpen => [16, 16, 1],              // 2D kernel processing
             VideoOperation::ColorCorrection => [64, 1, 1],       // 1D processing
             VideoOperation::Flip => [32, 8, 1],                  // Memory bandwidth bound
+            VideoOperation::Grayscale => [16, 16, 1],            // Per-pixel 2D processing
+        }
+    }
+
+    /// Base file name of the compute shader in the shaders directory
+    pub fn shader_name(&self) -> &'static str {
+        match self {
+            VideoOperation::ColorSpaceConversion => "color_space_conversion",
+            VideoOperation::Resize => "resize",
+            VideoOperation::Blur => "blur",
+            VideoOperation::Sharpen => "sharpen",
+            VideoOperation::ColorCorrection => "color_correction",
+            VideoOperation::Flip => "flip",
+            VideoOperation::Grayscale => "grayscale",
         }
     }
 }
@@ -990,12 +2146,20 @@ impl VideoOperation {
 impl Drop for ComputePipelineManager {
     fn drop(&mut self) {
         unsafe {
-            // Clean up pipelines
-            for pipeline in self.pipelines.values() {
-                if pipeline.pipeline != vk::Pipeline::null() {
-                    self.device.destroy_pipeline(pipeline.pipeline, None);
-                }
-            }
+            // Resources may still be referenced by submitted work
+            let _ = self.device.device_wait_idle();
+        }
+
+        self.release_completed_dispatches();
+
+        // Clean up pipelines
+        for pipeline in self.pipelines.values() {
+            self.destroy_pipeline(pipeline);
+        }
+
+        unsafe {
+            self.device
+                .destroy_descriptor_pool(self.descriptor_pool, None);
 
             // Clean up layouts
             self.device
@@ -1086,6 +2250,10 @@ pub struct MemoryUsage {
     pub total_allocated: u64,
     pub free_blocks: usize,
     pub total_pools: usize,
+    pub peak_allocation: u64,
+    pub buffers_in_use: u32,
+    pub failed_acquires: u64,
+    pub pool_stats: Vec<(FrameSize, PoolStats)>,
 }
 
 #[cfg(test)]
@@ -1101,6 +2269,23 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_select_device_by_index() {
+        let devices = VulkanContext::list_physical_devices();
+        let Some(last) = devices.len().checked_sub(1) else {
+            return;
+        };
+
+        // スコアの高い順に並んでいる
+        assert!(devices.windows(2).all(|pair| pair[0].1 >= pair[1].1));
+
+        let context = VulkanContext::new_with_device_index(last).unwrap();
+        let name = VulkanContext::device_name(&context.instance, context.physical_device);
+        assert_eq!(name, devices[last].0);
+
+        assert!(VulkanContext::new_with_device_index(devices.len()).is_err());
+    }
+
     #[test]
     fn test_memory_manager_creation() {
         if let Ok(context) = VulkanContext::new() {
@@ -1108,4 +2293,389 @@ mod tests {
             assert!(result.is_ok());
         }
     }
+
+    #[test]
+    fn test_blur_dispatch() {
+        let Ok(context) = VulkanContext::new() else {
+            return;
+        };
+
+        let mut pipeline_manager = ComputePipelineManager::new(&context).unwrap();
+        pipeline_manager
+            .create_pipeline(VideoOperation::Blur)
+            .unwrap();
+        if !pipeline_manager
+            .get_pipeline(&VideoOperation::Blur)
+            .unwrap()
+            .is_loaded()
+        {
+            println!("Blur shader not compiled, run scripts/compile-shaders.sh");
+            return;
+        }
+
+        let mut memory_manager = MemoryManager::new(&context).unwrap();
+        let frame_size = FrameSize {
+            width: 64,
+            height: 64,
+            format: FrameFormat::Rgba8,
+        };
+        memory_manager
+            .create_frame_pool(frame_size.clone(), 2, false)
+            .unwrap();
+        let input = memory_manager.acquire_frame_buffer(&frame_size).unwrap();
+        let output = memory_manager.acquire_frame_buffer(&frame_size).unwrap();
+
+        unsafe {
+            let device = &context.device;
+            let allocate_info = vk::CommandBufferAllocateInfo {
+                command_pool: context.command_pools[1],
+                level: vk::CommandBufferLevel::PRIMARY,
+                command_buffer_count: 1,
+                ..Default::default()
+            };
+            let command_buffer = device.allocate_command_buffers(&allocate_info).unwrap()[0];
+
+            device
+                .begin_command_buffer(command_buffer, &vk::CommandBufferBeginInfo::default())
+                .unwrap();
+            let result = pipeline_manager.execute_operation(
+                &VideoOperation::Blur,
+                &input,
+                &output,
+                BlurParams::new(3).as_bytes(),
+                &[],
+                command_buffer,
+            );
+            device.end_command_buffer(command_buffer).unwrap();
+
+            match result {
+                Ok(()) => {
+                    let command_buffers = [command_buffer];
+                    let submit_info = vk::SubmitInfo {
+                        command_buffer_count: 1,
+                        p_command_buffers: command_buffers.as_ptr(),
+                        ..Default::default()
+                    };
+                    device
+                        .queue_submit(context.compute_queue, &[submit_info], vk::Fence::null())
+                        .unwrap();
+                    device.queue_wait_idle(context.compute_queue).unwrap();
+                }
+                Err(VulkanError::HardwareNotSupported { hardware }) => {
+                    println!("Blur dispatch not supported: {hardware}");
+                }
+                Err(e) => panic!("Blur dispatch failed: {e}"),
+            }
+
+            pipeline_manager.release_completed_dispatches();
+            device.free_command_buffers(context.command_pools[1], &[command_buffer]);
+        }
+
+        memory_manager.release_frame_buffer(input);
+        memory_manager.release_frame_buffer(output);
+    }
+
+    /// Minimal GLSL450 compute shader with an empty `main`, as SPIR-V bytes
+    #[rustfmt::skip]
+    fn empty_compute_spirv(local_size_x: u32) -> Vec<u8> {
+        let words = [
+            0x0723_0203, 0x0001_0000, 0, 5, 0,
+            (2 << 16) | 17, 1,                          // OpCapability Shader
+            (3 << 16) | 14, 0, 1,                       // OpMemoryModel Logical GLSL450
+            (5 << 16) | 15, 5, 1, 0x6e69_616d, 0,       // OpEntryPoint GLCompute %1 "main"
+            (6 << 16) | 16, 1, 17, local_size_x, 1, 1,  // OpExecutionMode %1 LocalSize
+            (2 << 16) | 19, 2,                          // %2 = OpTypeVoid
+            (3 << 16) | 33, 3, 2,                       // %3 = OpTypeFunction %2
+            (5 << 16) | 54, 2, 1, 0, 3,                 // %1 = OpFunction %2 None %3
+            (2 << 16) | 248, 4,                         // OpLabel
+            (1 << 16) | 253,                            // OpReturn
+            (1 << 16) | 56,                             // OpFunctionEnd
+        ];
+        words.iter().flat_map(|word: &u32| word.to_le_bytes()).collect()
+    }
+
+    #[test]
+    fn test_reload_shader_replaces_pipeline() {
+        let Ok(context) = VulkanContext::new() else {
+            return;
+        };
+
+        let mut pipeline_manager = ComputePipelineManager::new(&context).unwrap();
+        pipeline_manager
+            .reload_shader(VideoOperation::Flip, &empty_compute_spirv(1))
+            .unwrap();
+        let original = pipeline_manager
+            .get_pipeline(&VideoOperation::Flip)
+            .unwrap()
+            .pipeline;
+        assert_ne!(original, vk::Pipeline::null());
+
+        pipeline_manager
+            .reload_shader(VideoOperation::Flip, &empty_compute_spirv(8))
+            .unwrap();
+        let reloaded = pipeline_manager
+            .get_pipeline(&VideoOperation::Flip)
+            .unwrap();
+        assert!(reloaded.is_loaded());
+        assert_ne!(reloaded.pipeline, original);
+
+        // Bytecode that is not a whole number of words is rejected
+        assert!(pipeline_manager
+            .reload_shader(VideoOperation::Flip, &[0x03, 0x02, 0x23])
+            .is_err());
+    }
+
+    #[test]
+    fn test_push_constant_range_covers_compute_stage() {
+        let range = ComputePipelineManager::push_constant_range();
+        assert_eq!(range.stage_flags, vk::ShaderStageFlags::COMPUTE);
+        assert_eq!(range.offset, 0);
+        assert_eq!(range.size, MAX_PUSH_CONSTANT_SIZE);
+    }
+
+    #[test]
+    fn test_push_constant_dispatch() {
+        let Ok(context) = VulkanContext::new() else {
+            return;
+        };
+
+        let mut pipeline_manager = ComputePipelineManager::new(&context).unwrap();
+        pipeline_manager
+            .reload_shader(VideoOperation::Flip, &empty_compute_spirv(32))
+            .unwrap();
+
+        let mut memory_manager = MemoryManager::new(&context).unwrap();
+        let frame_size = FrameSize {
+            width: 32,
+            height: 8,
+            format: FrameFormat::Rgba8,
+        };
+        memory_manager
+            .create_frame_pool(frame_size.clone(), 2, false)
+            .unwrap();
+        let input = memory_manager.acquire_frame_buffer(&frame_size).unwrap();
+        let output = memory_manager.acquire_frame_buffer(&frame_size).unwrap();
+
+        unsafe {
+            let device = &context.device;
+            let allocate_info = vk::CommandBufferAllocateInfo {
+                command_pool: context.command_pools[1],
+                level: vk::CommandBufferLevel::PRIMARY,
+                command_buffer_count: 1,
+                ..Default::default()
+            };
+            let command_buffer = device.allocate_command_buffers(&allocate_info).unwrap()[0];
+
+            device
+                .begin_command_buffer(command_buffer, &vk::CommandBufferBeginInfo::default())
+                .unwrap();
+
+            // Oversized and unaligned blocks are rejected before anything is recorded
+            let oversized = vec![0u8; MAX_PUSH_CONSTANT_SIZE as usize + 4];
+            for push_constants in [&oversized[..], &[0u8; 3][..]] {
+                assert!(pipeline_manager
+                    .execute_operation(
+                        &VideoOperation::Flip,
+                        &input,
+                        &output,
+                        &[],
+                        push_constants,
+                        command_buffer,
+                    )
+                    .is_err());
+            }
+
+            let result = pipeline_manager.execute_operation(
+                &VideoOperation::Flip,
+                &input,
+                &output,
+                &[],
+                &2.5f32.to_le_bytes(),
+                command_buffer,
+            );
+            device.end_command_buffer(command_buffer).unwrap();
+
+            match result {
+                Ok(()) => {
+                    let command_buffers = [command_buffer];
+                    let submit_info = vk::SubmitInfo {
+                        command_buffer_count: 1,
+                        p_command_buffers: command_buffers.as_ptr(),
+                        ..Default::default()
+                    };
+                    device
+                        .queue_submit(context.compute_queue, &[submit_info], vk::Fence::null())
+                        .unwrap();
+                    device.queue_wait_idle(context.compute_queue).unwrap();
+                }
+                Err(VulkanError::HardwareNotSupported { hardware }) => {
+                    println!("Push constant dispatch not supported: {hardware}");
+                }
+                Err(e) => panic!("Push constant dispatch failed: {e}"),
+            }
+
+            pipeline_manager.release_completed_dispatches();
+            device.free_command_buffers(context.command_pools[1], &[command_buffer]);
+        }
+
+        memory_manager.release_frame_buffer(input);
+        memory_manager.release_frame_buffer(output);
+    }
+
+    #[test]
+    fn test_descriptor_sets_are_recycled() {
+        let Ok(context) = VulkanContext::new() else {
+            return;
+        };
+
+        let mut pipeline_manager = ComputePipelineManager::new(&context).unwrap();
+        let mut memory_manager = MemoryManager::new(&context).unwrap();
+        let frame_size = FrameSize {
+            width: 64,
+            height: 64,
+            format: FrameFormat::Rgba8,
+        };
+        memory_manager
+            .create_frame_pool(frame_size.clone(), 2, false)
+            .unwrap();
+        let input = memory_manager.acquire_frame_buffer(&frame_size).unwrap();
+        let output = memory_manager.acquire_frame_buffer(&frame_size).unwrap();
+
+        let params = BlurParams::new(2);
+        match pipeline_manager.allocate_descriptor_set(&input, &output, params.as_bytes()) {
+            Ok(resources) => {
+                let descriptor_set = resources.descriptor_set();
+                assert_ne!(descriptor_set, vk::DescriptorSet::null());
+
+                pipeline_manager.recycle_descriptor_set(resources);
+                assert_eq!(pipeline_manager.recycled_descriptor_sets(), 1);
+
+                // The freed set is handed out again instead of growing the pool
+                let resources = pipeline_manager
+                    .allocate_descriptor_set(&input, &output, params.as_bytes())
+                    .unwrap();
+                assert_eq!(resources.descriptor_set(), descriptor_set);
+                assert_eq!(pipeline_manager.recycled_descriptor_sets(), 0);
+                pipeline_manager.recycle_descriptor_set(resources);
+            }
+            Err(VulkanError::HardwareNotSupported { hardware }) => {
+                println!("Descriptor set binding not supported: {hardware}");
+            }
+            Err(e) => panic!("Descriptor set allocation failed: {e}"),
+        }
+
+        memory_manager.release_frame_buffer(input);
+        memory_manager.release_frame_buffer(output);
+    }
+
+    #[test]
+    fn test_pool_slots_track_exhaustion() {
+        let slots = PoolSlots::new(2);
+        assert_eq!(slots.try_acquire(), Some(0));
+        assert_eq!(slots.try_acquire(), Some(1));
+        assert_eq!(slots.try_acquire(), None);
+        assert_eq!(slots.try_acquire(), None);
+
+        let stats = slots.stats();
+        assert_eq!(stats.in_use, 2);
+        assert_eq!(stats.high_water_mark, 2);
+        assert_eq!(stats.failed_acquires, 2);
+
+        slots.release(0);
+        let stats = slots.stats();
+        assert_eq!(stats.in_use, 1);
+        assert_eq!(stats.high_water_mark, 2);
+
+        // Timed out waits count as failures too
+        assert_eq!(slots.acquire_timeout(Duration::ZERO), Some(0));
+        assert_eq!(slots.acquire_timeout(Duration::from_millis(10)), None);
+        assert_eq!(slots.stats().failed_acquires, 3);
+    }
+
+    #[test]
+    fn test_rgba16_buffer_size_doubles() {
+        let size = |format| FrameSize {
+            width: 1920,
+            height: 1080,
+            format,
+        };
+
+        assert_eq!(FrameFormat::Rgba16.bytes_per_pixel(), 8);
+        assert_eq!(
+            size(FrameFormat::Rgba16).buffer_size(),
+            2 * size(FrameFormat::Rgba8).buffer_size()
+        );
+    }
+
+    #[test]
+    fn test_pool_slots_release_wakes_waiter() {
+        let slots = Arc::new(PoolSlots::new(1));
+        let held = slots.try_acquire().unwrap();
+
+        let releaser = Arc::clone(&slots);
+        let handle = std::thread::spawn(move || {
+            std::thread::sleep(Duration::from_millis(20));
+            releaser.release(held);
+        });
+
+        assert_eq!(slots.acquire_timeout(Duration::from_secs(5)), Some(held));
+        handle.join().unwrap();
+        assert_eq!(slots.stats().failed_acquires, 0);
+    }
+
+    #[test]
+    fn test_frame_pool_stats_and_timeout_acquire() {
+        let Ok(context) = VulkanContext::new() else {
+            return;
+        };
+
+        let mut memory_manager = MemoryManager::new(&context).unwrap();
+        let frame_size = FrameSize {
+            width: 16,
+            height: 16,
+            format: FrameFormat::Rgba8,
+        };
+        memory_manager
+            .create_frame_pool(frame_size.clone(), 1, false)
+            .unwrap();
+
+        let buffer = memory_manager.acquire_frame_buffer(&frame_size).unwrap();
+        assert!(memory_manager.acquire_frame_buffer(&frame_size).is_err());
+        let stats = memory_manager.pool_stats(&frame_size).unwrap();
+        assert_eq!(stats.in_use, 1);
+        assert_eq!(stats.failed_acquires, 1);
+
+        let releaser = memory_manager.frame_buffer_releaser(&frame_size).unwrap();
+        let handle = std::thread::spawn(move || {
+            std::thread::sleep(Duration::from_millis(20));
+            releaser.release(buffer);
+        });
+
+        let buffer = memory_manager
+            .acquire_frame_buffer_timeout(&frame_size, Duration::from_secs(5))
+            .unwrap();
+        handle.join().unwrap();
+
+        let usage = memory_manager.get_memory_usage();
+        assert_eq!(usage.buffers_in_use, 1);
+        assert_eq!(usage.failed_acquires, 1);
+        memory_manager.release_frame_buffer(buffer);
+    }
+
+    #[test]
+    fn test_grayscale_params_layout() {
+        let params = GrayscaleParams::new(1.5, true);
+        assert_eq!(params.as_bytes().len(), 16);
+        assert_eq!(&params.as_bytes()[0..4], &1.0f32.to_ne_bytes());
+        assert_eq!(&params.as_bytes()[4..8], &1i32.to_ne_bytes());
+        assert_eq!(VideoOperation::Grayscale.shader_name(), "grayscale");
+    }
+
+    #[test]
+    fn test_blur_params_layout() {
+        let params = BlurParams::new(4);
+        assert_eq!(params.as_bytes().len(), 16);
+        assert_eq!(&params.as_bytes()[0..4], &4i32.to_ne_bytes());
+        assert_eq!(params.sigma, 2.0);
+    }
 }
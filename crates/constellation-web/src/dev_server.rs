@@ -19,6 +19,7 @@
 // Development server for frontend communication testing
 // This server runs without Vulkan dependency for development purposes
 
+use crate::mock_engine::MockEngine;
 use anyhow::Result;
 use axum::{
     extract::{Path, State, WebSocketUpgrade},
@@ -27,7 +28,7 @@ use axum::{
     routing::{delete, get, post, put},
     Router,
 };
-use constellation_core::{ConnectionType, NodeConfig, NodeType};
+use constellation_core::{ConnectionType, ConstellationError, NodeConfig, NodeType, VideoEngine};
 use serde::{Deserialize, Serialize};
 use std::{
     collections::HashMap,
@@ -40,25 +41,8 @@ use uuid::Uuid;
 // Simplified state for development
 #[derive(Clone)]
 pub struct DevAppState {
-    pub nodes: Arc<Mutex<HashMap<Uuid, DevNode>>>,
-    pub connections: Arc<Mutex<Vec<DevConnection>>>,
+    pub engine: Arc<Mutex<Box<dyn VideoEngine>>>,
     pub event_sender: broadcast::Sender<DevEngineEvent>,
-    pub engine_running: Arc<Mutex<bool>>,
-}
-
-#[derive(Debug, Clone, Serialize, Deserialize)]
-pub struct DevNode {
-    pub id: Uuid,
-    pub node_type: NodeType,
-    pub config: NodeConfig,
-    pub created_at: u64,
-}
-
-#[derive(Debug, Clone)]
-pub struct DevConnection {
-    pub source_id: Uuid,
-    pub target_id: Uuid,
-    pub connection_type: ConnectionType,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -95,33 +79,26 @@ pub enum DevEngineEvent {
 }
 
 impl DevAppState {
+    /// Development state backed by `MockEngine` (no Vulkan required)
     pub fn new() -> Result<Self> {
-        let nodes = Arc::new(Mutex::new(HashMap::new()));
-        let connections = Arc::new(Mutex::new(Vec::new()));
-        let (event_sender, _) = broadcast::channel(1000);
-        let engine_running = Arc::new(Mutex::new(false));
-
-        Ok(Self {
-            nodes,
-            connections,
+        Ok(Self::with_engine(Box::new(MockEngine::new())))
+    }
+
+    pub fn with_engine(engine: Box<dyn VideoEngine>) -> Self {
+        let (event_sender, _) = broadcast::channel(crate::DEFAULT_EVENT_CAPACITY);
+
+        Self {
+            engine: Arc::new(Mutex::new(engine)),
             event_sender,
-            engine_running,
-        })
+        }
     }
 
     pub fn add_node(&self, node_type: NodeType, config: NodeConfig) -> Result<Uuid> {
-        let node_id = Uuid::new_v4();
-        let node = DevNode {
-            id: node_id,
-            node_type: node_type.clone(),
-            config,
-            created_at: std::time::SystemTime::now()
-                .duration_since(std::time::UNIX_EPOCH)
-                .unwrap()
-                .as_secs(),
-        };
-
-        self.nodes.lock().unwrap().insert(node_id, node);
+        let node_id = self
+            .engine
+            .lock()
+            .unwrap()
+            .create_node(node_type.clone(), config)?;
 
         let _ = self.event_sender.send(DevEngineEvent::NodeAdded {
             id: node_id,
@@ -133,18 +110,13 @@ impl DevAppState {
     }
 
     pub fn remove_node(&self, node_id: Uuid) -> Result<()> {
-        if self.nodes.lock().unwrap().remove(&node_id).is_some() {
-            // Remove any connections involving this node
-            self.connections
-                .lock()
-                .unwrap()
-                .retain(|conn| conn.source_id != node_id && conn.target_id != node_id);
+        // Connections involving this node are removed along with it
+        self.engine.lock().unwrap().remove_node(node_id)?;
 
-            let _ = self
-                .event_sender
-                .send(DevEngineEvent::NodeRemoved { id: node_id });
-            tracing::info!("Removed node: {}", node_id);
-        }
+        let _ = self
+            .event_sender
+            .send(DevEngineEvent::NodeRemoved { id: node_id });
+        tracing::info!("Removed node: {}", node_id);
         Ok(())
     }
 
@@ -154,20 +126,10 @@ impl DevAppState {
         target_id: Uuid,
         connection_type: ConnectionType,
     ) -> Result<()> {
-        // Check if nodes exist
-        let nodes = self.nodes.lock().unwrap();
-        if !nodes.contains_key(&source_id) || !nodes.contains_key(&target_id) {
-            return Err(anyhow::anyhow!("One or both nodes do not exist"));
-        }
-        drop(nodes);
-
-        let connection = DevConnection {
-            source_id,
-            target_id,
-            connection_type: connection_type.clone(),
-        };
-
-        self.connections.lock().unwrap().push(connection);
+        self.engine
+            .lock()
+            .unwrap()
+            .connect(source_id, target_id, connection_type.clone())?;
 
         let _ = self.event_sender.send(DevEngineEvent::NodeConnected {
             source_id,
@@ -185,16 +147,12 @@ impl DevAppState {
         parameter: String,
         value: serde_json::Value,
     ) -> Result<()> {
-        // Check if node exists and update its config
-        {
-            let mut nodes = self.nodes.lock().unwrap();
-            let node = nodes
-                .get_mut(&node_id)
-                .ok_or_else(|| anyhow::anyhow!("Node does not exist"))?;
-            node.config
-                .parameters
-                .insert(parameter.clone(), value.clone());
-        }
+        // Returns the value actually stored (clamped under OutOfRangePolicy::Clamp)
+        let value =
+            self.engine
+                .lock()
+                .unwrap()
+                .set_node_parameter(node_id, parameter.clone(), value)?;
 
         let _ = self.event_sender.send(DevEngineEvent::ParameterChanged {
             node_id,
@@ -212,30 +170,26 @@ impl DevAppState {
     }
 
     pub fn start_engine(&self) -> Result<()> {
-        *self.engine_running.lock().unwrap() = true;
+        self.engine.lock().unwrap().start()?;
         let _ = self.event_sender.send(DevEngineEvent::EngineStarted);
-        tracing::info!("Engine started (development mode)");
         Ok(())
     }
 
     pub fn stop_engine(&self) -> Result<()> {
-        *self.engine_running.lock().unwrap() = false;
+        self.engine.lock().unwrap().stop()?;
         let _ = self.event_sender.send(DevEngineEvent::EngineStopped);
-        tracing::info!("Engine stopped");
         Ok(())
     }
 
     pub fn get_engine_status(&self) -> DevEngineStatusResponse {
-        let running = *self.engine_running.lock().unwrap();
-        let node_count = self.nodes.lock().unwrap().len();
-        let connection_count = self.connections.lock().unwrap().len();
+        let status = self.engine.lock().unwrap().status();
 
         DevEngineStatusResponse {
-            running,
-            fps: if running { 30.0 } else { 0.0 },
-            frame_count: if running { 12345 } else { 0 }, // Mock frame count
-            node_count,
-            connection_count,
+            running: status.running,
+            fps: if status.running { 30.0 } else { 0.0 },
+            frame_count: status.frame_count,
+            node_count: status.node_count,
+            connection_count: status.connection_count,
         }
     }
 }
@@ -361,10 +315,12 @@ pub async fn create_dev_app(state: DevAppState) -> Router {
 
 // API handlers
 async fn dev_get_nodes(State(state): State<DevAppState>) -> Json<HashMap<Uuid, String>> {
-    let nodes = state.nodes.lock().unwrap();
-    let result = nodes
-        .iter()
-        .map(|(id, node)| (*id, format!("{:?}", node.node_type)))
+    let engine = state.engine.lock().unwrap();
+    let graph = engine.graph();
+    let result = graph
+        .node_ids()
+        .filter_map(|id| graph.get_node(id))
+        .map(|node| (node.id, format!("{:?}", node.node_type)))
         .collect();
     Json(result)
 }
@@ -386,7 +342,7 @@ async fn dev_get_node(
     State(state): State<DevAppState>,
     Path(id): Path<Uuid>,
 ) -> Result<Json<String>, StatusCode> {
-    match state.nodes.lock().unwrap().get(&id) {
+    match state.engine.lock().unwrap().node(id) {
         Some(node) => Ok(Json(format!("{:?}", node.node_type))),
         None => Err(StatusCode::NOT_FOUND),
     }
@@ -421,7 +377,13 @@ async fn dev_set_node_parameters(
     for (parameter, value) in request.parameters {
         if let Err(e) = state.set_node_parameter(id, parameter, value) {
             tracing::error!("Failed to set parameter: {}", e);
-            return Err(StatusCode::INTERNAL_SERVER_ERROR);
+            return match e.downcast_ref::<ConstellationError>() {
+                Some(ConstellationError::NodeNotFound { .. }) => Err(StatusCode::NOT_FOUND),
+                Some(ConstellationError::ParameterValidationFailed { .. }) => {
+                    Err(StatusCode::BAD_REQUEST)
+                }
+                _ => Err(StatusCode::INTERNAL_SERVER_ERROR),
+            };
         }
     }
     Ok(Json(()))
@@ -475,3 +437,99 @@ async fn dev_stop_engine(State(state): State<DevAppState>) -> Result<Json<()>, S
 async fn dev_get_engine_status(State(state): State<DevAppState>) -> Json<DevEngineStatusResponse> {
     Json(state.get_engine_status())
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use constellation_core::{InputType, OutputType};
+
+    fn create_request(node_type: NodeType) -> Json<CreateNodeRequest> {
+        Json(CreateNodeRequest {
+            node_type,
+            config: NodeConfig {
+                parameters: HashMap::new(),
+            },
+        })
+    }
+
+    #[tokio::test]
+    async fn test_dev_server_node_graph_runs_on_mock_engine() {
+        let state = DevAppState::new().unwrap();
+        let mut events = state.event_sender.subscribe();
+
+        let Json(source) = dev_create_node(
+            State(state.clone()),
+            create_request(NodeType::Input(InputType::TestPattern)),
+        )
+        .await
+        .unwrap();
+        let Json(target) = dev_create_node(
+            State(state.clone()),
+            create_request(NodeType::Output(OutputType::Preview)),
+        )
+        .await
+        .unwrap();
+        let Json(()) = dev_create_connection(
+            State(state.clone()),
+            Json(CreateConnectionRequest {
+                source_id: source,
+                target_id: target,
+                connection_type: ConnectionType::RenderData,
+            }),
+        )
+        .await
+        .unwrap();
+
+        let Json(nodes) = dev_get_nodes(State(state.clone())).await;
+        assert_eq!(nodes.len(), 2);
+        assert!(nodes.contains_key(&source) && nodes.contains_key(&target));
+
+        let Json(status) = dev_get_engine_status(State(state.clone())).await;
+        assert!(!status.running);
+        assert_eq!(status.node_count, 2);
+        assert_eq!(status.connection_count, 1);
+
+        assert!(matches!(
+            events.recv().await.unwrap(),
+            DevEngineEvent::NodeAdded { id, .. } if id == source
+        ));
+    }
+
+    #[tokio::test]
+    async fn test_dev_server_status_follows_engine_lifecycle() {
+        let state = DevAppState::new().unwrap();
+
+        let Json(()) = dev_start_engine(State(state.clone())).await.unwrap();
+        let Json(status) = dev_get_engine_status(State(state.clone())).await;
+        assert!(status.running);
+        assert_eq!(status.fps, 30.0);
+
+        let Json(()) = dev_stop_engine(State(state.clone())).await.unwrap();
+        let Json(status) = dev_get_engine_status(State(state)).await;
+        assert!(!status.running);
+        assert_eq!(status.fps, 0.0);
+    }
+
+    #[tokio::test]
+    async fn test_dev_server_rejects_connection_to_unknown_node() {
+        let state = DevAppState::new().unwrap();
+        let Json(source) = dev_create_node(
+            State(state.clone()),
+            create_request(NodeType::Input(InputType::TestPattern)),
+        )
+        .await
+        .unwrap();
+
+        let result = dev_create_connection(
+            State(state.clone()),
+            Json(CreateConnectionRequest {
+                source_id: source,
+                target_id: Uuid::new_v4(),
+                connection_type: ConnectionType::RenderData,
+            }),
+        )
+        .await;
+        assert_eq!(result.unwrap_err(), StatusCode::INTERNAL_SERVER_ERROR);
+        assert_eq!(state.get_engine_status().connection_count, 0);
+    }
+}
@@ -18,18 +18,20 @@
 
 use anyhow::Result;
 use axum::{
-    extract::{Path, State},
-    http::StatusCode,
-    response::Json,
+    extract::{Path, Query, State},
+    http::{header, StatusCode},
+    response::{IntoResponse, Json, Response},
     routing::{delete, get, post, put},
     Router,
 };
 use constellation_core::*;
-use constellation_nodes::NodeProperties;
+use constellation_nodes::{create_node_processor, FrameHistogram, NodeProperties};
+use constellation_pipeline::PipelineProcessor;
 use serde::{Deserialize, Serialize};
 use std::{
     collections::HashMap,
     sync::{Arc, Mutex},
+    time::{Duration, Instant},
 };
 use tokio::sync::broadcast;
 use tower_http::cors::CorsLayer;
@@ -37,16 +39,37 @@ use uuid::Uuid;
 
 pub mod api;
 pub mod dev_server;
+pub mod mock_engine;
+pub mod monitoring;
+pub mod preview;
+pub mod runner;
+pub mod snapshot;
 pub mod websocket;
 
 // pub use api::*;
+pub use mock_engine::MockEngine;
+pub use monitoring::SystemMonitor;
+pub use preview::PreviewManager;
+pub use runner::PipelineRunner;
 pub use websocket::*;
 
+/// Events a slow WebSocket client may fall behind by before it is resynced
+pub const DEFAULT_EVENT_CAPACITY: usize = 1000;
+
+/// How long a snapshot waits for the running pipeline to produce the node's next frame
+const SNAPSHOT_TIMEOUT: Duration = Duration::from_secs(2);
+const SNAPSHOT_POLL_INTERVAL: Duration = Duration::from_millis(5);
+
 #[derive(Clone)]
 pub struct AppState {
-    pub engine: Arc<Mutex<ConstellationEngine>>,
+    pub engine: Arc<Mutex<Box<dyn VideoEngine>>>,
     // pub node_processors: Arc<Mutex<HashMap<Uuid, Box<dyn NodeProcessor + Send>>>>,
     pub event_sender: broadcast::Sender<EngineEvent>,
+    pub previews: PreviewManager,
+    pub pipeline: Arc<Mutex<PipelineProcessor>>,
+    /// Frame loop driving `pipeline`; present while the engine is started
+    pub runner: Arc<Mutex<Option<PipelineRunner>>>,
+    pub system_monitor: Arc<Mutex<SystemMonitor>>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -78,6 +101,12 @@ pub enum EngineEvent {
     Error {
         message: String,
     },
+    /// Error attributed to a specific node, so the UI can highlight it
+    NodeError {
+        node_id: Uuid,
+        category: ErrorCategory,
+        message: String,
+    },
     AudioLevel {
         node_id: Uuid,
         peak_left: f32,
@@ -91,52 +120,115 @@ pub enum EngineEvent {
         is_clipping: bool,
         timestamp: u64,
     },
+    PreviewFrame {
+        node_id: Uuid,
+        width: u32,
+        height: u32,
+        format: String,
+        data_base64: String,
+    },
+    /// Scope analysis of the latest frame seen by a `Scope` output node
+    Histogram {
+        node_id: Uuid,
+        histogram: FrameHistogram,
+    },
+}
+
+impl EngineEvent {
+    /// Variant names accepted by the WebSocket `subscribe` message
+    pub const KINDS: &'static [&'static str] = &[
+        "NodeAdded",
+        "NodeRemoved",
+        "NodeConnected",
+        "NodeDisconnected",
+        "ParameterChanged",
+        "FrameProcessed",
+        "Error",
+        "NodeError",
+        "AudioLevel",
+        "PreviewFrame",
+        "Histogram",
+    ];
+
+    /// Variant name, matching the serialized tag
+    pub fn kind(&self) -> &'static str {
+        match self {
+            EngineEvent::NodeAdded { .. } => "NodeAdded",
+            EngineEvent::NodeRemoved { .. } => "NodeRemoved",
+            EngineEvent::NodeConnected { .. } => "NodeConnected",
+            EngineEvent::NodeDisconnected { .. } => "NodeDisconnected",
+            EngineEvent::ParameterChanged { .. } => "ParameterChanged",
+            EngineEvent::FrameProcessed { .. } => "FrameProcessed",
+            EngineEvent::Error { .. } => "Error",
+            EngineEvent::NodeError { .. } => "NodeError",
+            EngineEvent::AudioLevel { .. } => "AudioLevel",
+            EngineEvent::PreviewFrame { .. } => "PreviewFrame",
+            EngineEvent::Histogram { .. } => "Histogram",
+        }
+    }
+
+    /// Node-attributed errors become `NodeError`; anything else stays a generic `Error`
+    pub fn from_tracked_error(error: &TrackedError) -> Self {
+        match error.node_id {
+            Some(node_id) => EngineEvent::NodeError {
+                node_id,
+                category: error.category,
+                message: error.message.clone(),
+            },
+            None => EngineEvent::Error {
+                message: error.message.clone(),
+            },
+        }
+    }
 }
 
 impl AppState {
     pub fn new() -> Result<Self> {
-        // TODO: For development, use a mock engine to avoid Vulkan dependency
-        // In production, this should use the real ConstellationEngine
-        let engine = Arc::new(Mutex::new(Self::create_mock_engine()?));
-        let (event_sender, _) = broadcast::channel(1000);
+        // Falls back to the CPU backend when Vulkan is unavailable
+        let engine = ConstellationEngine::new(None)?;
+        tracing::info!("Engine running on {:?} backend", engine.backend());
+        Ok(Self::with_engine(Box::new(engine)))
+    }
 
-        Ok(Self {
-            engine,
-            event_sender,
-        })
+    pub fn with_engine(engine: Box<dyn VideoEngine>) -> Self {
+        Self::with_event_capacity(engine, DEFAULT_EVENT_CAPACITY)
     }
 
-    // Mock engine for development/testing without Vulkan
-    fn create_mock_engine() -> Result<ConstellationEngine> {
-        // Create a mock engine that doesn't require Vulkan initialization
-        // This is temporary for development and communication testing
-        tracing::warn!("Using mock engine without Vulkan for development");
-
-        // For now, we'll create the engine but handle the Vulkan error gracefully
-        match ConstellationEngine::new() {
-            Ok(engine) => Ok(engine),
-            Err(e) => {
-                tracing::warn!(
-                    "Vulkan initialization failed (expected in development): {}",
-                    e
-                );
-                // Return a custom error for now - in a real implementation,
-                // we'd create a mock engine struct
-                Err(anyhow::anyhow!(
-                    "Mock engine not implemented - Vulkan required"
-                ))
-            }
+    /// `event_capacity` bounds the broadcast backlog per WebSocket client (at least 1)
+    pub fn with_event_capacity(engine: Box<dyn VideoEngine>, event_capacity: usize) -> Self {
+        let (event_sender, _) = broadcast::channel(event_capacity.max(1));
+
+        let error_sender = event_sender.clone();
+        engine.add_error_listener(Box::new(move |error| {
+            let _ = error_sender.send(EngineEvent::from_tracked_error(error));
+        }));
+        let mut pipeline = PipelineProcessor::new();
+        pipeline.set_parameter_policy(engine.graph().parameter_policy());
+        // Route frames along the engine's connections, which connect/disconnect keep in sync
+        if let Err(e) = pipeline.set_graph(engine.graph()) {
+            tracing::warn!("Pipeline could not follow the engine graph: {}", e);
+        }
+        let engine = Arc::new(Mutex::new(engine));
+        let pipeline = Arc::new(Mutex::new(pipeline));
+        let previews = PreviewManager::new(event_sender.clone(), pipeline.clone());
+
+        Self {
+            engine,
+            event_sender,
+            previews,
+            pipeline,
+            runner: Arc::new(Mutex::new(None)),
+            system_monitor: Arc::new(Mutex::new(SystemMonitor::new())),
         }
     }
 
     pub fn add_node(&self, node_type: NodeType, config: NodeConfig) -> Result<Uuid> {
-        let node_id = Uuid::new_v4();
-
         // let processor = create_node_processor(node_type.clone(), node_id, config.clone())?;
         // self.node_processors.lock().unwrap().insert(node_id, processor);
 
         let mut engine = self.engine.lock().unwrap();
-        engine.add_node(node_type.clone(), config)?;
+        let node_id = engine.create_node(node_type.clone(), config.clone())?;
+        self.attach_processor(engine.as_mut(), node_id, node_type.clone(), config)?;
 
         let _ = self.event_sender.send(EngineEvent::NodeAdded {
             id: node_id,
@@ -146,8 +238,146 @@ impl AppState {
         Ok(node_id)
     }
 
+    /// Clone a node's type and parameters under a new id. Connections are not copied.
+    pub fn duplicate_node(&self, node_id: Uuid) -> Result<Uuid> {
+        let mut engine = self.engine.lock().unwrap();
+        let duplicate_id = engine.duplicate_node(node_id)?;
+        let (node_type, config) = {
+            let node = engine
+                .node(duplicate_id)
+                .ok_or(ConstellationError::NodeNotFound {
+                    node_id: duplicate_id,
+                })?;
+            (node.node_type.clone(), node.config.clone())
+        };
+        self.attach_processor(engine.as_mut(), duplicate_id, node_type.clone(), config)?;
+
+        let _ = self.event_sender.send(EngineEvent::NodeAdded {
+            id: duplicate_id,
+            node_type,
+        });
+
+        Ok(duplicate_id)
+    }
+
+    /// Replace the whole graph with one serialized by `NodeGraph::to_json`.
+    ///
+    /// Imported nodes get fresh ids; the returned map goes from the ids in the
+    /// payload to the new ones. The payload is fully validated (structure, cycles
+    /// and declared port types) before anything is touched, so a bad payload
+    /// leaves the current graph as it was.
+    pub fn apply_graph(&self, json: &str) -> Result<HashMap<Uuid, Uuid>> {
+        let imported = NodeGraph::from_json(json)?;
+
+        let mut graph = NodeGraph::new();
+        let mut id_map = HashMap::new();
+        let mut processors = Vec::new();
+        for &old_id in imported.node_ids() {
+            let node = imported
+                .get_node(&old_id)
+                .ok_or(ConstellationError::NodeNotFound { node_id: old_id })?;
+            let new_id = Uuid::new_v4();
+            let mut new_node = Node::new(new_id, node.node_type.clone(), node.config.clone());
+            match create_node_processor(node.node_type.clone(), new_id, node.config.clone()) {
+                Ok(processor) => {
+                    let properties = processor.get_properties();
+                    new_node = new_node
+                        .with_ports(NodePorts {
+                            inputs: properties.input_types,
+                            outputs: properties.output_types,
+                        })
+                        .with_parameter_definitions(properties.parameters);
+                    processors.push((new_id, processor));
+                }
+                Err(e) => tracing::warn!("No pipeline processor for node {}: {}", new_id, e),
+            }
+            graph.add_node(new_node);
+            id_map.insert(old_id, new_id);
+        }
+        for (source, target, connection_type) in imported.get_connections() {
+            graph.connect_nodes(id_map[source], id_map[target], connection_type.clone())?;
+        }
+
+        let mut engine = self.engine.lock().unwrap();
+        let removed: Vec<Uuid> = engine.graph().node_ids().copied().collect();
+        graph.set_parameter_policy(engine.graph().parameter_policy());
+        *engine.graph_mut() = graph;
+        {
+            let mut pipeline = self.pipeline.lock().unwrap();
+            for node_id in &removed {
+                pipeline.remove_node(node_id);
+            }
+            for (node_id, processor) in processors {
+                pipeline.add_node(node_id, processor);
+            }
+            pipeline.set_graph(engine.graph())?;
+        }
+
+        for id in removed {
+            let _ = self.event_sender.send(EngineEvent::NodeRemoved { id });
+        }
+        for &id in id_map.values() {
+            if let Some(node) = engine.node(id) {
+                let _ = self.event_sender.send(EngineEvent::NodeAdded {
+                    id,
+                    node_type: node.node_type.clone(),
+                });
+            }
+        }
+        for (source_id, target_id, connection_type) in engine.graph().get_connections() {
+            let _ = self.event_sender.send(EngineEvent::NodeConnected {
+                source_id: *source_id,
+                target_id: *target_id,
+                connection_type: connection_type.clone(),
+            });
+        }
+
+        tracing::info!(
+            "Applied graph with {} nodes and {} connections",
+            id_map.len(),
+            engine.graph().get_connections().len()
+        );
+        Ok(id_map)
+    }
+
+    // Processing-time metrics come from the pipeline, so give it a processor too
+    fn attach_processor(
+        &self,
+        engine: &mut dyn VideoEngine,
+        node_id: Uuid,
+        node_type: NodeType,
+        config: NodeConfig,
+    ) -> Result<()> {
+        match create_node_processor(node_type, node_id, config) {
+            Ok(processor) => {
+                // Declared ports let graph validation check connection types,
+                // and parameter definitions let the engine validate values
+                let properties = processor.get_properties();
+                engine.set_node_ports(
+                    node_id,
+                    NodePorts {
+                        inputs: properties.input_types,
+                        outputs: properties.output_types,
+                    },
+                )?;
+                engine.set_node_parameter_definitions(node_id, properties.parameters)?;
+                self.pipeline.lock().unwrap().add_node(node_id, processor);
+            }
+            Err(e) => tracing::warn!("No pipeline processor for node {}: {}", node_id, e),
+        }
+        Ok(())
+    }
+
     pub fn remove_node(&self, node_id: Uuid) -> Result<()> {
         // self.node_processors.lock().unwrap().remove(&node_id);
+        let mut engine = self.engine.lock().unwrap();
+        engine.remove_node(node_id)?;
+        {
+            let mut pipeline = self.pipeline.lock().unwrap();
+            pipeline.remove_node(&node_id);
+            pipeline.set_graph(engine.graph())?;
+        }
+
         let _ = self
             .event_sender
             .send(EngineEvent::NodeRemoved { id: node_id });
@@ -161,7 +391,8 @@ impl AppState {
         connection_type: ConnectionType,
     ) -> Result<()> {
         let mut engine = self.engine.lock().unwrap();
-        engine.connect_nodes(source_id, target_id, connection_type.clone())?;
+        engine.connect(source_id, target_id, connection_type.clone())?;
+        self.pipeline.lock().unwrap().set_graph(engine.graph())?;
 
         let _ = self.event_sender.send(EngineEvent::NodeConnected {
             source_id,
@@ -172,25 +403,143 @@ impl AppState {
         Ok(())
     }
 
+    pub fn disconnect_nodes(
+        &self,
+        source_id: Uuid,
+        target_id: Uuid,
+        connection_type: ConnectionType,
+    ) -> Result<()> {
+        let mut engine = self.engine.lock().unwrap();
+        engine.disconnect(source_id, target_id, connection_type)?;
+        self.pipeline.lock().unwrap().set_graph(engine.graph())?;
+
+        let _ = self.event_sender.send(EngineEvent::NodeDisconnected {
+            source_id,
+            target_id,
+        });
+
+        Ok(())
+    }
+
+    /// Validate a parameter against the node's definitions and apply it to both
+    /// the pipeline processor and the engine graph.
+    ///
+    /// Fails with `ParameterValidationFailed` for a value of the wrong type, or one
+    /// out of range under `OutOfRangePolicy::Reject`. Under `Clamp` the clamped
+    /// value is applied and broadcast.
     pub fn set_node_parameter(
         &self,
         node_id: Uuid,
         parameter: String,
         value: serde_json::Value,
     ) -> Result<()> {
-        // if let Some(processor) = self.node_processors.lock().unwrap().get_mut(&node_id) {
-        //     processor.set_parameter(&parameter, value.clone())?;
+        let mut engine = self.engine.lock().unwrap();
+        if engine.node(node_id).is_none() {
+            return Err(ConstellationError::NodeNotFound { node_id }.into());
+        }
+
+        // The processor goes first so node-specific checks in set_parameter
+        // can still reject the value before the graph stores it
+        let value = {
+            let mut pipeline = self.pipeline.lock().unwrap();
+            if pipeline.node_properties(&node_id).is_some() {
+                pipeline.set_node_parameter(&node_id, &parameter, value)?
+            } else {
+                value
+            }
+        };
+        let value = engine.set_node_parameter(node_id, parameter.clone(), value)?;
 
         let _ = self.event_sender.send(EngineEvent::ParameterChanged {
             node_id,
             parameter,
             value,
         });
-        // }
 
         Ok(())
     }
 
+    /// Choose whether out-of-range parameter values are rejected or clamped
+    pub fn set_parameter_policy(&self, policy: OutOfRangePolicy) {
+        self.engine.lock().unwrap().set_parameter_policy(policy);
+        self.pipeline.lock().unwrap().set_parameter_policy(policy);
+    }
+
+    /// Bypass a node (frames pass through it untouched) or re-enable it
+    ///
+    /// Takes effect from the next frame of the running pipeline.
+    pub fn set_node_bypass(&self, node_id: Uuid, bypass: bool) -> Result<()> {
+        if !self
+            .pipeline
+            .lock()
+            .unwrap()
+            .set_node_enabled(&node_id, !bypass)
+        {
+            return Err(ConstellationError::NodeNotFound { node_id }.into());
+        }
+
+        tracing::info!(
+            "Node {} {}",
+            node_id,
+            if bypass { "bypassed" } else { "enabled" }
+        );
+        Ok(())
+    }
+
+    /// Start driving the pipeline at `fps`. Does nothing if it is already running
+    pub fn start_pipeline(&self, fps: f64) -> Result<()> {
+        let mut runner = self.runner.lock().unwrap();
+        if runner.is_none() {
+            *runner = Some(PipelineRunner::spawn(
+                self.pipeline.clone(),
+                self.event_sender.clone(),
+                fps,
+            )?);
+        }
+        Ok(())
+    }
+
+    /// Stop the frame loop, waiting for the frame in flight to finish
+    pub fn stop_pipeline(&self) {
+        let runner = self.runner.lock().unwrap().take();
+        drop(runner);
+    }
+
+    pub fn is_pipeline_running(&self) -> bool {
+        self.runner.lock().unwrap().is_some()
+    }
+
+    /// Capture the node's current raster output.
+    ///
+    /// Nodes only keep a copy of their output while watched, so this watches
+    /// the node and waits for the running pipeline to finish its next frame.
+    /// Returns `Ok(None)` when the node produced no `Raster2D` frame, or when
+    /// no frame was processed (the engine is not started).
+    pub async fn capture_node_frame(&self, node_id: Uuid) -> Result<Option<VideoFrame>> {
+        if self.engine.lock().unwrap().node(node_id).is_none() {
+            return Err(ConstellationError::NodeNotFound { node_id }.into());
+        }
+
+        let watched_at = {
+            let mut pipeline = self.pipeline.lock().unwrap();
+            pipeline.watch_node_output(node_id);
+            pipeline.processed_frames()
+        };
+
+        let deadline = Instant::now() + SNAPSHOT_TIMEOUT;
+        while self.is_pipeline_running()
+            && self.pipeline.lock().unwrap().processed_frames() == watched_at
+            && Instant::now() < deadline
+        {
+            tokio::time::sleep(SNAPSHOT_POLL_INTERVAL).await;
+        }
+
+        let mut pipeline = self.pipeline.lock().unwrap();
+        let frame = pipeline.node_output(&node_id).cloned();
+        pipeline.unwatch_node_output(&node_id);
+        Ok(frame)
+    }
+
     /// Send audio level data for a specific node
     pub fn send_audio_level(&self, node_id: Uuid, audio_level: &AudioLevel) {
         let _ = self.event_sender.send(EngineEvent::AudioLevel {
@@ -208,23 +557,44 @@ impl AppState {
         });
     }
 
-    pub fn get_node_properties(&self, _node_id: Uuid) -> Option<NodeProperties> {
-        // self.node_processors
-        //     .lock()
-        //     .unwrap()
-        //     .get(&node_id)
-        //     .map(|processor| processor.get_properties())
-        None
+    /// Send scope data for a specific node
+    pub fn send_histogram(&self, node_id: Uuid, histogram: &FrameHistogram) {
+        let _ = self.event_sender.send(EngineEvent::Histogram {
+            node_id,
+            histogram: histogram.clone(),
+        });
+    }
+
+    pub fn engine_status(&self) -> EngineStatusResponse {
+        let status = self.engine.lock().unwrap().status();
+
+        EngineStatusResponse {
+            running: status.running,
+            backend: status.backend,
+            paused: status.paused,
+            pause_mode: status.pause_mode,
+            buffered_frames: status.buffered_frames,
+            fps: if status.running { 30.0 } else { 0.0 },
+            frame_count: status.frame_count,
+            node_count: status.node_count,
+        }
+    }
+
+    pub fn get_node_properties(&self, node_id: Uuid) -> Option<NodeProperties> {
+        self.pipeline.lock().unwrap().node_properties(&node_id)
     }
 
     pub fn get_all_nodes(&self) -> HashMap<Uuid, NodeProperties> {
-        // self.node_processors
-        //     .lock()
-        //     .unwrap()
-        //     .iter()
-        //     .map(|(&id, processor)| (id, processor.get_properties()))
-        //     .collect()
-        HashMap::new()
+        let pipeline = self.pipeline.lock().unwrap();
+        pipeline
+            .execution_order()
+            .iter()
+            .filter_map(|&id| {
+                pipeline
+                    .node_properties(&id)
+                    .map(|properties| (id, properties))
+            })
+            .collect()
     }
 }
 
@@ -236,6 +606,8 @@ pub async fn create_app(state: AppState) -> Router {
             get(get_node).put(update_node).delete(delete_node),
         )
         .route("/api/nodes/:id/parameters", put(set_node_parameters))
+        .route("/api/nodes/:id/duplicate", post(duplicate_node))
+        .route("/api/nodes/:id/bypass", put(set_node_bypass))
         .route("/api/connections", post(create_connection))
         .route(
             "/api/connections/:source_id/:target_id",
@@ -243,9 +615,14 @@ pub async fn create_app(state: AppState) -> Router {
         )
         .route("/api/engine/start", post(start_engine))
         .route("/api/engine/stop", post(stop_engine))
+        .route("/api/engine/pause", post(pause_engine))
+        .route("/api/engine/resume", post(resume_engine))
         .route("/api/engine/status", get(get_engine_status))
+        .route("/api/graph", post(apply_graph))
+        .route("/api/graph/validate", get(validate_graph))
         .route("/api/nodes/:id/preview", post(start_node_preview))
         .route("/api/nodes/:id/preview/stop", post(stop_node_preview))
+        .route("/api/nodes/:id/snapshot", post(snapshot_node))
         .route("/api/monitoring/start", post(start_monitoring))
         .route("/api/monitoring/stop", post(stop_monitoring))
         .route("/api/monitoring/metrics", get(get_monitoring_metrics))
@@ -276,20 +653,42 @@ pub struct CreateConnectionRequest {
     pub connection_type: ConnectionType,
 }
 
+#[derive(Debug, Serialize, Deserialize)]
+pub struct DeleteConnectionQuery {
+    /// Defaults to RenderData when omitted
+    pub connection_type: Option<ConnectionType>,
+}
+
 #[derive(Debug, Serialize, Deserialize)]
 pub struct SetParametersRequest {
     pub parameters: HashMap<String, serde_json::Value>,
 }
 
+#[derive(Debug, Serialize, Deserialize)]
+pub struct BypassRequest {
+    pub bypass: bool,
+}
+
 #[derive(Debug, Serialize, Deserialize)]
 pub struct EngineStatusResponse {
     pub running: bool,
+    pub backend: BackendKind,
+    pub paused: bool,
+    pub pause_mode: PauseMode,
+    /// Frames held while paused in `PauseMode::Buffer`
+    pub buffered_frames: usize,
     pub fps: f64,
     pub frame_count: u64,
     pub node_count: usize,
 }
 
 #[derive(Debug, Serialize, Deserialize)]
+pub struct GraphValidationResponse {
+    pub valid: bool,
+    pub issues: Vec<GraphIssue>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct PreviewRequest {
     pub width: u32,
     pub height: u32,
@@ -363,19 +762,78 @@ async fn delete_node(
     }
 }
 
+async fn duplicate_node(
+    State(state): State<AppState>,
+    Path(id): Path<Uuid>,
+) -> Result<Json<Uuid>, StatusCode> {
+    match state.duplicate_node(id) {
+        Ok(duplicate_id) => Ok(Json(duplicate_id)),
+        Err(e) => match e.downcast_ref::<ConstellationError>() {
+            Some(ConstellationError::NodeNotFound { .. }) => Err(StatusCode::NOT_FOUND),
+            _ => Err(StatusCode::INTERNAL_SERVER_ERROR),
+        },
+    }
+}
+
 async fn set_node_parameters(
     State(state): State<AppState>,
     Path(id): Path<Uuid>,
     Json(request): Json<SetParametersRequest>,
 ) -> Result<Json<()>, StatusCode> {
     for (parameter, value) in request.parameters {
-        if state.set_node_parameter(id, parameter, value).is_err() {
-            return Err(StatusCode::INTERNAL_SERVER_ERROR);
+        if let Err(e) = state.set_node_parameter(id, parameter, value) {
+            tracing::warn!("Rejected parameter for node {}: {}", id, e);
+            return match e.downcast_ref::<ConstellationError>() {
+                Some(ConstellationError::NodeNotFound { .. }) => Err(StatusCode::NOT_FOUND),
+                Some(ConstellationError::ParameterValidationFailed { .. }) => {
+                    Err(StatusCode::BAD_REQUEST)
+                }
+                _ => Err(StatusCode::INTERNAL_SERVER_ERROR),
+            };
         }
     }
     Ok(Json(()))
 }
 
+async fn set_node_bypass(
+    State(state): State<AppState>,
+    Path(id): Path<Uuid>,
+    Json(request): Json<BypassRequest>,
+) -> Result<Json<()>, StatusCode> {
+    match state.set_node_bypass(id, request.bypass) {
+        Ok(()) => Ok(Json(())),
+        Err(e) => match e.downcast_ref::<ConstellationError>() {
+            Some(ConstellationError::NodeNotFound { .. }) => Err(StatusCode::NOT_FOUND),
+            _ => Err(StatusCode::INTERNAL_SERVER_ERROR),
+        },
+    }
+}
+
+async fn snapshot_node(State(state): State<AppState>, Path(id): Path<Uuid>) -> Response {
+    let frame = match state.capture_node_frame(id).await {
+        Ok(Some(frame)) => frame,
+        Ok(None) => {
+            return (
+                StatusCode::CONFLICT,
+                "Node has no raster output (is the engine started?)",
+            )
+                .into_response();
+        }
+        Err(e) => {
+            let status = match e.downcast_ref::<ConstellationError>() {
+                Some(ConstellationError::NodeNotFound { .. }) => StatusCode::NOT_FOUND,
+                _ => StatusCode::INTERNAL_SERVER_ERROR,
+            };
+            return (status, e.to_string()).into_response();
+        }
+    };
+
+    match snapshot::encode_png(&frame) {
+        Ok(png) => ([(header::CONTENT_TYPE, "image/png")], png).into_response(),
+        Err(e) => (StatusCode::INTERNAL_SERVER_ERROR, e.to_string()).into_response(),
+    }
+}
+
 async fn create_connection(
     State(state): State<AppState>,
     Json(request): Json<CreateConnectionRequest>,
@@ -386,33 +844,120 @@ async fn create_connection(
         request.connection_type,
     ) {
         Ok(_) => Ok(Json(())),
-        Err(_) => Err(StatusCode::INTERNAL_SERVER_ERROR),
+        Err(e) => {
+            tracing::warn!("Rejected connection: {}", e);
+            match e.downcast_ref::<ConstellationError>() {
+                Some(ConstellationError::NodeNotFound { .. }) => Err(StatusCode::NOT_FOUND),
+                Some(
+                    ConstellationError::IncompatibleConnection { .. }
+                    | ConstellationError::ConnectionCycleDetected { .. },
+                ) => Err(StatusCode::BAD_REQUEST),
+                _ => Err(StatusCode::INTERNAL_SERVER_ERROR),
+            }
+        }
     }
 }
 
 async fn delete_connection(
-    State(_state): State<AppState>,
-    Path((_source_id, _target_id)): Path<(Uuid, Uuid)>,
+    State(state): State<AppState>,
+    Path((source_id, target_id)): Path<(Uuid, Uuid)>,
+    Query(query): Query<DeleteConnectionQuery>,
 ) -> Result<Json<()>, StatusCode> {
-    Ok(Json(()))
+    let connection_type = query.connection_type.unwrap_or(ConnectionType::RenderData);
+    match state.disconnect_nodes(source_id, target_id, connection_type) {
+        Ok(_) => Ok(Json(())),
+        Err(_) => Err(StatusCode::NOT_FOUND),
+    }
+}
+
+async fn start_engine(State(state): State<AppState>) -> Result<Json<()>, StatusCode> {
+    // Release the pipeline lock before the runner takes it
+    let started = state.pipeline.lock().unwrap().start();
+    let started = started
+        .and_then(|()| {
+            state
+                .engine
+                .lock()
+                .unwrap()
+                .start()
+                .map_err(anyhow::Error::from)
+        })
+        .and_then(|()| state.start_pipeline(runner::DEFAULT_PIPELINE_FPS));
+    match started {
+        Ok(()) => Ok(Json(())),
+        Err(e) => {
+            tracing::error!("Failed to start pipeline: {:#}", e);
+            let _ = state.event_sender.send(EngineEvent::Error {
+                message: format!("{:#}", e),
+            });
+            Err(StatusCode::INTERNAL_SERVER_ERROR)
+        }
+    }
 }
 
-async fn start_engine(State(_state): State<AppState>) -> Json<()> {
+async fn stop_engine(State(state): State<AppState>) -> Json<()> {
+    state.stop_pipeline();
+    if let Err(e) = state.pipeline.lock().unwrap().stop() {
+        tracing::warn!("Some nodes failed to stop cleanly: {}", e);
+    }
+    if let Err(e) = state.engine.lock().unwrap().stop() {
+        tracing::warn!("Engine failed to stop cleanly: {}", e);
+    }
     Json(())
 }
 
-async fn stop_engine(State(_state): State<AppState>) -> Json<()> {
+async fn pause_engine(State(state): State<AppState>) -> Json<()> {
+    state.engine.lock().unwrap().pause();
     Json(())
 }
 
+async fn resume_engine(State(state): State<AppState>) -> Result<Json<()>, StatusCode> {
+    match state.engine.lock().unwrap().resume() {
+        Ok(replayed) => {
+            tracing::info!(
+                "Engine resumed, replayed {} buffered frames",
+                replayed.len()
+            );
+            Ok(Json(()))
+        }
+        Err(e) => {
+            tracing::error!("Failed to replay buffered frames on resume: {}", e);
+            Err(StatusCode::INTERNAL_SERVER_ERROR)
+        }
+    }
+}
+
 async fn get_engine_status(State(state): State<AppState>) -> Json<EngineStatusResponse> {
-    let node_count = state.get_all_nodes().len();
+    Json(state.engine_status())
+}
 
-    Json(EngineStatusResponse {
-        running: true,
-        fps: 30.0,
-        frame_count: 0,
-        node_count,
+async fn apply_graph(
+    State(state): State<AppState>,
+    Json(graph): Json<serde_json::Value>,
+) -> Result<Json<HashMap<Uuid, Uuid>>, StatusCode> {
+    match state.apply_graph(&graph.to_string()) {
+        Ok(id_map) => Ok(Json(id_map)),
+        Err(e) => {
+            tracing::warn!("Rejected graph import: {}", e);
+            match e.downcast_ref::<ConstellationError>() {
+                Some(
+                    ConstellationError::ConfigurationError { .. }
+                    | ConstellationError::InvalidConnection { .. }
+                    | ConstellationError::IncompatibleConnection { .. }
+                    | ConstellationError::ConnectionCycleDetected { .. },
+                ) => Err(StatusCode::BAD_REQUEST),
+                _ => Err(StatusCode::INTERNAL_SERVER_ERROR),
+            }
+        }
+    }
+}
+
+async fn validate_graph(State(state): State<AppState>) -> Json<GraphValidationResponse> {
+    let issues = state.engine.lock().unwrap().validate_graph();
+
+    Json(GraphValidationResponse {
+        valid: issues.is_empty(),
+        issues,
     })
 }
 
@@ -420,7 +965,7 @@ async fn get_engine_status(State(state): State<AppState>) -> Json<EngineStatusRe
 
 async fn start_node_preview(
     Path(node_id): Path<Uuid>,
-    State(_state): State<AppState>,
+    State(state): State<AppState>,
     Json(request): Json<PreviewRequest>,
 ) -> Result<Json<String>, StatusCode> {
     tracing::info!(
@@ -429,25 +974,23 @@ async fn start_node_preview(
         request
     );
 
-    // For now, return success
-    // In a real implementation, we would:
-    // 1. Validate the node exists
-    // 2. Start capturing frames from the node
-    // 3. Set up streaming to the frontend
+    if let Err(e) = state.previews.start(node_id, &request) {
+        tracing::warn!("Rejected preview request for node {}: {}", node_id, e);
+        return Err(StatusCode::BAD_REQUEST);
+    }
 
     Ok(Json("Preview started successfully".to_string()))
 }
 
 async fn stop_node_preview(
     Path(node_id): Path<Uuid>,
-    State(_state): State<AppState>,
+    State(state): State<AppState>,
 ) -> Result<Json<String>, StatusCode> {
     tracing::info!("Stopping preview for node {}", node_id);
 
-    // For now, return success
-    // In a real implementation, we would:
-    // 1. Stop capturing frames from the node
-    // 2. Clean up streaming resources
+    if !state.previews.stop(node_id) {
+        return Err(StatusCode::NOT_FOUND);
+    }
 
     Ok(Json("Preview stopped successfully".to_string()))
 }
@@ -482,12 +1025,37 @@ async fn stop_monitoring(State(_state): State<AppState>) -> Result<Json<String>,
     Ok(Json("Monitoring stopped successfully".to_string()))
 }
 
+/// Per-node processing times measured by the pipeline, in execution order
+pub fn collect_node_metrics(pipeline: &PipelineProcessor) -> Vec<NodeMetrics> {
+    let timings = pipeline.node_metrics();
+
+    pipeline
+        .execution_order()
+        .iter()
+        .filter_map(|node_id| {
+            let timing = timings.get(node_id)?;
+            let node_name = pipeline
+                .node_properties(node_id)
+                .map(|properties| properties.name)
+                .unwrap_or_default();
+
+            Some(NodeMetrics {
+                node_id: node_id.to_string(),
+                node_name,
+                processing_time: timing.average().as_secs_f64() * 1000.0,
+                memory_usage: 0.0,
+                error_count: 0,
+                last_error: None,
+            })
+        })
+        .collect()
+}
+
 async fn get_monitoring_metrics(
-    State(_state): State<AppState>,
+    State(state): State<AppState>,
 ) -> Result<Json<MonitoringMetrics>, StatusCode> {
     use std::time::{SystemTime, UNIX_EPOCH};
 
-    // Generate mock metrics data
     let timestamp = SystemTime::now()
         .duration_since(UNIX_EPOCH)
         .map_err(|e| {
@@ -496,34 +1064,28 @@ async fn get_monitoring_metrics(
         })?
         .as_millis() as u64;
 
-    let metrics = MonitoringMetrics {
-        timestamp,
-        fps: 30.0 + (rand::random::<f64>() - 0.5) * 10.0,
-        cpu: 45.0 + (rand::random::<f64>() - 0.5) * 30.0,
-        memory: 68.0 + (rand::random::<f64>() - 0.5) * 20.0,
-        gpu: 52.0 + (rand::random::<f64>() - 0.5) * 25.0,
-        latency: 35.0 + (rand::random::<f64>() - 0.5) * 20.0,
-        frame_time: 33.3 + (rand::random::<f64>() - 0.5) * 10.0,
-        drops: rand::random::<u64>() % 5,
-        nodes: vec![
-            NodeMetrics {
-                node_id: "node_1".to_string(),
-                node_name: "Screen Capture".to_string(),
-                processing_time: 2.5 + (rand::random::<f64>() - 0.5) * 2.0,
-                memory_usage: 15.2 + (rand::random::<f64>() - 0.5) * 5.0,
-                error_count: 0,
-                last_error: None,
-            },
-            NodeMetrics {
-                node_id: "node_2".to_string(),
-                node_name: "Color Correction".to_string(),
-                processing_time: 1.8 + (rand::random::<f64>() - 0.5) * 1.0,
-                memory_usage: 8.7 + (rand::random::<f64>() - 0.5) * 3.0,
-                error_count: 0,
-                last_error: None,
-            },
-        ],
+    let usage = state.system_monitor.lock().unwrap().sample();
+    let (stats, gpu) = {
+        let engine = state.engine.lock().unwrap();
+        (engine.session_stats(), engine.gpu_utilization())
     };
+    let (pipeline_latency, nodes) = {
+        let pipeline = state.pipeline.lock().unwrap();
+        let latency = pipeline.path_latency();
+        (
+            latency.video.max(latency.audio),
+            collect_node_metrics(&pipeline),
+        )
+    };
+
+    let metrics = monitoring::build_monitoring_metrics(
+        timestamp,
+        &stats,
+        usage,
+        gpu,
+        pipeline_latency,
+        nodes,
+    );
 
     Ok(Json(metrics))
 }
@@ -632,52 +1194,679 @@ mod tests {
 
     #[tokio::test]
     async fn test_app_state_creation() {
-        // Skip Vulkan-dependent tests in CI environments or when Vulkan is not available
-        if std::env::var("CI").is_ok() {
-            return;
-        }
+        // Starts on the CPU backend when Vulkan is unavailable
+        let state = AppState::new().unwrap();
+        assert_eq!(state.get_all_nodes().len(), 0);
+    }
+
+    #[tokio::test]
+    async fn test_node_operations() {
+        let state = AppState::new().unwrap();
+        let node_id = state
+            .add_node(
+                NodeType::Input(InputType::TestPattern),
+                NodeConfig {
+                    parameters: HashMap::new(),
+                },
+            )
+            .unwrap();
+
+        assert_eq!(state.get_all_nodes().len(), 1);
+        assert!(state.get_node_properties(node_id).is_some());
+
+        state.remove_node(node_id).unwrap();
+        assert!(state.get_node_properties(node_id).is_none());
+    }
+
+    #[tokio::test]
+    async fn test_zero_event_capacity_is_clamped() {
+        let state = AppState::with_event_capacity(Box::new(MockEngine::new()), 0);
+        let mut events = state.event_sender.subscribe();
+        let node_id = state
+            .add_node(
+                NodeType::Input(InputType::TestPattern),
+                NodeConfig {
+                    parameters: HashMap::new(),
+                },
+            )
+            .unwrap();
+
+        assert!(matches!(
+            events.recv().await,
+            Ok(EngineEvent::NodeAdded { id, .. }) if id == node_id
+        ));
+    }
 
-        // Try to create AppState, but handle Vulkan initialization gracefully
-        match AppState::new() {
-            Ok(state) => {
-                assert_eq!(state.get_all_nodes().len(), 0);
+    #[test]
+    fn test_engine_event_from_tracked_error() {
+        let node_id = Uuid::new_v4();
+        let mut tracked = TrackedError {
+            timestamp: 0,
+            error_type: "NodeProcessingFailed".to_string(),
+            message: "boom".to_string(),
+            severity: ErrorSeverity::Error,
+            category: ErrorCategory::Node,
+            context: HashMap::new(),
+            node_id: Some(node_id),
+            stack_trace: None,
+        };
+
+        match EngineEvent::from_tracked_error(&tracked) {
+            EngineEvent::NodeError {
+                node_id: id,
+                category,
+                message,
+            } => {
+                assert_eq!(id, node_id);
+                assert_eq!(category, ErrorCategory::Node);
+                assert_eq!(message, "boom");
             }
-            Err(_) => {
-                // Vulkan not available - this is expected in some environments
-                println!("Vulkan not available, skipping test");
+            other => panic!("expected NodeError, got {other:?}"),
+        }
+
+        tracked.node_id = None;
+        assert!(matches!(
+            EngineEvent::from_tracked_error(&tracked),
+            EngineEvent::Error { .. }
+        ));
+    }
+
+    #[tokio::test]
+    async fn test_node_failure_broadcasts_node_error() {
+        let state = AppState::new().unwrap();
+        let mut events = state.event_sender.subscribe();
+
+        let node_id = Uuid::new_v4();
+        let error = ConstellationError::NodeProcessingFailed {
+            node_id,
+            reason: "shader dispatch failed".to_string(),
+        };
+        state
+            .engine
+            .lock()
+            .unwrap()
+            .report_node_error(node_id, &error);
+
+        match events.recv().await.unwrap() {
+            EngineEvent::NodeError {
+                node_id: id,
+                category,
+                ..
+            } => {
+                assert_eq!(id, node_id);
+                assert_eq!(category, ErrorCategory::Node);
             }
+            other => panic!("expected NodeError, got {other:?}"),
         }
     }
 
     #[tokio::test]
-    async fn test_node_operations() {
-        // Skip Vulkan-dependent tests in CI environments or when Vulkan is not available
-        if std::env::var("CI").is_ok() {
-            return;
-        }
-
-        // Try to create AppState, but handle Vulkan initialization gracefully
-        match AppState::new() {
-            Ok(state) => {
-                let node_id = state
-                    .add_node(
-                        NodeType::Input(InputType::TestPattern),
-                        NodeConfig {
-                            parameters: HashMap::new(),
-                        },
-                    )
-                    .unwrap();
-
-                assert_eq!(state.get_all_nodes().len(), 1);
-                assert!(state.get_node_properties(node_id).is_some());
-
-                state.remove_node(node_id).unwrap();
-                assert!(state.get_node_properties(node_id).is_none());
+    async fn test_send_histogram_broadcasts_scope_data() {
+        let state = AppState::with_engine(Box::new(MockEngine::new()));
+        let mut events = state.event_sender.subscribe();
+        let frame = VideoFrame {
+            width: 4,
+            height: 1,
+            format: VideoFormat::Rgb8,
+            data: vec![0, 0, 0, 85, 85, 85, 170, 170, 170, 255, 255, 255],
+        };
+        let histogram = constellation_nodes::compute_histogram(&frame, 4, 2).unwrap();
+
+        let node_id = Uuid::new_v4();
+        state.send_histogram(node_id, &histogram);
+
+        let event = events.recv().await.unwrap();
+        assert_eq!(event.kind(), "Histogram");
+        let json = serde_json::to_value(&event).unwrap();
+        assert_eq!(json["Histogram"]["node_id"], node_id.to_string());
+        assert_eq!(
+            json["Histogram"]["histogram"]["luma"],
+            serde_json::json!([1, 1, 1, 1])
+        );
+    }
+
+    const PNG_SIGNATURE: [u8; 8] = [0x89, b'P', b'N', b'G', 0x0D, 0x0A, 0x1A, 0x0A];
+
+    #[test]
+    fn test_encode_png_swaps_bgra() {
+        let frame = VideoFrame {
+            width: 1,
+            height: 1,
+            format: VideoFormat::Bgra8,
+            data: vec![10, 20, 30, 255],
+        };
+        let png = snapshot::encode_png(&frame).unwrap();
+        let decoded = image::load_from_memory(&png).unwrap().to_rgba8();
+        assert_eq!(decoded.get_pixel(0, 0).0, [30, 20, 10, 255]);
+    }
+
+    /// Keeps frames small so the pipeline thread runs quickly in debug builds
+    fn small_pattern() -> NodeConfig {
+        NodeConfig {
+            parameters: HashMap::from([
+                ("width".to_string(), serde_json::json!(64)),
+                ("height".to_string(), serde_json::json!(48)),
+            ]),
+        }
+    }
+
+    async fn snapshot_png(state: &AppState, node_id: Uuid) -> image::RgbaImage {
+        let response = snapshot_node(State(state.clone()), Path(node_id)).await;
+        assert_eq!(response.status(), StatusCode::OK);
+        assert_eq!(response.headers()[header::CONTENT_TYPE], "image/png");
+        let body = axum::body::to_bytes(response.into_body(), usize::MAX)
+            .await
+            .unwrap();
+        assert_eq!(&body[..8], &PNG_SIGNATURE);
+        image::load_from_memory(&body).unwrap().to_rgba8()
+    }
+
+    #[tokio::test]
+    async fn test_snapshot_endpoint_returns_png() {
+        let state = AppState::new().unwrap();
+        let node_id = state
+            .add_node(NodeType::Input(InputType::TestPattern), small_pattern())
+            .unwrap();
+        let mixer_id = state
+            .add_node(
+                NodeType::Audio(AudioType::Mixer),
+                NodeConfig {
+                    parameters: HashMap::new(),
+                },
+            )
+            .unwrap();
+
+        // The engine is not started, so there is no frame to serve
+        let response = snapshot_node(State(state.clone()), Path(node_id)).await;
+        assert_eq!(response.status(), StatusCode::CONFLICT);
+
+        let Json(()) = start_engine(State(state.clone())).await.unwrap();
+        assert!(state.is_pipeline_running());
+        snapshot_png(&state, node_id).await;
+
+        let response = snapshot_node(State(state.clone()), Path(mixer_id)).await;
+        assert_eq!(response.status(), StatusCode::CONFLICT);
+
+        let response = snapshot_node(State(state.clone()), Path(Uuid::new_v4())).await;
+        assert_eq!(response.status(), StatusCode::NOT_FOUND);
+
+        let Json(()) = stop_engine(State(state.clone())).await;
+        assert!(!state.is_pipeline_running());
+        // Snapshots no longer leave their node watched
+        assert!(state
+            .pipeline
+            .lock()
+            .unwrap()
+            .node_output(&node_id)
+            .is_none());
+    }
+
+    #[tokio::test]
+    async fn test_snapshot_of_effect_downstream_of_test_pattern() {
+        let state = AppState::new().unwrap();
+        let pattern_id = state
+            .add_node(NodeType::Input(InputType::TestPattern), small_pattern())
+            .unwrap();
+        let effect_id = state
+            .add_node(
+                NodeType::Effect(EffectType::Grayscale),
+                NodeConfig {
+                    parameters: HashMap::new(),
+                },
+            )
+            .unwrap();
+        state
+            .connect_nodes(pattern_id, effect_id, ConnectionType::RenderData)
+            .unwrap();
+
+        let Json(()) = start_engine(State(state.clone())).await.unwrap();
+        let source = snapshot_png(&state, pattern_id).await;
+        let effect = snapshot_png(&state, effect_id).await;
+        let Json(()) = stop_engine(State(state)).await;
+
+        // The effect's snapshot is its own (grayscale) output of the pattern
+        assert_eq!(effect.dimensions(), source.dimensions());
+        assert_ne!(effect, source);
+        assert!(effect.pixels().all(|p| p[0] == p[1] && p[1] == p[2]));
+    }
+
+    #[test]
+    fn test_collect_node_metrics_reports_measured_times() {
+        let mut pipeline = PipelineProcessor::new();
+        let mut ids = Vec::new();
+        for node_type in [
+            NodeType::Input(InputType::TestPattern),
+            NodeType::Effect(EffectType::ColorCorrection),
+        ] {
+            let node_id = Uuid::new_v4();
+            let mut parameters = HashMap::new();
+            parameters.insert("width".to_string(), serde_json::Value::from(160));
+            parameters.insert("height".to_string(), serde_json::Value::from(90));
+            let processor =
+                create_node_processor(node_type, node_id, NodeConfig { parameters }).unwrap();
+            pipeline.add_node(node_id, processor);
+            ids.push(node_id);
+        }
+
+        // Nothing is reported before the first frame
+        assert!(collect_node_metrics(&pipeline).is_empty());
+
+        pipeline
+            .process_frame(FrameData {
+                render_data: None,
+                audio_data: None,
+                control_data: None,
+                tally_metadata: TallyMetadata::new(),
+                timestamp: std::time::Duration::ZERO,
+                sequence: 0,
+                checksum: None,
+            })
+            .unwrap();
+
+        let metrics = collect_node_metrics(&pipeline);
+        assert_eq!(metrics.len(), 2);
+        for node_id in &ids {
+            let node = metrics
+                .iter()
+                .find(|m| m.node_id == node_id.to_string())
+                .unwrap();
+            assert!(!node.node_name.is_empty());
+            assert!(node.processing_time > 0.0 && node.processing_time < 1000.0);
+        }
+    }
+
+    #[tokio::test]
+    async fn test_engine_status_reports_pause_state() {
+        let state = AppState::new().unwrap();
+        let Json(status) = get_engine_status(State(state.clone())).await;
+        assert!(!status.paused);
+
+        let Json(()) = pause_engine(State(state.clone())).await;
+        let Json(status) = get_engine_status(State(state.clone())).await;
+        assert!(status.paused);
+        assert_eq!(status.buffered_frames, 0);
+
+        let Json(()) = resume_engine(State(state.clone())).await.unwrap();
+        let Json(status) = get_engine_status(State(state)).await;
+        assert!(!status.paused);
+    }
+
+    #[tokio::test]
+    async fn test_validate_graph_endpoint_reports_issues() {
+        let state = AppState::new().unwrap();
+        let Json(response) = validate_graph(State(state.clone())).await;
+        assert!(response.valid);
+
+        let output_id = state
+            .add_node(
+                NodeType::Output(OutputType::Preview),
+                NodeConfig {
+                    parameters: HashMap::new(),
+                },
+            )
+            .unwrap();
+
+        let Json(response) = validate_graph(State(state)).await;
+        assert!(!response.valid);
+        assert!(response
+            .issues
+            .contains(&GraphIssue::OrphanNode { node_id: output_id }));
+        assert!(response
+            .issues
+            .contains(&GraphIssue::MissingRenderSource { node_id: output_id }));
+    }
+
+    #[test]
+    fn test_graph_issue_serializes_with_kind_tag() {
+        let node_id = Uuid::new_v4();
+        let json = serde_json::to_value(GraphIssue::OrphanNode { node_id }).unwrap();
+        assert_eq!(json["kind"], "OrphanNode");
+        assert_eq!(json["node_id"], node_id.to_string());
+    }
+
+    #[tokio::test]
+    async fn test_connect_nodes_rejects_mismatched_types() {
+        let state = AppState::new().unwrap();
+        let config = || NodeConfig {
+            parameters: HashMap::new(),
+        };
+        let audio_id = state
+            .add_node(NodeType::Audio(AudioType::Input), config())
+            .unwrap();
+        let pattern_id = state
+            .add_node(NodeType::Input(InputType::TestPattern), config())
+            .unwrap();
+        let preview_id = state
+            .add_node(NodeType::Output(OutputType::Preview), config())
+            .unwrap();
+
+        let error = state
+            .connect_nodes(audio_id, preview_id, ConnectionType::RenderData)
+            .unwrap_err();
+        assert!(matches!(
+            error.downcast_ref::<ConstellationError>(),
+            Some(ConstellationError::IncompatibleConnection { .. })
+        ));
+
+        state
+            .connect_nodes(pattern_id, preview_id, ConnectionType::RenderData)
+            .unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_duplicate_node_endpoint_clones_parameters() {
+        let state = AppState::with_engine(Box::new(MockEngine::new()));
+        let mut parameters = HashMap::new();
+        parameters.insert("pattern".to_string(), serde_json::json!("color_bars"));
+        parameters.insert("resolution".to_string(), serde_json::json!("1280x720"));
+        let original = state
+            .add_node(
+                NodeType::Input(InputType::TestPattern),
+                NodeConfig { parameters },
+            )
+            .unwrap();
+        let preview = state
+            .add_node(
+                NodeType::Output(OutputType::Preview),
+                NodeConfig {
+                    parameters: HashMap::new(),
+                },
+            )
+            .unwrap();
+        state
+            .connect_nodes(original, preview, ConnectionType::RenderData)
+            .unwrap();
+        let mut events = state.event_sender.subscribe();
+
+        let Json(duplicate) = duplicate_node(State(state.clone()), Path(original))
+            .await
+            .unwrap();
+        assert_ne!(duplicate, original);
+
+        {
+            let engine = state.engine.lock().unwrap();
+            assert_eq!(
+                engine.node(duplicate).unwrap().config.parameters,
+                engine.node(original).unwrap().config.parameters
+            );
+            assert_eq!(engine.status().connection_count, 1);
+        }
+        assert!(state.get_node_properties(duplicate).is_some());
+        match events.recv().await.unwrap() {
+            EngineEvent::NodeAdded { id, node_type } => {
+                assert_eq!(id, duplicate);
+                assert_eq!(node_type, NodeType::Input(InputType::TestPattern));
             }
-            Err(_) => {
-                // Vulkan not available - this is expected in some environments
-                println!("Vulkan not available, skipping test");
+            other => panic!("expected NodeAdded, got {other:?}"),
+        }
+
+        let missing = duplicate_node(State(state), Path(Uuid::new_v4())).await;
+        assert_eq!(missing.unwrap_err(), StatusCode::NOT_FOUND);
+    }
+
+    #[tokio::test]
+    async fn test_bypass_endpoint_toggles_node() {
+        let state = AppState::with_engine(Box::new(MockEngine::new()));
+        let node_id = state
+            .add_node(
+                NodeType::Effect(EffectType::Grayscale),
+                NodeConfig {
+                    parameters: HashMap::new(),
+                },
+            )
+            .unwrap();
+        assert!(state.get_node_properties(node_id).unwrap().enabled);
+
+        let Json(()) = set_node_bypass(
+            State(state.clone()),
+            Path(node_id),
+            Json(BypassRequest { bypass: true }),
+        )
+        .await
+        .unwrap();
+        assert!(!state.get_node_properties(node_id).unwrap().enabled);
+
+        let Json(()) = set_node_bypass(
+            State(state.clone()),
+            Path(node_id),
+            Json(BypassRequest { bypass: false }),
+        )
+        .await
+        .unwrap();
+        assert!(state.get_node_properties(node_id).unwrap().enabled);
+
+        let missing = set_node_bypass(
+            State(state),
+            Path(Uuid::new_v4()),
+            Json(BypassRequest { bypass: true }),
+        )
+        .await;
+        assert_eq!(missing.unwrap_err(), StatusCode::NOT_FOUND);
+    }
+
+    #[tokio::test]
+    async fn test_bypass_applies_to_running_pipeline() {
+        let state = AppState::with_engine(Box::new(MockEngine::new()));
+        let pattern_id = state
+            .add_node(NodeType::Input(InputType::TestPattern), small_pattern())
+            .unwrap();
+        let effect_id = state
+            .add_node(
+                NodeType::Effect(EffectType::Grayscale),
+                NodeConfig {
+                    parameters: HashMap::new(),
+                },
+            )
+            .unwrap();
+        state
+            .connect_nodes(pattern_id, effect_id, ConnectionType::RenderData)
+            .unwrap();
+        let Json(()) = start_engine(State(state.clone())).await.unwrap();
+
+        let Json(()) = set_node_bypass(
+            State(state.clone()),
+            Path(effect_id),
+            Json(BypassRequest { bypass: true }),
+        )
+        .await
+        .unwrap();
+        let source = snapshot_png(&state, pattern_id).await;
+        assert_eq!(snapshot_png(&state, effect_id).await, source);
+
+        let Json(()) = set_node_bypass(
+            State(state.clone()),
+            Path(effect_id),
+            Json(BypassRequest { bypass: false }),
+        )
+        .await
+        .unwrap();
+        assert_ne!(snapshot_png(&state, effect_id).await, source);
+
+        let Json(()) = stop_engine(State(state)).await;
+    }
+
+    fn brightness_request(value: f64) -> Json<SetParametersRequest> {
+        Json(SetParametersRequest {
+            parameters: HashMap::from([("brightness".to_string(), serde_json::json!(value))]),
+        })
+    }
+
+    #[tokio::test]
+    async fn test_parameters_endpoint_validates_and_applies() {
+        let state = AppState::with_engine(Box::new(MockEngine::new()));
+        let node_id = state
+            .add_node(
+                NodeType::Effect(EffectType::ColorCorrection),
+                NodeConfig {
+                    parameters: HashMap::new(),
+                },
+            )
+            .unwrap();
+        let brightness = |state: &AppState| {
+            let engine_value = state
+                .engine
+                .lock()
+                .unwrap()
+                .node(node_id)
+                .unwrap()
+                .config
+                .parameters
+                .get("brightness")
+                .cloned();
+            let processor_value = state
+                .pipeline
+                .lock()
+                .unwrap()
+                .node_parameter(&node_id, "brightness");
+            assert_eq!(engine_value, processor_value);
+            engine_value
+        };
+
+        let Json(()) =
+            set_node_parameters(State(state.clone()), Path(node_id), brightness_request(0.5))
+                .await
+                .unwrap();
+        assert_eq!(brightness(&state), Some(serde_json::json!(0.5)));
+
+        // Out of range (brightness is -1..1) is rejected and nothing changes
+        let rejected =
+            set_node_parameters(State(state.clone()), Path(node_id), brightness_request(3.0)).await;
+        assert_eq!(rejected.unwrap_err(), StatusCode::BAD_REQUEST);
+        assert_eq!(brightness(&state), Some(serde_json::json!(0.5)));
+
+        state.set_parameter_policy(OutOfRangePolicy::Clamp);
+        let mut events = state.event_sender.subscribe();
+        let Json(()) =
+            set_node_parameters(State(state.clone()), Path(node_id), brightness_request(3.0))
+                .await
+                .unwrap();
+        assert_eq!(brightness(&state), Some(serde_json::json!(1.0)));
+        match events.recv().await.unwrap() {
+            EngineEvent::ParameterChanged { value, .. } => {
+                assert_eq!(value, serde_json::json!(1.0));
             }
+            other => panic!("expected ParameterChanged, got {other:?}"),
         }
+
+        let missing =
+            set_node_parameters(State(state), Path(Uuid::new_v4()), brightness_request(0.0)).await;
+        assert_eq!(missing.unwrap_err(), StatusCode::NOT_FOUND);
+    }
+
+    fn graph_payload(nodes: &[(Uuid, NodeType)], connections: &[(Uuid, Uuid)]) -> String {
+        let mut graph = NodeGraph::new();
+        for (id, node_type) in nodes {
+            graph.add_node(Node::new(
+                *id,
+                node_type.clone(),
+                NodeConfig {
+                    parameters: HashMap::new(),
+                },
+            ));
+        }
+        for (source, target) in connections {
+            graph
+                .connect_nodes(*source, *target, ConnectionType::RenderData)
+                .unwrap();
+        }
+        graph.to_json().unwrap()
+    }
+
+    #[tokio::test]
+    async fn test_apply_graph_replaces_graph_with_remapped_ids() {
+        let state = AppState::with_engine(Box::new(MockEngine::new()));
+        let previous = state
+            .add_node(
+                NodeType::Effect(EffectType::Blur),
+                NodeConfig {
+                    parameters: HashMap::new(),
+                },
+            )
+            .unwrap();
+
+        let (pattern, blur, preview) = (Uuid::new_v4(), Uuid::new_v4(), Uuid::new_v4());
+        let payload = graph_payload(
+            &[
+                (pattern, NodeType::Input(InputType::TestPattern)),
+                (blur, NodeType::Effect(EffectType::Blur)),
+                (preview, NodeType::Output(OutputType::Preview)),
+            ],
+            &[(pattern, blur), (blur, preview)],
+        );
+        let Json(id_map) = apply_graph(
+            State(state.clone()),
+            Json(serde_json::from_str(&payload).unwrap()),
+        )
+        .await
+        .unwrap();
+        assert_eq!(id_map.len(), 3);
+
+        let engine = state.engine.lock().unwrap();
+        assert!(engine.node(previous).is_none());
+        assert_eq!(engine.status().node_count, 3);
+        assert_eq!(
+            engine.node(id_map[&preview]).unwrap().node_type,
+            NodeType::Output(OutputType::Preview)
+        );
+        let connections = engine.graph().get_connections();
+        assert_eq!(connections.len(), 2);
+        assert!(connections.contains(&(
+            id_map[&pattern],
+            id_map[&blur],
+            ConnectionType::RenderData
+        )));
+        assert!(connections.contains(&(
+            id_map[&blur],
+            id_map[&preview],
+            ConnectionType::RenderData
+        )));
+        drop(engine);
+
+        assert!(state.get_node_properties(previous).is_none());
+        assert!(id_map
+            .values()
+            .all(|id| state.get_node_properties(*id).is_some()));
+    }
+
+    #[tokio::test]
+    async fn test_apply_graph_rejects_bad_payload_without_changes() {
+        let state = AppState::with_engine(Box::new(MockEngine::new()));
+        let existing = state
+            .add_node(
+                NodeType::Input(InputType::TestPattern),
+                NodeConfig {
+                    parameters: HashMap::new(),
+                },
+            )
+            .unwrap();
+
+        // Audio input can't feed a RenderData connection once ports are declared
+        let (audio, preview) = (Uuid::new_v4(), Uuid::new_v4());
+        let payload = graph_payload(
+            &[
+                (audio, NodeType::Audio(AudioType::Input)),
+                (preview, NodeType::Output(OutputType::Preview)),
+            ],
+            &[(audio, preview)],
+        );
+        let result = apply_graph(
+            State(state.clone()),
+            Json(serde_json::from_str(&payload).unwrap()),
+        )
+        .await;
+        assert_eq!(result.unwrap_err(), StatusCode::BAD_REQUEST);
+
+        let malformed = apply_graph(
+            State(state.clone()),
+            Json(serde_json::json!({ "nodes": "not a list" })),
+        )
+        .await;
+        assert_eq!(malformed.unwrap_err(), StatusCode::BAD_REQUEST);
+
+        let engine = state.engine.lock().unwrap();
+        assert!(engine.node(existing).is_some());
+        assert_eq!(engine.status().node_count, 1);
+        drop(engine);
+        assert_eq!(state.get_all_nodes().len(), 1);
     }
 }
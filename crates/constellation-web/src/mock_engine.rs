@@ -0,0 +1,170 @@
+// In-memory engine for the development server and tests.
+// Keeps a real node graph (so connection validation behaves like production)
+// but never touches Vulkan or processes frames.
+
+use constellation_core::{
+    BackendKind, ConnectionType, ConstellationResult, EngineStatus, FrameData, Node, NodeConfig,
+    NodeGraph, NodeType, PauseController, TelemetryManager, VideoEngine,
+};
+use uuid::Uuid;
+
+#[derive(Default)]
+pub struct MockEngine {
+    node_graph: NodeGraph,
+    telemetry_manager: TelemetryManager,
+    pause_controller: PauseController,
+    running: bool,
+}
+
+impl MockEngine {
+    pub fn new() -> Self {
+        Self::default()
+    }
+}
+
+impl VideoEngine for MockEngine {
+    fn start(&mut self) -> ConstellationResult<()> {
+        self.running = true;
+        tracing::info!("Engine started (mock)");
+        Ok(())
+    }
+
+    fn stop(&mut self) -> ConstellationResult<()> {
+        self.running = false;
+        tracing::info!("Engine stopped (mock)");
+        Ok(())
+    }
+
+    fn status(&self) -> EngineStatus {
+        EngineStatus {
+            running: self.running,
+            // Nothing is rendered, so report the backend that needs no GPU
+            backend: BackendKind::Cpu,
+            paused: self.pause_controller.is_paused(),
+            pause_mode: self.pause_controller.mode(),
+            buffered_frames: self.pause_controller.buffered_frames(),
+            frame_count: 0,
+            node_count: self.node_graph.node_ids().count(),
+            connection_count: self.node_graph.get_connections().len(),
+        }
+    }
+
+    fn create_node(
+        &mut self,
+        node_type: NodeType,
+        config: NodeConfig,
+    ) -> ConstellationResult<Uuid> {
+        let node_id = Uuid::new_v4();
+        self.node_graph
+            .add_node(Node::new(node_id, node_type, config));
+        Ok(node_id)
+    }
+
+    fn connect(
+        &mut self,
+        source_id: Uuid,
+        target_id: Uuid,
+        connection_type: ConnectionType,
+    ) -> ConstellationResult<()> {
+        self.node_graph
+            .connect_nodes(source_id, target_id, connection_type)
+    }
+
+    fn pause(&mut self) {
+        self.pause_controller.pause();
+    }
+
+    fn resume(&mut self) -> ConstellationResult<Vec<FrameData>> {
+        // Buffered frames are handed back unprocessed
+        Ok(self.pause_controller.resume())
+    }
+
+    fn graph(&self) -> &NodeGraph {
+        &self.node_graph
+    }
+
+    fn graph_mut(&mut self) -> &mut NodeGraph {
+        &mut self.node_graph
+    }
+
+    fn telemetry(&self) -> &TelemetryManager {
+        &self.telemetry_manager
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use constellation_core::{ConstellationError, InputType, OutputType};
+    use std::collections::HashMap;
+
+    fn empty_config() -> NodeConfig {
+        NodeConfig {
+            parameters: HashMap::new(),
+        }
+    }
+
+    #[test]
+    fn test_mock_engine_creates_and_connects_nodes() {
+        let mut engine = MockEngine::new();
+        let source = engine
+            .create_node(NodeType::Input(InputType::TestPattern), empty_config())
+            .unwrap();
+        let target = engine
+            .create_node(NodeType::Output(OutputType::Preview), empty_config())
+            .unwrap();
+
+        engine
+            .connect(source, target, ConnectionType::RenderData)
+            .unwrap();
+
+        let status = engine.status();
+        assert_eq!(status.node_count, 2);
+        assert_eq!(status.connection_count, 1);
+        assert_eq!(
+            engine.node(target).unwrap().node_type,
+            NodeType::Output(OutputType::Preview)
+        );
+    }
+
+    #[test]
+    fn test_mock_engine_rejects_unknown_nodes_and_cycles() {
+        let mut engine = MockEngine::new();
+        let a = engine
+            .create_node(NodeType::Input(InputType::TestPattern), empty_config())
+            .unwrap();
+        let b = engine
+            .create_node(NodeType::Output(OutputType::Preview), empty_config())
+            .unwrap();
+
+        assert!(matches!(
+            engine.connect(a, Uuid::new_v4(), ConnectionType::RenderData),
+            Err(ConstellationError::NodeNotFound { .. })
+        ));
+        engine.connect(a, b, ConnectionType::RenderData).unwrap();
+        assert!(matches!(
+            engine.connect(b, a, ConnectionType::RenderData),
+            Err(ConstellationError::ConnectionCycleDetected { .. })
+        ));
+        assert_eq!(engine.status().connection_count, 1);
+    }
+
+    #[test]
+    fn test_mock_engine_status_follows_lifecycle() {
+        let mut engine = MockEngine::new();
+        assert!(!engine.status().running);
+
+        engine.start().unwrap();
+        engine.pause();
+        let status = engine.status();
+        assert!(status.running);
+        assert!(status.paused);
+        assert_eq!(status.frame_count, 0);
+
+        assert!(engine.resume().unwrap().is_empty());
+        engine.stop().unwrap();
+        let status = engine.status();
+        assert!(!status.running);
+        assert!(!status.paused);
+    }
+}
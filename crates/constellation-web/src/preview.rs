@@ -0,0 +1,366 @@
+/*
+ * Constellation Studio - Professional Real-time Video Processing
+ * Copyright (c) 2025 MACHIKO LAB
+ *
+ * This program is free software: you can redistribute it and/or modify
+ * it under the terms of the GNU Affero General Public License as published by
+ * the Free Software Foundation, either version 3 of the License, or
+ * (at your option) any later version.
+ *
+ * This program is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+ * GNU Affero General Public License for more details.
+ *
+ * You should have received a copy of the GNU Affero General Public License
+ * along with this program. If not, see <https://www.gnu.org/licenses/>.
+ */
+
+use crate::{EngineEvent, PreviewRequest};
+use anyhow::{bail, Result};
+use base64::{engine::general_purpose::STANDARD, Engine as _};
+use constellation_core::{convert::convert_video_frame, StreamVideoFrame, VideoFormat};
+use constellation_pipeline::PipelineProcessor;
+use std::{
+    collections::HashMap,
+    sync::{Arc, Mutex},
+    time::Duration,
+};
+use tokio::{sync::broadcast, task::JoinHandle};
+use uuid::Uuid;
+
+const JPEG_QUALITY: u8 = 85;
+const DEFAULT_PREVIEW_INTERVAL: Duration = Duration::from_millis(33);
+
+/// Encoding used for preview frames sent to the frontend
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PreviewEncoding {
+    Jpeg,
+    Rgba,
+}
+
+impl PreviewEncoding {
+    pub fn from_name(name: &str) -> Option<Self> {
+        match name.to_ascii_lowercase().as_str() {
+            "jpeg" | "jpg" => Some(Self::Jpeg),
+            "rgba" | "raw" => Some(Self::Rgba),
+            _ => None,
+        }
+    }
+
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::Jpeg => "jpeg",
+            Self::Rgba => "rgba",
+        }
+    }
+}
+
+/// Manages the background tasks that stream node previews over the event channel
+#[derive(Clone)]
+pub struct PreviewManager {
+    event_sender: broadcast::Sender<EngineEvent>,
+    // Previewed nodes are watched so the pipeline keeps a copy of their output
+    pipeline: Arc<Mutex<PipelineProcessor>>,
+    tasks: Arc<Mutex<HashMap<Uuid, JoinHandle<()>>>>,
+    interval: Duration,
+}
+
+impl PreviewManager {
+    pub fn new(
+        event_sender: broadcast::Sender<EngineEvent>,
+        pipeline: Arc<Mutex<PipelineProcessor>>,
+    ) -> Self {
+        Self {
+            event_sender,
+            pipeline,
+            tasks: Arc::new(Mutex::new(HashMap::new())),
+            interval: DEFAULT_PREVIEW_INTERVAL,
+        }
+    }
+
+    pub fn with_interval(mut self, interval: Duration) -> Self {
+        self.interval = interval;
+        self
+    }
+
+    /// Start streaming preview frames for a node, replacing any existing preview for it
+    pub fn start(&self, node_id: Uuid, request: &PreviewRequest) -> Result<()> {
+        if request.width == 0 || request.height == 0 {
+            bail!("Invalid preview size {}x{}", request.width, request.height);
+        }
+        let Some(encoding) = PreviewEncoding::from_name(&request.format) else {
+            bail!("Unsupported preview format: {}", request.format);
+        };
+
+        let task = tokio::spawn(preview_loop(
+            self.event_sender.clone(),
+            self.pipeline.clone(),
+            node_id,
+            request.width,
+            request.height,
+            encoding,
+            self.interval,
+        ));
+
+        match self.tasks.lock().unwrap().insert(node_id, task) {
+            Some(previous) => previous.abort(),
+            None => self.pipeline.lock().unwrap().watch_node_output(node_id),
+        }
+        Ok(())
+    }
+
+    /// Stop streaming preview frames for a node. Returns false if no preview was running
+    pub fn stop(&self, node_id: Uuid) -> bool {
+        match self.tasks.lock().unwrap().remove(&node_id) {
+            Some(task) => {
+                task.abort();
+                self.pipeline.lock().unwrap().unwatch_node_output(&node_id);
+                true
+            }
+            None => false,
+        }
+    }
+
+    pub fn is_active(&self, node_id: Uuid) -> bool {
+        self.tasks.lock().unwrap().contains_key(&node_id)
+    }
+}
+
+async fn preview_loop(
+    event_sender: broadcast::Sender<EngineEvent>,
+    pipeline: Arc<Mutex<PipelineProcessor>>,
+    node_id: Uuid,
+    max_width: u32,
+    max_height: u32,
+    encoding: PreviewEncoding,
+    interval: Duration,
+) {
+    let mut ticker = tokio::time::interval(interval);
+    let mut frame_number = 0u64;
+
+    loop {
+        ticker.tick().await;
+
+        let frame = match capture_node_frame(&pipeline, node_id, frame_number) {
+            Ok(frame) => frame,
+            Err(e) => {
+                // Expected until the engine is started and the node has run once
+                tracing::trace!("No preview frame for node {}: {}", node_id, e);
+                continue;
+            }
+        };
+        let frame = downscale_frame(&frame, max_width, max_height);
+        frame_number += 1;
+
+        match encode_preview_frame(&frame, encoding) {
+            Ok(event) => {
+                // Sending only fails when nobody is subscribed; keep streaming for later clients
+                let _ = event_sender.send(event);
+            }
+            Err(e) => {
+                tracing::warn!("Failed to encode preview frame for node {}: {}", node_id, e);
+            }
+        }
+    }
+}
+
+/// Capture the node's latest raster output from the pipeline as RGBA8
+fn capture_node_frame(
+    pipeline: &Mutex<PipelineProcessor>,
+    node_id: Uuid,
+    frame_number: u64,
+) -> Result<StreamVideoFrame> {
+    let Some(frame) = pipeline.lock().unwrap().node_output(&node_id).cloned() else {
+        bail!("node has not produced a raster frame yet");
+    };
+    // Downscaling and raw previews work on RGBA8
+    let frame = convert_video_frame(frame, &VideoFormat::Rgba8)?;
+
+    let timestamp = std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)
+        .unwrap_or_default()
+        .as_millis() as u64;
+    Ok(StreamVideoFrame {
+        node_id,
+        width: frame.width,
+        height: frame.height,
+        format: frame.format,
+        data: frame.data,
+        timestamp,
+        frame_number,
+    })
+}
+
+/// Nearest-neighbour downscale of an RGBA frame to fit within the requested size
+pub fn downscale_frame(
+    frame: &StreamVideoFrame,
+    max_width: u32,
+    max_height: u32,
+) -> StreamVideoFrame {
+    let width = max_width.min(frame.width).max(1);
+    let height = max_height.min(frame.height).max(1);
+    if frame.format != VideoFormat::Rgba8 || (width == frame.width && height == frame.height) {
+        return frame.clone();
+    }
+
+    let mut data = Vec::with_capacity((width * height * 4) as usize);
+    for y in 0..height {
+        let src_y = (y as u64 * frame.height as u64 / height as u64) as usize;
+        for x in 0..width {
+            let src_x = (x as u64 * frame.width as u64 / width as u64) as usize;
+            let offset = (src_y * frame.width as usize + src_x) * 4;
+            data.extend_from_slice(&frame.data[offset..offset + 4]);
+        }
+    }
+
+    StreamVideoFrame {
+        width,
+        height,
+        data,
+        ..frame.clone()
+    }
+}
+
+/// Encode a frame into a `PreviewFrame` event
+pub fn encode_preview_frame(
+    frame: &StreamVideoFrame,
+    encoding: PreviewEncoding,
+) -> Result<EngineEvent> {
+    let bytes = match encoding {
+        PreviewEncoding::Jpeg => frame
+            .encode_jpeg(JPEG_QUALITY)
+            .map_err(|e| anyhow::anyhow!(e))?,
+        PreviewEncoding::Rgba => {
+            if frame.format != VideoFormat::Rgba8 {
+                bail!("Raw preview requires RGBA8 frames, got {:?}", frame.format);
+            }
+            frame.data.clone()
+        }
+    };
+
+    Ok(EngineEvent::PreviewFrame {
+        node_id: frame.node_id,
+        width: frame.width,
+        height: frame.height,
+        format: encoding.as_str().to_string(),
+        data_base64: STANDARD.encode(bytes),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use constellation_core::{FrameData, InputType, NodeConfig, NodeType};
+    use constellation_nodes::create_node_processor;
+
+    fn request(width: u32, height: u32, format: &str) -> PreviewRequest {
+        PreviewRequest {
+            width,
+            height,
+            format: format.to_string(),
+        }
+    }
+
+    /// A pipeline holding a single 128x96 test pattern node
+    fn pattern_pipeline() -> (Arc<Mutex<PipelineProcessor>>, Uuid) {
+        let node_id = Uuid::new_v4();
+        let config = NodeConfig {
+            parameters: HashMap::from([
+                ("width".to_string(), serde_json::json!(128)),
+                ("height".to_string(), serde_json::json!(96)),
+            ]),
+        };
+        let processor =
+            create_node_processor(NodeType::Input(InputType::TestPattern), node_id, config)
+                .unwrap();
+        let mut pipeline = PipelineProcessor::new();
+        pipeline.add_node(node_id, processor);
+        (Arc::new(Mutex::new(pipeline)), node_id)
+    }
+
+    #[tokio::test]
+    async fn test_preview_frames_are_broadcast() {
+        let (event_sender, _) = broadcast::channel(16);
+        let mut receiver = event_sender.subscribe();
+        let (pipeline, node_id) = pattern_pipeline();
+        let previews = PreviewManager::new(event_sender, pipeline.clone())
+            .with_interval(Duration::from_millis(5));
+
+        previews.start(node_id, &request(64, 48, "rgba")).unwrap();
+        pipeline
+            .lock()
+            .unwrap()
+            .process_frame(FrameData::empty())
+            .unwrap();
+        let expected = downscale_frame(&capture_node_frame(&pipeline, node_id, 0).unwrap(), 64, 48);
+
+        for _ in 0..2 {
+            let event = tokio::time::timeout(Duration::from_secs(1), receiver.recv())
+                .await
+                .expect("preview frame should arrive")
+                .unwrap();
+            match event {
+                EngineEvent::PreviewFrame {
+                    node_id: id,
+                    width,
+                    height,
+                    format,
+                    data_base64,
+                } => {
+                    assert_eq!(id, node_id);
+                    assert_eq!((width, height), (64, 48));
+                    assert_eq!(format, "rgba");
+                    // The node's own output, not a placeholder pattern
+                    assert_eq!(STANDARD.decode(data_base64).unwrap(), expected.data);
+                }
+                other => panic!("unexpected event: {:?}", other),
+            }
+        }
+
+        assert!(previews.stop(node_id));
+        assert!(!previews.is_active(node_id));
+        assert!(!previews.stop(node_id));
+        // Stopping the preview releases the node's recorded output
+        assert!(pipeline.lock().unwrap().node_output(&node_id).is_none());
+    }
+
+    #[tokio::test]
+    async fn test_preview_waits_for_a_frame() {
+        let (event_sender, _) = broadcast::channel(16);
+        let mut receiver = event_sender.subscribe();
+        let (pipeline, node_id) = pattern_pipeline();
+        let previews =
+            PreviewManager::new(event_sender, pipeline).with_interval(Duration::from_millis(5));
+
+        previews.start(node_id, &request(64, 48, "rgba")).unwrap();
+
+        // Nothing has been processed, so nothing is sent
+        assert!(
+            tokio::time::timeout(Duration::from_millis(50), receiver.recv())
+                .await
+                .is_err()
+        );
+        assert!(previews.stop(node_id));
+    }
+
+    #[tokio::test]
+    async fn test_invalid_preview_request_rejected() {
+        let (event_sender, _) = broadcast::channel(16);
+        let (pipeline, node_id) = pattern_pipeline();
+        let previews = PreviewManager::new(event_sender, pipeline);
+
+        assert!(previews.start(node_id, &request(0, 48, "rgba")).is_err());
+        assert!(previews.start(node_id, &request(64, 48, "webp")).is_err());
+        assert!(!previews.is_active(node_id));
+    }
+
+    #[test]
+    fn test_downscale_does_not_upscale() {
+        let frame = StreamVideoFrame::test_pattern(Uuid::new_v4(), 8, 4, 0, 0);
+        let scaled = downscale_frame(&frame, 4, 100);
+        assert_eq!((scaled.width, scaled.height), (4, 4));
+        assert_eq!(scaled.data.len(), 4 * 4 * 4);
+        assert_eq!(&scaled.data[4..8], &frame.data[8..12]);
+    }
+}
@@ -0,0 +1,103 @@
+/*
+ * Constellation Studio - Professional Real-time Video Processing
+ * Copyright (c) 2025 MACHIKO LAB
+ *
+ * This program is free software: you can redistribute it and/or modify
+ * it under the terms of the GNU Affero General Public License as published by
+ * the Free Software Foundation, either version 3 of the License, or
+ * (at your option) any later version.
+ *
+ * This program is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+ * GNU Affero General Public License for more details.
+ *
+ * You should have received a copy of the GNU Affero General Public License
+ * along with this program. If not, see <https://www.gnu.org/licenses/>.
+ */
+
+//! Background frame loop that drives the pipeline while the engine runs
+
+use crate::EngineEvent;
+use anyhow::Result;
+use constellation_core::{FrameClock, FrameData};
+use constellation_pipeline::PipelineProcessor;
+use std::{
+    sync::{
+        atomic::{AtomicBool, Ordering},
+        Arc, Mutex,
+    },
+    thread::JoinHandle,
+};
+use tokio::sync::broadcast;
+
+/// Frame rate the web server drives the pipeline at
+pub const DEFAULT_PIPELINE_FPS: f64 = 30.0;
+
+/// Runs `PipelineProcessor::process_frame` on a dedicated thread at a fixed rate
+///
+/// Nodes may block on devices, so the loop stays off the async runtime. The
+/// thread is stopped and joined when the runner is dropped.
+pub struct PipelineRunner {
+    running: Arc<AtomicBool>,
+    thread: Option<JoinHandle<()>>,
+}
+
+impl PipelineRunner {
+    pub fn spawn(
+        pipeline: Arc<Mutex<PipelineProcessor>>,
+        event_sender: broadcast::Sender<EngineEvent>,
+        target_fps: f64,
+    ) -> Result<Self> {
+        let mut clock = FrameClock::new(target_fps)?;
+        pipeline.lock().unwrap().set_frame_rate(target_fps)?;
+        let running = Arc::new(AtomicBool::new(true));
+
+        let thread_running = running.clone();
+        let thread = std::thread::Builder::new()
+            .name("pipeline".to_string())
+            .spawn(move || {
+                let mut last_error: Option<String> = None;
+                while thread_running.load(Ordering::Relaxed) {
+                    let tick = clock.tick();
+                    let input = FrameData {
+                        timestamp: tick.scheduled,
+                        sequence: tick.slot + 1,
+                        ..FrameData::empty()
+                    };
+
+                    let result = pipeline.lock().unwrap().process_frame(input);
+                    match result {
+                        Ok(_) => last_error = None,
+                        Err(e) => {
+                            // A failing node fails every frame; report each distinct error once
+                            let message = format!("{:#}", e);
+                            if last_error.as_ref() != Some(&message) {
+                                tracing::warn!("Pipeline frame failed: {}", message);
+                                let _ = event_sender.send(EngineEvent::Error {
+                                    message: message.clone(),
+                                });
+                                last_error = Some(message);
+                            }
+                        }
+                    }
+                }
+            })?;
+
+        Ok(Self {
+            running,
+            thread: Some(thread),
+        })
+    }
+}
+
+impl Drop for PipelineRunner {
+    fn drop(&mut self) {
+        self.running.store(false, Ordering::Relaxed);
+        if let Some(thread) = self.thread.take() {
+            if thread.join().is_err() {
+                tracing::error!("Pipeline thread panicked");
+            }
+        }
+    }
+}
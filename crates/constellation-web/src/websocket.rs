@@ -16,7 +16,7 @@
  * along with this program. If not, see <https://www.gnu.org/licenses/>.
  */
 
-use crate::AppState;
+use crate::{AppState, EngineEvent};
 use axum::{
     extract::{
         ws::{Message, WebSocket, WebSocketUpgrade},
@@ -26,14 +26,81 @@ use axum::{
 };
 use constellation_core::{AudioLevel, StreamVideoFrame};
 use futures::{sink::SinkExt, stream::StreamExt};
-use std::collections::HashMap;
+use std::collections::{HashMap, HashSet};
 use std::sync::{Arc, Mutex};
+use tokio::sync::broadcast::error::RecvError;
 use uuid::Uuid;
 
 pub async fn websocket_handler(ws: WebSocketUpgrade, State(state): State<AppState>) -> Response {
     ws.on_upgrade(|socket| websocket_connection(socket, state))
 }
 
+/// Which `EngineEvent` variants a connection receives.
+///
+/// Connections start with every event enabled; a `subscribe` message
+/// (`{"type": "subscribe", "events": ["AudioLevel", ...]}`) narrows the set,
+/// and omitting `events` or sending `null` restores all events.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct EventFilter {
+    // None forwards every event
+    kinds: Option<HashSet<&'static str>>,
+}
+
+impl EventFilter {
+    pub fn all() -> Self {
+        Self::default()
+    }
+
+    /// Forward only the named variants. Unknown names are ignored.
+    pub fn only<'a>(kinds: impl IntoIterator<Item = &'a str>) -> Self {
+        let kinds = kinds
+            .into_iter()
+            .filter_map(|kind| {
+                let known = EngineEvent::KINDS.iter().find(|known| **known == kind);
+                if known.is_none() {
+                    tracing::warn!("Ignoring subscription to unknown event type {}", kind);
+                }
+                known.copied()
+            })
+            .collect();
+        Self { kinds: Some(kinds) }
+    }
+
+    pub fn from_subscribe_message(message: &serde_json::Value) -> Self {
+        match message.get("events").and_then(|events| events.as_array()) {
+            Some(events) => Self::only(events.iter().filter_map(|event| event.as_str())),
+            None => Self::all(),
+        }
+    }
+
+    pub fn matches(&self, event: &EngineEvent) -> bool {
+        self.kinds
+            .as_ref()
+            .is_none_or(|kinds| kinds.contains(event.kind()))
+    }
+
+    /// Subscribed variants in `EngineEvent::KINDS` order, or `None` for all
+    pub fn kinds(&self) -> Option<Vec<&'static str>> {
+        self.kinds.as_ref().map(|kinds| {
+            EngineEvent::KINDS
+                .iter()
+                .copied()
+                .filter(|kind| kinds.contains(kind))
+                .collect()
+        })
+    }
+}
+
+/// Snapshot sent when a client has missed events, so it can rebuild its view
+pub fn resync_message(state: &AppState, skipped: u64) -> serde_json::Value {
+    serde_json::json!({
+        "type": "resync",
+        "skipped": skipped,
+        "nodes": state.get_all_nodes(),
+        "status": state.engine_status(),
+    })
+}
+
 #[derive(Debug, Clone)]
 pub enum WebSocketMessage {
     Event(crate::EngineEvent),
@@ -63,9 +130,15 @@ async fn websocket_connection(socket: WebSocket, state: AppState) {
     let mut event_receiver = state.event_sender.subscribe();
     let active_previews = Arc::new(Mutex::new(HashMap::<Uuid, bool>::new()));
     let active_audio_monitors = Arc::new(Mutex::new(HashMap::<Uuid, bool>::new()));
+    let event_filter = Arc::new(Mutex::new(EventFilter::all()));
+    // Replies to client control messages are written by the send task
+    let (reply_sender, mut reply_receiver) =
+        tokio::sync::mpsc::unbounded_channel::<serde_json::Value>();
 
     let active_previews_send = active_previews.clone();
     let active_audio_send = active_audio_monitors.clone();
+    let event_filter_send = event_filter.clone();
+    let resync_state = state.clone();
     let send_task = tokio::spawn(async move {
         let mut frame_counter = 0u64;
         let mut _last_frame_time = std::time::Instant::now();
@@ -77,6 +150,10 @@ async fn websocket_connection(socket: WebSocket, state: AppState) {
                 event_result = event_receiver.recv() => {
                     match event_result {
                         Ok(event) => {
+                            if !event_filter_send.lock().unwrap().matches(&event) {
+                                continue;
+                            }
+
                             let message = match serde_json::to_string(&event) {
                                 Ok(json) => Message::Text(json),
                                 Err(_) => continue,
@@ -86,7 +163,24 @@ async fn websocket_connection(socket: WebSocket, state: AppState) {
                                 break;
                             }
                         }
-                        Err(_) => break,
+                        Err(RecvError::Lagged(skipped)) => {
+                            // Dropped events can't be replayed; send a full snapshot instead
+                            tracing::warn!(
+                                "WebSocket client lagged by {} events, sending resync",
+                                skipped
+                            );
+                            let resync = resync_message(&resync_state, skipped);
+                            if sender.send(Message::Text(resync.to_string())).await.is_err() {
+                                break;
+                            }
+                        }
+                        Err(RecvError::Closed) => break,
+                    }
+                }
+
+                Some(reply) = reply_receiver.recv() => {
+                    if sender.send(Message::Text(reply.to_string())).await.is_err() {
+                        break;
                     }
                 }
 
@@ -207,6 +301,20 @@ async fn websocket_connection(socket: WebSocket, state: AppState) {
                         // Handle preview control messages
                         if let Ok(message) = serde_json::from_str::<serde_json::Value>(&text) {
                             match message.get("type").and_then(|t| t.as_str()) {
+                                Some("subscribe") => {
+                                    let filter = EventFilter::from_subscribe_message(&message);
+                                    tracing::info!(
+                                        "WebSocket subscribed to {:?}",
+                                        filter.kinds().unwrap_or_else(|| vec!["all"])
+                                    );
+                                    let events = filter.kinds();
+                                    // Install the filter before acknowledging it
+                                    *event_filter.lock().unwrap() = filter;
+                                    let _ = reply_sender.send(serde_json::json!({
+                                        "type": "subscribed",
+                                        "events": events,
+                                    }));
+                                }
                                 Some("preview_start") => {
                                     if let Some(node_id_str) =
                                         message.get("node_id").and_then(|id| id.as_str())
@@ -282,3 +390,157 @@ async fn websocket_connection(socket: WebSocket, state: AppState) {
         _ = recv_task => {},
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{create_app, MockEngine};
+    use constellation_core::NodeType;
+    use tokio::net::{TcpListener, TcpStream};
+    use tokio_tungstenite::{
+        connect_async, tungstenite::Message as ClientMessage, MaybeTlsStream, WebSocketStream,
+    };
+
+    type Client = WebSocketStream<MaybeTlsStream<TcpStream>>;
+
+    async fn spawn_server(state: AppState) -> std::net::SocketAddr {
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let addr = listener.local_addr().unwrap();
+        let app = create_app(state).await;
+        tokio::spawn(async move { axum::serve(listener, app).await });
+        addr
+    }
+
+    async fn next_json(client: &mut Client) -> serde_json::Value {
+        let message = tokio::time::timeout(std::time::Duration::from_secs(5), client.next())
+            .await
+            .expect("timed out waiting for a WebSocket message")
+            .unwrap()
+            .unwrap();
+        match message {
+            ClientMessage::Text(text) => serde_json::from_str(&text).unwrap(),
+            other => panic!("unexpected message: {other:?}"),
+        }
+    }
+
+    async fn connect(addr: std::net::SocketAddr, events: Option<&[&str]>) -> Client {
+        let (mut client, _) = connect_async(format!("ws://{addr}/ws")).await.unwrap();
+        if let Some(events) = events {
+            let subscribe = serde_json::json!({ "type": "subscribe", "events": events });
+            client
+                .send(ClientMessage::Text(subscribe.to_string()))
+                .await
+                .unwrap();
+            let ack = next_json(&mut client).await;
+            assert_eq!(ack["type"], "subscribed");
+            assert_eq!(ack["events"], serde_json::json!(events));
+        }
+        client
+    }
+
+    // Externally tagged: `{"AudioLevel": {...}}`
+    fn kind_of(message: &serde_json::Value) -> String {
+        let object = message.as_object().unwrap();
+        assert_eq!(object.len(), 1, "not an engine event: {message}");
+        object.keys().next().unwrap().clone()
+    }
+
+    #[test]
+    fn test_event_filter_defaults_to_all_and_ignores_unknown_kinds() {
+        let event = EngineEvent::NodeRemoved { id: Uuid::new_v4() };
+        assert!(EventFilter::all().matches(&event));
+        assert_eq!(
+            EventFilter::from_subscribe_message(&serde_json::json!({ "type": "subscribe" })),
+            EventFilter::all()
+        );
+
+        let filter = EventFilter::only(["AudioLevel", "Bogus"]);
+        assert_eq!(filter.kinds(), Some(vec!["AudioLevel"]));
+        assert!(!filter.matches(&event));
+    }
+
+    #[tokio::test]
+    async fn test_websocket_clients_receive_only_subscribed_events() {
+        let state = AppState::with_engine(Box::new(MockEngine::new()));
+        let addr = spawn_server(state.clone()).await;
+
+        let mut audio_client = connect(addr, Some(&["Error", "AudioLevel"])).await;
+        let mut graph_client = connect(addr, Some(&["NodeAdded", "Error"])).await;
+        let mut default_client = connect(addr, None).await;
+        // The default client never gets an ack, so wait until its socket is subscribed
+        while state.event_sender.receiver_count() < 3 {
+            tokio::task::yield_now().await;
+        }
+
+        let node_id = Uuid::new_v4();
+        state
+            .event_sender
+            .send(EngineEvent::NodeAdded {
+                id: node_id,
+                node_type: NodeType::Input(constellation_core::InputType::TestPattern),
+            })
+            .unwrap();
+        state.send_audio_level(node_id, &AudioLevel::new());
+        state
+            .event_sender
+            .send(EngineEvent::Error {
+                message: "boom".to_string(),
+            })
+            .unwrap();
+
+        let audio_kinds = [
+            kind_of(&next_json(&mut audio_client).await),
+            kind_of(&next_json(&mut audio_client).await),
+        ];
+        assert_eq!(audio_kinds, ["AudioLevel", "Error"]);
+
+        let graph_kinds = [
+            kind_of(&next_json(&mut graph_client).await),
+            kind_of(&next_json(&mut graph_client).await),
+        ];
+        assert_eq!(graph_kinds, ["NodeAdded", "Error"]);
+
+        let mut default_kinds = Vec::new();
+        for _ in 0..3 {
+            default_kinds.push(kind_of(&next_json(&mut default_client).await));
+        }
+        assert_eq!(default_kinds, ["NodeAdded", "AudioLevel", "Error"]);
+    }
+
+    #[tokio::test]
+    async fn test_lagged_client_receives_resync_snapshot() {
+        let state = AppState::with_event_capacity(Box::new(MockEngine::new()), 4);
+        let node_id = state
+            .add_node(
+                NodeType::Input(constellation_core::InputType::TestPattern),
+                constellation_core::NodeConfig {
+                    parameters: HashMap::new(),
+                },
+            )
+            .unwrap();
+        let addr = spawn_server(state.clone()).await;
+        let mut client = connect(addr, None).await;
+        while state.event_sender.receiver_count() < 1 {
+            tokio::task::yield_now().await;
+        }
+
+        // The test runtime is single-threaded, so the server can't drain the
+        // channel until we yield: every send past the capacity overwrites an event
+        for timestamp in 0..20 {
+            state
+                .event_sender
+                .send(EngineEvent::FrameProcessed { timestamp })
+                .unwrap();
+        }
+
+        let resync = next_json(&mut client).await;
+        assert_eq!(resync["type"], "resync");
+        assert_eq!(resync["skipped"], 16);
+        assert!(resync["nodes"].get(node_id.to_string()).is_some());
+        assert_eq!(resync["status"]["node_count"], 1);
+
+        // The stream continues with the events still in the channel
+        let next = next_json(&mut client).await;
+        assert_eq!(next["FrameProcessed"]["timestamp"], 16);
+    }
+}
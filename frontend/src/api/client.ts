@@ -5,7 +5,9 @@ import type {
   NodeType, 
   NodeConfig, 
   ConnectionType, 
-  EngineEvent
+  EngineEvent,
+  EngineEventKind,
+  NodeProperties
 } from '../types';
 
 // Configuration
@@ -28,14 +30,33 @@ export interface SetParametersRequest {
   parameters: Record<string, any>;
 }
 
+export type PauseMode = 'Freeze' | 'Buffer';
+
+export type EngineBackend = 'Vulkan' | 'Cpu';
+
 export interface ApiEngineStatus {
   running: boolean;
+  backend: EngineBackend;
+  paused: boolean;
+  pause_mode: PauseMode;
+  buffered_frames: number;
   fps: number;
   frame_count: number;
   node_count: number;
   connection_count?: number;
 }
 
+export type GraphIssue =
+  | { kind: 'OrphanNode'; node_id: string }
+  | { kind: 'MissingRenderSource'; node_id: string }
+  | { kind: 'TypeMismatch'; source_id: string; target_id: string; connection_type: ConnectionType }
+  | { kind: 'Cycle'; node_ids: string[] };
+
+export interface GraphValidation {
+  valid: boolean;
+  issues: GraphIssue[];
+}
+
 export interface VideoFrameMetadata {
   type: 'video_frame';
   node_id: string;
@@ -51,12 +72,21 @@ export interface VideoFrame {
   data: ArrayBuffer;
 }
 
+// Sent instead of the missed events when the client falls behind the event stream
+export interface ResyncPayload {
+  type: 'resync';
+  skipped: number;
+  nodes: Record<string, NodeProperties>;
+  status: ApiEngineStatus;
+}
+
 // API Client Class
 export class ConstellationApiClient {
   private api: AxiosInstance;
   private wsConnection: WebSocket | null = null;
   private eventListeners: ((event: EngineEvent) => void)[] = [];
   private videoFrameListeners: ((frame: VideoFrame) => void)[] = [];
+  private resyncListeners: ((payload: ResyncPayload) => void)[] = [];
   private reconnectAttempts = 0;
   private maxReconnectAttempts = 5;
   private pendingFrameMetadata: VideoFrameMetadata | null = null;
@@ -122,11 +152,22 @@ export class ConstellationApiClient {
     await this.api.delete(`/api/nodes/${nodeId}`);
   }
 
+  // Copies type and parameters; connections are not duplicated
+  async duplicateNode(nodeId: string): Promise<string> {
+    const response = await this.api.post<string>(`/api/nodes/${nodeId}/duplicate`);
+    return response.data;
+  }
+
   async setNodeParameters(nodeId: string, parameters: Record<string, any>): Promise<void> {
     const request: SetParametersRequest = { parameters };
     await this.api.put(`/api/nodes/${nodeId}/parameters`, request);
   }
 
+  // A bypassed node passes frames through untouched
+  async setNodeBypass(nodeId: string, bypass: boolean): Promise<void> {
+    await this.api.put(`/api/nodes/${nodeId}/bypass`, { bypass });
+  }
+
   // Connection Management
   async createConnection(sourceId: string, targetId: string, connectionType: ConnectionType): Promise<void> {
     const request: CreateConnectionRequest = {
@@ -137,8 +178,10 @@ export class ConstellationApiClient {
     await this.api.post('/api/connections', request);
   }
 
-  async deleteConnection(sourceId: string, targetId: string): Promise<void> {
-    await this.api.delete(`/api/connections/${sourceId}/${targetId}`);
+  async deleteConnection(sourceId: string, targetId: string, connectionType?: ConnectionType): Promise<void> {
+    await this.api.delete(`/api/connections/${sourceId}/${targetId}`, {
+      params: connectionType ? { connection_type: connectionType } : undefined,
+    });
   }
 
   // Engine Control
@@ -150,11 +193,30 @@ export class ConstellationApiClient {
     await this.api.post('/api/engine/stop');
   }
 
+  async pauseEngine(): Promise<void> {
+    await this.api.post('/api/engine/pause');
+  }
+
+  async resumeEngine(): Promise<void> {
+    await this.api.post('/api/engine/resume');
+  }
+
   async getEngineStatus(): Promise<ApiEngineStatus> {
     const response = await this.api.get<ApiEngineStatus>('/api/engine/status');
     return response.data;
   }
 
+  // Replaces the whole graph; resolves to a map of payload ids to the new node ids
+  async applyGraph(graph: unknown): Promise<Record<string, string>> {
+    const response = await this.api.post<Record<string, string>>('/api/graph', graph);
+    return response.data;
+  }
+
+  async validateGraph(): Promise<GraphValidation> {
+    const response = await this.api.get<GraphValidation>('/api/graph/validate');
+    return response.data;
+  }
+
   // WebSocket Connection
   connectWebSocket(): Promise<void> {
     return new Promise((resolve, reject) => {
@@ -208,6 +270,24 @@ export class ConstellationApiClient {
             this.pendingFrameMetadata = message as VideoFrameMetadata;
             return;
           }
+
+          if (message.type === 'resync') {
+            console.warn(`⚠️ Missed ${message.skipped} events, resyncing`);
+            this.resyncListeners.forEach(listener => {
+              try {
+                listener(message as ResyncPayload);
+              } catch (error) {
+                console.error('❌ Error in resync listener:', error);
+              }
+            });
+            return;
+          }
+
+          // Acknowledgement of subscribeEvents()
+          if (message.type === 'subscribed') {
+            console.log('📬 Subscribed to events:', message.events ?? 'all');
+            return;
+          }
           
           // Handle regular engine events
           const engineEvent: EngineEvent = message;
@@ -279,6 +359,30 @@ export class ConstellationApiClient {
     };
   }
 
+  // Resync Listeners
+  addResyncListener(listener: (payload: ResyncPayload) => void): () => void {
+    this.resyncListeners.push(listener);
+
+    // Return unsubscribe function
+    return () => {
+      const index = this.resyncListeners.indexOf(listener);
+      if (index > -1) {
+        this.resyncListeners.splice(index, 1);
+      }
+    };
+  }
+
+  // Event Subscription (pass null to receive every event type again)
+  subscribeEvents(events: EngineEventKind[] | null): void {
+    if (this.wsConnection?.readyState === WebSocket.OPEN) {
+      const message = {
+        type: 'subscribe',
+        events
+      };
+      this.wsConnection.send(JSON.stringify(message));
+    }
+  }
+
   // Video Preview Control
   startVideoPreview(nodeId: string): void {
     if (this.wsConnection?.readyState === WebSocket.OPEN) {
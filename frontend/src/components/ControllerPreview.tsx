@@ -134,36 +134,38 @@ export const ControllerPreview: React.FC<ControllerPreviewProps> = ({
   }, [controllerType, parameters, isPlaying, colors, width, height]);
 
   const calculateLFOValue = (time: number, params: Record<string, any>) => {
-    const frequency = params.frequency || 1.0;
-    const amplitude = params.amplitude || 1.0;
+    const frequency = params.frequency ?? 1.0;
+    const amplitude = params.amplitude ?? 1.0;
     const offset = params.offset || 0.0;
     const phase = params.phase || 0.0;
+    const dutyCycle = params.duty_cycle ?? 0.5;
     const waveform = params.waveform || 'Sine';
 
-    const phaseAdjustedTime = time + (phase * (1.0 / frequency));
+    // Position within the cycle (0-1); negative frequency runs backwards
+    const cycle = (((time * frequency + phase) % 1.0) + 1.0) % 1.0;
     let baseValue = 0;
 
     switch (waveform) {
       case 'Sine':
-        baseValue = Math.sin(phaseAdjustedTime * frequency * 2 * Math.PI);
+        baseValue = Math.sin(cycle * 2 * Math.PI);
         break;
       case 'Square':
-        const sqPhase = (phaseAdjustedTime * frequency) % 1.0;
-        baseValue = sqPhase < 0.5 ? 1.0 : -1.0;
+        baseValue = cycle < Math.max(0, Math.min(1, dutyCycle)) ? 1.0 : -1.0;
         break;
       case 'Triangle':
-        const triPhase = (phaseAdjustedTime * frequency) % 1.0;
-        baseValue = triPhase < 0.5 ? 4.0 * triPhase - 1.0 : 3.0 - 4.0 * triPhase;
+        baseValue = cycle < 0.5 ? 4.0 * cycle - 1.0 : 3.0 - 4.0 * cycle;
         break;
       case 'Sawtooth':
-        const sawPhase = (phaseAdjustedTime * frequency) % 1.0;
-        baseValue = 2.0 * sawPhase - 1.0;
+        baseValue = 2.0 * cycle - 1.0;
+        break;
+      case 'RampDown':
+        baseValue = 1.0 - 2.0 * cycle;
         break;
       case 'Noise':
         baseValue = Math.random() * 2.0 - 1.0;
         break;
       default:
-        baseValue = Math.sin(phaseAdjustedTime * frequency * 2 * Math.PI);
+        baseValue = Math.sin(cycle * 2 * Math.PI);
     }
 
     return Math.max(-1.0, Math.min(1.0, baseValue * amplitude + offset));
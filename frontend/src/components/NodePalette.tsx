@@ -1,5 +1,5 @@
 import React from 'react';
-import { Monitor, Mic, Camera, FileVideo, TestTube, Tv, Eye, Palette, Sparkles, Move, Layers, Settings, Play, Gamepad2, Wifi, Zap, Radio, Activity, GitBranch, Shuffle, Calculator, Clock, TrendingUp } from 'lucide-react';
+import { Monitor, Mic, Camera, FileVideo, TestTube, Tv, Eye, Palette, Contrast, Sparkles, Move, Layers, Settings, Play, Gamepad2, Wifi, Zap, Radio, Activity, GitBranch, Shuffle, Calculator, Clock, TrendingUp, Circle, Rows, Pipette, Aperture, Globe, BarChart3, Blend, FileAudio, Cast, Send, Droplets } from 'lucide-react';
 import { useTheme, getThemeColors, getThemeStyles } from '../contexts/ThemeContext';
 
 interface NodePaletteItem {
@@ -21,19 +21,30 @@ const nodeItems: NodePaletteItem[] = [
   { type: 'output-VirtualWebcam', label: 'Virtual Webcam', icon: <Tv size={16} />, category: 'Output' },
   { type: 'output-Preview', label: 'Preview', icon: <Eye size={16} />, category: 'Output' },
   { type: 'output-Viewer', label: 'Viewer', icon: <Monitor size={16} />, category: 'Output' },
+  { type: 'output-Recorder', label: 'Recorder', icon: <Circle size={16} />, category: 'Output' },
+  { type: 'output-Scope', label: 'Scope', icon: <BarChart3 size={16} />, category: 'Output' },
+  { type: 'output-Ndi', label: 'NDI Output', icon: <Cast size={16} />, category: 'Output' },
+  { type: 'output-Srt', label: 'SRT Output', icon: <Send size={16} />, category: 'Output' },
   
   // Effect Nodes
   { type: 'effect-ColorCorrection', label: 'Color Correction', icon: <Palette size={16} />, category: 'Effects' },
   { type: 'effect-Blur', label: 'Blur', icon: <Zap size={16} />, category: 'Effects' },
   { type: 'effect-Sharpen', label: 'Sharpen', icon: <Sparkles size={16} />, category: 'Effects' },
+  { type: 'effect-Grayscale', label: 'Grayscale', icon: <Contrast size={16} />, category: 'Effects' },
   { type: 'effect-Transform', label: 'Transform', icon: <Move size={16} />, category: 'Effects' },
   { type: 'effect-Composite', label: 'Composite', icon: <Layers size={16} />, category: 'Effects' },
+  { type: 'effect-Deinterlace', label: 'Deinterlace', icon: <Rows size={16} />, category: 'Effects' },
+  { type: 'effect-ChromaKey', label: 'Chroma Key', icon: <Pipette size={16} />, category: 'Effects' },
+  { type: 'effect-Transition', label: 'Transition', icon: <Blend size={16} />, category: 'Effects' },
+  { type: 'effect-Lut', label: 'LUT', icon: <Droplets size={16} />, category: 'Effects' },
   
   // Audio Nodes
   { type: 'audio-Input', label: 'Audio Input', icon: <Mic size={16} />, category: 'Audio' },
   { type: 'audio-Mixer', label: 'Audio Mixer', icon: <Settings size={16} />, category: 'Audio' },
   { type: 'audio-Effect', label: 'Audio Effect', icon: <Sparkles size={16} />, category: 'Audio' },
   { type: 'audio-Output', label: 'Audio Output', icon: <Mic size={16} />, category: 'Audio' },
+  { type: 'audio-FileInput', label: 'Audio File Input', icon: <FileAudio size={16} />, category: 'Audio' },
+  { type: 'audio-FileOutput', label: 'Audio File Output', icon: <FileAudio size={16} />, category: 'Audio' },
   
   // Control Nodes
   { type: 'control-LFO', label: 'LFO Controller', icon: <TrendingUp size={16} />, category: 'Control' },
@@ -43,6 +54,8 @@ const nodeItems: NodePaletteItem[] = [
   { type: 'control-OscController', label: 'OSC Controller', icon: <Wifi size={16} />, category: 'Control' },
   { type: 'control-ParameterController', label: 'Parameter Controller', icon: <Settings size={16} />, category: 'Control' },
   { type: 'control-AnimationController', label: 'Animation Controller', icon: <Play size={16} />, category: 'Control' },
+  { type: 'control-VideoAnalysis', label: 'Video Analysis', icon: <Aperture size={16} />, category: 'Control' },
+  { type: 'control-APIController', label: 'API Controller', icon: <Globe size={16} />, category: 'Control' },
   
   // Tally Nodes
   { type: 'tally-Generator', label: 'Tally Generator', icon: <Radio size={16} />, category: 'Tally' },
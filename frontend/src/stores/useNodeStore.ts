@@ -344,6 +344,13 @@ export const useNodeStore = create<NodeStoreState>((set, get) => ({
       case 'Error':
         console.error('🚨 Engine error:', event.Error?.message);
         break;
+      case 'NodeError':
+        if (event.NodeError) {
+          const { node_id, category, message } = event.NodeError;
+          console.error(`🚨 Node ${node_id} error (${category}):`, message);
+          get().updateNodeData(node_id, { error: message, errorCategory: category });
+        }
+        break;
       default:
         console.log('🔍 Unknown event type:', event);
     }
@@ -1,9 +1,9 @@
 export type NodeType = 
   | { Input: 'Camera' | 'ScreenCapture' | 'WindowCapture' | 'VideoFile' | 'TestPattern' }
-  | { Output: 'VirtualWebcam' | 'Preview' | 'Viewer' }
-  | { Effect: 'ColorCorrection' | 'Blur' | 'Sharpen' | 'Transform' | 'Composite' }
-  | { Audio: 'Input' | 'Mixer' | 'Effect' | 'Output' }
-  | { Control: 'LFO' | 'Timeline' | 'MathController' | 'MidiController' | 'OscController' | 'ParameterController' | 'AnimationController' }
+  | { Output: 'VirtualWebcam' | 'Preview' | 'Viewer' | 'Recorder' | 'Scope' | 'Ndi' | 'Srt' }
+  | { Effect: 'ColorCorrection' | 'Blur' | 'Sharpen' | 'Grayscale' | 'Transform' | 'Composite' | 'Deinterlace' | 'ChromaKey' | 'Transition' | 'Lut' }
+  | { Audio: 'Input' | 'Mixer' | 'Effect' | 'Output' | 'FileInput' | 'FileOutput' }
+  | { Control: 'LFO' | 'Timeline' | 'MathController' | 'MidiController' | 'OscController' | 'ParameterController' | 'AnimationController' | 'VideoAnalysis' | 'APIController' }
   | { Tally: 'Generator' | 'Monitor' | 'Logic' | 'Router' };
 
 // Updated to match Issue #12 new connection system
@@ -20,6 +20,7 @@ export interface NodeProperties {
   inputTypes: ConnectionType[];
   outputTypes: ConnectionType[];
   parameters: Record<string, ParameterDefinition>;
+  enabled: boolean;
 }
 
 export interface ParameterDefinition {
@@ -63,6 +64,7 @@ export interface EngineEvent {
   ParameterChanged?: { nodeId: string; parameter: string; value: any };
   FrameProcessed?: { timestamp: number };
   Error?: { message: string };
+  NodeError?: { node_id: string; category: string; message: string };
   AudioLevel?: {
     node_id: string;
     peak_left: number;
@@ -76,8 +78,29 @@ export interface EngineEvent {
     is_clipping: boolean;
     timestamp: number;
   };
+  PreviewFrame?: {
+    node_id: string;
+    width: number;
+    height: number;
+    format: 'jpeg' | 'rgba';
+    data_base64: string;
+  };
+  Histogram?: {
+    node_id: string;
+    histogram: {
+      width: number;
+      height: number;
+      red: number[];
+      green: number[];
+      blue: number[];
+      luma: number[];
+      waveform: { min: number; max: number; mean: number }[];
+    };
+  };
 }
 
+export type EngineEventKind = keyof EngineEvent;
+
 export interface EngineStatus {
   running: boolean;
   fps: number;
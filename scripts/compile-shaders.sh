@@ -0,0 +1,20 @@
+#!/bin/bash
+
+# Vulkan コンピュートシェーダーを SPIR-V にコンパイルするスクリプト
+# 必要: glslc (Vulkan SDK / shaderc)
+
+set -e
+
+SHADER_DIR="$(cd "$(dirname "$0")/../crates/constellation-vulkan/shaders" && pwd)"
+
+if ! command -v glslc >/dev/null 2>&1; then
+    echo "❌ glslc not found. Install the Vulkan SDK or shaderc."
+    exit 1
+fi
+
+for shader in "$SHADER_DIR"/*.comp; do
+    echo "🔨 Compiling $(basename "$shader")..."
+    glslc "$shader" -o "$shader.spv"
+done
+
+echo "✅ Shaders compiled to $SHADER_DIR"
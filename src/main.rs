@@ -44,8 +44,11 @@ fn error_handling_demo() -> ConstellationResult<()> {
     );
 
     match compatibility_report.overall_compatibility {
+        CompatibilityLevel::ProfessionalSupported => {
+            println!("  ✅ プロフェッショナル要件まで満たしています");
+        }
         CompatibilityLevel::FullySupported => {
-            println!("  ✅ すべてのフェーズがサポートされています");
+            println!("  ✅ すべてのフェーズの推奨要件を満たしています");
         }
         CompatibilityLevel::Supported => {
             println!("  ⚠️  基本機能はサポートされています");
@@ -63,7 +66,7 @@ fn error_handling_demo() -> ConstellationResult<()> {
 
     // 2. Constellation Engine初期化（ハードウェアチェック統合済み）
     println!("\n2. Constellation Engine初期化:");
-    let engine = match ConstellationEngine::new() {
+    let engine = match ConstellationEngine::new(None) {
         Ok(engine) => {
             println!("  ✅ エンジン初期化成功");
             engine
@@ -124,7 +127,7 @@ fn error_handling_demo() -> ConstellationResult<()> {
 /// エラーハンドリングの動作をデモ
 fn demonstrate_error_handling() -> ConstellationResult<()> {
     // 様々なエラータイプのデモ
-    let demo_errors = vec![
+    let demo_errors = [
         ConstellationError::NodeNotFound {
             node_id: uuid::Uuid::new_v4(),
         },